@@ -5,6 +5,7 @@ use collections::BTreeMap;
 use futures::{FutureExt, StreamExt, channel::mpsc};
 use gpui::{App, AppContext, AsyncApp, Context, Entity, Subscription, Task, WeakEntity};
 use language::{Anchor, Buffer, BufferEvent, DiskState, Point, ToPoint};
+use local_history::SnapshotSource;
 use project::{Project, ProjectItem, lsp_store::OpenLspBufferHandle};
 use std::{cmp, ops::Range, sync::Arc};
 use text::{Edit, Patch, Rope};
@@ -162,6 +163,7 @@ impl ActionLog {
                 TrackedBuffer {
                     buffer: buffer.clone(),
                     diff_base,
+                    recorded_in_history: false,
                     last_seen_base,
                     unreviewed_edits,
                     snapshot: text_snapshot,
@@ -346,6 +348,7 @@ impl ActionLog {
                 .context("buffer not tracked")
                 .unwrap();
             tracked_buffer.may_have_unnotified_user_edits |= has_user_changes;
+            tracked_buffer.recorded_in_history &= !has_user_changes;
         })?;
 
         Self::update_diff(
@@ -537,7 +540,24 @@ impl ActionLog {
 
     /// Mark a buffer as edited by agent, so we can refresh it in the context
     pub fn buffer_edited(&mut self, buffer: Entity<Buffer>, cx: &mut Context<Self>) {
-        let tracked_buffer = self.track_buffer_internal(buffer, false, cx);
+        let tracked_buffer = self.track_buffer_internal(buffer.clone(), false, cx);
+        // Keep the contents the agent started from in the local history, so they can be restored
+        // even if the file isn't under version control. They only change with the diff base, so
+        // they're recorded once rather than for every streamed edit.
+        if !tracked_buffer.recorded_in_history {
+            tracked_buffer.recorded_in_history = true;
+            let content_before_edits = match &tracked_buffer.status {
+                TrackedBufferStatus::Created {
+                    existing_file_content,
+                } => existing_file_content.clone(),
+                TrackedBufferStatus::Modified | TrackedBufferStatus::Deleted => {
+                    Some(tracked_buffer.diff_base.clone())
+                }
+            };
+            if let Some(content) = content_before_edits {
+                local_history::record_snapshot(&buffer, content, SnapshotSource::AgentEdit, cx);
+            }
+        }
         if let TrackedBufferStatus::Deleted = tracked_buffer.status {
             tracked_buffer.status = TrackedBufferStatus::Modified;
         }
@@ -552,6 +572,8 @@ impl ActionLog {
                 cx.notify();
             }
             TrackedBufferStatus::Modified => {
+                let content = buffer.read(cx).as_rope().clone();
+                local_history::record_snapshot(&buffer, content, SnapshotSource::AgentEdit, cx);
                 buffer.update(cx, |buffer, cx| buffer.set_text("", cx));
                 tracked_buffer.status = TrackedBufferStatus::Deleted;
                 tracked_buffer.schedule_diff_update(ChangeAuthor::Agent, cx);
@@ -621,6 +643,7 @@ impl ActionLog {
                 {
                     tracked_buffer.status = TrackedBufferStatus::Modified;
                 }
+                tracked_buffer.recorded_in_history = false;
                 tracked_buffer.schedule_diff_update(ChangeAuthor::User, cx);
             }
         }
@@ -771,6 +794,7 @@ impl ActionLog {
                     }
                     tracked_buffer.unreviewed_edits.clear();
                     tracked_buffer.diff_base = tracked_buffer.snapshot.as_rope().clone();
+                    tracked_buffer.recorded_in_history = false;
                     tracked_buffer.schedule_diff_update(ChangeAuthor::User, cx);
                     true
                 }
@@ -949,6 +973,9 @@ enum TrackedBufferStatus {
 struct TrackedBuffer {
     buffer: Entity<Buffer>,
     diff_base: Rope,
+    /// Whether the content the agent started editing from, as of the current `diff_base`, has
+    /// been recorded in the local history.
+    recorded_in_history: bool,
     last_seen_base: Rope,
     unreviewed_edits: Patch<u32>,
     status: TrackedBufferStatus,
@@ -1,6 +1,8 @@
 mod encrypted_password;
+mod git_editor;
 
 pub use encrypted_password::{EncryptedPassword, ProcessExt};
+pub use git_editor::{GitEditorDelegate, GitEditorRequest, GitEditorSession};
 
 #[cfg(target_os = "windows")]
 use std::sync::OnceLock;
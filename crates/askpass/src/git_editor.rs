@@ -0,0 +1,171 @@
+use std::ffi::OsStr;
+
+use anyhow::{Context as _, Result};
+use futures::channel::{mpsc, oneshot};
+use futures::{AsyncBufReadExt as _, AsyncWriteExt as _, SinkExt, StreamExt, io::BufReader};
+use gpui::{AsyncApp, BackgroundExecutor, Task};
+use smol::fs;
+use util::ResultExt as _;
+
+/// A file that git asked its editor to edit, like the todo list of an interactive rebase or a
+/// commit message.
+#[derive(Debug, Clone)]
+pub struct GitEditorRequest {
+    /// The name of the file, without its directory, such as `git-rebase-todo` or
+    /// `COMMIT_EDITMSG`.
+    pub file_name: String,
+    pub content: String,
+}
+
+/// Answers the requests of a [`GitEditorSession`] with the edited content of the file.
+///
+/// Sending an empty string makes git abort the operation, which is what it does when the user
+/// empties the file in a regular editor.
+pub struct GitEditorDelegate {
+    tx: mpsc::UnboundedSender<(GitEditorRequest, oneshot::Sender<String>)>,
+    _task: Task<()>,
+}
+
+impl GitEditorDelegate {
+    pub fn new(
+        cx: &mut AsyncApp,
+        edit: impl Fn(GitEditorRequest, oneshot::Sender<String>, &mut AsyncApp) + Send + Sync + 'static,
+    ) -> Self {
+        let (tx, mut rx) = mpsc::unbounded::<(GitEditorRequest, oneshot::Sender<_>)>();
+        let task = cx.spawn(async move |cx: &mut AsyncApp| {
+            while let Some((request, channel)) = rx.next().await {
+                edit(request, channel, cx);
+            }
+        });
+        Self { tx, _task: task }
+    }
+
+    pub async fn edit(&mut self, request: GitEditorRequest) -> Result<String> {
+        let (tx, rx) = oneshot::channel();
+        self.tx.send((request, tx)).await?;
+        Ok(rx.await?)
+    }
+}
+
+/// A script that can be used as `GIT_EDITOR` or `GIT_SEQUENCE_EDITOR`, which sends the file git
+/// asks it to edit back to this process and replaces it with the content returned by a
+/// [`GitEditorDelegate`].
+///
+/// Like [`crate::AskPassSession`], this relies on Zed running in netcat mode through the
+/// `--askpass` flag.
+pub struct GitEditorSession {
+    editor_command: String,
+    _editor_task: Task<()>,
+}
+
+#[cfg(not(target_os = "windows"))]
+const GIT_EDITOR_SCRIPT_NAME: &str = "git-editor.sh";
+#[cfg(target_os = "windows")]
+const GIT_EDITOR_SCRIPT_NAME: &str = "git-editor.ps1";
+
+impl GitEditorSession {
+    /// You must retain this session until the git process that uses it exits.
+    #[must_use]
+    pub async fn new(
+        executor: &BackgroundExecutor,
+        mut delegate: GitEditorDelegate,
+    ) -> Result<Self> {
+        use net::async_net::UnixListener;
+        use util::fs::make_file_executable;
+
+        let temp_dir = tempfile::Builder::new()
+            .prefix("zed-git-editor")
+            .tempdir()?;
+        let editor_socket = temp_dir.path().join("git-editor.sock");
+        let editor_script_path = temp_dir.path().join(GIT_EDITOR_SCRIPT_NAME);
+        let listener = UnixListener::bind(&editor_socket).context("creating git editor socket")?;
+        #[cfg(not(target_os = "windows"))]
+        let zed_path = util::get_shell_safe_zed_path()?;
+        #[cfg(target_os = "windows")]
+        let zed_path = std::env::current_exe()
+            .context("finding current executable path for use as git editor")?;
+
+        let editor_task = executor.spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut reader = BufReader::new(&mut stream);
+                let mut file_name = Vec::new();
+                let mut content = Vec::new();
+                if reader.read_until(b'\0', &mut file_name).await.is_err()
+                    || reader.read_until(b'\0', &mut content).await.is_err()
+                {
+                    continue;
+                }
+                file_name.pop_if(|byte| *byte == b'\0');
+                content.pop_if(|byte| *byte == b'\0');
+                let request = GitEditorRequest {
+                    file_name: String::from_utf8_lossy(&file_name).into_owned(),
+                    content: String::from_utf8_lossy(&content).into_owned(),
+                };
+                // When the delegate goes away, the file is emptied so that git aborts.
+                let response = delegate
+                    .edit(request)
+                    .await
+                    .context("getting git editor response")
+                    .log_err()
+                    .unwrap_or_default();
+                stream.write_all(response.as_bytes()).await.log_err();
+            }
+            drop(temp_dir)
+        });
+
+        let editor_script = generate_git_editor_script(&zed_path, &editor_socket);
+        fs::write(&editor_script_path, editor_script)
+            .await
+            .with_context(|| format!("creating git editor script at {editor_script_path:?}"))?;
+        make_file_executable(&editor_script_path).await?;
+        // Git runs its editor through a shell, so the path is quoted in case it contains spaces.
+        #[cfg(not(target_os = "windows"))]
+        let editor_command = format!("'{}'", editor_script_path.display());
+        #[cfg(target_os = "windows")]
+        let editor_command = format!(
+            "powershell.exe -ExecutionPolicy Bypass -File '{}'",
+            editor_script_path.display()
+        );
+
+        Ok(Self {
+            editor_command,
+            _editor_task: editor_task,
+        })
+    }
+
+    /// The command to set `GIT_EDITOR` or `GIT_SEQUENCE_EDITOR` to.
+    pub fn editor_command(&self) -> impl AsRef<OsStr> {
+        &self.editor_command
+    }
+}
+
+#[inline]
+#[cfg(not(target_os = "windows"))]
+fn generate_git_editor_script(zed_path: &str, editor_socket: &std::path::Path) -> String {
+    format!(
+        "{shebang}\n{print_file} | {zed_exe} --askpass={editor_socket} > \"$1.zed\" 2> /dev/null && mv \"$1.zed\" \"$1\"\n",
+        zed_exe = zed_path,
+        editor_socket = editor_socket.display(),
+        print_file = "{ printf '%s\\0' \"${1##*/}\"; cat \"$1\"; printf '\\0'; }",
+        shebang = "#!/bin/sh",
+    )
+}
+
+#[inline]
+#[cfg(target_os = "windows")]
+fn generate_git_editor_script(
+    zed_path: &std::path::Path,
+    editor_socket: &std::path::Path,
+) -> String {
+    format!(
+        r#"
+        $ErrorActionPreference = 'Stop';
+        $file = $args[0];
+        $content = [System.IO.File]::ReadAllText($file);
+        $response = ((Split-Path -Leaf $file) + [char]0 + $content + [char]0) | & "{zed_exe}" --askpass={editor_socket} 2> $null | Out-String;
+        [System.IO.File]::WriteAllText($file, $response);
+        "#,
+        zed_exe = zed_path.display(),
+        editor_socket = editor_socket.display(),
+    )
+}
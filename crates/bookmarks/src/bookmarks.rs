@@ -0,0 +1,383 @@
+mod label_editor;
+
+use std::{path::Path, sync::Arc};
+
+use editor::{Editor, SelectionEffects, scroll::Autoscroll};
+use fuzzy::{StringMatch, StringMatchCandidate};
+use gpui::{App, Context, DismissEvent, Entity, Task, WeakEntity, Window, actions, rems};
+use language::Buffer;
+use picker::{Picker, PickerDelegate};
+use project::Project;
+use text::Point;
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::Workspace;
+
+pub use label_editor::BookmarkLabelEditor;
+
+actions!(
+    bookmarks,
+    [
+        /// Opens a picker with all bookmarks in the project.
+        Toggle,
+        /// Edits the label of the bookmark at the current line.
+        EditLabel
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(
+        |workspace: &mut Workspace, _window, _: &mut Context<Workspace>| {
+            workspace.register_action(|workspace, _: &Toggle, window, cx| {
+                let project = workspace.project().clone();
+                let handle = cx.entity().downgrade();
+                workspace.toggle_modal(window, cx, move |window, cx| {
+                    let delegate = BookmarksDelegate::new(handle, project, cx);
+                    Picker::uniform_list(delegate, window, cx).width(rems(34.))
+                })
+            });
+        },
+    )
+    .detach();
+    cx.observe_new(BookmarkLabelEditor::register).detach();
+}
+
+pub type Bookmarks = Entity<Picker<BookmarksDelegate>>;
+
+struct BookmarkEntry {
+    buffer: Entity<Buffer>,
+    row: u32,
+    /// The bookmark's label, or the trimmed text of its line when it has none.
+    title: SharedString,
+    location: SharedString,
+}
+
+pub struct BookmarksDelegate {
+    workspace: WeakEntity<Workspace>,
+    entries: Vec<BookmarkEntry>,
+    matches: Vec<StringMatch>,
+    selected_match_index: usize,
+}
+
+impl BookmarksDelegate {
+    fn new(workspace: WeakEntity<Workspace>, project: Entity<Project>, cx: &App) -> Self {
+        let project = project.read(cx);
+        let path_style = project.path_style(cx);
+        let entries = project
+            .bookmark_store()
+            .read(cx)
+            .all_bookmarks()
+            .map(|(abs_path, buffer, bookmark)| {
+                let snapshot = buffer.read(cx).snapshot();
+                let row = snapshot.summary_for_anchor::<Point>(&bookmark.position).row;
+                let title = match &bookmark.label {
+                    Some(label) => SharedString::from(label.to_string()),
+                    None => {
+                        let line_end = Point::new(row, snapshot.line_len(row));
+                        let line = snapshot
+                            .text_for_range(Point::new(row, 0)..line_end)
+                            .collect::<String>();
+                        SharedString::from(line.trim().to_string())
+                    }
+                };
+                let path = display_path(project, abs_path, path_style, cx);
+                BookmarkEntry {
+                    buffer: buffer.clone(),
+                    row,
+                    title,
+                    location: format!("{path}:{}", row + 1).into(),
+                }
+            })
+            .collect::<Vec<_>>();
+        let matches = entries
+            .iter()
+            .enumerate()
+            .map(|(candidate_id, entry)| StringMatch {
+                candidate_id,
+                score: 0.,
+                positions: Vec::new(),
+                string: candidate_text(entry),
+            })
+            .collect();
+
+        Self {
+            workspace,
+            entries,
+            matches,
+            selected_match_index: 0,
+        }
+    }
+}
+
+fn display_path(
+    project: &Project,
+    abs_path: &Arc<Path>,
+    path_style: util::paths::PathStyle,
+    cx: &App,
+) -> String {
+    match project.find_project_path(abs_path, cx) {
+        Some(project_path) => project_path.path.display(path_style).into_owned(),
+        None => abs_path.to_string_lossy().into_owned(),
+    }
+}
+
+fn candidate_text(entry: &BookmarkEntry) -> String {
+    format!("{} {}", entry.title, entry.location)
+}
+
+impl PickerDelegate for BookmarksDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Search bookmarks...".into()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some(if self.entries.is_empty() {
+            "No bookmarks in this project".into()
+        } else {
+            "No matching bookmarks".into()
+        })
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_match_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_match_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let candidates = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(id, entry)| StringMatchCandidate::new(id, &candidate_text(entry)))
+            .collect::<Vec<_>>();
+        cx.spawn_in(window, async move |picker, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        score: 0.,
+                        positions: Vec::new(),
+                        string: candidate.string,
+                    })
+                    .collect()
+            } else {
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    100,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+            };
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    delegate.selected_match_index = delegate
+                        .selected_match_index
+                        .min(delegate.matches.len().saturating_sub(1));
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self
+            .matches
+            .get(self.selected_match_index)
+            .and_then(|mat| self.entries.get(mat.candidate_id))
+        else {
+            return;
+        };
+        let buffer = entry.buffer.clone();
+        let position = Point::new(entry.row, 0);
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let pane = if secondary {
+                    workspace.adjacent_pane(window, cx)
+                } else {
+                    workspace.active_pane().clone()
+                };
+                let editor =
+                    workspace.open_project_item::<Editor>(pane, buffer, true, true, window, cx);
+                editor.update(cx, |editor, cx| {
+                    editor.change_selections(
+                        SelectionEffects::scroll(Autoscroll::center()),
+                        window,
+                        cx,
+                        |s| s.select_ranges([position..position]),
+                    );
+                });
+            })
+            .log_err();
+        cx.emit(DismissEvent);
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, _cx: &mut Context<Picker<Self>>) {}
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let string_match = self.matches.get(ix)?;
+        let entry = self.entries.get(string_match.candidate_id)?;
+        // Candidates are the title and the location separated by a space.
+        let location_start = entry.title.len() + 1;
+        let (title_positions, location_positions): (Vec<_>, Vec<_>) = string_match
+            .positions
+            .iter()
+            .partition(|position| **position < location_start);
+        let location_positions = location_positions
+            .into_iter()
+            .map(|position| position - location_start)
+            .collect();
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot(
+                    Icon::new(IconName::Bookmark)
+                        .size(IconSize::Small)
+                        .color(Color::Accent),
+                )
+                .child(
+                    v_flex()
+                        .child(HighlightedLabel::new(entry.title.clone(), title_positions))
+                        .child(
+                            HighlightedLabel::new(entry.location.clone(), location_positions)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use editor::actions::ToggleBookmark;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_bookmarks_picker(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "a.rs": "fn main() {\n    let x = 1;\n}\n",
+                "b.rs": "struct Foo;\n",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+        let editor = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_abs_path(
+                    path!("/dir/a.rs").into(),
+                    workspace::OpenOptions::default(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+        editor.update_in(cx, |editor, window, cx| {
+            editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                s.select_ranges([Point::new(1, 4)..Point::new(1, 4)])
+            });
+            editor.toggle_bookmark(&ToggleBookmark, window, cx);
+            editor.set_bookmark_label(Some("the answer".into()), cx);
+            editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                s.select_ranges([Point::new(0, 0)..Point::new(0, 0)])
+            });
+            editor.toggle_bookmark(&ToggleBookmark, window, cx);
+        });
+
+        let picker = open_bookmarks(&workspace, cx);
+        picker.update(cx, |picker, _| {
+            let titles = picker
+                .delegate
+                .matches
+                .iter()
+                .map(|mat| picker.delegate.entries[mat.candidate_id].title.clone())
+                .collect::<Vec<_>>();
+            assert_eq!(titles, ["fn main() {", "the answer"]);
+            assert_eq!(picker.delegate.entries[1].location, "a.rs:2");
+        });
+
+        cx.simulate_input("answer");
+        picker.update(cx, |picker, _| {
+            assert_eq!(picker.delegate.matches.len(), 1);
+        });
+        cx.dispatch_action(menu::Confirm);
+        editor.update(cx, |editor, cx| {
+            let head = editor.selections.newest::<Point>(cx).head();
+            assert_eq!(head, Point::new(1, 0));
+        });
+    }
+
+    fn open_bookmarks(
+        workspace: &Entity<Workspace>,
+        cx: &mut VisualTestContext,
+    ) -> Entity<Picker<BookmarksDelegate>> {
+        cx.dispatch_action(Toggle);
+        workspace.update(cx, |workspace, cx| {
+            workspace
+                .active_modal::<Picker<BookmarksDelegate>>(cx)
+                .unwrap()
+        })
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            Project::init_settings(cx);
+            workspace::init_settings(cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+    }
+}
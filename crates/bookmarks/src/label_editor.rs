@@ -0,0 +1,118 @@
+use editor::Editor;
+use gpui::{
+    App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Render, Subscription, div,
+    prelude::*,
+};
+use theme::ActiveTheme;
+use ui::prelude::*;
+use workspace::ModalView;
+
+use crate::EditLabel;
+
+/// A modal for editing the label of the bookmark on the active editor's current line.
+pub struct BookmarkLabelEditor {
+    label_editor: Entity<Editor>,
+    active_editor: Entity<Editor>,
+    _subscription: Subscription,
+}
+
+impl ModalView for BookmarkLabelEditor {}
+
+impl Focusable for BookmarkLabelEditor {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.label_editor.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for BookmarkLabelEditor {}
+
+impl BookmarkLabelEditor {
+    pub(crate) fn register(
+        editor: &mut Editor,
+        _window: Option<&mut Window>,
+        cx: &mut Context<Editor>,
+    ) {
+        let handle = cx.entity().downgrade();
+        editor
+            .register_action(move |_: &EditLabel, window, cx| {
+                let Some(editor_handle) = handle.upgrade() else {
+                    return;
+                };
+                let Some(workspace) = editor_handle.read(cx).workspace() else {
+                    return;
+                };
+                workspace.update(cx, |workspace, cx| {
+                    workspace.toggle_modal(window, cx, move |window, cx| {
+                        BookmarkLabelEditor::new(editor_handle, window, cx)
+                    });
+                })
+            })
+            .detach();
+    }
+
+    pub fn new(active_editor: Entity<Editor>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let current_label = active_editor
+            .read(cx)
+            .bookmark_label_at_cursor(cx)
+            .flatten();
+        let label_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Bookmark label", window, cx);
+            if let Some(label) = current_label {
+                editor.set_text(label.as_ref(), window, cx);
+                editor.select_all(&editor::actions::SelectAll, window, cx);
+            }
+            editor
+        });
+        let subscription = cx.subscribe(&label_editor, |_, _, event, cx| {
+            if let editor::EditorEvent::Blurred = event {
+                cx.emit(DismissEvent);
+            }
+        });
+
+        Self {
+            label_editor,
+            active_editor,
+            _subscription: subscription,
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let label = self.label_editor.read(cx).text(cx);
+        self.active_editor.update(cx, |editor, cx| {
+            editor.set_bookmark_label(Some(label.into()), cx);
+            editor.focus_handle(cx).focus(window);
+        });
+        cx.emit(DismissEvent);
+    }
+}
+
+impl Render for BookmarkLabelEditor {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w(rems(24.))
+            .elevation_2(cx)
+            .key_context("BookmarkLabelEditor")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .child(
+                div()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .px_2()
+                    .py_1()
+                    .child(self.label_editor.clone()),
+            )
+            .child(
+                h_flex().px_2().py_1().child(
+                    Label::new("Leave empty to remove the label")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                ),
+            )
+    }
+}
@@ -5,6 +5,7 @@ use language::{Language, LanguageRegistry};
 use rope::Rope;
 use std::{
     cmp::Ordering,
+    collections::BTreeSet,
     future::Future,
     iter,
     ops::Range,
@@ -863,6 +864,140 @@ fn process_patch_hunk(
     }
 }
 
+/// A run of consecutive lines that were removed from an old text and added to a new one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LineHunk {
+    old_rows: Range<u32>,
+    new_rows: Range<u32>,
+}
+
+fn compute_line_hunks(old_text: &str, new_text: &str) -> Vec<LineHunk> {
+    let mut options = GitOptions::default();
+    options.context_lines(0);
+    let Some(patch) = GitPatch::from_buffers(
+        old_text.as_bytes(),
+        None,
+        new_text.as_bytes(),
+        None,
+        Some(&mut options),
+    )
+    .log_err() else {
+        return Vec::new();
+    };
+
+    // Hunk headers give the line before the hunk when it's empty on one side, which is also
+    // the zero-based row the empty side starts at.
+    let rows = |start: u32, len: u32| {
+        let start = if len == 0 { start } else { start - 1 };
+        start..start + len
+    };
+    (0..patch.num_hunks())
+        .filter_map(|hunk_index| {
+            let (hunk, _) = patch.hunk(hunk_index).log_err()?;
+            Some(LineHunk {
+                old_rows: rows(hunk.old_start(), hunk.old_lines()),
+                new_rows: rows(hunk.new_start(), hunk.new_lines()),
+            })
+        })
+        .collect()
+}
+
+/// Mixes the lines of a hunk, taking each added line for which `take_new` returns true and
+/// keeping the others out, and keeping each removed line unless it's replaced.
+///
+/// Removed lines are paired with the added lines at the same position in the hunk, and are
+/// replaced along with them. Removed lines without such a pair go along with the last added
+/// line, or, when the hunk only removes lines, with the row of the new text they were removed
+/// at.
+fn mix_hunk_lines(
+    hunk: &LineHunk,
+    old_lines: &[&str],
+    new_lines: &[&str],
+    take_new: &dyn Fn(u32) -> bool,
+    text: &mut String,
+) {
+    let added = hunk.new_rows.clone().map(take_new).collect::<Vec<_>>();
+    let replace_removed = |ix: usize| match added.get(ix).or(added.last()) {
+        Some(take) => *take,
+        None => take_new(hunk.new_rows.start),
+    };
+    for ix in 0..hunk.old_rows.len().max(added.len()) {
+        if ix < hunk.old_rows.len() && !replace_removed(ix) {
+            push_line(text, old_lines[hunk.old_rows.start as usize + ix]);
+        }
+        if added.get(ix) == Some(&true) {
+            push_line(text, new_lines[hunk.new_rows.start as usize + ix]);
+        }
+    }
+}
+
+/// Appends a line, making sure it doesn't get joined to a previous line that had no newline
+/// because it was the last line of its text.
+fn push_line(text: &mut String, line: &str) {
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text.push_str(line);
+}
+
+/// Returns `old_text` with the changes to the rows of `new_text` for which `take_new` returns
+/// true applied to it.
+fn apply_selected_lines(old_text: &str, new_text: &str, take_new: &dyn Fn(u32) -> bool) -> String {
+    let old_lines = old_text.split_inclusive('\n').collect::<Vec<_>>();
+    let new_lines = new_text.split_inclusive('\n').collect::<Vec<_>>();
+    let mut text = String::with_capacity(old_text.len());
+    let mut old_row = 0;
+    for hunk in compute_line_hunks(old_text, new_text) {
+        for line in &old_lines[old_row..hunk.old_rows.start as usize] {
+            push_line(&mut text, line);
+        }
+        mix_hunk_lines(&hunk, &old_lines, &new_lines, take_new, &mut text);
+        old_row = hunk.old_rows.end as usize;
+    }
+    for line in &old_lines[old_row..] {
+        push_line(&mut text, line);
+    }
+    text
+}
+
+/// Sorts row ranges and merges the ones that overlap or touch.
+fn normalize_rows(rows: &[Range<u32>]) -> Vec<Range<u32>> {
+    let mut rows = rows
+        .iter()
+        .filter(|range| !range.is_empty())
+        .cloned()
+        .collect::<Vec<_>>();
+    rows.sort_unstable_by_key(|range| range.start);
+    let mut normalized: Vec<Range<u32>> = Vec::with_capacity(rows.len());
+    for range in rows {
+        match normalized.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => normalized.push(range),
+        }
+    }
+    normalized
+}
+
+/// Returns whether a row is in one of the given sorted ranges.
+fn rows_contain(rows: &[Range<u32>], row: u32) -> bool {
+    let ix = rows.partition_point(|range| range.end <= row);
+    rows.get(ix).is_some_and(|range| range.start <= row)
+}
+
+/// Converts a row of `new_text` to the row of `old_text` it was left unchanged from, if any.
+fn unchanged_row_in_old_text(hunks: &[LineHunk], new_row: u32) -> Option<u32> {
+    let mut old_row = new_row;
+    for hunk in hunks {
+        if new_row < hunk.new_rows.start {
+            break;
+        } else if new_row < hunk.new_rows.end {
+            return None;
+        }
+        old_row = hunk.old_rows.end + (new_row - hunk.new_rows.end);
+    }
+    Some(old_row)
+}
+
 impl std::fmt::Debug for BufferDiff {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("BufferChangeSet")
@@ -972,6 +1107,96 @@ impl BufferDiff {
         new_index_text
     }
 
+    /// Stages or unstages only the changed lines in the given rows of the buffer, rather than
+    /// whole hunks, returning the new index text, or `None` if it doesn't change.
+    ///
+    /// Lines are staged by applying their changes between the index and the buffer to the index,
+    /// and unstaged by reverting their changes between `HEAD` and the index.
+    pub fn stage_or_unstage_lines(
+        &mut self,
+        stage: bool,
+        rows: &[Range<u32>],
+        buffer: &text::BufferSnapshot,
+        cx: &mut Context<Self>,
+    ) -> Option<Rope> {
+        let unstaged_diff = &self.secondary_diff.as_ref()?.read(cx).inner;
+        let index_text = unstaged_diff
+            .base_text_exists
+            .then(|| unstaged_diff.base_text.text())
+            .unwrap_or_default();
+        let rows = normalize_rows(rows);
+        let buffer_text = buffer.text();
+
+        let new_index_text = if stage {
+            apply_selected_lines(&index_text, &buffer_text, &|row| rows_contain(&rows, row))
+        } else {
+            let head_text = self
+                .inner
+                .base_text_exists
+                .then(|| self.inner.base_text.text())
+                .unwrap_or_default();
+            // Lines with unstaged changes aren't in the index, so there's nothing to unstage.
+            let unstaged_hunks = compute_line_hunks(&index_text, &buffer_text);
+            let index_rows = rows
+                .iter()
+                .flat_map(|range| range.clone())
+                .filter_map(|row| unchanged_row_in_old_text(&unstaged_hunks, row))
+                .collect::<BTreeSet<_>>();
+            apply_selected_lines(&head_text, &index_text, &|row| !index_rows.contains(&row))
+        };
+        if new_index_text == index_text {
+            return None;
+        }
+
+        let new_index_text = Rope::from(new_index_text.as_str());
+        cx.emit(BufferDiffEvent::HunksStagedOrUnstaged(Some(
+            new_index_text.clone(),
+        )));
+        Some(new_index_text)
+    }
+
+    /// Returns the edits that restore only the changed lines in the given rows of the buffer to
+    /// the base text, rather than whole hunks.
+    pub fn edits_to_restore_lines(
+        &self,
+        rows: &[Range<u32>],
+        buffer: &text::BufferSnapshot,
+    ) -> Vec<(Range<usize>, String)> {
+        let base_text = self
+            .inner
+            .base_text_exists
+            .then(|| self.inner.base_text.text())
+            .unwrap_or_default();
+        let buffer_text = buffer.text();
+        let rows = normalize_rows(rows);
+        let base_lines = base_text.split_inclusive('\n').collect::<Vec<_>>();
+        let buffer_lines = buffer_text.split_inclusive('\n').collect::<Vec<_>>();
+        let line_offsets = iter::once(0)
+            .chain(buffer_lines.iter().scan(0, |offset, line| {
+                *offset += line.len();
+                Some(*offset)
+            }))
+            .collect::<Vec<_>>();
+
+        let mut edits = Vec::new();
+        for hunk in compute_line_hunks(&base_text, &buffer_text) {
+            let mut new_text = String::new();
+            mix_hunk_lines(
+                &hunk,
+                &base_lines,
+                &buffer_lines,
+                &|row| !rows_contain(&rows, row),
+                &mut new_text,
+            );
+            let range = line_offsets[hunk.new_rows.start as usize]
+                ..line_offsets[hunk.new_rows.end as usize];
+            if buffer_text[range.clone()] != new_text {
+                edits.push((range, new_text));
+            }
+        }
+        edits
+    }
+
     pub fn range_to_hunk_range(
         &self,
         range: Range<Anchor>,
@@ -1917,6 +2142,160 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_stage_unstage_and_restore_lines(cx: &mut TestAppContext) {
+        let head_text = "
+            one
+            two
+            three
+            four
+        "
+        .unindent();
+        let index_text = "
+            one
+            TWO
+            three
+            four
+        "
+        .unindent();
+        let buffer_text = "
+            one
+            TWO
+            THREE
+            FOUR
+            five
+        "
+        .unindent();
+
+        let buffer = Buffer::new(0, BufferId::new(1).unwrap(), buffer_text.clone());
+        let unstaged = BufferDiffSnapshot::new_sync(buffer.clone(), index_text.clone(), cx);
+        let uncommitted = BufferDiffSnapshot::new_sync(buffer.clone(), head_text.clone(), cx);
+        let unstaged_diff = cx.new(|cx| {
+            let mut diff = BufferDiff::new(&buffer, cx);
+            diff.set_snapshot(unstaged, &buffer, cx);
+            diff
+        });
+        let uncommitted_diff = cx.new(|cx| {
+            let mut diff = BufferDiff::new(&buffer, cx);
+            diff.set_snapshot(uncommitted, &buffer, cx);
+            diff.set_secondary_diff(unstaged_diff.clone());
+            diff
+        });
+
+        uncommitted_diff.update(cx, |diff, cx| {
+            // Staging a modified line only stages the line it replaces.
+            let new_index_text = diff.stage_or_unstage_lines(true, &[2..3], &buffer, cx);
+            assert_eq!(
+                new_index_text.unwrap().to_string(),
+                "
+                one
+                TWO
+                THREE
+                four
+                "
+                .unindent()
+            );
+
+            let new_index_text = diff.stage_or_unstage_lines(true, &[4..5, 3..4], &buffer, cx);
+            assert_eq!(
+                new_index_text.unwrap().to_string(),
+                "
+                one
+                TWO
+                three
+                FOUR
+                five
+                "
+                .unindent()
+            );
+
+            let new_index_text = diff.stage_or_unstage_lines(false, &[1..2], &buffer, cx);
+            assert_eq!(new_index_text.unwrap().to_string(), head_text);
+
+            // Lines that only have unstaged changes can't be unstaged.
+            assert_eq!(
+                diff.stage_or_unstage_lines(false, &[2..5], &buffer, cx),
+                None
+            );
+            // Unchanged lines can't be staged.
+            assert_eq!(
+                diff.stage_or_unstage_lines(true, &[0..2], &buffer, cx),
+                None
+            );
+        });
+
+        let edits =
+            uncommitted_diff.read_with(cx, |diff, _| diff.edits_to_restore_lines(&[3..4], &buffer));
+        let mut restored_text = buffer_text.clone();
+        for (range, new_text) in edits.into_iter().rev() {
+            restored_text.replace_range(range, &new_text);
+        }
+        assert_eq!(
+            restored_text,
+            "
+            one
+            TWO
+            THREE
+            four
+            five
+            "
+            .unindent()
+        );
+    }
+
+    #[test]
+    fn test_apply_selected_lines() {
+        let old_text = "
+            a
+            b
+            c
+        "
+        .unindent();
+        let new_text = "
+            A
+            B
+            c
+            d
+        "
+        .unindent();
+
+        // Removed lines are replaced by the added lines they're paired with.
+        assert_eq!(
+            apply_selected_lines(&old_text, &new_text, &|row| row == 1),
+            "a\nB\nc\n"
+        );
+        assert_eq!(
+            apply_selected_lines(&old_text, &new_text, &|row| row == 3),
+            "a\nb\nc\nd\n"
+        );
+        assert_eq!(
+            apply_selected_lines(&old_text, &new_text, &|_| true),
+            new_text
+        );
+        assert_eq!(
+            apply_selected_lines(&old_text, &new_text, &|_| false),
+            old_text
+        );
+
+        // Removed lines without an added line go along with the last added line of their hunk,
+        // or the row they were removed at.
+        assert_eq!(
+            apply_selected_lines("a\nb\nc\nd\n", "A\nd\n", &|row| row == 0),
+            "A\nd\n"
+        );
+        assert_eq!(
+            apply_selected_lines("a\nb\nc\n", "a\nc\n", &|row| row == 1),
+            "a\nc\n"
+        );
+        assert_eq!(
+            apply_selected_lines("a\nb\nc\n", "a\nc\n", &|row| row == 0),
+            "a\nb\nc\n"
+        );
+
+        // A line without a newline isn't joined to the next one.
+        assert_eq!(apply_selected_lines("a", "b\nc", &|row| row == 1), "a\nc");
+    }
+
     #[gpui::test]
     async fn test_buffer_diff_compare(cx: &mut TestAppContext) {
         let base_text = "
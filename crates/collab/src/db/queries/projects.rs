@@ -998,6 +998,9 @@ impl Database {
                         is_last_update: true,
                         merge_message: db_repository_entry.merge_message,
                         stash_entries: Vec::new(),
+                        rebase_progress: None,
+                        submodules: Vec::new(),
+                        bisect_status: None,
                     });
                 }
             }
@@ -795,6 +795,9 @@ impl Database {
                             is_last_update: true,
                             merge_message: db_repository.merge_message,
                             stash_entries: Vec::new(),
+                            rebase_progress: None,
+                            submodules: Vec::new(),
+                            bisect_status: None,
                         });
                     }
                 }
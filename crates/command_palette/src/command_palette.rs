@@ -105,6 +105,7 @@ impl CommandPalette {
                 Some(Command {
                     name: humanize_action_name(action.name()),
                     action,
+                    intercepted: false,
                 })
             })
             .collect();
@@ -160,6 +161,7 @@ pub struct CommandPaletteDelegate {
 struct Command {
     name: String,
     action: Box<dyn Action>,
+    intercepted: bool,
 }
 
 impl Clone for Command {
@@ -167,6 +169,7 @@ impl Clone for Command {
         Self {
             name: self.name.clone(),
             action: self.action.boxed_clone(),
+            intercepted: self.intercepted,
         }
     }
 }
@@ -203,7 +206,8 @@ impl CommandPaletteDelegate {
             .map(|interceptor| interceptor.intercept(&query, cx))
             .unwrap_or_default();
 
-        if parse_zed_link(&query, cx).is_some() {
+        let is_zed_link = parse_zed_link(&query, cx).is_some();
+        if is_zed_link {
             intercept_results = vec![CommandInterceptResult {
                 action: OpenZedUrl { url: query.clone() }.boxed_clone(),
                 string: query,
@@ -228,6 +232,7 @@ impl CommandPaletteDelegate {
             commands.push(Command {
                 name: string.clone(),
                 action,
+                intercepted: !is_zed_link,
             });
             new_matches.push(StringMatch {
                 candidate_id: commands.len() - 1,
@@ -400,6 +405,9 @@ impl PickerDelegate for CommandPaletteDelegate {
                 .await
         })
         .detach_and_log_err(cx);
+        if command.intercepted {
+            CommandPaletteInterceptor::confirm(&self.latest_query, cx);
+        }
         let action = command.action;
         window.focus(&self.previous_focus_handle);
         self.dismissed(window, cx);
@@ -2,7 +2,7 @@
 
 #![deny(missing_docs)]
 
-use std::any::TypeId;
+use std::{any::TypeId, rc::Rc};
 
 use collections::HashSet;
 use derive_more::{Deref, DerefMut};
@@ -107,9 +107,10 @@ pub struct CommandInterceptResult {
 
 /// An interceptor for the command palette.
 #[derive(Default)]
-pub struct CommandPaletteInterceptor(
-    Option<Box<dyn Fn(&str, &App) -> Vec<CommandInterceptResult>>>,
-);
+pub struct CommandPaletteInterceptor {
+    handler: Option<Box<dyn Fn(&str, &App) -> Vec<CommandInterceptResult>>>,
+    confirm_handler: Option<Rc<dyn Fn(&str, &mut App)>>,
+}
 
 #[derive(Default)]
 struct GlobalCommandPaletteInterceptor(CommandPaletteInterceptor);
@@ -133,22 +134,40 @@ impl CommandPaletteInterceptor {
 
     /// Intercepts the given query from the command palette.
     pub fn intercept(&self, query: &str, cx: &App) -> Vec<CommandInterceptResult> {
-        if let Some(handler) = self.0.as_ref() {
+        if let Some(handler) = self.handler.as_ref() {
             (handler)(query, cx)
         } else {
             Vec::new()
         }
     }
 
+    /// Notifies the global interceptor that one of the results it returned for the given query
+    /// was confirmed.
+    pub fn confirm(query: &str, cx: &mut App) {
+        let confirm_handler =
+            Self::try_global(cx).and_then(|interceptor| interceptor.confirm_handler.clone());
+        if let Some(confirm_handler) = confirm_handler {
+            (confirm_handler)(query, cx);
+        }
+    }
+
     /// Clears the global interceptor.
     pub fn clear(&mut self) {
-        self.0 = None;
+        self.handler = None;
+        self.confirm_handler = None;
     }
 
     /// Sets the global interceptor.
     ///
     /// This will override the previous interceptor, if it exists.
     pub fn set(&mut self, handler: Box<dyn Fn(&str, &App) -> Vec<CommandInterceptResult>>) {
-        self.0 = Some(handler);
+        self.handler = Some(handler);
+    }
+
+    /// Sets the handler that's called when a result returned by the interceptor is confirmed.
+    ///
+    /// This will override the previous handler, if it exists.
+    pub fn set_confirm_handler(&mut self, handler: Rc<dyn Fn(&str, &mut App)>) {
+        self.confirm_handler = Some(handler);
     }
 }
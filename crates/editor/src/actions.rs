@@ -715,6 +715,14 @@ actions!(
         Backtab,
         /// Toggles a breakpoint at the current line.
         ToggleBreakpoint,
+        /// Toggles a bookmark at the current line.
+        ToggleBookmark,
+        /// Moves the cursor to the next bookmark in the editor.
+        GoToNextBookmark,
+        /// Moves the cursor to the previous bookmark in the editor.
+        GoToPreviousBookmark,
+        /// Removes all bookmarks in the project.
+        ClearBookmarks,
         /// Toggles the case of selected text.
         ToggleCase,
         /// Disables the breakpoint at the current line.
@@ -0,0 +1,232 @@
+use std::{ops::Range, sync::Arc};
+
+use collections::{HashMap, HashSet};
+use gpui::{ClickEvent, Context, SharedString, Window};
+use multi_buffer::{Anchor, MultiBufferRow, ToPoint as _};
+use text::{Bias, Point};
+use ui::{ButtonStyle, IconButton, IconName, IconSize, Tooltip, prelude::*};
+
+use crate::{
+    Direction, DisplayPoint, DisplayRow, Editor, HideMouseCursorOrigin, SelectionEffects,
+    actions::{ClearBookmarks, GoToNextBookmark, GoToPreviousBookmark, ToggleBookmark},
+    display_map::ToDisplayPoint as _,
+    scroll::Autoscroll,
+};
+
+impl Editor {
+    pub fn toggle_bookmark(
+        &mut self,
+        _: &ToggleBookmark,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = self.snapshot(window, cx);
+        let buffer_snapshot = &snapshot.buffer_snapshot;
+        // Multiple cursors on the same line toggle its bookmark once.
+        let rows = self
+            .selections
+            .disjoint_anchors_arc()
+            .iter()
+            .map(|selection| MultiBufferRow(selection.head().to_point(buffer_snapshot).row))
+            .collect::<HashSet<_>>();
+        for row in rows {
+            let anchor = buffer_snapshot.anchor_after(Point::new(row.0, 0));
+            self.toggle_bookmark_at_anchor(anchor, cx);
+        }
+    }
+
+    pub fn toggle_bookmark_at_anchor(&mut self, position: Anchor, cx: &mut Context<Self>) {
+        let Some(bookmark_store) = &self.bookmark_store else {
+            return;
+        };
+        let Some(buffer) = self.buffer.read(cx).buffer_for_anchor(position, cx) else {
+            return;
+        };
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.toggle_bookmark(buffer, position.text_anchor, cx);
+        });
+        cx.notify();
+    }
+
+    /// Sets the label of the bookmark on the line of the newest cursor, bookmarking the line if needed.
+    pub fn set_bookmark_label(&mut self, label: Option<Arc<str>>, cx: &mut Context<Self>) {
+        let Some(bookmark_store) = &self.bookmark_store else {
+            return;
+        };
+        let position = self.selections.newest_anchor().head();
+        let Some(buffer) = self.buffer.read(cx).buffer_for_anchor(position, cx) else {
+            return;
+        };
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.set_bookmark_label(buffer, position.text_anchor, label, cx);
+        });
+        cx.notify();
+    }
+
+    /// Returns the label of the bookmark on the line of the newest cursor, if there's a bookmark there.
+    pub fn bookmark_label_at_cursor(&self, cx: &App) -> Option<Option<Arc<str>>> {
+        let bookmark_store = self.bookmark_store.as_ref()?;
+        let multi_buffer = self.buffer.read(cx);
+        let position = self.selections.newest_anchor().head();
+        let buffer = multi_buffer.buffer_for_anchor(position, cx)?;
+        let row = text::ToPoint::to_point(&position.text_anchor, &buffer.read(cx).snapshot()).row;
+        bookmark_store
+            .read(cx)
+            .bookmark_at_row(&buffer, row, cx)
+            .map(|bookmark| bookmark.label.clone())
+    }
+
+    pub fn go_to_next_bookmark(
+        &mut self,
+        _: &GoToNextBookmark,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.go_to_bookmark(Direction::Next, window, cx);
+    }
+
+    pub fn go_to_previous_bookmark(
+        &mut self,
+        _: &GoToPreviousBookmark,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.go_to_bookmark(Direction::Prev, window, cx);
+    }
+
+    pub fn clear_bookmarks(&mut self, _: &ClearBookmarks, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(bookmark_store) = &self.bookmark_store {
+            bookmark_store.update(cx, |bookmark_store, cx| bookmark_store.clear_bookmarks(cx));
+        }
+    }
+
+    /// Moves the cursor to the closest bookmark after (or before) it, wrapping around the editor's contents.
+    fn go_to_bookmark(
+        &mut self,
+        direction: Direction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+        let bookmarks = self.bookmark_anchors(cx);
+        let multi_buffer_snapshot = self.buffer.read(cx).snapshot(cx);
+        let cursor_row = self
+            .selections
+            .newest_anchor()
+            .head()
+            .to_point(&multi_buffer_snapshot)
+            .row;
+        let rows = bookmarks
+            .iter()
+            .map(|(anchor, _)| anchor.to_point(&multi_buffer_snapshot).row)
+            .collect::<Vec<_>>();
+        let target_row = match direction {
+            Direction::Next => rows
+                .iter()
+                .find(|row| **row > cursor_row)
+                .or_else(|| rows.first()),
+            Direction::Prev => rows
+                .iter()
+                .rev()
+                .find(|row| **row < cursor_row)
+                .or_else(|| rows.last()),
+        };
+        let Some(target_row) = target_row else {
+            return;
+        };
+
+        let destination = Point::new(*target_row, 0);
+        self.unfold_ranges(&[destination..destination], false, false, cx);
+        self.change_selections(
+            SelectionEffects::scroll(Autoscroll::center()),
+            window,
+            cx,
+            |selections| selections.select_ranges([destination..destination]),
+        );
+    }
+
+    /// Returns the bookmarks shown in the editor, ordered by their position in the multibuffer.
+    pub(crate) fn bookmark_anchors(&self, cx: &App) -> Vec<(Anchor, Option<Arc<str>>)> {
+        let Some(bookmark_store) = &self.bookmark_store else {
+            return Vec::new();
+        };
+        let multi_buffer = self.buffer.read(cx);
+        let multi_buffer_snapshot = multi_buffer.snapshot(cx);
+        let bookmark_store = bookmark_store.read(cx);
+
+        let mut anchors = Vec::new();
+        for (excerpt_id, buffer_snapshot, excerpt_range) in multi_buffer_snapshot.excerpts() {
+            let Some(buffer) = multi_buffer.buffer(buffer_snapshot.remote_id()) else {
+                continue;
+            };
+            let context = excerpt_range.context.clone();
+            for bookmark in bookmark_store.bookmarks(&buffer, Some(context), buffer_snapshot, cx) {
+                if let Some(anchor) =
+                    multi_buffer_snapshot.anchor_in_excerpt(excerpt_id, bookmark.position)
+                {
+                    anchors.push((anchor, bookmark.label.clone()));
+                }
+            }
+        }
+        anchors
+    }
+
+    /// Returns the bookmarks to render in the gutter for the given display rows.
+    pub(crate) fn active_bookmarks(
+        &self,
+        range: Range<DisplayRow>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> HashMap<DisplayRow, (Anchor, Option<Arc<str>>)> {
+        let mut bookmark_display_points = HashMap::default();
+        if self.bookmark_store.is_none() {
+            return bookmark_display_points;
+        }
+
+        let snapshot = self.snapshot(window, cx);
+        let multi_buffer_snapshot = &snapshot.display_snapshot.buffer_snapshot;
+        let start = snapshot.display_point_to_point(DisplayPoint::new(range.start, 0), Bias::Left);
+        let end = snapshot.display_point_to_point(DisplayPoint::new(range.end, 0), Bias::Right);
+        for (anchor, label) in self.bookmark_anchors(cx) {
+            let point = anchor.to_point(multi_buffer_snapshot);
+            if point < start || point > end {
+                continue;
+            }
+            let display_row = point.to_display_point(&snapshot).row();
+            bookmark_display_points.insert(display_row, (anchor, label));
+        }
+        bookmark_display_points
+    }
+
+    pub(crate) fn render_bookmark(
+        &self,
+        position: Anchor,
+        row: DisplayRow,
+        label: Option<Arc<str>>,
+        cx: &mut Context<Self>,
+    ) -> IconButton {
+        let focus_handle = self.focus_handle.clone();
+        let title = label
+            .map(|label| SharedString::from(label.to_string()))
+            .unwrap_or_else(|| SharedString::from("Bookmark"));
+        IconButton::new(("bookmark_indicator", row.0 as usize), IconName::Bookmark)
+            .icon_size(IconSize::XSmall)
+            .size(ui::ButtonSize::None)
+            .icon_color(Color::Accent)
+            .style(ButtonStyle::Transparent)
+            .on_click(cx.listener(move |editor, _: &ClickEvent, window, cx| {
+                window.focus(&editor.focus_handle(cx));
+                editor.toggle_bookmark_at_anchor(position, cx);
+            }))
+            .tooltip(move |window, cx| {
+                Tooltip::with_meta_in(
+                    title.clone(),
+                    Some(&ToggleBookmark),
+                    "Click to remove the bookmark.",
+                    &focus_handle,
+                    window,
+                    cx,
+                )
+            })
+    }
+}
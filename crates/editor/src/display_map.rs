@@ -91,6 +91,16 @@ pub trait ToDisplayPoint {
 }
 
 type TextHighlights = TreeMap<HighlightKey, Arc<(HighlightStyle, Vec<Range<Anchor>>)>>;
+type FoldingSources = TreeMap<BufferId, FoldingSource>;
+
+/// Where the fold ranges of a buffer come from, when not derived from indentation.
+#[derive(Clone, Debug)]
+pub enum FoldingSource {
+    /// Multi-line bracket pairs of the buffer's syntax tree.
+    TreeSitter,
+    /// Ranges reported by a language server, sorted by their start.
+    Lsp(Arc<[Range<text::Anchor>]>),
+}
 type InlayHighlights = TreeMap<TypeId, TreeMap<InlayId, (HighlightStyle, InlayHighlight)>>;
 
 /// Decides how text in a [`MultiBuffer`] should be displayed in a buffer, handling inlay hints,
@@ -117,6 +127,8 @@ pub struct DisplayMap {
     inlay_highlights: InlayHighlights,
     /// A container for explicitly foldable ranges, which supersede indentation based fold range suggestions.
     crease_map: CreaseMap,
+    /// Fold range sources for buffers that do not use indentation based fold range suggestions.
+    folding_sources: FoldingSources,
     pub(crate) fold_placeholder: FoldPlaceholder,
     pub clip_at_line_ends: bool,
     pub(crate) masked: bool,
@@ -157,6 +169,7 @@ impl DisplayMap {
             wrap_map,
             block_map,
             crease_map,
+            folding_sources: Default::default(),
             fold_placeholder,
             diagnostics_max_severity,
             text_highlights: Default::default(),
@@ -187,6 +200,7 @@ impl DisplayMap {
             block_snapshot,
             diagnostics_max_severity: self.diagnostics_max_severity,
             crease_snapshot: self.crease_map.snapshot(),
+            folding_sources: self.folding_sources.clone(),
             text_highlights: self.text_highlights.clone(),
             inlay_highlights: self.inlay_highlights.clone(),
             clip_at_line_ends: self.clip_at_line_ends,
@@ -411,6 +425,16 @@ impl DisplayMap {
         self.crease_map.remove(crease_ids, &snapshot)
     }
 
+    /// Sets where the fold ranges of the buffer come from, `None` meaning indentation.
+    pub fn set_folding_source(&mut self, buffer_id: BufferId, source: Option<FoldingSource>) {
+        match source {
+            Some(source) => self.folding_sources.insert(buffer_id, source),
+            None => {
+                self.folding_sources.remove(&buffer_id);
+            }
+        }
+    }
+
     pub fn insert_blocks(
         &mut self,
         blocks: impl IntoIterator<Item = BlockProperties<Anchor>>,
@@ -529,6 +553,18 @@ impl DisplayMap {
         cleared
     }
 
+    /// Removes all text highlights stored under [`HighlightKey::TypePlus`] keys of the given type.
+    pub fn clear_keyed_highlights(&mut self, type_id: TypeId) -> bool {
+        let mut cleared = false;
+        self.text_highlights.retain(|key, _| {
+            let retain = *key != HighlightKey::Type(type_id)
+                && !matches!(key, HighlightKey::TypePlus(key_type_id, _) if *key_type_id == type_id);
+            cleared |= !retain;
+            retain
+        });
+        cleared
+    }
+
     pub fn set_font(&self, font: Font, font_size: Pixels, cx: &mut Context<Self>) -> bool {
         self.wrap_map
             .update(cx, |map, cx| map.set_font_with_size(font, font_size, cx))
@@ -765,6 +801,7 @@ pub struct DisplaySnapshot {
     pub buffer_snapshot: MultiBufferSnapshot,
     pub fold_snapshot: FoldSnapshot,
     pub crease_snapshot: CreaseSnapshot,
+    folding_sources: FoldingSources,
     inlay_snapshot: InlaySnapshot,
     tab_snapshot: TabSnapshot,
     wrap_snapshot: WrapSnapshot,
@@ -1270,6 +1307,50 @@ impl DisplaySnapshot {
             .unwrap_or(false)
     }
 
+    /// Whether a fold range starts at the given row, according to the fold range source of its buffer.
+    pub fn starts_fold(&self, buffer_row: MultiBufferRow) -> bool {
+        match self.fold_range_from_source(buffer_row) {
+            Some(range) => range.is_some(),
+            None => self.starts_indent(buffer_row),
+        }
+    }
+
+    /// Returns `None` if the row's buffer derives fold ranges from indentation, otherwise
+    /// the fold range its [`FoldingSource`] provides for the row, if any.
+    fn fold_range_from_source(&self, buffer_row: MultiBufferRow) -> Option<Option<Range<Point>>> {
+        let (buffer, buffer_point, excerpt_id) = self
+            .buffer_snapshot
+            .point_to_buffer_point(Point::new(buffer_row.0, 0))?;
+        let source = self.folding_sources.get(&buffer.remote_id())?;
+        let range = match source {
+            FoldingSource::TreeSitter => buffer.syntax_fold_range_for_row(buffer_point.row),
+            FoldingSource::Lsp(ranges) => {
+                let first_on_row = ranges.partition_point(|range| {
+                    buffer.summary_for_anchor::<Point>(&range.start).row < buffer_point.row
+                });
+                ranges[first_on_row..]
+                    .iter()
+                    .map(|range| {
+                        buffer.summary_for_anchor::<Point>(&range.start)
+                            ..buffer.summary_for_anchor::<Point>(&range.end)
+                    })
+                    .take_while(|range| range.start.row == buffer_point.row)
+                    .max_by_key(|range| range.end)
+            }
+        };
+        Some(range.and_then(|range| {
+            let start = self
+                .buffer_snapshot
+                .anchor_in_excerpt(excerpt_id, buffer.anchor_after(range.start))?
+                .to_point(&self.buffer_snapshot);
+            let end = self
+                .buffer_snapshot
+                .anchor_in_excerpt(excerpt_id, buffer.anchor_before(range.end))?
+                .to_point(&self.buffer_snapshot);
+            (start.row == buffer_row.0 && end.row > start.row).then_some(start..end)
+        }))
+    }
+
     pub fn crease_for_buffer_row(&self, buffer_row: MultiBufferRow) -> Option<Crease<Point>> {
         let start = MultiBufferPoint::new(buffer_row.0, self.buffer_snapshot.line_len(buffer_row));
         if let Some(crease) = self
@@ -1306,6 +1387,11 @@ impl DisplaySnapshot {
                     render_toggle: render_toggle.clone(),
                 }),
             }
+        } else if let Some(range) = self.fold_range_from_source(buffer_row) {
+            if self.is_line_folded(buffer_row) {
+                return None;
+            }
+            Some(Crease::simple(range?, self.fold_placeholder.clone()))
         } else if self.starts_indent(MultiBufferRow(start.row))
             && !self.is_line_folded(MultiBufferRow(start.row))
         {
@@ -14,12 +14,14 @@
 //! If you're looking to improve Vim mode, you should check out Vim crate that wraps Editor and overrides its behavior.
 pub mod actions;
 mod blink_manager;
+mod bookmarks;
 mod clangd_ext;
 pub mod code_context_menus;
 pub mod display_map;
 mod editor_settings;
 mod editor_settings_controls;
 mod element;
+mod folding_ranges;
 mod git;
 mod highlight_matching_bracket;
 mod hover_links;
@@ -38,6 +40,8 @@ mod proposed_changes_editor;
 mod rust_analyzer_ext;
 pub mod scroll;
 mod selections_collection;
+mod semantic_tokens;
+mod sticky_scroll;
 pub mod tasks;
 
 #[cfg(test)]
@@ -76,7 +80,7 @@ pub use proposed_changes_editor::{
 pub use text::Bias;
 
 use ::git::{
-    Restore,
+    Restore, RestoreLines,
     blame::{BlameEntry, ParsedCommitMessage},
 };
 use aho_corasick::AhoCorasick;
@@ -148,9 +152,10 @@ use parking_lot::Mutex;
 use persistence::DB;
 use project::{
     BreakpointWithPosition, CodeAction, Completion, CompletionDisplayOptions, CompletionIntent,
-    CompletionResponse, CompletionSource, DisableAiSettings, DocumentHighlight, InlayHint,
-    Location, LocationLink, PrepareRenameResponse, Project, ProjectItem, ProjectPath,
+    CompletionResponse, CompletionSource, DisableAiSettings, DocumentHighlight, DocumentLink,
+    InlayHint, Location, LocationLink, PrepareRenameResponse, Project, ProjectItem, ProjectPath,
     ProjectTransaction, TaskSourceKind,
+    bookmark_store::BookmarkStore,
     debugger::{
         breakpoint_store::{
             Breakpoint, BreakpointEditAction, BreakpointSessionState, BreakpointState,
@@ -168,6 +173,7 @@ use scroll::{Autoscroll, OngoingScroll, ScrollAnchor, ScrollManager};
 use selections_collection::{
     MutableSelectionsCollection, SelectionsCollection, resolve_selections,
 };
+use semantic_tokens::SemanticTokensState;
 use serde::{Deserialize, Serialize};
 use settings::{GitGutterSetting, Settings, SettingsLocation, SettingsStore, update_settings_file};
 use smallvec::{SmallVec, smallvec};
@@ -1037,6 +1043,7 @@ pub struct Editor {
     autoclose_regions: Vec<AutocloseRegion>,
     snippet_stack: InvalidationStack<SnippetState>,
     select_syntax_node_history: SelectSyntaxNodeHistory,
+    select_syntax_node_task: Task<()>,
     ime_transaction: Option<TransactionId>,
     pub diagnostics_max_severity: DiagnosticSeverity,
     active_diagnostics: ActiveDiagnostic,
@@ -1165,6 +1172,7 @@ pub struct Editor {
     tasks: BTreeMap<(BufferId, BufferRow), RunnableTasks>,
     tasks_update_task: Option<Task<()>>,
     breakpoint_store: Option<Entity<BreakpointStore>>,
+    bookmark_store: Option<Entity<BookmarkStore>>,
     gutter_breakpoint_indicator: (Option<PhantomBreakpointIndicator>, Option<Task<()>>),
     hovered_diff_hunk_row: Option<DisplayRow>,
     pull_diagnostics_task: Task<()>,
@@ -1190,6 +1198,10 @@ pub struct Editor {
     inline_value_cache: InlineValueCache,
     selection_drag_state: SelectionDragState,
     colors: Option<LspColorData>,
+    semantic_tokens: SemanticTokensState,
+    folding_ranges_tasks: HashMap<BufferId, Task<()>>,
+    /// The buffer versions that folding ranges were last requested from language servers for.
+    folding_ranges_versions: HashMap<BufferId, clock::Global>,
     folding_newlines: Task<()>,
     pub lookup_key: Option<Box<dyn Any + Send + Sync>>,
 }
@@ -1881,6 +1893,9 @@ impl Editor {
                     project::Event::RefreshInlayHints => {
                         editor.refresh_inlay_hints(InlayHintRefreshReason::RefreshRequested, cx);
                     }
+                    project::Event::RefreshSemanticTokens => {
+                        editor.refresh_semantic_tokens(true, None, window, cx);
+                    }
                     project::Event::LanguageServerAdded(..)
                     | project::Event::LanguageServerRemoved(..) => {
                         if editor.tasks_update_task.is_none() {
@@ -2057,6 +2072,10 @@ impl Editor {
             (EditorMode::Full { .. }, Some(project)) => Some(project.read(cx).breakpoint_store()),
             _ => None,
         };
+        let bookmark_store = match (&mode, project.as_ref()) {
+            (EditorMode::Full { .. }, Some(project)) => Some(project.read(cx).bookmark_store()),
+            _ => None,
+        };
 
         let mut code_action_providers = Vec::new();
         let mut load_uncommitted_diff = None;
@@ -2093,6 +2112,7 @@ impl Editor {
             autoclose_regions: Vec::new(),
             snippet_stack: InvalidationStack::default(),
             select_syntax_node_history: SelectSyntaxNodeHistory::default(),
+            select_syntax_node_task: Task::ready(()),
             ime_transaction: None,
             active_diagnostics: ActiveDiagnostic::None,
             show_inline_diagnostics: ProjectSettings::get_global(cx).diagnostics.inline.enabled,
@@ -2215,6 +2235,7 @@ impl Editor {
             tasks: BTreeMap::default(),
 
             breakpoint_store,
+            bookmark_store,
             gutter_breakpoint_indicator: (None, None),
             hovered_diff_hunk_row: None,
             _subscriptions: (!is_minimap)
@@ -2245,6 +2266,9 @@ impl Editor {
             tasks_update_task: None,
             pull_diagnostics_task: Task::ready(()),
             colors: None,
+            semantic_tokens: SemanticTokensState::default(),
+            folding_ranges_tasks: HashMap::default(),
+            folding_ranges_versions: HashMap::default(),
             next_color_inlay_id: 0,
             linked_edit_ranges: Default::default(),
             in_project_search: false,
@@ -2285,6 +2309,13 @@ impl Editor {
                     cx.notify();
                 }));
         }
+        if let Some(bookmarks) = editor.bookmark_store.as_ref() {
+            editor
+                ._subscriptions
+                .push(cx.observe(bookmarks, |_, _, cx| {
+                    cx.notify();
+                }));
+        }
         editor.tasks_update_task = Some(editor.refresh_runnables(window, cx));
         editor._subscriptions.extend(project_subscriptions);
 
@@ -10787,6 +10818,75 @@ impl Editor {
         }
     }
 
+    pub fn git_restore_lines(
+        &mut self,
+        _: &RestoreLines,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
+        let ranges = self.selections.disjoint_anchor_ranges().collect::<Vec<_>>();
+        let mut revert_changes = HashMap::default();
+        for (buffer_id, rows) in self.buffer_rows_for_ranges(&ranges, cx) {
+            let multibuffer = self.buffer.read(cx);
+            let Some((buffer, diff)) = multibuffer
+                .buffer(buffer_id)
+                .zip(multibuffer.diff_for(buffer_id))
+            else {
+                continue;
+            };
+            let snapshot = buffer.read(cx).text_snapshot();
+            // Like restored hunks, restored lines are unstaged too.
+            diff.update(cx, |diff, cx| {
+                diff.stage_or_unstage_lines(false, &rows, &snapshot, cx)
+            });
+            let changes = diff
+                .read(cx)
+                .edits_to_restore_lines(&rows, &snapshot)
+                .into_iter()
+                .map(|(range, text)| {
+                    let range =
+                        snapshot.anchor_before(range.start)..snapshot.anchor_after(range.end);
+                    (range, Rope::from(text.as_str()))
+                })
+                .collect::<Vec<_>>();
+            if !changes.is_empty() {
+                revert_changes.insert(buffer_id, changes);
+            }
+        }
+        if !revert_changes.is_empty() {
+            self.transact(window, cx, |editor, window, cx| {
+                editor.restore(revert_changes, window, cx);
+            });
+        }
+    }
+
+    /// Returns the rows of each buffer that the ranges span. A range ending at the start of a line
+    /// doesn't span that line, unless it's empty.
+    fn buffer_rows_for_ranges(
+        &self,
+        ranges: &[Range<Anchor>],
+        cx: &App,
+    ) -> HashMap<BufferId, Vec<Range<u32>>> {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut rows = HashMap::<BufferId, Vec<Range<u32>>>::default();
+        for range in ranges {
+            for (buffer, range, _) in snapshot.range_to_buffer_ranges(range.clone()) {
+                let start = buffer.offset_to_point(range.start);
+                let end = buffer.offset_to_point(range.end);
+                let end_row = if end.column == 0 && end.row > start.row {
+                    end.row
+                } else {
+                    end.row + 1
+                };
+                rows.entry(buffer.remote_id())
+                    .or_default()
+                    .push(start.row..end_row);
+            }
+        }
+        rows
+    }
+
     pub fn open_active_item_in_terminal(
         &mut self,
         _: &OpenInTerminal,
@@ -15133,9 +15233,6 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let Some(visible_row_count) = self.visible_row_count() else {
-            return;
-        };
         let old_selections: Box<[_]> = self.selections.all::<usize>(cx).into();
         if old_selections.is_empty() {
             return;
@@ -15143,6 +15240,43 @@ impl Editor {
 
         self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
 
+        // Any range containing a selection contains its start, so the ranges around the start are enough.
+        if let Some(selection_ranges) =
+            self.lsp_selection_ranges(&old_selections, |selection| selection.start, cx)
+        {
+            self.select_syntax_node_task = cx.spawn_in(window, async move |editor, cx| {
+                let selection_ranges = selection_ranges.await;
+                editor
+                    .update_in(cx, |editor, window, cx| {
+                        if *editor.selections.all::<usize>(cx) == *old_selections {
+                            editor.expand_selections_to_larger_nodes(
+                                old_selections,
+                                selection_ranges,
+                                window,
+                                cx,
+                            );
+                        }
+                    })
+                    .ok();
+            });
+        } else {
+            self.expand_selections_to_larger_nodes(old_selections, HashMap::default(), window, cx);
+        }
+    }
+
+    /// Expands every selection to the innermost range reported for it by a language server
+    /// that contains it, falling back to the enclosing tree-sitter node.
+    fn expand_selections_to_larger_nodes(
+        &mut self,
+        old_selections: Box<[Selection<usize>]>,
+        lsp_selection_ranges: HashMap<usize, Vec<Range<usize>>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(visible_row_count) = self.visible_row_count() else {
+            return;
+        };
+
         let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
         let buffer = self.buffer.read(cx).snapshot(cx);
 
@@ -15152,6 +15286,25 @@ impl Editor {
             .map(|selection| {
                 let old_range = selection.start..selection.end;
 
+                if let Some(lsp_range) =
+                    lsp_selection_ranges.get(&selection.id).and_then(|ranges| {
+                        ranges.iter().find(|range| {
+                            range.start <= old_range.start
+                                && old_range.end <= range.end
+                                && **range != old_range
+                        })
+                    })
+                {
+                    selected_larger_node = true;
+                    return Selection {
+                        id: selection.id,
+                        start: lsp_range.start,
+                        end: lsp_range.end,
+                        goal: SelectionGoal::None,
+                        reversed: selection.reversed,
+                    };
+                }
+
                 if let Some((node, _)) = buffer.syntax_ancestor(old_range.clone()) {
                     // manually select word at selection
                     if ["string_content", "inline"].contains(&node.kind()) {
@@ -15280,9 +15433,148 @@ impl Editor {
                     self.scroll_cursor_bottom(&ScrollCursorBottom, window, cx);
                 }
             }
+        } else {
+            self.shrink_selections_to_lsp_ranges(window, cx);
         }
     }
 
+    /// Shrinks every selection to the outermost range reported by a language server around its head
+    /// that lies within the selection, for selections that were not expanded before.
+    fn shrink_selections_to_lsp_ranges(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let old_selections: Box<[_]> = self.selections.all::<usize>(cx).into();
+        let Some(selection_ranges) =
+            self.lsp_selection_ranges(&old_selections, |selection| selection.head(), cx)
+        else {
+            return;
+        };
+
+        self.select_syntax_node_task = cx.spawn_in(window, async move |editor, cx| {
+            let selection_ranges = selection_ranges.await;
+            editor
+                .update_in(cx, |editor, window, cx| {
+                    if *editor.selections.all::<usize>(cx) != *old_selections {
+                        return;
+                    }
+
+                    let mut selected_smaller_node = false;
+                    let new_selections = old_selections
+                        .iter()
+                        .map(|selection| {
+                            let old_range = selection.range();
+                            let smaller_range =
+                                selection_ranges.get(&selection.id).and_then(|ranges| {
+                                    ranges.iter().rev().find(|range| {
+                                        old_range.start <= range.start
+                                            && range.end <= old_range.end
+                                            && **range != old_range
+                                            && !range.is_empty()
+                                    })
+                                });
+                            let Some(smaller_range) = smaller_range else {
+                                return selection.clone();
+                            };
+                            selected_smaller_node = true;
+                            Selection {
+                                id: selection.id,
+                                start: smaller_range.start,
+                                end: smaller_range.end,
+                                goal: SelectionGoal::None,
+                                reversed: selection.reversed,
+                            }
+                        })
+                        .collect::<Vec<_>>();
+
+                    if selected_smaller_node {
+                        editor.change_selections(Default::default(), window, cx, |s| {
+                            s.select(new_selections);
+                        });
+                    }
+                })
+                .ok();
+        });
+    }
+
+    /// Requests `textDocument/selectionRange` at the position of every selection given by `position`,
+    /// for the selections in buffers with a language server supporting it.
+    /// Resolves to the ranges around each selection, from the innermost to the outermost, keyed by selection id.
+    fn lsp_selection_ranges(
+        &self,
+        selections: &[Selection<usize>],
+        position: impl Fn(&Selection<usize>) -> usize,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<HashMap<usize, Vec<Range<usize>>>>> {
+        let provider = self.semantics_provider.clone()?;
+        let multi_buffer = self.buffer.read(cx);
+        let snapshot = multi_buffer.snapshot(cx);
+        let mut positions_by_buffer =
+            HashMap::<BufferId, (Entity<Buffer>, Vec<(usize, Anchor)>)>::default();
+        for selection in selections {
+            let anchor = snapshot.anchor_before(position(selection));
+            let Some(buffer) = anchor
+                .buffer_id
+                .and_then(|buffer_id| multi_buffer.buffer(buffer_id))
+            else {
+                continue;
+            };
+            positions_by_buffer
+                .entry(buffer.read(cx).remote_id())
+                .or_insert_with(|| (buffer, Vec::new()))
+                .1
+                .push((selection.id, anchor));
+        }
+        positions_by_buffer.retain(|_, (buffer, _)| provider.supports_selection_ranges(buffer, cx));
+        if positions_by_buffer.is_empty() {
+            return None;
+        }
+
+        let requests = positions_by_buffer
+            .into_values()
+            .filter_map(|(buffer, positions)| {
+                let task = provider.selection_ranges(
+                    &buffer,
+                    positions
+                        .iter()
+                        .map(|(_, anchor)| anchor.text_anchor)
+                        .collect(),
+                    cx,
+                )?;
+                Some(async move { (positions, task.await) })
+            })
+            .collect::<Vec<_>>();
+        Some(cx.spawn(async move |editor, cx| {
+            let responses = future::join_all(requests).await;
+            editor
+                .read_with(cx, |editor, cx| {
+                    let snapshot = editor.buffer.read(cx).snapshot(cx);
+                    let mut selection_ranges = HashMap::default();
+                    for (positions, response) in responses {
+                        let ranges = match response {
+                            Ok(ranges) => ranges,
+                            Err(e) => {
+                                log::error!("Failed to fetch selection ranges: {e:#}");
+                                continue;
+                            }
+                        };
+                        for ((selection_id, anchor), ranges) in positions.into_iter().zip(ranges) {
+                            let ranges = ranges
+                                .into_iter()
+                                .filter_map(|range| {
+                                    let start = snapshot
+                                        .anchor_in_excerpt(anchor.excerpt_id, range.start)?;
+                                    let end =
+                                        snapshot.anchor_in_excerpt(anchor.excerpt_id, range.end)?;
+                                    Some(start.to_offset(&snapshot)..end.to_offset(&snapshot))
+                                })
+                                .collect();
+                            selection_ranges.insert(selection_id, ranges);
+                        }
+                    }
+                    selection_ranges
+                })
+                .unwrap_or_default()
+        }))
+    }
+
     pub fn unwrap_syntax_node(
         &mut self,
         _: &UnwrapSyntaxNode,
@@ -16496,8 +16788,9 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Editor>,
     ) -> Task<Result<Navigated>> {
-        // Separate out url and file links, we can only handle one of them at most or an arbitrary number of locations
+        // Separate out url, file and document links, we can only handle one of them at most or an arbitrary number of locations
         let mut first_url_or_file = None;
+        let mut first_document_link = None;
         let definitions: Vec<_> = definitions
             .into_iter()
             .filter_map(|def| match def {
@@ -16515,6 +16808,10 @@ impl Editor {
                     first_url_or_file = Some(Either::Right(path));
                     None
                 }
+                HoverLink::DocumentLink(buffer, link) => {
+                    first_document_link = Some((buffer, link));
+                    None
+                }
             })
             .collect();
 
@@ -16611,7 +16908,27 @@ impl Editor {
                             .await?;
                         Ok(Navigated::Yes)
                     }
-                    None => Ok(Navigated::No),
+                    None => {
+                        let Some((buffer, link)) = first_document_link else {
+                            return Ok(Navigated::No);
+                        };
+                        let Some(resolve_target) = editor.update(cx, |editor, cx| {
+                            editor
+                                .semantics_provider
+                                .as_ref()?
+                                .resolve_document_link(&buffer, &link, cx)
+                        })?
+                        else {
+                            return Ok(Navigated::No);
+                        };
+                        let Some(target) = resolve_target.await? else {
+                            return Ok(Navigated::No);
+                        };
+                        cx.update(|window, cx| {
+                            hover_popover::open_markdown_url(target.to_string().into(), window, cx)
+                        })?;
+                        Ok(Navigated::Yes)
+                    }
                 }
             } else {
                 let Some(workspace) = workspace else {
@@ -18626,6 +18943,44 @@ impl Editor {
         self.do_stage_or_unstage_and_next(false, window, cx);
     }
 
+    pub fn stage_lines(&mut self, _: &::git::StageLines, _: &mut Window, cx: &mut Context<Self>) {
+        self.stage_or_unstage_selected_lines(true, cx);
+    }
+
+    pub fn unstage_lines(
+        &mut self,
+        _: &::git::UnstageLines,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.stage_or_unstage_selected_lines(false, cx);
+    }
+
+    /// Stages or unstages only the selected lines of the hunks in the selections.
+    pub fn stage_or_unstage_selected_lines(&mut self, stage: bool, cx: &mut Context<Self>) {
+        let ranges = self.selections.disjoint_anchor_ranges().collect::<Vec<_>>();
+        let task = self.save_buffers_for_ranges_if_needed(&ranges, cx);
+        cx.spawn(async move |this, cx| {
+            task.await?;
+            this.update(cx, |this, cx| {
+                for (buffer_id, rows) in this.buffer_rows_for_ranges(&ranges, cx) {
+                    let multibuffer = this.buffer.read(cx);
+                    let Some((buffer, diff)) = multibuffer
+                        .buffer(buffer_id)
+                        .zip(multibuffer.diff_for(buffer_id))
+                    else {
+                        continue;
+                    };
+                    let snapshot = buffer.read(cx).text_snapshot();
+                    diff.update(cx, |diff, cx| {
+                        diff.stage_or_unstage_lines(stage, &rows, &snapshot, cx)
+                    });
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub fn stage_or_unstage_diff_hunks(
         &mut self,
         stage: bool,
@@ -20945,6 +21300,9 @@ impl Editor {
             }
             self.refresh_colors(false, None, window, cx);
         }
+        self.update_semantic_token_highlights(cx);
+        self.refresh_semantic_tokens(false, None, window, cx);
+        self.refresh_folding_ranges(None, window, cx);
 
         cx.notify();
     }
@@ -21862,6 +22220,8 @@ impl Editor {
     ) {
         self.pull_diagnostics(for_buffer, window, cx);
         self.refresh_colors(ignore_cache, for_buffer, window, cx);
+        self.refresh_semantic_tokens(ignore_cache, for_buffer, window, cx);
+        self.refresh_folding_ranges(for_buffer, window, cx);
     }
 }
 
@@ -22587,6 +22947,30 @@ pub trait SemanticsProvider {
         new_name: String,
         cx: &mut App,
     ) -> Option<Task<Result<ProjectTransaction>>>;
+
+    fn supports_selection_ranges(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool;
+
+    fn document_links(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Arc<[DocumentLink]>>>>;
+
+    fn resolve_document_link(
+        &self,
+        buffer: &Entity<Buffer>,
+        link: &DocumentLink,
+        cx: &mut App,
+    ) -> Option<Task<Result<Option<lsp::Uri>>>>;
+
+    /// For each position, the ranges to select when expanding the selection from it,
+    /// from the innermost to the outermost.
+    fn selection_ranges(
+        &self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<text::Anchor>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<Vec<Range<text::Anchor>>>>>>;
 }
 
 pub trait CompletionProvider {
@@ -23115,6 +23499,49 @@ impl SemanticsProvider for Entity<Project> {
             project.perform_rename(buffer.clone(), position, new_name, cx)
         }))
     }
+
+    fn document_links(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Arc<[DocumentLink]>>>> {
+        Some(self.update(cx, |project, cx| project.document_links(buffer, cx)))
+    }
+
+    fn resolve_document_link(
+        &self,
+        buffer: &Entity<Buffer>,
+        link: &DocumentLink,
+        cx: &mut App,
+    ) -> Option<Task<Result<Option<lsp::Uri>>>> {
+        Some(self.update(cx, |project, cx| {
+            project.resolve_document_link(buffer, link, cx)
+        }))
+    }
+
+    fn supports_selection_ranges(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool {
+        self.update(cx, |project, cx| {
+            buffer.update(cx, |buffer, cx| {
+                project.any_language_server_supports_selection_ranges(buffer, cx)
+            })
+        })
+    }
+
+    fn selection_ranges(
+        &self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<text::Anchor>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<Vec<Range<text::Anchor>>>>>> {
+        let snapshot = buffer.read(cx).snapshot();
+        let positions = positions
+            .iter()
+            .map(|position| text::ToPointUtf16::to_point_utf16(position, &snapshot))
+            .collect();
+        Some(self.update(cx, |project, cx| {
+            project.selection_ranges(buffer, positions, cx)
+        }))
+    }
 }
 
 fn inlay_hint_settings(
@@ -23447,7 +23874,7 @@ impl EditorSnapshot {
             }
         }
 
-        is_foldable |= self.starts_indent(buffer_row);
+        is_foldable |= self.starts_fold(buffer_row);
 
         if folded || (is_foldable && (row_contains_cursor || self.gutter_hovered)) {
             Some(
@@ -56,6 +56,7 @@ pub struct EditorSettings {
     pub diagnostics_max_severity: Option<DiagnosticSeverity>,
     pub inline_code_actions: bool,
     pub drag_and_drop_selection: DragAndDropSelection,
+    pub sticky_scroll: StickyScroll,
     pub lsp_document_colors: DocumentColorsRenderMode,
     pub minimum_contrast_for_highlights: f32,
 }
@@ -165,6 +166,20 @@ pub struct DragAndDropSelection {
     pub delay: u64,
 }
 
+/// Whether to pin the headers of the scopes enclosing the top of the viewport.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct StickyScroll {
+    /// When true, the lines starting the enclosing outline items are pinned to the top of the editor.
+    ///
+    /// Default: false
+    pub enabled: bool,
+
+    /// The maximum number of nested scopes to pin at the same time.
+    ///
+    /// Default: 5
+    pub max_depth: usize,
+}
+
 /// Default options for buffer and project search items.
 #[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
 pub struct SearchSettings {
@@ -199,6 +214,7 @@ impl Settings for EditorSettings {
         let toolbar = editor.toolbar.unwrap();
         let search = editor.search.unwrap();
         let drag_and_drop_selection = editor.drag_and_drop_selection.unwrap();
+        let sticky_scroll = editor.sticky_scroll.unwrap();
         Self {
             cursor_blink: editor.cursor_blink.unwrap(),
             cursor_shape: editor.cursor_shape.map(Into::into),
@@ -284,6 +300,10 @@ impl Settings for EditorSettings {
                 enabled: drag_and_drop_selection.enabled.unwrap(),
                 delay: drag_and_drop_selection.delay.unwrap(),
             },
+            sticky_scroll: StickyScroll {
+                enabled: sticky_scroll.enabled.unwrap(),
+                max_depth: sticky_scroll.max_depth.unwrap(),
+            },
             lsp_document_colors: editor.lsp_document_colors.unwrap(),
             minimum_contrast_for_highlights: editor.minimum_contrast_for_highlights.unwrap(),
         }
@@ -491,5 +511,15 @@ impl Settings for EditorSettings {
         if minimap != settings::MinimapContent::default() {
             current.editor.minimap = Some(minimap)
         }
+
+        let mut sticky_scroll = settings::StickyScrollContent::default();
+        vscode.bool_setting("editor.stickyScroll.enabled", &mut sticky_scroll.enabled);
+        vscode.usize_setting(
+            "editor.stickyScroll.maxLineCount",
+            &mut sticky_scroll.max_depth,
+        );
+        if sticky_scroll != settings::StickyScrollContent::default() {
+            current.editor.sticky_scroll = Some(sticky_scroll)
+        }
     }
 }
@@ -26,8 +26,8 @@ use language::{
     DiagnosticSourceKind, FakeLspAdapter, IndentGuideSettings, LanguageConfig,
     LanguageConfigOverride, LanguageMatcher, LanguageName, Override, Point,
     language_settings::{
-        CompletionSettingsContent, FormatterList, LanguageSettingsContent, LspInsertMode,
-        SelectedFormatter,
+        CompletionSettingsContent, FoldingProvider, FormatterList, LanguageSettingsContent,
+        LspInsertMode, SelectedFormatter,
     },
     tree_sitter_python,
 };
@@ -8720,6 +8720,190 @@ async fn test_select_larger_smaller_syntax_node(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_select_larger_smaller_syntax_node_with_lsp_selection_ranges(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            selection_range_provider: Some(lsp::SelectionRangeProviderCapability::Simple(true)),
+            ..Default::default()
+        },
+        cx,
+    )
+    .await;
+
+    cx.set_request_handler::<lsp::request::SelectionRangeRequest, _, _>(
+        |_, params, _| async move {
+            let selection_ranges = params
+                .positions
+                .into_iter()
+                .map(|position| {
+                    // Ranges on the position's line, from the outermost to the innermost.
+                    let columns: &[(u32, u32)] = if position.line == 1 {
+                        &[(12, 23), (16, 22), (16, 19)]
+                    } else {
+                        &[(12, 17), (12, 13)]
+                    };
+                    let mut selection_range = None;
+                    for &(start, end) in columns {
+                        selection_range = Some(lsp::SelectionRange {
+                            range: lsp::Range::new(
+                                lsp::Position::new(position.line, start),
+                                lsp::Position::new(position.line, end),
+                            ),
+                            parent: selection_range.map(Box::new),
+                        });
+                    }
+                    selection_range.unwrap()
+                })
+                .collect();
+            Ok(Some(selection_ranges))
+        },
+    );
+
+    cx.set_state(indoc! {"
+        fn main() {
+            let a = foo(bˇar, 1);
+            let c = ˇd + 2;
+        }
+    "});
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+    });
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            let a = foo(«barˇ», 1);
+            let c = «dˇ» + 2;
+        }
+    "});
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+    });
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            let a = foo(«bar, 1ˇ»);
+            let c = «d + 2ˇ»;
+        }
+    "});
+
+    // Selections without a larger server-provided range fall back to tree-sitter independently.
+    cx.update_editor(|editor, window, cx| {
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+    });
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            let a = «foo(bar, 1)ˇ»;
+            «ˇlet c = d + 2;»
+        }
+    "});
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select_smaller_syntax_node(&SelectSmallerSyntaxNode, window, cx);
+    });
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            let a = foo(«bar, 1ˇ»);
+            let c = «d + 2ˇ»;
+        }
+    "});
+
+    // Without history to go back to, selections shrink to the server-provided ranges within them.
+    cx.set_state(indoc! {"
+        fn main() {
+            let a = foo(«bar, 1ˇ»);
+            let c = d + 2;
+        }
+    "});
+    cx.update_editor(|editor, window, cx| {
+        editor.select_smaller_syntax_node(&SelectSmallerSyntaxNode, window, cx);
+    });
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            let a = foo(«barˇ», 1);
+            let c = d + 2;
+        }
+    "});
+}
+
+#[gpui::test]
+async fn test_sticky_scopes(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let language = Arc::new(
+        Language::new(
+            LanguageConfig::default(),
+            Some(tree_sitter_rust::LANGUAGE.into()),
+        )
+        .with_outline_query(
+            r#"
+            (impl_item
+                "impl" @context
+                type: (_) @name) @item
+            (function_item
+                "fn" @context
+                name: (_) @name) @item
+            "#,
+        )
+        .unwrap(),
+    );
+
+    let text = r#"
+        impl Foo {
+            fn a() {
+                let x = 1;
+                let y = 2;
+            }
+
+            fn b() {
+                let z = 3;
+            }
+        }
+    "#
+    .unindent();
+
+    let buffer = cx.new(|cx| Buffer::local(text, cx).with_language(language, cx));
+    let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+    let (editor, cx) = cx.add_window_view(|window, cx| build_editor(buffer, window, cx));
+
+    editor
+        .condition::<crate::EditorEvent>(cx, |editor, cx| !editor.buffer.read(cx).is_parsing(cx))
+        .await;
+
+    editor.update_in(cx, |editor, window, cx| {
+        let snapshot = editor.snapshot(window, cx);
+        let header_rows = |top_row: u32, max_depth: usize| {
+            crate::sticky_scroll::sticky_scopes(
+                DisplayRow(top_row),
+                max_depth,
+                &snapshot.display_snapshot,
+            )
+            .into_iter()
+            .map(|scope| scope.header_row.0)
+            .collect::<Vec<_>>()
+        };
+
+        // Nothing is pinned while the outermost header is visible.
+        assert_eq!(header_rows(0, 5), Vec::<u32>::new());
+        // Each pinned header covers a row, so the innermost scope must enclose the rows below it too.
+        assert_eq!(header_rows(1, 5), vec![0, 1]);
+        assert_eq!(header_rows(1, 1), vec![0]);
+        assert_eq!(header_rows(3, 5), vec![0, 1]);
+        // `fn a` ends above the row its header would cover.
+        assert_eq!(header_rows(4, 5), vec![0]);
+        assert_eq!(header_rows(6, 5), vec![0, 6]);
+        assert_eq!(header_rows(9, 5), vec![0]);
+        assert_eq!(header_rows(10, 5), Vec::<u32>::new());
+    });
+}
+
 #[gpui::test]
 async fn test_select_larger_syntax_node_for_cursor_at_end(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -9324,6 +9508,100 @@ async fn test_fold_function_bodies(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_lsp_folding_ranges(cx: &mut TestAppContext) {
+    init_test(cx, |settings| {
+        settings.defaults.folding_provider = Some(FoldingProvider::Lsp);
+    });
+
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            folding_range_provider: Some(lsp::FoldingRangeProviderCapability::Simple(true)),
+            ..lsp::ServerCapabilities::default()
+        },
+        cx,
+    )
+    .await;
+    let mut folding_range_requests = cx
+        .set_request_handler::<lsp::request::FoldingRangeRequest, _, _>(|_, _, _| async move {
+            Ok(Some(vec![
+                lsp::FoldingRange {
+                    start_line: 0,
+                    end_line: 6,
+                    ..lsp::FoldingRange::default()
+                },
+                lsp::FoldingRange {
+                    start_line: 1,
+                    end_line: 3,
+                    kind: Some(lsp::FoldingRangeKind::Region),
+                    ..lsp::FoldingRange::default()
+                },
+            ]))
+        });
+    cx.set_state(indoc! {"
+        fn main() {
+            // region
+            let a = ˇ1;
+            // endregion
+            let b = [
+                2,
+            ];
+        }
+    "});
+
+    let fold_rows = |cx: &mut EditorLspTestContext| {
+        cx.update_editor(|editor, window, cx| {
+            let snapshot = editor.snapshot(window, cx);
+            (0..8)
+                .filter(|row| snapshot.starts_fold(MultiBufferRow(*row)))
+                .collect::<Vec<_>>()
+        })
+    };
+    let has_fold_indicator = |row: u32, cx: &mut EditorLspTestContext| {
+        cx.update_editor(|editor, window, cx| {
+            editor
+                .snapshot(window, cx)
+                .render_crease_toggle(MultiBufferRow(row), true, cx.entity(), window, cx)
+                .is_some()
+        })
+    };
+
+    // Lines are folded by indentation until the language server reports folding ranges.
+    assert_eq!(fold_rows(&mut cx), [0, 4]);
+
+    cx.executor().advance_clock(Duration::from_millis(100));
+    folding_range_requests.next().await.unwrap();
+    cx.run_until_parked();
+    assert_eq!(fold_rows(&mut cx), [0, 1]);
+    assert!(has_fold_indicator(1, &mut cx));
+    assert!(!has_fold_indicator(4, &mut cx));
+
+    cx.update_editor(|editor, window, cx| {
+        editor.fold(&Fold, window, cx);
+        assert_eq!(
+            editor.display_text(cx),
+            indoc! {"
+                fn main() {
+                    // region⋯
+                    let b = [
+                        2,
+                    ];
+                }
+            "}
+        );
+
+        editor.fold(&Fold, window, cx);
+        assert_eq!(editor.display_text(cx), "fn main() {⋯\n}\n");
+
+        editor.unfold_lines(&UnfoldLines, window, cx);
+        editor.unfold_lines(&UnfoldLines, window, cx);
+        assert_eq!(
+            editor.display_text(cx),
+            editor.buffer.read(cx).read(cx).text()
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_autoindent(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -19965,6 +20243,65 @@ async fn test_stage_and_unstage_added_file_hunk(
     cx.assert_index_text(None);
 }
 
+#[gpui::test]
+async fn test_stage_unstage_and_restore_lines(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    let head_text = indoc! {"
+        one
+        two
+        three
+        four
+    "};
+    cx.set_head_text(head_text);
+    cx.set_index_text(head_text);
+    cx.set_state(indoc! {"
+        one
+        TWO
+        «THREE
+        ˇ»FOUR
+        five
+    "});
+    executor.run_until_parked();
+
+    cx.update_editor(|editor, window, cx| {
+        editor.stage_lines(&Default::default(), window, cx);
+    });
+    executor.run_until_parked();
+    cx.assert_index_text(Some(indoc! {"
+        one
+        two
+        THREE
+        four
+    "}));
+
+    cx.update_editor(|editor, window, cx| {
+        editor.unstage_lines(&Default::default(), window, cx);
+    });
+    executor.run_until_parked();
+    cx.assert_index_text(Some(head_text));
+
+    cx.update_editor(|editor, window, cx| {
+        editor.git_restore_lines(&Default::default(), window, cx);
+    });
+    executor.run_until_parked();
+    assert_eq!(
+        cx.buffer_text(),
+        indoc! {"
+            one
+            TWO
+            three
+            FOUR
+            five
+        "}
+    );
+    cx.assert_index_text(Some(head_text));
+}
+
 async fn setup_indent_guides_editor(
     text: &str,
     cx: &mut TestAppContext,
@@ -22972,6 +23309,110 @@ async fn test_breakpoint_enabling_and_disabling(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_bookmark_toggling_and_navigation(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/a"),
+        json!({
+            "main.rs": "one\ntwo\nthree\nfour\nfive",
+        }),
+    )
+    .await;
+    let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+    let workspace = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*workspace.deref(), cx);
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, rel_path("main.rs")), cx)
+        })
+        .await
+        .unwrap();
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(buffer, cx),
+            Some(project.clone()),
+            window,
+            cx,
+        )
+    });
+
+    let bookmarked_rows = |cx: &mut VisualTestContext| {
+        project.read_with(cx, |project, cx| {
+            project
+                .bookmark_store()
+                .read(cx)
+                .all_serialized_bookmarks(cx)
+                .into_values()
+                .flatten()
+                .map(|bookmark| bookmark.row)
+                .collect::<Vec<_>>()
+        })
+    };
+    let cursor_row = |cx: &mut VisualTestContext| {
+        editor.update(cx, |editor, cx| {
+            editor.selections.newest::<Point>(cx).head().row
+        })
+    };
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([
+                Point::new(1, 2)..Point::new(1, 2),
+                Point::new(3, 0)..Point::new(3, 0),
+            ])
+        });
+        editor.toggle_bookmark(&actions::ToggleBookmark, window, cx);
+        editor.move_to_beginning(&MoveToBeginning, window, cx);
+    });
+    assert_eq!(bookmarked_rows(cx), vec![1, 3]);
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.go_to_next_bookmark(&actions::GoToNextBookmark, window, cx);
+    });
+    assert_eq!(cursor_row(cx), 1);
+    editor.update_in(cx, |editor, window, cx| {
+        editor.go_to_next_bookmark(&actions::GoToNextBookmark, window, cx);
+    });
+    assert_eq!(cursor_row(cx), 3);
+    // Navigation wraps around the editor's contents.
+    editor.update_in(cx, |editor, window, cx| {
+        editor.go_to_next_bookmark(&actions::GoToNextBookmark, window, cx);
+    });
+    assert_eq!(cursor_row(cx), 1);
+    editor.update_in(cx, |editor, window, cx| {
+        editor.go_to_previous_bookmark(&actions::GoToPreviousBookmark, window, cx);
+    });
+    assert_eq!(cursor_row(cx), 3);
+
+    // Bookmarks follow the lines they were set on.
+    editor.update_in(cx, |editor, window, cx| {
+        editor.move_to_beginning(&MoveToBeginning, window, cx);
+        editor.insert("zero\n", window, cx);
+    });
+    assert_eq!(bookmarked_rows(cx), vec![2, 4]);
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.go_to_previous_bookmark(&actions::GoToPreviousBookmark, window, cx);
+        editor.toggle_bookmark(&actions::ToggleBookmark, window, cx);
+    });
+    assert_eq!(cursor_row(cx), 4);
+    assert_eq!(bookmarked_rows(cx), vec![2]);
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.clear_bookmarks(&actions::ClearBookmarks, window, cx);
+        editor.go_to_next_bookmark(&actions::GoToNextBookmark, window, cx);
+    });
+    assert_eq!(bookmarked_rows(cx), Vec::<u32>::new());
+    assert_eq!(cursor_row(cx), 4);
+}
+
 #[gpui::test]
 async fn test_rename_with_duplicate_edits(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
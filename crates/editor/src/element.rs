@@ -29,6 +29,7 @@ use crate::{
     items::BufferSearchHighlights,
     mouse_context_menu::{self, MenuPosition},
     scroll::{ActiveScrollbarState, ScrollbarThumbState, scroll_amount::ScrollAmount},
+    sticky_scroll,
 };
 use buffer_diff::{DiffHunkStatus, DiffHunkStatusKind};
 use collections::{BTreeMap, HashMap};
@@ -46,9 +47,9 @@ use gpui::{
     KeybindingKeystroke, Length, Modifiers, ModifiersChangedEvent, MouseButton, MouseClickEvent,
     MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad, ParentElement, Pixels, ScrollDelta,
     ScrollHandle, ScrollWheelEvent, ShapedLine, SharedString, Size, StatefulInteractiveElement,
-    Style, Styled, TextRun, TextStyleRefinement, WeakEntity, Window, anchored, deferred, div, fill,
-    linear_color_stop, linear_gradient, outline, point, px, quad, relative, size, solid_background,
-    transparent_black,
+    Style, Styled, StyledText, TextRun, TextStyleRefinement, WeakEntity, Window, anchored,
+    deferred, div, fill, linear_color_stop, linear_gradient, outline, point, px, quad, relative,
+    size, solid_background, transparent_black,
 };
 use itertools::Itertools;
 use language::{IndentGuideSettings, language_settings::ShowWhitespaceSetting};
@@ -479,6 +480,8 @@ impl EditorElement {
         register_action(editor, window, Editor::toggle_staged_selected_diff_hunks);
         register_action(editor, window, Editor::stage_and_next);
         register_action(editor, window, Editor::unstage_and_next);
+        register_action(editor, window, Editor::stage_lines);
+        register_action(editor, window, Editor::unstage_lines);
         register_action(editor, window, Editor::expand_all_diff_hunks);
         register_action(editor, window, Editor::go_to_previous_change);
         register_action(editor, window, Editor::go_to_next_change);
@@ -580,6 +583,7 @@ impl EditorElement {
         register_action(editor, window, Editor::accept_edit_prediction);
         register_action(editor, window, Editor::restore_file);
         register_action(editor, window, Editor::git_restore);
+        register_action(editor, window, Editor::git_restore_lines);
         register_action(editor, window, Editor::apply_all_diff_hunks);
         register_action(editor, window, Editor::apply_selected_diff_hunks);
         register_action(editor, window, Editor::open_active_item_in_terminal);
@@ -592,6 +596,10 @@ impl EditorElement {
         register_action(editor, window, Editor::edit_log_breakpoint);
         register_action(editor, window, Editor::enable_breakpoint);
         register_action(editor, window, Editor::disable_breakpoint);
+        register_action(editor, window, Editor::toggle_bookmark);
+        register_action(editor, window, Editor::go_to_next_bookmark);
+        register_action(editor, window, Editor::go_to_previous_bookmark);
+        register_action(editor, window, Editor::clear_bookmarks);
         if editor.read(cx).enable_wrap_selections_in_tag(cx) {
             register_action(editor, window, Editor::wrap_selections_in_tag);
         }
@@ -2871,6 +2879,56 @@ impl EditorElement {
         })
     }
 
+    fn layout_bookmarks(
+        &self,
+        line_height: Pixels,
+        range: Range<DisplayRow>,
+        scroll_pixel_position: gpui::Point<Pixels>,
+        gutter_dimensions: &GutterDimensions,
+        gutter_hitbox: &Hitbox,
+        display_hunks: &[(DisplayDiffHunk, Option<Hitbox>)],
+        snapshot: &EditorSnapshot,
+        bookmarks: HashMap<DisplayRow, (Anchor, Option<Arc<str>>)>,
+        breakpoint_rows: &HashMap<DisplayRow, (Anchor, Breakpoint, Option<BreakpointSessionState>)>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Vec<AnyElement> {
+        self.editor.update(cx, |editor, cx| {
+            bookmarks
+                .into_iter()
+                .filter_map(|(display_row, (anchor, label))| {
+                    // Breakpoints take precedence over bookmarks in the gutter.
+                    if breakpoint_rows.contains_key(&display_row)
+                        || range.start > display_row
+                        || range.end < display_row
+                    {
+                        return None;
+                    }
+
+                    let row =
+                        MultiBufferRow(DisplayPoint::new(display_row, 0).to_point(snapshot).row);
+                    if snapshot.is_line_folded(row) {
+                        return None;
+                    }
+
+                    let button = editor.render_bookmark(anchor, display_row, label, cx);
+                    let button = prepaint_gutter_button(
+                        button,
+                        display_row,
+                        line_height,
+                        gutter_dimensions,
+                        scroll_pixel_position,
+                        gutter_hitbox,
+                        display_hunks,
+                        window,
+                        cx,
+                    );
+                    Some(button)
+                })
+                .collect_vec()
+        })
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn layout_run_indicators(
         &self,
@@ -4402,6 +4460,155 @@ impl EditorElement {
         header
     }
 
+    fn layout_sticky_scopes(
+        &self,
+        snapshot: &EditorSnapshot,
+        scroll_position: gpui::Point<f32>,
+        scroll_pixel_position: gpui::Point<Pixels>,
+        top_offset_rows: u32,
+        line_height: Pixels,
+        right_margin: Pixels,
+        hitbox: &Hitbox,
+        gutter_hitbox: &Hitbox,
+        gutter_dimensions: GutterDimensions,
+        content_origin: gpui::Point<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<AnyElement> {
+        let settings = EditorSettings::get_global(cx);
+        let scroll_top = scroll_position.y + top_offset_rows as f32;
+        let scopes = sticky_scroll::sticky_scopes(
+            DisplayRow(scroll_top as u32),
+            settings.sticky_scroll.max_depth,
+            &snapshot.display_snapshot,
+        );
+        if scopes.is_empty() {
+            return None;
+        }
+
+        let include_line_numbers = snapshot
+            .show_line_numbers
+            .unwrap_or(settings.gutter.line_numbers);
+        let colors = cx.theme().colors();
+        let scope_count = scopes.len();
+        let text_offset = content_origin.x - gutter_hitbox.bounds.right() - scroll_pixel_position.x;
+
+        let mut height = Pixels::ZERO;
+        let mut scope_elements = Vec::with_capacity(scope_count);
+        for (ix, scope) in scopes.into_iter().enumerate() {
+            // Push the header up once the last row of its scope scrolls underneath it,
+            // taking the headers of the scopes it encloses along.
+            let top = (line_height * ix as f32)
+                .min(line_height * (scope.end_row.as_f32() - scroll_top))
+                .min(height);
+            height = top + line_height;
+
+            let mut text = String::new();
+            let mut highlights = Vec::new();
+            for chunk in snapshot.highlighted_chunks(
+                scope.header_row..scope.header_row.next_row(),
+                true,
+                &self.style,
+            ) {
+                let chunk_text = match &chunk.replacement {
+                    Some(ChunkReplacement::Str(replacement)) => replacement.as_str(),
+                    Some(ChunkReplacement::Renderer(_)) => "⋯",
+                    None => chunk.text,
+                };
+                let (chunk_text, line_ended) = match chunk_text.split_once('\n') {
+                    Some((line_text, _)) => (line_text, true),
+                    None => (chunk_text, false),
+                };
+                let range = text.len()..text.len() + chunk_text.len();
+                text.push_str(chunk_text);
+                if let Some(style) = chunk.style {
+                    highlights.push((range, style));
+                }
+                if line_ended {
+                    break;
+                }
+            }
+
+            let editor = self.editor.clone();
+            let start = scope.start;
+            scope_elements.push(
+                div()
+                    .id(("sticky-scope", ix))
+                    .absolute()
+                    .top(top)
+                    .left_0()
+                    .w_full()
+                    .h(line_height)
+                    .bg(colors.editor_background)
+                    .hover(|style| style.bg(colors.editor_active_line_background))
+                    .when(ix + 1 == scope_count, |this| {
+                        this.border_b_1().border_color(colors.border_variant)
+                    })
+                    .cursor_pointer()
+                    .block_mouse_except_scroll()
+                    .on_mouse_down(MouseButton::Left, move |_, window, cx| {
+                        cx.stop_propagation();
+                        editor.update(cx, |editor, cx| {
+                            editor.jump_to_sticky_scope(start, window, cx);
+                        });
+                    })
+                    .when(include_line_numbers, |this| {
+                        this.child(
+                            div()
+                                .absolute()
+                                .left_0()
+                                .w(gutter_dimensions.width)
+                                .h_full()
+                                .flex()
+                                .justify_end()
+                                .pr(gutter_dimensions.right_padding)
+                                .text_color(colors.editor_line_number)
+                                .child((scope.buffer_row + 1).to_string()),
+                        )
+                    })
+                    .child(
+                        div()
+                            .absolute()
+                            .left(gutter_dimensions.width)
+                            .right_0()
+                            .h_full()
+                            .overflow_hidden()
+                            .child(
+                                div().absolute().left(text_offset).child(
+                                    StyledText::new(text)
+                                        .with_default_highlights(&self.style.text, highlights),
+                                ),
+                            ),
+                    )
+                    .into_any_element(),
+            );
+        }
+
+        let mut sticky_scopes = div()
+            .relative()
+            .w(hitbox.bounds.size.width - right_margin)
+            .h(height.max(Pixels::ZERO))
+            .overflow_hidden()
+            .font(self.style.text.font())
+            .text_size(self.style.text.font_size)
+            .line_height(line_height)
+            .children(scope_elements)
+            .into_any_element();
+
+        let origin = hitbox.origin + point(Pixels::ZERO, line_height * top_offset_rows as f32);
+        sticky_scopes.prepaint_as_root(
+            origin,
+            size(
+                AvailableSpace::Definite(hitbox.bounds.size.width - right_margin),
+                AvailableSpace::MinContent,
+            ),
+            window,
+            cx,
+        );
+
+        Some(sticky_scopes)
+    }
+
     fn layout_cursor_popovers(
         &self,
         line_height: Pixels,
@@ -5978,6 +6185,10 @@ impl EditorElement {
                 }
             });
 
+            for bookmark in layout.bookmarks.iter_mut() {
+                bookmark.paint(window, cx);
+            }
+
             for breakpoint in layout.breakpoints.iter_mut() {
                 breakpoint.paint(window, cx);
             }
@@ -8993,6 +9204,34 @@ impl Element for EditorElement {
                         cx,
                     );
 
+                    let sticky_scopes = if !is_minimap
+                        && snapshot.mode.is_full()
+                        && EditorSettings::get_global(cx).sticky_scroll.enabled
+                    {
+                        window.with_element_namespace("sticky_scopes", |window| {
+                            self.layout_sticky_scopes(
+                                &snapshot,
+                                scroll_position,
+                                scroll_pixel_position,
+                                if sticky_buffer_header.is_some() {
+                                    FILE_HEADER_HEIGHT
+                                } else {
+                                    0
+                                },
+                                line_height,
+                                right_margin,
+                                &hitbox,
+                                &gutter_hitbox,
+                                gutter_dimensions,
+                                content_origin,
+                                window,
+                                cx,
+                            )
+                        })
+                    } else {
+                        None
+                    };
+
                     let crease_trailers =
                         window.with_element_namespace("crease_trailers", |window| {
                             self.prepaint_crease_trailers(
@@ -9208,6 +9447,23 @@ impl Element for EditorElement {
                         cx,
                     );
 
+                    let bookmark_rows = self.editor.update(cx, |editor, cx| {
+                        editor.active_bookmarks(start_row..end_row, window, cx)
+                    });
+                    let bookmarks = self.layout_bookmarks(
+                        line_height,
+                        start_row..end_row,
+                        scroll_pixel_position,
+                        &gutter_dimensions,
+                        &gutter_hitbox,
+                        &display_hunks,
+                        &snapshot,
+                        bookmark_rows,
+                        &breakpoint_rows,
+                        window,
+                        cx,
+                    );
+
                     let test_indicators = if gutter_settings.runnables {
                         self.layout_run_indicators(
                             line_height,
@@ -9442,12 +9698,14 @@ impl Element for EditorElement {
                         diff_hunk_controls,
                         mouse_context_menu,
                         test_indicators,
+                        bookmarks,
                         breakpoints,
                         crease_toggles,
                         crease_trailers,
                         tab_invisible,
                         space_invisible,
                         sticky_buffer_header,
+                        sticky_scopes,
                         expand_toggles,
                     }
                 })
@@ -9512,6 +9770,12 @@ impl Element for EditorElement {
                         });
                     }
 
+                    window.with_element_namespace("sticky_scopes", |window| {
+                        if let Some(mut sticky_scopes) = layout.sticky_scopes.take() {
+                            sticky_scopes.paint(window, cx)
+                        }
+                    });
+
                     window.with_element_namespace("blocks", |window| {
                         if let Some(mut sticky_header) = layout.sticky_buffer_header.take() {
                             sticky_header.paint(window, cx)
@@ -9616,6 +9880,7 @@ pub struct EditorLayout {
     visible_cursors: Vec<CursorLayout>,
     selections: Vec<(PlayerColor, Vec<SelectionLayout>)>,
     test_indicators: Vec<AnyElement>,
+    bookmarks: Vec<AnyElement>,
     breakpoints: Vec<AnyElement>,
     crease_toggles: Vec<Option<AnyElement>>,
     expand_toggles: Vec<Option<(AnyElement, gpui::Point<Pixels>)>>,
@@ -9626,6 +9891,7 @@ pub struct EditorLayout {
     tab_invisible: ShapedLine,
     space_invisible: ShapedLine,
     sticky_buffer_header: Option<AnyElement>,
+    sticky_scopes: Option<AnyElement>,
     document_colors: Option<(DocumentColorsRenderMode, Vec<(Range<DisplayPoint>, Hsla)>)>,
 }
 
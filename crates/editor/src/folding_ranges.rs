@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+use itertools::Itertools;
+use language::{
+    Buffer,
+    language_settings::{FoldingProvider, language_settings},
+};
+use text::BufferId;
+use ui::{App, Context, Window};
+
+use crate::{Editor, display_map::FoldingSource};
+
+const FOLDING_RANGES_DEBOUNCE: Duration = Duration::from_millis(100);
+
+impl Editor {
+    /// Updates where the fold ranges of the editor's buffers come from, according to their `folding_provider` setting,
+    /// and requests folding ranges from language servers for the visible buffers that need them. Ranges are requested
+    /// again for a buffer that's given, and otherwise only once per buffer version.
+    pub(super) fn refresh_folding_ranges(
+        &mut self,
+        for_buffer: Option<BufferId>,
+        window: &Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.mode().is_full() {
+            return;
+        }
+
+        let mut buffers = self.buffer.read(cx).all_buffers();
+        if let Some(buffer_id) = for_buffer {
+            buffers.retain(|buffer| buffer.read(cx).remote_id() == buffer_id);
+        }
+
+        let mut lsp_buffers = Vec::new();
+        self.display_map.update(cx, |display_map, cx| {
+            for buffer in buffers {
+                let buffer_id = buffer.read(cx).remote_id();
+                match buffer_folding_provider(buffer.read(cx), cx) {
+                    FoldingProvider::Indentation => {
+                        display_map.set_folding_source(buffer_id, None);
+                        self.folding_ranges_versions.remove(&buffer_id);
+                    }
+                    FoldingProvider::TreeSitter => {
+                        display_map.set_folding_source(buffer_id, Some(FoldingSource::TreeSitter));
+                        self.folding_ranges_versions.remove(&buffer_id);
+                    }
+                    FoldingProvider::Lsp => lsp_buffers.push(buffer_id),
+                }
+            }
+        });
+
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+        let visible_lsp_buffers = self
+            .visible_excerpts(None, cx)
+            .into_values()
+            .map(|(buffer, ..)| buffer)
+            .filter(|buffer| lsp_buffers.contains(&buffer.read(cx).remote_id()))
+            .unique_by(|buffer| buffer.read(cx).remote_id())
+            .collect::<Vec<_>>();
+        for buffer in visible_lsp_buffers {
+            let buffer_id = buffer.read(cx).remote_id();
+            let version = buffer.read(cx).version();
+            if for_buffer.is_none()
+                && self.folding_ranges_versions.get(&buffer_id) == Some(&version)
+            {
+                continue;
+            }
+            self.folding_ranges_versions.insert(buffer_id, version);
+
+            let project = project.downgrade();
+            let task = cx.spawn_in(window, async move |editor, cx| {
+                cx.background_executor()
+                    .timer(FOLDING_RANGES_DEBOUNCE)
+                    .await;
+                let Ok(folding_ranges) =
+                    project.update(cx, |project, cx| project.folding_ranges(&buffer, cx))
+                else {
+                    return;
+                };
+                let ranges = match folding_ranges.await {
+                    Ok(ranges) => ranges,
+                    Err(e) => {
+                        log::debug!("Failed to fetch folding ranges: {e:#}");
+                        Vec::new()
+                    }
+                };
+                editor
+                    .update(cx, |editor, cx| {
+                        editor.folding_ranges_tasks.remove(&buffer_id);
+                        // Fold by indentation until a language server reports folding ranges.
+                        let source =
+                            (!ranges.is_empty()).then(|| FoldingSource::Lsp(ranges.into()));
+                        editor.display_map.update(cx, |display_map, _| {
+                            display_map.set_folding_source(buffer_id, source);
+                        });
+                        cx.notify();
+                    })
+                    .ok();
+            });
+            self.folding_ranges_tasks.insert(buffer_id, task);
+        }
+    }
+}
+
+fn buffer_folding_provider(buffer: &Buffer, cx: &App) -> FoldingProvider {
+    language_settings(
+        buffer.language().map(|language| language.name()),
+        buffer.file(),
+        cx,
+    )
+    .folding_provider
+}
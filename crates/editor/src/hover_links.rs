@@ -1,6 +1,7 @@
 use crate::{
     Anchor, Editor, EditorSettings, EditorSnapshot, FindAllReferences, GoToDefinition,
     GoToTypeDefinition, GotoDefinitionKind, InlayId, Navigated, PointForPosition, SelectPhase,
+    SemanticsProvider,
     editor_settings::GoToDefinitionFallback,
     hover_popover::{self, InlayHover},
     scroll::ScrollAmount,
@@ -10,11 +11,11 @@ use language::{Bias, ToOffset};
 use linkify::{LinkFinder, LinkKind};
 use lsp::LanguageServerId;
 use project::{
-    HoverBlock, HoverBlockKind, InlayHintLabelPartTooltip, InlayHintTooltip, LocationLink, Project,
-    ResolveState, ResolvedPath,
+    DocumentLink, HoverBlock, HoverBlockKind, InlayHintLabelPartTooltip, InlayHintTooltip,
+    LocationLink, Project, ResolveState, ResolvedPath,
 };
 use settings::Settings;
-use std::ops::Range;
+use std::{ops::Range, rc::Rc};
 use theme::ActiveTheme as _;
 use util::{ResultExt, TryFutureExt as _, maybe};
 
@@ -68,6 +69,7 @@ pub enum HoverLink {
     File(ResolvedPath),
     Text(LocationLink),
     InlayHint(lsp::Location, LanguageServerId),
+    DocumentLink(Entity<language::Buffer>, DocumentLink),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -514,7 +516,7 @@ pub fn show_link_definition(
         || hovered_link_state
             .links
             .first()
-            .is_some_and(|d| matches!(d, HoverLink::Url(_)));
+            .is_some_and(|d| matches!(d, HoverLink::Url(_) | HoverLink::DocumentLink(..)));
 
     if same_kind {
         if is_cached && (hovered_link_state.last_trigger_point == trigger_point)
@@ -539,7 +541,18 @@ pub fn show_link_definition(
         async move {
             let result = match &trigger_point {
                 TriggerPoint::Text(_) => {
-                    if let Some((url_range, url)) = find_url(&buffer, buffer_position, cx.clone()) {
+                    if let Some(link) =
+                        find_document_link(&buffer, buffer_position, provider.as_ref(), cx).await
+                    {
+                        let range = maybe!({
+                            let start = snapshot.anchor_in_excerpt(excerpt_id, link.range.start)?;
+                            let end = snapshot.anchor_in_excerpt(excerpt_id, link.range.end)?;
+                            Some(RangeInEditor::Text(start..end))
+                        });
+                        Some((range, vec![HoverLink::DocumentLink(buffer.clone(), link)]))
+                    } else if let Some((url_range, url)) =
+                        find_url(&buffer, buffer_position, cx.clone())
+                    {
                         this.read_with(cx, |_, _| {
                             let range = maybe!({
                                 let start =
@@ -659,6 +672,27 @@ pub fn show_link_definition(
     editor.hovered_link_state = Some(hovered_link_state);
 }
 
+/// Finds the link reported by a language server with `textDocument/documentLink` at the position.
+async fn find_document_link(
+    buffer: &Entity<language::Buffer>,
+    position: text::Anchor,
+    provider: Option<&Rc<dyn SemanticsProvider>>,
+    cx: &mut AsyncWindowContext,
+) -> Option<DocumentLink> {
+    let document_links = cx
+        .update(|_, cx| provider?.document_links(buffer, cx))
+        .ok()??;
+    let document_links = document_links.await.log_err()?;
+    let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot()).ok()?;
+    document_links
+        .iter()
+        .find(|link| {
+            link.range.start.cmp(&position, &snapshot).is_le()
+                && link.range.end.cmp(&position, &snapshot).is_ge()
+        })
+        .cloned()
+}
+
 pub(crate) fn find_url(
     buffer: &Entity<language::Buffer>,
     position: text::Anchor,
@@ -932,6 +966,7 @@ mod tests {
     use indoc::indoc;
     use lsp::request::{GotoDefinition, GotoTypeDefinition};
     use settings::InlayHintSettingsContent;
+    use std::str::FromStr as _;
     use util::{assert_set_eq, path};
     use workspace::item::Item;
 
@@ -1458,6 +1493,61 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_document_links(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorLspTestContext::new_rust(
+            lsp::ServerCapabilities {
+                document_link_provider: Some(lsp::DocumentLinkOptions {
+                    resolve_provider: Some(true),
+                    work_done_progress_options: Default::default(),
+                }),
+                ..Default::default()
+            },
+            cx,
+        )
+        .await;
+
+        cx.set_state(indoc! {"
+            use serde;
+            fn ˇmain() {}
+        "});
+
+        let mut links_requests =
+            cx.set_request_handler::<lsp::request::DocumentLinkRequest, _, _>(|_, _, _| async {
+                Ok(Some(vec![lsp::DocumentLink {
+                    range: lsp::Range::new(lsp::Position::new(0, 4), lsp::Position::new(0, 9)),
+                    target: None,
+                    tooltip: None,
+                    data: None,
+                }]))
+            });
+        let mut resolve_requests = cx
+            .set_request_handler::<lsp::request::DocumentLinkResolve, _, _>(|_, link, _| async {
+                Ok(lsp::DocumentLink {
+                    target: Some(lsp::Uri::from_str("https://docs.rs/serde").unwrap()),
+                    ..link
+                })
+            });
+
+        let screen_coord = cx.pixel_position(indoc! {"
+            use seˇrde;
+            fn main() {}
+        "});
+        cx.simulate_mouse_move(screen_coord, None, Modifiers::secondary_key());
+        links_requests.next().await;
+        cx.background_executor.run_until_parked();
+        cx.assert_editor_text_highlights::<HoveredLinkState>(indoc! {"
+            use «serdeˇ»;
+            fn main() {}
+        "});
+
+        cx.simulate_click(screen_coord, Modifiers::secondary_key());
+        resolve_requests.next().await;
+        cx.background_executor.run_until_parked();
+        assert_eq!(cx.opened_url(), Some("https://docs.rs/serde".into()));
+    }
+
     #[gpui::test]
     async fn test_urls_at_beginning_of_buffer(cx: &mut gpui::TestAppContext) {
         init_test(cx, |_| {});
@@ -197,6 +197,17 @@ pub fn deploy_context_menu(
                     .repository_and_path_for_buffer_id(buffer_id, cx)
                     .is_some()
             });
+        let selection_ranges = editor
+            .selections
+            .all_anchors(cx)
+            .iter()
+            .map(|selection| selection.range())
+            .collect::<Vec<_>>();
+        let has_hunks_in_selections = has_git_repo
+            && editor
+                .diff_hunks_in_ranges(&selection_ranges, buffer)
+                .next()
+                .is_some();
 
         let evaluate_selection = window.is_action_available(&EvaluateSelectedText, cx);
         let run_to_cursor = window.is_action_available(&RunToCursor, cx);
@@ -256,7 +267,24 @@ pub fn deploy_context_menu(
                     !has_git_repo,
                     "Copy Permalink",
                     Box::new(CopyPermalinkToLine),
-                );
+                )
+                .action_disabled_when(
+                    !has_git_repo,
+                    "Show File History",
+                    Box::new(git::FileHistory),
+                )
+                .action_disabled_when(
+                    !has_git_repo,
+                    "Show History of Selected Lines",
+                    Box::new(git::SelectionHistory),
+                )
+                .when(has_hunks_in_selections, |builder| {
+                    builder
+                        .separator()
+                        .action("Stage Selected Lines", Box::new(git::StageLines))
+                        .action("Unstage Selected Lines", Box::new(git::UnstageLines))
+                        .action("Restore Selected Lines", Box::new(git::RestoreLines))
+                });
             match focus {
                 Some(focus) => builder.context(focus),
                 None => builder,
@@ -7,7 +7,7 @@ use language::{Buffer, BufferEvent, Capability};
 use multi_buffer::{ExcerptRange, MultiBuffer};
 use project::Project;
 use smol::stream::StreamExt;
-use std::{any::TypeId, ops::Range, rc::Rc, time::Duration};
+use std::{any::TypeId, ops::Range, rc::Rc, sync::Arc, time::Duration};
 use text::ToOffset;
 use ui::{ButtonLike, KeyBinding, prelude::*};
 use workspace::{
@@ -513,4 +513,39 @@ impl SemanticsProvider for BranchBufferSemanticsProvider {
     ) -> Option<Task<anyhow::Result<project::ProjectTransaction>>> {
         None
     }
+
+    fn document_links(
+        &self,
+        _: &Entity<Buffer>,
+        _: &mut App,
+    ) -> Option<Task<anyhow::Result<Arc<[project::DocumentLink]>>>> {
+        None
+    }
+
+    fn resolve_document_link(
+        &self,
+        _: &Entity<Buffer>,
+        _: &project::DocumentLink,
+        _: &mut App,
+    ) -> Option<Task<anyhow::Result<Option<lsp::Uri>>>> {
+        None
+    }
+
+    fn supports_selection_ranges(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool {
+        if let Some(buffer) = self.to_base(buffer, &[], cx) {
+            self.0.supports_selection_ranges(&buffer, cx)
+        } else {
+            false
+        }
+    }
+
+    fn selection_ranges(
+        &self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<text::Anchor>,
+        cx: &mut App,
+    ) -> Option<Task<anyhow::Result<Vec<Vec<Range<text::Anchor>>>>>> {
+        let buffer = self.to_base(buffer, &positions, cx)?;
+        self.0.selection_ranges(&buffer, positions, cx)
+    }
 }
@@ -494,6 +494,8 @@ impl Editor {
                     .update_in(cx, |editor, window, cx| {
                         editor.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
                         editor.refresh_colors(false, None, window, cx);
+                        editor.refresh_semantic_tokens(false, None, window, cx);
+                        editor.refresh_folding_ranges(None, window, cx);
                     })
                     .ok()
             })
@@ -609,6 +611,8 @@ impl Editor {
 
         self.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
         self.refresh_colors(false, None, window, cx);
+        self.refresh_semantic_tokens(false, None, window, cx);
+        self.refresh_folding_ranges(None, window, cx);
         editor_was_scrolled
     }
 
@@ -0,0 +1,301 @@
+use std::{any::TypeId, ops::Range};
+
+use collections::HashMap;
+use futures::future::join_all;
+use gpui::{FontStyle, FontWeight, HighlightStyle, StrikethroughStyle};
+use itertools::Itertools as _;
+use language::language_settings::{SemanticTokens, language_settings};
+use multi_buffer::Anchor;
+use project::lsp_store::{LspFetchStrategy, ServerSemanticTokens};
+use text::BufferId;
+use theme::{ActiveTheme as _, SyntaxTheme};
+use ui::{App, Context, Window};
+
+use crate::{Editor, display_map::HighlightKey};
+
+/// Marker type for the text highlights produced from semantic tokens.
+///
+/// Highlights are keyed by [`HighlightKey::TypePlus`] with this type: key `0` is the base layer
+/// that hides tree-sitter highlights in [`SemanticTokens::Full`] mode, and every other key holds
+/// all tokens that share a style.
+enum SemanticTokenHighlight {}
+
+#[derive(Debug, Default)]
+pub(super) struct SemanticTokensState {
+    buffer_tokens: HashMap<BufferId, BufferTokens>,
+}
+
+#[derive(Debug)]
+struct BufferTokens {
+    mode: SemanticTokens,
+    cache_version_used: Option<usize>,
+    servers: HashMap<lsp::LanguageServerId, ServerSemanticTokens>,
+}
+
+impl Editor {
+    pub(super) fn refresh_semantic_tokens(
+        &mut self,
+        ignore_cache: bool,
+        buffer_id: Option<BufferId>,
+        _: &Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.mode().is_full() {
+            return;
+        }
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+
+        let mut disabled_buffers = Vec::new();
+        let visible_buffers = self
+            .visible_excerpts(None, cx)
+            .into_values()
+            .map(|(buffer, ..)| buffer)
+            .filter(|editor_buffer| {
+                buffer_id.is_none_or(|buffer_id| buffer_id == editor_buffer.read(cx).remote_id())
+            })
+            .unique_by(|buffer| buffer.read(cx).remote_id())
+            .filter_map(|buffer| {
+                let buffer_id = buffer.read(cx).remote_id();
+                let mode = buffer_semantic_tokens_mode(buffer.read(cx), cx);
+                if mode == SemanticTokens::Off {
+                    disabled_buffers.push(buffer_id);
+                    None
+                } else {
+                    Some((buffer, mode))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut highlights_changed = false;
+        for buffer_id in disabled_buffers {
+            highlights_changed |= self
+                .semantic_tokens
+                .buffer_tokens
+                .remove(&buffer_id)
+                .is_some();
+        }
+        if highlights_changed {
+            self.update_semantic_token_highlights(cx);
+        }
+
+        let all_tokens_task = project.read(cx).lsp_store().update(cx, |lsp_store, cx| {
+            visible_buffers
+                .into_iter()
+                .filter_map(|(buffer, mode)| {
+                    let buffer_id = buffer.read(cx).remote_id();
+                    let fetch_strategy = if ignore_cache {
+                        LspFetchStrategy::IgnoreCache
+                    } else {
+                        LspFetchStrategy::UseCache {
+                            known_cache_version: self
+                                .semantic_tokens
+                                .buffer_tokens
+                                .get(&buffer_id)
+                                .filter(|buffer_tokens| buffer_tokens.mode == mode)
+                                .and_then(|buffer_tokens| buffer_tokens.cache_version_used),
+                        }
+                    };
+                    let tokens_task = lsp_store.semantic_tokens(fetch_strategy, buffer, cx)?;
+                    Some(async move { (buffer_id, mode, tokens_task.await) })
+                })
+                .collect::<Vec<_>>()
+        });
+        if all_tokens_task.is_empty() {
+            return;
+        }
+
+        cx.spawn(async move |editor, cx| {
+            let all_tokens = join_all(all_tokens_task).await;
+            editor
+                .update(cx, |editor, cx| {
+                    let mut updated = false;
+                    for (buffer_id, mode, tokens) in all_tokens {
+                        match tokens {
+                            Ok(tokens) => {
+                                editor.semantic_tokens.buffer_tokens.insert(
+                                    buffer_id,
+                                    BufferTokens {
+                                        mode,
+                                        cache_version_used: tokens.cache_version,
+                                        servers: tokens.servers,
+                                    },
+                                );
+                                updated = true;
+                            }
+                            Err(e) => log::error!("Failed to retrieve semantic tokens: {e}"),
+                        }
+                    }
+                    if updated {
+                        editor.update_semantic_token_highlights(cx);
+                    }
+                })
+                .ok();
+        })
+        .detach();
+    }
+
+    pub(super) fn update_semantic_token_highlights(&mut self, cx: &mut Context<Self>) {
+        let multi_buffer_snapshot = self.buffer().read(cx).snapshot(cx);
+        let syntax_theme = cx.theme().syntax().clone();
+
+        let mut base_layer = Vec::new();
+        let mut styled_ranges = HashMap::<(u32, bool), Vec<Range<Anchor>>>::default();
+        for (excerpt_id, buffer_snapshot, excerpt_range) in multi_buffer_snapshot.excerpts() {
+            let Some(buffer_tokens) = self
+                .semantic_tokens
+                .buffer_tokens
+                .get(&buffer_snapshot.remote_id())
+            else {
+                continue;
+            };
+            let context = &excerpt_range.context;
+            if buffer_tokens.mode == SemanticTokens::Full
+                && buffer_tokens
+                    .servers
+                    .values()
+                    .any(|server_tokens| !server_tokens.tokens.is_empty())
+                && let Some(start) =
+                    multi_buffer_snapshot.anchor_in_excerpt(excerpt_id, context.start)
+                && let Some(end) = multi_buffer_snapshot.anchor_in_excerpt(excerpt_id, context.end)
+            {
+                base_layer.push(start..end);
+            }
+
+            for server_tokens in buffer_tokens.servers.values() {
+                for token in server_tokens.tokens.iter() {
+                    if token.range.end.cmp(&context.start, buffer_snapshot).is_lt()
+                        || token.range.start.cmp(&context.end, buffer_snapshot).is_gt()
+                    {
+                        continue;
+                    }
+                    let Some(highlight_id) =
+                        token_highlight_id(server_tokens, token, &syntax_theme)
+                    else {
+                        continue;
+                    };
+                    let deprecated = server_tokens
+                        .token_modifiers(token)
+                        .any(|modifier| modifier == "deprecated");
+                    let (Some(start), Some(end)) = (
+                        multi_buffer_snapshot.anchor_in_excerpt(excerpt_id, token.range.start),
+                        multi_buffer_snapshot.anchor_in_excerpt(excerpt_id, token.range.end),
+                    ) else {
+                        continue;
+                    };
+                    styled_ranges
+                        .entry((highlight_id, deprecated))
+                        .or_default()
+                        .push(start..end);
+                }
+            }
+        }
+
+        let editor_foreground = cx.theme().colors().editor_foreground;
+        self.display_map.update(cx, |display_map, _| {
+            display_map.clear_keyed_highlights(TypeId::of::<SemanticTokenHighlight>());
+            if !base_layer.is_empty() {
+                display_map.highlight_text(
+                    HighlightKey::TypePlus(TypeId::of::<SemanticTokenHighlight>(), 0),
+                    base_layer,
+                    HighlightStyle {
+                        color: Some(editor_foreground),
+                        font_weight: Some(FontWeight::NORMAL),
+                        font_style: Some(FontStyle::Normal),
+                        ..HighlightStyle::default()
+                    },
+                );
+            }
+            for (ix, ((highlight_id, deprecated), mut ranges)) in styled_ranges
+                .into_iter()
+                .sorted_by_key(|(key, _)| *key)
+                .enumerate()
+            {
+                let Some((_, mut style)) =
+                    syntax_theme.highlights.get(highlight_id as usize).cloned()
+                else {
+                    continue;
+                };
+                if deprecated {
+                    style.strikethrough = Some(StrikethroughStyle {
+                        thickness: 1.0.into(),
+                        ..StrikethroughStyle::default()
+                    });
+                }
+                ranges.sort_by(|a, b| a.start.cmp(&b.start, &multi_buffer_snapshot));
+                display_map.highlight_text(
+                    HighlightKey::TypePlus(TypeId::of::<SemanticTokenHighlight>(), ix + 1),
+                    ranges,
+                    style,
+                );
+            }
+        });
+        cx.notify();
+    }
+}
+
+fn buffer_semantic_tokens_mode(buffer: &language::Buffer, cx: &App) -> SemanticTokens {
+    language_settings(
+        buffer.language().map(|language| language.name()),
+        buffer.file(),
+        cx,
+    )
+    .semantic_tokens
+}
+
+/// Finds the most specific theme key for the token: `<type>.<modifier>` keys take precedence
+/// over the plain `<type>` key, so themes can style e.g. `variable.readonly` separately.
+/// Dotted keys missing from the theme fall back to their parent, e.g. `function.method` to `function`.
+fn token_highlight_id(
+    server_tokens: &ServerSemanticTokens,
+    token: &project::lsp_store::SemanticToken,
+    syntax_theme: &SyntaxTheme,
+) -> Option<u32> {
+    let token_type = server_tokens.token_type(token)?;
+    let theme_key = theme_key_for_token_type(token_type);
+    server_tokens
+        .token_modifiers(token)
+        .filter_map(|modifier| {
+            let modifier_key = match (theme_key, modifier) {
+                ("comment", "documentation") => return syntax_theme.highlight_id("comment.doc"),
+                (_, "defaultLibrary") => "builtin",
+                (_, modifier) => modifier,
+            };
+            syntax_theme.highlight_id(&format!("{theme_key}.{modifier_key}"))
+        })
+        .next()
+        .or_else(|| {
+            let mut key = theme_key;
+            loop {
+                if let Some(highlight_id) = syntax_theme.highlight_id(key) {
+                    return Some(highlight_id);
+                }
+                key = key.rsplit_once('.')?.0;
+            }
+        })
+}
+
+fn theme_key_for_token_type(token_type: &str) -> &str {
+    match token_type {
+        "namespace" => "namespace",
+        "type" | "class" | "struct" | "interface" | "typeParameter" => "type",
+        "enum" => "enum",
+        "enumMember" => "variant",
+        "parameter" => "variable.parameter",
+        "variable" => "variable",
+        "property" | "event" => "property",
+        "function" => "function",
+        "method" => "function.method",
+        "macro" => "function.special",
+        "keyword" | "modifier" => "keyword",
+        "comment" => "comment",
+        "string" => "string",
+        "number" => "number",
+        "regexp" => "string.regex",
+        "operator" => "operator",
+        "decorator" => "attribute",
+        // Non-standard token types (e.g. rust-analyzer's `lifetime`) can be styled by themes directly.
+        other => other,
+    }
+}
@@ -0,0 +1,145 @@
+use std::ops::RangeInclusive;
+
+use gpui::{Context, Window};
+use multi_buffer::Anchor;
+use text::ToPoint as _;
+
+use crate::{
+    DisplayPoint, DisplayRow, Editor, SelectionEffects,
+    display_map::{DisplaySnapshot, ToDisplayPoint as _},
+    scroll::Autoscroll,
+};
+
+/// An outline item enclosing the top of the viewport, whose first line is pinned by sticky scroll.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StickyScope {
+    /// Where the outline item starts.
+    pub start: Anchor,
+    /// The display row of the outline item's first line.
+    pub header_row: DisplayRow,
+    /// The last display row of the outline item.
+    pub end_row: DisplayRow,
+    /// The buffer row of the outline item's first line, used for its line number.
+    pub buffer_row: u32,
+}
+
+/// Returns the scopes to pin when `top_row` is the first row below any other pinned content,
+/// from the outermost to the innermost.
+///
+/// Each pinned line covers a row of the viewport, so the `n`-th scope is only pinned when it
+/// encloses the row `n` rows below `top_row` as well.
+pub(crate) fn sticky_scopes(
+    top_row: DisplayRow,
+    max_depth: usize,
+    snapshot: &DisplaySnapshot,
+) -> Vec<StickyScope> {
+    let last_row = (top_row.0 + max_depth as u32)
+        .saturating_sub(1)
+        .min(snapshot.max_point().row().0);
+    if max_depth == 0 || top_row.0 > last_row {
+        return Vec::new();
+    }
+
+    // Query the outline once for all the rows the scopes could cover.
+    let outline_scopes = outline_scopes(top_row..=DisplayRow(last_row), snapshot);
+    let mut scopes = Vec::new();
+    while scopes.len() < max_depth {
+        let row = DisplayRow(top_row.0 + scopes.len() as u32);
+        if row.0 > last_row {
+            break;
+        }
+        let enclosing_scopes = enclosing_scopes(row, &outline_scopes, snapshot);
+        if enclosing_scopes.len() <= scopes.len() || enclosing_scopes[..scopes.len()] != scopes[..]
+        {
+            break;
+        }
+        scopes.push(enclosing_scopes[scopes.len()].clone());
+    }
+    scopes
+}
+
+/// Returns the outline items of the excerpt at the start of `rows` that overlap them, along with
+/// their depth, in the order of the outline.
+fn outline_scopes(
+    rows: RangeInclusive<DisplayRow>,
+    snapshot: &DisplaySnapshot,
+) -> Vec<(usize, StickyScope)> {
+    let buffer_snapshot = &snapshot.buffer_snapshot;
+    let start =
+        buffer_snapshot.anchor_before(DisplayPoint::new(*rows.start(), 0).to_point(snapshot));
+    let excerpt_id = start.excerpt_id;
+    let (Some(buffer), Some(context)) = (
+        buffer_snapshot.buffer_for_excerpt(excerpt_id),
+        buffer_snapshot.context_range_for_excerpt(excerpt_id),
+    ) else {
+        return Vec::new();
+    };
+    let end = buffer_snapshot.anchor_after(DisplayPoint::new(*rows.end(), 0).to_point(snapshot));
+    let end = if end.excerpt_id == excerpt_id {
+        end.text_anchor
+    } else {
+        context.end
+    };
+
+    buffer
+        .outline_items_containing(start.text_anchor..end, false, None)
+        .into_iter()
+        .filter_map(|item| {
+            // Outline items starting above the excerpt have no header line to show.
+            if item.range.start.cmp(&context.start, buffer).is_lt() {
+                return None;
+            }
+
+            let buffer_row = item.range.start.to_point(buffer).row;
+            let range = Anchor::range_in_buffer(excerpt_id, buffer.remote_id(), item.range);
+            let scope = StickyScope {
+                start: range.start,
+                header_row: range.start.to_display_point(snapshot).row(),
+                end_row: range.end.to_display_point(snapshot).row(),
+                buffer_row,
+            };
+            Some((item.depth, scope))
+        })
+        .collect()
+}
+
+/// Returns the outline scopes that start above `row` and end on or below it,
+/// from the outermost to the innermost.
+fn enclosing_scopes(
+    row: DisplayRow,
+    outline_scopes: &[(usize, StickyScope)],
+    snapshot: &DisplaySnapshot,
+) -> Vec<StickyScope> {
+    if snapshot.is_block_line(row) {
+        return Vec::new();
+    }
+
+    let mut prev_depth = None;
+    outline_scopes
+        .iter()
+        .filter(|(_, scope)| scope.header_row < row && scope.end_row >= row)
+        .filter(|(depth, _)| {
+            let is_nested = prev_depth.is_none_or(|prev_depth| *depth > prev_depth);
+            prev_depth = Some(*depth);
+            is_nested
+        })
+        .map(|(_, scope)| scope.clone())
+        .collect()
+}
+
+impl Editor {
+    /// Moves the cursor to the start of a scope whose header is pinned by sticky scroll.
+    pub(crate) fn jump_to_sticky_scope(
+        &mut self,
+        scope_start: Anchor,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.change_selections(
+            SelectionEffects::scroll(Autoscroll::center()),
+            window,
+            cx,
+            |selections| selections.select_anchor_ranges([scope_start..scope_start]),
+        );
+    }
+}
@@ -4,10 +4,14 @@ use collections::{HashMap, HashSet};
 use futures::future::{self, BoxFuture, join_all};
 use git::{
     Oid,
+    bisect::{BisectOperation, BisectStatus},
     blame::Blame,
+    history::{FileHistoryEntry, LogEntry, LogOptions},
+    rebase::{RebaseOperation, RebaseProgress},
     repository::{
-        AskPassDelegate, Branch, CommitDetails, CommitOptions, FetchOptions, GitRepository,
-        GitRepositoryCheckpoint, PushOptions, Remote, RepoPath, ResetMode,
+        AskPassDelegate, Branch, CommitDetails, CommitOptions, FetchOptions, GitEditorDelegate,
+        GitRepository, GitRepositoryCheckpoint, GitWorktree, PushOptions, Remote, RepoPath,
+        ResetMode, Submodule, SubmoduleOperation, Tag,
     },
     status::{FileStatus, GitStatus, StatusCode, TrackedStatus, UnmergedStatus},
 };
@@ -16,7 +20,7 @@ use ignore::gitignore::GitignoreBuilder;
 use parking_lot::Mutex;
 use rope::Rope;
 use smol::future::FutureExt as _;
-use std::{path::PathBuf, sync::Arc};
+use std::{ops::Range, path::PathBuf, sync::Arc};
 use util::{paths::PathStyle, rel_path::RelPath};
 
 #[derive(Clone)]
@@ -40,6 +44,18 @@ pub struct FakeGitRepositoryState {
     pub branches: HashSet<String>,
     pub simulated_index_write_error_message: Option<String>,
     pub refs: HashMap<String, String>,
+    /// The commits returned by `log`, newest first.
+    pub log: Vec<LogEntry>,
+    pub tags: Vec<Tag>,
+    /// The tags pushed by `push_tag`, as the name of the remote and of the tag.
+    pub pushed_tags: Vec<(String, String)>,
+    /// The linked worktrees of the repository, which `worktrees` lists after the main one.
+    pub worktrees: Vec<GitWorktree>,
+    /// The contents of files at commits other than HEAD, which are compared to `head_contents`
+    /// when listing the changes since one of them.
+    pub commit_contents: HashMap<Oid, HashMap<RepoPath, String>>,
+    pub submodules: Vec<Submodule>,
+    pub bisect: Option<BisectStatus>,
 }
 
 impl FakeGitRepositoryState {
@@ -54,10 +70,57 @@ impl FakeGitRepositoryState {
             branches: Default::default(),
             simulated_index_write_error_message: Default::default(),
             refs: HashMap::from_iter([("HEAD".into(), "abc".into())]),
+            log: Default::default(),
+            tags: Default::default(),
+            pushed_tags: Default::default(),
+            worktrees: Default::default(),
+            commit_contents: Default::default(),
+            submodules: Default::default(),
+            bisect: Default::default(),
         }
     }
 }
 
+impl FakeGitRepositoryState {
+    /// Finds the index in the log of a commit given as `HEAD`, `HEAD^`, `HEAD~<n>` or a prefix of
+    /// its sha.
+    fn resolve_commit(&self, commit: &str) -> Result<usize> {
+        let ix = if commit == "HEAD" {
+            Some(0)
+        } else if commit == "HEAD^" {
+            Some(1)
+        } else if let Some(count) = commit.strip_prefix("HEAD~") {
+            Some(count.parse()?)
+        } else {
+            self.log
+                .iter()
+                .position(|entry| !commit.is_empty() && entry.sha.to_string().starts_with(commit))
+        };
+        ix.filter(|ix| *ix < self.log.len())
+            .with_context(|| format!("unknown revision {commit}"))
+    }
+
+    /// Adds a commit with the given subject on top of `HEAD`, which is the first commit of the
+    /// log. Commits don't record the files they change, so nothing else is updated.
+    fn commit_on_head(&mut self, subject: SharedString, author: &LogEntry) -> Result<()> {
+        let mut sha = [0xff; 20];
+        sha[12..].copy_from_slice(&(self.log.len() as u64).to_be_bytes());
+        let sha = Oid::from_bytes(&sha)?;
+        let entry = LogEntry {
+            sha,
+            parents: self.log.first().map(|head| head.sha).into_iter().collect(),
+            refs: Vec::new(),
+            subject,
+            author_name: author.author_name.clone(),
+            author_email: author.author_email.clone(),
+            commit_timestamp: author.commit_timestamp,
+        };
+        self.log.insert(0, entry);
+        self.refs.insert("HEAD".into(), sha.to_string());
+        Ok(())
+    }
+}
+
 impl FakeGitRepository {
     fn with_state_async<F, T>(&self, write: bool, f: F) -> BoxFuture<'static, Result<T>>
     where
@@ -108,6 +171,22 @@ impl GitRepository for FakeGitRepository {
         .boxed()
     }
 
+    fn load_text_at_commit(&self, commit: Oid, path: RepoPath) -> BoxFuture<'_, Option<String>> {
+        async move {
+            self.with_state_async(false, move |state| {
+                state
+                    .commit_contents
+                    .get(&commit)
+                    .and_then(|contents| contents.get(&path))
+                    .context("not present in commit")
+                    .cloned()
+            })
+            .await
+            .ok()
+        }
+        .boxed()
+    }
+
     fn load_commit(
         &self,
         _commit: String,
@@ -157,13 +236,198 @@ impl GitRepository for FakeGitRepository {
         .boxed()
     }
 
+    fn log(&self, options: LogOptions) -> BoxFuture<'_, Result<Vec<LogEntry>>> {
+        self.with_state_async(false, move |state| {
+            // Commits don't record which paths they touch, so the path filter is ignored.
+            let matches = |pattern: &Option<String>, text: &str| {
+                pattern
+                    .as_ref()
+                    .is_none_or(|pattern| text.to_lowercase().contains(&pattern.to_lowercase()))
+            };
+            Ok(state
+                .log
+                .iter()
+                .filter(|entry| {
+                    matches(&options.author, &entry.author_name)
+                        && matches(&options.message, &entry.subject)
+                })
+                .skip(options.skip)
+                .take(options.limit)
+                .cloned()
+                .collect())
+        })
+    }
+
     fn reset(
         &self,
-        _commit: String,
+        commit: String,
         _mode: ResetMode,
         _env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>> {
-        unimplemented!()
+        self.with_state_async(true, move |state| {
+            let ix = state.resolve_commit(&commit)?;
+            state.log.drain(..ix);
+            let head = state.log[0].sha.to_string();
+            state.refs.insert("HEAD".into(), head);
+            Ok(())
+        })
+    }
+
+    fn cherry_pick(
+        &self,
+        commit: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let picked = state.log[state.resolve_commit(&commit)?].clone();
+            state.commit_on_head(picked.subject.clone(), &picked)
+        })
+    }
+
+    fn revert(
+        &self,
+        commit: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let reverted = state.log[state.resolve_commit(&commit)?].clone();
+            let subject = format!("Revert \"{}\"", reverted.subject).into();
+            state.commit_on_head(subject, &reverted)
+        })
+    }
+
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>> {
+        self.with_state_async(false, |state| {
+            let mut tags = state.tags.clone();
+            tags.sort_by_key(|tag| std::cmp::Reverse(tag.timestamp));
+            Ok(tags)
+        })
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        commit: String,
+        message: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.tags.iter().any(|tag| tag.name.as_ref() == name) {
+                bail!("tag '{name}' already exists");
+            }
+            let (sha, timestamp) = match state.resolve_commit(&commit) {
+                Ok(ix) => {
+                    let entry = &state.log[ix];
+                    (entry.sha.to_string(), entry.commit_timestamp)
+                }
+                Err(error) => (state.refs.get(&commit).cloned().ok_or(error)?, 0),
+            };
+            state.tags.push(Tag {
+                name: name.into(),
+                sha: sha.into(),
+                message: message.map(Into::into),
+                timestamp,
+            });
+            Ok(())
+        })
+    }
+
+    fn delete_tag(
+        &self,
+        name: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let ix = state
+                .tags
+                .iter()
+                .position(|tag| tag.name.as_ref() == name)
+                .with_context(|| format!("tag '{name}' not found"))?;
+            state.tags.remove(ix);
+            Ok(())
+        })
+    }
+
+    fn worktrees(&self) -> BoxFuture<'_, Result<Vec<GitWorktree>>> {
+        let main_path = self.dot_git_path.parent().unwrap().to_path_buf();
+        self.with_state_async(false, move |state| {
+            let main = GitWorktree {
+                path: main_path,
+                sha: state.refs.get("HEAD").map(|sha| sha.clone().into()),
+                branch: state.current_branch_name.clone().map(Into::into),
+                is_main: true,
+                is_locked: false,
+                is_prunable: false,
+            };
+            Ok(std::iter::once(main)
+                .chain(state.worktrees.iter().cloned())
+                .collect())
+        })
+    }
+
+    fn create_worktree(
+        &self,
+        path: PathBuf,
+        commit: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.worktrees.iter().any(|worktree| worktree.path == path) {
+                bail!("'{}' already exists", path.display());
+            }
+            let (sha, branch) = match commit {
+                Some(commit) => {
+                    let sha = match state.resolve_commit(&commit) {
+                        Ok(ix) => state.log[ix].sha.to_string(),
+                        Err(error) => state.refs.get(&commit).cloned().ok_or(error)?,
+                    };
+                    (Some(sha), None)
+                }
+                None => {
+                    let branch = path
+                        .file_name()
+                        .context("worktree path has no file name")?
+                        .to_string_lossy()
+                        .into_owned();
+                    state.branches.insert(branch.clone());
+                    (state.refs.get("HEAD").cloned(), Some(branch))
+                }
+            };
+            state.worktrees.push(GitWorktree {
+                path,
+                sha: sha.map(Into::into),
+                branch: branch.map(Into::into),
+                is_main: false,
+                is_locked: false,
+                is_prunable: false,
+            });
+            Ok(())
+        })
+    }
+
+    fn remove_worktree(
+        &self,
+        path: PathBuf,
+        _force: bool,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let ix = state
+                .worktrees
+                .iter()
+                .position(|worktree| worktree.path == path)
+                .with_context(|| format!("'{}' is not a working tree", path.display()))?;
+            state.worktrees.remove(ix);
+            Ok(())
+        })
+    }
+
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>> {
+        self.with_state_async(false, |state| {
+            let mut submodules = state.submodules.clone();
+            submodules.sort_by(|a, b| a.path.cmp(&b.path));
+            Ok(submodules)
+        })
     }
 
     fn checkout_files(
@@ -187,6 +451,14 @@ impl GitRepository for FakeGitRepository {
         async move { None }.boxed()
     }
 
+    fn rebase_progress(&self) -> BoxFuture<'_, Option<RebaseProgress>> {
+        async move { None }.boxed()
+    }
+
+    fn bisect_status(&self) -> BoxFuture<'_, Result<Option<BisectStatus>>> {
+        self.with_state_async(false, |state| Ok(state.bisect.clone()))
+    }
+
     fn status(&self, path_prefixes: &[RepoPath]) -> Task<Result<GitStatus>> {
         let workdir_path = self.dot_git_path.parent().unwrap();
 
@@ -379,6 +651,56 @@ impl GitRepository for FakeGitRepository {
         })
     }
 
+    fn file_history(
+        &self,
+        _path: RepoPath,
+        _rows: Option<Range<u32>>,
+    ) -> BoxFuture<'_, Result<Vec<FileHistoryEntry>>> {
+        unimplemented!()
+    }
+
+    fn merge_base(&self, first: String, _second: String) -> BoxFuture<'_, Result<Option<Oid>>> {
+        // The fake doesn't record the ancestry of refs, so the first commit is taken to be an
+        // ancestor of the second.
+        self.with_state_async(false, move |state| {
+            let sha = match state.refs.get(&first) {
+                Some(sha) => sha.clone(),
+                None => state.log[state.resolve_commit(&first)?].sha.to_string(),
+            };
+            Ok(Some(sha.parse()?))
+        })
+    }
+
+    fn changed_paths_since(
+        &self,
+        commit: Oid,
+    ) -> BoxFuture<'_, Result<Vec<(RepoPath, StatusCode)>>> {
+        self.with_state_async(false, move |state| {
+            let base = state
+                .commit_contents
+                .get(&commit)
+                .with_context(|| format!("unknown commit {commit}"))?;
+            let paths = base
+                .keys()
+                .chain(state.head_contents.keys())
+                .collect::<HashSet<_>>();
+            let mut changes = paths
+                .into_iter()
+                .filter_map(|path| {
+                    let status = match (base.get(path), state.head_contents.get(path)) {
+                        (Some(old), Some(new)) if old != new => StatusCode::Modified,
+                        (Some(_), None) => StatusCode::Deleted,
+                        (None, Some(_)) => StatusCode::Added,
+                        _ => return None,
+                    };
+                    Some((path.clone(), status))
+                })
+                .collect::<Vec<_>>();
+            changes.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Ok(changes)
+        })
+    }
+
     fn stage_paths(
         &self,
         paths: Vec<RepoPath>,
@@ -481,6 +803,26 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn push_tag(
+        &self,
+        name: String,
+        remote: String,
+        _askpass: AskPassDelegate,
+        _env: Arc<HashMap<String, String>>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<git::repository::RemoteCommandOutput>> {
+        self.with_state_async(false, move |state| {
+            if !state.tags.iter().any(|tag| tag.name.as_ref() == name) {
+                bail!("tag '{name}' not found");
+            }
+            state.pushed_tags.push((remote, name));
+            Ok(git::repository::RemoteCommandOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+            })
+        })
+    }
+
     fn pull(
         &self,
         _branch: String,
@@ -502,6 +844,70 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn run_submodule_operation(
+        &self,
+        _operation: SubmoduleOperation,
+        _askpass: AskPassDelegate,
+        _env: Arc<HashMap<String, String>>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<git::repository::RemoteCommandOutput>> {
+        unimplemented!()
+    }
+
+    fn rebase(
+        &self,
+        _operation: RebaseOperation,
+        _editor: GitEditorDelegate,
+        _env: Arc<HashMap<String, String>>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<git::repository::RemoteCommandOutput>> {
+        unimplemented!()
+    }
+
+    fn bisect(
+        &self,
+        operation: BisectOperation,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<String>> {
+        self.with_state_async(true, move |state| {
+            let head = state.refs.get("HEAD").cloned().unwrap_or_default();
+            let commit = |commit: Option<SharedString>| -> SharedString {
+                commit.unwrap_or_else(|| head.clone().into())
+            };
+            let not_started = || anyhow::anyhow!("You need to start by \"git bisect start\"");
+            match operation {
+                BisectOperation::Start { bad, good } => {
+                    state.bisect = Some(BisectStatus {
+                        good: if bad.is_some() { good } else { Vec::new() },
+                        bad,
+                        ..Default::default()
+                    });
+                }
+                BisectOperation::Good(sha) => state
+                    .bisect
+                    .as_mut()
+                    .ok_or_else(not_started)?
+                    .good
+                    .push(commit(sha)),
+                BisectOperation::Bad(sha) => {
+                    state.bisect.as_mut().ok_or_else(not_started)?.bad = Some(commit(sha))
+                }
+                BisectOperation::Skip(sha) => state
+                    .bisect
+                    .as_mut()
+                    .ok_or_else(not_started)?
+                    .skipped
+                    .push(commit(sha)),
+                BisectOperation::Reset => state.bisect = None,
+            }
+            Ok(String::new())
+        })
+    }
+
+    fn bisect_log(&self) -> BoxFuture<'_, Result<String>> {
+        unimplemented!()
+    }
+
     fn get_remotes(&self, _branch: Option<String>) -> BoxFuture<'_, Result<Vec<Remote>>> {
         unimplemented!()
     }
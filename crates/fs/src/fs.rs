@@ -47,7 +47,8 @@ use collections::{BTreeMap, btree_map};
 use fake_git_repo::FakeGitRepositoryState;
 #[cfg(any(test, feature = "test-support"))]
 use git::{
-    repository::{RepoPath, repo_path},
+    history::LogEntry,
+    repository::{RepoPath, Submodule, repo_path},
     status::{FileStatus, StatusCode, TrackedStatus, UnmergedStatus},
 };
 #[cfg(any(test, feature = "test-support"))]
@@ -1645,6 +1646,20 @@ impl FakeFs {
         .unwrap();
     }
 
+    pub fn set_log_for_repo(&self, dot_git: &Path, log: Vec<LogEntry>) {
+        self.with_git_state(dot_git, true, |state| {
+            state.log = log;
+        })
+        .unwrap();
+    }
+
+    pub fn set_submodules_for_repo(&self, dot_git: &Path, submodules: Vec<Submodule>) {
+        self.with_git_state(dot_git, true, |state| {
+            state.submodules = submodules;
+        })
+        .unwrap();
+    }
+
     pub fn set_head_and_index_for_repo(&self, dot_git: &Path, contents_by_path: &[(&str, String)]) {
         self.with_git_state(dot_git, true, |state| {
             state.head_contents.clear();
@@ -0,0 +1,276 @@
+use anyhow::{Context as _, Result};
+use gpui::SharedString;
+
+/// The name of the file git creates in the git directory when a bisect is started, and removes
+/// when it's reset.
+pub const BISECT_START_FILE_NAME: &str = "BISECT_START";
+
+/// A step of a bisect, run by [`crate::repository::GitRepository::bisect`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BisectOperation {
+    /// Starts a bisect, marking the given commits as bad and good if there are any.
+    Start {
+        bad: Option<SharedString>,
+        good: Vec<SharedString>,
+    },
+    /// Marks a commit, or `HEAD` if `None`, as not containing the change being looked for.
+    Good(Option<SharedString>),
+    /// Marks a commit, or `HEAD` if `None`, as containing the change being looked for.
+    Bad(Option<SharedString>),
+    /// Marks a commit, or `HEAD` if `None`, as untestable.
+    Skip(Option<SharedString>),
+    /// Ends the bisect, checking out the branch it was started on.
+    Reset,
+}
+
+impl BisectOperation {
+    pub fn args(&self) -> Vec<&str> {
+        let mut args = vec!["bisect", self.subcommand()];
+        match self {
+            Self::Start { bad, good } => {
+                // Without a bad commit, the good ones would be taken for the bad one.
+                if let Some(bad) = bad {
+                    args.push(bad.as_ref());
+                    args.extend(good.iter().map(|sha| sha.as_ref()));
+                }
+            }
+            Self::Good(commit) | Self::Bad(commit) | Self::Skip(commit) => {
+                args.extend(commit.as_ref().map(|sha| sha.as_ref()));
+            }
+            Self::Reset => {}
+        }
+        args
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Start { .. } => "bisect start",
+            Self::Good(_) => "bisect good",
+            Self::Bad(_) => "bisect bad",
+            Self::Skip(_) => "bisect skip",
+            Self::Reset => "bisect reset",
+        }
+    }
+
+    fn subcommand(&self) -> &'static str {
+        match self {
+            Self::Start { .. } => "start",
+            Self::Good(_) => "good",
+            Self::Bad(_) => "bad",
+            Self::Skip(_) => "skip",
+            Self::Reset => "reset",
+        }
+    }
+
+    /// Returns the operation that marks `HEAD` according to the exit code of a command that tested
+    /// it, following the convention of `git bisect run`: 0 means good, 125 means the commit can't
+    /// be tested, and other codes below 128 mean bad. Returns `None` for the codes that should
+    /// stop the bisect instead.
+    pub fn for_exit_code(code: i32) -> Option<Self> {
+        match code {
+            0 => Some(Self::Good(None)),
+            125 => Some(Self::Skip(None)),
+            1..=127 => Some(Self::Bad(None)),
+            _ => None,
+        }
+    }
+}
+
+/// How many commits are left to test in a bisect, as computed by `git rev-list --bisect-vars`.
+/// Commits that were skipped are still counted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BisectProgress {
+    /// The number of commits that may be the first bad one, including the bad commit itself.
+    pub candidates: usize,
+    /// The number of commits that are left to test after the one checked out.
+    pub revisions_left: usize,
+    /// The number of steps that are roughly left after the one checked out.
+    pub steps_left: usize,
+}
+
+impl BisectProgress {
+    /// Parses the output of `git rev-list --bisect-vars`.
+    pub fn parse(text: &str) -> Result<Self> {
+        let var = |name: &str| -> Result<usize> {
+            text.lines()
+                .find_map(|line| line.strip_prefix(name)?.strip_prefix('='))
+                .with_context(|| format!("missing {name}"))?
+                .trim_matches('\'')
+                .parse()
+                .with_context(|| format!("parsing {name}"))
+        };
+        Ok(Self {
+            candidates: var("bisect_all")?,
+            revisions_left: var("bisect_nr")?,
+            steps_left: var("bisect_steps")?,
+        })
+    }
+}
+
+/// The state of the bisect in progress in a repository.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BisectStatus {
+    /// The sha of the commit marked as bad, if one has been.
+    pub bad: Option<SharedString>,
+    /// The shas of the commits marked as good.
+    pub good: Vec<SharedString>,
+    /// The shas of the commits marked as untestable.
+    pub skipped: Vec<SharedString>,
+    /// How many commits are left to test, which is `None` until both a bad commit and a good one
+    /// have been marked.
+    pub progress: Option<BisectProgress>,
+}
+
+impl BisectStatus {
+    /// Parses the refs git keeps under `refs/bisect`, as listed by
+    /// `git for-each-ref --format='%(refname) %(objectname)' refs/bisect`.
+    pub fn parse_refs(text: &str) -> Self {
+        let mut status = Self::default();
+        for line in text.lines() {
+            let Some((name, sha)) = line.trim().split_once(' ') else {
+                continue;
+            };
+            let Some(name) = name.strip_prefix("refs/bisect/") else {
+                continue;
+            };
+            let sha = SharedString::from(sha.to_string());
+            if name == "bad" {
+                status.bad = Some(sha);
+            } else if name.starts_with("good-") {
+                status.good.push(sha);
+            } else if name.starts_with("skip-") {
+                status.skipped.push(sha);
+            }
+        }
+        status
+    }
+
+    /// Returns the first bad commit, once the bisect narrowed it down to a single one.
+    pub fn first_bad_commit(&self) -> Option<&SharedString> {
+        self.progress
+            .filter(|progress| progress.candidates <= 1)
+            .and(self.bad.as_ref())
+    }
+}
+
+/// Returns whether the output of a bisect step says another commit was checked out to be tested,
+/// rather than the first bad commit having been found or only skipped commits being left.
+pub fn checked_out_next_commit(output: &str) -> bool {
+    output
+        .lines()
+        .any(|line| line.trim_start().starts_with("Bisecting:"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bisect_operation_args() {
+        assert_eq!(
+            BisectOperation::Start {
+                bad: Some("HEAD".into()),
+                good: vec!["v1.0".into(), "v1.1".into()],
+            }
+            .args(),
+            ["bisect", "start", "HEAD", "v1.0", "v1.1"]
+        );
+        assert_eq!(
+            BisectOperation::Start {
+                bad: None,
+                good: vec!["v1.0".into()],
+            }
+            .args(),
+            ["bisect", "start"]
+        );
+        assert_eq!(BisectOperation::Good(None).args(), ["bisect", "good"]);
+        assert_eq!(
+            BisectOperation::Skip(Some("1a2b3c4".into())).args(),
+            ["bisect", "skip", "1a2b3c4"]
+        );
+        assert_eq!(BisectOperation::Reset.args(), ["bisect", "reset"]);
+    }
+
+    #[test]
+    fn test_bisect_operation_for_exit_code() {
+        assert_eq!(
+            BisectOperation::for_exit_code(0),
+            Some(BisectOperation::Good(None))
+        );
+        assert_eq!(
+            BisectOperation::for_exit_code(1),
+            Some(BisectOperation::Bad(None))
+        );
+        assert_eq!(
+            BisectOperation::for_exit_code(125),
+            Some(BisectOperation::Skip(None))
+        );
+        assert_eq!(
+            BisectOperation::for_exit_code(127),
+            Some(BisectOperation::Bad(None))
+        );
+        assert_eq!(BisectOperation::for_exit_code(128), None);
+        assert_eq!(BisectOperation::for_exit_code(-1), None);
+    }
+
+    #[test]
+    fn test_parse_bisect_status() {
+        let mut status = BisectStatus::parse_refs(
+            "refs/bisect/bad 2222222222222222222222222222222222222222\n\
+             refs/bisect/good-1111111111111111111111111111111111111111 1111111111111111111111111111111111111111\n\
+             refs/bisect/skip-3333333333333333333333333333333333333333 3333333333333333333333333333333333333333\n",
+        );
+        assert_eq!(
+            status.bad.as_deref(),
+            Some("2222222222222222222222222222222222222222")
+        );
+        assert_eq!(status.good, ["1111111111111111111111111111111111111111"]);
+        assert_eq!(status.skipped, ["3333333333333333333333333333333333333333"]);
+        assert_eq!(status.first_bad_commit(), None);
+
+        let progress = BisectProgress::parse(
+            "bisect_rev='4444444444444444444444444444444444444444'\n\
+             bisect_nr=3\n\
+             bisect_good=2\n\
+             bisect_bad=3\n\
+             bisect_all=7\n\
+             bisect_steps=2\n",
+        )
+        .unwrap();
+        assert_eq!(
+            progress,
+            BisectProgress {
+                candidates: 7,
+                revisions_left: 3,
+                steps_left: 2,
+            }
+        );
+        status.progress = Some(progress);
+        assert_eq!(status.first_bad_commit(), None);
+
+        status.progress = Some(BisectProgress {
+            candidates: 1,
+            revisions_left: 0,
+            steps_left: 0,
+        });
+        assert_eq!(status.first_bad_commit(), status.bad.as_ref());
+
+        assert!(BisectProgress::parse("bisect_nr=3\n").is_err());
+    }
+
+    #[test]
+    fn test_checked_out_next_commit() {
+        assert!(checked_out_next_commit(
+            "Bisecting: 3 revisions left to test after this (roughly 2 steps)\n\
+             [4444444444444444444444444444444444444444] Fix the build\n"
+        ));
+        assert!(!checked_out_next_commit(
+            "4444444444444444444444444444444444444444 is the first bad commit\n\
+             commit 4444444444444444444444444444444444444444\n"
+        ));
+        assert!(!checked_out_next_commit(
+            "There are only 'skip'ped commits left to test.\n\
+             The first bad commit could be any of:\n"
+        ));
+    }
+}
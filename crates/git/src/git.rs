@@ -1,6 +1,9 @@
+pub mod bisect;
 pub mod blame;
 pub mod commit;
+pub mod history;
 mod hosting_provider;
+pub mod rebase;
 mod remote;
 pub mod repository;
 pub mod stash;
@@ -39,6 +42,13 @@ actions!(
         /// Restores the selected hunks to their original state.
         #[action(deprecated_aliases = ["editor::RevertSelectedHunks"])]
         Restore,
+        // per-line
+        /// Stages the changed lines in the selections, rather than whole hunks.
+        StageLines,
+        /// Unstages the changed lines in the selections, rather than whole hunks.
+        UnstageLines,
+        /// Restores the changed lines in the selections to their original state, rather than whole hunks.
+        RestoreLines,
         // per-file
         /// Shows git blame information for the current file.
         #[action(deprecated_aliases = ["editor::ToggleGitBlame"])]
@@ -47,6 +57,10 @@ actions!(
         StageFile,
         /// Unstages the current file.
         UnstageFile,
+        /// Shows the commits that changed the current file.
+        FileHistory,
+        /// Shows the commits that changed the selected lines.
+        SelectionHistory,
         // repo-wide
         /// Stages all changes in the repository.
         StageAll,
@@ -76,6 +90,32 @@ actions!(
         Fetch,
         /// Fetches changes from a specific remote.
         FetchFrom,
+        /// Registers the repository's submodules, without cloning them.
+        InitSubmodules,
+        /// Clones missing submodules and checks out the commits the repository records for them.
+        UpdateSubmodules,
+        /// Copies the submodule URLs from `.gitmodules` to the repository's configuration.
+        SyncSubmodules,
+        /// Interactively rebases the commits that aren't in the upstream branch.
+        InteractiveRebase,
+        /// Continues the rebase in progress.
+        RebaseContinue,
+        /// Skips the commit the rebase in progress stopped at.
+        RebaseSkip,
+        /// Aborts the rebase in progress, restoring the branch to its original state.
+        RebaseAbort,
+        /// Starts a bisect, marking the current commit as bad.
+        BisectStart,
+        /// Marks the commit the bisect checked out as not containing the change being looked for.
+        BisectGood,
+        /// Marks the commit the bisect checked out as containing the change being looked for.
+        BisectBad,
+        /// Marks the commit the bisect checked out as untestable.
+        BisectSkip,
+        /// Ends the bisect in progress, checking out the branch it was started on.
+        BisectReset,
+        /// Opens the log of the bisect in progress.
+        BisectLog,
         /// Creates a new commit with staged changes.
         Commit,
         /// Amends the last commit with staged changes.
@@ -109,6 +149,19 @@ pub struct RenameBranch {
     pub branch: Option<String>,
 }
 
+/// Runs a task on each commit the bisect in progress checks out, marking the commit good or bad
+/// from the task's exit code, like `git bisect run`.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema, Action)]
+#[action(namespace = git)]
+#[serde(deny_unknown_fields)]
+pub struct BisectRun {
+    /// The label of the task to run.
+    ///
+    /// Default: prompt for a task.
+    #[serde(default)]
+    pub task: Option<String>,
+}
+
 /// Restores a file to its last committed state, discarding local changes.
 #[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema, Action)]
 #[action(namespace = git, deprecated_aliases = ["editor::RevertFile"])]
@@ -0,0 +1,321 @@
+use crate::Oid;
+use crate::repository::RepoPath;
+use anyhow::{Context as _, Result, anyhow};
+use gpui::SharedString;
+use std::{ops::Range, str::FromStr};
+
+/// The `--format` passed to `git log`, matching what [`parse_log`] expects.
+///
+/// Fields are separated by NUL and entries are terminated by the record separator, since the
+/// subject can't contain either.
+pub const LOG_FORMAT: &str = "--format=%H%x00%P%x00%D%x00%ct%x00%an%x00%ae%x00%s%x1e";
+
+/// The `--format` passed to `git log` for [`parse_file_history`].
+///
+/// Unlike [`LOG_FORMAT`], entries start with the record separator, because `git log` prints the
+/// changed paths or the patch after each one.
+pub const FILE_HISTORY_FORMAT: &str = "--format=%x1e%H%x00%P%x00%D%x00%ct%x00%an%x00%ae%x00%s";
+
+const ENTRY_SEPARATOR: char = '\x1e';
+
+/// A commit in the history of a repository.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct LogEntry {
+    pub sha: Oid,
+    /// The commit's parents, with the first parent first.
+    pub parents: Vec<Oid>,
+    /// The branches, tags and `HEAD` pointing at this commit.
+    pub refs: Vec<SharedString>,
+    pub subject: SharedString,
+    pub author_name: SharedString,
+    pub author_email: SharedString,
+    /// This is a unix timestamp
+    pub commit_timestamp: i64,
+}
+
+/// A commit that changed a file, along with the paths it had in that commit, which differ from
+/// its current path if it was renamed since.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileHistoryEntry {
+    pub commit: LogEntry,
+    /// The path of the file in the commit.
+    pub path: RepoPath,
+    /// The path of the file in the commit's first parent, which is different from [`Self::path`]
+    /// if the commit renamed the file.
+    pub old_path: RepoPath,
+}
+
+/// Which commits to return from [`crate::repository::GitRepository::log`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LogOptions {
+    /// The number of commits to skip, for paging through the history.
+    pub skip: usize,
+    /// The maximum number of commits to return.
+    pub limit: usize,
+    /// Only return commits whose author matches this pattern, case-insensitively.
+    pub author: Option<String>,
+    /// Only return commits whose message matches this pattern, case-insensitively.
+    pub message: Option<String>,
+    /// Only return commits touching this path.
+    pub path: Option<RepoPath>,
+}
+
+impl LogOptions {
+    /// Returns the arguments passed to `git log` for these options, excluding the format.
+    pub fn to_args(&self) -> Vec<String> {
+        // `--parents` rewrites the parents of commits to skip those that are filtered out, so
+        // that the history can still be drawn as a graph.
+        let mut args = vec![
+            "--topo-order".to_string(),
+            "--parents".to_string(),
+            format!("--skip={}", self.skip),
+            format!("--max-count={}", self.limit),
+        ];
+        if self.author.is_some() || self.message.is_some() {
+            args.push("--regexp-ignore-case".to_string());
+        }
+        if let Some(author) = &self.author {
+            args.push(format!("--author={author}"));
+        }
+        if let Some(message) = &self.message {
+            args.push(format!("--grep={message}"));
+        }
+        args.push("HEAD".to_string());
+        args.push("--".to_string());
+        if let Some(path) = &self.path {
+            args.push(path.as_unix_str().to_string());
+        }
+        args
+    }
+}
+
+/// Returns the arguments passed to `git log` to list the commits that changed a file, excluding
+/// the format.
+///
+/// When `rows` is given, only the commits that changed those zero-based rows of the file in
+/// `HEAD` are listed, using `git log -L`, which prints a patch for each commit. Otherwise renames
+/// are followed and the changed paths are printed.
+pub fn file_history_args(path: &RepoPath, rows: Option<Range<u32>>) -> Vec<String> {
+    match rows {
+        Some(rows) => vec![
+            "-L".to_string(),
+            format!(
+                "{},{}:{}",
+                rows.start + 1,
+                rows.end.max(rows.start + 1),
+                path.as_unix_str()
+            ),
+            "HEAD".to_string(),
+        ],
+        None => vec![
+            "--follow".to_string(),
+            "--name-status".to_string(),
+            "HEAD".to_string(),
+            "--".to_string(),
+            path.as_unix_str().to_string(),
+        ],
+    }
+}
+
+/// Parses the output of `git log` with [`FILE_HISTORY_FORMAT`] and [`file_history_args`].
+///
+/// `path` is used for commits whose paths can't be determined from the output.
+pub fn parse_file_history(output: &str, path: &RepoPath) -> Result<Vec<FileHistoryEntry>> {
+    output
+        .split(ENTRY_SEPARATOR)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (header, body) = entry.split_once('\n').unwrap_or((entry, ""));
+            let commit = parse_log_entry(header)
+                .with_context(|| format!("parsing git log entry {header:?}"))?;
+            let (old_path, new_path) = parse_changed_paths(body);
+            let new_path = match new_path {
+                Some(new_path) => RepoPath::new(new_path)?,
+                None => path.clone(),
+            };
+            let old_path = match old_path {
+                Some(old_path) => RepoPath::new(old_path)?,
+                None => new_path.clone(),
+            };
+            Ok(FileHistoryEntry {
+                commit,
+                path: new_path,
+                old_path,
+            })
+        })
+        .collect()
+}
+
+/// Returns the old and new path of the file changed in a single commit, from either the patch
+/// printed by `git log -L` or the line printed by `--name-status`.
+fn parse_changed_paths(body: &str) -> (Option<&str>, Option<&str>) {
+    let mut old_path = None;
+    let mut new_path = None;
+    for line in body.lines() {
+        if let Some(path) = line.strip_prefix("--- a/") {
+            old_path = Some(path);
+        } else if let Some(path) = line.strip_prefix("+++ b/") {
+            new_path = Some(path);
+            break;
+        } else if line.starts_with("diff ") || line.starts_with("--- ") {
+            continue;
+        } else if old_path.is_none() && !line.is_empty() {
+            // A line like `M\tpath`, or `R100\told_path\tnew_path` for renames.
+            let mut fields = line.split('\t').skip(1);
+            let first = fields.next();
+            return match fields.next() {
+                Some(second) => (first, Some(second)),
+                None => (None, first),
+            };
+        }
+    }
+    (old_path, new_path)
+}
+
+/// Parses the output of `git log` with [`LOG_FORMAT`].
+pub fn parse_log(output: &str) -> Result<Vec<LogEntry>> {
+    output
+        .split(ENTRY_SEPARATOR)
+        .map(|entry| entry.trim_start_matches('\n'))
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            parse_log_entry(entry).with_context(|| format!("parsing git log entry {entry:?}"))
+        })
+        .collect()
+}
+
+fn parse_log_entry(entry: &str) -> Result<LogEntry> {
+    let fields = entry.split('\0').collect::<Vec<_>>();
+    let [
+        sha,
+        parents,
+        refs,
+        timestamp,
+        author_name,
+        author_email,
+        subject,
+    ] = fields[..]
+    else {
+        return Err(anyhow!("expected 7 fields, got {}", fields.len()));
+    };
+
+    Ok(LogEntry {
+        sha: Oid::from_str(sha)?,
+        parents: parents
+            .split_whitespace()
+            .map(Oid::from_str)
+            .collect::<Result<_>>()?,
+        refs: parse_refs(refs),
+        subject: subject.to_string().into(),
+        author_name: author_name.to_string().into(),
+        author_email: author_email.to_string().into(),
+        commit_timestamp: timestamp.parse()?,
+    })
+}
+
+/// Parses the ref names printed by `%D`, e.g. `HEAD -> main, origin/main, tag: v1.0`.
+fn parse_refs(refs: &str) -> Vec<SharedString> {
+    refs.split(", ")
+        .filter(|name| !name.is_empty())
+        .flat_map(|name| match name.split_once(" -> ") {
+            Some((head, branch)) => vec![head, branch],
+            None => vec![name],
+        })
+        .map(|name| SharedString::from(name.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::repo_path;
+
+    const SHA_1: &str = "1111111111111111111111111111111111111111";
+    const SHA_2: &str = "2222222222222222222222222222222222222222";
+    const SHA_3: &str = "3333333333333333333333333333333333333333";
+
+    #[test]
+    fn test_parse_refs() {
+        assert_eq!(parse_refs(""), Vec::<SharedString>::new());
+        assert_eq!(
+            parse_refs("HEAD -> main, origin/main, tag: v1.0"),
+            ["HEAD", "main", "origin/main", "tag: v1.0"]
+        );
+    }
+
+    #[test]
+    fn test_parse_log() {
+        let output = format!(
+            "{SHA_3}\0{SHA_1} {SHA_2}\0HEAD -> main\01700000002\0Alice\0alice@example.com\0Merge branch 'feature'\x1e\n\
+             {SHA_2}\0{SHA_1}\0feature\01700000001\0Bob\0bob@example.com\0Add a feature\x1e\n\
+             {SHA_1}\0\0\01700000000\0Alice\0alice@example.com\0Initial commit\x1e\n"
+        );
+        let entries = parse_log(&output).unwrap();
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].sha.to_string(), SHA_3);
+        assert_eq!(
+            entries[0]
+                .parents
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            [SHA_1, SHA_2]
+        );
+        assert_eq!(entries[0].refs, ["HEAD", "main"]);
+        assert_eq!(entries[0].subject, "Merge branch 'feature'");
+        assert_eq!(entries[1].author_name, "Bob");
+        assert_eq!(entries[1].author_email, "bob@example.com");
+        assert_eq!(entries[1].commit_timestamp, 1700000001);
+        assert!(entries[2].parents.is_empty());
+        assert!(entries[2].refs.is_empty());
+
+        assert!(parse_log("").unwrap().is_empty());
+        assert!(parse_log("not a log entry\x1e").is_err());
+    }
+
+    #[test]
+    fn test_parse_file_history() {
+        let path = repo_path("src/new.rs");
+
+        let output = format!(
+            "\x1e{SHA_3}\0{SHA_2}\0HEAD -> main\01700000002\0Alice\0alice@example.com\0Rename\n\n\
+             R087\tsrc/old.rs\tsrc/new.rs\n\
+             \x1e{SHA_1}\0\0\01700000000\0Bob\0bob@example.com\0Initial commit\n\n\
+             A\tsrc/old.rs\n"
+        );
+        let entries = parse_file_history(&output, &path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].commit.sha.to_string(), SHA_3);
+        assert_eq!(entries[0].path, path);
+        assert_eq!(entries[0].old_path, repo_path("src/old.rs"));
+        assert_eq!(entries[1].commit.subject, "Initial commit");
+        assert_eq!(entries[1].path, repo_path("src/old.rs"));
+        assert_eq!(entries[1].old_path, repo_path("src/old.rs"));
+
+        let output = format!(
+            "\x1e{SHA_2}\0{SHA_1}\0\01700000001\0Alice\0alice@example.com\0Change a line\n\n\
+             diff --git a/src/new.rs b/src/new.rs\n\
+             --- a/src/new.rs\n\
+             +++ b/src/new.rs\n\
+             @@ -2,1 +2,1 @@\n\
+             -a\n\
+             +b\n\
+             \x1e{SHA_1}\0\0\01700000000\0Bob\0bob@example.com\0Initial commit\n\n\
+             diff --git a/src/new.rs b/src/new.rs\n\
+             --- /dev/null\n\
+             +++ b/src/new.rs\n\
+             @@ -0,0 +2,1 @@\n\
+             +a\n"
+        );
+        let entries = parse_file_history(&output, &path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].commit.subject, "Change a line");
+        assert_eq!(entries[0].path, path);
+        assert_eq!(entries[0].old_path, path);
+        assert_eq!(entries[1].path, path);
+        assert_eq!(entries[1].old_path, path);
+
+        assert!(parse_file_history("", &path).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,297 @@
+use anyhow::{Context as _, Result};
+use gpui::SharedString;
+use std::{fmt, path::Path};
+
+/// The name of the file git asks the sequence editor to edit for an interactive rebase.
+pub const REBASE_TODO_FILE_NAME: &str = "git-rebase-todo";
+
+/// What an interactive rebase does with a commit.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum RebaseAction {
+    /// Use the commit.
+    Pick,
+    /// Use the commit, but edit its message.
+    Reword,
+    /// Use the commit, but stop for amending it.
+    Edit,
+    /// Meld the commit into the previous one, combining their messages.
+    Squash,
+    /// Meld the commit into the previous one, discarding its message.
+    Fixup,
+    /// Remove the commit.
+    Drop,
+}
+
+impl RebaseAction {
+    pub const ALL: [Self; 6] = [
+        Self::Pick,
+        Self::Reword,
+        Self::Edit,
+        Self::Squash,
+        Self::Fixup,
+        Self::Drop,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pick => "pick",
+            Self::Reword => "reword",
+            Self::Edit => "edit",
+            Self::Squash => "squash",
+            Self::Fixup => "fixup",
+            Self::Drop => "drop",
+        }
+    }
+
+    /// Parses a command of the todo list, in its long or abbreviated form.
+    fn parse(command: &str) -> Option<Self> {
+        Some(match command {
+            "pick" | "p" => Self::Pick,
+            "reword" | "r" => Self::Reword,
+            "edit" | "e" => Self::Edit,
+            "squash" | "s" => Self::Squash,
+            "fixup" | "f" => Self::Fixup,
+            "drop" | "d" => Self::Drop,
+            _ => return None,
+        })
+    }
+
+    /// Whether the commit is melded into the one before it.
+    pub fn is_melded(&self) -> bool {
+        matches!(self, Self::Squash | Self::Fixup)
+    }
+}
+
+impl fmt::Display for RebaseAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A commit in the todo list of an interactive rebase.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RebaseTodoCommit {
+    pub action: RebaseAction,
+    /// The abbreviated sha of the commit, as written by git.
+    pub sha: SharedString,
+    pub subject: SharedString,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RebaseTodoEntry {
+    Commit(RebaseTodoCommit),
+    /// A command that doesn't apply to a single commit, like `exec` or `update-ref`, which is
+    /// kept as written.
+    Command(SharedString),
+}
+
+/// The todo list of an interactive rebase, which git writes to [`REBASE_TODO_FILE_NAME`] for the
+/// sequence editor to reorder and change the actions of.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RebaseTodo {
+    pub entries: Vec<RebaseTodoEntry>,
+}
+
+impl RebaseTodo {
+    /// Parses a todo list, skipping comments and blank lines.
+    pub fn parse(text: &str) -> Self {
+        let entries = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let mut parts = line.splitn(3, ' ');
+                let action = parts.next().and_then(RebaseAction::parse);
+                let sha = parts.next().filter(|sha| !sha.starts_with('-'));
+                match (action, sha) {
+                    (Some(action), Some(sha)) => RebaseTodoEntry::Commit(RebaseTodoCommit {
+                        action,
+                        sha: sha.to_string().into(),
+                        subject: parts.next().unwrap_or_default().to_string().into(),
+                    }),
+                    _ => RebaseTodoEntry::Command(line.to_string().into()),
+                }
+            })
+            .collect();
+        Self { entries }
+    }
+
+    pub fn commits(&self) -> impl Iterator<Item = &RebaseTodoCommit> {
+        self.entries.iter().filter_map(|entry| match entry {
+            RebaseTodoEntry::Commit(commit) => Some(commit),
+            RebaseTodoEntry::Command(_) => None,
+        })
+    }
+
+    /// Returns an error if the todo list can't be executed, which is the case when a squash or a
+    /// fixup comes before any commit that it could be melded into.
+    pub fn validate(&self) -> Result<()> {
+        let first_kept = self
+            .commits()
+            .find(|commit| commit.action != RebaseAction::Drop);
+        if let Some(commit) = first_kept
+            && commit.action.is_melded()
+        {
+            anyhow::bail!(
+                "cannot {} {} without a previous commit",
+                commit.action,
+                commit.sha
+            );
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for RebaseTodo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            match entry {
+                RebaseTodoEntry::Commit(commit) => {
+                    writeln!(f, "{} {} {}", commit.action, commit.sha, commit.subject)?
+                }
+                RebaseTodoEntry::Command(command) => writeln!(f, "{command}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A step of a rebase, run by [`crate::repository::GitRepository::rebase`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RebaseOperation {
+    /// Starts an interactive rebase of the commits after `onto`, or of all commits if `onto` is
+    /// `None`.
+    Start {
+        onto: Option<SharedString>,
+    },
+    Continue,
+    Skip,
+    Abort,
+}
+
+impl RebaseOperation {
+    pub fn args(&self) -> Vec<&str> {
+        match self {
+            Self::Start { onto: Some(onto) } => vec!["rebase", "--interactive", onto.as_ref()],
+            Self::Start { onto: None } => vec!["rebase", "--interactive", "--root"],
+            Self::Continue => vec!["rebase", "--continue"],
+            Self::Skip => vec!["rebase", "--skip"],
+            Self::Abort => vec!["rebase", "--abort"],
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Start { .. } => "rebase",
+            Self::Continue => "rebase --continue",
+            Self::Skip => "rebase --skip",
+            Self::Abort => "rebase --abort",
+        }
+    }
+}
+
+/// How far an interactive rebase that's stopped, for editing a commit or resolving conflicts,
+/// has progressed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RebaseProgress {
+    /// The one-based index of the todo list entry the rebase stopped at.
+    pub step: usize,
+    /// The number of entries in the todo list.
+    pub total: usize,
+}
+
+impl RebaseProgress {
+    /// Reads the progress of the rebase in progress in the given git directory, if any.
+    pub fn load(git_dir: &Path) -> Result<Option<Self>> {
+        let rebase_dir = git_dir.join("rebase-merge");
+        if !rebase_dir.is_dir() {
+            return Ok(None);
+        }
+        let read_number = |name: &str| -> Result<usize> {
+            let path = rebase_dir.join(name);
+            std::fs::read_to_string(&path)
+                .with_context(|| format!("reading {path:?}"))?
+                .trim()
+                .parse()
+                .with_context(|| format!("parsing {path:?}"))
+        };
+        Ok(Some(Self {
+            step: read_number("msgnum").unwrap_or(0),
+            total: read_number("end")?,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rebase_todo() {
+        let text = "\
+            pick 1a2b3c4 Add a feature\n\
+            p 5d6e7f8 Fix the feature\n\
+            update-ref refs/heads/feature\n\
+            \n\
+            # Rebase 0123456..5d6e7f8 onto 0123456 (2 commands)\n\
+            #\n\
+            # Commands:\n\
+            # p, pick <commit> = use commit\n";
+        let todo = RebaseTodo::parse(text);
+
+        assert_eq!(
+            todo.entries,
+            [
+                RebaseTodoEntry::Commit(RebaseTodoCommit {
+                    action: RebaseAction::Pick,
+                    sha: "1a2b3c4".into(),
+                    subject: "Add a feature".into(),
+                }),
+                RebaseTodoEntry::Commit(RebaseTodoCommit {
+                    action: RebaseAction::Pick,
+                    sha: "5d6e7f8".into(),
+                    subject: "Fix the feature".into(),
+                }),
+                RebaseTodoEntry::Command("update-ref refs/heads/feature".into()),
+            ]
+        );
+        assert_eq!(
+            todo.to_string(),
+            "pick 1a2b3c4 Add a feature\n\
+             pick 5d6e7f8 Fix the feature\n\
+             update-ref refs/heads/feature\n"
+        );
+
+        // Options like `fixup -C` aren't understood, so the line is kept as is.
+        let todo = RebaseTodo::parse("fixup -C 1a2b3c4 Add a feature\n");
+        assert_eq!(
+            todo.entries,
+            [RebaseTodoEntry::Command(
+                "fixup -C 1a2b3c4 Add a feature".into()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_validate_rebase_todo() {
+        let mut todo = RebaseTodo::parse("pick 1a2b3c4 First\npick 5d6e7f8 Second\n");
+        assert!(todo.validate().is_ok());
+
+        let RebaseTodoEntry::Commit(second) = &mut todo.entries[1] else {
+            panic!("expected a commit");
+        };
+        second.action = RebaseAction::Fixup;
+        assert!(todo.validate().is_ok());
+
+        todo.entries.swap(0, 1);
+        assert!(todo.validate().is_err());
+
+        // A dropped commit can't be melded into either.
+        todo.entries.swap(0, 1);
+        let RebaseTodoEntry::Commit(first) = &mut todo.entries[0] else {
+            panic!("expected a commit");
+        };
+        first.action = RebaseAction::Drop;
+        assert!(todo.validate().is_err());
+    }
+}
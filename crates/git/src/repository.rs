@@ -1,4 +1,10 @@
+use crate::bisect::{BISECT_START_FILE_NAME, BisectOperation, BisectProgress, BisectStatus};
 use crate::commit::parse_git_diff_name_status;
+use crate::history::{
+    FILE_HISTORY_FORMAT, FileHistoryEntry, LOG_FORMAT, LogEntry, LogOptions, file_history_args,
+    parse_file_history, parse_log,
+};
+use crate::rebase::{RebaseOperation, RebaseProgress};
 use crate::stash::GitStash;
 use crate::status::{GitStatus, StatusCode};
 use crate::{Oid, SHORT_SHA_LENGTH};
@@ -20,6 +26,7 @@ use std::process::{ExitStatus, Stdio};
 use std::{
     cmp::Ordering,
     future,
+    ops::Range,
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -31,7 +38,10 @@ use util::rel_path::RelPath;
 use util::{ResultExt, paths};
 use uuid::Uuid;
 
-pub use askpass::{AskPassDelegate, AskPassResult, AskPassSession};
+pub use askpass::{
+    AskPassDelegate, AskPassResult, AskPassSession, GitEditorDelegate, GitEditorRequest,
+    GitEditorSession,
+};
 
 pub const REMOTE_CANCELLED_BY_USER: &str = "Operation cancelled by user";
 
@@ -72,6 +82,82 @@ impl Branch {
     }
 }
 
+/// A working tree of a repository, as listed by `git worktree list`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct GitWorktree {
+    pub path: PathBuf,
+    /// The sha of the commit checked out in the worktree, which is `None` for a bare repository.
+    pub sha: Option<SharedString>,
+    /// The name of the branch checked out in the worktree, or `None` if its `HEAD` is detached.
+    pub branch: Option<SharedString>,
+    /// Whether this is the main worktree, which is the one the repository was cloned or
+    /// initialized in, as opposed to a linked worktree created with `git worktree add`.
+    pub is_main: bool,
+    pub is_locked: bool,
+    /// Whether the worktree's directory is missing, so that `git worktree prune` would remove it.
+    pub is_prunable: bool,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Tag {
+    pub name: SharedString,
+    /// The sha of the commit the tag points at.
+    pub sha: SharedString,
+    /// The subject of the tag's message, or `None` for a lightweight tag.
+    pub message: Option<SharedString>,
+    /// When the tag was created, or when its commit was for a lightweight tag, as a unix
+    /// timestamp.
+    pub timestamp: i64,
+}
+
+/// A submodule of a repository, as configured in its `.gitmodules`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Submodule {
+    pub path: RepoPath,
+    pub name: SharedString,
+    pub url: Option<SharedString>,
+    /// The sha of the commit the superproject's index points the submodule at.
+    pub recorded_sha: Option<SharedString>,
+    /// The sha of the commit checked out in the submodule, which is `None` if it hasn't been
+    /// initialized.
+    pub checked_out_sha: Option<SharedString>,
+    /// Whether the submodule's working tree has uncommitted or untracked changes. This comes from
+    /// the superproject's status, rather than from a status of the submodule itself.
+    pub is_dirty: bool,
+}
+
+impl Submodule {
+    pub fn is_initialized(&self) -> bool {
+        self.checked_out_sha.is_some()
+    }
+
+    /// Whether the submodule has a different commit checked out than the one the superproject
+    /// records, either because it was updated upstream or because new commits were made in it.
+    pub fn is_out_of_date(&self) -> bool {
+        self.is_initialized() && self.checked_out_sha != self.recorded_sha
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmoduleOperation {
+    /// Registers the submodules in `.git/config`, without cloning them.
+    Init,
+    /// Clones missing submodules and checks out the commits the superproject records, recursively.
+    Update,
+    /// Copies the submodule URLs from `.gitmodules` to `.git/config`, recursively.
+    Sync,
+}
+
+impl SubmoduleOperation {
+    fn args(&self) -> &'static [&'static str] {
+        match self {
+            SubmoduleOperation::Init => &["submodule", "init"],
+            SubmoduleOperation::Update => &["submodule", "update", "--init", "--recursive"],
+            SubmoduleOperation::Sync => &["submodule", "sync", "--recursive"],
+        }
+    }
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct Upstream {
     pub ref_name: SharedString,
@@ -187,6 +273,7 @@ pub struct Remote {
     pub name: SharedString,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ResetMode {
     /// Reset the branch pointer, leave index and worktree unchanged (this will make it look like things that were
     /// committed are now staged).
@@ -194,6 +281,8 @@ pub enum ResetMode {
     /// Reset the branch pointer and index, leave worktree unchanged (this makes it look as though things that were
     /// committed are now unstaged).
     Mixed,
+    /// Reset the branch pointer, index and worktree, discarding any changes to tracked files.
+    Hard,
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -351,6 +440,11 @@ pub trait GitRepository: Send + Sync {
     /// Also returns `None` for symlinks.
     fn load_committed_text(&self, path: RepoPath) -> BoxFuture<'_, Option<String>>;
 
+    /// Returns the contents of an entry in the given commit, or None if the commit does not exist or has no entry for the given path.
+    ///
+    /// Also returns `None` for symlinks.
+    fn load_text_at_commit(&self, commit: Oid, path: RepoPath) -> BoxFuture<'_, Option<String>>;
+
     fn set_index_text(
         &self,
         path: RepoPath,
@@ -378,6 +472,12 @@ pub trait GitRepository: Send + Sync {
 
     fn merge_message(&self) -> BoxFuture<'_, Option<String>>;
 
+    /// Returns the progress of the interactive rebase in progress, if any.
+    fn rebase_progress(&self) -> BoxFuture<'_, Option<RebaseProgress>>;
+
+    /// Returns the state of the bisect in progress, if any.
+    fn bisect_status(&self) -> BoxFuture<'_, Result<Option<BisectStatus>>>;
+
     fn status(&self, path_prefixes: &[RepoPath]) -> Task<Result<GitStatus>>;
 
     fn stash_entries(&self) -> BoxFuture<'_, Result<GitStash>>;
@@ -388,6 +488,44 @@ pub trait GitRepository: Send + Sync {
     fn create_branch(&self, name: String) -> BoxFuture<'_, Result<()>>;
     fn rename_branch(&self, branch: String, new_name: String) -> BoxFuture<'_, Result<()>>;
 
+    /// Returns the tags of the repository, most recently created first.
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>>;
+    /// Creates a tag pointing at a commit, which is annotated if a message is given.
+    fn create_tag(
+        &self,
+        name: String,
+        commit: String,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+    fn delete_tag(
+        &self,
+        name: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the worktrees of the repository, starting with the main one.
+    fn worktrees(&self) -> BoxFuture<'_, Result<Vec<GitWorktree>>>;
+    /// Creates a linked worktree at `path`, checking out `commit` if given. Otherwise git checks
+    /// out a branch named after the last component of `path`, creating it from `HEAD` if it doesn't
+    /// exist.
+    fn create_worktree(
+        &self,
+        path: PathBuf,
+        commit: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+    /// Removes a linked worktree, which fails if it has uncommitted changes unless `force` is set.
+    fn remove_worktree(
+        &self,
+        path: PathBuf,
+        force: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the submodules of the repository, ordered by path.
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>>;
+
     fn reset(
         &self,
         commit: String,
@@ -395,6 +533,20 @@ pub trait GitRepository: Send + Sync {
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>>;
 
+    /// Applies the changes introduced by a commit on top of `HEAD`, in a new commit.
+    fn cherry_pick(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Creates a commit that undoes the changes introduced by a commit.
+    fn revert(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
     fn checkout_files(
         &self,
         commit: String,
@@ -405,8 +557,32 @@ pub trait GitRepository: Send + Sync {
     fn show(&self, commit: String) -> BoxFuture<'_, Result<CommitDetails>>;
 
     fn load_commit(&self, commit: String, cx: AsyncApp) -> BoxFuture<'_, Result<CommitDiff>>;
+
+    /// Returns the commits reachable from HEAD matching the given options, in topological order.
+    fn log(&self, options: LogOptions) -> BoxFuture<'_, Result<Vec<LogEntry>>>;
+
     fn blame(&self, path: RepoPath, content: Rope) -> BoxFuture<'_, Result<crate::blame::Blame>>;
 
+    /// Returns the commits reachable from HEAD that changed the given path, newest first.
+    ///
+    /// When `rows` is given, only the commits that changed those rows of the file in HEAD are
+    /// returned.
+    fn file_history(
+        &self,
+        path: RepoPath,
+        rows: Option<Range<u32>>,
+    ) -> BoxFuture<'_, Result<Vec<FileHistoryEntry>>>;
+
+    /// Returns the best common ancestor of two commits, or None if they have no common history.
+    fn merge_base(&self, first: String, second: String) -> BoxFuture<'_, Result<Option<Oid>>>;
+
+    /// Returns the paths whose contents differ between the given commit and HEAD, along with how
+    /// they changed.
+    fn changed_paths_since(
+        &self,
+        commit: Oid,
+    ) -> BoxFuture<'_, Result<Vec<(RepoPath, StatusCode)>>>;
+
     /// Returns the absolute path to the repository. For worktrees, this will be the path to the
     /// worktree's gitdir within the main repository (typically `.git/worktrees/<name>`).
     fn path(&self) -> PathBuf;
@@ -474,6 +650,17 @@ pub trait GitRepository: Send + Sync {
         cx: AsyncApp,
     ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
 
+    fn push_tag(
+        &self,
+        name: String,
+        remote_name: String,
+        askpass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        // This method takes an AsyncApp to ensure it's invoked on the main thread,
+        // otherwise git-credentials-manager won't work.
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
+
     fn pull(
         &self,
         branch_name: String,
@@ -495,6 +682,36 @@ pub trait GitRepository: Send + Sync {
         cx: AsyncApp,
     ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
 
+    fn run_submodule_operation(
+        &self,
+        operation: SubmoduleOperation,
+        askpass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        // This method takes an AsyncApp to ensure it's invoked on the main thread,
+        // otherwise git-credentials-manager won't work.
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
+
+    /// Runs a step of an interactive rebase, using the given delegate to edit the todo list and
+    /// the commit messages git asks for.
+    fn rebase(
+        &self,
+        operation: RebaseOperation,
+        editor: GitEditorDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
+
+    /// Runs a step of a bisect, returning what git reported about the commit to test next, or
+    /// about the first bad commit once it's found.
+    fn bisect(
+        &self,
+        operation: BisectOperation,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<String>>;
+    /// Returns the log of the bisect in progress, as printed by `git bisect log`.
+    fn bisect_log(&self) -> BoxFuture<'_, Result<String>>;
+
     fn get_remotes(&self, branch_name: Option<String>) -> BoxFuture<'_, Result<Vec<Remote>>>;
 
     /// returns a list of remote branches that contain HEAD
@@ -807,6 +1024,7 @@ impl GitRepository for RealGitRepository {
             let mode_flag = match mode {
                 ResetMode::Mixed => "--mixed",
                 ResetMode::Soft => "--soft",
+                ResetMode::Hard => "--hard",
             };
 
             let output = new_smol_command(&self.git_binary_path)
@@ -825,6 +1043,267 @@ impl GitRepository for RealGitRepository {
         .boxed()
     }
 
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                // Annotated tags are objects of their own, so the commit they point at is
+                // `*objectname` instead of `objectname`.
+                let fields = [
+                    "%(refname:strip=2)",
+                    "%(objecttype)",
+                    "%(objectname)",
+                    "%(*objectname)",
+                    "%(creatordate:unix)",
+                    "%(contents:subject)",
+                ]
+                .join("%00");
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args([
+                        "for-each-ref",
+                        "refs/tags",
+                        "--sort=-creatordate",
+                        "--format",
+                    ])
+                    .arg(&fields)
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to list git tags:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                parse_tag_input(&String::from_utf8_lossy(&output.stdout))
+            })
+            .boxed()
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        commit: String,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        async move {
+            let mut command = new_smol_command(&git_binary_path);
+            command
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .arg("tag");
+            if let Some(message) = message {
+                command.args(["--annotate", "--message", &message]);
+            }
+            let output = command.args([&name, &commit]).output().await?;
+            anyhow::ensure!(
+                output.status.success(),
+                "Failed to create tag:\n{}",
+                String::from_utf8_lossy(&output.stderr),
+            );
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn delete_tag(
+        &self,
+        name: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        async move {
+            let output = new_smol_command(&git_binary_path)
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .args(["tag", "--delete", &name])
+                .output()
+                .await?;
+            anyhow::ensure!(
+                output.status.success(),
+                "Failed to delete tag:\n{}",
+                String::from_utf8_lossy(&output.stderr),
+            );
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn worktrees(&self) -> BoxFuture<'_, Result<Vec<GitWorktree>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args(["worktree", "list", "--porcelain"])
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to list git worktrees:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(parse_worktree_input(&String::from_utf8_lossy(
+                    &output.stdout,
+                )))
+            })
+            .boxed()
+    }
+
+    fn create_worktree(
+        &self,
+        path: PathBuf,
+        commit: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        async move {
+            let output = new_smol_command(&git_binary_path)
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .args(["worktree", "add"])
+                .arg(&path)
+                .args(commit)
+                .output()
+                .await?;
+            anyhow::ensure!(
+                output.status.success(),
+                "Failed to create worktree:\n{}",
+                String::from_utf8_lossy(&output.stderr),
+            );
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn remove_worktree(
+        &self,
+        path: PathBuf,
+        force: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        async move {
+            let output = new_smol_command(&git_binary_path)
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .args(["worktree", "remove"])
+                .args(force.then_some("--force"))
+                .arg(&path)
+                .output()
+                .await?;
+            anyhow::ensure!(
+                output.status.success(),
+                "Failed to remove worktree:\n{}",
+                String::from_utf8_lossy(&output.stderr),
+            );
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>> {
+        let repo = self.repository.clone();
+        self.executor
+            .spawn(async move {
+                let repo = repo.lock();
+                let mut submodules = Vec::new();
+                for submodule in repo.submodules()? {
+                    let Some(name) = submodule.name() else {
+                        continue;
+                    };
+                    // Git stores submodule paths with forward slashes on every platform.
+                    let Some(path) = submodule
+                        .path()
+                        .to_str()
+                        .and_then(|path| RepoPath::new(path).log_err())
+                    else {
+                        continue;
+                    };
+                    // Looking inside the submodule's working tree is as slow as a status of it,
+                    // and the superproject's status already tells whether it has changes.
+                    let status = repo.submodule_status(name, git2::SubmoduleIgnore::Dirty)?;
+                    let checked_out_sha =
+                        if status.contains(git2::SubmoduleStatus::WD_UNINITIALIZED) {
+                            None
+                        } else {
+                            submodule.workdir_id()
+                        };
+                    submodules.push(Submodule {
+                        path,
+                        name: SharedString::from(name.to_string()),
+                        url: submodule
+                            .url()
+                            .map(|url| SharedString::from(url.to_string())),
+                        recorded_sha: submodule
+                            .index_id()
+                            .map(|oid| SharedString::from(oid.to_string())),
+                        checked_out_sha: checked_out_sha
+                            .map(|oid| SharedString::from(oid.to_string())),
+                        is_dirty: false,
+                    });
+                }
+                submodules.sort_by(|a, b| a.path.cmp(&b.path));
+                Ok(submodules)
+            })
+            .boxed()
+    }
+
+    fn cherry_pick(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        async move {
+            let output = new_smol_command(&git_binary_path)
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .args(["cherry-pick", &commit])
+                .output()
+                .await?;
+            anyhow::ensure!(
+                output.status.success(),
+                "Failed to cherry-pick:\n{}",
+                String::from_utf8_lossy(&output.stderr),
+            );
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn revert(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        async move {
+            let output = new_smol_command(&git_binary_path)
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .args(["revert", "--no-edit", &commit])
+                .output()
+                .await?;
+            anyhow::ensure!(
+                output.status.success(),
+                "Failed to revert:\n{}",
+                String::from_utf8_lossy(&output.stderr),
+            );
+            Ok(())
+        }
+        .boxed()
+    }
+
     fn checkout_files(
         &self,
         commit: String,
@@ -902,6 +1381,28 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn load_text_at_commit(&self, commit: Oid, path: RepoPath) -> BoxFuture<'_, Option<String>> {
+        let repo = self.repository.clone();
+        self.executor
+            .spawn(async move {
+                let repo = repo.lock();
+                let oid = git2::Oid::from_bytes(commit.as_bytes()).log_err()?;
+                // Annotated tags are peeled to the commit they point at.
+                let tree = repo
+                    .find_object(oid, None)
+                    .log_err()?
+                    .peel_to_tree()
+                    .log_err()?;
+                let entry = tree.get_path(path.as_std_path()).ok()?;
+                if entry.filemode() == i32::from(git2::FileMode::Link) {
+                    return None;
+                }
+                let content = repo.find_blob(entry.id()).log_err()?.content().to_owned();
+                String::from_utf8(content).ok()
+            })
+            .boxed()
+    }
+
     fn set_index_text(
         &self,
         path: RepoPath,
@@ -1029,6 +1530,62 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn rebase_progress(&self) -> BoxFuture<'_, Option<RebaseProgress>> {
+        let path = self.path();
+        self.executor
+            .spawn(async move { RebaseProgress::load(&path).log_err().flatten() })
+            .boxed()
+    }
+
+    fn bisect_status(&self) -> BoxFuture<'_, Result<Option<BisectStatus>>> {
+        let path = self.path();
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                if !path.join(BISECT_START_FILE_NAME).exists() {
+                    return Ok(None);
+                }
+                let working_directory = working_directory?;
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory)
+                    .args([
+                        "for-each-ref",
+                        "--format=%(refname) %(objectname)",
+                        "refs/bisect/",
+                    ])
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to list bisect refs:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                let mut status = BisectStatus::parse_refs(&String::from_utf8_lossy(&output.stdout));
+
+                if let Some(bad) = &status.bad
+                    && !status.good.is_empty()
+                {
+                    let output = new_smol_command(&git_binary_path)
+                        .current_dir(&working_directory)
+                        .args(["rev-list", "--bisect-vars", bad.as_ref(), "--not"])
+                        .args(status.good.iter().map(|sha| sha.as_ref()))
+                        .output()
+                        .await?;
+                    anyhow::ensure!(
+                        output.status.success(),
+                        "Failed to compute bisect progress:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    );
+                    status.progress = Some(BisectProgress::parse(&String::from_utf8_lossy(
+                        &output.stdout,
+                    ))?);
+                }
+                Ok(Some(status))
+            })
+            .boxed()
+    }
+
     fn status(&self, path_prefixes: &[RepoPath]) -> Task<Result<GitStatus>> {
         let git_binary_path = self.git_binary_path.clone();
         let working_directory = match self.working_directory() {
@@ -1053,6 +1610,27 @@ impl GitRepository for RealGitRepository {
         })
     }
 
+    fn log(&self, options: LogOptions) -> BoxFuture<'_, Result<Vec<LogEntry>>> {
+        let git_binary_path = self.git_binary_path.clone();
+        let working_directory = self.working_directory();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(working_directory?)
+                    .args(["--no-optional-locks", "log", LOG_FORMAT])
+                    .args(options.to_args())
+                    .output()
+                    .await?;
+                if output.status.success() {
+                    parse_log(&String::from_utf8_lossy(&output.stdout))
+                } else {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    anyhow::bail!("git log failed: {stderr}");
+                }
+            })
+            .boxed()
+    }
+
     fn stash_entries(&self) -> BoxFuture<'_, Result<GitStash>> {
         let git_binary_path = self.git_binary_path.clone();
         let working_directory = self.working_directory();
@@ -1229,6 +1807,103 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn file_history(
+        &self,
+        path: RepoPath,
+        rows: Option<Range<u32>>,
+    ) -> BoxFuture<'_, Result<Vec<FileHistoryEntry>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(working_directory?)
+                    .args([
+                        "--no-optional-locks",
+                        "-c",
+                        "core.quotePath=false",
+                        "log",
+                        FILE_HISTORY_FORMAT,
+                    ])
+                    .args(file_history_args(&path, rows))
+                    .output()
+                    .await?;
+                if output.status.success() {
+                    parse_file_history(&String::from_utf8_lossy(&output.stdout), &path)
+                } else {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    anyhow::bail!("git log failed: {stderr}");
+                }
+            })
+            .boxed()
+    }
+
+    fn merge_base(&self, first: String, second: String) -> BoxFuture<'_, Result<Option<Oid>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args(["--no-optional-locks", "merge-base"])
+                    .args([&first, &second])
+                    .output()
+                    .await?;
+                // git exits with 1, without an error message, when the commits have no common
+                // ancestor.
+                if output.status.code() == Some(1) && output.stderr.is_empty() {
+                    return Ok(None);
+                }
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to find the merge base of {first} and {second}:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(Some(
+                    String::from_utf8_lossy(&output.stdout).trim().parse()?,
+                ))
+            })
+            .boxed()
+    }
+
+    fn changed_paths_since(
+        &self,
+        commit: Oid,
+    ) -> BoxFuture<'_, Result<Vec<(RepoPath, StatusCode)>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args([
+                        "--no-optional-locks",
+                        "diff",
+                        "-z",
+                        "--no-renames",
+                        "--name-status",
+                    ])
+                    .arg(commit.to_string())
+                    .arg("HEAD")
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to list the changes since {commit}:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                Ok(parse_git_diff_name_status(&stdout)
+                    .filter_map(|(path, status)| {
+                        // git-diff outputs `/`-delimited paths even on Windows.
+                        let path = RelPath::unix(path).log_err()?;
+                        Some((path.into(), status))
+                    })
+                    .collect())
+            })
+            .boxed()
+    }
+
     fn diff(&self, diff: DiffType) -> BoxFuture<'_, Result<String>> {
         let working_directory = self.working_directory();
         let git_binary_path = self.git_binary_path.clone();
@@ -1503,6 +2178,33 @@ impl GitRepository for RealGitRepository {
         .boxed()
     }
 
+    fn push_tag(
+        &self,
+        name: String,
+        remote_name: String,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let executor = cx.background_executor().clone();
+        async move {
+            let mut command = new_smol_command("git");
+            command
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .args(["push"])
+                .arg(remote_name)
+                .arg(format!("refs/tags/{name}:refs/tags/{name}"))
+                .stdin(smol::process::Stdio::null())
+                .stdout(smol::process::Stdio::piped())
+                .stderr(smol::process::Stdio::piped());
+
+            run_git_command(env, ask_pass, command, &executor).await
+        }
+        .boxed()
+    }
+
     fn pull(
         &self,
         branch_name: String,
@@ -1529,30 +2231,135 @@ impl GitRepository for RealGitRepository {
         .boxed()
     }
 
-    fn fetch(
+    fn fetch(
+        &self,
+        fetch_options: FetchOptions,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let remote_name = format!("{}", fetch_options);
+        let executor = cx.background_executor().clone();
+        async move {
+            let mut command = new_smol_command("git");
+            command
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .args(["fetch", &remote_name])
+                .stdout(smol::process::Stdio::piped())
+                .stderr(smol::process::Stdio::piped());
+
+            run_git_command(env, ask_pass, command, &executor).await
+        }
+        .boxed()
+    }
+
+    fn run_submodule_operation(
+        &self,
+        operation: SubmoduleOperation,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let executor = cx.background_executor().clone();
+        async move {
+            let mut command = new_smol_command("git");
+            command
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .args(operation.args())
+                .stdin(smol::process::Stdio::null())
+                .stdout(smol::process::Stdio::piped())
+                .stderr(smol::process::Stdio::piped());
+
+            run_git_command(env, ask_pass, command, &executor).await
+        }
+        .boxed()
+    }
+
+    fn rebase(
         &self,
-        fetch_options: FetchOptions,
-        ask_pass: AskPassDelegate,
+        operation: RebaseOperation,
+        editor: GitEditorDelegate,
         env: Arc<HashMap<String, String>>,
         cx: AsyncApp,
     ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
         let working_directory = self.working_directory();
-        let remote_name = format!("{}", fetch_options);
+        let git_binary_path = self.git_binary_path.clone();
         let executor = cx.background_executor().clone();
         async move {
-            let mut command = new_smol_command("git");
-            command
+            let editor = GitEditorSession::new(&executor, editor).await?;
+            let output = new_smol_command(&git_binary_path)
                 .envs(env.iter())
+                .env("GIT_SEQUENCE_EDITOR", editor.editor_command())
+                .env("GIT_EDITOR", editor.editor_command())
                 .current_dir(&working_directory?)
-                .args(["fetch", &remote_name])
-                .stdout(smol::process::Stdio::piped())
-                .stderr(smol::process::Stdio::piped());
+                .args(operation.args())
+                .stdin(smol::process::Stdio::null())
+                .output()
+                .await?;
+            drop(editor);
 
-            run_git_command(env, ask_pass, command, &executor).await
+            anyhow::ensure!(
+                output.status.success(),
+                "{}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+            Ok(RemoteCommandOutput {
+                stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            })
+        }
+        .boxed()
+    }
+
+    fn bisect(
+        &self,
+        operation: BisectOperation,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<String>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        async move {
+            let output = new_smol_command(&git_binary_path)
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .args(operation.args())
+                .output()
+                .await?;
+            anyhow::ensure!(
+                output.status.success(),
+                "Failed to run git {}:\n{}",
+                operation.name(),
+                String::from_utf8_lossy(&output.stderr),
+            );
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
         }
         .boxed()
     }
 
+    fn bisect_log(&self) -> BoxFuture<'_, Result<String>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args(["bisect", "log"])
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to read the bisect log:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+            })
+            .boxed()
+    }
+
     fn get_remotes(&self, branch_name: Option<String>) -> BoxFuture<'_, Result<Vec<Remote>>> {
         let working_directory = self.working_directory();
         let git_binary_path = self.git_binary_path.clone();
@@ -2206,6 +3013,68 @@ fn parse_branch_input(input: &str) -> Result<Vec<Branch>> {
     Ok(branches)
 }
 
+/// Parses the output of `git worktree list --porcelain`, which has a paragraph per worktree.
+fn parse_worktree_input(input: &str) -> Vec<GitWorktree> {
+    let mut worktrees = Vec::new();
+    for paragraph in input.split("\n\n") {
+        let mut lines = paragraph.lines();
+        let Some(path) = lines.next().and_then(|line| line.strip_prefix("worktree ")) else {
+            continue;
+        };
+        let mut worktree = GitWorktree {
+            path: PathBuf::from(path),
+            sha: None,
+            branch: None,
+            // The main worktree is always listed first.
+            is_main: worktrees.is_empty(),
+            is_locked: false,
+            is_prunable: false,
+        };
+        for line in lines {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            match key {
+                "HEAD" => worktree.sha = Some(value.to_string().into()),
+                "branch" => {
+                    let branch = value.strip_prefix("refs/heads/").unwrap_or(value);
+                    worktree.branch = Some(branch.to_string().into());
+                }
+                "locked" => worktree.is_locked = true,
+                "prunable" => worktree.is_prunable = true,
+                _ => {}
+            }
+        }
+        worktrees.push(worktree);
+    }
+    worktrees
+}
+
+fn parse_tag_input(input: &str) -> Result<Vec<Tag>> {
+    let mut tags = Vec::new();
+    for line in input.split('\n') {
+        if line.is_empty() {
+            continue;
+        }
+        let mut fields = line.split('\x00');
+        let name = fields.next().context("no refname")?.to_string().into();
+        let is_annotated = fields.next().context("no objecttype")? == "tag";
+        let object_sha = fields.next().context("no objectname")?;
+        let peeled_sha = fields.next().context("no *objectname")?;
+        let timestamp = fields.next().context("no creatordate")?.parse::<i64>()?;
+        let subject = fields.next().context("no contents:subject")?;
+
+        tags.push(Tag {
+            name,
+            sha: if is_annotated { peeled_sha } else { object_sha }
+                .to_string()
+                .into(),
+            message: is_annotated.then(|| subject.to_string().into()),
+            timestamp,
+        });
+    }
+
+    Ok(tags)
+}
+
 fn parse_upstream_track(upstream_track: &str) -> Result<UpstreamTracking> {
     if upstream_track.is_empty() {
         return Ok(UpstreamTracking::Tracked(UpstreamTrackingStatus {
@@ -2458,6 +3327,160 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_log(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+
+        for (path, content, message, author) in [
+            ("a", "1", "Add a", "Alice"),
+            ("b", "1", "Add b", "Bob"),
+            ("a", "2", "Change a", "Bob"),
+        ] {
+            smol::fs::write(repo_dir.path().join(path), content)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path(path)], Arc::new(HashMap::default()))
+                .await
+                .unwrap();
+            repo.commit(
+                message.into(),
+                Some((author.into(), format!("{author}@example.com").into())),
+                CommitOptions::default(),
+                Arc::new(checkpoint_author_envs()),
+            )
+            .await
+            .unwrap();
+        }
+
+        let subjects = |entries: Vec<LogEntry>| {
+            entries
+                .into_iter()
+                .map(|entry| entry.subject.to_string())
+                .collect::<Vec<_>>()
+        };
+        let options = LogOptions {
+            limit: 10,
+            ..Default::default()
+        };
+
+        let entries = repo.log(options.clone()).await.unwrap();
+        assert_eq!(entries[0].parents, [entries[1].sha]);
+        assert_eq!(
+            entries[0].refs.first().map(|name| name.as_ref()),
+            Some("HEAD")
+        );
+        assert!(entries[2].parents.is_empty());
+        assert_eq!(subjects(entries), ["Change a", "Add b", "Add a"]);
+
+        let page = LogOptions {
+            skip: 1,
+            limit: 1,
+            ..options.clone()
+        };
+        assert_eq!(subjects(repo.log(page).await.unwrap()), ["Add b"]);
+
+        let by_author = LogOptions {
+            author: Some("bob".into()),
+            ..options.clone()
+        };
+        assert_eq!(
+            subjects(repo.log(by_author).await.unwrap()),
+            ["Change a", "Add b"]
+        );
+
+        let by_message = LogOptions {
+            message: Some("CHANGE".into()),
+            ..options.clone()
+        };
+        assert_eq!(subjects(repo.log(by_message).await.unwrap()), ["Change a"]);
+
+        let by_path = LogOptions {
+            path: Some(repo_path("a")),
+            ..options
+        };
+        let entries = repo.log(by_path).await.unwrap();
+        // Parents are rewritten to skip the commits that don't touch the path.
+        assert_eq!(entries[0].parents, [entries[1].sha]);
+        assert_eq!(subjects(entries), ["Change a", "Add a"]);
+    }
+
+    #[gpui::test]
+    async fn test_file_history(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+
+        let commit = |message: &'static str| {
+            repo.commit(
+                message.into(),
+                None,
+                CommitOptions::default(),
+                Arc::new(checkpoint_author_envs()),
+            )
+        };
+        let stage = |paths: &[&str]| {
+            repo.stage_paths(
+                paths.iter().map(|path| repo_path(path)).collect(),
+                Arc::new(HashMap::default()),
+            )
+        };
+
+        smol::fs::write(repo_dir.path().join("old"), "one\ntwo\nthree\n")
+            .await
+            .unwrap();
+        stage(&["old"]).await.unwrap();
+        commit("Add old").await.unwrap();
+
+        smol::fs::rename(repo_dir.path().join("old"), repo_dir.path().join("new"))
+            .await
+            .unwrap();
+        stage(&["old", "new"]).await.unwrap();
+        commit("Rename old to new").await.unwrap();
+
+        smol::fs::write(repo_dir.path().join("new"), "one\ntwo\nTHREE\n")
+            .await
+            .unwrap();
+        stage(&["new"]).await.unwrap();
+        commit("Change the third line").await.unwrap();
+
+        let subjects = |entries: &[FileHistoryEntry]| {
+            entries
+                .iter()
+                .map(|entry| entry.commit.subject.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let entries = repo.file_history(repo_path("new"), None).await.unwrap();
+        assert_eq!(
+            subjects(&entries),
+            ["Change the third line", "Rename old to new", "Add old"]
+        );
+        assert_eq!(entries[1].path, repo_path("new"));
+        assert_eq!(entries[1].old_path, repo_path("old"));
+        assert_eq!(entries[2].path, repo_path("old"));
+
+        let entries = repo
+            .file_history(repo_path("new"), Some(2..3))
+            .await
+            .unwrap();
+        assert_eq!(subjects(&entries), ["Change the third line", "Add old"]);
+
+        let entries = repo
+            .file_history(repo_path("new"), Some(0..2))
+            .await
+            .unwrap();
+        assert_eq!(subjects(&entries), ["Add old"]);
+        assert_eq!(entries[0].path, repo_path("old"));
+    }
+
     #[test]
     fn test_branches_parsing() {
         // suppress "help: octal escapes are not supported, `\0` is always null"
@@ -2486,6 +3509,286 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_tags_parsing() {
+        #[allow(clippy::octal_escapes)]
+        let input = "v1.1\0tag\0d3f1bd2bbbd5bf5b7c4e0bcbc5b3c2c5f0b2c7e1\0060964da10574cd9bf06463a53bf6e0769c5c45e\01733187480\0Release 1.1\n\
+                     v1.0\0commit\0a5e4b1c23bbd4f2b80b5f0f7d7bf21b3e9f6e0aa\0\01733187470\0generated protobuf\n";
+        assert_eq!(
+            parse_tag_input(input).unwrap(),
+            vec![
+                Tag {
+                    name: "v1.1".into(),
+                    sha: "060964da10574cd9bf06463a53bf6e0769c5c45e".into(),
+                    message: Some("Release 1.1".into()),
+                    timestamp: 1733187480,
+                },
+                Tag {
+                    name: "v1.0".into(),
+                    sha: "a5e4b1c23bbd4f2b80b5f0f7d7bf21b3e9f6e0aa".into(),
+                    message: None,
+                    timestamp: 1733187470,
+                },
+            ]
+        )
+    }
+
+    #[test]
+    fn test_worktrees_parsing() {
+        let input = "\
+            worktree /home/me/zed\n\
+            HEAD 060964da10574cd9bf06463a53bf6e0769c5c45e\n\
+            branch refs/heads/main\n\
+            \n\
+            worktree /home/me/zed-feature\n\
+            HEAD d3f1bd2bbbd5bf5b7c4e0bcbc5b3c2c5f0b2c7e1\n\
+            detached\n\
+            locked checked out by an agent\n\
+            \n\
+            worktree /tmp/gone\n\
+            HEAD a5e4b1c23bbd4f2b80b5f0f7d7bf21b3e9f6e0aa\n\
+            branch refs/heads/gone\n\
+            prunable gitdir file points to non-existent location\n\
+            \n";
+        assert_eq!(
+            parse_worktree_input(input),
+            vec![
+                GitWorktree {
+                    path: PathBuf::from("/home/me/zed"),
+                    sha: Some("060964da10574cd9bf06463a53bf6e0769c5c45e".into()),
+                    branch: Some("main".into()),
+                    is_main: true,
+                    is_locked: false,
+                    is_prunable: false,
+                },
+                GitWorktree {
+                    path: PathBuf::from("/home/me/zed-feature"),
+                    sha: Some("d3f1bd2bbbd5bf5b7c4e0bcbc5b3c2c5f0b2c7e1".into()),
+                    branch: None,
+                    is_main: false,
+                    is_locked: true,
+                    is_prunable: false,
+                },
+                GitWorktree {
+                    path: PathBuf::from("/tmp/gone"),
+                    sha: Some("a5e4b1c23bbd4f2b80b5f0f7d7bf21b3e9f6e0aa".into()),
+                    branch: Some("gone".into()),
+                    is_main: false,
+                    is_locked: false,
+                    is_prunable: true,
+                },
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_worktrees(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repo_dir = temp_dir.path().join("repo");
+        std::fs::create_dir(&repo_dir).unwrap();
+        git2::Repository::init(&repo_dir).unwrap();
+        let repo = RealGitRepository::new(&repo_dir.join(".git"), None, cx.executor()).unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        smol::fs::write(repo_dir.join("a"), "1").await.unwrap();
+        repo.stage_paths(vec![repo_path("a")], Arc::new(HashMap::default()))
+            .await
+            .unwrap();
+        repo.commit("Add a".into(), None, CommitOptions::default(), env.clone())
+            .await
+            .unwrap();
+        let head = repo.head_sha().await.unwrap();
+
+        let feature_dir = temp_dir.path().join("feature");
+        repo.create_worktree(feature_dir.clone(), None, env.clone())
+            .await
+            .unwrap();
+        let detached_dir = temp_dir.path().join("detached");
+        repo.create_worktree(detached_dir.clone(), Some(head.clone()), env.clone())
+            .await
+            .unwrap();
+
+        let worktrees = repo.worktrees().await.unwrap();
+        let canonical = |path: &Path| std::fs::canonicalize(path).unwrap();
+        assert_eq!(worktrees.len(), 3);
+        assert!(worktrees[0].is_main);
+        assert_eq!(canonical(&worktrees[0].path), canonical(&repo_dir));
+        let feature = worktrees
+            .iter()
+            .find(|worktree| canonical(&worktree.path) == canonical(&feature_dir))
+            .unwrap();
+        assert!(!feature.is_main);
+        assert_eq!(feature.branch.as_deref(), Some("feature"));
+        assert_eq!(feature.sha.as_deref(), Some(head.as_str()));
+        let detached = worktrees
+            .iter()
+            .find(|worktree| canonical(&worktree.path) == canonical(&detached_dir))
+            .unwrap();
+        assert_eq!(detached.branch, None);
+
+        // A linked worktree's `.git` is a file pointing at a directory inside the common one.
+        let linked_repo =
+            RealGitRepository::new(&feature_dir.join(".git"), None, cx.executor()).unwrap();
+        assert_eq!(
+            canonical(&linked_repo.main_repository_path()),
+            canonical(&repo_dir.join(".git"))
+        );
+
+        repo.remove_worktree(detached_dir.clone(), false, env.clone())
+            .await
+            .unwrap();
+        smol::fs::write(feature_dir.join("a"), "2").await.unwrap();
+        assert!(
+            repo.remove_worktree(feature_dir.clone(), false, env.clone())
+                .await
+                .is_err()
+        );
+        repo.remove_worktree(feature_dir.clone(), true, env.clone())
+            .await
+            .unwrap();
+        assert_eq!(repo.worktrees().await.unwrap().len(), 1);
+        assert!(!detached_dir.exists());
+        assert!(!feature_dir.exists());
+    }
+
+    #[gpui::test]
+    async fn test_changes_since_merge_base(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        smol::fs::write(repo_dir.path().join("a"), "1")
+            .await
+            .unwrap();
+        smol::fs::write(repo_dir.path().join("b"), "1")
+            .await
+            .unwrap();
+        repo.stage_paths(
+            vec![repo_path("a"), repo_path("b")],
+            Arc::new(HashMap::default()),
+        )
+        .await
+        .unwrap();
+        repo.commit(
+            "Initial".into(),
+            None,
+            CommitOptions::default(),
+            env.clone(),
+        )
+        .await
+        .unwrap();
+        let base: Oid = repo.head_sha().await.unwrap().parse().unwrap();
+        repo.create_branch("base".into()).await.unwrap();
+
+        smol::fs::write(repo_dir.path().join("a"), "2")
+            .await
+            .unwrap();
+        smol::fs::remove_file(repo_dir.path().join("b"))
+            .await
+            .unwrap();
+        smol::fs::write(repo_dir.path().join("c"), "1")
+            .await
+            .unwrap();
+        repo.stage_paths(
+            vec![repo_path("a"), repo_path("b"), repo_path("c")],
+            Arc::new(HashMap::default()),
+        )
+        .await
+        .unwrap();
+        repo.commit("Change".into(), None, CommitOptions::default(), env.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            repo.merge_base("base".into(), "HEAD".into()).await.unwrap(),
+            Some(base)
+        );
+        assert!(
+            repo.merge_base("base".into(), "missing".into())
+                .await
+                .is_err()
+        );
+
+        let mut changes = repo.changed_paths_since(base).await.unwrap();
+        changes.sort_by(|(a, _), (b, _)| a.cmp(b));
+        assert_eq!(
+            changes,
+            [
+                (repo_path("a"), StatusCode::Modified),
+                (repo_path("b"), StatusCode::Deleted),
+                (repo_path("c"), StatusCode::Added),
+            ]
+        );
+
+        assert_eq!(
+            repo.load_text_at_commit(base, repo_path("a")).await,
+            Some("1".to_string())
+        );
+        assert_eq!(
+            repo.load_text_at_commit(base, repo_path("b")).await,
+            Some("1".to_string())
+        );
+        assert_eq!(repo.load_text_at_commit(base, repo_path("c")).await, None);
+    }
+
+    #[gpui::test]
+    async fn test_tags(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        smol::fs::write(repo_dir.path().join("a"), "1")
+            .await
+            .unwrap();
+        repo.stage_paths(vec![repo_path("a")], Arc::new(HashMap::default()))
+            .await
+            .unwrap();
+        repo.commit("Add a".into(), None, CommitOptions::default(), env.clone())
+            .await
+            .unwrap();
+        let head = repo.head_sha().await.unwrap();
+
+        repo.create_tag("light".into(), "HEAD".into(), None, env.clone())
+            .await
+            .unwrap();
+        repo.create_tag(
+            "annotated".into(),
+            head.clone(),
+            Some("Release notes".into()),
+            env.clone(),
+        )
+        .await
+        .unwrap();
+
+        let mut tags = repo.tags().await.unwrap();
+        tags.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].name.as_ref(), "annotated");
+        assert_eq!(tags[0].sha.as_ref(), head);
+        assert_eq!(
+            tags[0].message.as_ref().map(|message| message.as_ref()),
+            Some("Release notes")
+        );
+        assert_eq!(tags[1].name.as_ref(), "light");
+        assert_eq!(tags[1].sha.as_ref(), head);
+        assert_eq!(tags[1].message, None);
+
+        repo.delete_tag("light".into(), env.clone()).await.unwrap();
+        let tags = repo.tags().await.unwrap();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].name.as_ref(), "annotated");
+    }
+
     impl RealGitRepository {
         /// Force a Git garbage collection on the repository.
         fn gc(&self) -> BoxFuture<'_, Result<()>> {
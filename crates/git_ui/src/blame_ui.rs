@@ -1,6 +1,7 @@
 use crate::{
     commit_tooltip::{CommitAvatar, CommitDetails, CommitTooltip},
     commit_view::CommitView,
+    git_panel::commit_operations_menu,
 };
 use editor::{BlameRenderer, Editor, hover_markdown_style};
 use git::{
@@ -71,10 +72,12 @@ impl BlameRenderer for GitBlameRenderer {
                         .on_mouse_down(MouseButton::Right, {
                             let blame_entry = blame_entry.clone();
                             let details = details.clone();
+                            let workspace = workspace.clone();
                             move |event, window, cx| {
                                 deploy_blame_entry_context_menu(
                                     &blame_entry,
                                     details.as_ref(),
+                                    workspace.clone(),
                                     editor.clone(),
                                     event.position,
                                     window,
@@ -404,16 +407,21 @@ impl BlameRenderer for GitBlameRenderer {
 fn deploy_blame_entry_context_menu(
     blame_entry: &BlameEntry,
     details: Option<&ParsedCommitMessage>,
+    workspace: WeakEntity<Workspace>,
     editor: Entity<Editor>,
     position: gpui::Point<Pixels>,
     window: &mut Window,
     cx: &mut App,
 ) {
-    let context_menu = ContextMenu::build(window, cx, move |menu, _, _| {
+    let context_menu = ContextMenu::build(window, cx, move |menu, _, cx| {
         let sha = format!("{}", blame_entry.sha);
-        menu.on_blur_subscription(Subscription::new(|| {}))
-            .entry("Copy commit SHA", None, move |_, cx| {
-                cx.write_to_clipboard(ClipboardItem::new_string(sha.clone()));
+        let menu = menu
+            .on_blur_subscription(Subscription::new(|| {}))
+            .entry("Copy commit SHA", None, {
+                let sha = sha.clone();
+                move |_, cx| {
+                    cx.write_to_clipboard(ClipboardItem::new_string(sha.clone()));
+                }
             })
             .when_some(
                 details.and_then(|details| details.permalink.clone()),
@@ -423,6 +431,8 @@ fn deploy_blame_entry_context_menu(
                     })
                 },
             )
+            .separator();
+        commit_operations_menu(menu, sha.into(), workspace, cx)
     });
 
     editor.update(cx, move |editor, cx| {
@@ -6,7 +6,7 @@ use git::repository::CommitSummary;
 use git::{GitRemote, blame::ParsedCommitMessage};
 use gpui::{
     App, Asset, ClipboardItem, Element, Entity, MouseButton, ParentElement, Render, ScrollHandle,
-    StatefulInteractiveElement, WeakEntity, prelude::*,
+    StatefulInteractiveElement, Task, WeakEntity, prelude::*,
 };
 use markdown::{Markdown, MarkdownElement};
 use project::git_store::Repository;
@@ -106,6 +106,9 @@ pub struct CommitTooltip {
     markdown: Entity<Markdown>,
     repository: Entity<Repository>,
     workspace: WeakEntity<Workspace>,
+    /// The names of the tags pointing at the commit.
+    tags: Vec<SharedString>,
+    _load_tags: Task<()>,
 }
 
 impl CommitTooltip {
@@ -157,12 +160,30 @@ impl CommitTooltip {
                 cx,
             )
         });
+        let tags = repository.update(cx, |repository, _| repository.tags());
+        let sha = commit.sha.clone();
+        let load_tags = cx.spawn(async move |this, cx| {
+            let Ok(Ok(tags)) = tags.await else {
+                return;
+            };
+            this.update(cx, |this, cx| {
+                this.tags = tags
+                    .into_iter()
+                    .filter(|tag| tag.sha == sha)
+                    .map(|tag| tag.name)
+                    .collect();
+                cx.notify();
+            })
+            .ok();
+        });
         Self {
             commit,
             repository,
             workspace,
             scroll_handle: ScrollHandle::new(),
             markdown,
+            tags: Vec::new(),
+            _load_tags: load_tags,
         }
     }
 }
@@ -208,6 +229,8 @@ impl Render for CommitTooltip {
             .as_ref()
             .and_then(|details| details.pull_request.clone());
 
+        let tags = self.tags.clone();
+
         let ui_font_size = ThemeSettings::get_global(cx).ui_font_size(cx);
         let message_max_height = window.line_height() * 12 + (ui_font_size / 0.4);
         let repo = self.repository.clone();
@@ -275,7 +298,18 @@ impl Render for CommitTooltip {
                                 .pt_1p5()
                                 .border_t_1()
                                 .border_color(cx.theme().colors().border_variant)
-                                .child(absolute_timestamp)
+                                .child(h_flex().gap_1p5().child(absolute_timestamp).children(
+                                    tags.into_iter().map(|tag| {
+                                        h_flex()
+                                            .gap_0p5()
+                                            .child(
+                                                Icon::new(IconName::Bookmark)
+                                                    .size(IconSize::Small)
+                                                    .color(Color::Muted),
+                                            )
+                                            .child(tag)
+                                    }),
+                                ))
                                 .child(
                                     h_flex()
                                         .gap_1p5()
@@ -1,3 +1,4 @@
+use crate::git_panel::commit_operations_menu;
 use anyhow::{Context as _, Result};
 use buffer_diff::{BufferDiff, BufferDiffSnapshot};
 use editor::{Editor, EditorEvent, MultiBuffer, SelectionEffects, multibuffer_context_lines};
@@ -18,7 +19,7 @@ use std::{
     path::PathBuf,
     sync::Arc,
 };
-use ui::{Color, Icon, IconName, Label, LabelCommon as _, SharedString};
+use ui::{Color, ContextMenu, Icon, IconName, Label, LabelCommon as _, SharedString};
 use util::{ResultExt, paths::PathStyle, rel_path::RelPath, truncate_and_trailoff};
 use workspace::{
     Item, ItemHandle as _, ItemNavHistory, ToolbarItemLocation, Workspace,
@@ -60,23 +61,36 @@ impl CommitView {
         let commit_details = repo
             .update(cx, |repo, _| repo.show(commit.sha.to_string()))
             .ok();
+        let tags = repo.update(cx, |repo, _| repo.tags()).ok();
 
         window
             .spawn(cx, async move |cx| {
-                let (commit_diff, commit_details) = futures::join!(commit_diff?, commit_details?);
+                let (commit_diff, commit_details, tags) =
+                    futures::join!(commit_diff?, commit_details?, tags?);
                 let commit_diff = commit_diff.log_err()?.log_err()?;
                 let commit_details = commit_details.log_err()?.log_err()?;
+                let tags = tags
+                    .log_err()
+                    .and_then(|tags| tags.log_err())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|tag| tag.sha == commit_details.sha)
+                    .map(|tag| tag.name)
+                    .collect::<Vec<_>>();
                 let repo = repo.upgrade()?;
 
                 workspace
                     .update_in(cx, |workspace, window, cx| {
                         let project = workspace.project();
+                        let workspace = cx.weak_entity();
                         let commit_view = cx.new(|cx| {
                             CommitView::new(
                                 commit_details,
+                                tags,
                                 commit_diff,
                                 repo,
                                 project.clone(),
+                                workspace,
                                 window,
                                 cx,
                             )
@@ -103,9 +117,11 @@ impl CommitView {
 
     fn new(
         commit: CommitDetails,
+        tags: Vec<SharedString>,
         commit_diff: CommitDiff,
         repository: Entity<Repository>,
         project: Entity<Project>,
+        workspace: WeakEntity<Workspace>,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
@@ -116,6 +132,19 @@ impl CommitView {
                 Editor::for_multibuffer(multibuffer.clone(), Some(project.clone()), window, cx);
             editor.disable_inline_diagnostics();
             editor.set_expand_all_diff_hunks(cx);
+            let sha = commit.sha.clone();
+            editor.set_custom_context_menu(move |editor, _, window, cx| {
+                let focus_handle = editor.focus_handle(cx);
+                let sha = sha.clone();
+                let workspace = workspace.clone();
+                Some(ContextMenu::build(window, cx, move |menu, _, cx| {
+                    let menu = menu
+                        .context(focus_handle)
+                        .action("Copy", Box::new(editor::actions::Copy))
+                        .separator();
+                    commit_operations_menu(menu, sha, workspace, cx)
+                }))
+            });
             editor
         });
 
@@ -138,7 +167,7 @@ impl CommitView {
                     0,
                     cx.entity_id().as_non_zero_u64().into(),
                     LineEnding::default(),
-                    format_commit(&commit).into(),
+                    format_commit(&commit, &tags).into(),
                 );
                 metadata_buffer_id = Some(buffer.remote_id());
                 Buffer::build(buffer, Some(file.clone()), Capability::ReadWrite)
@@ -369,9 +398,18 @@ async fn build_buffer_diff(
     })
 }
 
-fn format_commit(commit: &CommitDetails) -> String {
+fn format_commit(commit: &CommitDetails, tags: &[SharedString]) -> String {
     let mut result = String::new();
-    writeln!(&mut result, "commit {}", commit.sha).unwrap();
+    write!(&mut result, "commit {}", commit.sha).unwrap();
+    if !tags.is_empty() {
+        let tags = tags
+            .iter()
+            .map(|tag| format!("tag: {tag}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(&mut result, " ({tags})").unwrap();
+    }
+    result.push('\n');
     writeln!(
         &mut result,
         "Author: {} <{}>",
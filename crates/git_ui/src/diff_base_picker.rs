@@ -0,0 +1,405 @@
+use fuzzy::StringMatchCandidate;
+
+use gpui::{
+    Action, AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement, IntoElement, ParentElement, Render, SharedString, Styled, Subscription,
+    Task, WeakEntity, Window, rems,
+};
+use picker::{Picker, PickerDelegate};
+use project::git_store::Repository;
+use std::sync::Arc;
+use ui::{HighlightedLabel, KeyBinding, ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt;
+use workspace::{ModalView, Workspace};
+
+use crate::project_diff::{DiffAgainstBase, DiffBase, ProjectDiff};
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(open);
+}
+
+pub fn open(
+    workspace: &mut Workspace,
+    _: &DiffAgainstBase,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let repository = workspace.project().read(cx).active_repository(cx);
+    let workspace_handle = workspace.weak_handle();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        DiffBaseList::new(repository, workspace_handle, rems(34.), window, cx)
+    })
+}
+
+pub struct DiffBaseList {
+    width: Rems,
+    pub picker: Entity<Picker<DiffBaseListDelegate>>,
+    picker_focus_handle: FocusHandle,
+    _subscription: Subscription,
+}
+
+impl DiffBaseList {
+    fn new(
+        repository: Option<Entity<Repository>>,
+        workspace: WeakEntity<Workspace>,
+        width: Rems,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = DiffBaseListDelegate::new(repository, workspace, cx);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        let picker_focus_handle = picker.focus_handle(cx);
+        picker.update(cx, |picker, cx| {
+            picker.delegate.focus_handle = picker_focus_handle.clone();
+            picker.delegate.load_refs(window, cx);
+        });
+
+        let _subscription = cx.subscribe(&picker, |_, _, _, cx| {
+            cx.emit(DismissEvent);
+        });
+
+        Self {
+            picker,
+            picker_focus_handle,
+            width,
+            _subscription,
+        }
+    }
+}
+
+impl ModalView for DiffBaseList {}
+impl EventEmitter<DismissEvent> for DiffBaseList {}
+impl Focusable for DiffBaseList {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.picker_focus_handle.clone()
+    }
+}
+
+impl Render for DiffBaseList {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("DiffBaseList")
+            .w(self.width)
+            .child(self.picker.clone())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RefKind {
+    LocalBranch,
+    RemoteBranch,
+    Tag,
+    /// A revision typed in the query, such as a sha or `HEAD~3`.
+    Revision,
+}
+
+#[derive(Debug, Clone)]
+struct RefEntry {
+    name: SharedString,
+    kind: RefKind,
+}
+
+#[derive(Debug, Clone)]
+struct RefMatch {
+    entry: RefEntry,
+    positions: Vec<usize>,
+}
+
+pub struct DiffBaseListDelegate {
+    matches: Vec<RefMatch>,
+    all_refs: Option<Vec<RefEntry>>,
+    repo: Option<Entity<Repository>>,
+    workspace: WeakEntity<Workspace>,
+    selected_index: usize,
+    focus_handle: FocusHandle,
+}
+
+impl DiffBaseListDelegate {
+    fn new(
+        repo: Option<Entity<Repository>>,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<DiffBaseList>,
+    ) -> Self {
+        Self {
+            matches: vec![],
+            all_refs: None,
+            repo,
+            workspace,
+            selected_index: 0,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    /// Lists the branches and tags of the repository, with the default branch and its remote
+    /// counterparts first, as they're what changes are most often compared to.
+    fn load_refs(&self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let (branches, tags, default_branch) = repo.update(cx, |repo, _| {
+            (repo.branches(), repo.tags(), repo.default_branch())
+        });
+        cx.spawn_in(window, async move |picker, cx| {
+            let mut branches = branches.await??;
+            let tags = tags.await??;
+            let default_branch = default_branch.await?.log_err().flatten();
+
+            branches.retain(|branch| !branch.is_head);
+            branches.sort_by_key(|branch| {
+                let is_default = default_branch.as_ref().is_some_and(|default_branch| {
+                    let name = branch.name();
+                    name == default_branch.as_ref()
+                        || name
+                            .strip_suffix(default_branch.as_ref())
+                            .is_some_and(|remote| remote.ends_with('/'))
+                });
+                (
+                    !is_default,
+                    !branch.is_remote(),
+                    branch
+                        .most_recent_commit
+                        .as_ref()
+                        .map(|commit| -commit.commit_timestamp),
+                )
+            });
+            let refs = branches
+                .iter()
+                .map(|branch| RefEntry {
+                    name: branch.name().to_string().into(),
+                    kind: if branch.is_remote() {
+                        RefKind::RemoteBranch
+                    } else {
+                        RefKind::LocalBranch
+                    },
+                })
+                .chain(tags.into_iter().map(|tag| RefEntry {
+                    name: tag.name,
+                    kind: RefKind::Tag,
+                }))
+                .collect();
+
+            picker.update_in(cx, |picker, window, cx| {
+                picker.delegate.all_refs = Some(refs);
+                picker.refresh(window, cx);
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
+impl PickerDelegate for DiffBaseListDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Compare to a branch, tag or commit…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let Some(all_refs) = self.all_refs.clone() else {
+            return Task::ready(());
+        };
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let query = query.trim();
+            let mut matches: Vec<RefMatch> = if query.is_empty() {
+                all_refs
+                    .into_iter()
+                    .map(|entry| RefMatch {
+                        entry,
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            } else {
+                let candidates = all_refs
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, entry)| StringMatchCandidate::new(ix, &entry.name))
+                    .collect::<Vec<StringMatchCandidate>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    query,
+                    true,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .map(|candidate| RefMatch {
+                    entry: all_refs[candidate.candidate_id].clone(),
+                    positions: candidate.positions,
+                })
+                .collect()
+            };
+
+            if !query.is_empty()
+                && !matches
+                    .iter()
+                    .any(|ref_match| ref_match.entry.name.as_ref() == query)
+            {
+                matches.push(RefMatch {
+                    entry: RefEntry {
+                        name: query.to_string().into(),
+                        kind: RefKind::Revision,
+                    },
+                    positions: Vec::new(),
+                });
+            }
+
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    if delegate.matches.is_empty() {
+                        delegate.selected_index = 0;
+                    } else {
+                        delegate.selected_index =
+                            core::cmp::min(delegate.selected_index, delegate.matches.len() - 1);
+                    }
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(ref_match) = self.matches.get(self.selected_index()) else {
+            return;
+        };
+        let base = DiffBase {
+            name: ref_match.entry.name.clone(),
+            merge_base: !secondary,
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                ProjectDiff::deploy_with_base(workspace, base, window, cx);
+            })
+            .log_err();
+        cx.emit(DismissEvent);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let ref_match = &self.matches[ix];
+        let entry = &ref_match.entry;
+
+        let (icon, description) = match entry.kind {
+            RefKind::LocalBranch => (IconName::GitBranch, "Branch"),
+            RefKind::RemoteBranch => (IconName::GitBranch, "Remote branch"),
+            RefKind::Tag => (IconName::Bookmark, "Tag"),
+            RefKind::Revision => (IconName::Hash, "Revision"),
+        };
+
+        Some(
+            ListItem::new(SharedString::from(format!("diff-base-{ix}")))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot(Icon::new(icon).color(Color::Muted).size(IconSize::Small))
+                .child(
+                    h_flex()
+                        .w_full()
+                        .gap_2()
+                        .justify_between()
+                        .overflow_hidden()
+                        .child(
+                            HighlightedLabel::new(entry.name.clone(), ref_match.positions.clone())
+                                .truncate(),
+                        )
+                        .child(
+                            Label::new(description)
+                                .color(Color::Muted)
+                                .size(LabelSize::Small),
+                        ),
+                )
+                .tooltip(Tooltip::text(format!(
+                    "Show the changes since {} diverged from HEAD",
+                    entry.name
+                ))),
+        )
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No branches or tags found".into())
+    }
+
+    fn render_footer(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<AnyElement> {
+        self.matches.get(self.selected_index)?;
+        let focus_handle = self.focus_handle.clone();
+
+        Some(
+            h_flex()
+                .w_full()
+                .p_1p5()
+                .gap_0p5()
+                .justify_end()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Button::new("diff-against-ref", "Compare to Ref")
+                        .tooltip(Tooltip::text(
+                            "Include the changes made on it since the branches diverged",
+                        ))
+                        .key_binding(
+                            KeyBinding::for_action_in(
+                                &menu::SecondaryConfirm,
+                                &focus_handle,
+                                window,
+                                cx,
+                            )
+                            .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(menu::SecondaryConfirm.boxed_clone(), cx)
+                        }),
+                )
+                .child(
+                    Button::new("diff-against-merge-base", "Compare to Merge Base")
+                        .key_binding(
+                            KeyBinding::for_action_in(&menu::Confirm, &focus_handle, window, cx)
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(menu::Confirm.boxed_clone(), cx)
+                        }),
+                )
+                .into_any(),
+        )
+    }
+}
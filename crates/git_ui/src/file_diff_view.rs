@@ -29,6 +29,7 @@ pub struct FileDiffView {
     editor: Entity<Editor>,
     old_buffer: Entity<Buffer>,
     new_buffer: Entity<Buffer>,
+    old_title: Option<SharedString>,
     buffer_changes_tx: watch::Sender<()>,
     _recalculate_diff_task: Task<Result<()>>,
 }
@@ -122,6 +123,7 @@ impl FileDiffView {
             buffer_changes_tx,
             old_buffer,
             new_buffer,
+            old_title: None,
             _recalculate_diff_task: cx.spawn(async move |this, cx| {
                 while buffer_changes_rx.recv().await.is_ok() {
                     loop {
@@ -162,9 +164,15 @@ impl FileDiffView {
             }),
         }
     }
+
+    /// Overrides the title of the old side of the diff, for old buffers that don't have a file.
+    pub fn set_old_title(&mut self, title: impl Into<SharedString>, cx: &mut Context<Self>) {
+        self.old_title = Some(title.into());
+        cx.emit(EditorEvent::TitleChanged);
+    }
 }
 
-async fn build_buffer_diff(
+pub(crate) async fn build_buffer_diff(
     old_buffer: &Entity<Buffer>,
     new_buffer: &Entity<Buffer>,
     cx: &mut AsyncApp,
@@ -230,7 +238,10 @@ impl Item for FileDiffView {
                 })
                 .unwrap_or_else(|| "untitled".into())
         };
-        let old_filename = title_text(&self.old_buffer);
+        let old_filename = self
+            .old_title
+            .clone()
+            .unwrap_or_else(|| title_text(&self.old_buffer).into());
         let new_filename = title_text(&self.new_buffer);
 
         format!("{old_filename} ↔ {new_filename}").into()
@@ -244,7 +255,10 @@ impl Item for FileDiffView {
                 .map(|file| file.full_path(cx).compact().to_string_lossy().into_owned())
                 .unwrap_or_else(|| "untitled".into())
         };
-        let old_path = path(&self.old_buffer);
+        let old_path = self
+            .old_title
+            .clone()
+            .unwrap_or_else(|| path(&self.old_buffer).into());
         let new_path = path(&self.new_buffer);
 
         Some(format!("{old_path} ↔ {new_path}").into())
@@ -0,0 +1,723 @@
+//! FileHistoryView lists the commits that changed a file, or some lines of it, and shows each
+//! revision of the file as a diff against its parent.
+
+use std::{
+    any::{Any, TypeId},
+    ops::Range,
+    sync::Arc,
+};
+
+use anyhow::{Context as _, Result};
+use buffer_diff::BufferDiff;
+use editor::{Editor, EditorEvent, MultiBuffer};
+use git::{
+    FileHistory, SelectionHistory,
+    history::FileHistoryEntry,
+    repository::{CommitSummary, RepoPath},
+};
+use gpui::{
+    AnyElement, AnyView, App, AsyncApp, ClickEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    ScrollStrategy, Task, UniformListScrollHandle, WeakEntity, actions, uniform_list,
+};
+use language::{Buffer, Capability, Language, Point};
+use project::{Project, git_store::Repository};
+use time::{OffsetDateTime, UtcOffset};
+use ui::{Tooltip, WithScrollbar, prelude::*};
+use workspace::{
+    Item, ItemNavHistory, Workspace,
+    item::{ItemEvent, TabContentParams},
+    notifications::DetachAndPromptErr,
+    searchable::SearchableItemHandle,
+};
+
+use crate::{commit_view::CommitView, file_diff_view::build_buffer_diff};
+
+actions!(
+    file_history,
+    [
+        /// Shows the revision of the file before the selected one.
+        ShowOlderRevision,
+        /// Shows the revision of the file after the selected one.
+        ShowNewerRevision,
+        /// Opens the commit of the selected revision.
+        OpenRevisionCommit,
+    ]
+);
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &FileHistory, window, cx| {
+        FileHistoryView::deploy(workspace, false, window, cx);
+    });
+    workspace.register_action(|workspace, _: &SelectionHistory, window, cx| {
+        FileHistoryView::deploy(workspace, true, window, cx);
+    });
+}
+
+pub struct FileHistoryView {
+    project: Entity<Project>,
+    workspace: WeakEntity<Workspace>,
+    repository: Entity<Repository>,
+    path: RepoPath,
+    /// The rows of the file in `HEAD` whose history is shown, or `None` for the whole file.
+    rows: Option<Range<u32>>,
+    entries: Option<Vec<FileHistoryEntry>>,
+    selected_index: Option<usize>,
+    /// Shows the selected revision as a diff against its parent, once it's been loaded.
+    editor: Option<Entity<Editor>>,
+    error: Option<SharedString>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    timezone: UtcOffset,
+    _load_entries: Task<()>,
+    load_revision: Task<()>,
+}
+
+impl FileHistoryView {
+    /// Opens the history of the active editor's file, or of the newest selection's lines when
+    /// `selection` is true.
+    fn deploy(
+        workspace: &mut Workspace,
+        selection: bool,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+            return;
+        };
+        let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+            return;
+        };
+        let project = workspace.project().clone();
+        let Some((repository, path)) = project
+            .read(cx)
+            .git_store()
+            .read(cx)
+            .repository_and_path_for_buffer_id(buffer.read(cx).remote_id(), cx)
+        else {
+            return;
+        };
+        let selected_rows = selection.then(|| {
+            let selection = editor.update(cx, |editor, cx| editor.selections.newest::<Point>(cx));
+            let mut end_row = selection.end.row;
+            if selection.end.column > 0 || end_row == selection.start.row {
+                end_row += 1;
+            }
+            selection.start.row..end_row
+        });
+        let uncommitted_diff = selected_rows.is_some().then(|| {
+            project.update(cx, |project, cx| {
+                project.open_uncommitted_diff(buffer.clone(), cx)
+            })
+        });
+
+        let workspace_handle = cx.entity().downgrade();
+        cx.spawn_in(window, async move |workspace, cx| {
+            // `git log -L` takes rows in `HEAD`, which differ from the buffer's rows if it has
+            // uncommitted changes above or within the selection.
+            let rows = match (selected_rows, uncommitted_diff) {
+                (Some(rows), Some(uncommitted_diff)) => {
+                    let diff = uncommitted_diff.await?;
+                    Some(cx.update(|_, cx| head_rows(&diff, &buffer, rows, cx))?)
+                }
+                _ => None,
+            };
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let view = cx.new(|cx| {
+                    FileHistoryView::new(
+                        project,
+                        workspace_handle,
+                        repository,
+                        path,
+                        rows,
+                        window,
+                        cx,
+                    )
+                });
+                workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+            })
+        })
+        .detach_and_prompt_err("Failed to open file history", window, cx, |_, _, _| None);
+    }
+
+    fn new(
+        project: Entity<Project>,
+        workspace: WeakEntity<Workspace>,
+        repository: Entity<Repository>,
+        path: RepoPath,
+        rows: Option<Range<u32>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let entries = repository.update(cx, |repository, _| {
+            repository.file_history(path.clone(), rows.clone())
+        });
+        let load_entries = cx.spawn_in(window, async move |this, cx| {
+            let result = async { entries.await? }.await;
+            this.update_in(cx, |this, window, cx| {
+                match result {
+                    Ok(entries) => {
+                        let has_entries = !entries.is_empty();
+                        this.entries = Some(entries);
+                        if has_entries {
+                            this.select_entry(0, window, cx);
+                        }
+                    }
+                    Err(error) => this.error = Some(format!("{error:#}").into()),
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+
+        let timezone =
+            UtcOffset::from_whole_seconds(chrono::Local::now().offset().local_minus_utc())
+                .unwrap_or(UtcOffset::UTC);
+
+        Self {
+            project,
+            workspace,
+            repository,
+            path,
+            rows,
+            entries: None,
+            selected_index: None,
+            editor: None,
+            error: None,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            timezone,
+            _load_entries: load_entries,
+            load_revision: Task::ready(()),
+        }
+    }
+
+    fn selected_entry(&self) -> Option<&FileHistoryEntry> {
+        self.entries.as_ref()?.get(self.selected_index?)
+    }
+
+    fn select_entry(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.as_ref().and_then(|entries| entries.get(ix)) else {
+            return;
+        };
+        let entry = entry.clone();
+        self.selected_index = Some(ix);
+        self.scroll_handle.scroll_to_item(ix, ScrollStrategy::Top);
+
+        let commit_diff = self.repository.update(cx, |repository, _| {
+            repository.load_commit_diff(entry.commit.sha.to_string())
+        });
+        let language_registry = self.project.read(cx).languages().clone();
+        let language = language_registry.load_language_for_file_path(entry.path.as_std_path());
+        self.load_revision = cx.spawn_in(window, async move |this, cx| {
+            let result = async {
+                let commit_diff = commit_diff.await??;
+                // Renamed files are deleted from their old path and added at their new one.
+                let text = |path: &RepoPath, old: bool| {
+                    commit_diff
+                        .files
+                        .iter()
+                        .find(|file| file.path == *path)
+                        .and_then(|file| {
+                            if old {
+                                file.old_text.clone()
+                            } else {
+                                file.new_text.clone()
+                            }
+                        })
+                        .unwrap_or_default()
+                };
+                let old_text = text(&entry.old_path, true);
+                let new_text = text(&entry.path, false);
+                let language = language.await.ok();
+                let old_buffer = build_revision_buffer(old_text, language.clone(), cx)?;
+                let new_buffer = build_revision_buffer(new_text, language, cx)?;
+                let diff = build_buffer_diff(&old_buffer, &new_buffer, cx).await?;
+                anyhow::Ok((new_buffer, diff))
+            }
+            .await;
+
+            this.update_in(cx, |this, window, cx| {
+                match result {
+                    Ok((buffer, diff)) => {
+                        this.editor = Some(this.build_editor(buffer, diff, window, cx));
+                        this.error = None;
+                    }
+                    Err(error) => {
+                        this.editor = None;
+                        this.error = Some(format!("{error:#}").into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+        cx.notify();
+    }
+
+    fn build_editor(
+        &self,
+        buffer: Entity<Buffer>,
+        diff: Entity<BufferDiff>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<Editor> {
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::singleton(buffer, cx);
+            multibuffer.add_diff(diff, cx);
+            multibuffer
+        });
+        cx.new(|cx| {
+            let mut editor =
+                Editor::for_multibuffer(multibuffer, Some(self.project.clone()), window, cx);
+            editor.start_temporary_diff_override();
+            editor.disable_diagnostics(cx);
+            editor.set_expand_all_diff_hunks(cx);
+            editor.set_render_diff_hunk_controls(
+                Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
+                cx,
+            );
+            editor
+        })
+    }
+
+    fn show_older_revision(
+        &mut self,
+        _: &ShowOlderRevision,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(ix) = self.selected_index {
+            self.select_entry(ix + 1, window, cx);
+        }
+    }
+
+    fn show_newer_revision(
+        &mut self,
+        _: &ShowNewerRevision,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(ix) = self.selected_index.and_then(|ix| ix.checked_sub(1)) {
+            self.select_entry(ix, window, cx);
+        }
+    }
+
+    fn open_revision_commit(
+        &mut self,
+        _: &OpenRevisionCommit,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry) = self.selected_entry() else {
+            return;
+        };
+        let commit = &entry.commit;
+        CommitView::open(
+            CommitSummary {
+                sha: commit.sha.to_string().into(),
+                subject: commit.subject.clone(),
+                commit_timestamp: commit.commit_timestamp,
+                author_name: commit.author_name.clone(),
+                has_parent: !commit.parents.is_empty(),
+            },
+            self.repository.downgrade(),
+            self.workspace.clone(),
+            window,
+            cx,
+        );
+    }
+
+    fn format_timestamp(&self, timestamp: i64) -> String {
+        let timestamp =
+            OffsetDateTime::from_unix_timestamp(timestamp).unwrap_or(OffsetDateTime::now_utc());
+        time_format::format_localized_timestamp(
+            timestamp,
+            OffsetDateTime::now_utc(),
+            self.timezone,
+            time_format::TimestampFormat::Relative,
+        )
+    }
+
+    fn render_entry(&self, entry: &FileHistoryEntry, ix: usize, cx: &Context<Self>) -> AnyElement {
+        let selected = self.selected_index == Some(ix);
+        let colors = cx.theme().colors();
+        let commit = &entry.commit;
+        v_flex()
+            .id(("file-history-entry", ix))
+            .w_full()
+            .px_2()
+            .py_1()
+            .cursor_pointer()
+            .when(selected, |this| this.bg(colors.ghost_element_selected))
+            .hover(|this| this.bg(colors.ghost_element_hover))
+            .on_click(cx.listener(move |this, _: &ClickEvent, window, cx| {
+                this.select_entry(ix, window, cx);
+            }))
+            .child(Label::new(commit.subject.clone()).truncate())
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Label::new(commit.sha.display_short())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .buffer_font(cx),
+                    )
+                    .child(
+                        Label::new(commit.author_name.clone())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate(),
+                    )
+                    .child(
+                        Label::new(self.format_timestamp(commit.commit_timestamp))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .into_any_element()
+    }
+
+    fn render_entries(&self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entry_count = self.entries.as_ref().map_or(0, Vec::len);
+        v_flex()
+            .flex_none()
+            .w(rems(20.))
+            .h_full()
+            .border_r_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                uniform_list(
+                    "file-history-entries",
+                    entry_count,
+                    cx.processor(|this, range: Range<usize>, _, cx| {
+                        let Some(entries) = this.entries.as_ref() else {
+                            return Vec::new();
+                        };
+                        range
+                            .map(|ix| this.render_entry(&entries[ix], ix, cx))
+                            .collect()
+                    }),
+                )
+                .track_scroll(self.scroll_handle.clone())
+                .size_full(),
+            )
+            .vertical_scrollbar_for(self.scroll_handle.clone(), window, cx)
+    }
+
+    fn render_revision_header(&self, cx: &Context<Self>) -> impl IntoElement {
+        let entry = self.selected_entry();
+        let entry_count = self.entries.as_ref().map_or(0, Vec::len);
+        let ix = self.selected_index.unwrap_or_default();
+        let focus_handle = self.focus_handle.clone();
+        h_flex()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                h_flex()
+                    .flex_1()
+                    .min_w_0()
+                    .gap_2()
+                    .when_some(entry, |this, entry| {
+                        this.child(
+                            Label::new(format!(
+                                "{} by {}",
+                                entry.commit.sha.display_short(),
+                                entry.commit.author_name
+                            ))
+                            .color(Color::Muted),
+                        )
+                        .when(entry.old_path != entry.path, |this| {
+                            this.child(
+                                Label::new(format!(
+                                    "renamed from {}",
+                                    entry.old_path.as_unix_str()
+                                ))
+                                .color(Color::Muted)
+                                .truncate(),
+                            )
+                        })
+                    }),
+            )
+            .child(
+                Label::new(format!("{} of {}", ix + 1, entry_count))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                IconButton::new("newer-revision", IconName::ArrowUp)
+                    .icon_size(IconSize::Small)
+                    .disabled(ix == 0)
+                    .tooltip({
+                        let focus_handle = focus_handle.clone();
+                        move |window, cx| {
+                            Tooltip::for_action_in(
+                                "Newer Revision",
+                                &ShowNewerRevision,
+                                &focus_handle,
+                                window,
+                                cx,
+                            )
+                        }
+                    })
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.show_newer_revision(&ShowNewerRevision, window, cx)
+                    })),
+            )
+            .child(
+                IconButton::new("older-revision", IconName::ArrowDown)
+                    .icon_size(IconSize::Small)
+                    .disabled(ix + 1 >= entry_count)
+                    .tooltip({
+                        let focus_handle = focus_handle.clone();
+                        move |window, cx| {
+                            Tooltip::for_action_in(
+                                "Older Revision",
+                                &ShowOlderRevision,
+                                &focus_handle,
+                                window,
+                                cx,
+                            )
+                        }
+                    })
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.show_older_revision(&ShowOlderRevision, window, cx)
+                    })),
+            )
+            .child(
+                Button::new("open-revision-commit", "Open Commit")
+                    .label_size(LabelSize::Small)
+                    .disabled(entry.is_none())
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.open_revision_commit(&OpenRevisionCommit, window, cx)
+                    })),
+            )
+    }
+
+    fn render_message(&self, message: impl Into<SharedString>) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .p_4()
+            .justify_center()
+            .items_center()
+            .child(Label::new(message).color(Color::Muted))
+    }
+}
+
+/// Maps rows of a buffer to the corresponding rows of its uncommitted diff's base text, which is
+/// the file's content in `HEAD`.
+fn head_rows(
+    diff: &Entity<BufferDiff>,
+    buffer: &Entity<Buffer>,
+    rows: Range<u32>,
+    cx: &App,
+) -> Range<u32> {
+    let buffer = buffer.read(cx).text_snapshot();
+    let diff = diff.read(cx);
+    let base_text = diff.base_text();
+    let row_range =
+        |range: Range<Point>| range.start.row..range.end.row + (range.end.column > 0) as u32;
+    let hunks = diff
+        .hunks(&buffer, cx)
+        .map(|hunk| {
+            let base_range = base_text.offset_to_point(hunk.diff_base_byte_range.start)
+                ..base_text.offset_to_point(hunk.diff_base_byte_range.end);
+            (row_range(hunk.range), row_range(base_range))
+        })
+        .collect::<Vec<_>>();
+    map_rows_to_base(&hunks, rows)
+}
+
+/// Maps a range of rows through the given hunks, each of which is the range of rows it spans in
+/// the buffer and in the base text, in order.
+///
+/// Rows within a hunk are mapped to the start or end of the hunk in the base text, so that the
+/// mapped range covers all the base rows the hunk replaced.
+fn map_rows_to_base(hunks: &[(Range<u32>, Range<u32>)], rows: Range<u32>) -> Range<u32> {
+    let map_row = |row: u32, is_end: bool| {
+        let hunk = hunks
+            .iter()
+            .take_while(|(buffer_rows, _)| buffer_rows.start < row)
+            .last();
+        match hunk {
+            None => row,
+            Some((buffer_rows, base_rows)) if row < buffer_rows.end => {
+                if is_end {
+                    base_rows.end
+                } else {
+                    base_rows.start
+                }
+            }
+            Some((buffer_rows, base_rows)) => base_rows.end + (row - buffer_rows.end),
+        }
+    };
+    let start = map_row(rows.start, false);
+    let end = map_row(rows.end, true);
+    start..end.max(start + 1)
+}
+
+fn build_revision_buffer(
+    text: String,
+    language: Option<Arc<Language>>,
+    cx: &mut AsyncApp,
+) -> Result<Entity<Buffer>> {
+    cx.new(|cx| {
+        let mut buffer = Buffer::local(text, cx);
+        buffer.set_language(language, cx);
+        buffer.set_capability(Capability::ReadOnly, cx);
+        buffer
+    })
+    .context("creating buffer for revision")
+}
+
+impl EventEmitter<EditorEvent> for FileHistoryView {}
+
+impl Focusable for FileHistoryView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        match &self.editor {
+            Some(editor) => editor.focus_handle(cx),
+            None => self.focus_handle.clone(),
+        }
+    }
+}
+
+impl Item for FileHistoryView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::HistoryRerun).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(params.text_color())
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        let file_name = self.path.file_name().unwrap_or_default();
+        match &self.rows {
+            Some(rows) => format!("History of {file_name}:{}-{}", rows.start + 1, rows.end),
+            None => format!("History of {file_name}"),
+        }
+        .into()
+    }
+
+    fn tab_tooltip_text(&self, _: &App) -> Option<SharedString> {
+        Some(self.path.as_unix_str().to_string().into())
+    }
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("File History Opened")
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyView> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.to_any())
+        } else if type_id == TypeId::of::<Editor>() {
+            self.editor.as_ref().map(|editor| editor.to_any())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.editor.clone()?))
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(editor) = &self.editor {
+            editor.update(cx, |editor, _| editor.set_nav_history(Some(nav_history)));
+        }
+    }
+
+    fn navigate(
+        &mut self,
+        data: Box<dyn Any>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.editor
+            .as_ref()
+            .is_some_and(|editor| editor.update(cx, |editor, cx| editor.navigate(data, window, cx)))
+    }
+}
+
+impl Render for FileHistoryView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = match (&self.entries, &self.error) {
+            (_, Some(error)) if self.editor.is_none() => {
+                self.render_message(error.clone()).into_any_element()
+            }
+            (None, _) => self.render_message("Loading history…").into_any_element(),
+            (Some(entries), _) if entries.is_empty() => self
+                .render_message("No commits changed this file.")
+                .into_any_element(),
+            (Some(_), _) => h_flex()
+                .size_full()
+                .child(self.render_entries(window, cx))
+                .child(
+                    v_flex()
+                        .flex_1()
+                        .min_w_0()
+                        .h_full()
+                        .child(self.render_revision_header(cx))
+                        .child(match &self.editor {
+                            Some(editor) => div().flex_1().child(editor.clone()).into_any_element(),
+                            None => self.render_message("Loading revision…").into_any_element(),
+                        }),
+                )
+                .into_any_element(),
+        };
+
+        v_flex()
+            .key_context("FileHistoryView")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .on_action(cx.listener(Self::show_older_revision))
+            .on_action(cx.listener(Self::show_newer_revision))
+            .on_action(cx.listener(Self::open_revision_commit))
+            .child(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_rows_to_base() {
+        // Rows 2..4 were inserted, and row 8 of the base replaced by rows 10..12.
+        let hunks = [(2..4, 2..2), (10..12, 8..9)];
+
+        assert_eq!(map_rows_to_base(&[], 3..5), 3..5);
+        assert_eq!(map_rows_to_base(&hunks, 0..2), 0..2);
+        assert_eq!(map_rows_to_base(&hunks, 5..7), 3..5);
+        assert_eq!(map_rows_to_base(&hunks, 9..10), 7..8);
+        // Rows within a hunk cover the base rows it replaced.
+        assert_eq!(map_rows_to_base(&hunks, 10..11), 8..9);
+        assert_eq!(map_rows_to_base(&hunks, 9..11), 7..9);
+        assert_eq!(map_rows_to_base(&hunks, 12..13), 9..10);
+        // Inserted rows have no counterpart, so they map to the row below them.
+        assert_eq!(map_rows_to_base(&hunks, 2..4), 2..3);
+    }
+}
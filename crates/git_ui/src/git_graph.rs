@@ -0,0 +1,256 @@
+//! Lays out the history of a repository as a graph, where each branch of the history occupies
+//! a lane and edges connect commits to their parents.
+
+use git::{Oid, history::LogEntry};
+
+/// An edge between two lanes in one half of a row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GraphEdge {
+    pub from_lane: usize,
+    pub to_lane: usize,
+}
+
+impl GraphEdge {
+    fn new(from_lane: usize, to_lane: usize) -> Self {
+        Self { from_lane, to_lane }
+    }
+}
+
+/// The part of the graph drawn next to a single commit.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GraphRow {
+    /// The lane of the row's commit.
+    pub lane: usize,
+    /// The edges from the top of the row to its middle, either ending at the commit or passing
+    /// through the row.
+    pub incoming: Vec<GraphEdge>,
+    /// The edges from the middle of the row to its bottom, either leaving the commit towards its
+    /// parents or passing through the row.
+    pub outgoing: Vec<GraphEdge>,
+}
+
+/// Assigns lanes to commits, which must be pushed in topological order, newest first.
+#[derive(Default)]
+pub struct GraphLayout {
+    /// The commit expected next in each lane, or `None` for free lanes.
+    lanes: Vec<Option<Oid>>,
+    rows: Vec<GraphRow>,
+    max_lane_count: usize,
+}
+
+impl GraphLayout {
+    pub fn rows(&self) -> &[GraphRow] {
+        &self.rows
+    }
+
+    /// The largest number of lanes used by any row, which determines the width of the graph.
+    pub fn lane_count(&self) -> usize {
+        self.max_lane_count
+    }
+
+    pub fn push(&mut self, entry: &LogEntry) {
+        let sha = entry.sha;
+        let lane = self
+            .lane_expecting(sha)
+            .or_else(|| self.free_lane())
+            .unwrap_or_else(|| {
+                self.lanes.push(None);
+                self.lanes.len() - 1
+            });
+
+        let mut row = GraphRow {
+            lane,
+            ..Default::default()
+        };
+        for (ix, expected) in self.lanes.iter_mut().enumerate() {
+            match expected {
+                Some(expected_sha) if *expected_sha == sha => {
+                    row.incoming.push(GraphEdge::new(ix, lane));
+                    *expected = None;
+                }
+                Some(_) => {
+                    row.incoming.push(GraphEdge::new(ix, ix));
+                    row.outgoing.push(GraphEdge::new(ix, ix));
+                }
+                None => {}
+            }
+        }
+
+        for parent in &entry.parents {
+            let parent_lane = match self.lane_expecting(*parent) {
+                Some(parent_lane) => parent_lane,
+                None => {
+                    // The commit's own lane is free at this point, so the first parent continues
+                    // in it unless a lane further left is free, which keeps the graph narrow.
+                    let parent_lane = self.free_lane().unwrap_or_else(|| {
+                        self.lanes.push(None);
+                        self.lanes.len() - 1
+                    });
+                    self.lanes[parent_lane] = Some(*parent);
+                    parent_lane
+                }
+            };
+            row.outgoing.push(GraphEdge::new(lane, parent_lane));
+        }
+
+        while self.lanes.last().is_some_and(Option::is_none) {
+            self.lanes.pop();
+        }
+        let lane_count = row
+            .incoming
+            .iter()
+            .chain(&row.outgoing)
+            .flat_map(|edge| [edge.from_lane, edge.to_lane])
+            .chain([lane])
+            .max()
+            .unwrap_or_default()
+            + 1;
+        self.max_lane_count = self.max_lane_count.max(lane_count);
+        self.rows.push(row);
+    }
+
+    fn lane_expecting(&self, sha: Oid) -> Option<usize> {
+        self.lanes
+            .iter()
+            .position(|expected| *expected == Some(sha))
+    }
+
+    fn free_lane(&self) -> Option<usize> {
+        self.lanes.iter().position(Option::is_none)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn oid(n: u8) -> Oid {
+        Oid::from_str(&format!("{n:040x}")).unwrap()
+    }
+
+    fn entry(sha: u8, parents: &[u8]) -> LogEntry {
+        LogEntry {
+            sha: oid(sha),
+            parents: parents.iter().copied().map(oid).collect(),
+            refs: Vec::new(),
+            subject: Default::default(),
+            author_name: Default::default(),
+            author_email: Default::default(),
+            commit_timestamp: 0,
+        }
+    }
+
+    fn layout(entries: &[LogEntry]) -> GraphLayout {
+        let mut layout = GraphLayout::default();
+        for entry in entries {
+            layout.push(entry);
+        }
+        layout
+    }
+
+    fn edges(edges: &[(usize, usize)]) -> Vec<GraphEdge> {
+        edges
+            .iter()
+            .map(|(from, to)| GraphEdge::new(*from, *to))
+            .collect()
+    }
+
+    #[test]
+    fn test_linear_history() {
+        let layout = layout(&[entry(3, &[2]), entry(2, &[1]), entry(1, &[])]);
+
+        assert_eq!(layout.lane_count(), 1);
+        assert_eq!(
+            layout.rows(),
+            [
+                GraphRow {
+                    lane: 0,
+                    incoming: vec![],
+                    outgoing: edges(&[(0, 0)]),
+                },
+                GraphRow {
+                    lane: 0,
+                    incoming: edges(&[(0, 0)]),
+                    outgoing: edges(&[(0, 0)]),
+                },
+                GraphRow {
+                    lane: 0,
+                    incoming: edges(&[(0, 0)]),
+                    outgoing: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_branch_and_merge() {
+        // 5 merges 4 into 3, and both branched off 2.
+        let layout = layout(&[
+            entry(5, &[3, 4]),
+            entry(4, &[2]),
+            entry(3, &[2]),
+            entry(2, &[1]),
+            entry(1, &[]),
+        ]);
+
+        assert_eq!(layout.lane_count(), 2);
+        assert_eq!(
+            layout.rows(),
+            [
+                GraphRow {
+                    lane: 0,
+                    incoming: vec![],
+                    outgoing: edges(&[(0, 0), (0, 1)]),
+                },
+                GraphRow {
+                    lane: 1,
+                    incoming: edges(&[(0, 0), (1, 1)]),
+                    outgoing: edges(&[(0, 0), (1, 1)]),
+                },
+                GraphRow {
+                    lane: 0,
+                    incoming: edges(&[(0, 0), (1, 1)]),
+                    outgoing: edges(&[(1, 1), (0, 1)]),
+                },
+                GraphRow {
+                    lane: 1,
+                    incoming: edges(&[(1, 1)]),
+                    outgoing: edges(&[(1, 0)]),
+                },
+                GraphRow {
+                    lane: 0,
+                    incoming: edges(&[(0, 0)]),
+                    outgoing: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reuses_free_lanes() {
+        // 3 is a root commit, so its lane is free again once it's been laid out, and the
+        // branch head 5 that comes after it reuses that lane.
+        let layout = layout(&[
+            entry(4, &[3]),
+            entry(2, &[1]),
+            entry(3, &[]),
+            entry(5, &[1]),
+            entry(1, &[]),
+        ]);
+
+        assert_eq!(
+            layout.rows().iter().map(|row| row.lane).collect::<Vec<_>>(),
+            [0, 1, 0, 0, 1]
+        );
+        assert_eq!(
+            layout.rows()[3],
+            GraphRow {
+                lane: 0,
+                incoming: edges(&[(1, 1)]),
+                outgoing: edges(&[(1, 1), (0, 1)]),
+            }
+        );
+        assert_eq!(layout.lane_count(), 2);
+    }
+}
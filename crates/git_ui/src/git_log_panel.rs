@@ -0,0 +1,741 @@
+use std::{ops::Range, sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use db::kvp::KEY_VALUE_STORE;
+use editor::{Editor, EditorEvent};
+use git::{
+    history::{LogEntry, LogOptions},
+    rebase::RebaseOperation,
+    repository::{CommitSummary, RepoPath},
+};
+use gpui::{
+    Action, App, AsyncWindowContext, Bounds, ClickEvent, Corner, DismissEvent, Entity,
+    EventEmitter, FocusHandle, Focusable, KeyContext, MouseButton, MouseDownEvent, PathBuilder,
+    Point, ScrollStrategy, Subscription, Task, UniformListScrollHandle, WeakEntity, actions,
+    anchored, canvas, deferred, point, size, uniform_list,
+};
+use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use project::{
+    Fs,
+    git_store::{GitStoreEvent, Repository, RepositoryEvent},
+};
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use theme::AccentColors;
+use time::{OffsetDateTime, UtcOffset};
+use ui::{ContextMenu, Tooltip, WithScrollbar, prelude::*};
+use util::{ResultExt, TryFutureExt};
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+use crate::{
+    commit_view::CommitView,
+    git_graph::{GraphLayout, GraphRow},
+    git_panel::{GitPanel, commit_operations_menu},
+    git_panel_settings::GitLogPanelSettings,
+};
+
+actions!(
+    git_log_panel,
+    [
+        /// Toggles focus on the git log panel.
+        ToggleFocus,
+        /// Reloads the history shown in the git log panel.
+        Refresh,
+        /// Interactively rebases the commits from the selected one onwards.
+        RebaseFromSelected,
+    ]
+);
+
+const GIT_LOG_PANEL_KEY: &str = "GitLogPanel";
+/// The number of commits loaded at a time as the history is scrolled.
+const PAGE_SIZE: usize = 200;
+const FILTER_DEBOUNCE: Duration = Duration::from_millis(300);
+const ROW_HEIGHT: Pixels = px(26.);
+const LANE_WIDTH: Pixels = px(14.);
+const COMMIT_RADIUS: Pixels = px(3.5);
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+        workspace.toggle_panel_focus::<GitLogPanel>(window, cx);
+    });
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedGitLogPanel {
+    width: Option<Pixels>,
+}
+
+/// Shows the history of the active repository's `HEAD` as a graph.
+pub struct GitLogPanel {
+    workspace: WeakEntity<Workspace>,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    width: Option<Pixels>,
+    pending_serialization: Task<Option<()>>,
+    repository: Option<Entity<Repository>>,
+    /// The `HEAD` the history was loaded for, to reload it when `HEAD` moves.
+    head_sha: Option<SharedString>,
+    author_filter: Entity<Editor>,
+    path_filter: Entity<Editor>,
+    message_filter: Entity<Editor>,
+    entries: Vec<LogEntry>,
+    graph: GraphLayout,
+    selected_index: Option<usize>,
+    has_more: bool,
+    /// Dropping the task cancels the pending page, e.g. when the filters change.
+    load_task: Option<Task<()>>,
+    filter_task: Task<()>,
+    error: Option<SharedString>,
+    timezone: UtcOffset,
+    context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl GitLogPanel {
+    pub fn load(
+        workspace: WeakEntity<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<anyhow::Result<Entity<Self>>> {
+        cx.spawn(async move |cx| {
+            let serialized_panel = cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(GIT_LOG_PANEL_KEY) })
+                .await
+                .context("loading git log panel")
+                .log_err()
+                .flatten()
+                .map(|panel| serde_json::from_str::<SerializedGitLogPanel>(&panel))
+                .transpose()
+                .log_err()
+                .flatten();
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let panel = Self::new(workspace, window, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width.map(|width| width.round());
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn new(
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let project = workspace.project().clone();
+        let fs = workspace.app_state().fs.clone();
+        let git_store = project.read(cx).git_store().clone();
+        let repository = project.read(cx).active_repository(cx);
+        let workspace = cx.entity().downgrade();
+
+        cx.new(|cx| {
+            let mut subscriptions = Vec::new();
+            let mut filter_editor = |placeholder: &str, cx: &mut Context<Self>| {
+                let editor = cx.new(|cx| {
+                    let mut editor = Editor::single_line(window, cx);
+                    editor.set_placeholder_text(placeholder, window, cx);
+                    editor
+                });
+                subscriptions.push(cx.subscribe(&editor, |this, _, event, cx| {
+                    if let EditorEvent::BufferEdited = event {
+                        this.schedule_reload(cx);
+                    }
+                }));
+                editor
+            };
+            let author_filter = filter_editor("Author", cx);
+            let path_filter = filter_editor("Path", cx);
+            let message_filter = filter_editor("Message", cx);
+
+            subscriptions.push(cx.subscribe(
+                &git_store,
+                |this, git_store, event, cx| match event {
+                    GitStoreEvent::ActiveRepositoryChanged(_) => {
+                        this.repository = git_store.read(cx).active_repository();
+                        this.reload(cx);
+                    }
+                    GitStoreEvent::RepositoryUpdated(_, RepositoryEvent::Updated { .. }, true) => {
+                        if this.current_head_sha(cx) != this.head_sha {
+                            this.reload(cx);
+                        }
+                    }
+                    _ => {}
+                },
+            ));
+
+            let timezone =
+                UtcOffset::from_whole_seconds(chrono::Local::now().offset().local_minus_utc())
+                    .unwrap_or(UtcOffset::UTC);
+
+            let mut this = Self {
+                workspace,
+                fs,
+                focus_handle: cx.focus_handle(),
+                scroll_handle: UniformListScrollHandle::new(),
+                width: None,
+                pending_serialization: Task::ready(None),
+                repository,
+                head_sha: None,
+                author_filter,
+                path_filter,
+                message_filter,
+                entries: Vec::new(),
+                graph: GraphLayout::default(),
+                selected_index: None,
+                has_more: false,
+                load_task: None,
+                filter_task: Task::ready(()),
+                error: None,
+                timezone,
+                context_menu: None,
+                _subscriptions: subscriptions,
+            };
+            this.reload(cx);
+            this
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        GIT_LOG_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedGitLogPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn current_head_sha(&self, cx: &App) -> Option<SharedString> {
+        let repository = self.repository.as_ref()?.read(cx);
+        Some(repository.head_commit.as_ref()?.sha.clone())
+    }
+
+    fn filter_text(editor: &Entity<Editor>, cx: &App) -> Option<String> {
+        let text = editor.read(cx).text(cx);
+        let text = text.trim();
+        (!text.is_empty()).then(|| text.to_string())
+    }
+
+    fn log_options(&self, cx: &App) -> anyhow::Result<LogOptions> {
+        let path = Self::filter_text(&self.path_filter, cx)
+            .map(|path| RepoPath::new(path.trim_end_matches('/')))
+            .transpose()
+            .context("invalid path filter")?;
+        Ok(LogOptions {
+            skip: self.entries.len(),
+            limit: PAGE_SIZE,
+            author: Self::filter_text(&self.author_filter, cx),
+            message: Self::filter_text(&self.message_filter, cx),
+            path,
+        })
+    }
+
+    fn schedule_reload(&mut self, cx: &mut Context<Self>) {
+        self.filter_task = cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(FILTER_DEBOUNCE).await;
+            this.update(cx, |this, cx| this.reload(cx)).ok();
+        });
+    }
+
+    /// Discards the loaded history and loads the first page again.
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        self.entries.clear();
+        self.graph = GraphLayout::default();
+        self.selected_index = None;
+        self.error = None;
+        self.head_sha = self.current_head_sha(cx);
+        self.has_more = self.repository.is_some();
+        self.load_task = None;
+        self.load_more(cx);
+        cx.notify();
+    }
+
+    fn load_more(&mut self, cx: &mut Context<Self>) {
+        if !self.has_more || self.load_task.is_some() {
+            return;
+        }
+        let Some(repository) = self.repository.clone() else {
+            return;
+        };
+        let options = match self.log_options(cx) {
+            Ok(options) => options,
+            Err(error) => {
+                self.has_more = false;
+                self.error = Some(format!("{error:#}").into());
+                return;
+            }
+        };
+
+        let log = repository.update(cx, |repository, _| repository.log(options));
+        self.load_task = Some(cx.spawn(async move |this, cx| {
+            let result = async { log.await? }.await;
+            this.update(cx, |this, cx| {
+                this.load_task = None;
+                match result {
+                    Ok(entries) => {
+                        this.has_more = entries.len() == PAGE_SIZE;
+                        for entry in &entries {
+                            this.graph.push(entry);
+                        }
+                        this.entries.extend(entries);
+                        if this.selected_index.is_none() && !this.entries.is_empty() {
+                            this.selected_index = Some(0);
+                        }
+                    }
+                    Err(error) => {
+                        this.has_more = false;
+                        this.error = Some(format!("{error:#}").into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    fn refresh(&mut self, _: &Refresh, _: &mut Window, cx: &mut Context<Self>) {
+        self.reload(cx);
+    }
+
+    fn select_index(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if ix < self.entries.len() {
+            self.selected_index = Some(ix);
+            self.scroll_handle
+                .scroll_to_item(ix, ScrollStrategy::Center);
+            cx.notify();
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.selected_index.map_or(0, |ix| ix + 1);
+        self.select_index(ix, cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.selected_index.map_or(0, |ix| ix.saturating_sub(1));
+        self.select_index(ix, cx);
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_index(0, cx);
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.entries.len().checked_sub(1) {
+            self.select_index(ix, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_index {
+            self.open_commit(ix, window, cx);
+        }
+    }
+
+    fn open_commit(&self, ix: usize, window: &mut Window, cx: &mut App) {
+        let (Some(entry), Some(repository)) = (self.entries.get(ix), self.repository.as_ref())
+        else {
+            return;
+        };
+        CommitView::open(
+            CommitSummary {
+                sha: entry.sha.to_string().into(),
+                subject: entry.subject.clone(),
+                commit_timestamp: entry.commit_timestamp,
+                author_name: entry.author_name.clone(),
+                has_parent: !entry.parents.is_empty(),
+            },
+            repository.downgrade(),
+            self.workspace.clone(),
+            window,
+            cx,
+        );
+    }
+
+    fn rebase_from_selected(
+        &mut self,
+        _: &RebaseFromSelected,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry) = self.selected_index.and_then(|ix| self.entries.get(ix)) else {
+            return;
+        };
+        let Some(git_panel) = self
+            .workspace
+            .upgrade()
+            .and_then(|workspace| workspace.read(cx).panel::<GitPanel>(cx))
+        else {
+            return;
+        };
+        // Rebasing onto the parent includes the selected commit in the todo list.
+        let onto = entry
+            .parents
+            .first()
+            .map(|parent| parent.to_string().into());
+        git_panel.update(cx, |git_panel, cx| {
+            git_panel.rebase(RebaseOperation::Start { onto }, window, cx);
+        });
+    }
+
+    fn can_rebase(&self, cx: &App) -> bool {
+        self.workspace.upgrade().is_some_and(|workspace| {
+            let project = workspace.read(cx).project().read(cx);
+            !project.is_via_collab() && !project.is_read_only(cx)
+        })
+    }
+
+    fn deploy_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(sha) = self.entries.get(ix).map(|entry| entry.sha.to_string()) else {
+            return;
+        };
+        let can_rebase = self.can_rebase(cx);
+        let workspace = self.workspace.clone();
+        let context_menu = ContextMenu::build(window, cx, |context_menu, _, cx| {
+            let context_menu = context_menu
+                .context(self.focus_handle.clone())
+                .action("Open Commit", Confirm.boxed_clone())
+                .separator();
+            commit_operations_menu(context_menu, sha.into(), workspace, cx).when(
+                can_rebase,
+                |context_menu| {
+                    context_menu.separator().action(
+                        "Rebase Interactively from Here",
+                        RebaseFromSelected.boxed_clone(),
+                    )
+                },
+            )
+        });
+        self.selected_index = Some(ix);
+        let subscription = cx.subscribe_in(
+            &context_menu,
+            window,
+            |this, _, _: &DismissEvent, window, cx| {
+                if this.context_menu.as_ref().is_some_and(|context_menu| {
+                    context_menu.0.focus_handle(cx).contains_focused(window, cx)
+                }) {
+                    cx.focus_self(window);
+                }
+                this.context_menu.take();
+                cx.notify();
+            },
+        );
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.notify();
+    }
+
+    fn dispatch_context(&self) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("GitLogPanel");
+        dispatch_context.add("menu");
+        dispatch_context
+    }
+
+    fn format_timestamp(&self, timestamp: i64) -> String {
+        let timestamp =
+            OffsetDateTime::from_unix_timestamp(timestamp).unwrap_or(OffsetDateTime::now_utc());
+        time_format::format_localized_timestamp(
+            timestamp,
+            OffsetDateTime::now_utc(),
+            self.timezone,
+            time_format::TimestampFormat::Relative,
+        )
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let entry = &self.entries[ix];
+        let colors = cx.theme().colors();
+        let selected = self.selected_index == Some(ix);
+
+        h_flex()
+            .id(("git-log-entry", ix))
+            .h(ROW_HEIGHT)
+            .w_full()
+            .pr_2()
+            .gap_2()
+            .overflow_hidden()
+            .cursor_pointer()
+            .when(selected, |this| this.bg(colors.ghost_element_selected))
+            .hover(|this| this.bg(colors.ghost_element_hover))
+            .on_click(cx.listener(move |this, _: &ClickEvent, window, cx| {
+                this.selected_index = Some(ix);
+                this.open_commit(ix, window, cx);
+                cx.notify();
+            }))
+            .on_mouse_down(
+                MouseButton::Right,
+                cx.listener(move |this, event: &MouseDownEvent, window, cx| {
+                    this.deploy_context_menu(event.position, ix, window, cx);
+                }),
+            )
+            .child(self.render_graph_row(ix, cx))
+            .children(entry.refs.iter().map(|name| {
+                div()
+                    .flex_none()
+                    .px_1()
+                    .rounded_sm()
+                    .border_1()
+                    .border_color(colors.border)
+                    .child(
+                        Label::new(name.clone())
+                            .size(LabelSize::XSmall)
+                            .color(Color::Accent),
+                    )
+            }))
+            .child(
+                div()
+                    .flex_1()
+                    .min_w_0()
+                    .child(Label::new(entry.subject.clone()).truncate()),
+            )
+            .child(
+                Label::new(entry.author_name.clone())
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .truncate(),
+            )
+            .child(
+                Label::new(self.format_timestamp(entry.commit_timestamp))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .tooltip(Tooltip::text(format!(
+                "{} by {} <{}>",
+                entry.sha.display_short(),
+                entry.author_name,
+                entry.author_email
+            )))
+    }
+
+    fn render_graph_row(&self, ix: usize, cx: &App) -> impl IntoElement {
+        let row = self.graph.rows()[ix].clone();
+        let accents = cx.theme().accents().clone();
+        canvas(
+            |_, _, _| {},
+            move |bounds, _, window, _| paint_graph_row(&row, &accents, bounds, window),
+        )
+        .flex_none()
+        .h_full()
+        .w(LANE_WIDTH * self.graph.lane_count() as f32)
+    }
+
+    fn render_filters(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let filter = |editor: &Entity<Editor>| {
+            div()
+                .flex_1()
+                .min_w_0()
+                .px_1()
+                .rounded_sm()
+                .border_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(editor.clone())
+        };
+        h_flex()
+            .px_2()
+            .py_1()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(filter(&self.author_filter))
+            .child(filter(&self.path_filter))
+            .child(filter(&self.message_filter))
+            .child(
+                IconButton::new("refresh-git-log", IconName::RotateCw)
+                    .icon_size(IconSize::Small)
+                    .disabled(self.repository.is_none())
+                    .tooltip(move |window, cx| Tooltip::for_action("Refresh", &Refresh, window, cx))
+                    .on_click(|_, window, cx| window.dispatch_action(Refresh.boxed_clone(), cx)),
+            )
+    }
+
+    fn render_empty_state(&self) -> impl IntoElement {
+        let message = if let Some(error) = &self.error {
+            error.clone()
+        } else if self.repository.is_none() {
+            "No repository is open.".into()
+        } else if self.load_task.is_some() {
+            "Loading…".into()
+        } else {
+            "No commits match the filters.".into()
+        };
+        v_flex()
+            .size_full()
+            .p_4()
+            .justify_center()
+            .items_center()
+            .child(Label::new(message).color(Color::Muted))
+    }
+}
+
+/// Paints the lanes passing through a row and the dot for its commit.
+fn paint_graph_row(
+    row: &GraphRow,
+    accents: &AccentColors,
+    bounds: Bounds<Pixels>,
+    window: &mut Window,
+) {
+    let lane_x = |lane: usize| bounds.left() + LANE_WIDTH * (lane as f32 + 0.5);
+    let middle = bounds.center().y;
+    let halves = [
+        (&row.incoming, bounds.top(), middle),
+        (&row.outgoing, middle, bounds.bottom()),
+    ];
+    for (edges, start_y, end_y) in halves {
+        for edge in edges {
+            let mut builder = PathBuilder::stroke(px(1.5));
+            builder.move_to(point(lane_x(edge.from_lane), start_y));
+            builder.line_to(point(lane_x(edge.to_lane), end_y));
+            // Edges between lanes belong to the branch that's forking off or merging in, which is
+            // always the one further right.
+            let lane = edge.from_lane.max(edge.to_lane);
+            if let Ok(path) = builder.build() {
+                window.paint_path(path, accents.color_for_index(lane as u32));
+            }
+        }
+    }
+
+    let center = point(lane_x(row.lane), middle);
+    let diameter = COMMIT_RADIUS * 2.;
+    window.paint_quad(
+        gpui::fill(
+            Bounds::centered_at(center, size(diameter, diameter)),
+            accents.color_for_index(row.lane as u32),
+        )
+        .corner_radii(COMMIT_RADIUS),
+    );
+}
+
+impl Panel for GitLogPanel {
+    fn persistent_name() -> &'static str {
+        "GitLogPanel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        GitLogPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            settings.git_log_panel.get_or_insert_default().dock = Some(position.into())
+        });
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| GitLogPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        GitLogPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::GitBranch)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
+        Some("Git Log Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        4
+    }
+}
+
+impl Focusable for GitLogPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for GitLogPanel {}
+
+impl Render for GitLogPanel {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .id("git-log-panel")
+            .size_full()
+            .overflow_hidden()
+            .key_context(self.dispatch_context())
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::refresh))
+            .when(self.can_rebase(cx), |this| {
+                this.on_action(cx.listener(Self::rebase_from_selected))
+            })
+            .child(self.render_filters(cx))
+            .map(|this| {
+                if self.entries.is_empty() {
+                    this.child(self.render_empty_state())
+                } else {
+                    this.child(
+                        uniform_list(
+                            "git-log-entries",
+                            self.entries.len(),
+                            cx.processor(|panel, range: Range<usize>, _, cx| {
+                                // Load the next page before the end of the history is reached.
+                                if range.end + PAGE_SIZE / 4 >= panel.entries.len() {
+                                    panel.load_more(cx);
+                                }
+                                range
+                                    .map(|ix| panel.render_entry(ix, cx).into_any_element())
+                                    .collect()
+                            }),
+                        )
+                        .track_scroll(self.scroll_handle.clone())
+                        .size_full(),
+                    )
+                    .vertical_scrollbar_for(
+                        self.scroll_handle.clone(),
+                        window,
+                        cx,
+                    )
+                }
+            })
+            .children(self.context_menu.as_ref().map(|(menu, position, _)| {
+                deferred(
+                    anchored()
+                        .position(*position)
+                        .anchor(Corner::TopLeft)
+                        .child(menu.clone()),
+                )
+                .with_priority(1)
+            }))
+    }
+}
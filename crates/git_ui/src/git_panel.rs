@@ -3,6 +3,7 @@ use crate::commit_modal::CommitModal;
 use crate::commit_tooltip::CommitTooltip;
 use crate::commit_view::CommitView;
 use crate::project_diff::{self, Diff, ProjectDiff};
+use crate::rebase_editor;
 use crate::remote_output::{self, RemoteAction, SuccessMessage};
 use crate::{branch_picker, picker_prompt, render_remote_button};
 use crate::{
@@ -10,19 +11,23 @@ use crate::{
 };
 use agent_settings::AgentSettings;
 use anyhow::Context as _;
-use askpass::AskPassDelegate;
+use askpass::{AskPassDelegate, GitEditorDelegate};
 use db::kvp::KEY_VALUE_STORE;
 use editor::{Editor, EditorElement, EditorMode, MultiBuffer};
 use futures::StreamExt as _;
+use git::bisect::{BisectOperation, checked_out_next_commit};
 use git::blame::ParsedCommitMessage;
+use git::rebase::RebaseOperation;
 use git::repository::{
     Branch, CommitDetails, CommitOptions, CommitSummary, DiffType, FetchOptions, GitCommitter,
-    PushOptions, Remote, RemoteCommandOutput, ResetMode, Upstream, UpstreamTracking,
-    UpstreamTrackingStatus, get_git_committer,
+    PushOptions, Remote, RemoteCommandOutput, ResetMode, SubmoduleOperation, Upstream,
+    UpstreamTracking, UpstreamTrackingStatus, get_git_committer,
 };
 use git::stash::GitStash;
 use git::status::StageStatus;
-use git::{Amend, Signoff, ToggleStaged, repository::RepoPath, status::FileStatus};
+use git::{
+    Amend, SHORT_SHA_LENGTH, Signoff, ToggleStaged, repository::RepoPath, status::FileStatus,
+};
 use git::{
     ExpandCommitEditor, RestoreTrackedFiles, StageAll, StashAll, StashApply, StashPop,
     TrashUntrackedFiles, UnstageAll,
@@ -56,6 +61,7 @@ use std::ops::Range;
 use std::path::Path;
 use std::{collections::HashSet, sync::Arc, time::Duration, usize};
 use strum::{IntoEnumIterator, VariantNames};
+use task::TaskContext;
 use time::OffsetDateTime;
 use ui::{
     Checkbox, CommonAnimationExt, ContextMenu, ElevationIndex, IconPosition, Label, LabelSize,
@@ -119,6 +125,8 @@ struct GitMenuState {
     has_new_changes: bool,
     sort_by_path: bool,
     has_stash_items: bool,
+    has_submodules: bool,
+    is_bisecting: bool,
 }
 
 fn git_panel_context_menu(
@@ -161,6 +169,28 @@ fn git_panel_context_menu(
                 "Trash Untracked Files",
                 TrashUntrackedFiles.boxed_clone(),
             )
+            .when(state.has_submodules, |menu| {
+                menu.separator()
+                    .action("Update Submodules", git::UpdateSubmodules.boxed_clone())
+                    .action("Initialize Submodules", git::InitSubmodules.boxed_clone())
+                    .action("Sync Submodules", git::SyncSubmodules.boxed_clone())
+            })
+            .separator()
+            .map(|menu| {
+                if state.is_bisecting {
+                    menu.action("Mark Commit as Good", git::BisectGood.boxed_clone())
+                        .action("Mark Commit as Bad", git::BisectBad.boxed_clone())
+                        .action("Skip Commit", git::BisectSkip.boxed_clone())
+                        .action(
+                            "Run Bisect with Task…",
+                            git::BisectRun::default().boxed_clone(),
+                        )
+                        .action("View Bisect Log", git::BisectLog.boxed_clone())
+                        .action("End Bisect", git::BisectReset.boxed_clone())
+                } else {
+                    menu.action("Start Bisect", git::BisectStart.boxed_clone())
+                }
+            })
             .separator()
             .entry(
                 if state.sort_by_path {
@@ -174,6 +204,111 @@ fn git_panel_context_menu(
     })
 }
 
+/// Adds the operations that apply a commit to the current branch, or move the branch to it, to
+/// the context menu of a commit.
+pub(crate) fn commit_operations_menu(
+    context_menu: ContextMenu,
+    sha: SharedString,
+    workspace: WeakEntity<Workspace>,
+    cx: &App,
+) -> ContextMenu {
+    let Some(panel) = workspace
+        .upgrade()
+        .and_then(|workspace| workspace.read(cx).panel::<GitPanel>(cx))
+    else {
+        return context_menu;
+    };
+    if !panel.read(cx).has_write_access(cx) {
+        return context_menu;
+    }
+    let is_head = panel
+        .read(cx)
+        .head_commit(cx)
+        .is_some_and(|head| head.sha == sha);
+    let is_bisecting = panel.read(cx).is_bisecting(cx);
+    let handler =
+        |operation: fn(&mut GitPanel, SharedString, &mut Window, &mut Context<GitPanel>)| {
+            let panel = panel.downgrade();
+            let sha = sha.clone();
+            move |window: &mut Window, cx: &mut App| {
+                panel
+                    .update(cx, |panel, cx| operation(panel, sha.clone(), window, cx))
+                    .ok();
+            }
+        };
+
+    context_menu
+        .when(!is_head, |menu| {
+            menu.entry(
+                "Cherry-Pick Commit",
+                None,
+                handler(|panel, sha, window, cx| panel.cherry_pick(sha, window, cx)),
+            )
+        })
+        .entry(
+            "Revert Commit",
+            None,
+            handler(|panel, sha, window, cx| panel.revert(sha, window, cx)),
+        )
+        .separator()
+        .map(|menu| {
+            if is_bisecting {
+                menu.entry(
+                    "Bisect: Mark as Good",
+                    None,
+                    handler(|panel, sha, window, cx| {
+                        panel.bisect(BisectOperation::Good(Some(sha)), window, cx)
+                    }),
+                )
+                .entry(
+                    "Bisect: Mark as Bad",
+                    None,
+                    handler(|panel, sha, window, cx| {
+                        panel.bisect(BisectOperation::Bad(Some(sha)), window, cx)
+                    }),
+                )
+                .entry(
+                    "Bisect: Skip",
+                    None,
+                    handler(|panel, sha, window, cx| {
+                        panel.bisect(BisectOperation::Skip(Some(sha)), window, cx)
+                    }),
+                )
+                .separator()
+            } else if !is_head {
+                menu.entry(
+                    "Bisect from Here to HEAD",
+                    None,
+                    handler(|panel, sha, window, cx| panel.bisect_start(Some(sha), window, cx)),
+                )
+                .separator()
+            } else {
+                menu
+            }
+        })
+        .entry(
+            "Reset to Here (Soft)",
+            None,
+            handler(|panel, sha, window, cx| {
+                panel.reset_to_commit(sha, ResetMode::Soft, window, cx)
+            }),
+        )
+        .entry(
+            "Reset to Here (Mixed)",
+            None,
+            handler(|panel, sha, window, cx| {
+                panel.reset_to_commit(sha, ResetMode::Mixed, window, cx)
+            }),
+        )
+        .entry(
+            "Reset to Here (Hard)",
+            None,
+            handler(|panel, sha, window, cx| {
+                panel.reset_to_commit(sha, ResetMode::Hard, window, cx)
+            }),
+        )
+}
+
 const GIT_PANEL_KEY: &str = "GitPanel";
 
 const UPDATE_DEBOUNCE: Duration = Duration::from_millis(50);
@@ -1647,6 +1782,94 @@ impl GitPanel {
         self.pending_commit = Some(task);
     }
 
+    pub(crate) fn cherry_pick(
+        &mut self,
+        sha: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        telemetry::event!("Git Cherry-Picked");
+        self.run_commit_operation("cherry-pick", window, cx, move |repo, cx| {
+            repo.cherry_pick(sha.to_string(), cx)
+        });
+    }
+
+    pub(crate) fn revert(
+        &mut self,
+        sha: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        telemetry::event!("Git Reverted");
+        self.run_commit_operation("revert", window, cx, move |repo, cx| {
+            repo.revert(sha.to_string(), cx)
+        });
+    }
+
+    pub(crate) fn reset_to_commit(
+        &mut self,
+        sha: SharedString,
+        mode: ResetMode,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        telemetry::event!("Git Reset", mode = format!("{mode:?}"));
+        if mode != ResetMode::Hard {
+            self.run_commit_operation("reset", window, cx, move |repo, cx| {
+                repo.reset(sha.to_string(), mode, cx)
+            });
+            return;
+        }
+
+        #[derive(strum::EnumIter, strum::VariantNames)]
+        #[strum(serialize_all = "title_case")]
+        enum ResetCancel {
+            Reset,
+            Cancel,
+        }
+        let detail = format!(
+            "This resets the current branch to {} and discards all uncommitted changes to tracked files.",
+            &sha[..sha.len().min(SHORT_SHA_LENGTH)]
+        );
+        let prompt = prompt::<ResetCancel>("Discard all changes?", Some(&detail), window, cx);
+        cx.spawn_in(window, async move |this, cx| {
+            if let Ok(ResetCancel::Reset) = prompt.await {
+                this.update_in(cx, |this, window, cx| {
+                    this.run_commit_operation("reset", window, cx, move |repo, cx| {
+                        repo.reset(sha.to_string(), mode, cx)
+                    });
+                })
+                .ok();
+            }
+        })
+        .detach();
+    }
+
+    fn run_commit_operation(
+        &mut self,
+        action: &'static str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        operation: impl FnOnce(
+            &mut Repository,
+            &mut Context<Repository>,
+        ) -> futures::channel::oneshot::Receiver<anyhow::Result<()>>
+        + 'static,
+    ) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        cx.spawn_in(window, async move |this, cx| {
+            let result = repo.update(cx, operation)?.await?;
+            this.update(cx, |this, cx| {
+                if let Err(e) = result {
+                    this.show_error_toast(action, e, cx);
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn check_for_pushed_commits(
         &mut self,
         window: &mut Window,
@@ -1960,6 +2183,43 @@ impl GitPanel {
             .detach_and_log_err(cx);
     }
 
+    pub(crate) fn run_submodule_operation(
+        &mut self,
+        operation: SubmoduleOperation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.can_push_and_pull(cx) {
+            return;
+        }
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let action = RemoteAction::Submodule(operation);
+        telemetry::event!("Git Submodules Changed", operation = action.name());
+        let askpass = self.askpass_delegate(format!("git {}", action.name()), window, cx);
+        let this = cx.weak_entity();
+
+        window
+            .spawn(cx, async move |cx| {
+                let run = repo.update(cx, |repo, cx| {
+                    repo.run_submodule_operation(operation, askpass, cx)
+                })?;
+
+                let remote_output = run.await?;
+                this.update(cx, |this, cx| match remote_output {
+                    Ok(remote_message) => this.show_remote_output(action, remote_message, cx),
+                    Err(e) => {
+                        log::error!("Error while running git {}: {:?}", action.name(), e);
+                        this.show_error_toast(action.name(), e, cx)
+                    }
+                })
+                .ok();
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+    }
+
     pub(crate) fn git_clone(&mut self, repo: String, window: &mut Window, cx: &mut Context<Self>) {
         let path = cx.prompt_for_paths(gpui::PathPromptOptions {
             files: false,
@@ -2266,6 +2526,59 @@ impl GitPanel {
         .detach_and_log_err(cx);
     }
 
+    pub(crate) fn push_tag(
+        &mut self,
+        name: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.can_push_and_pull(cx) {
+            return;
+        }
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        telemetry::event!("Git Tag Pushed");
+        let remote = self.get_remote(true, window, cx);
+
+        cx.spawn_in(window, async move |this, cx| {
+            let remote = match remote.await {
+                Ok(Some(remote)) => remote,
+                Ok(None) => {
+                    return Ok(());
+                }
+                Err(e) => {
+                    log::error!("Failed to get current remote: {}", e);
+                    this.update(cx, |this, cx| this.show_error_toast("push", e, cx))
+                        .ok();
+                    return Ok(());
+                }
+            };
+
+            let askpass_delegate = this.update_in(cx, |this, window, cx| {
+                this.askpass_delegate(format!("git push {}", remote.name), window, cx)
+            })?;
+
+            let push = repo.update(cx, |repo, cx| {
+                repo.push_tag(name.clone(), remote.name.clone(), askpass_delegate, cx)
+            })?;
+
+            let remote_output = push.await?;
+
+            let action = RemoteAction::Push(name, remote);
+            this.update(cx, |this, cx| match remote_output {
+                Ok(remote_message) => this.show_remote_output(action, remote_message, cx),
+                Err(e) => {
+                    log::error!("Error while pushing tag {:?}", e);
+                    this.show_error_toast(action.name(), e, cx)
+                }
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn askpass_delegate(
         &self,
         operation: impl Into<SharedString>,
@@ -2290,6 +2603,298 @@ impl GitPanel {
         })
     }
 
+    /// Interactively rebases the commits that aren't in the upstream of the current branch.
+    pub(crate) fn interactive_rebase(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(repo) = self.active_repository.as_ref() else {
+            return;
+        };
+        let has_upstream = repo
+            .read(cx)
+            .branch
+            .as_ref()
+            .is_some_and(|branch| branch.upstream.is_some());
+        if !has_upstream {
+            self.show_error_toast(
+                "rebase",
+                anyhow::anyhow!(
+                    "The current branch has no upstream. Rebase from a commit in the git log instead."
+                ),
+                cx,
+            );
+            return;
+        }
+        self.rebase(
+            RebaseOperation::Start {
+                onto: Some("@{upstream}".into()),
+            },
+            window,
+            cx,
+        );
+    }
+
+    pub(crate) fn rebase(
+        &mut self,
+        operation: RebaseOperation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        telemetry::event!("Git Rebased", operation = operation.name());
+        let editor = self.git_editor_delegate(window, cx);
+        let name = operation.name();
+        let this = cx.weak_entity();
+
+        window
+            .spawn(cx, async move |cx| {
+                let rebase = repo.update(cx, |repo, cx| repo.rebase(operation, editor, cx))?;
+                let result = rebase.await?;
+                this.update(cx, |this, cx| {
+                    if let Err(e) = result {
+                        this.show_error_toast(name, e, cx);
+                    }
+                })?;
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+    }
+
+    pub(crate) fn bisect(
+        &mut self,
+        operation: BisectOperation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        telemetry::event!("Git Bisected", operation = operation.name());
+        let name = operation.name();
+        let this = cx.weak_entity();
+
+        window
+            .spawn(cx, async move |cx| {
+                let bisect = repo.update(cx, |repo, cx| repo.bisect(operation, cx))?;
+                let result = bisect.await?;
+                this.update(cx, |this, cx| match result {
+                    Ok(output) => this.show_bisect_output(name, output, cx),
+                    Err(e) => this.show_error_toast(name, e, cx),
+                })?;
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+    }
+
+    /// Starts a bisect with `HEAD` as the bad commit, and the given commit as the good one if
+    /// there's one.
+    pub(crate) fn bisect_start(
+        &mut self,
+        good: Option<SharedString>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.bisect(
+            BisectOperation::Start {
+                bad: Some("HEAD".into()),
+                good: good.into_iter().collect(),
+            },
+            window,
+            cx,
+        );
+    }
+
+    pub(crate) fn open_bisect_log(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let workspace = self.workspace.clone();
+        let this = cx.weak_entity();
+
+        window
+            .spawn(cx, async move |cx| {
+                let log = repo.update(cx, |repo, _| repo.bisect_log())?.await?;
+                match log {
+                    Ok(log) => workspace.update_in(cx, |workspace, window, cx| {
+                        Self::open_output("bisect log", workspace, &log, window, cx)
+                    })?,
+                    Err(e) => {
+                        this.update(cx, |this, cx| this.show_error_toast("bisect log", e, cx))?
+                    }
+                }
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+    }
+
+    /// Runs a task on each commit the bisect in progress checks out, and marks the commit from
+    /// the task's exit code, until the first bad commit is found.
+    pub(crate) fn bisect_run(
+        &mut self,
+        task_label: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        if repo.read(cx).bisect.is_none() {
+            self.show_error_toast(
+                "bisect run",
+                anyhow::anyhow!("No bisect is in progress. Start one before running a task."),
+                cx,
+            );
+            return;
+        }
+        let Some(inventory) = self
+            .project
+            .read(cx)
+            .task_store()
+            .read(cx)
+            .task_inventory()
+            .cloned()
+        else {
+            return;
+        };
+        let work_directory = repo.read(cx).work_directory_abs_path.to_path_buf();
+        let worktree_id = self
+            .project
+            .read(cx)
+            .find_worktree(&work_directory, cx)
+            .map(|(worktree, _)| worktree.read(cx).id());
+        let workspace = self.workspace.clone();
+        let this = cx.weak_entity();
+
+        window
+            .spawn(cx, async move |cx| {
+                let result = async {
+                    let template = if let Some(label) = task_label {
+                        inventory
+                            .update(cx, |inventory, cx| {
+                                inventory.task_template_by_label(None, worktree_id, &label, cx)
+                            })?
+                            .await
+                            .with_context(|| format!("No task is labeled {label:?}"))?
+                    } else {
+                        let mut templates = inventory
+                            .update(cx, |inventory, cx| {
+                                inventory.list_tasks(None, None, worktree_id, cx)
+                            })?
+                            .await;
+                        let labels = templates
+                            .iter()
+                            .map(|(_, template)| template.label.clone().into())
+                            .collect();
+                        let selection = cx
+                            .update(|window, cx| {
+                                picker_prompt::prompt(
+                                    "Pick a task to test each commit with",
+                                    labels,
+                                    workspace.clone(),
+                                    window,
+                                    cx,
+                                )
+                            })?
+                            .await;
+                        let Some(selection) = selection else {
+                            return Ok(None);
+                        };
+                        templates.swap_remove(selection).1
+                    };
+                    let task_context = TaskContext {
+                        cwd: Some(work_directory),
+                        ..TaskContext::default()
+                    };
+                    let task = template
+                        .resolve_task("git-bisect-run", &task_context)
+                        .with_context(|| format!("Failed to resolve task {:?}", template.label))?;
+
+                    loop {
+                        let exit_status = workspace
+                            .update_in(cx, |workspace, window, cx| {
+                                workspace.spawn_in_terminal(task.resolved.clone(), window, cx)
+                            })?
+                            .await;
+                        // The task was cancelled, which stops the bisect run.
+                        let Some(exit_status) = exit_status.transpose()? else {
+                            return Ok(None);
+                        };
+                        let operation = exit_status
+                            .code()
+                            .and_then(BisectOperation::for_exit_code)
+                            .with_context(|| {
+                                format!(
+                                    "Stopped the bisect run, as the task ended with {exit_status}"
+                                )
+                            })?;
+                        let output = repo
+                            .update(cx, |repo, cx| repo.bisect(operation, cx))?
+                            .await??;
+                        if !checked_out_next_commit(&output) {
+                            return anyhow::Ok(Some(output));
+                        }
+                    }
+                }
+                .await;
+
+                this.update(cx, |this, cx| match result {
+                    Ok(Some(output)) => this.show_bisect_output("bisect run", output, cx),
+                    Ok(None) => {}
+                    Err(e) => this.show_error_toast("bisect run", e, cx),
+                })?;
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+    }
+
+    fn show_bisect_output(&self, operation: &'static str, output: String, cx: &mut App) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let Some(message) = output
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(ToString::to_string)
+        else {
+            return;
+        };
+
+        workspace.update(cx, |workspace, cx| {
+            let workspace_weak = cx.weak_entity();
+            let status_toast = StatusToast::new(message, cx, move |this, _cx| {
+                this.icon(ToastIcon::new(IconName::GitBranchAlt).color(Color::Muted))
+                    .action("View Log", move |window, cx| {
+                        let output = output.clone();
+                        workspace_weak
+                            .update(cx, move |workspace, cx| {
+                                Self::open_output(operation, workspace, &output, window, cx)
+                            })
+                            .ok();
+                    })
+            });
+            workspace.toggle_status_toast(status_toast, cx)
+        });
+    }
+
+    fn git_editor_delegate(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> GitEditorDelegate {
+        let workspace = self.workspace.clone();
+        let window = window.window_handle();
+        GitEditorDelegate::new(&mut cx.to_async(), move |request, tx, cx| {
+            window
+                .update(cx, |_, window, cx| {
+                    workspace.update(cx, |workspace, cx| {
+                        rebase_editor::edit(workspace, request, tx, window, cx);
+                    })
+                })
+                .ok();
+        })
+    }
+
     fn can_push_and_pull(&self, cx: &App) -> bool {
         !self.project.read(cx).is_via_collab()
     }
@@ -2944,19 +3549,33 @@ impl GitPanel {
         self.has_staged_changes()
     }
 
+    fn has_submodules(&self, cx: &App) -> bool {
+        self.active_repository
+            .as_ref()
+            .is_some_and(|repo| !repo.read(cx).submodules.is_empty())
+    }
+
+    fn is_bisecting(&self, cx: &App) -> bool {
+        self.active_repository
+            .as_ref()
+            .is_some_and(|repo| repo.read(cx).bisect.is_some())
+    }
+
     // eventually we'll need to take depth into account here
     // if we add a tree view
     fn item_width_estimate(path: usize, file_name: usize) -> usize {
         path + file_name
     }
 
-    fn render_overflow_menu(&self, id: impl Into<ElementId>) -> impl IntoElement {
+    fn render_overflow_menu(&self, id: impl Into<ElementId>, cx: &App) -> impl IntoElement {
         let focus_handle = self.focus_handle.clone();
         let has_tracked_changes = self.has_tracked_changes();
         let has_staged_changes = self.has_staged_changes();
         let has_unstaged_changes = self.has_unstaged_changes();
         let has_new_changes = self.new_count > 0;
         let has_stash_items = self.stash_entries.entries.len() > 0;
+        let has_submodules = self.has_submodules(cx);
+        let is_bisecting = self.is_bisecting(cx);
 
         PopoverMenu::new(id.into())
             .trigger(
@@ -2974,6 +3593,8 @@ impl GitPanel {
                         has_new_changes,
                         sort_by_path: GitPanelSettings::get_global(cx).sort_by_path,
                         has_stash_items,
+                        has_submodules,
+                        is_bisecting,
                     },
                     window,
                     cx,
@@ -3242,7 +3863,7 @@ impl GitPanel {
                 .child(
                     h_flex()
                         .gap_1()
-                        .child(self.render_overflow_menu("overflow_menu"))
+                        .child(self.render_overflow_menu("overflow_menu", cx))
                         .child(
                             panel_filled_button(text)
                                 .tooltip(Tooltip::for_action_title_in(
@@ -3508,6 +4129,171 @@ impl GitPanel {
             )
     }
 
+    fn render_rebase_status(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let rebase = self.active_repository.as_ref()?.read(cx).rebase?;
+        let message = if self.has_unstaged_conflicts() {
+            format!(
+                "Rebase stopped at step {} of {}. Resolve and stage the conflicts to continue.",
+                rebase.step, rebase.total
+            )
+        } else {
+            format!(
+                "Rebase stopped at step {} of {}.",
+                rebase.step, rebase.total
+            )
+        };
+
+        Some(
+            h_flex()
+                .py_1p5()
+                .px_2()
+                .gap_1p5()
+                .justify_between()
+                .border_b_1()
+                .border_color(cx.theme().colors().border.opacity(0.8))
+                .child(
+                    div()
+                        .flex_grow()
+                        .overflow_hidden()
+                        .child(Label::new(message).size(LabelSize::Small).truncate()),
+                )
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            panel_button("Abort")
+                                .tooltip(Tooltip::for_action_title(
+                                    "Abort the Rebase",
+                                    &git::RebaseAbort,
+                                ))
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.rebase(RebaseOperation::Abort, window, cx)
+                                })),
+                        )
+                        .child(
+                            panel_button("Skip")
+                                .tooltip(Tooltip::for_action_title(
+                                    "Skip This Commit",
+                                    &git::RebaseSkip,
+                                ))
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.rebase(RebaseOperation::Skip, window, cx)
+                                })),
+                        )
+                        .child(
+                            panel_filled_button("Continue")
+                                .tooltip(Tooltip::for_action_title(
+                                    "Continue the Rebase",
+                                    &git::RebaseContinue,
+                                ))
+                                .disabled(self.has_unstaged_conflicts())
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.rebase(RebaseOperation::Continue, window, cx)
+                                })),
+                        ),
+                ),
+        )
+    }
+
+    fn render_bisect_status(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let bisect = self.active_repository.as_ref()?.read(cx).bisect.clone()?;
+        let plural = |count: usize| if count == 1 { "" } else { "s" };
+        let first_bad_commit = bisect.first_bad_commit().cloned();
+        let message = if let Some(sha) = &first_bad_commit {
+            format!(
+                "Bisect found the first bad commit: {}.",
+                sha.chars().take(SHORT_SHA_LENGTH).collect::<String>()
+            )
+        } else if let Some(progress) = bisect.progress {
+            format!(
+                "Bisecting: {} revision{} left to test after this (roughly {} step{}).",
+                progress.revisions_left,
+                plural(progress.revisions_left),
+                progress.steps_left,
+                plural(progress.steps_left),
+            )
+        } else if bisect.bad.is_none() {
+            "Bisecting. Mark a bad commit to continue.".to_string()
+        } else {
+            "Bisecting. Mark a good commit to continue.".to_string()
+        };
+
+        Some(
+            h_flex()
+                .py_1p5()
+                .px_2()
+                .gap_1p5()
+                .justify_between()
+                .border_b_1()
+                .border_color(cx.theme().colors().border.opacity(0.8))
+                .child(
+                    div()
+                        .flex_grow()
+                        .overflow_hidden()
+                        .child(Label::new(message).size(LabelSize::Small).truncate()),
+                )
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            panel_button("Reset")
+                                .tooltip(Tooltip::for_action_title(
+                                    "End the Bisect",
+                                    &git::BisectReset,
+                                ))
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.bisect(BisectOperation::Reset, window, cx)
+                                })),
+                        )
+                        .map(|this| {
+                            if first_bad_commit.is_some() {
+                                this.child(
+                                    panel_filled_button("View Log")
+                                        .tooltip(Tooltip::for_action_title(
+                                            "View the Bisect Log",
+                                            &git::BisectLog,
+                                        ))
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.open_bisect_log(window, cx)
+                                        })),
+                                )
+                            } else {
+                                this.child(
+                                    panel_button("Skip")
+                                        .tooltip(Tooltip::for_action_title(
+                                            "Skip This Commit",
+                                            &git::BisectSkip,
+                                        ))
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.bisect(BisectOperation::Skip(None), window, cx)
+                                        })),
+                                )
+                                .child(
+                                    panel_button("Bad")
+                                        .tooltip(Tooltip::for_action_title(
+                                            "Mark This Commit as Bad",
+                                            &git::BisectBad,
+                                        ))
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.bisect(BisectOperation::Bad(None), window, cx)
+                                        })),
+                                )
+                                .child(
+                                    panel_button("Good")
+                                        .tooltip(Tooltip::for_action_title(
+                                            "Mark This Commit as Good",
+                                            &git::BisectGood,
+                                        ))
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.bisect(BisectOperation::Good(None), window, cx)
+                                        })),
+                                )
+                            }
+                        }),
+                ),
+        )
+    }
+
     fn render_previous_commit(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
         let active_repository = self.active_repository.as_ref()?;
         let branch = active_repository.read(cx).branch.as_ref()?;
@@ -3534,6 +4320,17 @@ impl GitPanel {
                                 .truncate(),
                         )
                         .id("commit-msg-hover")
+                        .on_mouse_down(MouseButton::Right, {
+                            let commit = commit.clone();
+                            cx.listener(move |this, event: &MouseDownEvent, window, cx| {
+                                this.deploy_commit_context_menu(
+                                    event.position,
+                                    commit.clone(),
+                                    window,
+                                    cx,
+                                )
+                            })
+                        })
                         .on_click({
                             let commit = commit.clone();
                             let repo = active_repository.downgrade();
@@ -3828,6 +4625,43 @@ impl GitPanel {
         self.set_context_menu(context_menu, position, window, cx);
     }
 
+    fn deploy_commit_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        commit: CommitSummary,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let workspace = self.workspace.clone();
+        let context_menu = ContextMenu::build(window, cx, |context_menu, _, cx| {
+            let sha = commit.sha.clone();
+            let has_parent = commit.has_parent;
+            let context_menu = context_menu
+                .context(self.focus_handle.clone())
+                .entry("Open Commit", None, {
+                    let workspace = workspace.clone();
+                    move |window, cx| {
+                        CommitView::open(
+                            commit.clone(),
+                            repo.downgrade(),
+                            workspace.clone(),
+                            window,
+                            cx,
+                        )
+                    }
+                })
+                .when(has_parent, |menu| {
+                    menu.action("Uncommit", git::Uncommit.boxed_clone())
+                })
+                .separator();
+            commit_operations_menu(context_menu, sha, workspace, cx)
+        });
+        self.set_context_menu(context_menu, position, window, cx);
+    }
+
     fn deploy_panel_context_menu(
         &mut self,
         position: Point<Pixels>,
@@ -3843,6 +4677,8 @@ impl GitPanel {
                 has_new_changes: self.new_count > 0,
                 sort_by_path: GitPanelSettings::get_global(cx).sort_by_path,
                 has_stash_items: self.stash_entries.entries.len() > 0,
+                has_submodules: self.has_submodules(cx),
+                is_bisecting: self.is_bisecting(cx),
             },
             window,
             cx,
@@ -3927,6 +4763,27 @@ impl GitPanel {
             is_staged = ToggleState::Selected;
         }
 
+        // A submodule is staged as a single entry recording the commit checked out in it, so
+        // changes that haven't been committed inside the submodule can't be staged from here.
+        let submodule = self
+            .active_repository
+            .as_ref()
+            .and_then(|repo| repo.read(cx).submodule_for_path(&entry.repo_path).cloned());
+        let can_stage = submodule.as_ref().is_none_or(|submodule| {
+            submodule.is_out_of_date() || entry_staging.has_staged() || !submodule.is_dirty
+        });
+        let submodule_description = submodule.as_ref().map(|submodule| {
+            if !submodule.is_initialized() {
+                "submodule, not initialized"
+            } else if submodule.is_out_of_date() {
+                "submodule, new commits"
+            } else if submodule.is_dirty {
+                "submodule, modified content"
+            } else {
+                "submodule"
+            }
+        });
+
         let handle = cx.weak_entity();
 
         let selected_bg_alpha = 0.08;
@@ -4015,7 +4872,7 @@ impl GitPanel {
                     .cursor_pointer()
                     .child(
                         Checkbox::new(checkbox_id, is_staged)
-                            .disabled(!has_write_access)
+                            .disabled(!has_write_access || !can_stage)
                             .fill()
                             .elevation(ElevationIndex::Surface)
                             .on_click_ext({
@@ -4041,6 +4898,12 @@ impl GitPanel {
                                 }
                             })
                             .tooltip(move |window, cx| {
+                                if !can_stage {
+                                    return Tooltip::simple(
+                                        "Commit the changes inside the submodule to stage it",
+                                        cx,
+                                    );
+                                }
                                 let is_staged = entry_staging.is_fully_staged();
 
                                 let action = if is_staged { "Unstage" } else { "Stage" };
@@ -4072,7 +4935,16 @@ impl GitPanel {
                     .child(
                         self.entry_label(display_name, label_color)
                             .when(status.is_deleted(), |this| this.strikethrough()),
-                    ),
+                    )
+                    .when_some(submodule_description, |this, description| {
+                        this.child(
+                            div().ml_1p5().child(
+                                Label::new(description)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                        )
+                    }),
             )
             .into_any_element()
     }
@@ -4268,6 +5140,10 @@ impl Render for GitPanel {
                 v_flex()
                     .size_full()
                     .children(self.render_panel_header(window, cx))
+                    .when(has_write_access, |this| {
+                        this.children(self.render_rebase_status(cx))
+                            .children(self.render_bisect_status(cx))
+                    })
                     .map(|this| {
                         if has_entries {
                             this.child(self.render_entries(has_write_access, window, cx))
@@ -71,3 +71,21 @@ impl Settings for GitPanelSettings {
         }
     }
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitLogPanelSettings {
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+}
+
+impl Settings for GitLogPanelSettings {
+    fn from_settings(content: &settings::SettingsContent, _cx: &mut ui::App) -> Self {
+        let git_log_panel = content.git_log_panel.clone().unwrap();
+        Self {
+            button: git_log_panel.button.unwrap(),
+            dock: git_log_panel.dock.unwrap().into(),
+            default_width: px(git_log_panel.default_width.unwrap()),
+        }
+    }
+}
@@ -12,10 +12,12 @@ use ui::{
 mod blame_ui;
 
 use git::{
-    repository::{Branch, Upstream, UpstreamTracking, UpstreamTrackingStatus},
+    bisect::BisectOperation,
+    rebase::RebaseOperation,
+    repository::{Branch, SubmoduleOperation, Upstream, UpstreamTracking, UpstreamTrackingStatus},
     status::{FileStatus, StatusCode, UnmergedStatus, UnmergedStatusCode},
 };
-use git_panel_settings::GitPanelSettings;
+use git_panel_settings::{GitLogPanelSettings, GitPanelSettings};
 use gpui::{
     Action, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, SharedString,
     Window, actions,
@@ -36,16 +38,24 @@ mod commit_modal;
 pub mod commit_tooltip;
 mod commit_view;
 mod conflict_view;
+pub mod diff_base_picker;
 pub mod file_diff_view;
+pub mod file_history_view;
+mod git_graph;
+pub mod git_log_panel;
 pub mod git_panel;
 mod git_panel_settings;
+pub mod local_history_picker;
 pub mod onboarding;
 pub mod picker_prompt;
 pub mod project_diff;
+mod rebase_editor;
 pub(crate) mod remote_output;
 pub mod repository_selector;
 pub mod stash_picker;
+pub mod tag_picker;
 pub mod text_diff_view;
+pub mod worktree_picker;
 
 actions!(
     git,
@@ -57,6 +67,7 @@ actions!(
 
 pub fn init(cx: &mut App) {
     GitPanelSettings::register(cx);
+    GitLogPanelSettings::register(cx);
 
     editor::set_blame_renderer(blame_ui::GitBlameRenderer, cx);
 
@@ -69,9 +80,15 @@ pub fn init(cx: &mut App) {
         ProjectDiff::register(workspace, cx);
         CommitModal::register(workspace);
         git_panel::register(workspace);
+        git_log_panel::register(workspace);
+        file_history_view::register(workspace);
         repository_selector::register(workspace);
         branch_picker::register(workspace);
         stash_picker::register(workspace);
+        tag_picker::register(workspace);
+        worktree_picker::register(workspace);
+        diff_base_picker::register(workspace);
+        local_history_picker::register(workspace);
 
         let project = workspace.project().read(cx);
         if project.is_read_only(cx) {
@@ -94,6 +111,30 @@ pub fn init(cx: &mut App) {
                     panel.fetch(false, window, cx);
                 });
             });
+            workspace.register_action(|workspace, _: &git::InitSubmodules, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.run_submodule_operation(SubmoduleOperation::Init, window, cx);
+                });
+            });
+            workspace.register_action(|workspace, _: &git::UpdateSubmodules, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.run_submodule_operation(SubmoduleOperation::Update, window, cx);
+                });
+            });
+            workspace.register_action(|workspace, _: &git::SyncSubmodules, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.run_submodule_operation(SubmoduleOperation::Sync, window, cx);
+                });
+            });
             workspace.register_action(|workspace, _: &git::Push, window, cx| {
                 let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
                     return;
@@ -126,7 +167,95 @@ pub fn init(cx: &mut App) {
                     panel.pull(window, cx);
                 });
             });
+            workspace.register_action(|workspace, _: &git::InteractiveRebase, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.interactive_rebase(window, cx);
+                });
+            });
+            workspace.register_action(|workspace, _: &git::RebaseContinue, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.rebase(RebaseOperation::Continue, window, cx);
+                });
+            });
+            workspace.register_action(|workspace, _: &git::RebaseSkip, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.rebase(RebaseOperation::Skip, window, cx);
+                });
+            });
+            workspace.register_action(|workspace, _: &git::RebaseAbort, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.rebase(RebaseOperation::Abort, window, cx);
+                });
+            });
+            workspace.register_action(|workspace, action: &git::BisectRun, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.bisect_run(action.task.clone(), window, cx);
+                });
+            });
         }
+        workspace.register_action(|workspace, _: &git::BisectStart, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.bisect_start(None, window, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::BisectGood, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.bisect(BisectOperation::Good(None), window, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::BisectBad, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.bisect(BisectOperation::Bad(None), window, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::BisectSkip, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.bisect(BisectOperation::Skip(None), window, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::BisectReset, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.bisect(BisectOperation::Reset, window, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::BisectLog, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.open_bisect_log(window, cx);
+            });
+        });
         workspace.register_action(|workspace, action: &git::StashAll, window, cx| {
             let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
                 return;
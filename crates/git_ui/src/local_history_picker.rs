@@ -0,0 +1,445 @@
+use std::sync::Arc;
+
+use editor::Editor;
+use fuzzy::StringMatchCandidate;
+use gpui::{
+    Action, AnyElement, App, AppContext as _, Context, DismissEvent, Entity, EventEmitter,
+    FocusHandle, Focusable, InteractiveElement, IntoElement, ParentElement, Render, SharedString,
+    Styled, Subscription, Task, WeakEntity, Window, rems,
+};
+use language::{Buffer, Capability};
+use local_history::{ShowTimeline, SnapshotEntry};
+use picker::{Picker, PickerDelegate};
+use time::{OffsetDateTime, UtcOffset};
+use ui::{HighlightedLabel, KeyBinding, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::notifications::DetachAndPromptErr;
+use workspace::{ModalView, Workspace};
+
+use crate::file_diff_view::{FileDiffView, build_buffer_diff};
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(open);
+}
+
+pub fn open(
+    workspace: &mut Workspace,
+    _: &ShowTimeline,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(buffer) = workspace
+        .active_item_as::<Editor>(cx)
+        .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+    else {
+        return;
+    };
+    if local_history::history_path(buffer.read(cx), cx).is_none() {
+        return;
+    }
+    let workspace_handle = cx.entity().downgrade();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        LocalHistoryTimeline::new(buffer, workspace_handle, rems(34.), window, cx)
+    })
+}
+
+/// A modal listing the local history snapshots of a file, to compare against or restore.
+pub struct LocalHistoryTimeline {
+    width: Rems,
+    pub picker: Entity<Picker<LocalHistoryTimelineDelegate>>,
+    picker_focus_handle: FocusHandle,
+    _subscription: Subscription,
+}
+
+impl LocalHistoryTimeline {
+    fn new(
+        buffer: Entity<Buffer>,
+        workspace: WeakEntity<Workspace>,
+        width: Rems,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let snapshots = local_history::history_path(buffer.read(cx), cx)
+            .map(|abs_path| local_history::snapshots(abs_path, cx));
+        cx.spawn_in(window, async move |this, cx| {
+            let snapshots = match snapshots {
+                Some(snapshots) => snapshots.await?,
+                None => Vec::new(),
+            };
+            this.update_in(cx, |this, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    picker.delegate.all_snapshots = Some(snapshots);
+                    picker.refresh(window, cx);
+                })
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+
+        let delegate = LocalHistoryTimelineDelegate::new(buffer, workspace, cx);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        let picker_focus_handle = picker.focus_handle(cx);
+        picker.update(cx, |picker, _| {
+            picker.delegate.focus_handle = picker_focus_handle.clone();
+        });
+        let subscription = cx.subscribe(&picker, |_, _, _, cx| {
+            cx.emit(DismissEvent);
+        });
+
+        Self {
+            width,
+            picker,
+            picker_focus_handle,
+            _subscription: subscription,
+        }
+    }
+}
+
+impl ModalView for LocalHistoryTimeline {}
+impl EventEmitter<DismissEvent> for LocalHistoryTimeline {}
+impl Focusable for LocalHistoryTimeline {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.picker_focus_handle.clone()
+    }
+}
+
+impl Render for LocalHistoryTimeline {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("LocalHistoryTimeline")
+            .w(self.width)
+            .child(self.picker.clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SnapshotMatch {
+    entry: SnapshotEntry,
+    positions: Vec<usize>,
+    formatted_timestamp: String,
+}
+
+pub struct LocalHistoryTimelineDelegate {
+    matches: Vec<SnapshotMatch>,
+    all_snapshots: Option<Vec<SnapshotEntry>>,
+    buffer: Entity<Buffer>,
+    workspace: WeakEntity<Workspace>,
+    selected_index: usize,
+    focus_handle: FocusHandle,
+    timezone: UtcOffset,
+}
+
+impl LocalHistoryTimelineDelegate {
+    fn new(
+        buffer: Entity<Buffer>,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<LocalHistoryTimeline>,
+    ) -> Self {
+        let timezone =
+            UtcOffset::from_whole_seconds(chrono::Local::now().offset().local_minus_utc())
+                .unwrap_or(UtcOffset::UTC);
+
+        Self {
+            matches: Vec::new(),
+            all_snapshots: None,
+            buffer,
+            workspace,
+            selected_index: 0,
+            focus_handle: cx.focus_handle(),
+            timezone,
+        }
+    }
+
+    fn format_timestamp(timestamp: OffsetDateTime, timezone: UtcOffset) -> String {
+        time_format::format_localized_timestamp(
+            timestamp,
+            OffsetDateTime::now_utc(),
+            timezone,
+            time_format::TimestampFormat::EnhancedAbsolute,
+        )
+    }
+
+    /// Opens a diff of the snapshot against the current contents of the buffer.
+    fn compare_snapshot(
+        &self,
+        snapshot: &SnapshotMatch,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let buffer = self.buffer.clone();
+        let workspace = self.workspace.clone();
+        let language = buffer.read(cx).language().cloned();
+        let file_name = buffer
+            .read(cx)
+            .file()
+            .map(|file| file.file_name(cx).to_string())
+            .unwrap_or_else(|| "untitled".into());
+        let old_title = format!("{file_name} ({})", snapshot.formatted_timestamp);
+        let content = local_history::snapshot_content(snapshot.entry.id, cx);
+
+        cx.spawn_in(window, async move |_, cx| {
+            let content = content.await?;
+            let snapshot_buffer = cx.new(|cx| {
+                let mut snapshot_buffer = Buffer::local(content, cx);
+                snapshot_buffer.set_language(language, cx);
+                snapshot_buffer.set_capability(Capability::ReadOnly, cx);
+                snapshot_buffer
+            })?;
+            let diff = build_buffer_diff(&snapshot_buffer, &buffer, cx).await?;
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let project = workspace.project().clone();
+                let diff_view = cx.new(|cx| {
+                    let mut diff_view =
+                        FileDiffView::new(snapshot_buffer, buffer, diff, project, window, cx);
+                    diff_view.set_old_title(old_title, cx);
+                    diff_view
+                });
+                workspace.active_pane().update(cx, |pane, cx| {
+                    pane.add_item(Box::new(diff_view), true, true, None, window, cx);
+                });
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to open snapshot", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+        cx.emit(DismissEvent);
+    }
+
+    /// Replaces the contents of the buffer with the snapshot, as a single undoable edit.
+    fn restore_snapshot(
+        &self,
+        snapshot: &SnapshotMatch,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let buffer = self.buffer.clone();
+        let content = local_history::snapshot_content(snapshot.entry.id, cx);
+
+        cx.spawn_in(window, async move |_, cx| {
+            let content = content.await?;
+            let diff = buffer
+                .update(cx, |buffer, cx| buffer.diff(content, cx))?
+                .await;
+            buffer.update(cx, |buffer, cx| {
+                buffer.finalize_last_transaction();
+                buffer.apply_diff(diff, cx);
+                buffer.finalize_last_transaction();
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to restore snapshot", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+        cx.emit(DismissEvent);
+    }
+}
+
+impl PickerDelegate for LocalHistoryTimelineDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select a snapshot…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let Some(all_snapshots) = self.all_snapshots.clone() else {
+            return Task::ready(());
+        };
+        let timezone = self.timezone;
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let snapshots = all_snapshots
+                .into_iter()
+                .map(|entry| {
+                    let formatted_timestamp = Self::format_timestamp(entry.created_at, timezone);
+                    SnapshotMatch {
+                        entry,
+                        positions: Vec::new(),
+                        formatted_timestamp,
+                    }
+                })
+                .collect::<Vec<_>>();
+            let matches = if query.is_empty() {
+                snapshots
+            } else {
+                let candidates = snapshots
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, snapshot)| {
+                        StringMatchCandidate::new(ix, &snapshot.formatted_timestamp)
+                    })
+                    .collect::<Vec<_>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .map(|candidate| SnapshotMatch {
+                    positions: candidate.positions,
+                    ..snapshots[candidate.candidate_id].clone()
+                })
+                .collect()
+            };
+
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    if delegate.matches.is_empty() {
+                        delegate.selected_index = 0;
+                    } else {
+                        delegate.selected_index =
+                            core::cmp::min(delegate.selected_index, delegate.matches.len() - 1);
+                    }
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(snapshot) = self.matches.get(self.selected_index()).cloned() else {
+            return;
+        };
+        if secondary {
+            self.restore_snapshot(&snapshot, window, cx);
+        } else {
+            self.compare_snapshot(&snapshot, window, cx);
+        }
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let snapshot = &self.matches[ix];
+
+        let timestamp_label = HighlightedLabel::new(
+            snapshot.formatted_timestamp.clone(),
+            snapshot.positions.clone(),
+        )
+        .truncate()
+        .into_any_element();
+
+        let details = h_flex()
+            .gap_1p5()
+            .w_full()
+            .child(
+                Label::new(snapshot.entry.source.label())
+                    .color(Color::Muted)
+                    .size(LabelSize::Small),
+            )
+            .child(
+                Label::new("•")
+                    .alpha(0.5)
+                    .color(Color::Muted)
+                    .size(LabelSize::Small),
+            )
+            .child(
+                Label::new(util::size::format_file_size(
+                    snapshot.entry.len as u64,
+                    true,
+                ))
+                .color(Color::Muted)
+                .size(LabelSize::Small),
+            );
+
+        Some(
+            ListItem::new(SharedString::from(format!("local-history-snapshot-{ix}")))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    v_flex()
+                        .w_full()
+                        .overflow_hidden()
+                        .child(timestamp_label)
+                        .child(details),
+                ),
+        )
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No snapshots found".into())
+    }
+
+    fn render_footer(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<AnyElement> {
+        let focus_handle = self.focus_handle.clone();
+
+        Some(
+            h_flex()
+                .w_full()
+                .p_1p5()
+                .gap_0p5()
+                .justify_end()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Button::new("compare-snapshot", "Compare")
+                        .key_binding(
+                            KeyBinding::for_action_in(&menu::Confirm, &focus_handle, window, cx)
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(menu::Confirm.boxed_clone(), cx)
+                        }),
+                )
+                .child(
+                    Button::new("restore-snapshot", "Restore")
+                        .key_binding(
+                            KeyBinding::for_action_in(
+                                &menu::SecondaryConfirm,
+                                &focus_handle,
+                                window,
+                                cx,
+                            )
+                            .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(menu::SecondaryConfirm.boxed_clone(), cx)
+                        }),
+                )
+                .into_any(),
+        )
+    }
+}
@@ -4,9 +4,9 @@ use crate::{
     git_panel_settings::GitPanelSettings,
     remote_button::{render_publish_button, render_push_button},
 };
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use buffer_diff::{BufferDiff, DiffHunkSecondaryStatus};
-use collections::HashSet;
+use collections::{BTreeMap, HashSet};
 use editor::{
     Editor, EditorEvent, SelectionEffects,
     actions::{GoToHunk, GoToPreviousHunk},
@@ -15,9 +15,9 @@ use editor::{
 };
 use futures::StreamExt;
 use git::{
-    Commit, StageAll, StageAndNext, ToggleStaged, UnstageAll, UnstageAndNext,
-    repository::{Branch, Upstream, UpstreamTracking, UpstreamTrackingStatus},
-    status::FileStatus,
+    Commit, Oid, StageAll, StageAndNext, ToggleStaged, UnstageAll, UnstageAndNext,
+    repository::{Branch, RepoPath, Upstream, UpstreamTracking, UpstreamTrackingStatus},
+    status::{FileStatus, StatusCode, TrackedStatus},
 };
 use gpui::{
     Action, AnyElement, AnyView, App, AppContext as _, AsyncWindowContext, Entity, EventEmitter,
@@ -32,6 +32,7 @@ use project::{
 use settings::{Settings, SettingsStore};
 use std::any::{Any, TypeId};
 use std::ops::Range;
+use std::sync::Arc;
 use theme::ActiveTheme;
 use ui::{KeyBinding, Tooltip, prelude::*, vertical_divider};
 use util::ResultExt as _;
@@ -47,11 +48,23 @@ actions!(
     [
         /// Shows the diff between the working directory and the index.
         Diff,
+        /// Shows the diff between the working directory and a chosen branch, tag or commit.
+        DiffAgainstBase,
         /// Adds files to the git staging area.
         Add
     ]
 );
 
+/// A branch, tag or commit that the project diff compares the working directory to, instead of
+/// `HEAD` and the index.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiffBase {
+    pub name: SharedString,
+    /// Whether to compare to the merge base of `name` and `HEAD`, so that only the changes made
+    /// since the current branch diverged from `name` are shown.
+    pub merge_base: bool,
+}
+
 pub struct ProjectDiff {
     project: Entity<Project>,
     multibuffer: Entity<MultiBuffer>,
@@ -61,6 +74,8 @@ pub struct ProjectDiff {
     focus_handle: FocusHandle,
     update_needed: postage::watch::Sender<()>,
     pending_scroll: Option<PathKey>,
+    base: Option<DiffBase>,
+    base_error: Option<SharedString>,
     _task: Task<Result<()>>,
     _subscription: Subscription,
 }
@@ -95,21 +110,26 @@ impl ProjectDiff {
         Self::deploy_at(workspace, None, window, cx)
     }
 
-    pub fn deploy_at(
+    /// Opens the project diff, comparing the working directory to the given base.
+    pub fn deploy_with_base(
         workspace: &mut Workspace,
-        entry: Option<GitStatusEntry>,
+        base: DiffBase,
         window: &mut Window,
         cx: &mut Context<Workspace>,
     ) {
-        telemetry::event!(
-            "Git Diff Opened",
-            source = if entry.is_some() {
-                "Git Panel"
-            } else {
-                "Action"
-            }
-        );
-        let project_diff = if let Some(existing) = workspace.item_of_type::<Self>(cx) {
+        telemetry::event!("Git Diff Opened", source = "Diff Base");
+        let project_diff = Self::get_or_create(workspace, window, cx);
+        project_diff.update(cx, |project_diff, cx| {
+            project_diff.set_base(Some(base), cx);
+        });
+    }
+
+    fn get_or_create(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        if let Some(existing) = workspace.item_of_type::<Self>(cx) {
             workspace.activate_item(&existing, true, true, window, cx);
             existing
         } else {
@@ -124,11 +144,72 @@ impl ProjectDiff {
                 cx,
             );
             project_diff
-        };
-        if let Some(entry) = entry {
-            project_diff.update(cx, |project_diff, cx| {
+        }
+    }
+
+    pub fn deploy_at(
+        workspace: &mut Workspace,
+        entry: Option<GitStatusEntry>,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        telemetry::event!(
+            "Git Diff Opened",
+            source = if entry.is_some() {
+                "Git Panel"
+            } else {
+                "Action"
+            }
+        );
+        let project_diff = Self::get_or_create(workspace, window, cx);
+        project_diff.update(cx, |project_diff, cx| {
+            project_diff.set_base(None, cx);
+            if let Some(entry) = entry {
                 project_diff.move_to_entry(entry, window, cx);
-            })
+            }
+        })
+    }
+
+    /// Changes what the working directory is compared to, which is `HEAD` and the index when no
+    /// base is given. Hunks can only be staged when comparing to them.
+    pub fn set_base(&mut self, base: Option<DiffBase>, cx: &mut Context<Self>) {
+        if self.base == base {
+            return;
+        }
+        let had_base = self.base.is_some();
+        let has_base = base.is_some();
+        self.base = base;
+        self.base_error = None;
+
+        self.editor.update(cx, |editor, cx| {
+            if has_base {
+                // Keep the editor from replacing the diffs against the base with the uncommitted ones.
+                editor.start_temporary_diff_override();
+                editor.set_render_diff_hunk_controls(
+                    Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
+                    cx,
+                );
+            } else if had_base {
+                editor.end_temporary_diff_override(cx);
+                editor.set_expand_all_diff_hunks(cx);
+            }
+        });
+        // The files changed since the previous base may not have changed since the new one.
+        self.multibuffer
+            .update(cx, |multibuffer, cx| multibuffer.clear(cx));
+        *self.update_needed.borrow_mut() = ();
+        cx.emit(EditorEvent::TitleChanged);
+        cx.notify();
+    }
+
+    pub fn base(&self) -> Option<&DiffBase> {
+        self.base.as_ref()
+    }
+
+    fn title(&self) -> SharedString {
+        match &self.base {
+            Some(base) => format!("Changes Since {}", base.name).into(),
+            None => "Uncommitted Changes".into(),
         }
     }
 
@@ -218,6 +299,8 @@ impl ProjectDiff {
             editor,
             multibuffer,
             pending_scroll: None,
+            base: None,
+            base_error: None,
             update_needed: send,
             _task: worker,
             _subscription: git_store_subscription,
@@ -369,6 +452,68 @@ impl ProjectDiff {
     }
 
     fn load_buffers(&mut self, cx: &mut Context<Self>) -> Vec<Task<Result<DiffBuffer>>> {
+        let entries = self
+            .git_store
+            .read(cx)
+            .active_repository()
+            .map(|repo| {
+                repo.read(cx)
+                    .cached_status()
+                    .filter(|entry| entry.status.has_changes())
+                    .map(|entry| (entry.repo_path, entry.status))
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.load_buffers_for_entries(entries, None, cx)
+    }
+
+    /// Lists the files of the active repository that changed since the base, along with the
+    /// commit the base resolved to. Uncommitted changes take precedence over committed ones.
+    fn changes_since_base(
+        &self,
+        base: &DiffBase,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<(Oid, Vec<(RepoPath, FileStatus)>)>> {
+        let Some(repo) = self.git_store.read(cx).active_repository() else {
+            return Task::ready(Err(anyhow!("no active repository")));
+        };
+        let resolve_base = repo.update(cx, |repo, _| {
+            repo.resolve_diff_base(base.name.to_string(), base.merge_base)
+        });
+        cx.spawn(async move |_, cx| {
+            let commit = resolve_base.await??;
+            let committed_changes = repo
+                .update(cx, |repo, _| repo.changed_paths_since(commit))?
+                .await??;
+            let mut entries = committed_changes
+                .into_iter()
+                .map(|(repo_path, status)| {
+                    let status = FileStatus::Tracked(TrackedStatus {
+                        index_status: status,
+                        worktree_status: StatusCode::Unmodified,
+                    });
+                    (repo_path, status)
+                })
+                .collect::<BTreeMap<_, _>>();
+            repo.read_with(cx, |repo, _| {
+                for entry in repo.cached_status() {
+                    if entry.status.has_changes() {
+                        entries.insert(entry.repo_path, entry.status);
+                    }
+                }
+            })?;
+            Ok((commit, entries.into_iter().collect()))
+        })
+    }
+
+    /// Opens the buffers of the given files, diffed against the commit if there is one and against
+    /// the index and `HEAD` otherwise, and removes the excerpts of any other files.
+    fn load_buffers_for_entries(
+        &mut self,
+        entries: Vec<(RepoPath, FileStatus)>,
+        commit: Option<Oid>,
+        cx: &mut Context<Self>,
+    ) -> Vec<Task<Result<DiffBuffer>>> {
         let Some(repo) = self.git_store.read(cx).active_repository() else {
             self.multibuffer.update(cx, |multibuffer, cx| {
                 multibuffer.clear(cx);
@@ -380,24 +525,24 @@ impl ProjectDiff {
 
         let mut result = vec![];
         repo.update(cx, |repo, cx| {
-            for entry in repo.cached_status() {
-                if !entry.status.has_changes() {
+            for (repo_path, status) in entries {
+                // A submodule is a directory, so there's no buffer to show its pointer change in.
+                if repo.submodule_for_path(&repo_path).is_some() {
                     continue;
                 }
-                let Some(project_path) = repo.repo_path_to_project_path(&entry.repo_path, cx)
-                else {
+                let Some(project_path) = repo.repo_path_to_project_path(&repo_path, cx) else {
                     continue;
                 };
                 let namespace = if GitPanelSettings::get_global(cx).sort_by_path {
                     TRACKED_NAMESPACE
-                } else if repo.had_conflict_on_last_merge_head_change(&entry.repo_path) {
+                } else if repo.had_conflict_on_last_merge_head_change(&repo_path) {
                     CONFLICT_NAMESPACE
-                } else if entry.status.is_created() {
+                } else if status.is_created() {
                     NEW_NAMESPACE
                 } else {
                     TRACKED_NAMESPACE
                 };
-                let path_key = PathKey::namespaced(namespace, entry.repo_path.as_unix_str().into());
+                let path_key = PathKey::namespaced(namespace, repo_path.as_unix_str().into());
 
                 previous_paths.remove(&path_key);
                 let load_buffer = self
@@ -408,15 +553,16 @@ impl ProjectDiff {
                 result.push(cx.spawn(async move |_, cx| {
                     let buffer = load_buffer.await?;
                     let changes = project
-                        .update(cx, |project, cx| {
-                            project.open_uncommitted_diff(buffer.clone(), cx)
+                        .update(cx, |project, cx| match commit {
+                            Some(commit) => project.open_diff_since(commit, buffer.clone(), cx),
+                            None => project.open_uncommitted_diff(buffer.clone(), cx),
                         })?
                         .await?;
                     Ok(DiffBuffer {
                         path_key,
                         buffer,
                         diff: changes,
-                        file_status: entry.status,
+                        file_status: status,
                     })
                 }));
             }
@@ -437,7 +583,6 @@ impl ProjectDiff {
     ) {
         let path_key = diff_buffer.path_key;
         let buffer = diff_buffer.buffer;
-        let diff = diff_buffer.diff;
 
         let conflict_addon = self
             .editor
@@ -446,8 +591,9 @@ impl ProjectDiff {
             .expect("project diff editor should have a conflict addon");
 
         let snapshot = buffer.read(cx).snapshot();
-        let diff = diff.read(cx);
-        let diff_hunk_ranges = diff
+        let diff_hunk_ranges = diff_buffer
+            .diff
+            .read(cx)
             .hunks_intersecting_range(Anchor::MIN..Anchor::MAX, &snapshot, cx)
             .map(|diff_hunk| diff_hunk.buffer_range);
         let conflicts = conflict_addon
@@ -460,8 +606,11 @@ impl ProjectDiff {
             .map(|range| range.to_point(&snapshot))
             .collect::<Vec<_>>();
 
+        let diff = diff_buffer.diff;
         let (was_empty, is_excerpt_newly_added) = self.multibuffer.update(cx, |multibuffer, cx| {
             let was_empty = multibuffer.is_empty();
+            // Switching bases replaces the diff of buffers that are already in the multibuffer.
+            multibuffer.add_diff(diff, cx);
             let (_, is_newly_added) = multibuffer.set_excerpts_for_path(
                 path_key.clone(),
                 buffer,
@@ -512,7 +661,31 @@ impl ProjectDiff {
         cx: &mut AsyncWindowContext,
     ) -> Result<()> {
         while (recv.next().await).is_some() {
-            let buffers_to_load = this.update(cx, |this, cx| this.load_buffers(cx))?;
+            let base = this.read_with(cx, |this, _| this.base.clone())?;
+            let buffers_to_load = if let Some(base) = base {
+                let changes = this
+                    .update(cx, |this, cx| this.changes_since_base(&base, cx))?
+                    .await;
+                this.update(cx, |this, cx| {
+                    if this.base.as_ref() != Some(&base) {
+                        // The base changed while its changes were listed, and will be reloaded.
+                        return Vec::new();
+                    }
+                    match changes {
+                        Ok((commit, entries)) => {
+                            this.base_error = None;
+                            this.load_buffers_for_entries(entries, Some(commit), cx)
+                        }
+                        Err(error) => {
+                            log::error!("failed to list changes since {}: {error:?}", base.name);
+                            this.base_error = Some(error.to_string().into());
+                            this.load_buffers_for_entries(Vec::new(), None, cx)
+                        }
+                    }
+                })?
+            } else {
+                this.update(cx, |this, cx| this.load_buffers(cx))?
+            };
             for buffer_to_load in buffers_to_load {
                 if let Some(buffer) = buffer_to_load.await.log_err() {
                     cx.update(|window, cx| {
@@ -579,11 +752,21 @@ impl Item for ProjectDiff {
     }
 
     fn tab_tooltip_text(&self, _: &App) -> Option<SharedString> {
-        Some("Project Diff".into())
+        match &self.base {
+            Some(base) if base.merge_base => Some(
+                format!(
+                    "Project Diff against the merge base of {} and HEAD",
+                    base.name
+                )
+                .into(),
+            ),
+            Some(base) => Some(format!("Project Diff against {}", base.name).into()),
+            None => Some("Project Diff".into()),
+        }
     }
 
     fn tab_content(&self, params: TabContentParams, _window: &Window, _: &App) -> AnyElement {
-        Label::new("Uncommitted Changes")
+        Label::new(self.title())
             .color(if params.selected {
                 Color::Default
             } else {
@@ -593,7 +776,7 @@ impl Item for ProjectDiff {
     }
 
     fn tab_content_text(&self, _detail: usize, _: &App) -> SharedString {
-        "Uncommitted Changes".into()
+        self.title()
     }
 
     fn telemetry_event_text(&self) -> Option<&'static str> {
@@ -637,7 +820,12 @@ impl Item for ProjectDiff {
         Self: Sized,
     {
         let workspace = self.workspace.upgrade()?;
-        Some(cx.new(|cx| ProjectDiff::new(self.project.clone(), workspace, window, cx)))
+        let base = self.base.clone();
+        Some(cx.new(|cx| {
+            let mut project_diff = ProjectDiff::new(self.project.clone(), workspace, window, cx);
+            project_diff.set_base(base, cx);
+            project_diff
+        }))
     }
 
     fn is_dirty(&self, cx: &App) -> bool {
@@ -739,15 +927,17 @@ impl Render for ProjectDiff {
                     None
                 };
                 let keybinding_focus_handle = self.focus_handle(cx);
+                let message: SharedString = match (&self.base, &self.base_error) {
+                    (Some(_), Some(error)) => error.clone(),
+                    (Some(base), None) => format!("No changes since {}", base.name).into(),
+                    (None, _) => "No uncommitted changes".into(),
+                };
+                let has_base = self.base.is_some();
                 el.child(
                     v_flex()
                         .gap_1()
-                        .child(
-                            h_flex()
-                                .justify_around()
-                                .child(Label::new("No uncommitted changes")),
-                        )
-                        .map(|el| match remote_button {
+                        .child(h_flex().justify_around().child(Label::new(message)))
+                        .when(!has_base, |el| match remote_button {
                             Some(button) => el.child(h_flex().justify_around().child(button)),
                             None => el.child(
                                 h_flex()
@@ -923,6 +1113,8 @@ impl Render for ProjectDiffToolbar {
         };
         let focus_handle = project_diff.focus_handle(cx);
         let button_states = project_diff.read(cx).button_states(cx);
+        // Hunks can't be staged when comparing to a base, as they aren't relative to the index.
+        let has_base = project_diff.read(cx).base.is_some();
 
         h_group_xl()
             .my_neg_1()
@@ -930,47 +1122,49 @@ impl Render for ProjectDiffToolbar {
             .items_center()
             .flex_wrap()
             .justify_between()
-            .child(
-                h_group_sm()
-                    .when(button_states.selection, |el| {
-                        el.child(
-                            Button::new("stage", "Toggle Staged")
-                                .tooltip(Tooltip::for_action_title_in(
-                                    "Toggle Staged",
-                                    &ToggleStaged,
-                                    &focus_handle,
-                                ))
-                                .disabled(!button_states.stage && !button_states.unstage)
-                                .on_click(cx.listener(|this, _, window, cx| {
-                                    this.dispatch_action(&ToggleStaged, window, cx)
-                                })),
-                        )
-                    })
-                    .when(!button_states.selection, |el| {
-                        el.child(
-                            Button::new("stage", "Stage")
-                                .tooltip(Tooltip::for_action_title_in(
-                                    "Stage and go to next hunk",
-                                    &StageAndNext,
-                                    &focus_handle,
-                                ))
-                                .on_click(cx.listener(|this, _, window, cx| {
-                                    this.dispatch_action(&StageAndNext, window, cx)
-                                })),
-                        )
-                        .child(
-                            Button::new("unstage", "Unstage")
-                                .tooltip(Tooltip::for_action_title_in(
-                                    "Unstage and go to next hunk",
-                                    &UnstageAndNext,
-                                    &focus_handle,
-                                ))
-                                .on_click(cx.listener(|this, _, window, cx| {
-                                    this.dispatch_action(&UnstageAndNext, window, cx)
-                                })),
-                        )
-                    }),
-            )
+            .when(!has_base, |el| {
+                el.child(
+                    h_group_sm()
+                        .when(button_states.selection, |el| {
+                            el.child(
+                                Button::new("stage", "Toggle Staged")
+                                    .tooltip(Tooltip::for_action_title_in(
+                                        "Toggle Staged",
+                                        &ToggleStaged,
+                                        &focus_handle,
+                                    ))
+                                    .disabled(!button_states.stage && !button_states.unstage)
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.dispatch_action(&ToggleStaged, window, cx)
+                                    })),
+                            )
+                        })
+                        .when(!button_states.selection, |el| {
+                            el.child(
+                                Button::new("stage", "Stage")
+                                    .tooltip(Tooltip::for_action_title_in(
+                                        "Stage and go to next hunk",
+                                        &StageAndNext,
+                                        &focus_handle,
+                                    ))
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.dispatch_action(&StageAndNext, window, cx)
+                                    })),
+                            )
+                            .child(
+                                Button::new("unstage", "Unstage")
+                                    .tooltip(Tooltip::for_action_title_in(
+                                        "Unstage and go to next hunk",
+                                        &UnstageAndNext,
+                                        &focus_handle,
+                                    ))
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.dispatch_action(&UnstageAndNext, window, cx)
+                                    })),
+                            )
+                        }),
+                )
+            })
             // n.b. the only reason these arrows are here is because we don't
             // support "undo" for staging so we need a way to go back.
             .child(
@@ -1003,57 +1197,86 @@ impl Render for ProjectDiffToolbar {
                     ),
             )
             .child(vertical_divider())
-            .child(
-                h_group_sm()
-                    .when(
-                        button_states.unstage_all && !button_states.stage_all,
-                        |el| {
-                            el.child(
-                                Button::new("unstage-all", "Unstage All")
-                                    .tooltip(Tooltip::for_action_title_in(
-                                        "Unstage all changes",
-                                        &UnstageAll,
-                                        &focus_handle,
-                                    ))
-                                    .on_click(cx.listener(|this, _, window, cx| {
-                                        this.unstage_all(window, cx)
-                                    })),
-                            )
-                        },
-                    )
-                    .when(
-                        !button_states.unstage_all || button_states.stage_all,
-                        |el| {
-                            el.child(
-                                // todo make it so that changing to say "Unstaged"
-                                // doesn't change the position.
-                                div().child(
-                                    Button::new("stage-all", "Stage All")
-                                        .disabled(!button_states.stage_all)
+            .when(has_base, |el| {
+                el.child(
+                    h_group_sm()
+                        .child(
+                            Button::new("change-base", "Change Base")
+                                .tooltip(Tooltip::for_action_title_in(
+                                    "Compare to another branch, tag or commit",
+                                    &DiffAgainstBase,
+                                    &focus_handle,
+                                ))
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.dispatch_action(&DiffAgainstBase, window, cx)
+                                })),
+                        )
+                        .child(
+                            Button::new("uncommitted-changes", "Uncommitted Changes")
+                                .tooltip(Tooltip::for_action_title_in(
+                                    "Show uncommitted changes",
+                                    &Diff,
+                                    &focus_handle,
+                                ))
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.dispatch_action(&Diff, window, cx)
+                                })),
+                        ),
+                )
+            })
+            .when(!has_base, |el| {
+                el.child(
+                    h_group_sm()
+                        .when(
+                            button_states.unstage_all && !button_states.stage_all,
+                            |el| {
+                                el.child(
+                                    Button::new("unstage-all", "Unstage All")
                                         .tooltip(Tooltip::for_action_title_in(
-                                            "Stage all changes",
-                                            &StageAll,
+                                            "Unstage all changes",
+                                            &UnstageAll,
                                             &focus_handle,
                                         ))
                                         .on_click(cx.listener(|this, _, window, cx| {
-                                            this.stage_all(window, cx)
+                                            this.unstage_all(window, cx)
                                         })),
-                                ),
-                            )
-                        },
-                    )
-                    .child(
-                        Button::new("commit", "Commit")
-                            .tooltip(Tooltip::for_action_title_in(
-                                "Commit",
-                                &Commit,
-                                &focus_handle,
-                            ))
-                            .on_click(cx.listener(|this, _, window, cx| {
-                                this.dispatch_action(&Commit, window, cx);
-                            })),
-                    ),
-            )
+                                )
+                            },
+                        )
+                        .when(
+                            !button_states.unstage_all || button_states.stage_all,
+                            |el| {
+                                el.child(
+                                    // todo make it so that changing to say "Unstaged"
+                                    // doesn't change the position.
+                                    div().child(
+                                        Button::new("stage-all", "Stage All")
+                                            .disabled(!button_states.stage_all)
+                                            .tooltip(Tooltip::for_action_title_in(
+                                                "Stage all changes",
+                                                &StageAll,
+                                                &focus_handle,
+                                            ))
+                                            .on_click(cx.listener(|this, _, window, cx| {
+                                                this.stage_all(window, cx)
+                                            })),
+                                    ),
+                                )
+                            },
+                        )
+                        .child(
+                            Button::new("commit", "Commit")
+                                .tooltip(Tooltip::for_action_title_in(
+                                    "Commit",
+                                    &Commit,
+                                    &focus_handle,
+                                ))
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.dispatch_action(&Commit, window, cx);
+                                })),
+                        ),
+                )
+            })
     }
 }
 
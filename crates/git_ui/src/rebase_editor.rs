@@ -0,0 +1,561 @@
+//! Modals standing in for the editor git opens during an interactive rebase: one to reorder the
+//! todo list and change what happens to each commit, and one to edit commit messages.
+
+use askpass::GitEditorRequest;
+use editor::Editor;
+use futures::channel::oneshot;
+use git::rebase::{
+    REBASE_TODO_FILE_NAME, RebaseAction, RebaseTodo, RebaseTodoCommit, RebaseTodoEntry,
+};
+use gpui::{
+    Action, App, ClickEvent, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    ScrollHandle, actions,
+};
+use ui::{KeyBinding, Tooltip, prelude::*};
+use workspace::{DismissDecision, ModalView, Workspace};
+
+actions!(
+    rebase_todo,
+    [
+        /// Uses the selected commit.
+        Pick,
+        /// Uses the selected commit, but edits its message.
+        Reword,
+        /// Uses the selected commit, but stops the rebase to amend it.
+        Edit,
+        /// Melds the selected commit into the previous one, combining their messages.
+        Squash,
+        /// Melds the selected commit into the previous one, discarding its message.
+        Fixup,
+        /// Removes the selected commit.
+        DropCommit,
+        /// Moves the selected entry up in the todo list.
+        MoveUp,
+        /// Moves the selected entry down in the todo list.
+        MoveDown,
+    ]
+);
+
+/// The name of the file git asks the editor to edit for commit messages.
+const COMMIT_MESSAGE_FILE_NAME: &str = "COMMIT_EDITMSG";
+
+/// Opens the modal for the file git asked its editor to edit, sending the edited content to `tx`.
+///
+/// Files other than the todo list and commit messages are sent back unchanged.
+pub(crate) fn edit(
+    workspace: &mut Workspace,
+    request: GitEditorRequest,
+    tx: oneshot::Sender<String>,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    match request.file_name.as_str() {
+        REBASE_TODO_FILE_NAME => {
+            let todo = RebaseTodo::parse(&request.content);
+            workspace.toggle_modal(window, cx, |window, cx| {
+                RebaseTodoEditor::new(todo, tx, window, cx)
+            });
+        }
+        COMMIT_MESSAGE_FILE_NAME => {
+            workspace.toggle_modal(window, cx, |window, cx| {
+                RebaseMessageEditor::new(request.content, tx, window, cx)
+            });
+        }
+        _ => {
+            tx.send(request.content).ok();
+        }
+    }
+}
+
+/// Edits the todo list of an interactive rebase. Dismissing it aborts the rebase.
+pub(crate) struct RebaseTodoEditor {
+    todo: RebaseTodo,
+    selected_index: usize,
+    error: Option<SharedString>,
+    tx: Option<oneshot::Sender<String>>,
+    focus_handle: FocusHandle,
+    scroll_handle: ScrollHandle,
+}
+
+impl EventEmitter<DismissEvent> for RebaseTodoEditor {}
+impl ModalView for RebaseTodoEditor {}
+
+impl Focusable for RebaseTodoEditor {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl RebaseTodoEditor {
+    fn new(
+        todo: RebaseTodo,
+        tx: oneshot::Sender<String>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        Self {
+            todo,
+            selected_index: 0,
+            error: None,
+            tx: Some(tx),
+            focus_handle: cx.focus_handle(),
+            scroll_handle: ScrollHandle::new(),
+        }
+    }
+
+    fn select_index(&mut self, index: usize, cx: &mut Context<Self>) {
+        self.selected_index = index.min(self.todo.entries.len().saturating_sub(1));
+        self.scroll_handle.scroll_to_item(self.selected_index);
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_index(self.selected_index + 1, cx);
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_index(self.selected_index.saturating_sub(1), cx);
+    }
+
+    fn select_first(&mut self, _: &menu::SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_index(0, cx);
+    }
+
+    fn select_last(&mut self, _: &menu::SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_index(usize::MAX, cx);
+    }
+
+    fn set_action(&mut self, index: usize, action: RebaseAction, cx: &mut Context<Self>) {
+        if let Some(RebaseTodoEntry::Commit(commit)) = self.todo.entries.get_mut(index) {
+            commit.action = action;
+            self.error = None;
+            cx.notify();
+        }
+    }
+
+    fn cycle_action(&mut self, index: usize, cx: &mut Context<Self>) {
+        if let Some(RebaseTodoEntry::Commit(commit)) = self.todo.entries.get(index) {
+            let position = RebaseAction::ALL
+                .iter()
+                .position(|action| *action == commit.action)
+                .unwrap_or_default();
+            let action = RebaseAction::ALL[(position + 1) % RebaseAction::ALL.len()];
+            self.set_action(index, action, cx);
+        }
+    }
+
+    fn pick(&mut self, _: &Pick, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(self.selected_index, RebaseAction::Pick, cx);
+    }
+
+    fn reword(&mut self, _: &Reword, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(self.selected_index, RebaseAction::Reword, cx);
+    }
+
+    fn edit(&mut self, _: &Edit, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(self.selected_index, RebaseAction::Edit, cx);
+    }
+
+    fn squash(&mut self, _: &Squash, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(self.selected_index, RebaseAction::Squash, cx);
+    }
+
+    fn fixup(&mut self, _: &Fixup, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(self.selected_index, RebaseAction::Fixup, cx);
+    }
+
+    fn drop_commit(&mut self, _: &DropCommit, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(self.selected_index, RebaseAction::Drop, cx);
+    }
+
+    fn move_entry(&mut self, from: usize, to: usize, cx: &mut Context<Self>) {
+        let len = self.todo.entries.len();
+        if from >= len || to >= len || from == to {
+            return;
+        }
+        let entry = self.todo.entries.remove(from);
+        self.todo.entries.insert(to, entry);
+        self.error = None;
+        self.select_index(to, cx);
+    }
+
+    fn move_up(&mut self, _: &MoveUp, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(to) = self.selected_index.checked_sub(1) {
+            self.move_entry(self.selected_index, to, cx);
+        }
+    }
+
+    fn move_down(&mut self, _: &MoveDown, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_entry(self.selected_index, self.selected_index + 1, cx);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _: &mut Window, cx: &mut Context<Self>) {
+        if let Err(error) = self.todo.validate() {
+            self.error = Some(error.to_string().into());
+            cx.notify();
+            return;
+        }
+        if let Some(tx) = self.tx.take() {
+            tx.send(self.todo.to_string()).ok();
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_entry(&self, index: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let entry = &self.todo.entries[index];
+        let is_selected = index == self.selected_index;
+        let dragged = DraggedRebaseEntry {
+            index,
+            label: match entry {
+                RebaseTodoEntry::Commit(commit) => commit.subject.clone(),
+                RebaseTodoEntry::Command(command) => command.clone(),
+            },
+        };
+
+        h_flex()
+            .id(("rebase-todo-entry", index))
+            .w_full()
+            .px_2()
+            .py_0p5()
+            .gap_2()
+            .rounded_sm()
+            .cursor_grab()
+            .when(is_selected, |row| {
+                row.bg(cx.theme().colors().element_selected)
+            })
+            .hover(|row| row.bg(cx.theme().colors().element_hover))
+            .on_click(cx.listener(move |this, _: &ClickEvent, _, cx| {
+                this.select_index(index, cx);
+            }))
+            .on_drag(dragged, |entry, _, _, cx| cx.new(|_| entry.clone()))
+            .drag_over::<DraggedRebaseEntry>(|style, _, _, cx| {
+                style.bg(cx.theme().colors().drop_target_background)
+            })
+            .on_drop(cx.listener(move |this, entry: &DraggedRebaseEntry, _, cx| {
+                this.move_entry(entry.index, index, cx);
+            }))
+            .map(|row| match entry {
+                RebaseTodoEntry::Commit(commit) => {
+                    row.children(self.render_commit(index, commit, cx))
+                }
+                RebaseTodoEntry::Command(command) => row.child(
+                    Label::new(command.clone())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .buffer_font(cx)
+                        .truncate(),
+                ),
+            })
+    }
+
+    fn render_commit(
+        &self,
+        index: usize,
+        commit: &RebaseTodoCommit,
+        cx: &mut Context<Self>,
+    ) -> [AnyElement; 3] {
+        let action_color = match commit.action {
+            RebaseAction::Pick => Color::Default,
+            RebaseAction::Reword | RebaseAction::Edit => Color::Accent,
+            RebaseAction::Squash | RebaseAction::Fixup => Color::Warning,
+            RebaseAction::Drop => Color::Error,
+        };
+        let is_dropped = commit.action == RebaseAction::Drop;
+        let subject = Label::new(commit.subject.clone())
+            .size(LabelSize::Small)
+            .truncate()
+            .when(is_dropped, |label| {
+                label.strikethrough().color(Color::Muted)
+            });
+
+        [
+            div()
+                .w(rems(4.5))
+                .flex_none()
+                .child(
+                    Button::new(("rebase-action", index), commit.action.as_str())
+                        .label_size(LabelSize::Small)
+                        .color(action_color)
+                        .full_width()
+                        .tooltip(Tooltip::text("Click to change the action"))
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.select_index(index, cx);
+                            this.cycle_action(index, cx);
+                        })),
+                )
+                .into_any_element(),
+            Label::new(commit.sha.clone())
+                .size(LabelSize::Small)
+                .color(Color::Muted)
+                .buffer_font(cx)
+                .into_any_element(),
+            div()
+                .flex_1()
+                .overflow_hidden()
+                .child(subject)
+                .into_any_element(),
+        ]
+    }
+}
+
+impl Render for RebaseTodoEditor {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle.clone();
+        let commit_count = self.todo.commits().count();
+
+        v_flex()
+            .key_context("RebaseTodoEditor")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::pick))
+            .on_action(cx.listener(Self::reword))
+            .on_action(cx.listener(Self::edit))
+            .on_action(cx.listener(Self::squash))
+            .on_action(cx.listener(Self::fixup))
+            .on_action(cx.listener(Self::drop_commit))
+            .on_action(cx.listener(Self::move_up))
+            .on_action(cx.listener(Self::move_down))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .elevation_3(cx)
+            .w(rems(40.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::GitBranch).size(IconSize::XSmall))
+                    .child(Headline::new("Interactive Rebase").size(HeadlineSize::XSmall))
+                    .child(
+                        Label::new(match commit_count {
+                            1 => "1 commit".to_string(),
+                            count => format!("{count} commits"),
+                        })
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .id("rebase-todo-entries")
+                    .p_1()
+                    .max_h(rems(24.))
+                    .overflow_y_scroll()
+                    .track_scroll(&self.scroll_handle)
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .children(
+                        (0..self.todo.entries.len()).map(|index| self.render_entry(index, cx)),
+                    ),
+            )
+            .children(self.error.clone().map(|error| {
+                h_flex()
+                    .px_3()
+                    .py_1()
+                    .gap_1p5()
+                    .child(
+                        Icon::new(IconName::Warning)
+                            .size(IconSize::Small)
+                            .color(Color::Warning),
+                    )
+                    .child(Label::new(error).size(LabelSize::Small))
+            }))
+            .child(
+                h_flex()
+                    .w_full()
+                    .p_1p5()
+                    .gap_0p5()
+                    .justify_between()
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        Label::new("Drag commits to reorder them")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(
+                        h_flex()
+                            .gap_0p5()
+                            .child(
+                                Button::new("abort-rebase", "Cancel")
+                                    .key_binding(
+                                        KeyBinding::for_action_in(
+                                            &menu::Cancel,
+                                            &focus_handle,
+                                            window,
+                                            cx,
+                                        )
+                                        .map(|kb| kb.size(rems_from_px(12.))),
+                                    )
+                                    .on_click(|_, window, cx| {
+                                        window.dispatch_action(menu::Cancel.boxed_clone(), cx)
+                                    }),
+                            )
+                            .child(
+                                Button::new("start-rebase", "Rebase")
+                                    .key_binding(
+                                        KeyBinding::for_action_in(
+                                            &menu::Confirm,
+                                            &focus_handle,
+                                            window,
+                                            cx,
+                                        )
+                                        .map(|kb| kb.size(rems_from_px(12.))),
+                                    )
+                                    .on_click(|_, window, cx| {
+                                        window.dispatch_action(menu::Confirm.boxed_clone(), cx)
+                                    }),
+                            ),
+                    ),
+            )
+    }
+}
+
+#[derive(Clone)]
+struct DraggedRebaseEntry {
+    index: usize,
+    label: SharedString,
+}
+
+impl Render for DraggedRebaseEntry {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .px_2()
+            .py_0p5()
+            .rounded_sm()
+            .bg(cx.theme().colors().background)
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .child(Label::new(self.label.clone()).size(LabelSize::Small))
+    }
+}
+
+/// Edits a commit message git asks for while rebasing, such as when rewording or squashing
+/// commits. Dismissing it keeps the message git suggested.
+pub(crate) struct RebaseMessageEditor {
+    original: String,
+    editor: Entity<Editor>,
+    tx: Option<oneshot::Sender<String>>,
+}
+
+impl EventEmitter<DismissEvent> for RebaseMessageEditor {}
+
+impl ModalView for RebaseMessageEditor {
+    fn on_before_dismiss(&mut self, _: &mut Window, _: &mut Context<Self>) -> DismissDecision {
+        if let Some(tx) = self.tx.take() {
+            tx.send(self.original.clone()).ok();
+        }
+        DismissDecision::Dismiss(true)
+    }
+}
+
+impl Focusable for RebaseMessageEditor {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl RebaseMessageEditor {
+    fn new(
+        original: String,
+        tx: oneshot::Sender<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        // Git strips comments from the message once the editor exits, so they're hidden here.
+        let message = original
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::auto_height(4, 16, window, cx);
+            editor.set_text(message.trim(), window, cx);
+            editor
+        });
+        Self {
+            original,
+            editor,
+            tx: Some(tx),
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(tx) = self.tx.take() {
+            let message = self.editor.read(cx).text(cx);
+            tx.send(format!("{}\n", message.trim_end())).ok();
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+}
+
+impl Render for RebaseMessageEditor {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.editor.focus_handle(cx);
+
+        v_flex()
+            .key_context("RebaseMessageEditor")
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .elevation_3(cx)
+            .w(rems(40.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::GitBranch).size(IconSize::XSmall))
+                    .child(Headline::new("Commit Message").size(HeadlineSize::XSmall)),
+            )
+            .child(
+                div()
+                    .px_3()
+                    .py_2()
+                    .bg(cx.theme().colors().editor_background)
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(self.editor.clone()),
+            )
+            .child(
+                h_flex()
+                    .w_full()
+                    .p_1p5()
+                    .justify_end()
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        Button::new("confirm-message", "Continue")
+                            .key_binding(
+                                KeyBinding::for_action_in(
+                                    &menu::Confirm,
+                                    &focus_handle,
+                                    window,
+                                    cx,
+                                )
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                            )
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(menu::Confirm.boxed_clone(), cx)
+                            }),
+                    ),
+            )
+    }
+}
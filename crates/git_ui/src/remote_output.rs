@@ -1,5 +1,5 @@
 use anyhow::Context as _;
-use git::repository::{Remote, RemoteCommandOutput};
+use git::repository::{Remote, RemoteCommandOutput, SubmoduleOperation};
 use linkify::{LinkFinder, LinkKind};
 use ui::SharedString;
 use util::ResultExt as _;
@@ -9,6 +9,7 @@ pub enum RemoteAction {
     Fetch(Option<Remote>),
     Pull(Remote),
     Push(SharedString, Remote),
+    Submodule(SubmoduleOperation),
 }
 
 impl RemoteAction {
@@ -17,6 +18,9 @@ impl RemoteAction {
             RemoteAction::Fetch(_) => "fetch",
             RemoteAction::Pull(_) => "pull",
             RemoteAction::Push(_, _) => "push",
+            RemoteAction::Submodule(SubmoduleOperation::Init) => "submodule init",
+            RemoteAction::Submodule(SubmoduleOperation::Update) => "submodule update",
+            RemoteAction::Submodule(SubmoduleOperation::Sync) => "submodule sync",
         }
     }
 }
@@ -156,6 +160,43 @@ pub fn format_output(action: &RemoteAction, output: RemoteCommandOutput) -> Succ
                 style: style.unwrap_or(SuccessStyle::ToastWithLog { output }),
             }
         }
+        RemoteAction::Submodule(operation) => {
+            if output.stdout.trim().is_empty() && output.stderr.trim().is_empty() {
+                let message = match operation {
+                    SubmoduleOperation::Init => "Submodules: Already initialized",
+                    SubmoduleOperation::Update => "Submodules: Already up to date",
+                    SubmoduleOperation::Sync => "Submodules: Nothing to synchronize",
+                };
+                return SuccessMessage {
+                    message: message.into(),
+                    style: SuccessStyle::Toast,
+                };
+            }
+            let message = match operation {
+                SubmoduleOperation::Init => "Initialized submodules".to_string(),
+                SubmoduleOperation::Update => {
+                    let updated = output
+                        .stdout
+                        .lines()
+                        .filter(|line| line.starts_with("Submodule path"))
+                        .count();
+                    if updated == 0 {
+                        "Submodules: Already up to date".to_string()
+                    } else {
+                        format!(
+                            "Updated {} submodule{}",
+                            updated,
+                            if updated == 1 { "" } else { "s" }
+                        )
+                    }
+                }
+                SubmoduleOperation::Sync => "Synchronized submodule URLs".to_string(),
+            };
+            SuccessMessage {
+                message,
+                style: SuccessStyle::ToastWithLog { output },
+            }
+        }
     }
 }
 
@@ -296,4 +337,31 @@ mod tests {
             panic!("Expected ToastWithLog variant");
         }
     }
+
+    #[test]
+    fn test_submodule_update() {
+        let action = RemoteAction::Submodule(SubmoduleOperation::Update);
+
+        let output = RemoteCommandOutput {
+            stdout: indoc! {"
+                Submodule path 'vendor/a': checked out '0123456789abcdef0123456789abcdef01234567'
+                Submodule path 'vendor/b': checked out '89abcdef0123456789abcdef0123456789abcdef'
+                "}
+            .to_string(),
+            stderr: "Cloning into '/tmp/project/vendor/b'...\n".to_string(),
+        };
+        let msg = format_output(&action, output);
+        assert_eq!(msg.message, "Updated 2 submodules");
+        assert!(matches!(msg.style, SuccessStyle::ToastWithLog { .. }));
+
+        let msg = format_output(
+            &action,
+            RemoteCommandOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+            },
+        );
+        assert_eq!(msg.message, "Submodules: Already up to date");
+        assert!(matches!(msg.style, SuccessStyle::Toast));
+    }
 }
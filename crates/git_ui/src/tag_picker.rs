@@ -0,0 +1,606 @@
+use fuzzy::StringMatchCandidate;
+
+use git::repository::{CommitSummary, Tag};
+use gpui::{
+    Action, AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement, IntoElement, ParentElement, Render, SharedString, Styled, Subscription,
+    Task, WeakEntity, Window, actions, rems,
+};
+use picker::{Picker, PickerDelegate};
+use project::git_store::{Repository, RepositoryEvent};
+use std::sync::Arc;
+use time::{OffsetDateTime, UtcOffset};
+use ui::{HighlightedLabel, KeyBinding, ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt;
+use workspace::notifications::DetachAndPromptErr;
+use workspace::{ModalView, Workspace};
+
+use crate::commit_view::CommitView;
+use crate::git_panel::GitPanel;
+use crate::tag_picker;
+
+actions!(
+    tag_picker,
+    [
+        /// Deletes the selected tag.
+        DeleteTag,
+        /// Pushes the selected tag to a remote.
+        PushTag,
+    ]
+);
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(open);
+}
+
+pub fn open(
+    workspace: &mut Workspace,
+    _: &zed_actions::git::ViewTags,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let repository = workspace.project().read(cx).active_repository(cx);
+    let workspace_handle = workspace.weak_handle();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        TagList::new(repository, workspace_handle, rems(34.), window, cx)
+    })
+}
+
+pub struct TagList {
+    width: Rems,
+    pub picker: Entity<Picker<TagListDelegate>>,
+    picker_focus_handle: FocusHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl TagList {
+    fn new(
+        repository: Option<Entity<Repository>>,
+        workspace: WeakEntity<Workspace>,
+        width: Rems,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut _subscriptions = Vec::new();
+        if let Some(repo) = repository.clone() {
+            _subscriptions.push(
+                cx.subscribe_in(&repo, window, |this, _, event, window, cx| {
+                    if matches!(event, RepositoryEvent::Updated { .. }) {
+                        this.picker.update(cx, |picker, cx| {
+                            picker.delegate.reload_tags(window, cx);
+                        });
+                    }
+                }),
+            )
+        }
+
+        let delegate = TagListDelegate::new(repository, workspace, cx);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        let picker_focus_handle = picker.focus_handle(cx);
+        picker.update(cx, |picker, cx| {
+            picker.delegate.focus_handle = picker_focus_handle.clone();
+            picker.delegate.reload_tags(window, cx);
+        });
+
+        _subscriptions.push(cx.subscribe(&picker, |_, _, _, cx| {
+            cx.emit(DismissEvent);
+        }));
+
+        Self {
+            picker,
+            picker_focus_handle,
+            width,
+            _subscriptions,
+        }
+    }
+
+    fn handle_delete_tag(&mut self, _: &DeleteTag, window: &mut Window, cx: &mut Context<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .delete_tag_at(picker.delegate.selected_index(), window, cx);
+        });
+        cx.notify();
+    }
+
+    fn handle_push_tag(&mut self, _: &PushTag, window: &mut Window, cx: &mut Context<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .push_tag_at(picker.delegate.selected_index(), window, cx);
+        });
+    }
+}
+
+impl ModalView for TagList {}
+impl EventEmitter<DismissEvent> for TagList {}
+impl Focusable for TagList {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.picker_focus_handle.clone()
+    }
+}
+
+impl Render for TagList {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("TagList")
+            .w(self.width)
+            .on_action(cx.listener(Self::handle_delete_tag))
+            .on_action(cx.listener(Self::handle_push_tag))
+            .child(self.picker.clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TagEntry {
+    tag: Tag,
+    positions: Vec<usize>,
+    formatted_timestamp: String,
+    /// Whether this entry creates a tag from the query, instead of being an existing tag.
+    is_new: bool,
+}
+
+pub struct TagListDelegate {
+    matches: Vec<TagEntry>,
+    all_tags: Option<Vec<Tag>>,
+    repo: Option<Entity<Repository>>,
+    workspace: WeakEntity<Workspace>,
+    selected_index: usize,
+    last_query: String,
+    focus_handle: FocusHandle,
+    timezone: UtcOffset,
+}
+
+impl TagListDelegate {
+    fn new(
+        repo: Option<Entity<Repository>>,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<TagList>,
+    ) -> Self {
+        let timezone =
+            UtcOffset::from_whole_seconds(chrono::Local::now().offset().local_minus_utc())
+                .unwrap_or(UtcOffset::UTC);
+
+        Self {
+            matches: vec![],
+            all_tags: None,
+            repo,
+            workspace,
+            selected_index: 0,
+            last_query: Default::default(),
+            focus_handle: cx.focus_handle(),
+            timezone,
+        }
+    }
+
+    fn format_timestamp(timestamp: i64, timezone: UtcOffset) -> String {
+        let timestamp =
+            OffsetDateTime::from_unix_timestamp(timestamp).unwrap_or(OffsetDateTime::now_utc());
+        time_format::format_localized_timestamp(
+            timestamp,
+            OffsetDateTime::now_utc(),
+            timezone,
+            time_format::TimestampFormat::EnhancedAbsolute,
+        )
+    }
+
+    /// Splits a query into the name of the tag to create and, when it's followed by more words,
+    /// the message of an annotated tag.
+    fn parse_new_tag(query: &str) -> Option<(String, Option<String>)> {
+        let query = query.trim();
+        if query.is_empty() {
+            return None;
+        }
+        Some(match query.split_once(char::is_whitespace) {
+            Some((name, message)) => (name.to_string(), Some(message.trim().to_string())),
+            None => (query.to_string(), None),
+        })
+    }
+
+    fn reload_tags(&self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let tags = repo.update(cx, |repo, _| repo.tags());
+        cx.spawn_in(window, async move |picker, cx| {
+            let tags = tags.await??;
+            picker.update_in(cx, |picker, window, cx| {
+                picker.delegate.all_tags = Some(tags);
+                picker.refresh(window, cx);
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        message: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+
+        cx.spawn(async move |_, cx| {
+            repo.update(cx, |repo, cx| {
+                repo.create_tag(name, "HEAD".to_string(), message, cx)
+            })?
+            .await??;
+            Ok(())
+        })
+        .detach_and_prompt_err("Failed to create tag", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn delete_tag_at(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.matches.get(ix).filter(|entry| !entry.is_new) else {
+            return;
+        };
+        let name = entry.tag.name.to_string();
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+
+        cx.spawn_in(window, async move |picker, cx| {
+            repo.update(cx, |repo, cx| repo.delete_tag(name, cx))?
+                .await??;
+            picker.update_in(cx, |picker, window, cx| {
+                picker.delegate.reload_tags(window, cx);
+            })?;
+            Ok(())
+        })
+        .detach_and_prompt_err("Failed to delete tag", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+
+    fn push_tag_at(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.matches.get(ix).filter(|entry| !entry.is_new) else {
+            return;
+        };
+        let Some(panel) = self
+            .workspace
+            .upgrade()
+            .and_then(|workspace| workspace.read(cx).panel::<GitPanel>(cx))
+        else {
+            return;
+        };
+        let name = entry.tag.name.clone();
+        // Choosing the remote opens another modal, which replaces this one.
+        cx.emit(DismissEvent);
+        panel.update(cx, |panel, cx| panel.push_tag(name, window, cx));
+    }
+
+    fn open_commit(&self, tag: &Tag, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(repo) = self.repo.as_ref() else {
+            return;
+        };
+        CommitView::open(
+            CommitSummary {
+                sha: tag.sha.clone(),
+                subject: tag.message.clone().unwrap_or_default(),
+                commit_timestamp: tag.timestamp,
+                author_name: SharedString::default(),
+                has_parent: true,
+            },
+            repo.downgrade(),
+            self.workspace.clone(),
+            window,
+            cx,
+        );
+        cx.emit(DismissEvent);
+    }
+}
+
+impl PickerDelegate for TagListDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select a tag or type a name to create one…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let Some(all_tags) = self.all_tags.clone() else {
+            return Task::ready(());
+        };
+
+        let timezone = self.timezone;
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let mut matches: Vec<TagEntry> = if query.is_empty() {
+                all_tags
+                    .into_iter()
+                    .map(|tag| TagEntry {
+                        formatted_timestamp: Self::format_timestamp(tag.timestamp, timezone),
+                        tag,
+                        positions: Vec::new(),
+                        is_new: false,
+                    })
+                    .collect()
+            } else {
+                let candidates = all_tags
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, tag)| StringMatchCandidate::new(ix, &tag.name))
+                    .collect::<Vec<StringMatchCandidate>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .map(|candidate| {
+                    let tag = all_tags[candidate.candidate_id].clone();
+                    TagEntry {
+                        formatted_timestamp: Self::format_timestamp(tag.timestamp, timezone),
+                        tag,
+                        positions: candidate.positions,
+                        is_new: false,
+                    }
+                })
+                .collect()
+            };
+
+            if let Some((name, message)) = Self::parse_new_tag(&query)
+                && !matches.iter().any(|entry| entry.tag.name.as_ref() == name)
+            {
+                matches.push(TagEntry {
+                    tag: Tag {
+                        name: name.into(),
+                        sha: SharedString::default(),
+                        message: message.map(Into::into),
+                        timestamp: 0,
+                    },
+                    positions: Vec::new(),
+                    formatted_timestamp: String::new(),
+                    is_new: true,
+                });
+            }
+
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    if delegate.matches.is_empty() {
+                        delegate.selected_index = 0;
+                    } else {
+                        delegate.selected_index =
+                            core::cmp::min(delegate.selected_index, delegate.matches.len() - 1);
+                    }
+                    delegate.last_query = query;
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.matches.get(self.selected_index()) else {
+            return;
+        };
+        if entry.is_new {
+            let name = entry.tag.name.to_string();
+            let message = entry.tag.message.as_ref().map(ToString::to_string);
+            self.create_tag(name, message, window, cx);
+        } else {
+            let tag = entry.tag.clone();
+            self.open_commit(&tag, window, cx);
+        }
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let entry = &self.matches[ix];
+
+        if entry.is_new {
+            let label = match &entry.tag.message {
+                Some(_) => format!("Create annotated tag \"{}\"…", entry.tag.name),
+                None => format!("Create tag \"{}\"…", entry.tag.name),
+            };
+            return Some(
+                ListItem::new(SharedString::from(format!("tag-{ix}")))
+                    .inset(true)
+                    .spacing(ListItemSpacing::Sparse)
+                    .toggle_state(selected)
+                    .child(
+                        v_flex()
+                            .w_full()
+                            .overflow_hidden()
+                            .child(
+                                h_flex()
+                                    .gap_1()
+                                    .child(
+                                        Icon::new(IconName::Plus)
+                                            .size(IconSize::Small)
+                                            .color(Color::Muted),
+                                    )
+                                    .child(Label::new(label).single_line().truncate()),
+                            )
+                            .child(
+                                Label::new(match &entry.tag.message {
+                                    Some(message) => format!("at HEAD, with message \"{message}\""),
+                                    None => "at HEAD".to_string(),
+                                })
+                                .truncate()
+                                .color(Color::Muted)
+                                .size(LabelSize::Small),
+                            ),
+                    ),
+            );
+        }
+
+        let tag_label = HighlightedLabel::new(entry.tag.name.clone(), entry.positions.clone())
+            .truncate()
+            .into_any_element();
+
+        let short_sha = &entry.tag.sha[..entry.tag.sha.len().min(7)];
+        let details = h_flex()
+            .gap_1p5()
+            .w_full()
+            .child(
+                Label::new(short_sha.to_string())
+                    .buffer_font(cx)
+                    .color(Color::Muted)
+                    .size(LabelSize::Small),
+            )
+            .when_some(entry.tag.message.clone(), |this, message| {
+                this.child(
+                    Label::new("•")
+                        .alpha(0.5)
+                        .color(Color::Muted)
+                        .size(LabelSize::Small),
+                )
+                .child(
+                    Label::new(message)
+                        .truncate()
+                        .color(Color::Muted)
+                        .size(LabelSize::Small),
+                )
+            })
+            .child(
+                Label::new("•")
+                    .alpha(0.5)
+                    .color(Color::Muted)
+                    .size(LabelSize::Small),
+            )
+            .child(
+                Label::new(entry.formatted_timestamp.clone())
+                    .color(Color::Muted)
+                    .size(LabelSize::Small),
+            );
+
+        Some(
+            ListItem::new(SharedString::from(format!("tag-{ix}")))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot(
+                    Icon::new(IconName::Bookmark)
+                        .color(Color::Muted)
+                        .size(IconSize::Small),
+                )
+                .child(
+                    v_flex()
+                        .w_full()
+                        .overflow_hidden()
+                        .child(tag_label)
+                        .child(details.into_element()),
+                )
+                .tooltip(Tooltip::text(if entry.tag.message.is_some() {
+                    format!("Annotated tag {}", entry.tag.name)
+                } else {
+                    format!("Lightweight tag {}", entry.tag.name)
+                })),
+        )
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No tags found".into())
+    }
+
+    fn render_footer(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<AnyElement> {
+        if self
+            .matches
+            .get(self.selected_index)
+            .is_none_or(|entry| entry.is_new)
+        {
+            return None;
+        }
+        let focus_handle = self.focus_handle.clone();
+
+        Some(
+            h_flex()
+                .w_full()
+                .p_1p5()
+                .gap_0p5()
+                .justify_end()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Button::new("open-tag", "Open Commit")
+                        .key_binding(
+                            KeyBinding::for_action_in(&menu::Confirm, &focus_handle, window, cx)
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(menu::Confirm.boxed_clone(), cx)
+                        }),
+                )
+                .child(
+                    Button::new("push-tag", "Push")
+                        .key_binding(
+                            KeyBinding::for_action_in(
+                                &tag_picker::PushTag,
+                                &focus_handle,
+                                window,
+                                cx,
+                            )
+                            .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(tag_picker::PushTag.boxed_clone(), cx)
+                        }),
+                )
+                .child(
+                    Button::new("delete-tag", "Delete")
+                        .key_binding(
+                            KeyBinding::for_action_in(
+                                &tag_picker::DeleteTag,
+                                &focus_handle,
+                                window,
+                                cx,
+                            )
+                            .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(tag_picker::DeleteTag.boxed_clone(), cx)
+                        }),
+                )
+                .into_any(),
+        )
+    }
+}
@@ -0,0 +1,626 @@
+use fuzzy::StringMatchCandidate;
+
+use collections::HashSet;
+use git::repository::GitWorktree;
+use gpui::{
+    Action, AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement, IntoElement, ParentElement, Render, SharedString, Styled, Subscription,
+    Task, WeakEntity, Window, actions, rems,
+};
+use picker::{Picker, PickerDelegate};
+use project::git_store::{Repository, RepositoryEvent};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use ui::{HighlightedLabel, KeyBinding, ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt;
+use workspace::notifications::DetachAndPromptErr;
+use workspace::{ModalView, OpenOptions, Workspace};
+
+use crate::worktree_picker;
+
+actions!(
+    worktree_picker,
+    [
+        /// Removes the selected worktree.
+        RemoveWorktree,
+    ]
+);
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(open);
+}
+
+pub fn open(
+    workspace: &mut Workspace,
+    _: &zed_actions::git::ViewWorktrees,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let repository = workspace.project().read(cx).active_repository(cx);
+    let workspace_handle = workspace.weak_handle();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        WorktreeList::new(repository, workspace_handle, rems(34.), window, cx)
+    })
+}
+
+pub struct WorktreeList {
+    width: Rems,
+    pub picker: Entity<Picker<WorktreeListDelegate>>,
+    picker_focus_handle: FocusHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl WorktreeList {
+    fn new(
+        repository: Option<Entity<Repository>>,
+        workspace: WeakEntity<Workspace>,
+        width: Rems,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut _subscriptions = Vec::new();
+        if let Some(repo) = repository.clone() {
+            _subscriptions.push(
+                cx.subscribe_in(&repo, window, |this, _, event, window, cx| {
+                    if matches!(event, RepositoryEvent::Updated { .. }) {
+                        this.picker.update(cx, |picker, cx| {
+                            picker.delegate.reload_worktrees(window, cx);
+                        });
+                    }
+                }),
+            )
+        }
+
+        let delegate = WorktreeListDelegate::new(repository, workspace, cx);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        let picker_focus_handle = picker.focus_handle(cx);
+        picker.update(cx, |picker, cx| {
+            picker.delegate.focus_handle = picker_focus_handle.clone();
+            picker.delegate.reload_worktrees(window, cx);
+        });
+
+        _subscriptions.push(cx.subscribe(&picker, |_, _, _, cx| {
+            cx.emit(DismissEvent);
+        }));
+
+        Self {
+            picker,
+            picker_focus_handle,
+            width,
+            _subscriptions,
+        }
+    }
+
+    fn handle_remove_worktree(
+        &mut self,
+        _: &RemoveWorktree,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .remove_worktree_at(picker.delegate.selected_index(), window, cx);
+        });
+        cx.notify();
+    }
+}
+
+impl ModalView for WorktreeList {}
+impl EventEmitter<DismissEvent> for WorktreeList {}
+impl Focusable for WorktreeList {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.picker_focus_handle.clone()
+    }
+}
+
+impl Render for WorktreeList {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("WorktreeList")
+            .w(self.width)
+            .on_action(cx.listener(Self::handle_remove_worktree))
+            .child(self.picker.clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct WorktreeEntry {
+    worktree: GitWorktree,
+    positions: Vec<usize>,
+    /// Whether this entry creates a worktree at the path typed in the query, instead of being an
+    /// existing worktree.
+    is_new: bool,
+}
+
+pub struct WorktreeListDelegate {
+    matches: Vec<WorktreeEntry>,
+    all_worktrees: Option<Vec<GitWorktree>>,
+    repo: Option<Entity<Repository>>,
+    workspace: WeakEntity<Workspace>,
+    /// The paths of the project's worktrees, to tell which git worktrees are already open.
+    project_paths: HashSet<PathBuf>,
+    is_local: bool,
+    selected_index: usize,
+    last_query: String,
+    focus_handle: FocusHandle,
+}
+
+impl WorktreeListDelegate {
+    fn new(
+        repo: Option<Entity<Repository>>,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<WorktreeList>,
+    ) -> Self {
+        let (project_paths, is_local) = workspace
+            .read_with(cx, |workspace, cx| {
+                let project = workspace.project().read(cx);
+                let project_paths = project
+                    .visible_worktrees(cx)
+                    .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+                    .collect();
+                (project_paths, project.is_local())
+            })
+            .unwrap_or_default();
+
+        Self {
+            matches: vec![],
+            all_worktrees: None,
+            repo,
+            workspace,
+            project_paths,
+            is_local,
+            selected_index: 0,
+            last_query: Default::default(),
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    /// Resolves the path typed in the query, which is relative to the directory containing the
+    /// main worktree, so that `../feature` style paths aren't needed.
+    fn path_for_query(query: &str, worktrees: &[GitWorktree]) -> Option<PathBuf> {
+        let query = query.trim();
+        if query.is_empty() {
+            return None;
+        }
+        let path = Path::new(query);
+        if path.is_absolute() {
+            return Some(path.to_path_buf());
+        }
+        let main_worktree = worktrees.iter().find(|worktree| worktree.is_main)?;
+        Some(main_worktree.path.parent()?.join(path))
+    }
+
+    fn reload_worktrees(&self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let worktrees = repo.update(cx, |repo, _| repo.worktrees());
+        cx.spawn_in(window, async move |picker, cx| {
+            let worktrees = worktrees.await??;
+            picker.update_in(cx, |picker, window, cx| {
+                picker.delegate.all_worktrees = Some(worktrees);
+                picker.refresh(window, cx);
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn create_worktree(
+        &self,
+        path: PathBuf,
+        add_to_project: bool,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+
+        let workspace = self.workspace.clone();
+        let is_local = self.is_local;
+        cx.spawn_in(window, async move |_, cx| {
+            repo.update(cx, |repo, cx| repo.create_worktree(path.clone(), None, cx))?
+                .await??;
+            cx.update(|window, cx| {
+                Self::open_worktree(path, add_to_project, is_local, workspace, window, cx)
+            })?;
+            Ok(())
+        })
+        .detach_and_prompt_err("Failed to create worktree", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn remove_worktree_at(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self
+            .matches
+            .get(ix)
+            .filter(|entry| !entry.is_new && !entry.worktree.is_main)
+        else {
+            return;
+        };
+        let path = entry.worktree.path.clone();
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+
+        cx.spawn_in(window, async move |picker, cx| {
+            repo.update(cx, |repo, cx| repo.remove_worktree(path, false, cx))?
+                .await??;
+            picker.update_in(cx, |picker, window, cx| {
+                picker.delegate.reload_worktrees(window, cx);
+            })?;
+            Ok(())
+        })
+        .detach_and_prompt_err("Failed to remove worktree", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+
+    /// Opens a worktree in a new window or, when `add_to_project` is set or the project isn't
+    /// local, adds it to the current project.
+    fn open_worktree(
+        path: PathBuf,
+        add_to_project: bool,
+        is_local: bool,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let Some(workspace) = workspace.upgrade() else {
+            return;
+        };
+        if add_to_project || !is_local {
+            let project = workspace.read(cx).project().clone();
+            project
+                .update(cx, |project, cx| {
+                    project.find_or_create_worktree(&path, true, cx)
+                })
+                .detach_and_prompt_err("Failed to add worktree", window, cx, |e, _, _| {
+                    Some(e.to_string())
+                });
+        } else {
+            let app_state = workspace.read(cx).app_state().clone();
+            workspace::open_paths(
+                &[path],
+                app_state,
+                OpenOptions {
+                    open_new_workspace: Some(true),
+                    ..Default::default()
+                },
+                cx,
+            )
+            .detach_and_log_err(cx);
+        }
+    }
+}
+
+impl PickerDelegate for WorktreeListDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select a worktree or type a path to create one…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let Some(all_worktrees) = self.all_worktrees.clone() else {
+            return Task::ready(());
+        };
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let mut matches: Vec<WorktreeEntry> = if query.is_empty() {
+                all_worktrees
+                    .iter()
+                    .cloned()
+                    .map(|worktree| WorktreeEntry {
+                        worktree,
+                        positions: Vec::new(),
+                        is_new: false,
+                    })
+                    .collect()
+            } else {
+                let candidates = all_worktrees
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, worktree)| {
+                        StringMatchCandidate::new(ix, &worktree.path.to_string_lossy())
+                    })
+                    .collect::<Vec<StringMatchCandidate>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .map(|candidate| WorktreeEntry {
+                    worktree: all_worktrees[candidate.candidate_id].clone(),
+                    positions: candidate.positions,
+                    is_new: false,
+                })
+                .collect()
+            };
+
+            if let Some(path) = Self::path_for_query(&query, &all_worktrees)
+                && !all_worktrees.iter().any(|worktree| worktree.path == path)
+            {
+                matches.push(WorktreeEntry {
+                    worktree: GitWorktree {
+                        path,
+                        sha: None,
+                        branch: None,
+                        is_main: false,
+                        is_locked: false,
+                        is_prunable: false,
+                    },
+                    positions: Vec::new(),
+                    is_new: true,
+                });
+            }
+
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    if delegate.matches.is_empty() {
+                        delegate.selected_index = 0;
+                    } else {
+                        delegate.selected_index =
+                            core::cmp::min(delegate.selected_index, delegate.matches.len() - 1);
+                    }
+                    delegate.last_query = query;
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.matches.get(self.selected_index()) else {
+            return;
+        };
+        let path = entry.worktree.path.clone();
+        if entry.is_new {
+            self.create_worktree(path, secondary, window, cx);
+        } else {
+            Self::open_worktree(
+                path,
+                secondary,
+                self.is_local,
+                self.workspace.clone(),
+                window,
+                cx,
+            );
+            cx.emit(DismissEvent);
+        }
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let entry = &self.matches[ix];
+        let worktree = &entry.worktree;
+        let path = worktree.path.to_string_lossy().into_owned();
+
+        if entry.is_new {
+            let branch = worktree
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            return Some(
+                ListItem::new(SharedString::from(format!("worktree-{ix}")))
+                    .inset(true)
+                    .spacing(ListItemSpacing::Sparse)
+                    .toggle_state(selected)
+                    .child(
+                        v_flex()
+                            .w_full()
+                            .overflow_hidden()
+                            .child(
+                                h_flex()
+                                    .gap_1()
+                                    .child(
+                                        Icon::new(IconName::Plus)
+                                            .size(IconSize::Small)
+                                            .color(Color::Muted),
+                                    )
+                                    .child(
+                                        Label::new(format!("Create worktree at \"{path}\"…"))
+                                            .single_line()
+                                            .truncate(),
+                                    ),
+                            )
+                            .child(
+                                Label::new(format!("on branch {branch}, created from HEAD if new"))
+                                    .truncate()
+                                    .color(Color::Muted)
+                                    .size(LabelSize::Small),
+                            ),
+                    ),
+            );
+        }
+
+        let is_open = self.project_paths.contains(&worktree.path);
+        let head = match (&worktree.branch, &worktree.sha) {
+            (Some(branch), _) => branch.to_string(),
+            (None, Some(sha)) => format!("detached at {}", &sha[..sha.len().min(7)]),
+            (None, None) => "bare".to_string(),
+        };
+        let details = h_flex()
+            .gap_1p5()
+            .w_full()
+            .child(
+                h_flex()
+                    .gap_0p5()
+                    .child(
+                        Icon::new(IconName::GitBranch)
+                            .color(Color::Muted)
+                            .size(IconSize::Small),
+                    )
+                    .child(
+                        Label::new(head)
+                            .truncate()
+                            .color(Color::Muted)
+                            .size(LabelSize::Small),
+                    ),
+            )
+            .children(
+                [
+                    worktree.is_main.then_some("main worktree"),
+                    is_open.then_some("in project"),
+                    worktree.is_locked.then_some("locked"),
+                    worktree.is_prunable.then_some("missing"),
+                ]
+                .into_iter()
+                .flatten()
+                .flat_map(|marker| {
+                    [
+                        Label::new("•")
+                            .alpha(0.5)
+                            .color(Color::Muted)
+                            .size(LabelSize::Small),
+                        Label::new(marker)
+                            .color(Color::Muted)
+                            .size(LabelSize::Small),
+                    ]
+                }),
+            );
+
+        Some(
+            ListItem::new(SharedString::from(format!("worktree-{ix}")))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot(
+                    Icon::new(IconName::Folder)
+                        .color(Color::Muted)
+                        .size(IconSize::Small),
+                )
+                .child(
+                    v_flex()
+                        .w_full()
+                        .overflow_hidden()
+                        .child(
+                            HighlightedLabel::new(path.clone(), entry.positions.clone()).truncate(),
+                        )
+                        .child(details.into_element()),
+                )
+                .tooltip(Tooltip::text(path)),
+        )
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No worktrees found".into())
+    }
+
+    fn render_footer(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<AnyElement> {
+        let entry = self.matches.get(self.selected_index)?;
+        let focus_handle = self.focus_handle.clone();
+
+        Some(
+            h_flex()
+                .w_full()
+                .p_1p5()
+                .gap_0p5()
+                .justify_end()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .when(self.is_local, |this| {
+                    this.child(
+                        Button::new("open-worktree", "Open in New Window")
+                            .key_binding(
+                                KeyBinding::for_action_in(
+                                    &menu::Confirm,
+                                    &focus_handle,
+                                    window,
+                                    cx,
+                                )
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                            )
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(menu::Confirm.boxed_clone(), cx)
+                            }),
+                    )
+                })
+                .child(
+                    Button::new("add-worktree", "Add to Project")
+                        .key_binding(
+                            KeyBinding::for_action_in(
+                                &menu::SecondaryConfirm,
+                                &focus_handle,
+                                window,
+                                cx,
+                            )
+                            .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(menu::SecondaryConfirm.boxed_clone(), cx)
+                        }),
+                )
+                .when(!entry.is_new && !entry.worktree.is_main, |this| {
+                    this.child(
+                        Button::new("remove-worktree", "Remove")
+                            .key_binding(
+                                KeyBinding::for_action_in(
+                                    &worktree_picker::RemoveWorktree,
+                                    &focus_handle,
+                                    window,
+                                    cx,
+                                )
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                            )
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(
+                                    worktree_picker::RemoveWorktree.boxed_clone(),
+                                    cx,
+                                )
+                            }),
+                    )
+                })
+                .into_any(),
+        )
+    }
+}
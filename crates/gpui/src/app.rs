@@ -1682,6 +1682,12 @@ impl App {
         self.pending_effects.push_back(Effect::RefreshWindows);
     }
 
+    /// Remove the key bindings for which the given predicate returns true.
+    pub fn remove_key_bindings(&mut self, predicate: impl FnMut(&KeyBinding) -> bool) {
+        self.keymap.borrow_mut().remove_bindings(predicate);
+        self.pending_effects.push_back(Effect::RefreshWindows);
+    }
+
     /// Clear all key bindings in the app.
     pub fn clear_key_bindings(&mut self) {
         self.keymap.borrow_mut().clear();
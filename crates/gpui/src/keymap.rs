@@ -43,21 +43,41 @@ impl Keymap {
     /// Add more bindings to the keymap.
     pub fn add_bindings<T: IntoIterator<Item = KeyBinding>>(&mut self, bindings: T) {
         for binding in bindings {
-            let action_id = binding.action().as_any().type_id();
-            if is_no_action(&*binding.action) {
-                self.no_action_binding_indices.push(self.bindings.len());
-            } else {
-                self.binding_indices_by_action_id
-                    .entry(action_id)
-                    .or_default()
-                    .push(self.bindings.len());
-            }
+            self.index_binding(self.bindings.len(), &binding);
             self.bindings.push(binding);
         }
 
         self.version.0 += 1;
     }
 
+    /// Remove the bindings for which the given predicate returns true.
+    pub fn remove_bindings(&mut self, mut predicate: impl FnMut(&KeyBinding) -> bool) {
+        let len = self.bindings.len();
+        self.bindings.retain(|binding| !predicate(binding));
+        if self.bindings.len() == len {
+            return;
+        }
+
+        self.binding_indices_by_action_id.clear();
+        self.no_action_binding_indices.clear();
+        for (ix, binding) in std::mem::take(&mut self.bindings).into_iter().enumerate() {
+            self.index_binding(ix, &binding);
+            self.bindings.push(binding);
+        }
+        self.version.0 += 1;
+    }
+
+    fn index_binding(&mut self, ix: usize, binding: &KeyBinding) {
+        if is_no_action(&*binding.action) {
+            self.no_action_binding_indices.push(ix);
+        } else {
+            self.binding_indices_by_action_id
+                .entry(binding.action().as_any().type_id())
+                .or_default()
+                .push(ix);
+        }
+    }
+
     /// Reset this keymap to its initial state.
     pub fn clear(&mut self) {
         self.bindings.clear();
@@ -442,6 +462,35 @@ mod tests {
         assert!(space_editor.1);
     }
 
+    #[test]
+    fn test_remove_bindings() {
+        let mut keymap = Keymap::default();
+        keymap.add_bindings([
+            KeyBinding::new("ctrl-a", ActionAlpha {}, None),
+            KeyBinding::new("ctrl-b", ActionBeta {}, None),
+            KeyBinding::new("ctrl-c", ActionAlpha {}, None),
+            KeyBinding::new("ctrl-a", NoAction {}, None),
+        ]);
+        let version = keymap.version();
+
+        keymap.remove_bindings(|binding| binding.action().partial_eq(&ActionBeta {}));
+        assert!(keymap.version() != version);
+        assert_eq!(keymap.bindings().len(), 3);
+        assert_eq!(keymap.bindings_for_action(&ActionBeta {}).count(), 0);
+
+        // The remaining bindings are still found by action, and still disabled by `NoAction`.
+        let alpha_keystrokes = keymap
+            .bindings_for_action(&ActionAlpha {})
+            .flat_map(|binding| {
+                binding
+                    .keystrokes()
+                    .iter()
+                    .map(|keystroke| keystroke.unparse())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(alpha_keystrokes, ["ctrl-c"]);
+    }
+
     #[test]
     fn test_override_multikey() {
         let bindings = [
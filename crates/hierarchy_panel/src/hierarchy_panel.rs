@@ -0,0 +1,883 @@
+mod hierarchy_panel_settings;
+
+use std::ops::Range;
+
+use anyhow::Context as _;
+use db::kvp::KEY_VALUE_STORE;
+use editor::{Editor, MultibufferSelectionMode, SelectionEffects, scroll::Autoscroll};
+use gpui::{
+    Action, App, AsyncWindowContext, ClickEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    KeyContext, ScrollStrategy, Task, UniformListScrollHandle, WeakEntity, actions, uniform_list,
+};
+use hierarchy_panel_settings::HierarchyPanelSettings;
+use itertools::Itertools;
+use language::{Buffer, OffsetRangeExt as _};
+use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use project::{CallHierarchyItem, Fs, Location, Project, TypeHierarchyItem};
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use std::sync::Arc;
+use ui::{ListItem, ListItemSpacing, Tooltip, WithScrollbar, prelude::*};
+use util::{ResultExt, TryFutureExt};
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+actions!(
+    hierarchy_panel,
+    [
+        /// Shows the callers of the symbol under the cursor in the hierarchy panel.
+        ShowIncomingCalls,
+        /// Shows the callees of the symbol under the cursor in the hierarchy panel.
+        ShowOutgoingCalls,
+        /// Shows the supertypes of the type under the cursor in the hierarchy panel.
+        ShowSupertypes,
+        /// Shows the subtypes of the type under the cursor in the hierarchy panel.
+        ShowSubtypes,
+        /// Switches the hierarchy panel between incoming and outgoing calls,
+        /// or between supertypes and subtypes.
+        ToggleDirection,
+        /// Opens the children of the selected entry in a multibuffer.
+        OpenInMultibuffer,
+        /// Collapses the selected entry.
+        CollapseSelectedEntry,
+        /// Expands the selected entry.
+        ExpandSelectedEntry,
+        /// Toggles focus on the hierarchy panel.
+        ToggleFocus,
+    ]
+);
+
+const HIERARCHY_PANEL_KEY: &str = "HierarchyPanel";
+
+pub fn init(cx: &mut App) {
+    HierarchyPanelSettings::register(cx);
+
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace
+            .register_action(|workspace, _: &ToggleFocus, window, cx| {
+                workspace.toggle_panel_focus::<HierarchyPanel>(window, cx);
+            })
+            .register_action(|workspace, _: &ShowIncomingCalls, window, cx| {
+                show_hierarchy(workspace, HierarchyKind::IncomingCalls, window, cx);
+            })
+            .register_action(|workspace, _: &ShowOutgoingCalls, window, cx| {
+                show_hierarchy(workspace, HierarchyKind::OutgoingCalls, window, cx);
+            })
+            .register_action(|workspace, _: &ShowSupertypes, window, cx| {
+                show_hierarchy(workspace, HierarchyKind::Supertypes, window, cx);
+            })
+            .register_action(|workspace, _: &ShowSubtypes, window, cx| {
+                show_hierarchy(workspace, HierarchyKind::Subtypes, window, cx);
+            });
+    })
+    .detach();
+}
+
+fn show_hierarchy(
+    workspace: &mut Workspace,
+    kind: HierarchyKind,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+        return;
+    };
+    let Some((buffer, position)) = ({
+        let editor = editor.read(cx);
+        let head = editor.selections.newest_anchor().head();
+        editor.buffer().read(cx).text_anchor_for_position(head, cx)
+    }) else {
+        return;
+    };
+    let Some(panel) = workspace.focus_panel::<HierarchyPanel>(window, cx) else {
+        return;
+    };
+    panel.update(cx, |panel, cx| {
+        panel.show_hierarchy(buffer, position, kind, window, cx)
+    });
+}
+
+/// The hierarchy shown in the panel, i.e. which edges are followed when expanding an entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HierarchyKind {
+    IncomingCalls,
+    OutgoingCalls,
+    Supertypes,
+    Subtypes,
+}
+
+impl HierarchyKind {
+    fn title(self) -> &'static str {
+        match self {
+            Self::IncomingCalls => "Incoming Calls",
+            Self::OutgoingCalls => "Outgoing Calls",
+            Self::Supertypes => "Supertypes",
+            Self::Subtypes => "Subtypes",
+        }
+    }
+
+    fn toggle(self) -> Self {
+        match self {
+            Self::IncomingCalls => Self::OutgoingCalls,
+            Self::OutgoingCalls => Self::IncomingCalls,
+            Self::Supertypes => Self::Subtypes,
+            Self::Subtypes => Self::Supertypes,
+        }
+    }
+
+    fn icon(self) -> IconName {
+        match self {
+            Self::IncomingCalls | Self::Supertypes => IconName::ArrowDownRight,
+            Self::OutgoingCalls | Self::Subtypes => IconName::ArrowUpRight,
+        }
+    }
+
+    fn multibuffer_title(self, name: &str) -> String {
+        match self {
+            Self::IncomingCalls => format!("Callers of {name}"),
+            Self::OutgoingCalls => format!("Callees of {name}"),
+            Self::Supertypes => format!("Supertypes of {name}"),
+            Self::Subtypes => format!("Subtypes of {name}"),
+        }
+    }
+}
+
+#[derive(Clone)]
+enum HierarchyItem {
+    Call(CallHierarchyItem),
+    Type(TypeHierarchyItem),
+}
+
+impl HierarchyItem {
+    fn name(&self) -> &str {
+        match self {
+            Self::Call(item) => &item.name,
+            Self::Type(item) => &item.name,
+        }
+    }
+
+    fn detail(&self) -> Option<&str> {
+        match self {
+            Self::Call(item) => item.detail.as_deref(),
+            Self::Type(item) => item.detail.as_deref(),
+        }
+    }
+
+    fn location(&self) -> &Location {
+        match self {
+            Self::Call(item) => &item.location,
+            Self::Type(item) => &item.location,
+        }
+    }
+
+    fn selection_range(&self) -> &Range<language::Anchor> {
+        match self {
+            Self::Call(item) => &item.selection_range,
+            Self::Type(item) => &item.selection_range,
+        }
+    }
+}
+
+type NodeId = usize;
+
+struct Node {
+    item: HierarchyItem,
+    /// Where the parent entry is called from (incoming) or calls this entry (outgoing).
+    /// Always empty for type hierarchies.
+    call_sites: Vec<Location>,
+    depth: usize,
+    expanded: bool,
+    children: Children,
+}
+
+enum Children {
+    Unresolved,
+    /// Dropping the task cancels the request, e.g. when the hierarchy is replaced.
+    Loading {
+        _task: Task<()>,
+    },
+    Resolved(Vec<NodeId>),
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedHierarchyPanel {
+    width: Option<Pixels>,
+}
+
+pub struct HierarchyPanel {
+    project: Entity<Project>,
+    workspace: WeakEntity<Workspace>,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    width: Option<Pixels>,
+    pending_serialization: Task<Option<()>>,
+    kind: HierarchyKind,
+    nodes: Vec<Node>,
+    roots: Vec<NodeId>,
+    visible_entries: Vec<NodeId>,
+    selected_node: Option<NodeId>,
+    loading: bool,
+    prepare_task: Task<()>,
+}
+
+impl HierarchyPanel {
+    pub fn load(
+        workspace: WeakEntity<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<anyhow::Result<Entity<Self>>> {
+        cx.spawn(async move |cx| {
+            let serialized_panel = cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(HIERARCHY_PANEL_KEY) })
+                .await
+                .context("loading hierarchy panel")
+                .log_err()
+                .flatten()
+                .map(|panel| serde_json::from_str::<SerializedHierarchyPanel>(&panel))
+                .transpose()
+                .log_err()
+                .flatten();
+
+            workspace.update_in(cx, |workspace, _, cx| {
+                let panel = Self::new(workspace, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width.map(|width| width.round());
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn new(workspace: &Workspace, cx: &mut Context<Workspace>) -> Entity<Self> {
+        let project = workspace.project().clone();
+        let fs = workspace.app_state().fs.clone();
+        let workspace = cx.entity().downgrade();
+        cx.new(|cx| Self {
+            project,
+            workspace,
+            fs,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            width: None,
+            pending_serialization: Task::ready(None),
+            kind: HierarchyKind::IncomingCalls,
+            nodes: Vec::new(),
+            roots: Vec::new(),
+            visible_entries: Vec::new(),
+            selected_node: None,
+            loading: false,
+            prepare_task: Task::ready(()),
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        HIERARCHY_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedHierarchyPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    /// Replaces the panel contents with the hierarchy of the symbol at `position`.
+    pub fn show_hierarchy(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: language::Anchor,
+        kind: HierarchyKind,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.kind = kind;
+        self.clear();
+        self.loading = true;
+        cx.notify();
+
+        let prepare = self.project.update(cx, |project, cx| match kind {
+            HierarchyKind::IncomingCalls | HierarchyKind::OutgoingCalls => {
+                let prepare = project.prepare_call_hierarchy(&buffer, position, cx);
+                cx.background_spawn(async move {
+                    anyhow::Ok(
+                        prepare
+                            .await?
+                            .into_iter()
+                            .map(HierarchyItem::Call)
+                            .collect::<Vec<_>>(),
+                    )
+                })
+            }
+            HierarchyKind::Supertypes | HierarchyKind::Subtypes => {
+                let prepare = project.prepare_type_hierarchy(&buffer, position, cx);
+                cx.background_spawn(async move {
+                    anyhow::Ok(
+                        prepare
+                            .await?
+                            .into_iter()
+                            .map(HierarchyItem::Type)
+                            .collect::<Vec<_>>(),
+                    )
+                })
+            }
+        });
+        self.prepare_task = cx.spawn_in(window, async move |panel, cx| {
+            let items = prepare.await.log_err().unwrap_or_default();
+            panel
+                .update_in(cx, |panel, window, cx| {
+                    panel.loading = false;
+                    panel.set_roots(items, window, cx);
+                })
+                .ok();
+        });
+    }
+
+    fn clear(&mut self) {
+        self.nodes.clear();
+        self.roots.clear();
+        self.visible_entries.clear();
+        self.selected_node = None;
+    }
+
+    fn set_roots(
+        &mut self,
+        items: Vec<HierarchyItem>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.clear();
+        for item in items {
+            let root = self.push_node(item, Vec::new(), 0);
+            self.roots.push(root);
+            self.expand_node(root, window, cx);
+        }
+        self.selected_node = self.roots.first().copied();
+        self.update_visible_entries(cx);
+    }
+
+    fn push_node(
+        &mut self,
+        item: HierarchyItem,
+        call_sites: Vec<Location>,
+        depth: usize,
+    ) -> NodeId {
+        self.nodes.push(Node {
+            item,
+            call_sites,
+            depth,
+            expanded: false,
+            children: Children::Unresolved,
+        });
+        self.nodes.len() - 1
+    }
+
+    fn expand_node(&mut self, id: NodeId, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(node) = self.nodes.get_mut(id) else {
+            return;
+        };
+        node.expanded = true;
+        if let Children::Unresolved = node.children {
+            let item = node.item.clone();
+            let depth = node.depth + 1;
+            let kind = self.kind;
+            let children = self
+                .project
+                .update(cx, |project, cx| fetch_children(project, kind, &item, cx));
+            let task = cx.spawn_in(window, async move |panel, cx| {
+                let children = children.await.log_err().unwrap_or_default();
+                panel
+                    .update(cx, |panel, cx| {
+                        let children = children
+                            .into_iter()
+                            .map(|(item, call_sites)| panel.push_node(item, call_sites, depth))
+                            .collect();
+                        if let Some(node) = panel.nodes.get_mut(id) {
+                            node.children = Children::Resolved(children);
+                        }
+                        panel.update_visible_entries(cx);
+                    })
+                    .ok();
+            });
+            self.nodes[id].children = Children::Loading { _task: task };
+        }
+        self.update_visible_entries(cx);
+    }
+
+    fn collapse_node(&mut self, id: NodeId, cx: &mut Context<Self>) {
+        if let Some(node) = self.nodes.get_mut(id) {
+            node.expanded = false;
+            self.update_visible_entries(cx);
+        }
+    }
+
+    fn toggle_node(&mut self, id: NodeId, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(node) = self.nodes.get(id) else {
+            return;
+        };
+        if node.expanded {
+            self.collapse_node(id, cx);
+        } else {
+            self.expand_node(id, window, cx);
+        }
+    }
+
+    fn update_visible_entries(&mut self, cx: &mut Context<Self>) {
+        self.visible_entries.clear();
+        let mut stack = self.roots.iter().rev().copied().collect::<Vec<_>>();
+        while let Some(id) = stack.pop() {
+            self.visible_entries.push(id);
+            let node = &self.nodes[id];
+            if node.expanded
+                && let Children::Resolved(children) = &node.children
+            {
+                stack.extend(children.iter().rev().copied());
+            }
+        }
+        cx.notify();
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        let selected_node = self.selected_node?;
+        self.visible_entries
+            .iter()
+            .position(|id| *id == selected_node)
+    }
+
+    fn select_index(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if let Some(id) = self.visible_entries.get(ix) {
+            self.selected_node = Some(*id);
+            self.scroll_handle
+                .scroll_to_item(ix, ScrollStrategy::Center);
+            cx.notify();
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.selected_index().map_or(0, |ix| ix + 1);
+        if ix < self.visible_entries.len() {
+            self.select_index(ix, cx);
+        }
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.selected_index().map_or(0, |ix| ix.saturating_sub(1));
+        self.select_index(ix, cx);
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_index(0, cx);
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.visible_entries.len().checked_sub(1) {
+            self.select_index(ix, cx);
+        }
+    }
+
+    fn expand_selected_entry(
+        &mut self,
+        _: &ExpandSelectedEntry,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(id) = self.selected_node {
+            self.expand_node(id, window, cx);
+        }
+    }
+
+    fn collapse_selected_entry(
+        &mut self,
+        _: &CollapseSelectedEntry,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(id) = self.selected_node {
+            self.collapse_node(id, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(id) = self.selected_node {
+            self.open_node(id, window, cx);
+        }
+    }
+
+    fn toggle_direction(
+        &mut self,
+        _: &ToggleDirection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.kind = self.kind.toggle();
+        let roots = self
+            .roots
+            .iter()
+            .map(|id| self.nodes[*id].item.clone())
+            .collect();
+        self.set_roots(roots, window, cx);
+    }
+
+    fn open_in_multibuffer(
+        &mut self,
+        _: &OpenInMultibuffer,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(id) = self.selected_node else {
+            return;
+        };
+        let Children::Resolved(children) = &self.nodes[id].children else {
+            // The children are opened once the entry is expanded and the action is repeated.
+            self.expand_node(id, window, cx);
+            return;
+        };
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+
+        let locations = children
+            .iter()
+            .flat_map(|child| {
+                let child = &self.nodes[*child];
+                if child.call_sites.is_empty() {
+                    std::slice::from_ref(child.item.location())
+                } else {
+                    child.call_sites.as_slice()
+                }
+            })
+            .map(|location| {
+                let range = location.range.to_point(location.buffer.read(cx));
+                (location.buffer.clone(), range)
+            })
+            .into_group_map();
+        if locations.is_empty() {
+            return;
+        }
+
+        let title = self.kind.multibuffer_title(self.nodes[id].item.name());
+        workspace.update(cx, |workspace, cx| {
+            Editor::open_locations_in_multibuffer(
+                workspace,
+                locations,
+                title,
+                false,
+                MultibufferSelectionMode::First,
+                window,
+                cx,
+            );
+        });
+    }
+
+    /// Reveals the call sites of the entry, or the symbol itself for root entries and types.
+    fn open_node(&mut self, id: NodeId, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(node) = self.nodes.get(id) else {
+            return;
+        };
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+
+        if node.call_sites.len() > 1 {
+            let locations = node
+                .call_sites
+                .iter()
+                .map(|call_site| {
+                    let range = call_site.range.to_point(call_site.buffer.read(cx));
+                    (call_site.buffer.clone(), range)
+                })
+                .into_group_map();
+            let title = format!("Calls in {}", node.item.name());
+            workspace.update(cx, |workspace, cx| {
+                Editor::open_locations_in_multibuffer(
+                    workspace,
+                    locations,
+                    title,
+                    false,
+                    MultibufferSelectionMode::All,
+                    window,
+                    cx,
+                );
+            });
+            return;
+        }
+
+        let (buffer, range) = match node.call_sites.first() {
+            Some(call_site) => (call_site.buffer.clone(), call_site.range.clone()),
+            None => (
+                node.item.location().buffer.clone(),
+                node.item.selection_range().clone(),
+            ),
+        };
+        open_range(&workspace, buffer, range, window, cx);
+    }
+
+    fn dispatch_context(&self) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("HierarchyPanel");
+        dispatch_context.add("menu");
+        dispatch_context
+    }
+
+    fn render_entry(&self, id: NodeId, cx: &mut Context<Self>) -> impl IntoElement {
+        let node = &self.nodes[id];
+        let toggle = match &node.children {
+            Children::Resolved(children) if children.is_empty() => None,
+            _ => Some(node.expanded),
+        };
+        let call_count = (node.call_sites.len() > 1).then(|| {
+            Label::new(format!("×{}", node.call_sites.len()))
+                .size(LabelSize::Small)
+                .color(Color::Muted)
+        });
+
+        ListItem::new(("hierarchy-entry", id))
+            .indent_level(node.depth)
+            .indent_step_size(px(12.))
+            .spacing(ListItemSpacing::Sparse)
+            .toggle(toggle)
+            .toggle_state(self.selected_node == Some(id))
+            .on_toggle(cx.listener(move |panel, _, window, cx| {
+                panel.toggle_node(id, window, cx);
+            }))
+            .on_click(cx.listener(move |panel, _: &ClickEvent, window, cx| {
+                panel.selected_node = Some(id);
+                panel.open_node(id, window, cx);
+                cx.notify();
+            }))
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(Label::new(node.item.name().to_string()))
+                    .when_some(node.item.detail(), |this, detail| {
+                        this.child(
+                            Label::new(detail.to_string())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .truncate(),
+                        )
+                    })
+                    .children(call_count),
+            )
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let kind = self.kind;
+        h_flex()
+            .px_2()
+            .py_1()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(Label::new(kind.title()).size(LabelSize::Small))
+            .child(
+                IconButton::new("toggle-hierarchy-direction", kind.icon())
+                    .icon_size(IconSize::Small)
+                    .disabled(self.roots.is_empty())
+                    .tooltip(move |window, cx| {
+                        Tooltip::for_action(
+                            format!("Show {}", kind.toggle().title()),
+                            &ToggleDirection,
+                            window,
+                            cx,
+                        )
+                    })
+                    .on_click(|_, window, cx| {
+                        window.dispatch_action(ToggleDirection.boxed_clone(), cx)
+                    }),
+            )
+    }
+
+    fn render_empty_state(&self) -> impl IntoElement {
+        let message = if self.loading {
+            "Loading…"
+        } else {
+            "Show the call or type hierarchy of a symbol to populate this panel."
+        };
+        v_flex()
+            .size_full()
+            .p_4()
+            .justify_center()
+            .items_center()
+            .child(Label::new(message).color(Color::Muted))
+    }
+}
+
+/// Requests the children of `item` in the given hierarchy, along with the call sites
+/// connecting them to `item` for call hierarchies.
+fn fetch_children(
+    project: &mut Project,
+    kind: HierarchyKind,
+    item: &HierarchyItem,
+    cx: &mut Context<Project>,
+) -> Task<anyhow::Result<Vec<(HierarchyItem, Vec<Location>)>>> {
+    match (kind, item) {
+        (
+            HierarchyKind::IncomingCalls | HierarchyKind::OutgoingCalls,
+            HierarchyItem::Call(item),
+        ) => {
+            let calls = if kind == HierarchyKind::IncomingCalls {
+                project.incoming_calls(item, cx)
+            } else {
+                project.outgoing_calls(item, cx)
+            };
+            cx.background_spawn(async move {
+                Ok(calls
+                    .await?
+                    .into_iter()
+                    .map(|call| (HierarchyItem::Call(call.item), call.call_sites))
+                    .collect())
+            })
+        }
+        (HierarchyKind::Supertypes | HierarchyKind::Subtypes, HierarchyItem::Type(item)) => {
+            let types = if kind == HierarchyKind::Supertypes {
+                project.supertypes(item, cx)
+            } else {
+                project.subtypes(item, cx)
+            };
+            cx.background_spawn(async move {
+                Ok(types
+                    .await?
+                    .into_iter()
+                    .map(|item| (HierarchyItem::Type(item), Vec::new()))
+                    .collect())
+            })
+        }
+        _ => Task::ready(Ok(Vec::new())),
+    }
+}
+
+fn open_range(
+    workspace: &Entity<Workspace>,
+    buffer: Entity<Buffer>,
+    range: Range<language::Anchor>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let range = range.to_point(buffer.read(cx));
+    workspace.update(cx, |workspace, cx| {
+        let pane = workspace.active_pane().clone();
+        let editor = workspace.open_project_item::<Editor>(pane, buffer, true, true, window, cx);
+        editor.update(cx, |editor, cx| {
+            editor.change_selections(
+                SelectionEffects::scroll(Autoscroll::center()),
+                window,
+                cx,
+                |selections| selections.select_ranges([range]),
+            );
+        });
+    });
+}
+
+impl Panel for HierarchyPanel {
+    fn persistent_name() -> &'static str {
+        "HierarchyPanel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        HierarchyPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            settings.hierarchy_panel.get_or_insert_default().dock = Some(position.into())
+        });
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| HierarchyPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        HierarchyPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::FileTree)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
+        Some("Hierarchy Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        7
+    }
+}
+
+impl Focusable for HierarchyPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for HierarchyPanel {}
+
+impl Render for HierarchyPanel {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .id("hierarchy-panel")
+            .size_full()
+            .overflow_hidden()
+            .key_context(self.dispatch_context())
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::expand_selected_entry))
+            .on_action(cx.listener(Self::collapse_selected_entry))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::toggle_direction))
+            .on_action(cx.listener(Self::open_in_multibuffer))
+            .child(self.render_header(cx))
+            .map(|this| {
+                if self.visible_entries.is_empty() {
+                    this.child(self.render_empty_state())
+                } else {
+                    this.child(
+                        uniform_list(
+                            "hierarchy-entries",
+                            self.visible_entries.len(),
+                            cx.processor(|panel, range: Range<usize>, _, cx| {
+                                panel.visible_entries[range]
+                                    .to_vec()
+                                    .into_iter()
+                                    .map(|id| panel.render_entry(id, cx).into_any_element())
+                                    .collect()
+                            }),
+                        )
+                        .track_scroll(self.scroll_handle.clone())
+                        .size_full(),
+                    )
+                    .vertical_scrollbar_for(
+                        self.scroll_handle.clone(),
+                        window,
+                        cx,
+                    )
+                }
+            })
+    }
+}
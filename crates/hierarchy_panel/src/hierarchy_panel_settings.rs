@@ -0,0 +1,22 @@
+use gpui::Pixels;
+use settings::Settings;
+use ui::px;
+use workspace::dock::DockPosition;
+
+#[derive(Debug)]
+pub struct HierarchyPanelSettings {
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+}
+
+impl Settings for HierarchyPanelSettings {
+    fn from_settings(content: &settings::SettingsContent, _cx: &mut ui::App) -> Self {
+        let panel = content.hierarchy_panel.as_ref().unwrap();
+        Self {
+            button: panel.button.unwrap(),
+            dock: panel.dock.unwrap().into(),
+            default_width: panel.default_width.map(px).unwrap(),
+        }
+    }
+}
@@ -48,6 +48,7 @@ pub enum IconName {
     BoltFilled,
     Book,
     BookCopy,
+    Bookmark,
     CaseSensitive,
     Chat,
     Check,
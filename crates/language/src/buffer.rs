@@ -4038,6 +4038,23 @@ impl BufferSnapshot {
             .filter_map(|(range, obj)| (obj == TextObject::InsideFunction).then_some(range))
     }
 
+    /// Returns the range folded by the outermost multi-line bracket pair that opens on the given row,
+    /// spanning from the end of that row to the end of the row before the closing bracket.
+    pub fn syntax_fold_range_for_row(&self, row: u32) -> Option<Range<Point>> {
+        let row_range =
+            Point::new(row, 0).to_offset(self)..Point::new(row, self.line_len(row)).to_offset(self);
+        self.all_bracket_ranges(row_range)
+            .filter_map(|pair| {
+                let open = pair.open_range.start.to_point(self);
+                let close = pair.close_range.start.to_point(self);
+                (open.row == row && close.row > row + 1).then_some(close.row - 1)
+            })
+            .max()
+            .map(|end_row| {
+                Point::new(row, self.line_len(row))..Point::new(end_row, self.line_len(end_row))
+            })
+    }
+
     /// For each grammar in the language, runs the provided
     /// [`tree_sitter::Query`] against the given range.
     pub fn matches(
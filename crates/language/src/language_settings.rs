@@ -11,9 +11,10 @@ use gpui::{App, Modifiers, SharedString};
 use itertools::{Either, Itertools};
 
 pub use settings::{
-    CompletionSettingsContent, EditPredictionProvider, EditPredictionsMode, FormatOnSave,
-    Formatter, FormatterList, InlayHintKind, LanguageSettingsContent, LspInsertMode,
-    RewrapBehavior, SelectedFormatter, ShowWhitespaceSetting, SoftWrap, WordsCompletionMode,
+    CompletionSettingsContent, EditPredictionProvider, EditPredictionsMode, FoldingProvider,
+    FormatOnSave, Formatter, FormatterList, InlayHintKind, LanguageSettingsContent, LspInsertMode,
+    RewrapBehavior, SelectedFormatter, SemanticTokens, ShowWhitespaceSetting, SoftWrap,
+    WordsCompletionMode,
 };
 use settings::{ExtendingVec, Settings, SettingsContent, SettingsLocation, SettingsStore};
 use shellexpand;
@@ -146,6 +147,10 @@ pub struct LanguageSettings {
     pub code_actions_on_format: HashMap<String, bool>,
     /// Whether to perform linked edits
     pub linked_edits: bool,
+    /// How to use semantic tokens from language servers for syntax highlighting.
+    pub semantic_tokens: SemanticTokens,
+    /// Where foldable ranges come from.
+    pub folding_provider: FoldingProvider,
     /// Task configuration for this language.
     pub tasks: LanguageTaskSettings,
     /// Whether to pop the completions menu while typing in an editor without
@@ -570,6 +575,8 @@ impl settings::Settings for AllLanguageSettings {
                     .unwrap(),
                 code_actions_on_format: settings.code_actions_on_format.unwrap(),
                 linked_edits: settings.linked_edits.unwrap(),
+                semantic_tokens: settings.semantic_tokens.unwrap(),
+                folding_provider: settings.folding_provider.unwrap(),
                 tasks: LanguageTaskSettings {
                     variables: tasks.variables,
                     enabled: tasks.enabled.unwrap(),
@@ -0,0 +1,8 @@
+[
+  (fenced_code_block)
+  (indented_code_block)
+  (html_block)
+  (link_destination)
+  (minus_metadata)
+  (plus_metadata)
+] @code
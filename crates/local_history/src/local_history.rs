@@ -0,0 +1,195 @@
+//! Keeps a local history of the files edited in Zed, independent of any version control system.
+//!
+//! Snapshots of a file are recorded in a local database when it's saved, when it's changed on disk
+//! and before an agent edits it, so that they can be restored later.
+
+mod persistence;
+
+use std::path::PathBuf;
+
+use collections::HashMap;
+use gpui::{App, AppContext as _, Context, Entity, Global, Subscription, Task, actions};
+use language::{Buffer, BufferEvent};
+use project::{Project, buffer_store::BufferStoreEvent};
+use settings::Settings;
+use text::{BufferId, Rope};
+
+pub use persistence::{
+    LOCAL_HISTORY_DB, LocalHistoryDb, Retention, SnapshotEntry, SnapshotId, SnapshotSource,
+};
+
+actions!(
+    local_history,
+    [
+        /// Shows the local history of the active file.
+        ShowTimeline
+    ]
+);
+
+#[derive(Clone, Debug)]
+pub struct LocalHistorySettings {
+    pub enabled: bool,
+    pub max_snapshots_per_file: u32,
+    pub max_age_days: u32,
+    pub max_file_size: u64,
+}
+
+impl LocalHistorySettings {
+    pub fn retention(&self) -> Retention {
+        Retention {
+            max_snapshots_per_file: self.max_snapshots_per_file,
+            max_age: time::Duration::days(self.max_age_days.into()),
+        }
+    }
+}
+
+impl Settings for LocalHistorySettings {
+    fn from_settings(content: &settings::SettingsContent, _cx: &mut App) -> Self {
+        let local_history = content.local_history.clone().unwrap();
+
+        Self {
+            enabled: local_history.enabled.unwrap(),
+            max_snapshots_per_file: local_history.max_snapshots_per_file.unwrap(),
+            max_age_days: local_history.max_age_days.unwrap(),
+            max_file_size: local_history.max_file_size.unwrap(),
+        }
+    }
+}
+
+pub fn init(cx: &mut App) {
+    LocalHistorySettings::register(cx);
+
+    let local_history = cx.new(|_| LocalHistory {
+        buffers: HashMap::default(),
+    });
+    cx.set_global(GlobalLocalHistory(local_history.clone()));
+
+    cx.observe_new(move |project: &mut Project, _, cx: &mut Context<Project>| {
+        if !project.is_local() {
+            return;
+        }
+        let local_history = local_history.clone();
+        cx.subscribe(project.buffer_store(), move |_, _, event, cx| {
+            if let BufferStoreEvent::BufferAdded(buffer) = event {
+                local_history.update(cx, |local_history, cx| {
+                    local_history.track_buffer(buffer, cx)
+                });
+            }
+        })
+        .detach();
+    })
+    .detach();
+}
+
+struct GlobalLocalHistory(Entity<LocalHistory>);
+
+impl Global for GlobalLocalHistory {}
+
+/// Records snapshots of the buffers of local projects when they're saved or changed on disk.
+pub struct LocalHistory {
+    buffers: HashMap<BufferId, TrackedBuffer>,
+}
+
+struct TrackedBuffer {
+    /// The buffer's contents when it was opened, recorded the first time it's saved.
+    opened_content: Option<Rope>,
+    _subscriptions: [Subscription; 2],
+}
+
+impl LocalHistory {
+    fn track_buffer(&mut self, buffer: &Entity<Buffer>, cx: &mut Context<Self>) {
+        let buffer_id = buffer.read(cx).remote_id();
+        let opened_content = buffer
+            .read(cx)
+            .file()
+            .is_some_and(|file| file.disk_state().exists() && !buffer.read(cx).is_dirty())
+            .then(|| buffer.read(cx).as_rope().clone());
+        let subscriptions = [
+            cx.subscribe(buffer, move |this, buffer, event, cx| match event {
+                BufferEvent::Saved => {
+                    let opened_content = this
+                        .buffers
+                        .get_mut(&buffer_id)
+                        .and_then(|tracked_buffer| tracked_buffer.opened_content.take());
+                    let mut snapshots = Vec::new();
+                    if let Some(opened_content) = opened_content {
+                        snapshots.push((opened_content, SnapshotSource::Opened));
+                    }
+                    snapshots.push((buffer.read(cx).as_rope().clone(), SnapshotSource::Saved));
+                    record_snapshots(&buffer, snapshots, cx);
+                }
+                BufferEvent::Reloaded => {
+                    if let Some(tracked_buffer) = this.buffers.get_mut(&buffer_id) {
+                        tracked_buffer.opened_content = None;
+                    }
+                    let content = buffer.read(cx).as_rope().clone();
+                    record_snapshots(&buffer, vec![(content, SnapshotSource::ExternalChange)], cx);
+                }
+                _ => {}
+            }),
+            cx.observe_release(buffer, move |this, _, _| {
+                this.buffers.remove(&buffer_id);
+            }),
+        ];
+        self.buffers.insert(
+            buffer_id,
+            TrackedBuffer {
+                opened_content,
+                _subscriptions: subscriptions,
+            },
+        );
+    }
+}
+
+/// Records a snapshot of a buffer's file with the given contents, such as its contents before an
+/// agent edits it. Does nothing if the buffer isn't a local file or local history is disabled.
+pub fn record_snapshot(buffer: &Entity<Buffer>, content: Rope, source: SnapshotSource, cx: &App) {
+    record_snapshots(buffer, vec![(content, source)], cx);
+}
+
+fn record_snapshots(buffer: &Entity<Buffer>, snapshots: Vec<(Rope, SnapshotSource)>, cx: &App) {
+    if !cx.has_global::<GlobalLocalHistory>() {
+        return;
+    }
+    let Some(abs_path) = history_path(buffer.read(cx), cx) else {
+        return;
+    };
+
+    let settings = LocalHistorySettings::get_global(cx);
+    let retention = settings.retention();
+    let max_file_size = settings.max_file_size;
+    cx.background_spawn(async move {
+        for (content, source) in snapshots {
+            if content.len() as u64 > max_file_size {
+                continue;
+            }
+            LOCAL_HISTORY_DB
+                .record_snapshot(abs_path.clone(), source, content.to_string(), retention)
+                .await?;
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Returns the snapshots recorded for the file at the given path, newest first.
+pub fn snapshots(abs_path: PathBuf, cx: &App) -> Task<anyhow::Result<Vec<SnapshotEntry>>> {
+    cx.background_spawn(async move { LOCAL_HISTORY_DB.snapshots(abs_path) })
+}
+
+/// Returns the contents of a snapshot.
+pub fn snapshot_content(id: SnapshotId, cx: &App) -> Task<anyhow::Result<String>> {
+    cx.background_spawn(async move {
+        LOCAL_HISTORY_DB
+            .snapshot_content(id)?
+            .ok_or_else(|| anyhow::anyhow!("local history snapshot {id:?} no longer exists"))
+    })
+}
+
+/// Returns the absolute path of a buffer's file, if it's a local file that can have a history.
+pub fn history_path(buffer: &Buffer, cx: &App) -> Option<PathBuf> {
+    buffer
+        .file()
+        .filter(|_| LocalHistorySettings::get_global(cx).enabled)
+        .and_then(|file| Some(file.as_local()?.abs_path(cx)))
+}
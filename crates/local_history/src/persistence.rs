@@ -0,0 +1,288 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use db::{
+    query,
+    sqlez::{
+        bindable::{Bind, Column, StaticColumnCount},
+        domain::Domain,
+        statement::Statement,
+        thread_safe_connection::ThreadSafeConnection,
+    },
+    sqlez_macros::sql,
+};
+use time::OffsetDateTime;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SnapshotId(pub i64);
+
+impl StaticColumnCount for SnapshotId {}
+
+impl Bind for SnapshotId {
+    fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
+        statement.bind(&self.0, start_index)
+    }
+}
+
+impl Column for SnapshotId {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let (id, next_index) = i64::column(statement, start_index)?;
+        Ok((Self(id), next_index))
+    }
+}
+
+/// What caused a snapshot of a file to be recorded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapshotSource {
+    /// The file's contents when it was opened, recorded when it's first saved.
+    Opened,
+    /// The file was saved.
+    Saved,
+    /// The file was changed on disk by another program.
+    ExternalChange,
+    /// The file's contents before an agent edited it.
+    AgentEdit,
+}
+
+impl SnapshotSource {
+    fn to_sql(self) -> &'static str {
+        match self {
+            Self::Opened => "opened",
+            Self::Saved => "saved",
+            Self::ExternalChange => "external_change",
+            Self::AgentEdit => "agent_edit",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Opened => "Before First Save",
+            Self::Saved => "Saved",
+            Self::ExternalChange => "Changed on Disk",
+            Self::AgentEdit => "Before Agent Edit",
+        }
+    }
+}
+
+impl StaticColumnCount for SnapshotSource {}
+
+impl Bind for SnapshotSource {
+    fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
+        statement.bind(&self.to_sql(), start_index)
+    }
+}
+
+impl Column for SnapshotSource {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let (source, next_index) = String::column(statement, start_index)?;
+        let source = match source.as_str() {
+            "opened" => Self::Opened,
+            "saved" => Self::Saved,
+            "external_change" => Self::ExternalChange,
+            "agent_edit" => Self::AgentEdit,
+            _ => anyhow::bail!("invalid local history snapshot source {source:?}"),
+        };
+        Ok((source, next_index))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SnapshotEntry {
+    pub id: SnapshotId,
+    pub source: SnapshotSource,
+    pub created_at: OffsetDateTime,
+    /// The length of the snapshot's contents, in bytes.
+    pub len: usize,
+}
+
+impl Column for SnapshotEntry {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let (id, next_index) = SnapshotId::column(statement, start_index)?;
+        let (source, next_index) = SnapshotSource::column(statement, next_index)?;
+        let (created_at, next_index) = i64::column(statement, next_index)?;
+        let (len, next_index) = i64::column(statement, next_index)?;
+        let entry = Self {
+            id,
+            source,
+            created_at: OffsetDateTime::from_unix_timestamp(created_at)?,
+            len: len as usize,
+        };
+        Ok((entry, next_index))
+    }
+}
+
+/// How many snapshots are kept.
+#[derive(Clone, Copy, Debug)]
+pub struct Retention {
+    pub max_snapshots_per_file: u32,
+    pub max_age: time::Duration,
+}
+
+pub struct LocalHistoryDb(ThreadSafeConnection);
+
+impl Domain for LocalHistoryDb {
+    const NAME: &str = stringify!(LocalHistoryDb);
+    const MIGRATIONS: &[&str] = &[sql!(
+        CREATE TABLE local_history_snapshots(
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            abs_path TEXT NOT NULL,
+            source TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            content TEXT NOT NULL
+        ) STRICT;
+        CREATE INDEX local_history_snapshots_by_path ON local_history_snapshots(abs_path, id);
+    )];
+}
+
+db::static_connection!(LOCAL_HISTORY_DB, LocalHistoryDb, []);
+
+impl LocalHistoryDb {
+    /// Records a snapshot of a file, unless it's identical to the latest one, and deletes the
+    /// snapshots that are no longer retained. Returns whether a snapshot was recorded.
+    pub async fn record_snapshot(
+        &self,
+        abs_path: PathBuf,
+        source: SnapshotSource,
+        content: String,
+        retention: Retention,
+    ) -> Result<bool> {
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let oldest_retained = now - retention.max_age.whole_seconds();
+        self.write(move |connection| {
+            let latest_content = connection.select_row_bound::<PathBuf, String>(sql!(
+                SELECT content FROM local_history_snapshots
+                WHERE abs_path = ?
+                ORDER BY id DESC
+                LIMIT 1
+            ))?(abs_path.clone())?;
+            if latest_content.as_ref() == Some(&content) {
+                return Ok(false);
+            }
+
+            connection.exec_bound::<(PathBuf, SnapshotSource, i64, String)>(sql!(
+                INSERT INTO local_history_snapshots (abs_path, source, created_at, content)
+                VALUES (?, ?, ?, ?)
+            ))?((abs_path.clone(), source, now, content))?;
+            connection.exec_bound::<(PathBuf, u32)>(sql!(
+                DELETE FROM local_history_snapshots
+                WHERE abs_path = ?1 AND id NOT IN (
+                    SELECT id FROM local_history_snapshots
+                    WHERE abs_path = ?1
+                    ORDER BY id DESC
+                    LIMIT ?2
+                )
+            ))?((abs_path, retention.max_snapshots_per_file))?;
+            connection.exec_bound::<i64>(sql!(
+                DELETE FROM local_history_snapshots WHERE created_at < ?
+            ))?(oldest_retained)?;
+            Ok(true)
+        })
+        .await
+    }
+
+    query! {
+        pub fn snapshots(abs_path: PathBuf) -> Result<Vec<SnapshotEntry>> {
+            SELECT id, source, created_at, length(CAST(content AS BLOB))
+            FROM local_history_snapshots
+            WHERE abs_path = ?
+            ORDER BY id DESC
+        }
+    }
+
+    query! {
+        pub fn snapshot_content(id: SnapshotId) -> Result<Option<String>> {
+            SELECT content FROM local_history_snapshots WHERE id = ?
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RETENTION: Retention = Retention {
+        max_snapshots_per_file: 3,
+        max_age: time::Duration::days(30),
+    };
+
+    #[gpui::test]
+    async fn test_record_snapshots() {
+        let db = LocalHistoryDb::open_test_db("test_record_snapshots").await;
+        let path = PathBuf::from("/project/a.txt");
+
+        assert!(
+            db.record_snapshot(
+                path.clone(),
+                SnapshotSource::Opened,
+                "one".into(),
+                RETENTION
+            )
+            .await
+            .unwrap()
+        );
+        // Snapshots identical to the latest one aren't recorded again.
+        assert!(
+            !db.record_snapshot(path.clone(), SnapshotSource::Saved, "one".into(), RETENTION)
+                .await
+                .unwrap()
+        );
+        db.record_snapshot(path.clone(), SnapshotSource::Saved, "two".into(), RETENTION)
+            .await
+            .unwrap();
+        db.record_snapshot(
+            PathBuf::from("/project/b.txt"),
+            SnapshotSource::Saved,
+            "other".into(),
+            RETENTION,
+        )
+        .await
+        .unwrap();
+
+        let snapshots = db.snapshots(path.clone()).unwrap();
+        assert_eq!(
+            snapshots
+                .iter()
+                .map(|snapshot| (snapshot.source, snapshot.len))
+                .collect::<Vec<_>>(),
+            [(SnapshotSource::Saved, 3), (SnapshotSource::Opened, 3)]
+        );
+        assert_eq!(
+            db.snapshot_content(snapshots[1].id).unwrap().as_deref(),
+            Some("one")
+        );
+    }
+
+    #[gpui::test]
+    async fn test_snapshot_retention() {
+        let db = LocalHistoryDb::open_test_db("test_snapshot_retention").await;
+        let path = PathBuf::from("/project/a.txt");
+
+        for content in ["1", "2", "3", "4", "5"] {
+            db.record_snapshot(
+                path.clone(),
+                SnapshotSource::AgentEdit,
+                content.into(),
+                RETENTION,
+            )
+            .await
+            .unwrap();
+        }
+        let contents = db
+            .snapshots(path.clone())
+            .unwrap()
+            .into_iter()
+            .map(|snapshot| db.snapshot_content(snapshot.id).unwrap().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(contents, ["5", "4", "3"]);
+
+        // Snapshots older than the maximum age are deleted on the next recording.
+        let no_age = Retention {
+            max_age: time::Duration::seconds(-1),
+            ..RETENTION
+        };
+        db.record_snapshot(path.clone(), SnapshotSource::Saved, "6".into(), no_age)
+            .await
+            .unwrap();
+        assert_eq!(db.snapshots(path).unwrap(), []);
+    }
+}
@@ -34,7 +34,7 @@ use std::{
     pin::Pin,
     sync::{
         Arc, Weak,
-        atomic::{AtomicI32, Ordering::SeqCst},
+        atomic::{AtomicBool, AtomicI32, Ordering::SeqCst},
     },
     task::Poll,
     time::{Duration, Instant},
@@ -89,6 +89,8 @@ pub struct LanguageServer {
     process_name: Arc<str>,
     binary: LanguageServerBinary,
     capabilities: RwLock<ServerCapabilities>,
+    /// Whether the server provides type hierarchies, which [`ServerCapabilities`] doesn't model.
+    type_hierarchy_provider: AtomicBool,
     /// Configuration sent to the server, stored for display in the language server logs
     /// buffer. This is represented as the message sent to the LSP in order to avoid cloning it (can
     /// be large in cases like sending schemas to the json server).
@@ -237,6 +239,16 @@ enum LspResult<T> {
     Error(Option<Error>),
 }
 
+/// The `initialize` request, responded to with the raw result so that the capabilities
+/// [`ServerCapabilities`] doesn't model can be read from it.
+enum RawInitialize {}
+
+impl request::Request for RawInitialize {
+    type Params = InitializeParams;
+    type Result = Value;
+    const METHOD: &'static str = request::Initialize::METHOD;
+}
+
 /// Language server protocol RPC notification message.
 ///
 /// [LSP Specification](https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#notificationMessage)
@@ -307,6 +319,8 @@ pub struct AdapterServerCapabilities {
     pub server_capabilities: ServerCapabilities,
     // List of code actions supported by the LspAdapter matching the server
     pub code_action_kinds: Option<Vec<CodeActionKind>>,
+    // Whether the server provides type hierarchies, which `ServerCapabilities` doesn't model
+    pub type_hierarchy_provider: bool,
 }
 
 impl LanguageServer {
@@ -493,6 +507,7 @@ impl LanguageServer {
                 .unwrap_or_default(),
             binary,
             capabilities: Default::default(),
+            type_hierarchy_provider: AtomicBool::new(false),
             configuration,
             code_action_kinds,
             next_id: Default::default(),
@@ -686,6 +701,9 @@ impl LanguageServer {
                     code_lens: Some(CodeLensWorkspaceClientCapabilities {
                         refresh_support: Some(true),
                     }),
+                    semantic_tokens: Some(SemanticTokensWorkspaceClientCapabilities {
+                        refresh_support: Some(true),
+                    }),
                     workspace_edit: Some(WorkspaceEditClientCapabilities {
                         resource_operations: Some(vec![
                             ResourceOperationKind::Create,
@@ -854,6 +872,73 @@ impl LanguageServer {
                     color_provider: Some(DocumentColorClientCapabilities {
                         dynamic_registration: Some(true),
                     }),
+                    call_hierarchy: Some(CallHierarchyClientCapabilities {
+                        dynamic_registration: Some(true),
+                    }),
+                    type_hierarchy: Some(TypeHierarchyClientCapabilities {
+                        dynamic_registration: None,
+                    }),
+                    folding_range: Some(FoldingRangeClientCapabilities {
+                        dynamic_registration: Some(true),
+                        line_folding_only: Some(true),
+                        ..FoldingRangeClientCapabilities::default()
+                    }),
+                    document_link: Some(DocumentLinkClientCapabilities {
+                        dynamic_registration: Some(true),
+                        tooltip_support: Some(true),
+                    }),
+                    selection_range: Some(SelectionRangeClientCapabilities {
+                        dynamic_registration: Some(true),
+                    }),
+                    semantic_tokens: Some(SemanticTokensClientCapabilities {
+                        dynamic_registration: Some(true),
+                        requests: SemanticTokensClientCapabilitiesRequests {
+                            range: None,
+                            full: Some(SemanticTokensFullOptions::Delta { delta: Some(true) }),
+                        },
+                        token_types: vec![
+                            SemanticTokenType::NAMESPACE,
+                            SemanticTokenType::TYPE,
+                            SemanticTokenType::CLASS,
+                            SemanticTokenType::ENUM,
+                            SemanticTokenType::INTERFACE,
+                            SemanticTokenType::STRUCT,
+                            SemanticTokenType::TYPE_PARAMETER,
+                            SemanticTokenType::PARAMETER,
+                            SemanticTokenType::VARIABLE,
+                            SemanticTokenType::PROPERTY,
+                            SemanticTokenType::ENUM_MEMBER,
+                            SemanticTokenType::EVENT,
+                            SemanticTokenType::FUNCTION,
+                            SemanticTokenType::METHOD,
+                            SemanticTokenType::MACRO,
+                            SemanticTokenType::KEYWORD,
+                            SemanticTokenType::MODIFIER,
+                            SemanticTokenType::COMMENT,
+                            SemanticTokenType::STRING,
+                            SemanticTokenType::NUMBER,
+                            SemanticTokenType::REGEXP,
+                            SemanticTokenType::OPERATOR,
+                            SemanticTokenType::DECORATOR,
+                        ],
+                        token_modifiers: vec![
+                            SemanticTokenModifier::DECLARATION,
+                            SemanticTokenModifier::DEFINITION,
+                            SemanticTokenModifier::READONLY,
+                            SemanticTokenModifier::STATIC,
+                            SemanticTokenModifier::DEPRECATED,
+                            SemanticTokenModifier::ABSTRACT,
+                            SemanticTokenModifier::ASYNC,
+                            SemanticTokenModifier::MODIFICATION,
+                            SemanticTokenModifier::DOCUMENTATION,
+                            SemanticTokenModifier::DEFAULT_LIBRARY,
+                        ],
+                        formats: vec![TokenFormat::RELATIVE],
+                        overlapping_token_support: Some(false),
+                        multiline_token_support: Some(false),
+                        server_cancel_support: Some(true),
+                        augments_syntax_tokens: Some(true),
+                    }),
                     ..TextDocumentClientCapabilities::default()
                 }),
                 experimental: Some(json!({
@@ -893,7 +978,7 @@ impl LanguageServer {
     ) -> Task<Result<Arc<Self>>> {
         cx.background_spawn(async move {
             let response = self
-                .request::<request::Initialize>(params)
+                .request::<RawInitialize>(params)
                 .await
                 .into_response()
                 .with_context(|| {
@@ -903,6 +988,12 @@ impl LanguageServer {
                         self.server_id()
                     )
                 })?;
+            let type_hierarchy_provider = response
+                .pointer("/capabilities/typeHierarchyProvider")
+                .is_some_and(|provider| !provider.is_null() && provider != &Value::Bool(false));
+            let response = serde_json::from_value::<InitializeResult>(response)
+                .context("parsing initialize response")?;
+            self.type_hierarchy_provider = AtomicBool::new(type_hierarchy_provider);
             if let Some(info) = response.server_info {
                 self.process_name = info.name.into();
             }
@@ -1147,6 +1238,7 @@ impl LanguageServer {
         AdapterServerCapabilities {
             server_capabilities: self.capabilities(),
             code_action_kinds: self.code_action_kinds(),
+            type_hierarchy_provider: self.type_hierarchy_provider.load(SeqCst),
         }
     }
 
@@ -1154,6 +1246,12 @@ impl LanguageServer {
         update(self.capabilities.write().deref_mut());
     }
 
+    /// Sets whether the server provides type hierarchies, when it registers or unregisters the
+    /// capability dynamically.
+    pub fn set_type_hierarchy_provider(&self, provider: bool) {
+        self.type_hierarchy_provider.store(provider, SeqCst);
+    }
+
     pub fn configuration(&self) -> &Value {
         &self.configuration.settings
     }
@@ -281,6 +281,14 @@ pub fn snippets_dir() -> &'static PathBuf {
     SNIPPETS_DIR.get_or_init(|| config_dir().join("snippets"))
 }
 
+/// Returns the path to the dictionaries directory.
+///
+/// This is where Hunspell dictionaries used for spell checking can be installed.
+pub fn dictionaries_dir() -> &'static PathBuf {
+    static DICTIONARIES_DIR: OnceLock<PathBuf> = OnceLock::new();
+    DICTIONARIES_DIR.get_or_init(|| config_dir().join("dictionaries"))
+}
+
 /// Returns the path to the contexts directory.
 ///
 /// This is where the saved contexts from the Assistant are stored.
@@ -418,6 +426,11 @@ pub fn local_tasks_file_relative_path() -> &'static RelPath {
     RelPath::unix(".zed/tasks.json").unwrap()
 }
 
+/// Returns the relative path to the file listing a project's spell checked words.
+pub fn local_dictionary_file_relative_path() -> &'static RelPath {
+    RelPath::unix(".zed/dictionary.txt").unwrap()
+}
+
 /// Returns the relative path to a `.vscode/tasks.json` file within a project.
 pub fn local_vscode_tasks_file_relative_path() -> &'static RelPath {
     RelPath::unix(".vscode/tasks.json").unwrap()
@@ -0,0 +1,332 @@
+//! Module for managing line bookmarks in a project.
+//!
+//! Bookmarks are anchored to buffer positions, so they follow the lines they were set on as buffers are edited.
+//! They're keyed by the absolute path of their file, which allows them to be persisted with the workspace.
+use std::{ops::Range, path::Path, sync::Arc};
+
+use anyhow::Result;
+use collections::BTreeMap;
+use gpui::{App, Context, Entity, EventEmitter, Subscription, Task};
+use language::{Buffer, BufferEvent, BufferSnapshot, DiskState};
+use text::Point;
+
+use crate::{ProjectPath, buffer_store::BufferStore, worktree_store::WorktreeStore};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bookmark {
+    /// The start of the bookmarked line.
+    pub position: text::Anchor,
+    pub label: Option<Arc<str>>,
+}
+
+/// A bookmark as stored in the workspace database.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SerializedBookmark {
+    pub path: Arc<Path>,
+    pub row: u32,
+    pub label: Option<Arc<str>>,
+}
+
+struct BookmarksInFile {
+    buffer: Entity<Buffer>,
+    /// Sorted by position.
+    bookmarks: Vec<Bookmark>,
+    _subscription: Subscription,
+}
+
+impl BookmarksInFile {
+    fn new(buffer: Entity<Buffer>, cx: &mut Context<BookmarkStore>) -> Self {
+        let subscription = cx.subscribe(&buffer, |bookmark_store, buffer, event, cx| {
+            if let BufferEvent::FileHandleChanged = event {
+                bookmark_store.file_handle_changed(buffer, cx);
+            }
+        });
+        Self {
+            buffer,
+            bookmarks: Vec::new(),
+            _subscription: subscription,
+        }
+    }
+
+    fn bookmark_at_row(&self, row: u32, snapshot: &BufferSnapshot) -> Option<usize> {
+        self.bookmarks.iter().position(|bookmark| {
+            snapshot.summary_for_anchor::<Point>(&bookmark.position).row == row
+        })
+    }
+}
+
+pub enum BookmarkStoreEvent {
+    BookmarksUpdated(Arc<Path>),
+    BookmarksCleared(Vec<Arc<Path>>),
+}
+
+impl EventEmitter<BookmarkStoreEvent> for BookmarkStore {}
+
+pub struct BookmarkStore {
+    bookmarks: BTreeMap<Arc<Path>, BookmarksInFile>,
+    worktree_store: Entity<WorktreeStore>,
+    buffer_store: Entity<BufferStore>,
+}
+
+impl BookmarkStore {
+    pub fn new(worktree_store: Entity<WorktreeStore>, buffer_store: Entity<BufferStore>) -> Self {
+        Self {
+            bookmarks: BTreeMap::new(),
+            worktree_store,
+            buffer_store,
+        }
+    }
+
+    pub fn abs_path_from_buffer(buffer: &Entity<Buffer>, cx: &App) -> Option<Arc<Path>> {
+        worktree::File::from_dyn(buffer.read(cx).file())
+            .map(|file| file.worktree.read(cx).absolutize(&file.path))
+            .map(Arc::<Path>::from)
+    }
+
+    /// Bookmarks the line containing `position`, or removes the bookmark from it if there is one.
+    pub fn toggle_bookmark(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: text::Anchor,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(abs_path) = Self::abs_path_from_buffer(&buffer, cx) else {
+            return;
+        };
+        let snapshot = buffer.read(cx).snapshot();
+        let row = snapshot.summary_for_anchor::<Point>(&position).row;
+
+        let bookmarks_in_file = self
+            .bookmarks
+            .entry(abs_path.clone())
+            .or_insert_with(|| BookmarksInFile::new(buffer, cx));
+        if let Some(ix) = bookmarks_in_file.bookmark_at_row(row, &snapshot) {
+            bookmarks_in_file.bookmarks.remove(ix);
+            if bookmarks_in_file.bookmarks.is_empty() {
+                self.bookmarks.remove(&abs_path);
+            }
+        } else {
+            insert_bookmark(bookmarks_in_file, row, None, &snapshot);
+        }
+
+        cx.emit(BookmarkStoreEvent::BookmarksUpdated(abs_path));
+        cx.notify();
+    }
+
+    /// Sets the label of the bookmark on the line containing `position`, bookmarking the line if needed.
+    pub fn set_bookmark_label(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: text::Anchor,
+        label: Option<Arc<str>>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(abs_path) = Self::abs_path_from_buffer(&buffer, cx) else {
+            return;
+        };
+        let snapshot = buffer.read(cx).snapshot();
+        let row = snapshot.summary_for_anchor::<Point>(&position).row;
+        let label = label.filter(|label| !label.trim().is_empty());
+
+        let bookmarks_in_file = self
+            .bookmarks
+            .entry(abs_path.clone())
+            .or_insert_with(|| BookmarksInFile::new(buffer, cx));
+        if let Some(ix) = bookmarks_in_file.bookmark_at_row(row, &snapshot) {
+            bookmarks_in_file.bookmarks[ix].label = label;
+        } else {
+            insert_bookmark(bookmarks_in_file, row, label, &snapshot);
+        }
+
+        cx.emit(BookmarkStoreEvent::BookmarksUpdated(abs_path));
+        cx.notify();
+    }
+
+    pub fn clear_bookmarks(&mut self, cx: &mut Context<Self>) {
+        let bookmark_paths = self.bookmarks.keys().cloned().collect();
+        self.bookmarks.clear();
+        cx.emit(BookmarkStoreEvent::BookmarksCleared(bookmark_paths));
+        cx.notify();
+    }
+
+    /// Returns the bookmarks of the buffer, in order, optionally limited to those within `range`.
+    pub fn bookmarks<'a>(
+        &'a self,
+        buffer: &Entity<Buffer>,
+        range: Option<Range<text::Anchor>>,
+        buffer_snapshot: &'a BufferSnapshot,
+        cx: &App,
+    ) -> impl Iterator<Item = &'a Bookmark> + 'a {
+        Self::abs_path_from_buffer(buffer, cx)
+            .and_then(|path| self.bookmarks.get(&path))
+            .into_iter()
+            .flat_map(move |bookmarks_in_file| {
+                let range = range.clone();
+                bookmarks_in_file.bookmarks.iter().filter(move |bookmark| {
+                    range.as_ref().is_none_or(|range| {
+                        bookmark.position.cmp(&range.start, buffer_snapshot).is_ge()
+                            && bookmark.position.cmp(&range.end, buffer_snapshot).is_le()
+                    })
+                })
+            })
+    }
+
+    pub fn bookmark_at_row(
+        &self,
+        buffer: &Entity<Buffer>,
+        row: u32,
+        cx: &App,
+    ) -> Option<&Bookmark> {
+        let abs_path = Self::abs_path_from_buffer(buffer, cx)?;
+        let bookmarks_in_file = self.bookmarks.get(&abs_path)?;
+        let snapshot = bookmarks_in_file.buffer.read(cx).snapshot();
+        let ix = bookmarks_in_file.bookmark_at_row(row, &snapshot)?;
+        bookmarks_in_file.bookmarks.get(ix)
+    }
+
+    /// Returns every bookmark in the project, ordered by path and then by position.
+    pub fn all_bookmarks(&self) -> impl Iterator<Item = (&Arc<Path>, &Entity<Buffer>, &Bookmark)> {
+        self.bookmarks.iter().flat_map(|(path, bookmarks_in_file)| {
+            bookmarks_in_file
+                .bookmarks
+                .iter()
+                .map(move |bookmark| (path, &bookmarks_in_file.buffer, bookmark))
+        })
+    }
+
+    pub fn all_serialized_bookmarks(
+        &self,
+        cx: &App,
+    ) -> BTreeMap<Arc<Path>, Vec<SerializedBookmark>> {
+        self.bookmarks
+            .iter()
+            .map(|(path, bookmarks_in_file)| {
+                let snapshot = bookmarks_in_file.buffer.read(cx).snapshot();
+                (
+                    path.clone(),
+                    bookmarks_in_file
+                        .bookmarks
+                        .iter()
+                        .map(|bookmark| SerializedBookmark {
+                            path: path.clone(),
+                            row: snapshot.summary_for_anchor::<Point>(&bookmark.position).row,
+                            label: bookmark.label.clone(),
+                        })
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    pub fn with_serialized_bookmarks(
+        &self,
+        bookmarks: BTreeMap<Arc<Path>, Vec<SerializedBookmark>>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let worktree_store = self.worktree_store.clone();
+        let buffer_store = self.buffer_store.clone();
+        cx.spawn(async move |this, cx| {
+            for (path, serialized_bookmarks) in bookmarks {
+                if serialized_bookmarks.is_empty() {
+                    continue;
+                }
+                let (worktree, relative_path) = worktree_store
+                    .update(cx, |worktree_store, cx| {
+                        worktree_store.find_or_create_worktree(&path, false, cx)
+                    })?
+                    .await?;
+                let buffer = buffer_store
+                    .update(cx, |buffer_store, cx| {
+                        let path = ProjectPath {
+                            worktree_id: worktree.read(cx).id(),
+                            path: relative_path,
+                        };
+                        buffer_store.open_buffer(path, cx)
+                    })?
+                    .await;
+                let buffer = match buffer {
+                    Ok(buffer) => buffer,
+                    Err(e) => {
+                        log::error!("Failed to open buffer for bookmarks at {path:?}: {e:#}");
+                        continue;
+                    }
+                };
+                let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot())?;
+
+                this.update(cx, |this, cx| {
+                    let bookmarks_in_file = this
+                        .bookmarks
+                        .entry(path.clone())
+                        .or_insert_with(|| BookmarksInFile::new(buffer, cx));
+                    for serialized_bookmark in serialized_bookmarks {
+                        if serialized_bookmark.row > snapshot.max_point().row {
+                            log::error!("skipping a deserialized bookmark that's out of range");
+                            continue;
+                        }
+                        if bookmarks_in_file
+                            .bookmark_at_row(serialized_bookmark.row, &snapshot)
+                            .is_none()
+                        {
+                            insert_bookmark(
+                                bookmarks_in_file,
+                                serialized_bookmark.row,
+                                serialized_bookmark.label,
+                                &snapshot,
+                            );
+                        }
+                    }
+                    cx.notify();
+                })?;
+            }
+            Ok(())
+        })
+    }
+
+    fn file_handle_changed(&mut self, buffer: Entity<Buffer>, cx: &mut Context<Self>) {
+        let entity_id = buffer.entity_id();
+        let Some(old_path) = self
+            .bookmarks
+            .iter()
+            .find(|(_, bookmarks_in_file)| bookmarks_in_file.buffer.entity_id() == entity_id)
+            .map(|(path, _)| path.clone())
+        else {
+            return;
+        };
+
+        if buffer
+            .read(cx)
+            .file()
+            .is_none_or(|file| file.disk_state() == DiskState::Deleted)
+        {
+            self.bookmarks.remove(&old_path);
+            cx.emit(BookmarkStoreEvent::BookmarksCleared(vec![old_path]));
+            cx.notify();
+            return;
+        }
+
+        if let Some(new_path) = Self::abs_path_from_buffer(&buffer, cx)
+            && new_path != old_path
+            && !self.bookmarks.contains_key(&new_path)
+            && let Some(bookmarks_in_file) = self.bookmarks.remove(&old_path)
+        {
+            self.bookmarks.insert(new_path.clone(), bookmarks_in_file);
+            cx.emit(BookmarkStoreEvent::BookmarksUpdated(new_path));
+            cx.notify();
+        }
+    }
+}
+
+fn insert_bookmark(
+    bookmarks_in_file: &mut BookmarksInFile,
+    row: u32,
+    label: Option<Arc<str>>,
+    snapshot: &BufferSnapshot,
+) {
+    let position = snapshot.anchor_after(Point::new(row, 0));
+    let ix = bookmarks_in_file
+        .bookmarks
+        .partition_point(|bookmark| bookmark.position.cmp(&position, snapshot).is_lt());
+    bookmarks_in_file
+        .bookmarks
+        .insert(ix, Bookmark { position, label });
+}
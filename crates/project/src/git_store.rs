@@ -7,7 +7,7 @@ use crate::{
     worktree_store::{WorktreeStore, WorktreeStoreEvent},
 };
 use anyhow::{Context as _, Result, anyhow, bail};
-use askpass::{AskPassDelegate, EncryptedPassword};
+use askpass::{AskPassDelegate, EncryptedPassword, GitEditorDelegate, GitEditorRequest};
 use buffer_diff::{BufferDiff, BufferDiffEvent};
 use client::ProjectId;
 use collections::HashMap;
@@ -21,12 +21,16 @@ use futures::{
 };
 use git::{
     BuildPermalinkParams, GitHostingProviderRegistry, Oid,
+    bisect::{BisectOperation, BisectProgress, BisectStatus},
     blame::Blame,
+    history::{FileHistoryEntry, LogEntry, LogOptions},
     parse_git_remote_url,
+    rebase::{RebaseOperation, RebaseProgress},
     repository::{
         Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions, DiffType, FetchOptions,
-        GitRepository, GitRepositoryCheckpoint, PushOptions, Remote, RemoteCommandOutput, RepoPath,
-        ResetMode, UpstreamTrackingStatus,
+        GitRepository, GitRepositoryCheckpoint, GitWorktree, PushOptions, Remote,
+        RemoteCommandOutput, RepoPath, ResetMode, Submodule, SubmoduleOperation, Tag,
+        UpstreamTrackingStatus,
     },
     stash::{GitStash, StashEntry},
     status::{
@@ -98,6 +102,7 @@ struct SharedDiffs {
 struct BufferGitState {
     unstaged_diff: Option<WeakEntity<BufferDiff>>,
     uncommitted_diff: Option<WeakEntity<BufferDiff>>,
+    commit_diffs: HashMap<Oid, DiffSinceCommit>,
     conflict_set: Option<WeakEntity<ConflictSet>>,
     recalculate_diff_task: Option<Task<Result<()>>>,
     reparse_conflict_markers_task: Option<Task<Result<()>>>,
@@ -124,6 +129,13 @@ struct BufferGitState {
     language_changed: bool,
 }
 
+/// A diff between a buffer and the contents of its file at a commit, which never change.
+struct DiffSinceCommit {
+    diff: WeakEntity<BufferDiff>,
+    base_text: Option<Arc<String>>,
+    base_text_changed: bool,
+}
+
 #[derive(Clone, Debug)]
 enum DiffBasesChange {
     SetIndex(Option<String>),
@@ -139,6 +151,7 @@ enum DiffBasesChange {
 enum DiffKind {
     Unstaged,
     Uncommitted,
+    SinceCommit(Oid),
 }
 
 enum GitStoreState {
@@ -253,6 +266,12 @@ pub struct RepositorySnapshot {
     pub remote_origin_url: Option<String>,
     pub remote_upstream_url: Option<String>,
     pub stash_entries: GitStash,
+    /// The progress of the interactive rebase that's stopped in this repository, if any.
+    pub rebase: Option<RebaseProgress>,
+    /// The submodules of the repository, ordered by path.
+    pub submodules: Arc<[Submodule]>,
+    /// The state of the bisect in progress in this repository, if any.
+    pub bisect: Option<BisectStatus>,
 }
 
 type JobId = u64;
@@ -276,6 +295,8 @@ pub struct Repository {
     job_id: JobId,
     askpass_delegates: Arc<Mutex<HashMap<u64, AskPassDelegate>>>,
     latest_askpass_id: u64,
+    git_editor_delegates: Arc<Mutex<HashMap<u64, GitEditorDelegate>>>,
+    latest_git_editor_id: u64,
 }
 
 impl std::ops::Deref for Repository {
@@ -410,6 +431,7 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_push);
         client.add_entity_request_handler(Self::handle_pull);
         client.add_entity_request_handler(Self::handle_fetch);
+        client.add_entity_request_handler(Self::handle_rebase);
         client.add_entity_request_handler(Self::handle_stage);
         client.add_entity_request_handler(Self::handle_unstage);
         client.add_entity_request_handler(Self::handle_stash);
@@ -418,12 +440,30 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_stash_drop);
         client.add_entity_request_handler(Self::handle_commit);
         client.add_entity_request_handler(Self::handle_reset);
+        client.add_entity_request_handler(Self::handle_cherry_pick);
+        client.add_entity_request_handler(Self::handle_revert);
+        client.add_entity_request_handler(Self::handle_bisect);
+        client.add_entity_request_handler(Self::handle_bisect_log);
+        client.add_entity_request_handler(Self::handle_get_tags);
+        client.add_entity_request_handler(Self::handle_create_tag);
+        client.add_entity_request_handler(Self::handle_delete_tag);
+        client.add_entity_request_handler(Self::handle_push_tag);
+        client.add_entity_request_handler(Self::handle_submodule_operation);
+        client.add_entity_request_handler(Self::handle_get_worktrees);
+        client.add_entity_request_handler(Self::handle_create_worktree);
+        client.add_entity_request_handler(Self::handle_remove_worktree);
+        client.add_entity_request_handler(Self::handle_resolve_diff_base);
+        client.add_entity_request_handler(Self::handle_changed_paths_since);
+        client.add_entity_request_handler(Self::handle_load_text_at_commit);
         client.add_entity_request_handler(Self::handle_show);
+        client.add_entity_request_handler(Self::handle_log);
+        client.add_entity_request_handler(Self::handle_file_history);
         client.add_entity_request_handler(Self::handle_load_commit_diff);
         client.add_entity_request_handler(Self::handle_checkout_files);
         client.add_entity_request_handler(Self::handle_open_commit_message_buffer);
         client.add_entity_request_handler(Self::handle_set_index_text);
         client.add_entity_request_handler(Self::handle_askpass);
+        client.add_entity_request_handler(Self::handle_git_editor);
         client.add_entity_request_handler(Self::handle_check_for_pushed_commits);
         client.add_entity_request_handler(Self::handle_git_diff);
         client.add_entity_request_handler(Self::handle_open_unstaged_diff);
@@ -655,6 +695,124 @@ impl GitStore {
         cx.background_spawn(async move { task.await.map_err(|e| anyhow!("{e}")) })
     }
 
+    /// Opens a diff between the buffer and the contents of its file at the given commit, which is
+    /// kept up to date as the buffer changes. Unlike the other diffs, its hunks can't be staged.
+    pub fn open_diff_since(
+        &mut self,
+        commit: Oid,
+        buffer: Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Entity<BufferDiff>>> {
+        let buffer_id = buffer.read(cx).remote_id();
+
+        if let Some(diff_state) = self.diffs.get(&buffer_id)
+            && let Some(diff) = diff_state
+                .read(cx)
+                .commit_diffs
+                .get(&commit)
+                .and_then(|commit_diff| commit_diff.diff.upgrade())
+        {
+            if let Some(task) =
+                diff_state.update(cx, |diff_state, _| diff_state.wait_for_recalculation())
+            {
+                return cx.background_executor().spawn(async move {
+                    task.await;
+                    Ok(diff)
+                });
+            }
+            return Task::ready(Ok(diff));
+        }
+
+        let Some((repo, repo_path)) =
+            self.repository_and_path_for_buffer_id(buffer.read(cx).remote_id(), cx)
+        else {
+            return Task::ready(Err(anyhow!("failed to find git repository for buffer")));
+        };
+
+        let task = self
+            .loading_diffs
+            .entry((buffer_id, DiffKind::SinceCommit(commit)))
+            .or_insert_with(|| {
+                let text = repo.update(cx, |repo, cx| {
+                    repo.load_text_at_commit(commit, repo_path, cx)
+                });
+
+                cx.spawn(async move |this, cx| {
+                    Self::open_diff_since_internal(this, commit, text.await, buffer, cx)
+                        .await
+                        .map_err(Arc::new)
+                })
+                .shared()
+            })
+            .clone();
+
+        cx.background_spawn(async move { task.await.map_err(|e| anyhow!("{e}")) })
+    }
+
+    async fn open_diff_since_internal(
+        this: WeakEntity<Self>,
+        commit: Oid,
+        text: Result<Option<String>>,
+        buffer_entity: Entity<Buffer>,
+        cx: &mut AsyncApp,
+    ) -> Result<Entity<BufferDiff>> {
+        let kind = DiffKind::SinceCommit(commit);
+        let text = match text {
+            Err(e) => {
+                this.update(cx, |this, cx| {
+                    let buffer_id = buffer_entity.read(cx).remote_id();
+                    this.loading_diffs.remove(&(buffer_id, kind));
+                })?;
+                return Err(e);
+            }
+            Ok(text) => text,
+        };
+
+        this.update(cx, |this, cx| {
+            let buffer = buffer_entity.read(cx);
+            let buffer_id = buffer.remote_id();
+            let language = buffer.language().cloned();
+            let language_registry = buffer.language_registry();
+            let text_snapshot = buffer.text_snapshot();
+            this.loading_diffs.remove(&(buffer_id, kind));
+
+            let git_store = cx.weak_entity();
+            let diff_state = this
+                .diffs
+                .entry(buffer_id)
+                .or_insert_with(|| cx.new(|_| BufferGitState::new(git_store)));
+
+            // The diff isn't subscribed to, as its hunks are never staged.
+            let diff = cx.new(|cx| BufferDiff::new(&text_snapshot, cx));
+            diff_state.update(cx, |diff_state, cx| {
+                diff_state.language = language;
+                diff_state.language_registry = language_registry;
+                diff_state.commit_diffs.insert(
+                    commit,
+                    DiffSinceCommit {
+                        diff: diff.downgrade(),
+                        base_text: text.map(|mut text| {
+                            text::LineEnding::normalize(&mut text);
+                            Arc::new(text)
+                        }),
+                        base_text_changed: true,
+                    },
+                );
+
+                diff_state.recalculate_diffs(text_snapshot, cx);
+                let rx = diff_state.wait_for_recalculation();
+
+                anyhow::Ok(async move {
+                    if let Some(rx) = rx {
+                        rx.await;
+                    }
+                    Ok(diff)
+                })
+            })
+        })??
+        .await
+    }
+
     async fn open_diff_internal(
         this: WeakEntity<Self>,
         kind: DiffKind,
@@ -697,6 +855,7 @@ impl GitStore {
 
                 match kind {
                     DiffKind::Unstaged => diff_state.unstaged_diff = Some(diff.downgrade()),
+                    DiffKind::SinceCommit(_) => unreachable!("opened by open_diff_since"),
                     DiffKind::Uncommitted => {
                         let unstaged_diff = if let Some(diff) = diff_state.unstaged_diff() {
                             diff
@@ -1397,6 +1556,21 @@ impl GitStore {
             .max_by_key(|(repo, _)| repo.read(cx).work_directory_abs_path.clone())
     }
 
+    /// Returns the submodule checked out at the given path, as recorded by the repository that
+    /// contains it. The submodule is also discovered as a repository of its own, so this can't go
+    /// through [`Self::repository_and_path_for_project_path`].
+    pub fn submodule_for_project_path(&self, path: &ProjectPath, cx: &App) -> Option<Submodule> {
+        let abs_path = self.worktree_store.read(cx).absolutize(path, cx)?;
+        self.repositories.values().find_map(|repo| {
+            let repo = repo.read(cx);
+            if repo.submodules.is_empty() {
+                return None;
+            }
+            let repo_path = repo.abs_path_to_repo_path(&abs_path)?;
+            repo.submodule_for_path(&repo_path).cloned()
+        })
+    }
+
     pub fn git_init(
         &self,
         path: Arc<Path>,
@@ -1642,6 +1816,70 @@ impl GitStore {
         })
     }
 
+    async fn handle_push_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitPushTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let askpass_id = envelope.payload.askpass_id;
+        let askpass = make_remote_delegate(
+            this,
+            envelope.payload.project_id,
+            repository_id,
+            askpass_id,
+            &mut cx,
+        );
+
+        let name = envelope.payload.name.into();
+        let remote_name = envelope.payload.remote_name.into();
+
+        let remote_output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.push_tag(name, remote_name, askpass, cx)
+            })?
+            .await??;
+        Ok(proto::RemoteMessageResponse {
+            stdout: remote_output.stdout,
+            stderr: remote_output.stderr,
+        })
+    }
+
+    async fn handle_submodule_operation(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitSubmoduleOperation>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let operation = match envelope.payload.operation() {
+            proto::git_submodule_operation::Operation::Init => SubmoduleOperation::Init,
+            proto::git_submodule_operation::Operation::Update => SubmoduleOperation::Update,
+            proto::git_submodule_operation::Operation::Sync => SubmoduleOperation::Sync,
+        };
+
+        let askpass_id = envelope.payload.askpass_id;
+        let askpass = make_remote_delegate(
+            this,
+            envelope.payload.project_id,
+            repository_id,
+            askpass_id,
+            &mut cx,
+        );
+
+        let remote_output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.run_submodule_operation(operation, askpass, cx)
+            })?
+            .await??;
+        Ok(proto::RemoteMessageResponse {
+            stdout: remote_output.stdout,
+            stderr: remote_output.stderr,
+        })
+    }
+
     async fn handle_pull(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::Pull>,
@@ -1673,6 +1911,41 @@ impl GitStore {
         })
     }
 
+    async fn handle_rebase(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebase>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let operation = match envelope.payload.operation() {
+            proto::git_rebase::Operation::Start => RebaseOperation::Start {
+                onto: envelope.payload.onto.map(SharedString::from),
+            },
+            proto::git_rebase::Operation::Continue => RebaseOperation::Continue,
+            proto::git_rebase::Operation::Skip => RebaseOperation::Skip,
+            proto::git_rebase::Operation::Abort => RebaseOperation::Abort,
+        };
+        let editor = make_remote_git_editor(
+            this,
+            envelope.payload.project_id,
+            repository_id,
+            envelope.payload.editor_id,
+            &mut cx,
+        );
+
+        let output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.rebase(operation, editor, cx)
+            })?
+            .await??;
+
+        Ok(proto::RemoteMessageResponse {
+            stdout: output.stdout,
+            stderr: output.stderr,
+        })
+    }
+
     async fn handle_stage(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::Stage>,
@@ -1988,6 +2261,67 @@ impl GitStore {
         })
     }
 
+    async fn handle_log(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitLog>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitLogResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let options = LogOptions {
+            skip: envelope.payload.skip as usize,
+            limit: envelope.payload.limit as usize,
+            author: envelope.payload.author,
+            message: envelope.payload.message,
+            path: envelope
+                .payload
+                .path
+                .as_deref()
+                .map(RepoPath::from_proto)
+                .transpose()?,
+        };
+
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.log(options)
+            })?
+            .await??;
+        Ok(proto::GitLogResponse {
+            entries: entries.iter().map(log_entry_to_proto).collect(),
+        })
+    }
+
+    async fn handle_file_history(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitFileHistory>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitFileHistoryResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let path = RepoPath::from_proto(&envelope.payload.path)?;
+        let rows = envelope
+            .payload
+            .start_row
+            .zip(envelope.payload.end_row)
+            .map(|(start, end)| start..end);
+
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.file_history(path, rows)
+            })?
+            .await??;
+        Ok(proto::GitFileHistoryResponse {
+            entries: entries
+                .iter()
+                .map(|entry| proto::GitFileHistoryEntry {
+                    commit: Some(log_entry_to_proto(&entry.commit)),
+                    path: entry.path.to_proto(),
+                    old_path: entry.old_path.to_proto(),
+                })
+                .collect(),
+        })
+    }
+
     async fn handle_load_commit_diff(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::LoadCommitDiff>,
@@ -2025,6 +2359,7 @@ impl GitStore {
         let mode = match envelope.payload.mode() {
             git_reset::ResetMode::Soft => ResetMode::Soft,
             git_reset::ResetMode::Mixed => ResetMode::Mixed,
+            git_reset::ResetMode::Hard => ResetMode::Hard,
         };
 
         repository_handle
@@ -2035,72 +2370,327 @@ impl GitStore {
         Ok(proto::Ack {})
     }
 
-    async fn handle_checkout_files(
+    async fn handle_cherry_pick(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::GitCheckoutFiles>,
+        envelope: TypedEnvelope<proto::GitCherryPick>,
         mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
-        let paths = envelope
-            .payload
-            .paths
-            .iter()
-            .map(|s| RepoPath::from_proto(s))
-            .collect::<Result<Vec<_>>>()?;
 
         repository_handle
             .update(&mut cx, |repository_handle, cx| {
-                repository_handle.checkout_files(&envelope.payload.commit, paths, cx)
+                repository_handle.cherry_pick(envelope.payload.commit, cx)
             })?
             .await??;
         Ok(proto::Ack {})
     }
 
-    async fn handle_open_commit_message_buffer(
+    async fn handle_bisect(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::OpenCommitMessageBuffer>,
+        envelope: TypedEnvelope<proto::GitBisect>,
         mut cx: AsyncApp,
-    ) -> Result<proto::OpenBufferResponse> {
+    ) -> Result<proto::GitBisectResponse> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
-        let buffer = repository
-            .update(&mut cx, |repository, cx| {
-                repository.open_commit_buffer(None, this.read(cx).buffer_store.clone(), cx)
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let commit = envelope.payload.commit.clone().map(SharedString::from);
+        let operation = match envelope.payload.operation() {
+            proto::git_bisect::Operation::Start => BisectOperation::Start {
+                bad: commit,
+                good: envelope
+                    .payload
+                    .good
+                    .into_iter()
+                    .map(SharedString::from)
+                    .collect(),
+            },
+            proto::git_bisect::Operation::Good => BisectOperation::Good(commit),
+            proto::git_bisect::Operation::Bad => BisectOperation::Bad(commit),
+            proto::git_bisect::Operation::Skip => BisectOperation::Skip(commit),
+            proto::git_bisect::Operation::Reset => BisectOperation::Reset,
+        };
+
+        let output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.bisect(operation, cx)
             })?
-            .await?;
+            .await??;
+        Ok(proto::GitBisectResponse { output })
+    }
 
-        let buffer_id = buffer.read_with(&cx, |buffer, _| buffer.remote_id())?;
-        this.update(&mut cx, |this, cx| {
-            this.buffer_store.update(cx, |buffer_store, cx| {
-                buffer_store
-                    .create_buffer_for_peer(
-                        &buffer,
-                        envelope.original_sender_id.unwrap_or(envelope.sender_id),
-                        cx,
-                    )
-                    .detach_and_log_err(cx);
-            })
-        })?;
+    async fn handle_bisect_log(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectLog>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitBisectLogResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
 
-        Ok(proto::OpenBufferResponse {
-            buffer_id: buffer_id.to_proto(),
-        })
+        let log = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.bisect_log()
+            })?
+            .await??;
+        Ok(proto::GitBisectLogResponse { log })
     }
 
-    async fn handle_askpass(
+    async fn handle_revert(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::AskPassRequest>,
+        envelope: TypedEnvelope<proto::GitRevert>,
         mut cx: AsyncApp,
-    ) -> Result<proto::AskPassResponse> {
+    ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
-
-        let delegates = cx.update(|cx| repository.read(cx).askpass_delegates.clone())?;
-        let Some(mut askpass) = delegates.lock().remove(&envelope.payload.askpass_id) else {
-            debug_panic!("no askpass found");
-            anyhow::bail!("no askpass found");
-        };
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.revert(envelope.payload.commit, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_get_tags(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitGetTags>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitTagsResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let tags = repository_handle
+            .update(&mut cx, |repository_handle, _| repository_handle.tags())?
+            .await??;
+
+        Ok(proto::GitTagsResponse {
+            tags: tags.iter().map(tag_to_proto).collect(),
+        })
+    }
+
+    async fn handle_create_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCreateTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.create_tag(
+                    envelope.payload.name,
+                    envelope.payload.commit,
+                    envelope.payload.message,
+                    cx,
+                )
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_delete_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitDeleteTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.delete_tag(envelope.payload.name, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_get_worktrees(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitGetWorktrees>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitWorktreesResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let worktrees = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.worktrees()
+            })?
+            .await??;
+
+        Ok(proto::GitWorktreesResponse {
+            worktrees: worktrees.iter().map(worktree_to_proto).collect(),
+        })
+    }
+
+    async fn handle_create_worktree(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCreateWorktree>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.create_worktree(
+                    PathBuf::from(envelope.payload.path),
+                    envelope.payload.commit,
+                    cx,
+                )
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_remove_worktree(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRemoveWorktree>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.remove_worktree(
+                    PathBuf::from(envelope.payload.path),
+                    envelope.payload.force,
+                    cx,
+                )
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_resolve_diff_base(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitResolveDiffBase>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitResolveDiffBaseResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let commit = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle
+                    .resolve_diff_base(envelope.payload.base, envelope.payload.merge_base)
+            })?
+            .await??;
+        Ok(proto::GitResolveDiffBaseResponse {
+            sha: commit.to_string(),
+        })
+    }
+
+    async fn handle_changed_paths_since(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitChangedPathsSince>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitChangedPathsSinceResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let commit = envelope.payload.commit.parse()?;
+
+        let changes = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.changed_paths_since(commit)
+            })?
+            .await??;
+        Ok(proto::GitChangedPathsSinceResponse {
+            changes: changes
+                .into_iter()
+                .map(|(path, status)| proto::GitChangedPath {
+                    path: path.to_proto(),
+                    status: tracked_status_to_proto(status),
+                })
+                .collect(),
+        })
+    }
+
+    async fn handle_load_text_at_commit(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitLoadTextAtCommit>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitLoadTextAtCommitResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let commit = envelope.payload.commit.parse()?;
+        let repo_path = RepoPath::from_proto(&envelope.payload.path)?;
+
+        let text = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.load_text_at_commit(commit, repo_path, cx)
+            })?
+            .await?;
+        Ok(proto::GitLoadTextAtCommitResponse { text })
+    }
+
+    async fn handle_checkout_files(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCheckoutFiles>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let paths = envelope
+            .payload
+            .paths
+            .iter()
+            .map(|s| RepoPath::from_proto(s))
+            .collect::<Result<Vec<_>>>()?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.checkout_files(&envelope.payload.commit, paths, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_open_commit_message_buffer(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::OpenCommitMessageBuffer>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::OpenBufferResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let buffer = repository
+            .update(&mut cx, |repository, cx| {
+                repository.open_commit_buffer(None, this.read(cx).buffer_store.clone(), cx)
+            })?
+            .await?;
+
+        let buffer_id = buffer.read_with(&cx, |buffer, _| buffer.remote_id())?;
+        this.update(&mut cx, |this, cx| {
+            this.buffer_store.update(cx, |buffer_store, cx| {
+                buffer_store
+                    .create_buffer_for_peer(
+                        &buffer,
+                        envelope.original_sender_id.unwrap_or(envelope.sender_id),
+                        cx,
+                    )
+                    .detach_and_log_err(cx);
+            })
+        })?;
+
+        Ok(proto::OpenBufferResponse {
+            buffer_id: buffer_id.to_proto(),
+        })
+    }
+
+    async fn handle_askpass(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::AskPassRequest>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::AskPassResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let delegates = cx.update(|cx| repository.read(cx).askpass_delegates.clone())?;
+        let Some(mut askpass) = delegates.lock().remove(&envelope.payload.askpass_id) else {
+            debug_panic!("no askpass found");
+            anyhow::bail!("no askpass found");
+        };
 
         let response = askpass.ask_password(envelope.payload.prompt).await?;
 
@@ -2111,6 +2701,32 @@ impl GitStore {
         response.try_into()
     }
 
+    async fn handle_git_editor(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitEditorRequest>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitEditorResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let delegates = cx.update(|cx| repository.read(cx).git_editor_delegates.clone())?;
+        let Some(mut editor) = delegates.lock().remove(&envelope.payload.editor_id) else {
+            debug_panic!("no git editor found");
+            anyhow::bail!("no git editor found");
+        };
+
+        let response = editor
+            .edit(GitEditorRequest {
+                file_name: envelope.payload.file_name,
+                content: envelope.payload.content,
+            })
+            .await;
+
+        delegates.lock().insert(envelope.payload.editor_id, editor);
+
+        Ok(proto::GitEditorResponse { content: response? })
+    }
+
     async fn handle_check_for_pushed_commits(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::CheckForPushedCommits>,
@@ -2416,6 +3032,7 @@ impl BufferGitState {
         Self {
             unstaged_diff: Default::default(),
             uncommitted_diff: Default::default(),
+            commit_diffs: Default::default(),
             recalculate_diff_task: Default::default(),
             language: Default::default(),
             language_registry: Default::default(),
@@ -2607,6 +3224,20 @@ impl BufferGitState {
             (None, None) => true,
             _ => false,
         };
+        self.commit_diffs
+            .retain(|_, commit_diff| commit_diff.diff.upgrade().is_some());
+        let commit_diffs = self
+            .commit_diffs
+            .iter()
+            .filter_map(|(commit, commit_diff)| {
+                Some((
+                    *commit,
+                    commit_diff.diff.upgrade()?,
+                    commit_diff.base_text.clone(),
+                    commit_diff.base_text_changed,
+                ))
+            })
+            .collect::<Vec<_>>();
         self.recalculate_diff_task = Some(cx.spawn(async move |this, cx| {
             log::debug!(
                 "start recalculating diffs for buffer {}",
@@ -2651,6 +3282,22 @@ impl BufferGitState {
                 }
             }
 
+            let mut new_commit_diffs = Vec::new();
+            for (commit, diff, base_text, base_text_changed) in commit_diffs {
+                let snapshot = BufferDiff::update_diff(
+                    diff.clone(),
+                    buffer.clone(),
+                    base_text,
+                    base_text_changed,
+                    language_changed,
+                    language.clone(),
+                    language_registry.clone(),
+                    cx,
+                )
+                .await?;
+                new_commit_diffs.push((commit, diff, snapshot));
+            }
+
             let cancel = this.update(cx, |this, _| {
                 // This checks whether all pending stage/unstage operations
                 // have quiesced (i.e. both the corresponding write and the
@@ -2706,6 +3353,17 @@ impl BufferGitState {
                 })?;
             }
 
+            let mut updated_commits = Vec::new();
+            for (commit, diff, snapshot) in new_commit_diffs {
+                diff.update(cx, |diff, cx| {
+                    if language_changed {
+                        diff.language_changed(cx);
+                    }
+                    diff.set_snapshot(snapshot, &buffer, cx);
+                })?;
+                updated_commits.push(commit);
+            }
+
             log::debug!(
                 "finished recalculating diffs for buffer {}",
                 buffer.remote_id()
@@ -2713,6 +3371,11 @@ impl BufferGitState {
 
             if let Some(this) = this.upgrade() {
                 this.update(cx, |this, _| {
+                    for commit in &updated_commits {
+                        if let Some(commit_diff) = this.commit_diffs.get_mut(commit) {
+                            commit_diff.base_text_changed = false;
+                        }
+                    }
                     this.index_changed = false;
                     this.head_changed = false;
                     this.language_changed = false;
@@ -2756,6 +3419,35 @@ fn make_remote_delegate(
     })
 }
 
+fn make_remote_git_editor(
+    this: Entity<GitStore>,
+    project_id: u64,
+    repository_id: RepositoryId,
+    editor_id: u64,
+    cx: &mut AsyncApp,
+) -> GitEditorDelegate {
+    GitEditorDelegate::new(cx, move |request, tx, cx| {
+        this.update(cx, |this, cx| {
+            let Some((client, _)) = this.downstream_client() else {
+                return;
+            };
+            let response = client.request(proto::GitEditorRequest {
+                project_id,
+                repository_id: repository_id.to_proto(),
+                editor_id,
+                file_name: request.file_name,
+                content: request.content,
+            });
+            cx.spawn(async move |_, _| {
+                tx.send(response.await?.content).ok();
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+        })
+        .log_err();
+    })
+}
+
 impl RepositoryId {
     pub fn to_proto(self) -> u64 {
         self.0
@@ -2779,6 +3471,9 @@ impl RepositorySnapshot {
             remote_origin_url: None,
             remote_upstream_url: None,
             stash_entries: Default::default(),
+            rebase: None,
+            submodules: Arc::default(),
+            bisect: None,
             path_style,
         }
     }
@@ -2812,6 +3507,12 @@ impl RepositorySnapshot {
                 .iter()
                 .map(stash_to_proto)
                 .collect(),
+            rebase_progress: self.rebase.map(|rebase| proto::RebaseProgress {
+                step: rebase.step as u64,
+                total: rebase.total as u64,
+            }),
+            submodules: self.submodules.iter().map(submodule_to_proto).collect(),
+            bisect_status: self.bisect.as_ref().map(bisect_status_to_proto),
         }
     }
 
@@ -2881,6 +3582,12 @@ impl RepositorySnapshot {
                 .iter()
                 .map(stash_to_proto)
                 .collect(),
+            rebase_progress: self.rebase.map(|rebase| proto::RebaseProgress {
+                step: rebase.step as u64,
+                total: rebase.total as u64,
+            }),
+            submodules: self.submodules.iter().map(submodule_to_proto).collect(),
+            bisect_status: self.bisect.as_ref().map(bisect_status_to_proto),
         }
     }
 
@@ -2898,6 +3605,14 @@ impl RepositorySnapshot {
             .cloned()
     }
 
+    pub fn submodule_for_path(&self, path: &RepoPath) -> Option<&Submodule> {
+        let ix = self
+            .submodules
+            .binary_search_by(|submodule| submodule.path.cmp(path))
+            .ok()?;
+        self.submodules.get(ix)
+    }
+
     pub fn abs_path_to_repo_path(&self, abs_path: &Path) -> Option<RepoPath> {
         Self::abs_path_to_repo_path_inner(&self.work_directory_abs_path, abs_path, self.path_style)
     }
@@ -3047,6 +3762,8 @@ impl Repository {
             askpass_delegates: Default::default(),
             paths_needing_status_update: Default::default(),
             latest_askpass_id: 0,
+            git_editor_delegates: Default::default(),
+            latest_git_editor_id: 0,
             job_sender: Repository::spawn_local_git_worker(
                 work_directory_abs_path,
                 dot_git_abs_path,
@@ -3080,6 +3797,8 @@ impl Repository {
             job_sender: Self::spawn_remote_git_worker(project_id, client, cx),
             askpass_delegates: Default::default(),
             latest_askpass_id: 0,
+            git_editor_delegates: Default::default(),
+            latest_git_editor_id: 0,
             active_jobs: Default::default(),
             job_id: 0,
         }
@@ -3480,39 +4199,421 @@ impl Repository {
                         Ok(())
                     }
                 }
-            },
-        )
+            },
+        )
+    }
+
+    pub fn reset(
+        &mut self,
+        commit: String,
+        reset_mode: ResetMode,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(None, move |git_repo, _| async move {
+            match git_repo {
+                RepositoryState::Local {
+                    backend,
+                    environment,
+                    ..
+                } => backend.reset(commit, reset_mode, environment).await,
+                RepositoryState::Remote { project_id, client } => {
+                    client
+                        .request(proto::GitReset {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            commit,
+                            mode: match reset_mode {
+                                ResetMode::Soft => git_reset::ResetMode::Soft.into(),
+                                ResetMode::Mixed => git_reset::ResetMode::Mixed.into(),
+                                ResetMode::Hard => git_reset::ResetMode::Hard.into(),
+                            },
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn cherry_pick(&mut self, commit: String, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git cherry-pick {commit}").into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.cherry_pick(commit, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitCherryPick {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commit,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn revert(&mut self, commit: String, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git revert {commit}").into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.revert(commit, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitRevert {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commit,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn bisect(
+        &mut self,
+        operation: BisectOperation,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<String>> {
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git {}", operation.name()).into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.bisect(operation, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        let (operation, commit, good) = match operation {
+                            BisectOperation::Start { bad, good } => (
+                                proto::git_bisect::Operation::Start,
+                                bad,
+                                good.iter().map(ToString::to_string).collect(),
+                            ),
+                            BisectOperation::Good(commit) => {
+                                (proto::git_bisect::Operation::Good, commit, Vec::new())
+                            }
+                            BisectOperation::Bad(commit) => {
+                                (proto::git_bisect::Operation::Bad, commit, Vec::new())
+                            }
+                            BisectOperation::Skip(commit) => {
+                                (proto::git_bisect::Operation::Skip, commit, Vec::new())
+                            }
+                            BisectOperation::Reset => {
+                                (proto::git_bisect::Operation::Reset, None, Vec::new())
+                            }
+                        };
+                        let response = client
+                            .request(proto::GitBisect {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                operation: operation.into(),
+                                commit: commit.map(|commit| commit.to_string()),
+                                good,
+                            })
+                            .await
+                            .context("sending bisect request")?;
+
+                        Ok(response.output)
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn bisect_log(&mut self) -> oneshot::Receiver<Result<String>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _| async move {
+            match git_repo {
+                RepositoryState::Local { backend, .. } => backend.bisect_log().await,
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GitBisectLog {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+
+                    Ok(response.log)
+                }
+            }
+        })
+    }
+
+    pub fn tags(&mut self) -> oneshot::Receiver<Result<Vec<Tag>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _| async move {
+            match repo {
+                RepositoryState::Local { backend, .. } => backend.tags().await,
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GitGetTags {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+
+                    Ok(response.tags.iter().map(proto_to_tag).collect())
+                }
+            }
+        })
+    }
+
+    /// Creates a tag pointing at `commit`, which is annotated if a message is given.
+    pub fn create_tag(
+        &mut self,
+        name: String,
+        commit: String,
+        message: Option<String>,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git tag {name}").into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.create_tag(name, commit, message, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitCreateTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name,
+                                commit,
+                                message,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn delete_tag(&mut self, name: String, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git tag -d {name}").into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.delete_tag(name, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitDeleteTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    /// Lists the worktrees of the repository, starting with the main one. This includes the
+    /// worktrees that aren't part of the project.
+    pub fn worktrees(&mut self) -> oneshot::Receiver<Result<Vec<GitWorktree>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _| async move {
+            match repo {
+                RepositoryState::Local { backend, .. } => backend.worktrees().await,
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GitGetWorktrees {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+
+                    Ok(response.worktrees.iter().map(proto_to_worktree).collect())
+                }
+            }
+        })
+    }
+
+    pub fn create_worktree(
+        &mut self,
+        path: PathBuf,
+        commit: Option<String>,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git worktree add {}", path.display()).into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.create_worktree(path, commit, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitCreateWorktree {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                path: path.to_string_lossy().into_owned(),
+                                commit,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn remove_worktree(
+        &mut self,
+        path: PathBuf,
+        force: bool,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git worktree remove {}", path.display()).into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.remove_worktree(path, force, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitRemoveWorktree {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                path: path.to_string_lossy().into_owned(),
+                                force,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    /// Resolves the commit that the working tree is compared to when showing the changes since
+    /// `base`. With `merge_base`, this is the best common ancestor of `base` and `HEAD`, so that
+    /// only the changes of the current branch are shown.
+    pub fn resolve_diff_base(
+        &mut self,
+        base: String,
+        merge_base: bool,
+    ) -> oneshot::Receiver<Result<Oid>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _| async move {
+            match git_repo {
+                RepositoryState::Local { backend, .. } => {
+                    if merge_base {
+                        backend
+                            .merge_base(base.clone(), "HEAD".into())
+                            .await?
+                            .with_context(|| format!("{base} has no history in common with HEAD"))
+                    } else {
+                        let sha = backend
+                            .revparse_batch(vec![base.clone()])
+                            .await?
+                            .into_iter()
+                            .next()
+                            .flatten()
+                            .with_context(|| format!("unknown revision {base}"))?;
+                        Ok(sha.parse()?)
+                    }
+                }
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GitResolveDiffBase {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            base,
+                            merge_base,
+                        })
+                        .await?;
+                    Ok(response.sha.parse()?)
+                }
+            }
+        })
     }
 
-    pub fn reset(
+    /// Lists the paths that changed between a commit and `HEAD`. Uncommitted changes are not
+    /// included, and can be found in the repository's status.
+    pub fn changed_paths_since(
         &mut self,
-        commit: String,
-        reset_mode: ResetMode,
-        _cx: &mut App,
-    ) -> oneshot::Receiver<Result<()>> {
+        commit: Oid,
+    ) -> oneshot::Receiver<Result<Vec<(RepoPath, StatusCode)>>> {
         let id = self.id;
-
         self.send_job(None, move |git_repo, _| async move {
             match git_repo {
-                RepositoryState::Local {
-                    backend,
-                    environment,
-                    ..
-                } => backend.reset(commit, reset_mode, environment).await,
+                RepositoryState::Local { backend, .. } => backend.changed_paths_since(commit).await,
                 RepositoryState::Remote { project_id, client } => {
-                    client
-                        .request(proto::GitReset {
+                    let response = client
+                        .request(proto::GitChangedPathsSince {
                             project_id: project_id.0,
                             repository_id: id.to_proto(),
-                            commit,
-                            mode: match reset_mode {
-                                ResetMode::Soft => git_reset::ResetMode::Soft.into(),
-                                ResetMode::Mixed => git_reset::ResetMode::Mixed.into(),
-                            },
+                            commit: commit.to_string(),
                         })
                         .await?;
-
-                    Ok(())
+                    response
+                        .changes
+                        .into_iter()
+                        .map(|change| {
+                            let status = match proto::GitStatus::from_i32(change.status) {
+                                Some(proto::GitStatus::Added) => StatusCode::Added,
+                                Some(proto::GitStatus::Deleted) => StatusCode::Deleted,
+                                Some(proto::GitStatus::Modified) => StatusCode::Modified,
+                                _ => bail!("Invalid status for a changed path: {}", change.status),
+                            };
+                            Ok((RepoPath::from_proto(&change.path)?, status))
+                        })
+                        .collect()
                 }
             }
         })
@@ -3544,6 +4645,70 @@ impl Repository {
         })
     }
 
+    /// Loads a page of the history of HEAD.
+    pub fn log(&mut self, options: LogOptions) -> oneshot::Receiver<Result<Vec<LogEntry>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local { backend, .. } => backend.log(options).await,
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GitLog {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            skip: options.skip as u64,
+                            limit: options.limit as u64,
+                            author: options.author,
+                            message: options.message,
+                            path: options.path.map(|path| path.to_proto()),
+                        })
+                        .await?;
+
+                    response.entries.iter().map(proto_to_log_entry).collect()
+                }
+            }
+        })
+    }
+
+    /// Loads the commits that changed a file, or the given rows of it in HEAD, newest first.
+    pub fn file_history(
+        &mut self,
+        path: RepoPath,
+        rows: Option<Range<u32>>,
+    ) -> oneshot::Receiver<Result<Vec<FileHistoryEntry>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local { backend, .. } => backend.file_history(path, rows).await,
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GitFileHistory {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            path: path.to_proto(),
+                            start_row: rows.as_ref().map(|rows| rows.start),
+                            end_row: rows.as_ref().map(|rows| rows.end),
+                        })
+                        .await?;
+
+                    response
+                        .entries
+                        .iter()
+                        .map(|entry| {
+                            Ok(FileHistoryEntry {
+                                commit: proto_to_log_entry(
+                                    entry.commit.as_ref().context("missing commit")?,
+                                )?,
+                                path: RepoPath::from_proto(&entry.path)?,
+                                old_path: RepoPath::from_proto(&entry.old_path)?,
+                            })
+                        })
+                        .collect()
+                }
+            }
+        })
+    }
+
     pub fn load_commit_diff(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDiff>> {
         let id = self.id;
         self.send_job(None, move |git_repo, cx| async move {
@@ -4091,6 +5256,121 @@ impl Repository {
         )
     }
 
+    pub fn push_tag(
+        &mut self,
+        name: SharedString,
+        remote: SharedString,
+        askpass: AskPassDelegate,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git push {} {}", remote, name).into()),
+            move |git_repo, cx| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => {
+                        backend
+                            .push_tag(
+                                name.to_string(),
+                                remote.to_string(),
+                                askpass,
+                                environment.clone(),
+                                cx,
+                            )
+                            .await
+                    }
+                    RepositoryState::Remote { project_id, client } => {
+                        askpass_delegates.lock().insert(askpass_id, askpass);
+                        let _defer = util::defer(|| {
+                            let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                            debug_assert!(askpass_delegate.is_some());
+                        });
+                        let response = client
+                            .request(proto::GitPushTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                askpass_id,
+                                name: name.to_string(),
+                                remote_name: remote.to_string(),
+                            })
+                            .await
+                            .context("sending push tag request")?;
+
+                        Ok(RemoteCommandOutput {
+                            stdout: response.stdout,
+                            stderr: response.stderr,
+                        })
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn run_submodule_operation(
+        &mut self,
+        operation: SubmoduleOperation,
+        askpass: AskPassDelegate,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
+        let id = self.id;
+        let job_name = match operation {
+            SubmoduleOperation::Init => "git submodule init",
+            SubmoduleOperation::Update => "git submodule update",
+            SubmoduleOperation::Sync => "git submodule sync",
+        };
+
+        self.send_job(Some(job_name.into()), move |git_repo, cx| async move {
+            match git_repo {
+                RepositoryState::Local {
+                    backend,
+                    environment,
+                    ..
+                } => {
+                    backend
+                        .run_submodule_operation(operation, askpass, environment.clone(), cx)
+                        .await
+                }
+                RepositoryState::Remote { project_id, client } => {
+                    askpass_delegates.lock().insert(askpass_id, askpass);
+                    let _defer = util::defer(|| {
+                        let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                        debug_assert!(askpass_delegate.is_some());
+                    });
+                    let operation = match operation {
+                        SubmoduleOperation::Init => proto::git_submodule_operation::Operation::Init,
+                        SubmoduleOperation::Update => {
+                            proto::git_submodule_operation::Operation::Update
+                        }
+                        SubmoduleOperation::Sync => proto::git_submodule_operation::Operation::Sync,
+                    };
+                    let response = client
+                        .request(proto::GitSubmoduleOperation {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            askpass_id,
+                            operation: operation.into(),
+                        })
+                        .await
+                        .context("sending submodule operation request")?;
+
+                    Ok(RemoteCommandOutput {
+                        stdout: response.stdout,
+                        stderr: response.stderr,
+                    })
+                }
+            }
+        })
+    }
+
     pub fn pull(
         &mut self,
         branch: SharedString,
@@ -4148,6 +5428,63 @@ impl Repository {
         )
     }
 
+    pub fn rebase(
+        &mut self,
+        operation: RebaseOperation,
+        editor: GitEditorDelegate,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let git_editor_delegates = self.git_editor_delegates.clone();
+        let editor_id = util::post_inc(&mut self.latest_git_editor_id);
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git {}", operation.name()).into()),
+            move |git_repo, cx| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.rebase(operation, editor, environment, cx).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        git_editor_delegates.lock().insert(editor_id, editor);
+                        let _defer = util::defer(|| {
+                            let editor = git_editor_delegates.lock().remove(&editor_id);
+                            debug_assert!(editor.is_some());
+                        });
+                        let (operation, onto) = match operation {
+                            RebaseOperation::Start { onto } => (
+                                proto::git_rebase::Operation::Start,
+                                onto.map(|onto| onto.to_string()),
+                            ),
+                            RebaseOperation::Continue => {
+                                (proto::git_rebase::Operation::Continue, None)
+                            }
+                            RebaseOperation::Skip => (proto::git_rebase::Operation::Skip, None),
+                            RebaseOperation::Abort => (proto::git_rebase::Operation::Abort, None),
+                        };
+                        let response = client
+                            .request(proto::GitRebase {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                editor_id,
+                                operation: operation.into(),
+                                onto,
+                            })
+                            .await
+                            .context("sending rebase request")?;
+
+                        Ok(RemoteCommandOutput {
+                            stdout: response.stdout,
+                            stderr: response.stderr,
+                        })
+                    }
+                }
+            },
+        )
+    }
+
     fn spawn_set_index_text_job(
         &mut self,
         path: RepoPath,
@@ -4463,6 +5800,16 @@ impl Repository {
 
         self.snapshot.merge.conflicted_paths = conflicted_paths;
         self.snapshot.merge.message = update.merge_message.map(SharedString::from);
+        self.snapshot.rebase = update.rebase_progress.map(|rebase| RebaseProgress {
+            step: rebase.step as usize,
+            total: rebase.total as usize,
+        });
+        self.snapshot.bisect = update.bisect_status.as_ref().map(proto_to_bisect_status);
+        self.snapshot.submodules = update
+            .submodules
+            .iter()
+            .filter_map(|submodule| proto_to_submodule(submodule).log_err())
+            .collect();
         self.snapshot.stash_entries = GitStash {
             entries: update
                 .stash_entries
@@ -4755,6 +6102,34 @@ impl Repository {
         cx.spawn(|_: &mut AsyncApp| async move { rx.await? })
     }
 
+    fn load_text_at_commit(
+        &mut self,
+        commit: Oid,
+        repo_path: RepoPath,
+        cx: &App,
+    ) -> Task<Result<Option<String>>> {
+        let id = self.id;
+        let rx = self.send_job(None, move |state, _| async move {
+            match state {
+                RepositoryState::Local { backend, .. } => {
+                    anyhow::Ok(backend.load_text_at_commit(commit, repo_path).await)
+                }
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GitLoadTextAtCommit {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            commit: commit.to_string(),
+                            path: repo_path.to_proto(),
+                        })
+                        .await?;
+                    Ok(response.text)
+                }
+            }
+        });
+        cx.spawn(|_: &mut AsyncApp| async move { rx.await? })
+    }
+
     fn paths_changed(
         &mut self,
         paths: Vec<RepoPath>,
@@ -5049,6 +6424,94 @@ fn proto_to_branch(proto: &proto::Branch) -> git::repository::Branch {
     }
 }
 
+fn tag_to_proto(tag: &Tag) -> proto::GitTag {
+    proto::GitTag {
+        name: tag.name.to_string(),
+        sha: tag.sha.to_string(),
+        message: tag.message.as_ref().map(ToString::to_string),
+        timestamp: tag.timestamp,
+    }
+}
+
+fn proto_to_tag(proto: &proto::GitTag) -> Tag {
+    Tag {
+        name: proto.name.clone().into(),
+        sha: proto.sha.clone().into(),
+        message: proto.message.clone().map(Into::into),
+        timestamp: proto.timestamp,
+    }
+}
+
+fn worktree_to_proto(worktree: &GitWorktree) -> proto::GitWorktree {
+    proto::GitWorktree {
+        path: worktree.path.to_string_lossy().into_owned(),
+        sha: worktree.sha.as_ref().map(ToString::to_string),
+        branch: worktree.branch.as_ref().map(ToString::to_string),
+        is_main: worktree.is_main,
+        is_locked: worktree.is_locked,
+        is_prunable: worktree.is_prunable,
+    }
+}
+
+fn proto_to_worktree(proto: &proto::GitWorktree) -> GitWorktree {
+    GitWorktree {
+        path: PathBuf::from(&proto.path),
+        sha: proto.sha.clone().map(Into::into),
+        branch: proto.branch.clone().map(Into::into),
+        is_main: proto.is_main,
+        is_locked: proto.is_locked,
+        is_prunable: proto.is_prunable,
+    }
+}
+
+fn submodule_to_proto(submodule: &Submodule) -> proto::Submodule {
+    proto::Submodule {
+        path: submodule.path.to_proto(),
+        name: submodule.name.to_string(),
+        url: submodule.url.as_ref().map(ToString::to_string),
+        recorded_sha: submodule.recorded_sha.as_ref().map(ToString::to_string),
+        checked_out_sha: submodule.checked_out_sha.as_ref().map(ToString::to_string),
+        is_dirty: submodule.is_dirty,
+    }
+}
+
+fn proto_to_submodule(proto: &proto::Submodule) -> Result<Submodule> {
+    Ok(Submodule {
+        path: RepoPath::from_proto(&proto.path)?,
+        name: proto.name.clone().into(),
+        url: proto.url.clone().map(Into::into),
+        recorded_sha: proto.recorded_sha.clone().map(Into::into),
+        checked_out_sha: proto.checked_out_sha.clone().map(Into::into),
+        is_dirty: proto.is_dirty,
+    })
+}
+
+fn bisect_status_to_proto(status: &BisectStatus) -> proto::BisectStatus {
+    proto::BisectStatus {
+        bad: status.bad.as_ref().map(ToString::to_string),
+        good: status.good.iter().map(ToString::to_string).collect(),
+        skipped: status.skipped.iter().map(ToString::to_string).collect(),
+        progress: status.progress.map(|progress| proto::BisectProgress {
+            candidates: progress.candidates as u64,
+            revisions_left: progress.revisions_left as u64,
+            steps_left: progress.steps_left as u64,
+        }),
+    }
+}
+
+fn proto_to_bisect_status(proto: &proto::BisectStatus) -> BisectStatus {
+    BisectStatus {
+        bad: proto.bad.clone().map(Into::into),
+        good: proto.good.iter().cloned().map(Into::into).collect(),
+        skipped: proto.skipped.iter().cloned().map(Into::into).collect(),
+        progress: proto.progress.as_ref().map(|progress| BisectProgress {
+            candidates: progress.candidates as usize,
+            revisions_left: progress.revisions_left as usize,
+            steps_left: progress.steps_left as usize,
+        }),
+    }
+}
+
 fn commit_details_to_proto(commit: &CommitDetails) -> proto::GitCommitDetails {
     proto::GitCommitDetails {
         sha: commit.sha.to_string(),
@@ -5069,6 +6532,34 @@ fn proto_to_commit_details(proto: &proto::GitCommitDetails) -> CommitDetails {
     }
 }
 
+fn log_entry_to_proto(entry: &LogEntry) -> proto::GitLogEntry {
+    proto::GitLogEntry {
+        sha: entry.sha.to_string(),
+        parents: entry.parents.iter().map(ToString::to_string).collect(),
+        refs: entry.refs.iter().map(ToString::to_string).collect(),
+        subject: entry.subject.to_string(),
+        author_name: entry.author_name.to_string(),
+        author_email: entry.author_email.to_string(),
+        commit_timestamp: entry.commit_timestamp,
+    }
+}
+
+fn proto_to_log_entry(proto: &proto::GitLogEntry) -> Result<LogEntry> {
+    Ok(LogEntry {
+        sha: proto.sha.parse()?,
+        parents: proto
+            .parents
+            .iter()
+            .map(|parent| parent.parse())
+            .collect::<Result<_>>()?,
+        refs: proto.refs.iter().cloned().map(Into::into).collect(),
+        subject: proto.subject.clone().into(),
+        author_name: proto.author_name.clone().into(),
+        author_email: proto.author_email.clone().into(),
+        commit_timestamp: proto.commit_timestamp,
+    })
+}
+
 async fn compute_snapshot(
     id: RepositoryId,
     work_directory_abs_path: Arc<Path>,
@@ -5093,8 +6584,39 @@ async fn compute_snapshot(
     let (merge_details, merge_heads_changed) =
         MergeDetails::load(&backend, &statuses_by_path, &prev_snapshot).await?;
     log::debug!("new merge details (changed={merge_heads_changed:?}): {merge_details:?}");
+    let rebase = backend.rebase_progress().await;
+    let submodules: Arc<[Submodule]> = backend
+        .submodules()
+        .await
+        .log_err()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|mut submodule| {
+            // Git's status looks inside submodules, so a submodule with changes in its working
+            // tree but no new commit checked out is one with uncommitted changes.
+            if !submodule.is_out_of_date() {
+                submodule.is_dirty |= statuses_by_path
+                    .get(&PathKey(submodule.path.0.clone()), ())
+                    .is_some_and(|entry| {
+                        matches!(
+                            entry.status,
+                            FileStatus::Tracked(TrackedStatus {
+                                worktree_status: StatusCode::Modified,
+                                ..
+                            })
+                        )
+                    });
+            }
+            submodule
+        })
+        .collect();
+
+    let bisect = backend.bisect_status().await.log_err().flatten();
 
     if merge_heads_changed
+        || rebase != prev_snapshot.rebase
+        || bisect != prev_snapshot.bisect
+        || submodules != prev_snapshot.submodules
         || branch != prev_snapshot.branch
         || statuses_by_path != prev_snapshot.statuses_by_path
     {
@@ -5132,6 +6654,9 @@ async fn compute_snapshot(
         remote_origin_url,
         remote_upstream_url,
         stash_entries,
+        rebase,
+        submodules,
+        bisect,
     };
 
     Ok((snapshot, events))
@@ -1,11 +1,12 @@
 mod signature_help;
 
 use crate::{
-    CodeAction, CompletionSource, CoreCompletion, CoreCompletionResponse, DocumentColor,
-    DocumentHighlight, DocumentSymbol, Hover, HoverBlock, HoverBlockKind, InlayHint,
-    InlayHintLabel, InlayHintLabelPart, InlayHintLabelPartTooltip, InlayHintTooltip, Location,
-    LocationLink, LspAction, LspPullDiagnostics, MarkupContent, PrepareRenameResponse,
-    ProjectTransaction, PulledDiagnostics, ResolveState,
+    CallHierarchyCall, CallHierarchyItem, CodeAction, CompletionSource, CoreCompletion,
+    CoreCompletionResponse, DocumentColor, DocumentHighlight, DocumentLink, DocumentSymbol, Hover,
+    HoverBlock, HoverBlockKind, InlayHint, InlayHintLabel, InlayHintLabelPart,
+    InlayHintLabelPartTooltip, InlayHintTooltip, Location, LocationLink, LspAction,
+    LspPullDiagnostics, MarkupContent, PrepareRenameResponse, ProjectTransaction,
+    PulledDiagnostics, ResolveState, TypeHierarchyItem,
     lsp_store::{LocalLspStore, LspStore},
 };
 use anyhow::{Context as _, Result};
@@ -17,7 +18,7 @@ use futures::future;
 use gpui::{App, AsyncApp, Entity, Task};
 use language::{
     Anchor, Bias, Buffer, BufferSnapshot, CachedLspAdapter, CharKind, CharScopeContext,
-    OffsetRangeExt, PointUtf16, ToOffset, ToPointUtf16, Transaction, Unclipped,
+    OffsetRangeExt, Point, PointUtf16, ToOffset, ToPointUtf16, Transaction, Unclipped,
     language_settings::{InlayHintKind, LanguageSettings, language_settings},
     point_from_lsp, point_to_lsp,
     proto::{deserialize_anchor, deserialize_version, serialize_anchor, serialize_version},
@@ -204,6 +205,36 @@ pub(crate) struct GetDocumentHighlights {
 #[derive(Debug, Copy, Clone)]
 pub(crate) struct GetDocumentSymbols;
 
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PrepareCallHierarchy {
+    pub position: PointUtf16,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct GetIncomingCalls {
+    pub item: lsp::CallHierarchyItem,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct GetOutgoingCalls {
+    pub item: lsp::CallHierarchyItem,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PrepareTypeHierarchy {
+    pub position: PointUtf16,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct GetSupertypes {
+    pub item: lsp::TypeHierarchyItem,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct GetSubtypes {
+    pub item: lsp::TypeHierarchyItem,
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct GetSignatureHelp {
     pub position: PointUtf16,
@@ -245,6 +276,35 @@ pub(crate) struct GetCodeLens;
 #[derive(Debug, Copy, Clone)]
 pub(crate) struct GetDocumentColor;
 
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct GetFoldingRanges;
+
+#[derive(Debug, Clone)]
+pub(crate) struct GetSelectionRanges {
+    pub positions: Vec<PointUtf16>,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct GetDocumentLinks;
+
+#[derive(Debug, Clone)]
+pub(crate) struct ResolveDocumentLink {
+    pub link: lsp::DocumentLink,
+}
+
+impl GetSelectionRanges {
+    pub(crate) fn check_server_capabilities(capabilities: &ServerCapabilities) -> bool {
+        capabilities
+            .selection_range_provider
+            .as_ref()
+            .is_some_and(|capability| match capability {
+                lsp::SelectionRangeProviderCapability::Simple(supported) => *supported,
+                lsp::SelectionRangeProviderCapability::Options(..) => true,
+                lsp::SelectionRangeProviderCapability::RegistrationOptions(..) => true,
+            })
+    }
+}
+
 impl GetCodeLens {
     pub(crate) fn can_resolve_lens(capabilities: &ServerCapabilities) -> bool {
         capabilities
@@ -1798,6 +1858,1391 @@ impl LspCommand for GetDocumentSymbols {
     }
 }
 
+fn supports_call_hierarchy(capabilities: &AdapterServerCapabilities) -> bool {
+    capabilities
+        .server_capabilities
+        .call_hierarchy_provider
+        .as_ref()
+        .is_some_and(|capability| match capability {
+            lsp::CallHierarchyServerCapability::Simple(enabled) => *enabled,
+            lsp::CallHierarchyServerCapability::Options(_) => true,
+        })
+}
+
+fn anchor_range_from_lsp(buffer: &Buffer, range: lsp::Range) -> Range<Anchor> {
+    let start = buffer.clip_point_utf16(point_from_lsp(range.start), Bias::Left);
+    let end = buffer.clip_point_utf16(point_from_lsp(range.end), Bias::Left);
+    buffer.anchor_after(start)..buffer.anchor_before(end)
+}
+
+async fn call_hierarchy_item_from_lsp(
+    lsp_item: lsp::CallHierarchyItem,
+    lsp_store: &Entity<LspStore>,
+    server_id: LanguageServerId,
+    cx: &mut AsyncApp,
+) -> Result<CallHierarchyItem> {
+    let buffer = lsp_store
+        .update(cx, |lsp_store, cx| {
+            lsp_store.open_local_buffer_via_lsp(lsp_item.uri.clone(), server_id, cx)
+        })?
+        .await?;
+    buffer
+        .clone()
+        .read_with(cx, |snapshot, _| CallHierarchyItem {
+            name: lsp_item.name.clone(),
+            kind: lsp_item.kind,
+            detail: lsp_item.detail.clone(),
+            location: Location {
+                buffer,
+                range: anchor_range_from_lsp(snapshot, lsp_item.range),
+            },
+            selection_range: anchor_range_from_lsp(snapshot, lsp_item.selection_range),
+            server_id,
+            lsp_item,
+        })
+}
+
+async fn call_hierarchy_call_from_lsp(
+    lsp_item: lsp::CallHierarchyItem,
+    call_sites_buffer: Option<&Entity<Buffer>>,
+    from_ranges: Vec<lsp::Range>,
+    lsp_store: &Entity<LspStore>,
+    server_id: LanguageServerId,
+    cx: &mut AsyncApp,
+) -> Result<CallHierarchyCall> {
+    let item = call_hierarchy_item_from_lsp(lsp_item, lsp_store, server_id, cx).await?;
+    let call_sites_buffer = call_sites_buffer.unwrap_or(&item.location.buffer).clone();
+    let call_sites = call_sites_buffer.read_with(cx, |snapshot, _| {
+        from_ranges
+            .into_iter()
+            .map(|range| Location {
+                buffer: call_sites_buffer.clone(),
+                range: anchor_range_from_lsp(snapshot, range),
+            })
+            .collect()
+    })?;
+    Ok(CallHierarchyCall { item, call_sites })
+}
+
+fn location_to_proto(
+    location: Location,
+    lsp_store: &mut LspStore,
+    peer_id: PeerId,
+    cx: &mut App,
+) -> proto::Location {
+    lsp_store
+        .buffer_store()
+        .update(cx, |buffer_store, cx| {
+            buffer_store.create_buffer_for_peer(&location.buffer, peer_id, cx)
+        })
+        .detach_and_log_err(cx);
+    proto::Location {
+        start: Some(serialize_anchor(&location.range.start)),
+        end: Some(serialize_anchor(&location.range.end)),
+        buffer_id: location.buffer.read(cx).remote_id().into(),
+    }
+}
+
+async fn location_from_proto(
+    location: proto::Location,
+    lsp_store: &Entity<LspStore>,
+    cx: &mut AsyncApp,
+) -> Result<Location> {
+    let buffer_id = BufferId::new(location.buffer_id)?;
+    let buffer = lsp_store
+        .update(cx, |lsp_store, cx| {
+            lsp_store.wait_for_remote_buffer(buffer_id, cx)
+        })?
+        .await?;
+    let start = location
+        .start
+        .and_then(deserialize_anchor)
+        .context("missing location start")?;
+    let end = location
+        .end
+        .and_then(deserialize_anchor)
+        .context("missing location end")?;
+    buffer
+        .update(cx, |buffer, _| buffer.wait_for_anchors([start, end]))?
+        .await?;
+    Ok(Location {
+        buffer,
+        range: start..end,
+    })
+}
+
+fn call_hierarchy_item_to_proto(
+    item: CallHierarchyItem,
+    lsp_store: &mut LspStore,
+    peer_id: PeerId,
+    cx: &mut App,
+) -> proto::CallHierarchyItem {
+    proto::CallHierarchyItem {
+        name: item.name,
+        kind: unsafe { mem::transmute::<lsp::SymbolKind, i32>(item.kind) },
+        detail: item.detail,
+        location: Some(location_to_proto(item.location, lsp_store, peer_id, cx)),
+        selection_start: Some(serialize_anchor(&item.selection_range.start)),
+        selection_end: Some(serialize_anchor(&item.selection_range.end)),
+        server_id: item.server_id.to_proto(),
+        lsp_item: serde_json::to_vec(&item.lsp_item).unwrap(),
+    }
+}
+
+async fn call_hierarchy_item_from_proto(
+    item: proto::CallHierarchyItem,
+    lsp_store: &Entity<LspStore>,
+    cx: &mut AsyncApp,
+) -> Result<CallHierarchyItem> {
+    let location = location_from_proto(
+        item.location.context("missing item location")?,
+        lsp_store,
+        cx,
+    )
+    .await?;
+    let selection_start = item
+        .selection_start
+        .and_then(deserialize_anchor)
+        .context("missing selection start")?;
+    let selection_end = item
+        .selection_end
+        .and_then(deserialize_anchor)
+        .context("missing selection end")?;
+    Ok(CallHierarchyItem {
+        name: item.name,
+        kind: unsafe { mem::transmute::<i32, lsp::SymbolKind>(item.kind) },
+        detail: item.detail,
+        location,
+        selection_range: selection_start..selection_end,
+        server_id: LanguageServerId::from_proto(item.server_id),
+        lsp_item: serde_json::from_slice(&item.lsp_item)?,
+    })
+}
+
+fn call_hierarchy_calls_to_proto(
+    calls: Vec<CallHierarchyCall>,
+    lsp_store: &mut LspStore,
+    peer_id: PeerId,
+    cx: &mut App,
+) -> Vec<proto::CallHierarchyCall> {
+    calls
+        .into_iter()
+        .map(|call| proto::CallHierarchyCall {
+            item: Some(call_hierarchy_item_to_proto(
+                call.item, lsp_store, peer_id, cx,
+            )),
+            call_sites: call
+                .call_sites
+                .into_iter()
+                .map(|location| location_to_proto(location, lsp_store, peer_id, cx))
+                .collect(),
+        })
+        .collect()
+}
+
+async fn call_hierarchy_calls_from_proto(
+    calls: Vec<proto::CallHierarchyCall>,
+    lsp_store: Entity<LspStore>,
+    mut cx: AsyncApp,
+) -> Result<Vec<CallHierarchyCall>> {
+    let mut result = Vec::with_capacity(calls.len());
+    for call in calls {
+        let item = call_hierarchy_item_from_proto(
+            call.item.context("missing call item")?,
+            &lsp_store,
+            &mut cx,
+        )
+        .await?;
+        let mut call_sites = Vec::with_capacity(call.call_sites.len());
+        for location in call.call_sites {
+            call_sites.push(location_from_proto(location, &lsp_store, &mut cx).await?);
+        }
+        result.push(CallHierarchyCall { item, call_sites });
+    }
+    Ok(result)
+}
+
+#[async_trait(?Send)]
+impl LspCommand for PrepareCallHierarchy {
+    type Response = Vec<CallHierarchyItem>;
+    type LspRequest = lsp::request::CallHierarchyPrepare;
+    type ProtoRequest = proto::PrepareCallHierarchy;
+
+    fn display_name(&self) -> &str {
+        "Prepare call hierarchy"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        supports_call_hierarchy(&capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::CallHierarchyPrepareParams> {
+        Ok(lsp::CallHierarchyPrepareParams {
+            text_document_position_params: make_lsp_text_document_position(path, self.position)?,
+            work_done_progress_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::CallHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        server_id: LanguageServerId,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<CallHierarchyItem>> {
+        let mut items = Vec::new();
+        for lsp_item in message.unwrap_or_default() {
+            items.push(
+                call_hierarchy_item_from_lsp(lsp_item, &lsp_store, server_id, &mut cx).await?,
+            );
+        }
+        Ok(items)
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::PrepareCallHierarchy {
+        proto::PrepareCallHierarchy {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            position: Some(language::proto::serialize_anchor(
+                &buffer.anchor_before(self.position),
+            )),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::PrepareCallHierarchy,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        let position = message
+            .position
+            .and_then(deserialize_anchor)
+            .context("invalid position")?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self {
+            position: buffer.read_with(&cx, |buffer, _| position.to_point_utf16(buffer))?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<CallHierarchyItem>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::PrepareCallHierarchyResponse {
+        let items = response
+            .into_iter()
+            .map(|item| call_hierarchy_item_to_proto(item, lsp_store, peer_id, cx))
+            .collect();
+        proto::PrepareCallHierarchyResponse { items }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::PrepareCallHierarchyResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<CallHierarchyItem>> {
+        let mut items = Vec::with_capacity(message.items.len());
+        for item in message.items {
+            items.push(call_hierarchy_item_from_proto(item, &lsp_store, &mut cx).await?);
+        }
+        Ok(items)
+    }
+
+    fn buffer_id_from_proto(message: &proto::PrepareCallHierarchy) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetIncomingCalls {
+    type Response = Vec<CallHierarchyCall>;
+    type LspRequest = lsp::request::CallHierarchyIncomingCalls;
+    type ProtoRequest = proto::GetIncomingCalls;
+
+    fn display_name(&self) -> &str {
+        "Get incoming calls"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        supports_call_hierarchy(&capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::CallHierarchyIncomingCallsParams> {
+        Ok(lsp::CallHierarchyIncomingCallsParams {
+            item: self.item.clone(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::CallHierarchyIncomingCall>>,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        server_id: LanguageServerId,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<CallHierarchyCall>> {
+        let mut calls = Vec::new();
+        for incoming_call in message.unwrap_or_default() {
+            calls.push(
+                call_hierarchy_call_from_lsp(
+                    incoming_call.from,
+                    None,
+                    incoming_call.from_ranges,
+                    &lsp_store,
+                    server_id,
+                    &mut cx,
+                )
+                .await?,
+            );
+        }
+        Ok(calls)
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetIncomingCalls {
+        proto::GetIncomingCalls {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            lsp_item: serde_json::to_vec(&self.item).unwrap(),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetIncomingCalls,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            item: serde_json::from_slice(&message.lsp_item)?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<CallHierarchyCall>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::GetIncomingCallsResponse {
+        proto::GetIncomingCallsResponse {
+            calls: call_hierarchy_calls_to_proto(response, lsp_store, peer_id, cx),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetIncomingCallsResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<CallHierarchyCall>> {
+        call_hierarchy_calls_from_proto(message.calls, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetIncomingCalls) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetOutgoingCalls {
+    type Response = Vec<CallHierarchyCall>;
+    type LspRequest = lsp::request::CallHierarchyOutgoingCalls;
+    type ProtoRequest = proto::GetOutgoingCalls;
+
+    fn display_name(&self) -> &str {
+        "Get outgoing calls"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        supports_call_hierarchy(&capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::CallHierarchyOutgoingCallsParams> {
+        Ok(lsp::CallHierarchyOutgoingCallsParams {
+            item: self.item.clone(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::CallHierarchyOutgoingCall>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<CallHierarchyCall>> {
+        let mut calls = Vec::new();
+        for outgoing_call in message.unwrap_or_default() {
+            // The call sites of outgoing calls are located in the caller, i.e. the queried item.
+            calls.push(
+                call_hierarchy_call_from_lsp(
+                    outgoing_call.to,
+                    Some(&buffer),
+                    outgoing_call.from_ranges,
+                    &lsp_store,
+                    server_id,
+                    &mut cx,
+                )
+                .await?,
+            );
+        }
+        Ok(calls)
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetOutgoingCalls {
+        proto::GetOutgoingCalls {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            lsp_item: serde_json::to_vec(&self.item).unwrap(),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetOutgoingCalls,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            item: serde_json::from_slice(&message.lsp_item)?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<CallHierarchyCall>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::GetOutgoingCallsResponse {
+        proto::GetOutgoingCallsResponse {
+            calls: call_hierarchy_calls_to_proto(response, lsp_store, peer_id, cx),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetOutgoingCallsResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<CallHierarchyCall>> {
+        call_hierarchy_calls_from_proto(message.calls, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetOutgoingCalls) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+async fn type_hierarchy_item_from_lsp(
+    lsp_item: lsp::TypeHierarchyItem,
+    lsp_store: &Entity<LspStore>,
+    server_id: LanguageServerId,
+    cx: &mut AsyncApp,
+) -> Result<TypeHierarchyItem> {
+    let buffer = lsp_store
+        .update(cx, |lsp_store, cx| {
+            lsp_store.open_local_buffer_via_lsp(lsp_item.uri.clone(), server_id, cx)
+        })?
+        .await?;
+    buffer
+        .clone()
+        .read_with(cx, |snapshot, _| TypeHierarchyItem {
+            name: lsp_item.name.clone(),
+            kind: lsp_item.kind,
+            detail: lsp_item.detail.clone(),
+            location: Location {
+                buffer,
+                range: anchor_range_from_lsp(snapshot, lsp_item.range),
+            },
+            selection_range: anchor_range_from_lsp(snapshot, lsp_item.selection_range),
+            server_id,
+            lsp_item,
+        })
+}
+
+async fn type_hierarchy_items_from_lsp(
+    lsp_items: Option<Vec<lsp::TypeHierarchyItem>>,
+    lsp_store: Entity<LspStore>,
+    server_id: LanguageServerId,
+    mut cx: AsyncApp,
+) -> Result<Vec<TypeHierarchyItem>> {
+    let mut items = Vec::new();
+    for lsp_item in lsp_items.unwrap_or_default() {
+        items.push(type_hierarchy_item_from_lsp(lsp_item, &lsp_store, server_id, &mut cx).await?);
+    }
+    Ok(items)
+}
+
+fn type_hierarchy_items_to_proto(
+    items: Vec<TypeHierarchyItem>,
+    lsp_store: &mut LspStore,
+    peer_id: PeerId,
+    cx: &mut App,
+) -> Vec<proto::TypeHierarchyItem> {
+    items
+        .into_iter()
+        .map(|item| proto::TypeHierarchyItem {
+            name: item.name,
+            kind: unsafe { mem::transmute::<lsp::SymbolKind, i32>(item.kind) },
+            detail: item.detail,
+            location: Some(location_to_proto(item.location, lsp_store, peer_id, cx)),
+            selection_start: Some(serialize_anchor(&item.selection_range.start)),
+            selection_end: Some(serialize_anchor(&item.selection_range.end)),
+            server_id: item.server_id.to_proto(),
+            lsp_item: serde_json::to_vec(&item.lsp_item).unwrap(),
+        })
+        .collect()
+}
+
+async fn type_hierarchy_items_from_proto(
+    items: Vec<proto::TypeHierarchyItem>,
+    lsp_store: Entity<LspStore>,
+    mut cx: AsyncApp,
+) -> Result<Vec<TypeHierarchyItem>> {
+    let mut result = Vec::with_capacity(items.len());
+    for item in items {
+        let location = location_from_proto(
+            item.location.context("missing item location")?,
+            &lsp_store,
+            &mut cx,
+        )
+        .await?;
+        let selection_start = item
+            .selection_start
+            .and_then(deserialize_anchor)
+            .context("missing selection start")?;
+        let selection_end = item
+            .selection_end
+            .and_then(deserialize_anchor)
+            .context("missing selection end")?;
+        result.push(TypeHierarchyItem {
+            name: item.name,
+            kind: unsafe { mem::transmute::<i32, lsp::SymbolKind>(item.kind) },
+            detail: item.detail,
+            location,
+            selection_range: selection_start..selection_end,
+            server_id: LanguageServerId::from_proto(item.server_id),
+            lsp_item: serde_json::from_slice(&item.lsp_item)?,
+        });
+    }
+    Ok(result)
+}
+
+#[async_trait(?Send)]
+impl LspCommand for PrepareTypeHierarchy {
+    type Response = Vec<TypeHierarchyItem>;
+    type LspRequest = lsp::request::TypeHierarchyPrepare;
+    type ProtoRequest = proto::PrepareTypeHierarchy;
+
+    fn display_name(&self) -> &str {
+        "Prepare type hierarchy"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        capabilities.type_hierarchy_provider
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::TypeHierarchyPrepareParams> {
+        Ok(lsp::TypeHierarchyPrepareParams {
+            text_document_position_params: make_lsp_text_document_position(path, self.position)?,
+            work_done_progress_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::TypeHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<TypeHierarchyItem>> {
+        type_hierarchy_items_from_lsp(message, lsp_store, server_id, cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::PrepareTypeHierarchy {
+        proto::PrepareTypeHierarchy {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            position: Some(language::proto::serialize_anchor(
+                &buffer.anchor_before(self.position),
+            )),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::PrepareTypeHierarchy,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        let position = message
+            .position
+            .and_then(deserialize_anchor)
+            .context("invalid position")?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self {
+            position: buffer.read_with(&cx, |buffer, _| position.to_point_utf16(buffer))?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<TypeHierarchyItem>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::PrepareTypeHierarchyResponse {
+        proto::PrepareTypeHierarchyResponse {
+            items: type_hierarchy_items_to_proto(response, lsp_store, peer_id, cx),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::PrepareTypeHierarchyResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<TypeHierarchyItem>> {
+        type_hierarchy_items_from_proto(message.items, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::PrepareTypeHierarchy) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSupertypes {
+    type Response = Vec<TypeHierarchyItem>;
+    type LspRequest = lsp::request::TypeHierarchySupertypes;
+    type ProtoRequest = proto::GetSupertypes;
+
+    fn display_name(&self) -> &str {
+        "Get supertypes"
+    }
+
+    fn check_capabilities(&self, _: AdapterServerCapabilities) -> bool {
+        true
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::TypeHierarchySupertypesParams> {
+        Ok(lsp::TypeHierarchySupertypesParams {
+            item: self.item.clone(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::TypeHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<TypeHierarchyItem>> {
+        type_hierarchy_items_from_lsp(message, lsp_store, server_id, cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetSupertypes {
+        proto::GetSupertypes {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            lsp_item: serde_json::to_vec(&self.item).unwrap(),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetSupertypes,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            item: serde_json::from_slice(&message.lsp_item)?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<TypeHierarchyItem>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::GetSupertypesResponse {
+        proto::GetSupertypesResponse {
+            items: type_hierarchy_items_to_proto(response, lsp_store, peer_id, cx),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetSupertypesResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<TypeHierarchyItem>> {
+        type_hierarchy_items_from_proto(message.items, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetSupertypes) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSubtypes {
+    type Response = Vec<TypeHierarchyItem>;
+    type LspRequest = lsp::request::TypeHierarchySubtypes;
+    type ProtoRequest = proto::GetSubtypes;
+
+    fn display_name(&self) -> &str {
+        "Get subtypes"
+    }
+
+    fn check_capabilities(&self, _: AdapterServerCapabilities) -> bool {
+        true
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::TypeHierarchySubtypesParams> {
+        Ok(lsp::TypeHierarchySubtypesParams {
+            item: self.item.clone(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::TypeHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<TypeHierarchyItem>> {
+        type_hierarchy_items_from_lsp(message, lsp_store, server_id, cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetSubtypes {
+        proto::GetSubtypes {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            lsp_item: serde_json::to_vec(&self.item).unwrap(),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetSubtypes,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            item: serde_json::from_slice(&message.lsp_item)?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<TypeHierarchyItem>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::GetSubtypesResponse {
+        proto::GetSubtypesResponse {
+            items: type_hierarchy_items_to_proto(response, lsp_store, peer_id, cx),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetSubtypesResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<TypeHierarchyItem>> {
+        type_hierarchy_items_from_proto(message.items, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetSubtypes) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetFoldingRanges {
+    type Response = Vec<Range<Anchor>>;
+    type LspRequest = lsp::request::FoldingRangeRequest;
+    type ProtoRequest = proto::GetFoldingRanges;
+
+    fn display_name(&self) -> &str {
+        "Get folding ranges"
+    }
+
+    fn check_capabilities(&self, server_capabilities: AdapterServerCapabilities) -> bool {
+        server_capabilities
+            .server_capabilities
+            .folding_range_provider
+            .as_ref()
+            .is_some_and(|capability| match capability {
+                lsp::FoldingRangeProviderCapability::Simple(supported) => *supported,
+                lsp::FoldingRangeProviderCapability::FoldingProvider(..) => true,
+                lsp::FoldingRangeProviderCapability::Options(..) => true,
+            })
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::FoldingRangeParams> {
+        Ok(lsp::FoldingRangeParams {
+            text_document: make_text_document_identifier(path)?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::FoldingRange>>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        _: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<Range<Anchor>>> {
+        let snapshot = buffer.read_with(&cx, |buffer, _| buffer.snapshot())?;
+        let max_row = snapshot.max_point().row;
+        // Only whole lines are folded, so the folded text always spans from the end of
+        // the start line to the end of the end line, whatever characters the server reports.
+        let mut ranges = message
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|range| range.start_line < range.end_line && range.start_line <= max_row)
+            .map(|range| {
+                let end_line = range.end_line.min(max_row);
+                let start = Point::new(range.start_line, snapshot.line_len(range.start_line));
+                let end = Point::new(end_line, snapshot.line_len(end_line));
+                snapshot.anchor_after(start)..snapshot.anchor_before(end)
+            })
+            .collect::<Vec<_>>();
+        ranges.sort_by(|a, b| {
+            a.start
+                .cmp(&b.start, &snapshot)
+                .then_with(|| b.end.cmp(&a.end, &snapshot))
+        });
+        ranges.dedup();
+        Ok(ranges)
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetFoldingRanges {
+        proto::GetFoldingRanges {
+            project_id,
+            buffer_id: buffer.remote_id().to_proto(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetFoldingRanges,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self)
+    }
+
+    fn response_to_proto(
+        response: Vec<Range<Anchor>>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetFoldingRangesResponse {
+        proto::GetFoldingRangesResponse {
+            ranges: response
+                .into_iter()
+                .map(language::proto::serialize_anchor_range)
+                .collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetFoldingRangesResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<Range<Anchor>>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        message
+            .ranges
+            .into_iter()
+            .map(language::proto::deserialize_anchor_range)
+            .collect()
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetFoldingRanges) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSelectionRanges {
+    type Response = Vec<Vec<Range<Anchor>>>;
+    type LspRequest = lsp::request::SelectionRangeRequest;
+    type ProtoRequest = proto::GetSelectionRanges;
+
+    fn display_name(&self) -> &str {
+        "Get selection ranges"
+    }
+
+    fn check_capabilities(&self, server_capabilities: AdapterServerCapabilities) -> bool {
+        Self::check_server_capabilities(&server_capabilities.server_capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::SelectionRangeParams> {
+        Ok(lsp::SelectionRangeParams {
+            text_document: make_text_document_identifier(path)?,
+            positions: self.positions.iter().copied().map(point_to_lsp).collect(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::SelectionRange>>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        _: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<Vec<Range<Anchor>>>> {
+        buffer.read_with(&cx, |buffer, _| {
+            message
+                .unwrap_or_default()
+                .into_iter()
+                .map(|selection_range| {
+                    let mut ranges = Vec::new();
+                    let mut selection_range = Some(selection_range);
+                    while let Some(lsp::SelectionRange { range, parent }) = selection_range {
+                        let start =
+                            buffer.clip_point_utf16(point_from_lsp(range.start), Bias::Left);
+                        let end = buffer.clip_point_utf16(point_from_lsp(range.end), Bias::Left);
+                        ranges.push(buffer.anchor_before(start)..buffer.anchor_after(end));
+                        selection_range = parent.map(|parent| *parent);
+                    }
+                    ranges
+                })
+                .collect()
+        })
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetSelectionRanges {
+        proto::GetSelectionRanges {
+            project_id,
+            buffer_id: buffer.remote_id().to_proto(),
+            positions: self
+                .positions
+                .iter()
+                .map(|position| serialize_anchor(&buffer.anchor_before(*position)))
+                .collect(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetSelectionRanges,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        let positions = message
+            .positions
+            .into_iter()
+            .map(|position| deserialize_anchor(position).context("invalid position"))
+            .collect::<Result<Vec<_>>>()?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        buffer.read_with(&cx, |buffer, _| Self {
+            positions: positions
+                .iter()
+                .map(|position| position.to_point_utf16(buffer))
+                .collect(),
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<Vec<Range<Anchor>>>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetSelectionRangesResponse {
+        proto::GetSelectionRangesResponse {
+            selection_ranges: response
+                .into_iter()
+                .map(|ranges| proto::SelectionRanges {
+                    ranges: ranges
+                        .into_iter()
+                        .map(language::proto::serialize_anchor_range)
+                        .collect(),
+                })
+                .collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetSelectionRangesResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<Vec<Range<Anchor>>>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        message
+            .selection_ranges
+            .into_iter()
+            .map(|selection_ranges| {
+                selection_ranges
+                    .ranges
+                    .into_iter()
+                    .map(language::proto::deserialize_anchor_range)
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetSelectionRanges) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetDocumentLinks {
+    type Response = Vec<DocumentLink>;
+    type LspRequest = lsp::request::DocumentLinkRequest;
+    type ProtoRequest = proto::GetDocumentLinks;
+
+    fn display_name(&self) -> &str {
+        "Get document links"
+    }
+
+    fn check_capabilities(&self, server_capabilities: AdapterServerCapabilities) -> bool {
+        server_capabilities
+            .server_capabilities
+            .document_link_provider
+            .is_some()
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::DocumentLinkParams> {
+        Ok(lsp::DocumentLinkParams {
+            text_document: make_text_document_identifier(path)?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::DocumentLink>>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<DocumentLink>> {
+        buffer.read_with(&cx, |buffer, _| {
+            message
+                .unwrap_or_default()
+                .into_iter()
+                .map(|link| {
+                    let start =
+                        buffer.clip_point_utf16(point_from_lsp(link.range.start), Bias::Left);
+                    let end = buffer.clip_point_utf16(point_from_lsp(link.range.end), Bias::Left);
+                    DocumentLink {
+                        range: buffer.anchor_after(start)..buffer.anchor_before(end),
+                        target: link.target.clone(),
+                        tooltip: link.tooltip.clone(),
+                        server_id,
+                        lsp_link: link,
+                    }
+                })
+                .collect()
+        })
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetDocumentLinks {
+        proto::GetDocumentLinks {
+            project_id,
+            buffer_id: buffer.remote_id().to_proto(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetDocumentLinks,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self)
+    }
+
+    fn response_to_proto(
+        response: Vec<DocumentLink>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetDocumentLinksResponse {
+        proto::GetDocumentLinksResponse {
+            links: response
+                .into_iter()
+                .map(|link| proto::DocumentLink {
+                    start: Some(serialize_anchor(&link.range.start)),
+                    end: Some(serialize_anchor(&link.range.end)),
+                    target: link.target.map(|target| target.to_string()),
+                    tooltip: link.tooltip,
+                    server_id: link.server_id.to_proto(),
+                    lsp_link: serde_json::to_vec(&link.lsp_link).unwrap(),
+                })
+                .collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetDocumentLinksResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<DocumentLink>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        message
+            .links
+            .into_iter()
+            .map(|link| {
+                let start = link
+                    .start
+                    .and_then(deserialize_anchor)
+                    .context("missing link start")?;
+                let end = link
+                    .end
+                    .and_then(deserialize_anchor)
+                    .context("missing link end")?;
+                Ok(DocumentLink {
+                    range: start..end,
+                    target: link
+                        .target
+                        .map(|target| {
+                            lsp::Uri::from_str(&target).context("invalid document link target")
+                        })
+                        .transpose()?,
+                    tooltip: link.tooltip,
+                    server_id: LanguageServerId::from_proto(link.server_id),
+                    lsp_link: serde_json::from_slice(&link.lsp_link)?,
+                })
+            })
+            .collect()
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetDocumentLinks) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for ResolveDocumentLink {
+    type Response = Option<lsp::Uri>;
+    type LspRequest = lsp::request::DocumentLinkResolve;
+    type ProtoRequest = proto::ResolveDocumentLink;
+
+    fn display_name(&self) -> &str {
+        "Resolve document link"
+    }
+
+    fn check_capabilities(&self, server_capabilities: AdapterServerCapabilities) -> bool {
+        server_capabilities
+            .server_capabilities
+            .document_link_provider
+            .as_ref()
+            .and_then(|options| options.resolve_provider)
+            .unwrap_or(false)
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::DocumentLink> {
+        Ok(self.link.clone())
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: lsp::DocumentLink,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: LanguageServerId,
+        _: AsyncApp,
+    ) -> Result<Option<lsp::Uri>> {
+        Ok(message.target)
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::ResolveDocumentLink {
+        proto::ResolveDocumentLink {
+            project_id,
+            buffer_id: buffer.remote_id().to_proto(),
+            lsp_link: serde_json::to_vec(&self.link).unwrap(),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::ResolveDocumentLink,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            link: serde_json::from_slice(&message.lsp_link)?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Option<lsp::Uri>,
+        _: &mut LspStore,
+        _: PeerId,
+        _: &clock::Global,
+        _: &mut App,
+    ) -> proto::ResolveDocumentLinkResponse {
+        proto::ResolveDocumentLinkResponse {
+            target: response.map(|target| target.to_string()),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::ResolveDocumentLinkResponse,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Option<lsp::Uri>> {
+        message
+            .target
+            .map(|target| lsp::Uri::from_str(&target).context("invalid document link target"))
+            .transpose()
+    }
+
+    fn buffer_id_from_proto(message: &proto::ResolveDocumentLink) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
 #[async_trait(?Send)]
 impl LspCommand for GetSignatureHelp {
     type Response = Option<SignatureHelp>;
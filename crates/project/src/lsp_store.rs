@@ -17,8 +17,8 @@ pub mod rust_analyzer_ext;
 
 use crate::{
     CodeAction, ColorPresentation, Completion, CompletionDisplayOptions, CompletionResponse,
-    CompletionSource, CoreCompletion, DocumentColor, Hover, InlayHint, LocationLink, LspAction,
-    LspPullDiagnostics, ManifestProvidersStore, Project, ProjectItem, ProjectPath,
+    CompletionSource, CoreCompletion, DocumentColor, DocumentLink, Hover, InlayHint, LocationLink,
+    LspAction, LspPullDiagnostics, ManifestProvidersStore, Project, ProjectItem, ProjectPath,
     ProjectTransaction, PulledDiagnostics, ResolveState, Symbol,
     buffer_store::{BufferStore, BufferStoreEvent},
     environment::ProjectEnvironment,
@@ -838,6 +838,28 @@ impl LocalLspStore {
             })
             .detach();
 
+        language_server
+            .on_request::<lsp::request::SemanticTokensRefresh, _, _>({
+                let this = this.clone();
+                move |(), cx| {
+                    let this = this.clone();
+                    let mut cx = cx.clone();
+                    async move {
+                        this.update(&mut cx, |this, cx| {
+                            cx.emit(LspStoreEvent::RefreshSemanticTokens);
+                            this.downstream_client.as_ref().map(|(client, project_id)| {
+                                client.send(proto::RefreshSemanticTokens {
+                                    project_id: *project_id,
+                                })
+                            })
+                        })?
+                        .transpose()?;
+                        Ok(())
+                    }
+                }
+            })
+            .detach();
+
         language_server
             .on_request::<lsp::request::WorkspaceDiagnosticRefresh, _, _>({
                 let this = this.clone();
@@ -3492,7 +3514,9 @@ pub struct LspStore {
         HashMap<WorktreeId, HashMap<Arc<RelPath>, HashMap<LanguageServerId, DiagnosticSummary>>>,
     pub lsp_server_capabilities: HashMap<LanguageServerId, lsp::ServerCapabilities>,
     lsp_document_colors: HashMap<BufferId, DocumentColorData>,
+    lsp_semantic_tokens: HashMap<BufferId, SemanticTokensData>,
     lsp_code_lens: HashMap<BufferId, CodeLensData>,
+    lsp_document_links: HashMap<BufferId, DocumentLinksData>,
     running_lsp_requests: HashMap<TypeId, (Global, HashMap<LspRequestId, Task<()>>)>,
 }
 
@@ -3503,7 +3527,10 @@ pub struct DocumentColors {
 }
 
 type DocumentColorTask = Shared<Task<std::result::Result<DocumentColors, Arc<anyhow::Error>>>>;
+type SemanticTokensTask =
+    Shared<Task<std::result::Result<BufferSemanticTokens, Arc<anyhow::Error>>>>;
 type CodeLensTask = Shared<Task<std::result::Result<Option<Vec<CodeAction>>, Arc<anyhow::Error>>>>;
+type DocumentLinksTask = Shared<Task<std::result::Result<Arc<[DocumentLink]>, Arc<anyhow::Error>>>>;
 
 #[derive(Debug, Default)]
 struct DocumentColorData {
@@ -3513,6 +3540,74 @@ struct DocumentColorData {
     colors_update: Option<(Global, DocumentColorTask)>,
 }
 
+/// Semantic tokens of a buffer, as reported by every language server that provides them.
+#[derive(Debug, Default, Clone)]
+pub struct BufferSemanticTokens {
+    pub servers: HashMap<LanguageServerId, ServerSemanticTokens>,
+    pub cache_version: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ServerSemanticTokens {
+    pub legend: Arc<lsp::SemanticTokensLegend>,
+    pub tokens: Arc<[SemanticToken]>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemanticToken {
+    pub range: Range<Anchor>,
+    /// An index into the token types of the server's legend.
+    pub token_type: u32,
+    /// A bitset of indices into the token modifiers of the server's legend.
+    pub token_modifiers: u32,
+}
+
+impl ServerSemanticTokens {
+    pub fn token_type(&self, token: &SemanticToken) -> Option<&str> {
+        self.legend
+            .token_types
+            .get(token.token_type as usize)
+            .map(|token_type| token_type.as_str())
+    }
+
+    pub fn token_modifiers<'a>(
+        &'a self,
+        token: &'a SemanticToken,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        self.legend
+            .token_modifiers
+            .iter()
+            .enumerate()
+            .filter(|(ix, _)| *ix < 32 && token.token_modifiers & (1 << ix) != 0)
+            .map(|(_, modifier)| modifier.as_str())
+    }
+}
+
+#[derive(Debug, Default)]
+struct SemanticTokensData {
+    tokens_for_version: Global,
+    raw_tokens: HashMap<LanguageServerId, RawSemanticTokens>,
+    tokens: HashMap<LanguageServerId, ServerSemanticTokens>,
+    cache_version: usize,
+    tokens_update: Option<(Global, SemanticTokensTask)>,
+}
+
+/// Semantic tokens in the LSP wire format: relative 5-tuples of `u32`s.
+/// Kept around to apply `textDocument/semanticTokens/full/delta` edits on top of them.
+#[derive(Debug, Clone)]
+struct RawSemanticTokens {
+    legend: Arc<lsp::SemanticTokensLegend>,
+    result_id: Option<String>,
+    data: Vec<u32>,
+}
+
+#[derive(Debug, Default)]
+struct DocumentLinksData {
+    links_for_version: Global,
+    links: Option<Arc<[DocumentLink]>>,
+    update: Option<(Global, DocumentLinksTask)>,
+}
+
 #[derive(Debug, Default)]
 struct CodeLensData {
     lens_for_version: Global,
@@ -3544,6 +3639,7 @@ pub enum LspStoreEvent {
     Notification(String),
     RefreshInlayHints,
     RefreshCodeLens,
+    RefreshSemanticTokens,
     DiagnosticsUpdated {
         server_id: LanguageServerId,
         paths: Vec<ProjectPath>,
@@ -3621,6 +3717,8 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_open_buffer_for_symbol);
         client.add_entity_request_handler(Self::handle_refresh_inlay_hints);
         client.add_entity_request_handler(Self::handle_refresh_code_lens);
+        client.add_entity_request_handler(Self::handle_refresh_semantic_tokens);
+        client.add_entity_request_handler(Self::handle_get_semantic_tokens);
         client.add_entity_request_handler(Self::handle_on_type_formatting);
         client.add_entity_request_handler(Self::handle_apply_additional_edits_for_completion);
         client.add_entity_request_handler(Self::handle_register_buffer_with_language_servers);
@@ -3629,6 +3727,16 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_lsp_command::<GetCompletions>);
         client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentHighlights>);
         client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentSymbols>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<PrepareCallHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetIncomingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetOutgoingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<PrepareTypeHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSupertypes>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSubtypes>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetFoldingRanges>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSelectionRanges>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentLinks>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<ResolveDocumentLink>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PrepareRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PerformRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<LinkedEditingRange>);
@@ -3765,7 +3873,9 @@ impl LspStore {
             diagnostic_summaries: HashMap::default(),
             lsp_server_capabilities: HashMap::default(),
             lsp_document_colors: HashMap::default(),
+            lsp_semantic_tokens: HashMap::default(),
             lsp_code_lens: HashMap::default(),
+            lsp_document_links: HashMap::default(),
             running_lsp_requests: HashMap::default(),
             active_entry: None,
             _maintain_workspace_config,
@@ -3826,7 +3936,9 @@ impl LspStore {
             diagnostic_summaries: HashMap::default(),
             lsp_server_capabilities: HashMap::default(),
             lsp_document_colors: HashMap::default(),
+            lsp_semantic_tokens: HashMap::default(),
             lsp_code_lens: HashMap::default(),
+            lsp_document_links: HashMap::default(),
             running_lsp_requests: HashMap::default(),
             active_entry: None,
 
@@ -4025,7 +4137,9 @@ impl LspStore {
                     };
                     if refcount == 0 {
                         lsp_store.lsp_document_colors.remove(&buffer_id);
+                        lsp_store.lsp_semantic_tokens.remove(&buffer_id);
                         lsp_store.lsp_code_lens.remove(&buffer_id);
+                        lsp_store.lsp_document_links.remove(&buffer_id);
                         let local = lsp_store.as_local_mut().unwrap();
                         local.registered_buffers.remove(&buffer_id);
                         local.buffers_opened_in_servers.remove(&buffer_id);
@@ -4303,6 +4417,8 @@ impl LspStore {
                 request.check_capabilities(AdapterServerCapabilities {
                     server_capabilities: capabilities.clone(),
                     code_action_kinds: None,
+                    // Collaborators aren't sent this capability, so the host checks it instead.
+                    type_hierarchy_provider: true,
                 })
             },
             cx,
@@ -5612,6 +5728,62 @@ impl LspStore {
         new_task
     }
 
+    pub fn document_links(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> DocumentLinksTask {
+        let version_queried_for = buffer.read(cx).version();
+        let buffer_id = buffer.read(cx).remote_id();
+
+        let lsp_data = self.lsp_document_links.entry(buffer_id).or_default();
+        if let Some(links) = &lsp_data.links
+            && !version_queried_for.changed_since(&lsp_data.links_for_version)
+        {
+            return Task::ready(Ok(links.clone())).shared();
+        }
+        if let Some((updating_for, running_update)) = &lsp_data.update
+            && !version_queried_for.changed_since(updating_for)
+        {
+            return running_update.clone();
+        }
+
+        let request = self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetDocumentLinks,
+            cx,
+        );
+        let query_version_queried_for = version_queried_for.clone();
+        let new_task = cx
+            .spawn(async move |lsp_store, cx| {
+                let links = request
+                    .await
+                    .context("fetching document links")
+                    .map(Arc::<[DocumentLink]>::from)
+                    .map_err(Arc::new);
+                lsp_store
+                    .update(cx, |lsp_store, _| {
+                        let lsp_data = lsp_store.lsp_document_links.entry(buffer_id).or_default();
+                        lsp_data.update = None;
+                        if let Ok(links) = &links
+                            && !lsp_data
+                                .links_for_version
+                                .changed_since(&query_version_queried_for)
+                        {
+                            lsp_data.links_for_version = query_version_queried_for;
+                            lsp_data.links = Some(links.clone());
+                        }
+                    })
+                    .ok();
+                links
+            })
+            .shared();
+        self.lsp_document_links.entry(buffer_id).or_default().update =
+            Some((version_queried_for, new_task.clone()));
+        new_task
+    }
+
     fn fetch_code_lens(
         &mut self,
         buffer: &Entity<Buffer>,
@@ -6727,6 +6899,275 @@ impl LspStore {
         }
     }
 
+    pub fn semantic_tokens(
+        &mut self,
+        fetch_strategy: LspFetchStrategy,
+        buffer: Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Option<SemanticTokensTask> {
+        let version_queried_for = buffer.read(cx).version();
+        let buffer_id = buffer.read(cx).remote_id();
+
+        match fetch_strategy {
+            LspFetchStrategy::IgnoreCache => {}
+            LspFetchStrategy::UseCache {
+                known_cache_version,
+            } => {
+                if let Some(cached_data) = self.lsp_semantic_tokens.get(&buffer_id)
+                    && !version_queried_for.changed_since(&cached_data.tokens_for_version)
+                {
+                    let has_different_servers = self.as_local().is_some_and(|local| {
+                        local
+                            .buffers_opened_in_servers
+                            .get(&buffer_id)
+                            .cloned()
+                            .unwrap_or_default()
+                            != cached_data.tokens.keys().copied().collect()
+                    });
+                    if !has_different_servers {
+                        if Some(cached_data.cache_version) == known_cache_version {
+                            return None;
+                        } else {
+                            return Some(
+                                Task::ready(Ok(BufferSemanticTokens {
+                                    servers: cached_data.tokens.clone(),
+                                    cache_version: Some(cached_data.cache_version),
+                                }))
+                                .shared(),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        let lsp_data = self.lsp_semantic_tokens.entry(buffer_id).or_default();
+        if let Some((updating_for, running_update)) = &lsp_data.tokens_update
+            && !version_queried_for.changed_since(updating_for)
+        {
+            return Some(running_update.clone());
+        }
+        let query_version_queried_for = version_queried_for.clone();
+        let new_task = cx
+            .spawn(async move |lsp_store, cx| {
+                cx.background_executor()
+                    .timer(Duration::from_millis(30))
+                    .await;
+                let fetched_tokens = lsp_store
+                    .update(cx, |lsp_store, cx| {
+                        lsp_store.fetch_semantic_tokens_for_buffer(&buffer, cx)
+                    })?
+                    .await
+                    .context("fetching semantic tokens")
+                    .map_err(Arc::new);
+                let fetched_tokens = match fetched_tokens {
+                    Ok(fetched_tokens) => fetched_tokens,
+                    Err(e) => {
+                        lsp_store
+                            .update(cx, |lsp_store, _| {
+                                lsp_store
+                                    .lsp_semantic_tokens
+                                    .entry(buffer_id)
+                                    .or_default()
+                                    .tokens_update = None;
+                            })
+                            .ok();
+                        return Err(e);
+                    }
+                };
+
+                // Token positions are only meaningful for the buffer version they were computed for.
+                let snapshot = buffer
+                    .read_with(cx, |buffer, _| buffer.snapshot())
+                    .map_err(Arc::new)?;
+                let decoded_tokens = match fetched_tokens {
+                    Some(fetched_tokens) if snapshot.version() == &query_version_queried_for => {
+                        Some(
+                            cx.background_spawn(async move {
+                                let decoded = fetched_tokens
+                                    .iter()
+                                    .map(|(server_id, raw_tokens)| {
+                                        (
+                                            *server_id,
+                                            ServerSemanticTokens {
+                                                legend: raw_tokens.legend.clone(),
+                                                tokens: decode_semantic_tokens(
+                                                    &raw_tokens.data,
+                                                    &snapshot,
+                                                )
+                                                .into(),
+                                            },
+                                        )
+                                    })
+                                    .collect::<HashMap<_, _>>();
+                                (fetched_tokens, decoded)
+                            })
+                            .await,
+                        )
+                    }
+                    _ => None,
+                };
+
+                lsp_store
+                    .update(cx, |lsp_store, _| {
+                        let lsp_data = lsp_store.lsp_semantic_tokens.entry(buffer_id).or_default();
+                        if let Some((raw_tokens, tokens)) = decoded_tokens
+                            && !lsp_data
+                                .tokens_for_version
+                                .changed_since(&query_version_queried_for)
+                        {
+                            lsp_data.tokens_for_version = query_version_queried_for.clone();
+                            lsp_data.raw_tokens = raw_tokens;
+                            lsp_data.tokens = tokens;
+                            lsp_data.cache_version += 1;
+                        }
+                        if lsp_data
+                            .tokens_update
+                            .as_ref()
+                            .is_some_and(|(updating_for, _)| {
+                                updating_for == &query_version_queried_for
+                            })
+                        {
+                            lsp_data.tokens_update = None;
+                        }
+                        BufferSemanticTokens {
+                            servers: lsp_data.tokens.clone(),
+                            cache_version: Some(lsp_data.cache_version),
+                        }
+                    })
+                    .map_err(Arc::new)
+            })
+            .shared();
+        lsp_data.tokens_update = Some((version_queried_for, new_task.clone()));
+        Some(new_task)
+    }
+
+    fn fetch_semantic_tokens_for_buffer(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<Option<HashMap<LanguageServerId, RawSemanticTokens>>>> {
+        if let Some((client, project_id)) = self.upstream_client() {
+            if !self.check_if_capable_for_proto_request(
+                buffer,
+                |capabilities| semantic_tokens_options(capabilities).is_some(),
+                cx,
+            ) {
+                return Task::ready(Ok(None));
+            }
+
+            let request = client.request(proto::GetSemanticTokens {
+                project_id,
+                buffer_id: buffer.read(cx).remote_id().to_proto(),
+                version: serialize_version(&buffer.read(cx).version()),
+            });
+            let buffer = buffer.clone();
+            cx.spawn(async move |_, cx| {
+                let response = request.await.context("semantic tokens proto request")?;
+                buffer
+                    .update(cx, |buffer, _| {
+                        buffer.wait_for_version(deserialize_version(&response.version))
+                    })?
+                    .await?;
+                Ok(Some(
+                    response
+                        .servers
+                        .into_iter()
+                        .map(|server_tokens| {
+                            let legend = lsp::SemanticTokensLegend {
+                                token_types: server_tokens
+                                    .token_types
+                                    .into_iter()
+                                    .map(lsp::SemanticTokenType::from)
+                                    .collect(),
+                                token_modifiers: server_tokens
+                                    .token_modifiers
+                                    .into_iter()
+                                    .map(lsp::SemanticTokenModifier::from)
+                                    .collect(),
+                            };
+                            (
+                                LanguageServerId::from_proto(server_tokens.server_id),
+                                RawSemanticTokens {
+                                    legend: Arc::new(legend),
+                                    result_id: None,
+                                    data: server_tokens.data,
+                                },
+                            )
+                        })
+                        .collect(),
+                ))
+            })
+        } else {
+            let Some(abs_path) =
+                File::from_dyn(buffer.read(cx).file()).map(|file| file.abs_path(cx))
+            else {
+                return Task::ready(Ok(None));
+            };
+            let uri = match file_path_to_lsp_url(&abs_path) {
+                Ok(uri) => uri,
+                Err(e) => return Task::ready(Err(e)),
+            };
+            let previous_tokens = self
+                .lsp_semantic_tokens
+                .get(&buffer.read(cx).remote_id())
+                .map(|data| data.raw_tokens.clone())
+                .unwrap_or_default();
+            let requests = buffer.update(cx, |buffer, cx| {
+                self.language_servers_for_local_buffer(buffer, cx)
+                    .filter_map(|(_, server)| {
+                        let options = semantic_tokens_options(&server.capabilities())?.clone();
+                        let supports_delta = match options.full? {
+                            lsp::SemanticTokensFullOptions::Bool(supported) => {
+                                if !supported {
+                                    return None;
+                                }
+                                false
+                            }
+                            lsp::SemanticTokensFullOptions::Delta { delta } => {
+                                delta.unwrap_or(false)
+                            }
+                        };
+                        let server_id = server.server_id();
+                        let previous = previous_tokens
+                            .get(&server_id)
+                            .filter(|previous| supports_delta && previous.result_id.is_some())
+                            .cloned();
+                        let legend = Arc::new(options.legend);
+                        let server = server.clone();
+                        let text_document = lsp::TextDocumentIdentifier::new(uri.clone());
+                        Some(async move {
+                            let tokens = request_semantic_tokens(&server, text_document, previous)
+                                .await
+                                .with_context(|| {
+                                    format!("semantic tokens request to server {server_id}")
+                                })?;
+                            anyhow::Ok(tokens.map(|(result_id, data)| {
+                                (
+                                    server_id,
+                                    RawSemanticTokens {
+                                        legend,
+                                        result_id,
+                                        data,
+                                    },
+                                )
+                            }))
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            });
+            cx.background_spawn(async move {
+                Ok(Some(
+                    join_all(requests)
+                        .await
+                        .into_iter()
+                        .filter_map(|tokens| tokens.log_err().flatten())
+                        .collect(),
+                ))
+            })
+        }
+    }
+
     pub fn signature_help<T: ToPointUtf16>(
         &mut self,
         buffer: &Entity<Buffer>,
@@ -7645,6 +8086,45 @@ impl LspStore {
         Ok(())
     }
 
+    /// Replaces the diagnostics that a source other than a language server, such as the
+    /// spellchecker, reports for a buffer. The source's id comes from
+    /// [`LanguageRegistry::next_language_server_id`], so it never clashes with a server's id.
+    pub fn update_buffer_diagnostics_from_source(
+        &mut self,
+        source_id: LanguageServerId,
+        buffer: &Entity<Buffer>,
+        diagnostics: Vec<DiagnosticEntry<Anchor>>,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let buffer = buffer.read(cx);
+        let document_abs_path = File::from_dyn(buffer.file())
+            .map(|file| file.abs_path(cx))
+            .context("buffer has no file")?;
+        let snapshot = buffer.snapshot();
+        let diagnostics = diagnostics
+            .into_iter()
+            .map(|entry| DiagnosticEntry {
+                range: Unclipped(entry.range.start.to_point_utf16(&snapshot))
+                    ..Unclipped(entry.range.end.to_point_utf16(&snapshot)),
+                diagnostic: entry.diagnostic,
+            })
+            .collect();
+        self.merge_diagnostic_entries(
+            vec![DocumentDiagnosticsUpdate {
+                diagnostics: DocumentDiagnostics {
+                    diagnostics,
+                    document_abs_path,
+                    version: None,
+                },
+                result_id: None,
+                server_id: source_id,
+                disk_based_sources: Cow::Borrowed(&[]),
+            }],
+            |_, _, _| false,
+            cx,
+        )
+    }
+
     fn update_worktree_diagnostics(
         &mut self,
         worktree_id: WorktreeId,
@@ -9362,6 +9842,76 @@ impl LspStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_refresh_semantic_tokens(
+        this: Entity<Self>,
+        _: TypedEnvelope<proto::RefreshSemanticTokens>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        this.update(&mut cx, |_, cx| {
+            cx.emit(LspStoreEvent::RefreshSemanticTokens);
+        })?;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_get_semantic_tokens(
+        lsp_store: Entity<Self>,
+        envelope: TypedEnvelope<proto::GetSemanticTokens>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GetSemanticTokensResponse> {
+        let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
+        let buffer = lsp_store.update(&mut cx, |lsp_store, cx| {
+            lsp_store.buffer_store.read(cx).get_existing(buffer_id)
+        })??;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&envelope.payload.version))
+            })?
+            .await
+            .with_context(|| format!("waiting for version for buffer {}", buffer.entity_id()))?;
+
+        let tokens_task = lsp_store.update(&mut cx, |lsp_store, cx| {
+            lsp_store.semantic_tokens(
+                LspFetchStrategy::UseCache {
+                    known_cache_version: None,
+                },
+                buffer.clone(),
+                cx,
+            )
+        })?;
+        if let Some(tokens_task) = tokens_task {
+            tokens_task.await.map_err(|e| anyhow!("{e:#}"))?;
+        }
+
+        lsp_store.update(&mut cx, |lsp_store, _| {
+            let Some(data) = lsp_store.lsp_semantic_tokens.get(&buffer_id) else {
+                return proto::GetSemanticTokensResponse::default();
+            };
+            proto::GetSemanticTokensResponse {
+                servers: data
+                    .raw_tokens
+                    .iter()
+                    .map(|(server_id, raw)| proto::SemanticTokensForServer {
+                        server_id: server_id.to_proto(),
+                        token_types: raw
+                            .legend
+                            .token_types
+                            .iter()
+                            .map(|token_type| token_type.as_str().to_owned())
+                            .collect(),
+                        token_modifiers: raw
+                            .legend
+                            .token_modifiers
+                            .iter()
+                            .map(|modifier| modifier.as_str().to_owned())
+                            .collect(),
+                        data: raw.data.clone(),
+                    })
+                    .collect(),
+                version: serialize_version(&data.tokens_for_version),
+            }
+        })
+    }
+
     async fn handle_open_buffer_for_symbol(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::OpenBufferForSymbol>,
@@ -11073,9 +11623,17 @@ impl LspStore {
             buffer_colors.colors.remove(&for_server);
             buffer_colors.cache_version += 1;
         }
+        for buffer_tokens in self.lsp_semantic_tokens.values_mut() {
+            buffer_tokens.raw_tokens.remove(&for_server);
+            buffer_tokens.tokens.remove(&for_server);
+            buffer_tokens.cache_version += 1;
+        }
         for buffer_lens in self.lsp_code_lens.values_mut() {
             buffer_lens.lens.remove(&for_server);
         }
+        for buffer_links in self.lsp_document_links.values_mut() {
+            buffer_links.links = None;
+        }
         if let Some(local) = self.as_local_mut() {
             local.buffer_pull_diagnostics_result_ids.remove(&for_server);
             for buffer_servers in local.buffers_opened_in_servers.values_mut() {
@@ -11467,6 +12025,17 @@ impl LspStore {
                         notify_server_capabilities_updated(&server, cx);
                     }
                 }
+                "textDocument/documentLink" => {
+                    let caps = reg
+                        .register_options
+                        .map(serde_json::from_value)
+                        .transpose()?
+                        .unwrap_or_default();
+                    server.update_capabilities(|capabilities| {
+                        capabilities.document_link_provider = Some(caps);
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
                 "textDocument/diagnostic" => {
                     if let Some(caps) = reg
                         .register_options
@@ -11490,6 +12059,65 @@ impl LspStore {
                     });
                     notify_server_capabilities_updated(&server, cx);
                 }
+                "textDocument/prepareCallHierarchy" => {
+                    let options = parse_register_capabilities(reg)?;
+                    let provider = match options {
+                        OneOf::Left(value) => lsp::CallHierarchyServerCapability::Simple(value),
+                        OneOf::Right(options) => {
+                            lsp::CallHierarchyServerCapability::Options(options)
+                        }
+                    };
+                    server.update_capabilities(|capabilities| {
+                        capabilities.call_hierarchy_provider = Some(provider);
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/prepareTypeHierarchy" => {
+                    server.set_type_hierarchy_provider(true);
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/foldingRange" => {
+                    let options = parse_register_capabilities(reg)?;
+                    let provider = match options {
+                        OneOf::Left(value) => lsp::FoldingRangeProviderCapability::Simple(value),
+                        OneOf::Right(options) => {
+                            lsp::FoldingRangeProviderCapability::FoldingProvider(options)
+                        }
+                    };
+                    server.update_capabilities(|capabilities| {
+                        capabilities.folding_range_provider = Some(provider);
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/selectionRange" => {
+                    let options = parse_register_capabilities(reg)?;
+                    let provider = match options {
+                        OneOf::Left(value) => lsp::SelectionRangeProviderCapability::Simple(value),
+                        OneOf::Right(options) => {
+                            lsp::SelectionRangeProviderCapability::Options(options)
+                        }
+                    };
+                    server.update_capabilities(|capabilities| {
+                        capabilities.selection_range_provider = Some(provider);
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/semanticTokens" => {
+                    if let Some(caps) = reg
+                        .register_options
+                        .map(serde_json::from_value)
+                        .transpose()?
+                    {
+                        server.update_capabilities(|capabilities| {
+                            capabilities.semantic_tokens_provider = Some(
+                                lsp::SemanticTokensServerCapabilities::SemanticTokensRegistrationOptions(
+                                    caps,
+                                ),
+                            );
+                        });
+                        notify_server_capabilities_updated(&server, cx);
+                    }
+                }
                 _ => log::warn!("unhandled capability registration: {reg:?}"),
             }
         }
@@ -11635,6 +12263,12 @@ impl LspStore {
                     });
                     notify_server_capabilities_updated(&server, cx);
                 }
+                "textDocument/documentLink" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.document_link_provider = None;
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
                 "textDocument/diagnostic" => {
                     server.update_capabilities(|capabilities| {
                         capabilities.diagnostic_provider = None;
@@ -11647,6 +12281,34 @@ impl LspStore {
                     });
                     notify_server_capabilities_updated(&server, cx);
                 }
+                "textDocument/prepareCallHierarchy" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.call_hierarchy_provider = None;
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/prepareTypeHierarchy" => {
+                    server.set_type_hierarchy_provider(false);
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/foldingRange" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.folding_range_provider = None;
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/selectionRange" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.selection_range_provider = None;
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/semanticTokens" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.semantic_tokens_provider = None;
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
                 _ => log::warn!("unhandled capability unregistration: {unreg:?}"),
             }
         }
@@ -11989,6 +12651,128 @@ fn resolve_word_completion(snapshot: &BufferSnapshot, completion: &mut Completio
 
 impl EventEmitter<LspStoreEvent> for LspStore {}
 
+fn semantic_tokens_options(
+    capabilities: &lsp::ServerCapabilities,
+) -> Option<&lsp::SemanticTokensOptions> {
+    match capabilities.semantic_tokens_provider.as_ref()? {
+        lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(options) => Some(options),
+        lsp::SemanticTokensServerCapabilities::SemanticTokensRegistrationOptions(options) => {
+            Some(&options.semantic_tokens_options)
+        }
+    }
+}
+
+/// Requests the full set of semantic tokens from the server,
+/// or a delta against the previous result if there is one.
+async fn request_semantic_tokens(
+    server: &LanguageServer,
+    text_document: lsp::TextDocumentIdentifier,
+    previous: Option<RawSemanticTokens>,
+) -> Result<Option<(Option<String>, Vec<u32>)>> {
+    if let Some(previous) = previous
+        && let Some(previous_result_id) = previous.result_id
+    {
+        let response = server
+            .request::<lsp::request::SemanticTokensFullDeltaRequest>(
+                lsp::SemanticTokensDeltaParams {
+                    text_document,
+                    previous_result_id,
+                    work_done_progress_params: Default::default(),
+                    partial_result_params: Default::default(),
+                },
+            )
+            .await
+            .into_response()?;
+        return Ok(response.map(|response| match response {
+            lsp::SemanticTokensFullDeltaResult::Tokens(tokens) => {
+                (tokens.result_id, raw_semantic_tokens(tokens.data))
+            }
+            lsp::SemanticTokensFullDeltaResult::TokensDelta(delta) => {
+                let mut data = previous.data;
+                apply_semantic_token_edits(&mut data, delta.edits);
+                (delta.result_id, data)
+            }
+            lsp::SemanticTokensFullDeltaResult::PartialTokensDelta { edits } => {
+                let mut data = previous.data;
+                apply_semantic_token_edits(&mut data, edits);
+                (None, data)
+            }
+        }));
+    }
+
+    let response = server
+        .request::<lsp::request::SemanticTokensFullRequest>(lsp::SemanticTokensParams {
+            text_document,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+        .await
+        .into_response()?;
+    Ok(response.map(|response| match response {
+        lsp::SemanticTokensResult::Tokens(tokens) => {
+            (tokens.result_id, raw_semantic_tokens(tokens.data))
+        }
+        lsp::SemanticTokensResult::Partial(partial) => (None, raw_semantic_tokens(partial.data)),
+    }))
+}
+
+fn raw_semantic_tokens(tokens: Vec<lsp::SemanticToken>) -> Vec<u32> {
+    tokens
+        .into_iter()
+        .flat_map(|token| {
+            [
+                token.delta_line,
+                token.delta_start,
+                token.length,
+                token.token_type,
+                token.token_modifiers_bitset,
+            ]
+        })
+        .collect()
+}
+
+/// Edits index into the flat `u32` token data, and all of them are relative to the previous result,
+/// so apply them back to front.
+fn apply_semantic_token_edits(data: &mut Vec<u32>, mut edits: Vec<lsp::SemanticTokensEdit>) {
+    edits.sort_by_key(|edit| Reverse(edit.start));
+    for edit in edits {
+        let start = (edit.start as usize).min(data.len());
+        let end = (start + edit.delete_count as usize).min(data.len());
+        data.splice(
+            start..end,
+            raw_semantic_tokens(edit.data.unwrap_or_default()),
+        );
+    }
+}
+
+fn decode_semantic_tokens(data: &[u32], snapshot: &BufferSnapshot) -> Vec<SemanticToken> {
+    let mut line = 0;
+    let mut start_column = 0;
+    data.chunks_exact(5)
+        .map(|token| {
+            let [delta_line, delta_start, length, token_type, token_modifiers] =
+                [token[0], token[1], token[2], token[3], token[4]];
+            if delta_line == 0 {
+                start_column += delta_start;
+            } else {
+                line += delta_line;
+                start_column = delta_start;
+            }
+            let start = snapshot
+                .clip_point_utf16(Unclipped(PointUtf16::new(line, start_column)), Bias::Left);
+            let end = snapshot.clip_point_utf16(
+                Unclipped(PointUtf16::new(line, start_column.saturating_add(length))),
+                Bias::Left,
+            );
+            SemanticToken {
+                range: snapshot.anchor_after(start)..snapshot.anchor_before(end),
+                token_type,
+                token_modifiers,
+            }
+        })
+        .collect()
+}
+
 fn remove_empty_hover_blocks(mut hover: Hover) -> Option<Hover> {
     hover
         .contents
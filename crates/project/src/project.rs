@@ -1,4 +1,5 @@
 pub mod agent_server_store;
+pub mod bookmark_store;
 pub mod buffer_store;
 mod color_extractor;
 pub mod connection_manager;
@@ -46,6 +47,7 @@ pub use git_store::{
 pub use manifest_tree::ManifestTree;
 
 use anyhow::{Context as _, Result, anyhow};
+use bookmark_store::BookmarkStore;
 use buffer_store::{BufferStore, BufferStoreEvent};
 use client::{Client, Collaborator, PendingEntitySubscription, TypedEnvelope, UserStore, proto};
 use clock::ReplicaId;
@@ -71,7 +73,7 @@ use futures::{
 pub use image_store::{ImageItem, ImageStore};
 use image_store::{ImageItemEvent, ImageStoreEvent};
 
-use ::git::{blame::Blame, status::FileStatus};
+use ::git::{Oid, blame::Blame, status::FileStatus};
 use gpui::{
     App, AppContext, AsyncApp, BorrowAppContext, Context, Entity, EventEmitter, Hsla, SharedString,
     Task, WeakEntity, Window,
@@ -184,6 +186,7 @@ pub struct Project {
     agent_server_store: Entity<AgentServerStore>,
 
     breakpoint_store: Entity<BreakpointStore>,
+    bookmark_store: Entity<BookmarkStore>,
     collab_client: Arc<client::Client>,
     join_project_response_message_id: u32,
     task_store: Entity<TaskStore>,
@@ -337,6 +340,7 @@ pub enum Event {
     Rejoined,
     RefreshInlayHints,
     RefreshCodeLens,
+    RefreshSemanticTokens,
     RevealInProjectPanel(ProjectEntryId),
     SnippetEdit(BufferId, Vec<(lsp::Range, Snippet)>),
     ExpandedAllForEntry(WorktreeId, ProjectEntryId),
@@ -760,6 +764,60 @@ pub struct DocumentSymbol {
     pub children: Vec<DocumentSymbol>,
 }
 
+/// A symbol participating in a call hierarchy, as reported by `textDocument/prepareCallHierarchy`.
+#[derive(Clone, Debug)]
+pub struct CallHierarchyItem {
+    pub name: String,
+    pub kind: lsp::SymbolKind,
+    pub detail: Option<String>,
+    /// The whole range of the symbol, e.g. a function including its body.
+    pub location: Location,
+    /// The range to reveal when navigating to the symbol, e.g. the function name.
+    pub selection_range: Range<language::Anchor>,
+    /// The language server that produced this item, and that has to be queried for its calls.
+    pub server_id: LanguageServerId,
+    /// The item as sent by the language server, passed back verbatim in subsequent requests.
+    pub lsp_item: lsp::CallHierarchyItem,
+}
+
+/// A call edge in a call hierarchy.
+#[derive(Clone, Debug)]
+pub struct CallHierarchyCall {
+    /// The caller for incoming calls, the callee for outgoing calls.
+    pub item: CallHierarchyItem,
+    /// Ranges of the call expressions, always located inside the caller.
+    pub call_sites: Vec<Location>,
+}
+
+/// A type participating in a type hierarchy, as reported by `textDocument/prepareTypeHierarchy`.
+#[derive(Clone, Debug)]
+pub struct TypeHierarchyItem {
+    pub name: String,
+    pub kind: lsp::SymbolKind,
+    pub detail: Option<String>,
+    /// The whole range of the type, e.g. a class including its body.
+    pub location: Location,
+    /// The range to reveal when navigating to the type, e.g. the class name.
+    pub selection_range: Range<language::Anchor>,
+    /// The language server that produced this item, and that has to be queried for its super- and subtypes.
+    pub server_id: LanguageServerId,
+    /// The item as sent by the language server, passed back verbatim in subsequent requests.
+    pub lsp_item: lsp::TypeHierarchyItem,
+}
+
+/// A link in a buffer, as reported by `textDocument/documentLink`.
+#[derive(Clone, Debug)]
+pub struct DocumentLink {
+    pub range: Range<language::Anchor>,
+    /// Where the link points to. When missing, it has to be resolved with `documentLink/resolve`.
+    pub target: Option<lsp::Uri>,
+    pub tooltip: Option<String>,
+    /// The language server that produced this link, and that has to be queried to resolve it.
+    pub server_id: LanguageServerId,
+    /// The link as sent by the language server, passed back verbatim when resolving it.
+    pub lsp_link: lsp::DocumentLink,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct HoverBlock {
     pub text: String,
@@ -1072,6 +1130,8 @@ impl Project {
 
             let breakpoint_store =
                 cx.new(|_| BreakpointStore::local(worktree_store.clone(), buffer_store.clone()));
+            let bookmark_store =
+                cx.new(|_| BookmarkStore::new(worktree_store.clone(), buffer_store.clone()));
 
             let dap_store = cx.new(|cx| {
                 DapStore::new_local(
@@ -1180,6 +1240,7 @@ impl Project {
                 fs,
                 remote_client: None,
                 breakpoint_store,
+                bookmark_store,
                 dap_store,
                 agent_server_store,
 
@@ -1297,6 +1358,8 @@ impl Project {
 
             let breakpoint_store =
                 cx.new(|_| BreakpointStore::remote(REMOTE_SERVER_PROJECT_ID, remote_proto.clone()));
+            let bookmark_store =
+                cx.new(|_| BookmarkStore::new(worktree_store.clone(), buffer_store.clone()));
 
             let dap_store = cx.new(|cx| {
                 DapStore::new_remote(
@@ -1332,6 +1395,7 @@ impl Project {
                 lsp_store,
                 context_server_store,
                 breakpoint_store,
+                bookmark_store,
                 dap_store,
                 join_project_response_message_id: 0,
                 client_state: ProjectClientState::Local,
@@ -1495,6 +1559,8 @@ impl Project {
 
         let breakpoint_store =
             cx.new(|_| BreakpointStore::remote(remote_id, client.clone().into()))?;
+        let bookmark_store =
+            cx.new(|_| BookmarkStore::new(worktree_store.clone(), buffer_store.clone()))?;
         let dap_store = cx.new(|cx| {
             DapStore::new_collab(
                 remote_id,
@@ -1617,6 +1683,7 @@ impl Project {
                     replica_id,
                 },
                 breakpoint_store,
+                bookmark_store,
                 dap_store: dap_store.clone(),
                 git_store: git_store.clone(),
                 agent_server_store,
@@ -1824,6 +1891,10 @@ impl Project {
         self.breakpoint_store.clone()
     }
 
+    pub fn bookmark_store(&self) -> Entity<BookmarkStore> {
+        self.bookmark_store.clone()
+    }
+
     pub fn active_debug_session(&self, cx: &App) -> Option<(Entity<Session>, ActiveStackFrame)> {
         let active_position = self.breakpoint_store.read(cx).active_position()?;
         let session = self
@@ -2669,6 +2740,20 @@ impl Project {
         })
     }
 
+    pub fn open_diff_since(
+        &mut self,
+        commit: Oid,
+        buffer: Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Entity<BufferDiff>>> {
+        if self.is_disconnected(cx) {
+            return Task::ready(Err(anyhow!(ErrorCode::Disconnected)));
+        }
+        self.git_store.update(cx, |git_store, cx| {
+            git_store.open_diff_since(commit, buffer, cx)
+        })
+    }
+
     pub fn open_buffer_by_id(
         &mut self,
         id: BufferId,
@@ -2980,6 +3065,7 @@ impl Project {
             }
             LspStoreEvent::RefreshInlayHints => cx.emit(Event::RefreshInlayHints),
             LspStoreEvent::RefreshCodeLens => cx.emit(Event::RefreshCodeLens),
+            LspStoreEvent::RefreshSemanticTokens => cx.emit(Event::RefreshSemanticTokens),
             LspStoreEvent::LanguageServerPrompt(prompt) => {
                 cx.emit(Event::LanguageServerPrompt(prompt.clone()))
             }
@@ -3645,6 +3731,162 @@ impl Project {
         )
     }
 
+    pub fn prepare_call_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareCallHierarchy { position },
+            cx,
+        )
+    }
+
+    pub fn incoming_calls(
+        &mut self,
+        item: &CallHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyCall>>> {
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::Other(item.server_id),
+            GetIncomingCalls {
+                item: item.lsp_item.clone(),
+            },
+            cx,
+        )
+    }
+
+    pub fn outgoing_calls(
+        &mut self,
+        item: &CallHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyCall>>> {
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::Other(item.server_id),
+            GetOutgoingCalls {
+                item: item.lsp_item.clone(),
+            },
+            cx,
+        )
+    }
+
+    pub fn prepare_type_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareTypeHierarchy { position },
+            cx,
+        )
+    }
+
+    pub fn supertypes(
+        &mut self,
+        item: &TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::Other(item.server_id),
+            GetSupertypes {
+                item: item.lsp_item.clone(),
+            },
+            cx,
+        )
+    }
+
+    pub fn subtypes(
+        &mut self,
+        item: &TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::Other(item.server_id),
+            GetSubtypes {
+                item: item.lsp_item.clone(),
+            },
+            cx,
+        )
+    }
+
+    /// Returns the links in the buffer, as reported by `textDocument/documentLink`.
+    pub fn document_links(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Arc<[DocumentLink]>>> {
+        let document_links = self
+            .lsp_store
+            .update(cx, |lsp_store, cx| lsp_store.document_links(buffer, cx));
+        cx.background_spawn(async move {
+            document_links
+                .await
+                .map_err(|e| anyhow!("document links fetch failed: {e:#}"))
+        })
+    }
+
+    /// Resolves the target of a link without one, with `documentLink/resolve`.
+    pub fn resolve_document_link(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        link: &DocumentLink,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<lsp::Uri>>> {
+        if link.target.is_some() {
+            return Task::ready(Ok(link.target.clone()));
+        }
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::Other(link.server_id),
+            ResolveDocumentLink {
+                link: link.lsp_link.clone(),
+            },
+            cx,
+        )
+    }
+
+    /// Returns the ranges that can be folded in the buffer, as reported by `textDocument/foldingRange`.
+    pub fn folding_ranges(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Range<Anchor>>>> {
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetFoldingRanges,
+            cx,
+        )
+    }
+
+    /// Returns the ranges to select when expanding a selection from each of the given positions,
+    /// from the innermost to the outermost, as reported by `textDocument/selectionRange`.
+    pub fn selection_ranges(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<PointUtf16>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Vec<Range<Anchor>>>>> {
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetSelectionRanges { positions },
+            cx,
+        )
+    }
+
     pub fn symbols(&self, query: &str, cx: &mut Context<Self>) -> Task<Result<Vec<Symbol>>> {
         self.lsp_store
             .update(cx, |lsp_store, cx| lsp_store.symbols(query, cx))
@@ -5155,6 +5397,33 @@ impl Project {
         })
     }
 
+    pub fn any_language_server_supports_selection_ranges(
+        &self,
+        buffer: &Buffer,
+        cx: &mut App,
+    ) -> bool {
+        let Some(language) = buffer.language().cloned() else {
+            return false;
+        };
+        self.lsp_store.update(cx, |lsp_store, _| {
+            let relevant_language_servers = lsp_store
+                .languages
+                .lsp_adapters(&language.name())
+                .into_iter()
+                .map(|lsp_adapter| lsp_adapter.name())
+                .collect::<HashSet<_>>();
+            lsp_store
+                .language_server_statuses()
+                .filter_map(|(server_id, server_status)| {
+                    relevant_language_servers
+                        .contains(&server_status.name)
+                        .then_some(server_id)
+                })
+                .filter_map(|server_id| lsp_store.lsp_server_capabilities.get(&server_id))
+                .any(GetSelectionRanges::check_server_capabilities)
+        })
+    }
+
     pub fn language_server_id_for_name(
         &self,
         buffer: &Buffer,
@@ -4,6 +4,7 @@ use crate::{
     Event, git_store::StatusEntry, task_inventory::TaskContexts, task_store::TaskSettingsLocation,
     *,
 };
+use askpass::AskPassDelegate;
 use async_trait::async_trait;
 use buffer_diff::{
     BufferDiffEvent, CALCULATE_DIFF_TASK, DiffHunkSecondaryStatus, DiffHunkStatus,
@@ -12,8 +13,10 @@ use buffer_diff::{
 use fs::FakeFs;
 use futures::{StreamExt, future};
 use git::{
-    GitHostingProviderRegistry,
-    repository::{RepoPath, repo_path},
+    GitHostingProviderRegistry, Oid,
+    bisect::{BisectOperation, BisectStatus},
+    history::{LogEntry, LogOptions},
+    repository::{RepoPath, ResetMode, Submodule, repo_path},
     status::{StatusCode, TrackedStatus},
 };
 use git2::RepositoryInitOptions;
@@ -3265,14 +3268,15 @@ async fn test_definition(cx: &mut gpui::TestAppContext) {
 }
 
 #[gpui::test]
-async fn test_completions_with_text_edit(cx: &mut gpui::TestAppContext) {
+async fn test_call_hierarchy(cx: &mut gpui::TestAppContext) {
     init_test(cx);
 
     let fs = FakeFs::new(cx.executor());
     fs.insert_tree(
         path!("/dir"),
         json!({
-            "a.ts": "",
+            "a.rs": "fn a() { b() }",
+            "b.rs": "fn b() {}",
         }),
     )
     .await;
@@ -3280,82 +3284,118 @@ async fn test_completions_with_text_edit(cx: &mut gpui::TestAppContext) {
     let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
 
     let language_registry = project.read_with(cx, |project, _| project.languages().clone());
-    language_registry.add(typescript_lang());
-    let mut fake_language_servers = language_registry.register_fake_lsp(
-        "TypeScript",
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
         FakeLspAdapter {
             capabilities: lsp::ServerCapabilities {
-                completion_provider: Some(lsp::CompletionOptions {
-                    trigger_characters: Some(vec![".".to_string()]),
-                    ..Default::default()
-                }),
-                ..Default::default()
+                call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+                ..lsp::ServerCapabilities::default()
             },
-            ..Default::default()
+            ..FakeLspAdapter::default()
         },
     );
 
     let (buffer, _handle) = project
-        .update(cx, |p, cx| {
-            p.open_local_buffer_with_lsp(path!("/dir/a.ts"), cx)
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/b.rs"), cx)
         })
         .await
         .unwrap();
 
-    let fake_server = fake_language_servers.next().await.unwrap();
-
-    // When text_edit exists, it takes precedence over insert_text and label
-    let text = "let a = obj.fqn";
-    buffer.update(cx, |buffer, cx| buffer.set_text(text, cx));
-    let completions = project.update(cx, |project, cx| {
-        project.completions(&buffer, text.len(), DEFAULT_COMPLETION_CONTEXT, cx)
+    let fake_server = fake_servers.next().await.unwrap();
+    let b_item = lsp::CallHierarchyItem {
+        name: "b".to_string(),
+        kind: lsp::SymbolKind::FUNCTION,
+        tags: None,
+        detail: None,
+        uri: lsp::Uri::from_file_path(path!("/dir/b.rs")).unwrap(),
+        range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, 9)),
+        selection_range: lsp::Range::new(lsp::Position::new(0, 3), lsp::Position::new(0, 4)),
+        data: Some(json!({ "id": "b" })),
+    };
+    let a_item = lsp::CallHierarchyItem {
+        name: "a".to_string(),
+        kind: lsp::SymbolKind::FUNCTION,
+        tags: None,
+        detail: None,
+        uri: lsp::Uri::from_file_path(path!("/dir/a.rs")).unwrap(),
+        range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, 14)),
+        selection_range: lsp::Range::new(lsp::Position::new(0, 3), lsp::Position::new(0, 4)),
+        data: None,
+    };
+    fake_server.set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>({
+        let b_item = b_item.clone();
+        move |params, _| {
+            let b_item = b_item.clone();
+            async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(0, 3)
+                );
+                Ok(Some(vec![b_item]))
+            }
+        }
+    });
+    fake_server.set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>({
+        let a_item = a_item.clone();
+        move |params, _| {
+            let a_item = a_item.clone();
+            async move {
+                // The prepared item has to be passed back to the server untouched.
+                assert_eq!(params.item.data, Some(json!({ "id": "b" })));
+                Ok(Some(vec![lsp::CallHierarchyIncomingCall {
+                    from: a_item,
+                    from_ranges: vec![lsp::Range::new(
+                        lsp::Position::new(0, 9),
+                        lsp::Position::new(0, 10),
+                    )],
+                }]))
+            }
+        }
     });
 
-    fake_server
-        .set_request_handler::<lsp::request::Completion, _, _>(|_, _| async {
-            Ok(Some(lsp::CompletionResponse::Array(vec![
-                lsp::CompletionItem {
-                    label: "labelText".into(),
-                    insert_text: Some("insertText".into()),
-                    text_edit: Some(lsp::CompletionTextEdit::Edit(lsp::TextEdit {
-                        range: lsp::Range::new(
-                            lsp::Position::new(0, text.len() as u32 - 3),
-                            lsp::Position::new(0, text.len() as u32),
-                        ),
-                        new_text: "textEditText".into(),
-                    })),
-                    ..Default::default()
-                },
-            ])))
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_call_hierarchy(&buffer, 3, cx)
         })
-        .next()
-        .await;
-
-    let completions = completions
         .await
-        .unwrap()
-        .into_iter()
-        .flat_map(|response| response.completions)
-        .collect::<Vec<_>>();
-    let snapshot = buffer.update(cx, |buffer, _| buffer.snapshot());
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    let item = items.into_iter().next().unwrap();
+    cx.update(|cx| {
+        assert_eq!(item.name, "b");
+        let b_buffer = item.location.buffer.read(cx);
+        assert_eq!(item.selection_range.to_offset(b_buffer), 3..4);
+        assert_eq!(item.location.range.to_offset(b_buffer), 0..9);
+    });
 
-    assert_eq!(completions.len(), 1);
-    assert_eq!(completions[0].new_text, "textEditText");
-    assert_eq!(
-        completions[0].replace_range.to_offset(&snapshot),
-        text.len() - 3..text.len()
-    );
+    let calls = project
+        .update(cx, |project, cx| project.incoming_calls(&item, cx))
+        .await
+        .unwrap();
+    assert_eq!(calls.len(), 1);
+    cx.update(|cx| {
+        let call = &calls[0];
+        assert_eq!(call.item.name, "a");
+        let a_buffer = call.item.location.buffer.read(cx);
+        assert_eq!(a_buffer.text(), "fn a() { b() }");
+        assert_eq!(call.call_sites.len(), 1);
+        assert_eq!(call.call_sites[0].buffer, call.item.location.buffer);
+        assert_eq!(call.call_sites[0].range.to_offset(a_buffer), 9..10);
+    });
 }
 
 #[gpui::test]
-async fn test_completions_with_edit_ranges(cx: &mut gpui::TestAppContext) {
+async fn test_type_hierarchy(cx: &mut gpui::TestAppContext) {
     init_test(cx);
 
     let fs = FakeFs::new(cx.executor());
     fs.insert_tree(
         path!("/dir"),
         json!({
-            "a.ts": "",
+            "a.rs": "trait A {}",
+            "b.rs": "struct B;\nimpl A for B {}",
         }),
     )
     .await;
@@ -3363,135 +3403,121 @@ async fn test_completions_with_edit_ranges(cx: &mut gpui::TestAppContext) {
     let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
 
     let language_registry = project.read_with(cx, |project, _| project.languages().clone());
-    language_registry.add(typescript_lang());
-    let mut fake_language_servers = language_registry.register_fake_lsp(
-        "TypeScript",
-        FakeLspAdapter {
-            capabilities: lsp::ServerCapabilities {
-                completion_provider: Some(lsp::CompletionOptions {
-                    trigger_characters: Some(vec![".".to_string()]),
-                    ..Default::default()
-                }),
-                ..Default::default()
-            },
-            ..Default::default()
-        },
-    );
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp("Rust", FakeLspAdapter::default());
 
     let (buffer, _handle) = project
-        .update(cx, |p, cx| {
-            p.open_local_buffer_with_lsp(path!("/dir/a.ts"), cx)
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/b.rs"), cx)
         })
         .await
         .unwrap();
 
-    let fake_server = fake_language_servers.next().await.unwrap();
-    let text = "let a = obj.fqn";
-
-    // Test 1: When text_edit is None but insert_text exists with default edit_range
-    {
-        buffer.update(cx, |buffer, cx| buffer.set_text(text, cx));
-        let completions = project.update(cx, |project, cx| {
-            project.completions(&buffer, text.len(), DEFAULT_COMPLETION_CONTEXT, cx)
-        });
-
-        fake_server
-            .set_request_handler::<lsp::request::Completion, _, _>(|_, _| async {
-                Ok(Some(lsp::CompletionResponse::List(lsp::CompletionList {
-                    is_incomplete: false,
-                    item_defaults: Some(lsp::CompletionListItemDefaults {
-                        edit_range: Some(lsp::CompletionListItemDefaultsEditRange::Range(
-                            lsp::Range::new(
-                                lsp::Position::new(0, text.len() as u32 - 3),
-                                lsp::Position::new(0, text.len() as u32),
-                            ),
-                        )),
-                        ..Default::default()
-                    }),
-                    items: vec![lsp::CompletionItem {
-                        label: "labelText".into(),
-                        insert_text: Some("insertText".into()),
-                        text_edit: None,
-                        ..Default::default()
-                    }],
-                })))
-            })
-            .next()
-            .await;
-
-        let completions = completions
-            .await
-            .unwrap()
-            .into_iter()
-            .flat_map(|response| response.completions)
-            .collect::<Vec<_>>();
-        let snapshot = buffer.update(cx, |buffer, _| buffer.snapshot());
-
-        assert_eq!(completions.len(), 1);
-        assert_eq!(completions[0].new_text, "insertText");
-        assert_eq!(
-            completions[0].replace_range.to_offset(&snapshot),
-            text.len() - 3..text.len()
-        );
-    }
+    let fake_server = fake_servers.next().await.unwrap();
+    let b_item = lsp::TypeHierarchyItem {
+        name: "B".to_string(),
+        kind: lsp::SymbolKind::STRUCT,
+        tags: None,
+        detail: None,
+        uri: lsp::Uri::from_file_path(path!("/dir/b.rs")).unwrap(),
+        range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, 9)),
+        selection_range: lsp::Range::new(lsp::Position::new(0, 7), lsp::Position::new(0, 8)),
+        data: Some(json!({ "id": "B" })),
+    };
+    let a_item = lsp::TypeHierarchyItem {
+        name: "A".to_string(),
+        kind: lsp::SymbolKind::INTERFACE,
+        tags: None,
+        detail: Some("trait".to_string()),
+        uri: lsp::Uri::from_file_path(path!("/dir/a.rs")).unwrap(),
+        range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, 10)),
+        selection_range: lsp::Range::new(lsp::Position::new(0, 6), lsp::Position::new(0, 7)),
+        data: None,
+    };
+    fake_server.set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>({
+        let b_item = b_item.clone();
+        move |params, _| {
+            let b_item = b_item.clone();
+            async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(0, 7)
+                );
+                Ok(Some(vec![b_item]))
+            }
+        }
+    });
+    fake_server.set_request_handler::<lsp::request::TypeHierarchySupertypes, _, _>({
+        let a_item = a_item.clone();
+        move |params, _| {
+            let a_item = a_item.clone();
+            async move {
+                assert_eq!(params.item.data, Some(json!({ "id": "B" })));
+                Ok(Some(vec![a_item]))
+            }
+        }
+    });
 
-    // Test 2: When both text_edit and insert_text are None with default edit_range
-    {
-        buffer.update(cx, |buffer, cx| buffer.set_text(text, cx));
-        let completions = project.update(cx, |project, cx| {
-            project.completions(&buffer, text.len(), DEFAULT_COMPLETION_CONTEXT, cx)
-        });
+    // The server isn't asked for type hierarchies until it registers the capability.
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_type_hierarchy(&buffer, 7, cx)
+        })
+        .await
+        .unwrap();
+    assert!(items.is_empty());
 
-        fake_server
-            .set_request_handler::<lsp::request::Completion, _, _>(|_, _| async {
-                Ok(Some(lsp::CompletionResponse::List(lsp::CompletionList {
-                    is_incomplete: false,
-                    item_defaults: Some(lsp::CompletionListItemDefaults {
-                        edit_range: Some(lsp::CompletionListItemDefaultsEditRange::Range(
-                            lsp::Range::new(
-                                lsp::Position::new(0, text.len() as u32 - 3),
-                                lsp::Position::new(0, text.len() as u32),
-                            ),
-                        )),
-                        ..Default::default()
-                    }),
-                    items: vec![lsp::CompletionItem {
-                        label: "labelText".into(),
-                        insert_text: None,
-                        text_edit: None,
-                        ..Default::default()
-                    }],
-                })))
-            })
-            .next()
-            .await;
+    fake_server
+        .request::<lsp::request::RegisterCapability>(lsp::RegistrationParams {
+            registrations: vec![lsp::Registration {
+                id: Default::default(),
+                method: "textDocument/prepareTypeHierarchy".to_string(),
+                register_options: None,
+            }],
+        })
+        .await
+        .into_response()
+        .unwrap();
+    cx.executor().run_until_parked();
 
-        let completions = completions
-            .await
-            .unwrap()
-            .into_iter()
-            .flat_map(|response| response.completions)
-            .collect::<Vec<_>>();
-        let snapshot = buffer.update(cx, |buffer, _| buffer.snapshot());
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_type_hierarchy(&buffer, 7, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    let item = items.into_iter().next().unwrap();
+    cx.update(|cx| {
+        assert_eq!(item.name, "B");
+        assert_eq!(item.location.buffer, buffer);
+        assert_eq!(item.selection_range.to_offset(buffer.read(cx)), 7..8);
+    });
 
-        assert_eq!(completions.len(), 1);
-        assert_eq!(completions[0].new_text, "labelText");
-        assert_eq!(
-            completions[0].replace_range.to_offset(&snapshot),
-            text.len() - 3..text.len()
-        );
-    }
+    let supertypes = project
+        .update(cx, |project, cx| project.supertypes(&item, cx))
+        .await
+        .unwrap();
+    assert_eq!(supertypes.len(), 1);
+    cx.update(|cx| {
+        let supertype = &supertypes[0];
+        assert_eq!(supertype.name, "A");
+        assert_eq!(supertype.detail.as_deref(), Some("trait"));
+        let a_buffer = supertype.location.buffer.read(cx);
+        assert_eq!(a_buffer.text(), "trait A {}");
+        assert_eq!(supertype.selection_range.to_offset(a_buffer), 6..7);
+    });
 }
 
 #[gpui::test]
-async fn test_completions_without_edit_ranges(cx: &mut gpui::TestAppContext) {
+async fn test_semantic_tokens(cx: &mut gpui::TestAppContext) {
     init_test(cx);
 
     let fs = FakeFs::new(cx.executor());
     fs.insert_tree(
         path!("/dir"),
         json!({
-            "a.ts": "",
+            "a.rs": "fn main() {\n    let x = 1;\n}",
         }),
     )
     .await;
@@ -3499,98 +3525,287 @@ async fn test_completions_without_edit_ranges(cx: &mut gpui::TestAppContext) {
     let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
 
     let language_registry = project.read_with(cx, |project, _| project.languages().clone());
-    language_registry.add(typescript_lang());
-    let mut fake_language_servers = language_registry.register_fake_lsp(
-        "TypeScript",
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
         FakeLspAdapter {
             capabilities: lsp::ServerCapabilities {
-                completion_provider: Some(lsp::CompletionOptions {
-                    trigger_characters: Some(vec![":".to_string()]),
-                    ..Default::default()
-                }),
-                ..Default::default()
+                semantic_tokens_provider: Some(
+                    lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(
+                        lsp::SemanticTokensOptions {
+                            legend: lsp::SemanticTokensLegend {
+                                token_types: vec![
+                                    lsp::SemanticTokenType::FUNCTION,
+                                    lsp::SemanticTokenType::VARIABLE,
+                                ],
+                                token_modifiers: vec![lsp::SemanticTokenModifier::DECLARATION],
+                            },
+                            full: Some(lsp::SemanticTokensFullOptions::Delta { delta: Some(true) }),
+                            ..lsp::SemanticTokensOptions::default()
+                        },
+                    ),
+                ),
+                ..lsp::ServerCapabilities::default()
             },
-            ..Default::default()
+            ..FakeLspAdapter::default()
         },
     );
 
     let (buffer, _handle) = project
-        .update(cx, |p, cx| {
-            p.open_local_buffer_with_lsp(path!("/dir/a.ts"), cx)
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
         })
         .await
         .unwrap();
 
-    let fake_server = fake_language_servers.next().await.unwrap();
-
-    // Test 1: When text_edit is None but insert_text exists (no edit_range in defaults)
-    let text = "let a = b.fqn";
-    buffer.update(cx, |buffer, cx| buffer.set_text(text, cx));
-    let completions = project.update(cx, |project, cx| {
-        project.completions(&buffer, text.len(), DEFAULT_COMPLETION_CONTEXT, cx)
-    });
-
-    fake_server
-        .set_request_handler::<lsp::request::Completion, _, _>(|_, _| async move {
-            Ok(Some(lsp::CompletionResponse::Array(vec![
-                lsp::CompletionItem {
-                    label: "fullyQualifiedName?".into(),
-                    insert_text: Some("fullyQualifiedName".into()),
-                    ..Default::default()
+    let fake_server = fake_servers.next().await.unwrap();
+    fake_server.set_request_handler::<lsp::request::SemanticTokensFullRequest, _, _>(
+        |_, _| async move {
+            Ok(Some(lsp::SemanticTokensResult::Tokens(
+                lsp::SemanticTokens {
+                    result_id: Some("1".to_string()),
+                    data: vec![
+                        lsp::SemanticToken {
+                            delta_line: 0,
+                            delta_start: 3,
+                            length: 4,
+                            token_type: 0,
+                            token_modifiers_bitset: 1,
+                        },
+                        lsp::SemanticToken {
+                            delta_line: 1,
+                            delta_start: 8,
+                            length: 1,
+                            token_type: 1,
+                            token_modifiers_bitset: 1,
+                        },
+                    ],
                 },
-            ])))
+            )))
+        },
+    );
+    fake_server.set_request_handler::<lsp::request::SemanticTokensFullDeltaRequest, _, _>(
+        |params, _| async move {
+            assert_eq!(params.previous_result_id, "1");
+            Ok(Some(lsp::SemanticTokensFullDeltaResult::TokensDelta(
+                lsp::SemanticTokensDelta {
+                    result_id: Some("2".to_string()),
+                    edits: vec![lsp::SemanticTokensEdit {
+                        start: 10,
+                        delete_count: 0,
+                        data: Some(vec![lsp::SemanticToken {
+                            delta_line: 2,
+                            delta_start: 3,
+                            length: 1,
+                            token_type: 0,
+                            token_modifiers_bitset: 0,
+                        }]),
+                    }],
+                },
+            )))
+        },
+    );
+
+    let fetch_tokens = |cx: &mut gpui::TestAppContext| {
+        project
+            .read_with(cx, |project, _| project.lsp_store())
+            .update(cx, |lsp_store, cx| {
+                lsp_store.semantic_tokens(
+                    lsp_store::LspFetchStrategy::IgnoreCache,
+                    buffer.clone(),
+                    cx,
+                )
+            })
+            .unwrap()
+    };
+    let decoded_tokens = |tokens: &lsp_store::BufferSemanticTokens, cx: &gpui::TestAppContext| {
+        cx.read(|cx| {
+            let server_tokens = tokens.servers.values().next().unwrap();
+            server_tokens
+                .tokens
+                .iter()
+                .map(|token| {
+                    (
+                        token.range.to_offset(buffer.read(cx)),
+                        server_tokens.token_type(token).unwrap().to_string(),
+                        server_tokens.token_modifiers(token).join(","),
+                    )
+                })
+                .collect::<Vec<_>>()
         })
-        .next()
-        .await;
-    let completions = completions
-        .await
-        .unwrap()
-        .into_iter()
-        .flat_map(|response| response.completions)
-        .collect::<Vec<_>>();
-    let snapshot = buffer.update(cx, |buffer, _| buffer.snapshot());
-    assert_eq!(completions.len(), 1);
-    assert_eq!(completions[0].new_text, "fullyQualifiedName");
+    };
+
+    let tokens = fetch_tokens(cx).await.unwrap();
     assert_eq!(
-        completions[0].replace_range.to_offset(&snapshot),
-        text.len() - 3..text.len()
+        decoded_tokens(&tokens, cx),
+        vec![
+            (3..7, "function".to_string(), "declaration".to_string()),
+            (20..21, "variable".to_string(), "declaration".to_string()),
+        ]
     );
 
-    // Test 2: When both text_edit and insert_text are None (no edit_range in defaults)
-    let text = "let a = \"atoms/cmp\"";
-    buffer.update(cx, |buffer, cx| buffer.set_text(text, cx));
-    let completions = project.update(cx, |project, cx| {
-        project.completions(&buffer, text.len() - 1, DEFAULT_COMPLETION_CONTEXT, cx)
+    buffer.update(cx, |buffer, cx| {
+        let len = buffer.len();
+        buffer.edit([(len..len, "\nfn b() {}")], None, cx);
     });
+    let tokens = fetch_tokens(cx).await.unwrap();
+    assert_eq!(
+        decoded_tokens(&tokens, cx),
+        vec![
+            (3..7, "function".to_string(), "declaration".to_string()),
+            (20..21, "variable".to_string(), "declaration".to_string()),
+            (32..33, "function".to_string(), String::new()),
+        ]
+    );
+}
 
-    fake_server
-        .set_request_handler::<lsp::request::Completion, _, _>(|_, _| async move {
-            Ok(Some(lsp::CompletionResponse::Array(vec![
-                lsp::CompletionItem {
-                    label: "component".into(),
-                    ..Default::default()
-                },
-            ])))
+#[gpui::test]
+async fn test_folding_ranges(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "fn main() {\n    let x = vec![\n        1,\n    ];\n}\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                folding_range_provider: Some(lsp::FoldingRangeProviderCapability::Simple(true)),
+                ..lsp::ServerCapabilities::default()
+            },
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
         })
-        .next()
-        .await;
-    let completions = completions
         .await
-        .unwrap()
-        .into_iter()
-        .flat_map(|response| response.completions)
-        .collect::<Vec<_>>();
-    let snapshot = buffer.update(cx, |buffer, _| buffer.snapshot());
-    assert_eq!(completions.len(), 1);
-    assert_eq!(completions[0].new_text, "component");
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    fake_server.set_request_handler::<lsp::request::FoldingRangeRequest, _, _>(|_, _| async move {
+        Ok(Some(vec![
+            lsp::FoldingRange {
+                start_line: 1,
+                start_character: Some(17),
+                end_line: 2,
+                end_character: Some(10),
+                ..lsp::FoldingRange::default()
+            },
+            lsp::FoldingRange {
+                start_line: 0,
+                end_line: 3,
+                ..lsp::FoldingRange::default()
+            },
+            // Single-line ranges cannot be folded.
+            lsp::FoldingRange {
+                start_line: 2,
+                end_line: 2,
+                ..lsp::FoldingRange::default()
+            },
+        ]))
+    });
+
+    let ranges = project
+        .update(cx, |project, cx| project.folding_ranges(&buffer, cx))
+        .await
+        .unwrap();
+    buffer.read_with(cx, |buffer, _| {
+        assert_eq!(
+            ranges
+                .iter()
+                .map(|range| range.to_offset(buffer))
+                .collect::<Vec<_>>(),
+            vec![11..47, 29..40]
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_bookmarks(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "one\ntwo\nthree\nfour\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let bookmark_store = project.read_with(cx, |project, _| project.bookmark_store());
+    let bookmarked_rows = |cx: &mut gpui::TestAppContext| {
+        bookmark_store.read_with(cx, |bookmark_store, cx| {
+            bookmark_store
+                .all_serialized_bookmarks(cx)
+                .into_values()
+                .flatten()
+                .map(|bookmark| (bookmark.row, bookmark.label))
+                .collect::<Vec<_>>()
+        })
+    };
+
+    bookmark_store.update(cx, |bookmark_store, cx| {
+        let snapshot = buffer.read(cx).snapshot();
+        bookmark_store.toggle_bookmark(
+            buffer.clone(),
+            snapshot.anchor_before(Point::new(3, 2)),
+            cx,
+        );
+        bookmark_store.toggle_bookmark(
+            buffer.clone(),
+            snapshot.anchor_before(Point::new(1, 0)),
+            cx,
+        );
+        bookmark_store.set_bookmark_label(
+            buffer.clone(),
+            snapshot.anchor_before(Point::new(3, 0)),
+            Some("four".into()),
+            cx,
+        );
+    });
     assert_eq!(
-        completions[0].replace_range.to_offset(&snapshot),
-        text.len() - 4..text.len() - 1
+        bookmarked_rows(cx),
+        vec![(1, None), (3, Some(Arc::from("four")))]
+    );
+
+    // Bookmarks follow their lines as the buffer is edited.
+    buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "zero\n")], None, cx));
+    assert_eq!(
+        bookmarked_rows(cx),
+        vec![(2, None), (4, Some(Arc::from("four")))]
     );
+
+    // Toggling a bookmarked line removes its bookmark.
+    bookmark_store.update(cx, |bookmark_store, cx| {
+        let snapshot = buffer.read(cx).snapshot();
+        bookmark_store.toggle_bookmark(buffer.clone(), snapshot.anchor_after(Point::new(2, 3)), cx);
+    });
+    assert_eq!(bookmarked_rows(cx), vec![(4, Some(Arc::from("four")))]);
 }
 
 #[gpui::test]
-async fn test_completions_with_carriage_returns(cx: &mut gpui::TestAppContext) {
+async fn test_completions_with_text_edit(cx: &mut gpui::TestAppContext) {
     init_test(cx);
 
     let fs = FakeFs::new(cx.executor());
@@ -3611,7 +3826,7 @@ async fn test_completions_with_carriage_returns(cx: &mut gpui::TestAppContext) {
         FakeLspAdapter {
             capabilities: lsp::ServerCapabilities {
                 completion_provider: Some(lsp::CompletionOptions {
-                    trigger_characters: Some(vec![":".to_string()]),
+                    trigger_characters: Some(vec![".".to_string()]),
                     ..Default::default()
                 }),
                 ..Default::default()
@@ -3629,43 +3844,58 @@ async fn test_completions_with_carriage_returns(cx: &mut gpui::TestAppContext) {
 
     let fake_server = fake_language_servers.next().await.unwrap();
 
-    let text = "let a = b.fqn";
+    // When text_edit exists, it takes precedence over insert_text and label
+    let text = "let a = obj.fqn";
     buffer.update(cx, |buffer, cx| buffer.set_text(text, cx));
     let completions = project.update(cx, |project, cx| {
         project.completions(&buffer, text.len(), DEFAULT_COMPLETION_CONTEXT, cx)
     });
 
     fake_server
-        .set_request_handler::<lsp::request::Completion, _, _>(|_, _| async move {
+        .set_request_handler::<lsp::request::Completion, _, _>(|_, _| async {
             Ok(Some(lsp::CompletionResponse::Array(vec![
                 lsp::CompletionItem {
-                    label: "fullyQualifiedName?".into(),
-                    insert_text: Some("fully\rQualified\r\nName".into()),
+                    label: "labelText".into(),
+                    insert_text: Some("insertText".into()),
+                    text_edit: Some(lsp::CompletionTextEdit::Edit(lsp::TextEdit {
+                        range: lsp::Range::new(
+                            lsp::Position::new(0, text.len() as u32 - 3),
+                            lsp::Position::new(0, text.len() as u32),
+                        ),
+                        new_text: "textEditText".into(),
+                    })),
                     ..Default::default()
                 },
             ])))
         })
         .next()
         .await;
+
     let completions = completions
         .await
         .unwrap()
         .into_iter()
         .flat_map(|response| response.completions)
         .collect::<Vec<_>>();
+    let snapshot = buffer.update(cx, |buffer, _| buffer.snapshot());
+
     assert_eq!(completions.len(), 1);
-    assert_eq!(completions[0].new_text, "fully\nQualified\nName");
+    assert_eq!(completions[0].new_text, "textEditText");
+    assert_eq!(
+        completions[0].replace_range.to_offset(&snapshot),
+        text.len() - 3..text.len()
+    );
 }
 
-#[gpui::test(iterations = 10)]
-async fn test_apply_code_actions_with_commands(cx: &mut gpui::TestAppContext) {
+#[gpui::test]
+async fn test_completions_with_edit_ranges(cx: &mut gpui::TestAppContext) {
     init_test(cx);
 
     let fs = FakeFs::new(cx.executor());
     fs.insert_tree(
         path!("/dir"),
         json!({
-            "a.ts": "a",
+            "a.ts": "",
         }),
     )
     .await;
@@ -3678,19 +3908,13 @@ async fn test_apply_code_actions_with_commands(cx: &mut gpui::TestAppContext) {
         "TypeScript",
         FakeLspAdapter {
             capabilities: lsp::ServerCapabilities {
-                code_action_provider: Some(lsp::CodeActionProviderCapability::Options(
-                    lsp::CodeActionOptions {
-                        resolve_provider: Some(true),
-                        ..lsp::CodeActionOptions::default()
-                    },
-                )),
-                execute_command_provider: Some(lsp::ExecuteCommandOptions {
-                    commands: vec!["_the/command".to_string()],
-                    ..lsp::ExecuteCommandOptions::default()
+                completion_provider: Some(lsp::CompletionOptions {
+                    trigger_characters: Some(vec![".".to_string()]),
+                    ..Default::default()
                 }),
-                ..lsp::ServerCapabilities::default()
+                ..Default::default()
             },
-            ..FakeLspAdapter::default()
+            ..Default::default()
         },
     );
 
@@ -3702,45 +3926,361 @@ async fn test_apply_code_actions_with_commands(cx: &mut gpui::TestAppContext) {
         .unwrap();
 
     let fake_server = fake_language_servers.next().await.unwrap();
+    let text = "let a = obj.fqn";
 
-    // Language server returns code actions that contain commands, and not edits.
-    let actions = project.update(cx, |project, cx| {
-        project.code_actions(&buffer, 0..0, None, cx)
-    });
-    fake_server
-        .set_request_handler::<lsp::request::CodeActionRequest, _, _>(|_, _| async move {
-            Ok(Some(vec![
-                lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
-                    title: "The code action".into(),
-                    data: Some(serde_json::json!({
-                        "command": "_the/command",
-                    })),
-                    ..lsp::CodeAction::default()
-                }),
-                lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
-                    title: "two".into(),
-                    ..lsp::CodeAction::default()
-                }),
-            ]))
-        })
-        .next()
-        .await;
-
-    let action = actions.await.unwrap().unwrap()[0].clone();
-    let apply = project.update(cx, |project, cx| {
-        project.apply_code_action(buffer.clone(), action, true, cx)
-    });
+    // Test 1: When text_edit is None but insert_text exists with default edit_range
+    {
+        buffer.update(cx, |buffer, cx| buffer.set_text(text, cx));
+        let completions = project.update(cx, |project, cx| {
+            project.completions(&buffer, text.len(), DEFAULT_COMPLETION_CONTEXT, cx)
+        });
 
-    // Resolving the code action does not populate its edits. In absence of
-    // edits, we must execute the given command.
-    fake_server.set_request_handler::<lsp::request::CodeActionResolveRequest, _, _>(
-        |mut action, _| async move {
-            if action.data.is_some() {
-                action.command = Some(lsp::Command {
-                    title: "The command".into(),
-                    command: "_the/command".into(),
-                    arguments: Some(vec![json!("the-argument")]),
-                });
+        fake_server
+            .set_request_handler::<lsp::request::Completion, _, _>(|_, _| async {
+                Ok(Some(lsp::CompletionResponse::List(lsp::CompletionList {
+                    is_incomplete: false,
+                    item_defaults: Some(lsp::CompletionListItemDefaults {
+                        edit_range: Some(lsp::CompletionListItemDefaultsEditRange::Range(
+                            lsp::Range::new(
+                                lsp::Position::new(0, text.len() as u32 - 3),
+                                lsp::Position::new(0, text.len() as u32),
+                            ),
+                        )),
+                        ..Default::default()
+                    }),
+                    items: vec![lsp::CompletionItem {
+                        label: "labelText".into(),
+                        insert_text: Some("insertText".into()),
+                        text_edit: None,
+                        ..Default::default()
+                    }],
+                })))
+            })
+            .next()
+            .await;
+
+        let completions = completions
+            .await
+            .unwrap()
+            .into_iter()
+            .flat_map(|response| response.completions)
+            .collect::<Vec<_>>();
+        let snapshot = buffer.update(cx, |buffer, _| buffer.snapshot());
+
+        assert_eq!(completions.len(), 1);
+        assert_eq!(completions[0].new_text, "insertText");
+        assert_eq!(
+            completions[0].replace_range.to_offset(&snapshot),
+            text.len() - 3..text.len()
+        );
+    }
+
+    // Test 2: When both text_edit and insert_text are None with default edit_range
+    {
+        buffer.update(cx, |buffer, cx| buffer.set_text(text, cx));
+        let completions = project.update(cx, |project, cx| {
+            project.completions(&buffer, text.len(), DEFAULT_COMPLETION_CONTEXT, cx)
+        });
+
+        fake_server
+            .set_request_handler::<lsp::request::Completion, _, _>(|_, _| async {
+                Ok(Some(lsp::CompletionResponse::List(lsp::CompletionList {
+                    is_incomplete: false,
+                    item_defaults: Some(lsp::CompletionListItemDefaults {
+                        edit_range: Some(lsp::CompletionListItemDefaultsEditRange::Range(
+                            lsp::Range::new(
+                                lsp::Position::new(0, text.len() as u32 - 3),
+                                lsp::Position::new(0, text.len() as u32),
+                            ),
+                        )),
+                        ..Default::default()
+                    }),
+                    items: vec![lsp::CompletionItem {
+                        label: "labelText".into(),
+                        insert_text: None,
+                        text_edit: None,
+                        ..Default::default()
+                    }],
+                })))
+            })
+            .next()
+            .await;
+
+        let completions = completions
+            .await
+            .unwrap()
+            .into_iter()
+            .flat_map(|response| response.completions)
+            .collect::<Vec<_>>();
+        let snapshot = buffer.update(cx, |buffer, _| buffer.snapshot());
+
+        assert_eq!(completions.len(), 1);
+        assert_eq!(completions[0].new_text, "labelText");
+        assert_eq!(
+            completions[0].replace_range.to_offset(&snapshot),
+            text.len() - 3..text.len()
+        );
+    }
+}
+
+#[gpui::test]
+async fn test_completions_without_edit_ranges(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.ts": "",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(typescript_lang());
+    let mut fake_language_servers = language_registry.register_fake_lsp(
+        "TypeScript",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                completion_provider: Some(lsp::CompletionOptions {
+                    trigger_characters: Some(vec![":".to_string()]),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |p, cx| {
+            p.open_local_buffer_with_lsp(path!("/dir/a.ts"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_language_servers.next().await.unwrap();
+
+    // Test 1: When text_edit is None but insert_text exists (no edit_range in defaults)
+    let text = "let a = b.fqn";
+    buffer.update(cx, |buffer, cx| buffer.set_text(text, cx));
+    let completions = project.update(cx, |project, cx| {
+        project.completions(&buffer, text.len(), DEFAULT_COMPLETION_CONTEXT, cx)
+    });
+
+    fake_server
+        .set_request_handler::<lsp::request::Completion, _, _>(|_, _| async move {
+            Ok(Some(lsp::CompletionResponse::Array(vec![
+                lsp::CompletionItem {
+                    label: "fullyQualifiedName?".into(),
+                    insert_text: Some("fullyQualifiedName".into()),
+                    ..Default::default()
+                },
+            ])))
+        })
+        .next()
+        .await;
+    let completions = completions
+        .await
+        .unwrap()
+        .into_iter()
+        .flat_map(|response| response.completions)
+        .collect::<Vec<_>>();
+    let snapshot = buffer.update(cx, |buffer, _| buffer.snapshot());
+    assert_eq!(completions.len(), 1);
+    assert_eq!(completions[0].new_text, "fullyQualifiedName");
+    assert_eq!(
+        completions[0].replace_range.to_offset(&snapshot),
+        text.len() - 3..text.len()
+    );
+
+    // Test 2: When both text_edit and insert_text are None (no edit_range in defaults)
+    let text = "let a = \"atoms/cmp\"";
+    buffer.update(cx, |buffer, cx| buffer.set_text(text, cx));
+    let completions = project.update(cx, |project, cx| {
+        project.completions(&buffer, text.len() - 1, DEFAULT_COMPLETION_CONTEXT, cx)
+    });
+
+    fake_server
+        .set_request_handler::<lsp::request::Completion, _, _>(|_, _| async move {
+            Ok(Some(lsp::CompletionResponse::Array(vec![
+                lsp::CompletionItem {
+                    label: "component".into(),
+                    ..Default::default()
+                },
+            ])))
+        })
+        .next()
+        .await;
+    let completions = completions
+        .await
+        .unwrap()
+        .into_iter()
+        .flat_map(|response| response.completions)
+        .collect::<Vec<_>>();
+    let snapshot = buffer.update(cx, |buffer, _| buffer.snapshot());
+    assert_eq!(completions.len(), 1);
+    assert_eq!(completions[0].new_text, "component");
+    assert_eq!(
+        completions[0].replace_range.to_offset(&snapshot),
+        text.len() - 4..text.len() - 1
+    );
+}
+
+#[gpui::test]
+async fn test_completions_with_carriage_returns(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.ts": "",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(typescript_lang());
+    let mut fake_language_servers = language_registry.register_fake_lsp(
+        "TypeScript",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                completion_provider: Some(lsp::CompletionOptions {
+                    trigger_characters: Some(vec![":".to_string()]),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |p, cx| {
+            p.open_local_buffer_with_lsp(path!("/dir/a.ts"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_language_servers.next().await.unwrap();
+
+    let text = "let a = b.fqn";
+    buffer.update(cx, |buffer, cx| buffer.set_text(text, cx));
+    let completions = project.update(cx, |project, cx| {
+        project.completions(&buffer, text.len(), DEFAULT_COMPLETION_CONTEXT, cx)
+    });
+
+    fake_server
+        .set_request_handler::<lsp::request::Completion, _, _>(|_, _| async move {
+            Ok(Some(lsp::CompletionResponse::Array(vec![
+                lsp::CompletionItem {
+                    label: "fullyQualifiedName?".into(),
+                    insert_text: Some("fully\rQualified\r\nName".into()),
+                    ..Default::default()
+                },
+            ])))
+        })
+        .next()
+        .await;
+    let completions = completions
+        .await
+        .unwrap()
+        .into_iter()
+        .flat_map(|response| response.completions)
+        .collect::<Vec<_>>();
+    assert_eq!(completions.len(), 1);
+    assert_eq!(completions[0].new_text, "fully\nQualified\nName");
+}
+
+#[gpui::test(iterations = 10)]
+async fn test_apply_code_actions_with_commands(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.ts": "a",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(typescript_lang());
+    let mut fake_language_servers = language_registry.register_fake_lsp(
+        "TypeScript",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                code_action_provider: Some(lsp::CodeActionProviderCapability::Options(
+                    lsp::CodeActionOptions {
+                        resolve_provider: Some(true),
+                        ..lsp::CodeActionOptions::default()
+                    },
+                )),
+                execute_command_provider: Some(lsp::ExecuteCommandOptions {
+                    commands: vec!["_the/command".to_string()],
+                    ..lsp::ExecuteCommandOptions::default()
+                }),
+                ..lsp::ServerCapabilities::default()
+            },
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |p, cx| {
+            p.open_local_buffer_with_lsp(path!("/dir/a.ts"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_language_servers.next().await.unwrap();
+
+    // Language server returns code actions that contain commands, and not edits.
+    let actions = project.update(cx, |project, cx| {
+        project.code_actions(&buffer, 0..0, None, cx)
+    });
+    fake_server
+        .set_request_handler::<lsp::request::CodeActionRequest, _, _>(|_, _| async move {
+            Ok(Some(vec![
+                lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
+                    title: "The code action".into(),
+                    data: Some(serde_json::json!({
+                        "command": "_the/command",
+                    })),
+                    ..lsp::CodeAction::default()
+                }),
+                lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
+                    title: "two".into(),
+                    ..lsp::CodeAction::default()
+                }),
+            ]))
+        })
+        .next()
+        .await;
+
+    let action = actions.await.unwrap().unwrap()[0].clone();
+    let apply = project.update(cx, |project, cx| {
+        project.apply_code_action(buffer.clone(), action, true, cx)
+    });
+
+    // Resolving the code action does not populate its edits. In absence of
+    // edits, we must execute the given command.
+    fake_server.set_request_handler::<lsp::request::CodeActionResolveRequest, _, _>(
+        |mut action, _| async move {
+            if action.data.is_some() {
+                action.command = Some(lsp::Command {
+                    title: "The command".into(),
+                    command: "_the/command".into(),
+                    arguments: Some(vec![json!("the-argument")]),
+                });
             }
             Ok(action)
         },
@@ -7208,13 +7748,142 @@ async fn test_uncommitted_diff_for_buffer(cx: &mut gpui::TestAppContext) {
             &snapshot,
             &diff.base_text_string().unwrap(),
             &[(
-                0..0,
-                "// the-deleted-contents\n",
-                "",
-                DiffHunkStatus::deleted(DiffHunkSecondaryStatus::NoSecondaryHunk),
+                0..0,
+                "// the-deleted-contents\n",
+                "",
+                DiffHunkStatus::deleted(DiffHunkSecondaryStatus::NoSecondaryHunk),
+            )],
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_diff_since_commit(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            ".git": {},
+            "src": {
+                "a.rs": "one\ntwo-changed\nthree\n",
+                "b.rs": "new\n",
+            }
+        }),
+    )
+    .await;
+    fs.set_head_and_index_for_repo(
+        path!("/dir/.git").as_ref(),
+        &[
+            ("src/a.rs", "one\ntwo\nthree\n".into()),
+            ("src/b.rs", "new\n".into()),
+        ],
+    );
+    let base: Oid = "1111111111111111111111111111111111111111".parse().unwrap();
+    fs.with_git_state(path!("/dir/.git").as_ref(), true, |state| {
+        state.refs.insert("main".into(), base.to_string());
+        state.commit_contents.insert(
+            base,
+            HashMap::from_iter([(repo_path("src/a.rs"), "zero\none\ntwo\nthree\n".into())]),
+        );
+    })
+    .unwrap();
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    cx.run_until_parked();
+    let repository = project.read_with(cx, |project, cx| {
+        project.repositories(cx).values().next().unwrap().clone()
+    });
+
+    let commit = repository
+        .update(cx, |repository, _| {
+            repository.resolve_diff_base("main".into(), false)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(commit, base);
+    let changes = repository
+        .update(cx, |repository, _| repository.changed_paths_since(base))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        changes,
+        [
+            (repo_path("src/a.rs"), StatusCode::Modified),
+            (repo_path("src/b.rs"), StatusCode::Added),
+        ]
+    );
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/dir/src/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let diff = project
+        .update(cx, |project, cx| {
+            project.open_diff_since(base, buffer.clone(), cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+    diff.update(cx, |diff, cx| {
+        let snapshot = buffer.read(cx).snapshot();
+        assert_hunks(
+            diff.hunks_intersecting_range(Anchor::MIN..Anchor::MAX, &snapshot, cx),
+            &snapshot,
+            &diff.base_text_string().unwrap(),
+            &[
+                (0..0, "zero\n", "", DiffHunkStatus::deleted_none()),
+                (
+                    1..2,
+                    "two\n",
+                    "two-changed\n",
+                    DiffHunkStatus::modified_none(),
+                ),
+            ],
+        );
+    });
+
+    // The diff follows the edits to the buffer, while its base stays the same.
+    buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "zero\n")], None, cx));
+    cx.run_until_parked();
+    diff.update(cx, |diff, cx| {
+        let snapshot = buffer.read(cx).snapshot();
+        assert_hunks(
+            diff.hunks_intersecting_range(Anchor::MIN..Anchor::MAX, &snapshot, cx),
+            &snapshot,
+            &diff.base_text_string().unwrap(),
+            &[(
+                2..3,
+                "two\n",
+                "two-changed\n",
+                DiffHunkStatus::modified_none(),
             )],
         );
     });
+
+    // Opening the same diff again reuses it, and the uncommitted diff is unaffected.
+    let same_diff = project
+        .update(cx, |project, cx| {
+            project.open_diff_since(base, buffer.clone(), cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(same_diff.entity_id(), diff.entity_id());
+    let uncommitted_diff = project
+        .update(cx, |project, cx| {
+            project.open_uncommitted_diff(buffer.clone(), cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+    uncommitted_diff.read_with(cx, |diff, _| {
+        assert_eq!(diff.base_text_string().unwrap(), "one\ntwo\nthree\n");
+    });
 }
 
 #[gpui::test]
@@ -9055,135 +9724,427 @@ async fn test_git_worktrees_and_submodules(cx: &mut gpui::TestAppContext) {
                             "config": "",
                         }
                     }
-                }
-            },
-            "src": {
-                "a.txt": "A",
+                }
+            },
+            "src": {
+                "a.txt": "A",
+            },
+            "some-worktree": {
+                ".git": "gitdir: ../.git/worktrees/some-worktree\n",
+                "src": {
+                    "b.txt": "B",
+                }
+            },
+            "subdir": {
+                "some-submodule": {
+                    ".git": "gitdir: ../../.git/modules/subdir/some-submodule\n",
+                    "c.txt": "C",
+                }
+            }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+    let scan_complete = project.update(cx, |project, cx| project.git_scans_complete(cx));
+    scan_complete.await;
+
+    let mut repositories = project.update(cx, |project, cx| {
+        project
+            .repositories(cx)
+            .values()
+            .map(|repo| repo.read(cx).work_directory_abs_path.clone())
+            .collect::<Vec<_>>()
+    });
+    repositories.sort();
+    pretty_assertions::assert_eq!(
+        repositories,
+        [
+            Path::new(path!("/project")).into(),
+            Path::new(path!("/project/some-worktree")).into(),
+            Path::new(path!("/project/subdir/some-submodule")).into(),
+        ]
+    );
+
+    // Generate a git-related event for the worktree and check that it's refreshed.
+    fs.with_git_state(
+        path!("/project/some-worktree/.git").as_ref(),
+        true,
+        |state| {
+            state
+                .head_contents
+                .insert(repo_path("src/b.txt"), "b".to_owned());
+            state
+                .index_contents
+                .insert(repo_path("src/b.txt"), "b".to_owned());
+        },
+    )
+    .unwrap();
+    cx.run_until_parked();
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/project/some-worktree/src/b.txt"), cx)
+        })
+        .await
+        .unwrap();
+    let (worktree_repo, barrier) = project.update(cx, |project, cx| {
+        let (repo, _) = project
+            .git_store()
+            .read(cx)
+            .repository_and_path_for_buffer_id(buffer.read(cx).remote_id(), cx)
+            .unwrap();
+        pretty_assertions::assert_eq!(
+            repo.read(cx).work_directory_abs_path,
+            Path::new(path!("/project/some-worktree")).into(),
+        );
+        let barrier = repo.update(cx, |repo, _| repo.barrier());
+        (repo.clone(), barrier)
+    });
+    barrier.await.unwrap();
+    worktree_repo.update(cx, |repo, _| {
+        pretty_assertions::assert_eq!(
+            repo.status_for_path(&repo_path("src/b.txt"))
+                .unwrap()
+                .status,
+            StatusCode::Modified.worktree(),
+        );
+    });
+
+    // The same for the submodule.
+    fs.with_git_state(
+        path!("/project/subdir/some-submodule/.git").as_ref(),
+        true,
+        |state| {
+            state
+                .head_contents
+                .insert(repo_path("c.txt"), "c".to_owned());
+            state
+                .index_contents
+                .insert(repo_path("c.txt"), "c".to_owned());
+        },
+    )
+    .unwrap();
+    cx.run_until_parked();
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/project/subdir/some-submodule/c.txt"), cx)
+        })
+        .await
+        .unwrap();
+    let (submodule_repo, barrier) = project.update(cx, |project, cx| {
+        let (repo, _) = project
+            .git_store()
+            .read(cx)
+            .repository_and_path_for_buffer_id(buffer.read(cx).remote_id(), cx)
+            .unwrap();
+        pretty_assertions::assert_eq!(
+            repo.read(cx).work_directory_abs_path,
+            Path::new(path!("/project/subdir/some-submodule")).into(),
+        );
+        let barrier = repo.update(cx, |repo, _| repo.barrier());
+        (repo.clone(), barrier)
+    });
+    barrier.await.unwrap();
+    submodule_repo.update(cx, |repo, _| {
+        pretty_assertions::assert_eq!(
+            repo.status_for_path(&repo_path("c.txt")).unwrap().status,
+            StatusCode::Modified.worktree(),
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_submodule_status(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            ".git": {
+                "modules": {
+                    "some-submodule": {
+                        // For is_git_dir
+                        "HEAD": "",
+                        "config": "",
+                    }
+                }
+            },
+            ".gitmodules": "",
+            "src": {
+                "a.txt": "A",
+            },
+            "some-submodule": {
+                ".git": "gitdir: ../.git/modules/some-submodule\n",
+                "b.txt": "B",
+            }
+        }),
+    )
+    .await;
+
+    let submodule = Submodule {
+        path: repo_path("some-submodule"),
+        name: "some-submodule".into(),
+        url: Some("https://example.com/some-submodule.git".into()),
+        recorded_sha: Some("1111111111111111111111111111111111111111".into()),
+        checked_out_sha: Some("2222222222222222222222222222222222222222".into()),
+        is_dirty: false,
+    };
+    fs.set_submodules_for_repo(Path::new(path!("/project/.git")), vec![submodule.clone()]);
+
+    let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+    project
+        .update(cx, |project, cx| project.git_scans_complete(cx))
+        .await;
+
+    let worktree_id = project.read_with(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    let submodule_for_path = |path: &str, cx: &mut gpui::TestAppContext| {
+        project.read_with(cx, |project, cx| {
+            project.git_store().read(cx).submodule_for_project_path(
+                &ProjectPath {
+                    worktree_id,
+                    path: rel_path(path).into(),
+                },
+                cx,
+            )
+        })
+    };
+
+    // The submodule is reported by the repository containing it, even though it's also a
+    // repository of its own.
+    let found = submodule_for_path("some-submodule", cx).unwrap();
+    assert_eq!(found, submodule);
+    assert!(found.is_initialized());
+    assert!(found.is_out_of_date());
+    assert_eq!(submodule_for_path("src", cx), None);
+
+    // Committing the new pointer and leaving changes in the submodule updates its state.
+    fs.set_submodules_for_repo(
+        Path::new(path!("/project/.git")),
+        vec![Submodule {
+            recorded_sha: submodule.checked_out_sha.clone(),
+            is_dirty: true,
+            ..submodule.clone()
+        }],
+    );
+    cx.run_until_parked();
+    let found = submodule_for_path("some-submodule", cx).unwrap();
+    assert!(!found.is_out_of_date());
+    assert!(found.is_dirty);
+
+    // An uninitialized submodule isn't out of date, as nothing is checked out in it.
+    fs.set_submodules_for_repo(
+        Path::new(path!("/project/.git")),
+        vec![Submodule {
+            checked_out_sha: None,
+            ..submodule.clone()
+        }],
+    );
+    cx.run_until_parked();
+    let found = submodule_for_path("some-submodule", cx).unwrap();
+    assert!(!found.is_initialized());
+    assert!(!found.is_out_of_date());
+}
+
+#[gpui::test]
+async fn test_bisect_status(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            ".git": {},
+            "a.txt": "a",
+        }),
+    )
+    .await;
+    fs.set_head_for_repo(
+        Path::new(path!("/project/.git")),
+        &[("a.txt", "a".into())],
+        "2222222222222222222222222222222222222222",
+    );
+
+    let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+    project
+        .update(cx, |project, cx| project.git_scans_complete(cx))
+        .await;
+    let repository = project.read_with(cx, |project, cx| {
+        project.repositories(cx).values().next().unwrap().clone()
+    });
+    let bisect = |operation: BisectOperation, cx: &mut gpui::TestAppContext| {
+        let result = repository.update(cx, |repository, cx| repository.bisect(operation, cx));
+        cx.run_until_parked();
+        result
+    };
+    assert_eq!(
+        repository.read_with(cx, |repository, _| repository.bisect.clone()),
+        None
+    );
+
+    // Marking a commit before starting fails.
+    bisect(BisectOperation::Good(None), cx)
+        .await
+        .unwrap()
+        .unwrap_err();
+
+    bisect(
+        BisectOperation::Start {
+            bad: Some("HEAD".into()),
+            good: Vec::new(),
+        },
+        cx,
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    bisect(
+        BisectOperation::Good(Some("1111111111111111111111111111111111111111".into())),
+        cx,
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    bisect(BisectOperation::Skip(None), cx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        repository.read_with(cx, |repository, _| repository.bisect.clone()),
+        Some(BisectStatus {
+            bad: Some("HEAD".into()),
+            good: vec!["1111111111111111111111111111111111111111".into()],
+            skipped: vec!["2222222222222222222222222222222222222222".into()],
+            progress: None,
+        })
+    );
+
+    bisect(BisectOperation::Reset, cx).await.unwrap().unwrap();
+    assert_eq!(
+        repository.read_with(cx, |repository, _| repository.bisect.clone()),
+        None
+    );
+}
+
+#[gpui::test]
+async fn test_git_linked_worktrees(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            ".git": {
+                // Unlike the common directory, the directory of a linked worktree has no config.
+                "worktrees": {
+                    "feature": {
+                        "commondir": "../..\n",
+                        "gitdir": "/project/feature/.git\n",
+                        "HEAD": "",
+                    }
+                },
             },
-            "some-worktree": {
-                ".git": "gitdir: ../.git/worktrees/some-worktree\n",
-                "src": {
-                    "b.txt": "B",
-                }
+            "a.txt": "A",
+            "feature": {
+                ".git": "gitdir: ../.git/worktrees/feature\n",
+                "b.txt": "B",
             },
-            "subdir": {
-                "some-submodule": {
-                    ".git": "gitdir: ../../.git/modules/subdir/some-submodule\n",
-                    "c.txt": "C",
-                }
-            }
         }),
     )
     .await;
 
     let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
-    let scan_complete = project.update(cx, |project, cx| project.git_scans_complete(cx));
-    scan_complete.await;
-
-    let mut repositories = project.update(cx, |project, cx| {
-        project
-            .repositories(cx)
-            .values()
-            .map(|repo| repo.read(cx).work_directory_abs_path.clone())
-            .collect::<Vec<_>>()
-    });
-    repositories.sort();
-    pretty_assertions::assert_eq!(
-        repositories,
-        [
-            Path::new(path!("/project")).into(),
-            Path::new(path!("/project/some-worktree")).into(),
-            Path::new(path!("/project/subdir/some-submodule")).into(),
-        ]
-    );
+    project
+        .update(cx, |project, cx| project.git_scans_complete(cx))
+        .await;
 
-    // Generate a git-related event for the worktree and check that it's refreshed.
-    fs.with_git_state(
-        path!("/project/some-worktree/.git").as_ref(),
-        true,
-        |state| {
-            state
-                .head_contents
-                .insert(repo_path("src/b.txt"), "b".to_owned());
-            state
-                .index_contents
-                .insert(repo_path("src/b.txt"), "b".to_owned());
-        },
-    )
+    let repository = |path: &str, cx: &mut gpui::TestAppContext| {
+        project.read_with(cx, |project, cx| {
+            project
+                .repositories(cx)
+                .values()
+                .find(|repo| repo.read(cx).work_directory_abs_path.as_ref() == Path::new(path))
+                .unwrap()
+                .clone()
+        })
+    };
+    let main_repo = repository(path!("/project"), cx);
+    let linked_repo = repository(path!("/project/feature"), cx);
+
+    // A change to the linked worktree's directory inside the common one rescans it, even though
+    // the main worktree shares that common directory.
+    fs.with_git_state(path!("/project/feature/.git").as_ref(), true, |state| {
+        state
+            .head_contents
+            .insert(repo_path("b.txt"), "b".to_owned());
+        state
+            .index_contents
+            .insert(repo_path("b.txt"), "b".to_owned());
+    })
     .unwrap();
     cx.run_until_parked();
-
-    let buffer = project
-        .update(cx, |project, cx| {
-            project.open_local_buffer(path!("/project/some-worktree/src/b.txt"), cx)
-        })
+    linked_repo
+        .update(cx, |repo, _| repo.barrier())
         .await
         .unwrap();
-    let (worktree_repo, barrier) = project.update(cx, |project, cx| {
-        let (repo, _) = project
-            .git_store()
-            .read(cx)
-            .repository_and_path_for_buffer_id(buffer.read(cx).remote_id(), cx)
-            .unwrap();
-        pretty_assertions::assert_eq!(
-            repo.read(cx).work_directory_abs_path,
-            Path::new(path!("/project/some-worktree")).into(),
-        );
-        let barrier = repo.update(cx, |repo, _| repo.barrier());
-        (repo.clone(), barrier)
-    });
-    barrier.await.unwrap();
-    worktree_repo.update(cx, |repo, _| {
+    linked_repo.read_with(cx, |repo, _| {
         pretty_assertions::assert_eq!(
-            repo.status_for_path(&repo_path("src/b.txt"))
-                .unwrap()
-                .status,
+            repo.status_for_path(&repo_path("b.txt")).unwrap().status,
             StatusCode::Modified.worktree(),
         );
     });
 
-    // The same for the submodule.
-    fs.with_git_state(
-        path!("/project/subdir/some-submodule/.git").as_ref(),
-        true,
-        |state| {
-            state
-                .head_contents
-                .insert(repo_path("c.txt"), "c".to_owned());
-            state
-                .index_contents
-                .insert(repo_path("c.txt"), "c".to_owned());
-        },
-    )
-    .unwrap();
-    cx.run_until_parked();
+    let worktrees = async |cx: &mut gpui::TestAppContext| {
+        main_repo
+            .update(cx, |repo, _| repo.worktrees())
+            .await
+            .unwrap()
+            .unwrap()
+            .into_iter()
+            .map(|worktree| (worktree.path, worktree.branch, worktree.is_main))
+            .collect::<Vec<_>>()
+    };
+    main_repo
+        .update(cx, |repo, cx| {
+            repo.create_worktree(PathBuf::from(path!("/other/hotfix")), None, cx)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    pretty_assertions::assert_eq!(
+        worktrees(cx).await,
+        [
+            (PathBuf::from(path!("/project")), None, true),
+            (
+                PathBuf::from(path!("/other/hotfix")),
+                Some("hotfix".into()),
+                false
+            ),
+        ]
+    );
 
-    let buffer = project
-        .update(cx, |project, cx| {
-            project.open_local_buffer(path!("/project/subdir/some-submodule/c.txt"), cx)
+    main_repo
+        .update(cx, |repo, cx| {
+            repo.remove_worktree(PathBuf::from(path!("/other/hotfix")), false, cx)
         })
         .await
+        .unwrap()
         .unwrap();
-    let (submodule_repo, barrier) = project.update(cx, |project, cx| {
-        let (repo, _) = project
-            .git_store()
-            .read(cx)
-            .repository_and_path_for_buffer_id(buffer.read(cx).remote_id(), cx)
-            .unwrap();
-        pretty_assertions::assert_eq!(
-            repo.read(cx).work_directory_abs_path,
-            Path::new(path!("/project/subdir/some-submodule")).into(),
-        );
-        let barrier = repo.update(cx, |repo, _| repo.barrier());
-        (repo.clone(), barrier)
-    });
-    barrier.await.unwrap();
-    submodule_repo.update(cx, |repo, _| {
-        pretty_assertions::assert_eq!(
-            repo.status_for_path(&repo_path("c.txt")).unwrap().status,
-            StatusCode::Modified.worktree(),
-        );
-    });
+    pretty_assertions::assert_eq!(
+        worktrees(cx).await,
+        [(PathBuf::from(path!("/project")), None, true)]
+    );
+    let missing = main_repo
+        .update(cx, |repo, cx| {
+            repo.remove_worktree(PathBuf::from(path!("/other/hotfix")), false, cx)
+        })
+        .await
+        .unwrap();
+    assert!(missing.is_err());
 }
 
 #[gpui::test]
@@ -9233,6 +10194,205 @@ async fn test_repository_deduplication(cx: &mut gpui::TestAppContext) {
     pretty_assertions::assert_eq!(repos, [Path::new(path!("/root/project")).into()]);
 }
 
+#[gpui::test]
+async fn test_cherry_pick_revert_and_reset(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        path!("/root"),
+        json!({
+            ".git": {},
+            "a.txt": "A",
+        }),
+    )
+    .await;
+
+    let commit = |sha: &str, subject: &str| LogEntry {
+        sha: sha.parse().unwrap(),
+        parents: Vec::new(),
+        refs: Vec::new(),
+        subject: subject.to_string().into(),
+        author_name: "Someone".into(),
+        author_email: "someone@example.com".into(),
+        commit_timestamp: 0,
+    };
+    let second = commit("2222222222222222222222222222222222222222", "Second");
+    let first = commit("1111111111111111111111111111111111111111", "First");
+    fs.set_log_for_repo(
+        Path::new(path!("/root/.git")),
+        vec![second.clone(), first.clone()],
+    );
+
+    let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+    project
+        .update(cx, |project, cx| project.git_scans_complete(cx))
+        .await;
+    let repository = project.read_with(cx, |project, cx| {
+        project.repositories(cx).values().next().unwrap().clone()
+    });
+    let subjects = async |cx: &mut gpui::TestAppContext| {
+        repository
+            .update(cx, |repository, _| {
+                repository.log(LogOptions {
+                    limit: 10,
+                    ..Default::default()
+                })
+            })
+            .await
+            .unwrap()
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.subject.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    repository
+        .update(cx, |repository, cx| {
+            repository.revert(second.sha.to_string(), cx)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(subjects(cx).await, ["Revert \"Second\"", "Second", "First"]);
+
+    repository
+        .update(cx, |repository, cx| {
+            repository.cherry_pick(first.sha.to_string(), cx)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        subjects(cx).await,
+        ["First", "Revert \"Second\"", "Second", "First"]
+    );
+
+    repository
+        .update(cx, |repository, cx| {
+            repository.reset(second.sha.to_string(), ResetMode::Hard, cx)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(subjects(cx).await, ["Second", "First"]);
+
+    let unknown = repository
+        .update(cx, |repository, cx| {
+            repository.cherry_pick("3333333".to_string(), cx)
+        })
+        .await
+        .unwrap();
+    assert!(unknown.is_err());
+}
+
+#[gpui::test]
+async fn test_tags(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        path!("/root"),
+        json!({
+            ".git": {},
+            "a.txt": "A",
+        }),
+    )
+    .await;
+
+    let commit = |sha: &str, subject: &str, commit_timestamp: i64| LogEntry {
+        sha: sha.parse().unwrap(),
+        parents: Vec::new(),
+        refs: Vec::new(),
+        subject: subject.to_string().into(),
+        author_name: "Someone".into(),
+        author_email: "someone@example.com".into(),
+        commit_timestamp,
+    };
+    let second = commit("2222222222222222222222222222222222222222", "Second", 2);
+    let first = commit("1111111111111111111111111111111111111111", "First", 1);
+    fs.set_log_for_repo(
+        Path::new(path!("/root/.git")),
+        vec![second.clone(), first.clone()],
+    );
+
+    let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+    project
+        .update(cx, |project, cx| project.git_scans_complete(cx))
+        .await;
+    let repository = project.read_with(cx, |project, cx| {
+        project.repositories(cx).values().next().unwrap().clone()
+    });
+    let tags = async |cx: &mut gpui::TestAppContext| {
+        repository
+            .update(cx, |repository, _| repository.tags())
+            .await
+            .unwrap()
+            .unwrap()
+            .into_iter()
+            .map(|tag| (tag.name.to_string(), tag.sha.to_string(), tag.message))
+            .collect::<Vec<_>>()
+    };
+
+    repository
+        .update(cx, |repository, cx| {
+            repository.create_tag("v1".into(), first.sha.to_string(), None, cx)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    repository
+        .update(cx, |repository, cx| {
+            repository.create_tag("v2".into(), "HEAD".into(), Some("Release".into()), cx)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        tags(cx).await,
+        [
+            (
+                "v2".to_string(),
+                second.sha.to_string(),
+                Some("Release".into())
+            ),
+            ("v1".to_string(), first.sha.to_string(), None),
+        ]
+    );
+
+    let duplicate = repository
+        .update(cx, |repository, cx| {
+            repository.create_tag("v1".into(), "HEAD".into(), None, cx)
+        })
+        .await
+        .unwrap();
+    assert!(duplicate.is_err());
+
+    repository
+        .update(cx, |repository, cx| repository.delete_tag("v2".into(), cx))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        tags(cx).await,
+        [("v1".to_string(), first.sha.to_string(), None)]
+    );
+
+    let push_tag = |name: &str, cx: &mut gpui::TestAppContext| {
+        let askpass = AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {});
+        repository.update(cx, |repository, cx| {
+            repository.push_tag(name.to_string().into(), "origin".into(), askpass, cx)
+        })
+    };
+    push_tag("v1", cx).await.unwrap().unwrap();
+    let missing = push_tag("v2", cx).await.unwrap();
+    assert!(missing.is_err());
+    let pushed_tags = fs
+        .with_git_state(Path::new(path!("/root/.git")), false, |state| {
+            state.pushed_tags.clone()
+        })
+        .unwrap();
+    assert_eq!(pushed_tags, [("origin".to_string(), "v1".to_string())]);
+}
+
 async fn search(
     project: &Entity<Project>,
     query: SearchQuery,
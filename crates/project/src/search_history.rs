@@ -64,6 +64,16 @@ impl SearchHistory {
         cursor.selection = Some(self.history.len() - 1);
     }
 
+    /// Replaces the history with the given queries, ordered from oldest to newest.
+    pub fn replace(&mut self, queries: impl IntoIterator<Item = String>) {
+        self.history = queries.into_iter().collect();
+        if let Some(max_history_len) = self.max_history_len {
+            while self.history.len() > max_history_len {
+                self.history.pop_front();
+            }
+        }
+    }
+
     pub fn next(&mut self, cursor: &mut SearchHistoryCursor) -> Option<&str> {
         let selected = cursor.selection?;
         let next_index = selected + 1;
@@ -14,7 +14,7 @@ use editor::{
     },
 };
 use file_icons::FileIcons;
-use git::status::GitSummary;
+use git::{SHORT_SHA_LENGTH, repository::Submodule, status::GitSummary};
 use git_ui::file_diff_view::FileDiffView;
 use gpui::{
     Action, AnyElement, App, AsyncWindowContext, Bounds, ClipboardItem, Context, CursorStyle,
@@ -188,6 +188,8 @@ struct EntryDetails {
     filename_text_color: Color,
     diagnostic_severity: Option<DiagnosticSeverity>,
     git_status: GitSummary,
+    /// The submodule checked out in this directory, if any.
+    submodule: Option<Submodule>,
     is_private: bool,
     worktree_id: WorktreeId,
     canonical_path: Option<Arc<Path>>,
@@ -458,6 +460,19 @@ fn get_item_color(is_sticky: bool, cx: &App) -> ItemColors {
     }
 }
 
+/// Describes the state of a submodule that needs attention, which is shown next to its directory.
+fn submodule_state_label(submodule: &Submodule) -> Option<(&'static str, Color)> {
+    if !submodule.is_initialized() {
+        Some(("uninitialized", Color::Muted))
+    } else if submodule.is_out_of_date() {
+        Some(("out of date", Color::VersionControlModified))
+    } else if submodule.is_dirty {
+        Some(("modified", Color::VersionControlModified))
+    } else {
+        None
+    }
+}
+
 impl ProjectPanel {
     fn new(
         workspace: &mut Workspace,
@@ -4140,6 +4155,18 @@ impl ProjectPanel {
             .canonical_path
             .as_ref()
             .map(|f| f.to_string_lossy().into_owned());
+        let submodule_state = details
+            .submodule
+            .as_ref()
+            .filter(|_| canonical_path.is_none())
+            .and_then(|submodule| {
+                let (label, color) = submodule_state_label(submodule)?;
+                let sha = submodule
+                    .recorded_sha
+                    .as_ref()
+                    .map(|sha| sha.chars().take(SHORT_SHA_LENGTH).collect::<String>());
+                Some((label, color, sha))
+            });
         let path_style = self.project.read(cx).path_style(cx);
         let path = details.path.clone();
         let path_for_external_paths = path.clone();
@@ -4538,6 +4565,27 @@ impl ProjectPanel {
                                 .into_any_element(),
                         )
                     })
+                    .when_some(submodule_state, |this, (label, color, sha)| {
+                        this.end_slot::<AnyElement>(
+                            div()
+                                .id("submodule_state")
+                                .pr_3()
+                                .tooltip(move |window, cx| {
+                                    Tooltip::with_meta(
+                                        "Submodule",
+                                        None,
+                                        match &sha {
+                                            Some(sha) => format!("Recorded at {sha}"),
+                                            None => "Not recorded in the index".to_string(),
+                                        },
+                                        window,
+                                        cx,
+                                    )
+                                })
+                                .child(Label::new(label).size(LabelSize::XSmall).color(color))
+                                .into_any_element(),
+                        )
+                    })
                     .child(if let Some(icon) = &icon {
                         if let Some((_, decoration_color)) =
                             entry_diagnostic_aware_icon_decoration_and_color(diagnostic_severity)
@@ -4865,6 +4913,19 @@ impl ProjectPanel {
             .as_ref()
             .is_some_and(|e| e.is_cut() && e.items().contains(&selection));
 
+        let submodule = if entry.is_dir() && !entry.path.is_empty() {
+            let project = self.project.read(cx);
+            project.git_store().read(cx).submodule_for_project_path(
+                &ProjectPath {
+                    worktree_id,
+                    path: entry.path.clone(),
+                },
+                cx,
+            )
+        } else {
+            None
+        };
+
         EntryDetails {
             filename,
             icon,
@@ -4882,6 +4943,7 @@ impl ProjectPanel {
             filename_text_color,
             diagnostic_severity,
             git_status,
+            submodule,
             is_private: entry.is_private,
             worktree_id,
             canonical_path: entry.canonical_path.clone(),
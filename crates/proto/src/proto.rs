@@ -95,6 +95,29 @@ messages!(
     (GetDocumentHighlightsResponse, Background),
     (GetDocumentSymbols, Background),
     (GetDocumentSymbolsResponse, Background),
+    (PrepareCallHierarchy, Background),
+    (PrepareCallHierarchyResponse, Background),
+    (GetIncomingCalls, Background),
+    (GetIncomingCallsResponse, Background),
+    (GetOutgoingCalls, Background),
+    (GetOutgoingCallsResponse, Background),
+    (PrepareTypeHierarchy, Background),
+    (PrepareTypeHierarchyResponse, Background),
+    (GetSupertypes, Background),
+    (GetSupertypesResponse, Background),
+    (GetSubtypes, Background),
+    (GetSubtypesResponse, Background),
+    (GetSemanticTokens, Background),
+    (GetSemanticTokensResponse, Background),
+    (RefreshSemanticTokens, Background),
+    (GetFoldingRanges, Background),
+    (GetFoldingRangesResponse, Background),
+    (GetSelectionRanges, Background),
+    (GetSelectionRangesResponse, Background),
+    (GetDocumentLinks, Background),
+    (GetDocumentLinksResponse, Background),
+    (ResolveDocumentLink, Background),
+    (ResolveDocumentLinkResponse, Background),
     (GetHover, Background),
     (GetHoverResponse, Background),
     (GetNotifications, Foreground),
@@ -288,6 +311,10 @@ messages!(
     (GitReset, Background),
     (GitCheckoutFiles, Background),
     (GitShow, Background),
+    (GitLog, Background),
+    (GitLogResponse, Background),
+    (GitFileHistory, Background),
+    (GitFileHistoryResponse, Background),
     (GitCommitDetails, Background),
     (SetIndexText, Background),
     (Push, Background),
@@ -298,6 +325,31 @@ messages!(
     (RemoteMessageResponse, Background),
     (AskPassRequest, Background),
     (AskPassResponse, Background),
+    (GitRebase, Background),
+    (GitEditorRequest, Background),
+    (GitEditorResponse, Background),
+    (GitCherryPick, Background),
+    (GitRevert, Background),
+    (GitGetTags, Background),
+    (GitTagsResponse, Background),
+    (GitCreateTag, Background),
+    (GitDeleteTag, Background),
+    (GitPushTag, Background),
+    (GitGetWorktrees, Background),
+    (GitWorktreesResponse, Background),
+    (GitCreateWorktree, Background),
+    (GitRemoveWorktree, Background),
+    (GitResolveDiffBase, Background),
+    (GitResolveDiffBaseResponse, Background),
+    (GitChangedPathsSince, Background),
+    (GitChangedPathsSinceResponse, Background),
+    (GitLoadTextAtCommit, Background),
+    (GitLoadTextAtCommitResponse, Background),
+    (GitSubmoduleOperation, Background),
+    (GitBisect, Background),
+    (GitBisectResponse, Background),
+    (GitBisectLog, Background),
+    (GitBisectLogResponse, Background),
     (GitCreateBranch, Background),
     (GitChangeBranch, Background),
     (GitRenameBranch, Background),
@@ -358,6 +410,18 @@ request_messages!(
     (GetImplementation, GetImplementationResponse),
     (GetDocumentHighlights, GetDocumentHighlightsResponse),
     (GetDocumentSymbols, GetDocumentSymbolsResponse),
+    (PrepareCallHierarchy, PrepareCallHierarchyResponse),
+    (GetIncomingCalls, GetIncomingCallsResponse),
+    (GetOutgoingCalls, GetOutgoingCallsResponse),
+    (PrepareTypeHierarchy, PrepareTypeHierarchyResponse),
+    (GetSupertypes, GetSupertypesResponse),
+    (GetSubtypes, GetSubtypesResponse),
+    (GetSemanticTokens, GetSemanticTokensResponse),
+    (RefreshSemanticTokens, Ack),
+    (GetFoldingRanges, GetFoldingRangesResponse),
+    (GetSelectionRanges, GetSelectionRangesResponse),
+    (GetDocumentLinks, GetDocumentLinksResponse),
+    (ResolveDocumentLink, ResolveDocumentLinkResponse),
     (GetHover, GetHoverResponse),
     (GetNotifications, GetNotificationsResponse),
     (GetProjectSymbols, GetProjectSymbolsResponse),
@@ -474,6 +538,8 @@ request_messages!(
     (InstallExtension, Ack),
     (RegisterBufferWithLanguageServers, Ack),
     (GitShow, GitCommitDetails),
+    (GitLog, GitLogResponse),
+    (GitFileHistory, GitFileHistoryResponse),
     (GitReset, Ack),
     (GitCheckoutFiles, Ack),
     (SetIndexText, Ack),
@@ -482,6 +548,23 @@ request_messages!(
     (GetRemotes, GetRemotesResponse),
     (Pull, RemoteMessageResponse),
     (AskPassRequest, AskPassResponse),
+    (GitRebase, RemoteMessageResponse),
+    (GitEditorRequest, GitEditorResponse),
+    (GitCherryPick, Ack),
+    (GitRevert, Ack),
+    (GitGetTags, GitTagsResponse),
+    (GitCreateTag, Ack),
+    (GitDeleteTag, Ack),
+    (GitPushTag, RemoteMessageResponse),
+    (GitGetWorktrees, GitWorktreesResponse),
+    (GitCreateWorktree, Ack),
+    (GitRemoveWorktree, Ack),
+    (GitResolveDiffBase, GitResolveDiffBaseResponse),
+    (GitChangedPathsSince, GitChangedPathsSinceResponse),
+    (GitLoadTextAtCommit, GitLoadTextAtCommitResponse),
+    (GitSubmoduleOperation, RemoteMessageResponse),
+    (GitBisect, GitBisectResponse),
+    (GitBisectLog, GitBisectLogResponse),
     (GitCreateBranch, Ack),
     (GitChangeBranch, Ack),
     (GitRenameBranch, Ack),
@@ -544,6 +627,18 @@ entity_messages!(
     GetImplementation,
     GetDocumentHighlights,
     GetDocumentSymbols,
+    PrepareCallHierarchy,
+    GetIncomingCalls,
+    GetOutgoingCalls,
+    PrepareTypeHierarchy,
+    GetSupertypes,
+    GetSubtypes,
+    GetSemanticTokens,
+    RefreshSemanticTokens,
+    GetFoldingRanges,
+    GetSelectionRanges,
+    GetDocumentLinks,
+    ResolveDocumentLink,
     GetHover,
     GetProjectSymbols,
     GetReferences,
@@ -628,6 +723,8 @@ entity_messages!(
     CancelLanguageServerWork,
     RegisterBufferWithLanguageServers,
     GitShow,
+    GitLog,
+    GitFileHistory,
     GitReset,
     GitCheckoutFiles,
     SetIndexText,
@@ -638,6 +735,23 @@ entity_messages!(
     GetRemotes,
     Pull,
     AskPassRequest,
+    GitRebase,
+    GitEditorRequest,
+    GitCherryPick,
+    GitRevert,
+    GitGetTags,
+    GitCreateTag,
+    GitDeleteTag,
+    GitPushTag,
+    GitGetWorktrees,
+    GitCreateWorktree,
+    GitRemoveWorktree,
+    GitResolveDiffBase,
+    GitChangedPathsSince,
+    GitLoadTextAtCommit,
+    GitSubmoduleOperation,
+    GitBisect,
+    GitBisectLog,
     GitChangeBranch,
     GitRenameBranch,
     GitCreateBranch,
@@ -834,6 +834,13 @@ impl BufferSearchBar {
         self.query_editor.read(cx).text(cx)
     }
 
+    /// Replaces the queries the history actions navigate through, for when the history is shared
+    /// with other search bars. The queries are ordered from oldest to newest.
+    pub fn set_search_history(&mut self, queries: impl IntoIterator<Item = String>) {
+        self.search_history.replace(queries);
+        self.search_history_cursor.reset();
+    }
+
     pub fn replacement(&self, cx: &mut App) -> String {
         self.replacement_editor.read(cx).text(cx)
     }
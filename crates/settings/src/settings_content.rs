@@ -56,6 +56,9 @@ pub struct SettingsContent {
 
     pub git_panel: Option<GitPanelSettingsContent>,
 
+    /// Configuration for the git log panel.
+    pub git_log_panel: Option<GitLogPanelSettingsContent>,
+
     pub tabs: Option<ItemSettingsContent>,
     pub tab_bar: Option<TabBarSettingsContent>,
 
@@ -105,6 +108,9 @@ pub struct SettingsContent {
 
     pub journal: Option<JournalSettingsContent>,
 
+    /// Configuration for the local history of files.
+    pub local_history: Option<LocalHistorySettingsContent>,
+
     /// A map of log scopes to the desired log level.
     /// Useful for filtering out noisy logs or enabling more verbose logging.
     ///
@@ -128,6 +134,9 @@ pub struct SettingsContent {
     /// Configuration for the Notification Panel
     pub notification_panel: Option<NotificationPanelSettingsContent>,
 
+    /// Configuration for the Hierarchy Panel
+    pub hierarchy_panel: Option<HierarchyPanelSettingsContent>,
+
     pub proxy: Option<String>,
 
     /// The URL of the Zed server to connect to.
@@ -135,6 +144,10 @@ pub struct SettingsContent {
 
     /// Configuration for session-related features
     pub session: Option<SessionSettingsContent>,
+
+    /// Configuration for spell checking.
+    pub spellcheck: Option<SpellcheckSettingsContent>,
+
     /// Control what info is collected by Zed.
     pub telemetry: Option<TelemetrySettingsContent>,
 
@@ -470,6 +483,23 @@ pub struct GitPanelSettingsContent {
     pub collapse_untracked_diff: Option<bool>,
 }
 
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema, MergeFrom, Debug)]
+pub struct GitLogPanelSettingsContent {
+    /// Whether to show the panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the panel.
+    ///
+    /// Default: right
+    pub dock: Option<DockPosition>,
+    /// Default width of the panel in pixels.
+    ///
+    /// Default: 480
+    pub default_width: Option<f32>,
+}
+
 #[derive(
     Default, Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq,
 )]
@@ -503,6 +533,23 @@ pub struct NotificationPanelSettingsContent {
     pub default_width: Option<f32>,
 }
 
+#[skip_serializing_none]
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, MergeFrom, Debug, PartialEq)]
+pub struct HierarchyPanelSettingsContent {
+    /// Whether to show the panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the panel.
+    ///
+    /// Default: right
+    pub dock: Option<DockPosition>,
+    /// Default width of the panel in pixels.
+    ///
+    /// Default: 300
+    pub default_width: Option<f32>,
+}
+
 #[skip_serializing_none]
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema, MergeFrom, Debug, PartialEq)]
 pub struct PanelSettingsContent {
@@ -585,6 +632,21 @@ pub struct VimSettingsContent {
     pub custom_digraphs: Option<HashMap<String, Arc<str>>>,
     pub highlight_on_yank_duration: Option<u64>,
     pub cursor_shape: Option<CursorShapeSettings>,
+    /// The path of a vimrc-style file with mappings, `:set` options and `let mapleader`
+    /// to load when Zed starts.
+    ///
+    /// Default: null
+    pub vimrc: Option<String>,
+    /// Abbreviations that are expanded in insert mode when a non-keyword character is typed
+    /// after them, like the ones created with `:iabbrev`.
+    ///
+    /// Default: {}
+    pub abbreviations: Option<HashMap<String, String>>,
+    /// Insert mode abbreviations that only apply to the language they're listed under, such as
+    /// `"Rust"`. They take precedence over the ones in `abbreviations`.
+    ///
+    /// Default: {}
+    pub language_abbreviations: Option<HashMap<String, HashMap<String, String>>>,
 }
 
 #[derive(Copy, Clone, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Debug)]
@@ -651,6 +713,53 @@ pub enum HourFormat {
     Hour24,
 }
 
+/// Settings for the local history of files.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
+pub struct LocalHistorySettingsContent {
+    /// Whether to record snapshots of files when they're saved, changed on disk or about to be
+    /// edited by an agent.
+    ///
+    /// Default: true
+    pub enabled: Option<bool>,
+    /// The maximum number of snapshots kept for each file.
+    ///
+    /// Default: 50
+    pub max_snapshots_per_file: Option<u32>,
+    /// The number of days after which snapshots are deleted.
+    ///
+    /// Default: 30
+    pub max_age_days: Option<u32>,
+    /// Files larger than this many bytes aren't recorded.
+    ///
+    /// Default: 1048576
+    pub max_file_size: Option<u64>,
+}
+
+/// Settings for spell checking comments, strings and prose.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
+pub struct SpellcheckSettingsContent {
+    /// Whether to check the spelling of comments, strings and prose.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// The name of the Hunspell dictionary to use, without the `.aff`/`.dic` extension.
+    ///
+    /// Default: "en_US"
+    pub dictionary: Option<String>,
+    /// Additional directories to look for Hunspell dictionaries in, before the
+    /// `dictionaries` directory in Zed's config directory and the system ones.
+    ///
+    /// Default: []
+    pub dictionary_paths: Option<Vec<String>>,
+    /// Words that are always spelled correctly, in addition to the ones listed
+    /// in a project's `.zed/dictionary.txt`.
+    ///
+    /// Default: []
+    pub words: Option<Vec<String>>,
+}
+
 #[skip_serializing_none]
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema, MergeFrom, Debug, PartialEq)]
 pub struct OutlinePanelSettingsContent {
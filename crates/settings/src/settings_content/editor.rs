@@ -187,6 +187,9 @@ pub struct EditorSettingsContent {
     /// Drag and drop related settings
     pub drag_and_drop_selection: Option<DragAndDropSelectionContent>,
 
+    /// Sticky scroll related settings
+    pub sticky_scroll: Option<StickyScrollContent>,
+
     /// How to render LSP `textDocument/documentColor` colors in the editor.
     ///
     /// Default: [`DocumentColorsRenderMode::Inlay`]
@@ -602,6 +605,22 @@ pub struct DragAndDropSelectionContent {
     pub delay: Option<u64>,
 }
 
+/// Whether to pin the headers of the scopes enclosing the top of the viewport.
+#[skip_serializing_none]
+#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq)]
+pub struct StickyScrollContent {
+    /// When true, the lines starting the functions, classes and other outline items
+    /// that enclose the first visible line are pinned to the top of the editor.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+
+    /// The maximum number of nested scopes to pin at the same time.
+    ///
+    /// Default: 5
+    pub max_depth: Option<usize>,
+}
+
 /// When to show the minimap in the editor.
 ///
 /// Default: never
@@ -302,6 +302,14 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: true
     pub linked_edits: Option<bool>,
+    /// How to use the semantic tokens provided by language servers for syntax highlighting.
+    ///
+    /// Default: off
+    pub semantic_tokens: Option<SemanticTokens>,
+    /// Where the foldable ranges shown in the gutter and used by fold actions come from.
+    ///
+    /// Default: indentation
+    pub folding_provider: Option<FoldingProvider>,
     /// Whether indentation should be adjusted based on the context whilst typing.
     ///
     /// Default: true
@@ -360,6 +368,38 @@ pub struct WhitespaceMapContent {
     pub tab: Option<char>,
 }
 
+/// How semantic tokens from language servers are used for syntax highlighting.
+#[derive(
+    Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, MergeFrom,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SemanticTokens {
+    /// Only use tree-sitter based highlighting.
+    #[default]
+    Off,
+    /// Layer semantic tokens on top of tree-sitter based highlighting.
+    Combined,
+    /// Only use semantic tokens, falling back to tree-sitter based highlighting
+    /// until a language server provides them.
+    Full,
+}
+
+/// Where foldable ranges come from.
+#[derive(
+    Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, MergeFrom,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum FoldingProvider {
+    /// Fold lines that are indented deeper than the line before them.
+    #[default]
+    Indentation,
+    /// Fold multi-line bracket pairs, as defined by the language's tree-sitter bracket queries.
+    TreeSitter,
+    /// Fold the ranges reported by the language server's `textDocument/foldingRange` response,
+    /// falling back to indentation until a language server provides them.
+    Lsp,
+}
+
 /// The behavior of `editor::Rewrap`.
 #[derive(Debug, PartialEq, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, MergeFrom)]
 #[serde(rename_all = "snake_case")]
@@ -0,0 +1,667 @@
+//! A Hunspell-compatible dictionary.
+//!
+//! This supports the parts of the `.aff` format that most dictionaries rely on for checking words
+//! and suggesting corrections: prefix and suffix rules with conditions, flag types and aliases,
+//! `TRY`, `REP`, `KEY`, `FORBIDDENWORD`, `NOSUGGEST` and `NEEDAFFIX`. Compound words aren't supported.
+use anyhow::{Context as _, Result, anyhow};
+use collections::{HashMap, HashSet, hash_map::Entry};
+
+type Flag = u32;
+
+/// How flags are encoded in a dictionary's `.aff` and `.dic` files.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum FlagType {
+    /// One ASCII character per flag.
+    #[default]
+    Short,
+    /// Two ASCII characters per flag.
+    Long,
+    /// Comma-separated decimal numbers.
+    Numeric,
+    /// One Unicode character per flag.
+    Utf8,
+}
+
+impl FlagType {
+    fn parse_flags(self, flags: &str) -> Result<Vec<Flag>> {
+        match self {
+            FlagType::Short | FlagType::Utf8 => {
+                Ok(flags.chars().map(|flag| flag as Flag).collect())
+            }
+            FlagType::Long => {
+                let chars = flags.chars().collect::<Vec<_>>();
+                anyhow::ensure!(
+                    chars.len() % 2 == 0,
+                    "long flags must have two characters: {flags:?}"
+                );
+                Ok(chars
+                    .chunks(2)
+                    .map(|pair| ((pair[0] as Flag) << 16) | pair[1] as Flag)
+                    .collect())
+            }
+            FlagType::Numeric => flags
+                .split(',')
+                .map(|flag| {
+                    flag.trim()
+                        .parse::<Flag>()
+                        .with_context(|| format!("invalid numeric flag: {flag:?}"))
+                })
+                .collect(),
+        }
+    }
+
+    fn parse_flag(self, flag: &str) -> Result<Flag> {
+        self.parse_flags(flag)?
+            .first()
+            .copied()
+            .ok_or_else(|| anyhow!("missing flag"))
+    }
+}
+
+/// The condition an affix rule puts on the stem it's applied to,
+/// a sequence of characters, `.` wildcards and `[...]` or `[^...]` character classes.
+#[derive(Debug)]
+struct Condition(Vec<ConditionAtom>);
+
+#[derive(Debug)]
+enum ConditionAtom {
+    Any,
+    Char(char),
+    Class { chars: Vec<char>, negated: bool },
+}
+
+impl ConditionAtom {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            ConditionAtom::Any => true,
+            ConditionAtom::Char(expected) => c == *expected,
+            ConditionAtom::Class { chars, negated } => chars.contains(&c) != *negated,
+        }
+    }
+}
+
+impl Condition {
+    fn parse(condition: &str) -> Result<Self> {
+        let mut atoms = Vec::new();
+        let mut chars = condition.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '.' => atoms.push(ConditionAtom::Any),
+                '[' => {
+                    let mut class = Vec::new();
+                    let mut negated = false;
+                    loop {
+                        match chars.next() {
+                            Some(']') => break,
+                            Some('^') if class.is_empty() && !negated => negated = true,
+                            Some(c) => class.push(c),
+                            None => anyhow::bail!("unterminated condition: {condition:?}"),
+                        }
+                    }
+                    atoms.push(ConditionAtom::Class {
+                        chars: class,
+                        negated,
+                    });
+                }
+                c => atoms.push(ConditionAtom::Char(c)),
+            }
+        }
+        Ok(Self(atoms))
+    }
+
+    fn matches_start(&self, word: &str) -> bool {
+        let mut chars = word.chars();
+        self.0
+            .iter()
+            .all(|atom| chars.next().is_some_and(|c| atom.matches(c)))
+    }
+
+    fn matches_end(&self, word: &str) -> bool {
+        let mut chars = word.chars().rev();
+        self.0
+            .iter()
+            .rev()
+            .all(|atom| chars.next().is_some_and(|c| atom.matches(c)))
+    }
+}
+
+#[derive(Debug)]
+struct Affix {
+    flag: Flag,
+    cross_product: bool,
+    strip: String,
+    condition: Condition,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Casing {
+    Lower,
+    Title,
+    Upper,
+    Mixed,
+}
+
+fn casing(word: &str) -> Casing {
+    let mut chars = word.chars().filter(|c| c.is_alphabetic());
+    let Some(first) = chars.next() else {
+        return Casing::Lower;
+    };
+    let (mut upper, mut lower) = (0, 0);
+    for c in chars {
+        if c.is_uppercase() {
+            upper += 1;
+        } else if c.is_lowercase() {
+            lower += 1;
+        }
+    }
+    match (first.is_uppercase(), upper, lower) {
+        (false, 0, _) => Casing::Lower,
+        (true, 0, _) => Casing::Title,
+        (true, _, 0) => Casing::Upper,
+        _ => Casing::Mixed,
+    }
+}
+
+fn to_title_case(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+fn apply_casing(word: String, casing: Casing) -> String {
+    match casing {
+        Casing::Title if self::casing(&word) == Casing::Lower => to_title_case(&word),
+        Casing::Upper => word.to_uppercase(),
+        _ => word,
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    /// The flags of every root word. A word may have several entries with different flags.
+    words: HashMap<String, Vec<Box<[Flag]>>>,
+    /// Prefix rules, keyed by the text they add.
+    prefixes: HashMap<String, Vec<Affix>>,
+    /// Suffix rules, keyed by the text they add.
+    suffixes: HashMap<String, Vec<Affix>>,
+    try_chars: Vec<char>,
+    replacements: Vec<(String, String)>,
+    keyboard_groups: Vec<Vec<char>>,
+    forbidden_flag: Option<Flag>,
+    no_suggest_flag: Option<Flag>,
+    need_affix_flag: Option<Flag>,
+    only_in_compound_flag: Option<Flag>,
+}
+
+impl Dictionary {
+    /// Parses a dictionary from the contents of its `.aff` and `.dic` files.
+    pub fn new(aff: &str, dic: &str) -> Result<Self> {
+        let mut dictionary = Self::default();
+        let format = dictionary.parse_aff(aff).context("parsing .aff file")?;
+        dictionary
+            .parse_dic(dic, &format)
+            .context("parsing .dic file")?;
+        Ok(dictionary)
+    }
+
+    /// Decodes the contents of a dictionary file, which aren't necessarily UTF-8.
+    pub fn decode(bytes: &[u8]) -> String {
+        match std::str::from_utf8(bytes) {
+            Ok(text) => text.trim_start_matches('\u{feff}').to_string(),
+            // Legacy dictionaries are mostly ISO-8859-1, which maps bytes to the same code points.
+            Err(_) => bytes.iter().map(|byte| *byte as char).collect(),
+        }
+    }
+
+    fn parse_aff(&mut self, aff: &str) -> Result<DicFormat> {
+        let mut format = DicFormat::default();
+        let mut seen_tables = HashSet::default();
+        // Whether the affix rules of each flag can be combined with affixes of the other kind.
+        let mut cross_products = HashMap::default();
+
+        for (line_ix, line) in aff.lines().enumerate() {
+            let mut tokens = line.split_whitespace();
+            let Some(tag) = tokens.next() else {
+                continue;
+            };
+            let args = tokens.collect::<Vec<_>>();
+            let first_arg = || {
+                args.first()
+                    .copied()
+                    .with_context(|| format!("missing argument on line {}", line_ix + 1))
+            };
+            match tag {
+                "FLAG" => {
+                    format.flag_type = match first_arg()? {
+                        "long" => FlagType::Long,
+                        "num" => FlagType::Numeric,
+                        "UTF-8" => FlagType::Utf8,
+                        other => anyhow::bail!("unknown flag type {other:?}"),
+                    }
+                }
+                "TRY" => self.try_chars = first_arg()?.chars().collect(),
+                "KEY" => {
+                    self.keyboard_groups = first_arg()?
+                        .split('|')
+                        .map(|group| group.chars().collect())
+                        .collect()
+                }
+                "FORBIDDENWORD" => {
+                    self.forbidden_flag = Some(format.flag_type.parse_flag(first_arg()?)?)
+                }
+                "NOSUGGEST" => {
+                    self.no_suggest_flag = Some(format.flag_type.parse_flag(first_arg()?)?)
+                }
+                "NEEDAFFIX" | "PSEUDOROOT" => {
+                    self.need_affix_flag = Some(format.flag_type.parse_flag(first_arg()?)?)
+                }
+                "ONLYINCOMPOUND" => {
+                    self.only_in_compound_flag = Some(format.flag_type.parse_flag(first_arg()?)?)
+                }
+                // The first line of `REP` and `AF` tables holds the number of entries.
+                "REP" => {
+                    if seen_tables.insert(tag) {
+                        continue;
+                    }
+                    let (Some(from), Some(to)) = (args.first(), args.get(1)) else {
+                        anyhow::bail!("invalid replacement on line {}", line_ix + 1);
+                    };
+                    self.replacements
+                        .push((from.replace('_', " "), to.replace('_', " ")));
+                }
+                "AF" => {
+                    if seen_tables.insert(tag) {
+                        continue;
+                    }
+                    format
+                        .aliases
+                        .push(format.flag_type.parse_flags(first_arg()?)?);
+                }
+                "PFX" | "SFX" => {
+                    let flag = format.flag_type.parse_flag(first_arg()?)?;
+                    let is_prefix = tag == "PFX";
+                    if let Entry::Vacant(entry) = cross_products.entry((is_prefix, flag)) {
+                        entry.insert(args.get(1).copied() == Some("Y"));
+                        continue;
+                    }
+                    let (Some(strip), Some(add)) = (args.get(1), args.get(2)) else {
+                        anyhow::bail!("invalid affix rule on line {}", line_ix + 1);
+                    };
+                    let strip = if *strip == "0" { "" } else { strip };
+                    // Continuation flags after the added text aren't supported.
+                    let add = add.split('/').next().unwrap_or_default();
+                    let add = if add == "0" { "" } else { add };
+                    let affix = Affix {
+                        flag,
+                        cross_product: cross_products[&(is_prefix, flag)],
+                        strip: strip.to_string(),
+                        condition: Condition::parse(args.get(3).copied().unwrap_or("."))?,
+                    };
+                    let affixes = if is_prefix {
+                        &mut self.prefixes
+                    } else {
+                        &mut self.suffixes
+                    };
+                    affixes.entry(add.to_string()).or_default().push(affix);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(format)
+    }
+
+    fn parse_dic(&mut self, dic: &str, format: &DicFormat) -> Result<()> {
+        let mut lines = dic.lines().peekable();
+        // The first line is the approximate number of words.
+        if lines
+            .peek()
+            .is_some_and(|line| line.trim().parse::<usize>().is_ok())
+        {
+            lines.next();
+        }
+
+        for line in lines {
+            let Some(entry) = line.split_whitespace().next() else {
+                continue;
+            };
+            let (word, flags) = split_dic_entry(entry);
+            if word.is_empty() {
+                continue;
+            }
+            let flags = match flags {
+                Some(flags) if !format.aliases.is_empty() => flags
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|ix| format.aliases.get(ix.checked_sub(1)?))
+                    .cloned()
+                    .with_context(|| format!("unknown flag alias {flags:?}"))?,
+                Some(flags) => format.flag_type.parse_flags(flags)?,
+                None => Vec::new(),
+            };
+            self.words
+                .entry(word)
+                .or_default()
+                .push(flags.into_boxed_slice());
+        }
+        Ok(())
+    }
+
+    /// Returns whether the word is spelled correctly.
+    ///
+    /// Capitalized and upper case words are also accepted when their lower case form is in the dictionary.
+    pub fn check(&self, word: &str) -> bool {
+        if self.lookup(word, false) {
+            return true;
+        }
+        match casing(word) {
+            Casing::Title => self.lookup(&word.to_lowercase(), false),
+            Casing::Upper => {
+                self.lookup(&word.to_lowercase(), false) || self.lookup(&to_title_case(word), false)
+            }
+            Casing::Lower | Casing::Mixed => false,
+        }
+    }
+
+    /// Returns up to `limit` correctly spelled words similar to the given one, best matches first.
+    pub fn suggest(&self, word: &str, limit: usize) -> Vec<String> {
+        let casing = casing(word);
+        let word = match casing {
+            Casing::Title | Casing::Upper => word.to_lowercase(),
+            Casing::Lower | Casing::Mixed => word.to_string(),
+        };
+        let chars = word.chars().collect::<Vec<_>>();
+        let try_chars = if self.try_chars.is_empty() {
+            ('a'..='z').collect()
+        } else {
+            self.try_chars.clone()
+        };
+
+        let mut suggestions = Vec::new();
+        let mut seen = HashSet::default();
+        let mut add_candidate = |candidate: String| {
+            if suggestions.len() >= limit || !seen.insert(candidate.clone()) {
+                return;
+            }
+            if self.lookup(&candidate, true) {
+                suggestions.push(candidate);
+            } else {
+                // Suggest proper nouns, e.g. "paris" -> "Paris".
+                let title_case = to_title_case(&candidate);
+                if title_case != candidate
+                    && seen.insert(title_case.clone())
+                    && self.lookup(&title_case, true)
+                {
+                    suggestions.push(title_case);
+                }
+            }
+        };
+
+        add_candidate(word.clone());
+        for (from, to) in &self.replacements {
+            for (ix, _) in word.match_indices(from.as_str()) {
+                add_candidate(format!("{}{to}{}", &word[..ix], &word[ix + from.len()..]));
+            }
+        }
+        for ix in 0..chars.len() {
+            for group in &self.keyboard_groups {
+                if let Some(key_ix) = group.iter().position(|c| *c == chars[ix]) {
+                    for neighbor in [key_ix.checked_sub(1), Some(key_ix + 1)] {
+                        if let Some(neighbor) = neighbor.and_then(|ix| group.get(ix)) {
+                            let mut candidate = chars.clone();
+                            candidate[ix] = *neighbor;
+                            add_candidate(candidate.into_iter().collect());
+                        }
+                    }
+                }
+            }
+        }
+        for ix in 1..chars.len() {
+            let mut candidate = chars.clone();
+            candidate.swap(ix - 1, ix);
+            add_candidate(candidate.into_iter().collect());
+        }
+        for ix in 0..chars.len() {
+            let mut candidate = chars.clone();
+            candidate.remove(ix);
+            add_candidate(candidate.into_iter().collect());
+        }
+        for ix in 0..chars.len() {
+            for c in &try_chars {
+                if *c != chars[ix] {
+                    let mut candidate = chars.clone();
+                    candidate[ix] = *c;
+                    add_candidate(candidate.into_iter().collect());
+                }
+            }
+        }
+        for ix in 0..=chars.len() {
+            for c in &try_chars {
+                let mut candidate = chars.clone();
+                candidate.insert(ix, *c);
+                add_candidate(candidate.into_iter().collect());
+            }
+        }
+        for ix in 1..chars.len() {
+            let (first, second) = chars.split_at(ix);
+            let first = first.iter().collect::<String>();
+            let second = second.iter().collect::<String>();
+            if self.lookup(&first, true) && self.lookup(&second, true) {
+                add_candidate(format!("{first} {second}"));
+            }
+        }
+        drop(add_candidate);
+
+        suggestions
+            .into_iter()
+            .map(|suggestion| apply_casing(suggestion, casing))
+            .collect()
+    }
+
+    /// Returns whether the word, or each word of a phrase, is in the dictionary or can be derived
+    /// from one of its words with affix rules.
+    fn lookup(&self, word: &str, for_suggestion: bool) -> bool {
+        if word.contains(' ') {
+            return word
+                .split(' ')
+                .all(|word| !word.is_empty() && self.lookup(word, for_suggestion));
+        }
+        if let Some(entries) = self.words.get(word) {
+            if entries
+                .iter()
+                .any(|flags| self.has_flag(flags, self.forbidden_flag))
+            {
+                return false;
+            }
+            if entries
+                .iter()
+                .any(|flags| self.is_valid_root(flags, for_suggestion))
+            {
+                return true;
+            }
+        }
+        self.lookup_suffixed(word, None, for_suggestion)
+            || self.lookup_prefixed(word, for_suggestion)
+    }
+
+    fn lookup_suffixed(&self, word: &str, prefix: Option<&Affix>, for_suggestion: bool) -> bool {
+        for (split_ix, _) in word.char_indices().skip(1).chain([(word.len(), ' ')]) {
+            let Some(affixes) = self.suffixes.get(&word[split_ix..]) else {
+                continue;
+            };
+            for affix in affixes {
+                if prefix.is_some() && !affix.cross_product {
+                    continue;
+                }
+                let stem = format!("{}{}", &word[..split_ix], affix.strip);
+                if !affix.condition.matches_end(&stem) {
+                    continue;
+                }
+                let Some(entries) = self.words.get(&stem) else {
+                    continue;
+                };
+                if entries.iter().any(|flags| {
+                    flags.contains(&affix.flag)
+                        && prefix.is_none_or(|prefix| flags.contains(&prefix.flag))
+                        && self.is_valid_affixed_root(flags, for_suggestion)
+                }) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn lookup_prefixed(&self, word: &str, for_suggestion: bool) -> bool {
+        for (split_ix, _) in word.char_indices().skip(1) {
+            let Some(affixes) = self.prefixes.get(&word[..split_ix]) else {
+                continue;
+            };
+            for affix in affixes {
+                let stem = format!("{}{}", affix.strip, &word[split_ix..]);
+                if !affix.condition.matches_start(&stem) {
+                    continue;
+                }
+                if self.words.get(&stem).is_some_and(|entries| {
+                    entries.iter().any(|flags| {
+                        flags.contains(&affix.flag)
+                            && self.is_valid_affixed_root(flags, for_suggestion)
+                    })
+                }) {
+                    return true;
+                }
+                if affix.cross_product && self.lookup_suffixed(&stem, Some(affix), for_suggestion) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn has_flag(&self, flags: &[Flag], flag: Option<Flag>) -> bool {
+        flag.is_some_and(|flag| flags.contains(&flag))
+    }
+
+    fn is_valid_affixed_root(&self, flags: &[Flag], for_suggestion: bool) -> bool {
+        !self.has_flag(flags, self.forbidden_flag)
+            && !self.has_flag(flags, self.only_in_compound_flag)
+            && !(for_suggestion && self.has_flag(flags, self.no_suggest_flag))
+    }
+
+    fn is_valid_root(&self, flags: &[Flag], for_suggestion: bool) -> bool {
+        self.is_valid_affixed_root(flags, for_suggestion)
+            && !self.has_flag(flags, self.need_affix_flag)
+    }
+}
+
+/// What's needed from the `.aff` file to parse the flags of `.dic` entries.
+#[derive(Default)]
+struct DicFormat {
+    flag_type: FlagType,
+    /// Flag sets that `.dic` entries refer to by their 1-based index, declared with `AF`.
+    aliases: Vec<Vec<Flag>>,
+}
+
+/// Splits a `.dic` entry into its word and flags, at the first `/` that isn't escaped.
+fn split_dic_entry(entry: &str) -> (String, Option<&str>) {
+    let mut word = String::new();
+    let mut chars = entry.char_indices();
+    while let Some((ix, c)) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some((_, escaped)) = chars.next() {
+                    word.push(escaped);
+                }
+            }
+            '/' => return (word, Some(&entry[ix + 1..])),
+            c => word.push(c),
+        }
+    }
+    (word, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFF: &str = "
+SET UTF-8
+TRY esianrtolcdugmphbyfvkwzESIANRTOLCDUGMPHBYFVKWZ'
+NOSUGGEST !
+FORBIDDENWORD *
+
+REP 2
+REP f ph
+REP ph f
+
+PFX U Y 1
+PFX U   0     un         .
+
+SFX S Y 4
+SFX S   y     ies        [^aeiou]y
+SFX S   0     s          [aeiou]y
+SFX S   0     es         [sxzh]
+SFX S   0     s          [^sxzhy]
+
+SFX D Y 2
+SFX D   0     d          e
+SFX D   0     ed         [^e]
+";
+
+    const DIC: &str = "7
+hello/S
+city/S
+box/S
+lock/USD
+phone/S
+Paris
+damn/!
+";
+
+    #[test]
+    fn test_check() {
+        let dictionary = Dictionary::new(AFF, DIC).unwrap();
+        for word in [
+            "hello", "hellos", "cities", "boxes", "unlocked", "unlocks", "Hello", "HELLO", "Paris",
+            "PARIS", "damn",
+        ] {
+            assert!(
+                dictionary.check(word),
+                "{word:?} should be spelled correctly"
+            );
+        }
+        for word in [
+            "helo", "citys", "boxs", "unhello", "lockd", "paris", "hELLO",
+        ] {
+            assert!(!dictionary.check(word), "{word:?} should be misspelled");
+        }
+    }
+
+    #[test]
+    fn test_suggest() {
+        let dictionary = Dictionary::new(AFF, DIC).unwrap();
+        assert_eq!(dictionary.suggest("helo", 3), ["hello"]);
+        assert_eq!(dictionary.suggest("fone", 3), ["phone"]);
+        assert_eq!(dictionary.suggest("Citys", 3), ["City"]);
+        assert_eq!(dictionary.suggest("paris", 3), ["Paris"]);
+        assert_eq!(dictionary.suggest("boxcity", 3), ["box city"]);
+        // Words marked with `NOSUGGEST` are accepted but never suggested.
+        assert_eq!(dictionary.suggest("damm", 3), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_flag_types() {
+        let aff = "FLAG long\nSFX Aa Y 1\nSFX Aa 0 s .\n";
+        let dictionary = Dictionary::new(aff, "1\nword/AaBb\n").unwrap();
+        assert!(dictionary.check("words"));
+
+        let aff = "FLAG num\nAF 1\nAF 10,20\nSFX 10 Y 1\nSFX 10 0 s .\n";
+        let dictionary = Dictionary::new(aff, "1\nword/1\n").unwrap();
+        assert!(dictionary.check("words"));
+    }
+}
@@ -0,0 +1,979 @@
+mod dictionary;
+
+use std::{ops::Range, path::PathBuf, rc::Rc, sync::Arc, time::Duration};
+
+use anyhow::{Context as _, Result, anyhow};
+use collections::{HashMap, HashSet};
+use editor::{CodeActionProvider, Editor, EditorEvent, ExcerptId};
+use fs::Fs;
+use gpui::{App, AppContext as _, Context, Entity, Global, Subscription, Task, WeakEntity, Window};
+use language::{
+    Buffer, BufferEvent, BufferSnapshot, Diagnostic, DiagnosticEntry, DiagnosticSourceKind,
+    LanguageServerId, Point,
+};
+use lsp::DiagnosticSeverity;
+use project::{CodeAction, LspAction, LspStore, ProjectTransaction};
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsStore};
+use text::{BufferId, OffsetRangeExt as _};
+use util::ResultExt as _;
+
+pub use dictionary::Dictionary;
+
+/// The `source` of spelling diagnostics.
+pub const DIAGNOSTIC_SOURCE: &str = "spellcheck";
+
+const CHECK_DEBOUNCE: Duration = Duration::from_millis(500);
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Syntax scopes (from a language's `overrides.scm`) whose text is checked.
+const CHECKED_SCOPES: &[&str] = &["comment", "string"];
+/// Languages whose text is checked everywhere outside of their syntax scopes.
+const PROSE_LANGUAGES: &[&str] = &["Markdown", "Markdown-Inline", "Plain Text", "Git Commit"];
+
+#[cfg(target_os = "macos")]
+const SYSTEM_DICTIONARY_DIRECTORIES: &[&str] = &["/Library/Spelling"];
+#[cfg(target_os = "windows")]
+const SYSTEM_DICTIONARY_DIRECTORIES: &[&str] = &[];
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const SYSTEM_DICTIONARY_DIRECTORIES: &[&str] = &[
+    "/usr/share/hunspell",
+    "/usr/local/share/hunspell",
+    "/usr/share/myspell",
+    "/usr/share/myspell/dicts",
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpellcheckSettings {
+    pub enabled: bool,
+    pub dictionary: String,
+    pub dictionary_paths: Vec<String>,
+    pub words: Vec<String>,
+}
+
+impl Settings for SpellcheckSettings {
+    fn from_settings(content: &settings::SettingsContent, _cx: &mut App) -> Self {
+        let spellcheck = content.spellcheck.clone().unwrap();
+
+        Self {
+            enabled: spellcheck.enabled.unwrap(),
+            dictionary: spellcheck.dictionary.unwrap(),
+            dictionary_paths: spellcheck.dictionary_paths.unwrap(),
+            words: spellcheck.words.unwrap(),
+        }
+    }
+}
+
+/// Sets up spell checking, which reports its diagnostics to the project under `server_id`.
+pub fn init(server_id: LanguageServerId, cx: &mut App) {
+    SpellcheckSettings::register(cx);
+
+    let fs = <dyn Fs>::global(cx);
+    let spellchecker = cx.new(|cx| Spellchecker::new(server_id, fs, cx));
+    cx.set_global(GlobalSpellchecker(spellchecker));
+
+    cx.observe_new(|editor: &mut Editor, window, cx: &mut Context<Editor>| {
+        let Some(window) = window else {
+            return;
+        };
+        if !editor.mode().is_full() {
+            return;
+        }
+        let Some(project) = editor
+            .project()
+            .filter(|project| project.read(cx).is_local())
+        else {
+            return;
+        };
+        let Some(spellchecker) = Spellchecker::global(cx) else {
+            return;
+        };
+
+        let lsp_store = project.read(cx).lsp_store();
+        editor.add_code_action_provider(Rc::new(SpellcheckCodeActionProvider), window, cx);
+        for buffer in editor.buffer().read(cx).all_buffers() {
+            spellchecker.update(cx, |spellchecker, cx| {
+                spellchecker.register_buffer(&buffer, &lsp_store, cx)
+            });
+        }
+        cx.subscribe_self(move |_, event: &EditorEvent, cx| {
+            if let EditorEvent::ExcerptsAdded { buffer, .. } = event {
+                spellchecker.update(cx, |spellchecker, cx| {
+                    spellchecker.register_buffer(buffer, &lsp_store, cx)
+                });
+            }
+        })
+        .detach();
+    })
+    .detach();
+}
+
+struct GlobalSpellchecker(Entity<Spellchecker>);
+
+impl Global for GlobalSpellchecker {}
+
+/// Checks the spelling of the comments, strings and prose of the buffers open in local projects,
+/// reporting misspelled words as diagnostics.
+pub struct Spellchecker {
+    server_id: LanguageServerId,
+    fs: Arc<dyn Fs>,
+    settings: SpellcheckSettings,
+    dictionary: Option<Arc<Dictionary>>,
+    load_dictionary_task: Task<()>,
+    buffers: HashMap<BufferId, SpellcheckedBuffer>,
+    _settings_subscription: Subscription,
+}
+
+struct SpellcheckedBuffer {
+    buffer: WeakEntity<Buffer>,
+    lsp_store: WeakEntity<LspStore>,
+    /// The buffer version that `diagnostics` were found in, or `None` if the whole buffer needs
+    /// to be checked. Otherwise, only the lines edited since then are checked again.
+    checked_version: Option<clock::Global>,
+    diagnostics: Vec<DiagnosticEntry<text::Anchor>>,
+    check_task: Task<()>,
+    _subscriptions: [Subscription; 2],
+}
+
+impl SpellcheckedBuffer {
+    fn report_diagnostics(
+        &self,
+        server_id: LanguageServerId,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) {
+        self.lsp_store
+            .update(cx, |lsp_store, cx| {
+                lsp_store.update_buffer_diagnostics_from_source(
+                    server_id,
+                    buffer,
+                    self.diagnostics.clone(),
+                    cx,
+                )
+            })
+            .and_then(|result| result)
+            .log_err();
+    }
+}
+
+impl Spellchecker {
+    pub fn global(cx: &App) -> Option<Entity<Self>> {
+        cx.try_global::<GlobalSpellchecker>()
+            .map(|spellchecker| spellchecker.0.clone())
+    }
+
+    fn new(server_id: LanguageServerId, fs: Arc<dyn Fs>, cx: &mut Context<Self>) -> Self {
+        let mut this = Self {
+            server_id,
+            fs,
+            settings: SpellcheckSettings::get_global(cx).clone(),
+            dictionary: None,
+            load_dictionary_task: Task::ready(()),
+            buffers: HashMap::default(),
+            _settings_subscription: cx.observe_global::<SettingsStore>(Self::settings_changed),
+        };
+        this.load_dictionary(cx);
+        this
+    }
+
+    pub fn dictionary(&self) -> Option<&Arc<Dictionary>> {
+        self.dictionary.as_ref()
+    }
+
+    fn settings_changed(&mut self, cx: &mut Context<Self>) {
+        let settings = SpellcheckSettings::get_global(cx).clone();
+        if settings == self.settings {
+            return;
+        }
+
+        let dictionary_changed = settings.enabled != self.settings.enabled
+            || settings.dictionary != self.settings.dictionary
+            || settings.dictionary_paths != self.settings.dictionary_paths;
+        self.settings = settings;
+        if dictionary_changed {
+            self.load_dictionary(cx);
+        } else {
+            self.check_all_buffers(cx);
+        }
+    }
+
+    fn load_dictionary(&mut self, cx: &mut Context<Self>) {
+        self.dictionary = None;
+        if !self.settings.enabled {
+            self.load_dictionary_task = Task::ready(());
+            self.check_all_buffers(cx);
+            return;
+        }
+
+        let fs = self.fs.clone();
+        let name = self.settings.dictionary.clone();
+        let directories = dictionary_directories(&self.settings);
+        self.load_dictionary_task = cx.spawn(async move |this, cx| {
+            let dictionary =
+                match load_dictionary(fs.as_ref(), &name, &directories, cx.background_executor())
+                    .await
+                {
+                    Ok(dictionary) => Some(Arc::new(dictionary)),
+                    Err(error) => {
+                        log::error!("failed to load the {name:?} dictionary: {error:#}");
+                        None
+                    }
+                };
+            this.update(cx, |this, cx| {
+                this.dictionary = dictionary;
+                this.check_all_buffers(cx);
+            })
+            .ok();
+        });
+    }
+
+    /// Starts checking the spelling of the given buffer, and re-checks it whenever it changes.
+    pub fn register_buffer(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        lsp_store: &Entity<LspStore>,
+        cx: &mut Context<Self>,
+    ) {
+        let buffer_id = buffer.read(cx).remote_id();
+        // The project tracks diagnostics by path, so buffers without a file aren't checked.
+        if self.buffers.contains_key(&buffer_id)
+            || project::File::from_dyn(buffer.read(cx).file()).is_none()
+        {
+            return;
+        }
+
+        let subscriptions = [
+            cx.subscribe(buffer, move |this, _, event, cx| match event {
+                BufferEvent::Edited | BufferEvent::Reparsed => {
+                    this.schedule_check(buffer_id, CHECK_DEBOUNCE, cx)
+                }
+                BufferEvent::LanguageChanged => {
+                    if let Some(spellchecked_buffer) = this.buffers.get_mut(&buffer_id) {
+                        spellchecked_buffer.checked_version = None;
+                    }
+                    this.schedule_check(buffer_id, CHECK_DEBOUNCE, cx);
+                }
+                _ => {}
+            }),
+            cx.observe_release(buffer, move |this, _, _| {
+                this.buffers.remove(&buffer_id);
+            }),
+        ];
+        self.buffers.insert(
+            buffer_id,
+            SpellcheckedBuffer {
+                buffer: buffer.downgrade(),
+                lsp_store: lsp_store.downgrade(),
+                checked_version: None,
+                diagnostics: Vec::new(),
+                check_task: Task::ready(()),
+                _subscriptions: subscriptions,
+            },
+        );
+        self.schedule_check(buffer_id, Duration::ZERO, cx);
+    }
+
+    fn check_all_buffers(&mut self, cx: &mut Context<Self>) {
+        let buffer_ids = self.buffers.keys().copied().collect::<Vec<_>>();
+        for buffer_id in buffer_ids {
+            if let Some(spellchecked_buffer) = self.buffers.get_mut(&buffer_id) {
+                spellchecked_buffer.checked_version = None;
+            }
+            self.schedule_check(buffer_id, Duration::ZERO, cx);
+        }
+    }
+
+    fn schedule_check(&mut self, buffer_id: BufferId, delay: Duration, cx: &mut Context<Self>) {
+        let server_id = self.server_id;
+        let Some(spellchecked_buffer) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let Some(buffer) = spellchecked_buffer.buffer.upgrade() else {
+            return;
+        };
+        let Some(dictionary) = self.dictionary.clone().filter(|_| self.settings.enabled) else {
+            spellchecked_buffer.check_task = Task::ready(());
+            spellchecked_buffer.checked_version = None;
+            if !spellchecked_buffer.diagnostics.is_empty() {
+                spellchecked_buffer.diagnostics.clear();
+                spellchecked_buffer.report_diagnostics(server_id, &buffer, cx);
+            }
+            return;
+        };
+
+        let fs = self.fs.clone();
+        let project_dictionary_path = project_dictionary_path(buffer.read(cx), cx);
+        let user_words = self.settings.words.clone();
+        let checked_version = spellchecked_buffer.checked_version.clone();
+        let old_diagnostics = spellchecked_buffer.diagnostics.clone();
+        let buffer = spellchecked_buffer.buffer.clone();
+        spellchecked_buffer.check_task = cx.spawn(async move |this, cx| {
+            if !delay.is_zero() {
+                cx.background_executor().timer(delay).await;
+            }
+
+            let mut accepted_words = user_words
+                .iter()
+                .map(|word| word.to_lowercase())
+                .collect::<HashSet<_>>();
+            if let Some(path) = project_dictionary_path
+                && fs.is_file(&path).await
+                && let Ok(text) = fs.load(&path).await
+            {
+                accepted_words.extend(parse_word_list(&text));
+            }
+
+            let Ok((snapshot, is_parsing)) =
+                buffer.read_with(cx, |buffer, _| (buffer.snapshot(), buffer.is_parsing()))
+            else {
+                return;
+            };
+            let version = snapshot.version().clone();
+            let checked_ranges = match &checked_version {
+                Some(checked_version) => edited_lines(&snapshot, checked_version),
+                None => vec![0..snapshot.len()],
+            };
+            if checked_ranges.is_empty() {
+                return;
+            }
+
+            let diagnostics = cx
+                .background_spawn(async move {
+                    let mut misspellings = old_diagnostics
+                        .iter()
+                        .map(|entry| entry.range.to_offset(&snapshot))
+                        .filter(|range| {
+                            !range.is_empty()
+                                && !checked_ranges.iter().any(|checked_range| {
+                                    range.start <= checked_range.end
+                                        && checked_range.start <= range.end
+                                })
+                        })
+                        .collect::<Vec<_>>();
+                    for range in checked_ranges {
+                        misspellings.extend(misspelled_words(
+                            &snapshot,
+                            range,
+                            &dictionary,
+                            &accepted_words,
+                        ));
+                    }
+                    misspellings.sort_unstable_by_key(|range| range.start);
+
+                    misspellings
+                        .into_iter()
+                        .enumerate()
+                        .map(|(group_id, range)| {
+                            let word = snapshot.text_for_range(range.clone()).collect::<String>();
+                            DiagnosticEntry {
+                                range: snapshot.anchor_before(range.start)
+                                    ..snapshot.anchor_after(range.end),
+                                diagnostic: Diagnostic {
+                                    source: Some(DIAGNOSTIC_SOURCE.to_string()),
+                                    source_kind: DiagnosticSourceKind::Other,
+                                    severity: DiagnosticSeverity::INFORMATION,
+                                    message: format!("Unknown word: \"{word}\""),
+                                    group_id,
+                                    is_primary: true,
+                                    ..Diagnostic::default()
+                                },
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .await;
+            this.update(cx, |this, cx| {
+                let Some(spellchecked_buffer) = this.buffers.get_mut(&buffer_id) else {
+                    return;
+                };
+                let Some(buffer) = spellchecked_buffer.buffer.upgrade() else {
+                    return;
+                };
+                // The edited lines are checked again once the buffer is parsed, as their syntax
+                // scopes may have changed.
+                if !is_parsing {
+                    spellchecked_buffer.checked_version = Some(version);
+                }
+                spellchecked_buffer.diagnostics = diagnostics;
+                spellchecked_buffer.report_diagnostics(server_id, &buffer, cx);
+            })
+            .ok();
+        });
+    }
+
+    /// Adds a word to the `.zed/dictionary.txt` of the worktree containing the given buffer.
+    pub fn add_to_project_dictionary(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        word: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let Some(path) = project_dictionary_path(buffer.read(cx), cx) else {
+            return Task::ready(Err(anyhow!("the buffer isn't part of a project directory")));
+        };
+        let fs = self.fs.clone();
+        cx.spawn(async move |this, cx| {
+            let mut text = if fs.is_file(&path).await {
+                fs.load(&path).await?
+            } else {
+                String::new()
+            };
+            let lowercase_word = word.to_lowercase();
+            if !parse_word_list(&text).any(|existing_word| existing_word == lowercase_word) {
+                if !text.is_empty() && !text.ends_with('\n') {
+                    text.push('\n');
+                }
+                text.push_str(&word);
+                text.push('\n');
+                if let Some(parent) = path.parent() {
+                    fs.create_dir(parent).await?;
+                }
+                fs.atomic_write(path.clone(), text)
+                    .await
+                    .with_context(|| format!("writing {path:?}"))?;
+            }
+            this.update(cx, |this, cx| this.check_all_buffers(cx))
+        })
+    }
+
+    /// Adds a word to the `spellcheck.words` user setting.
+    pub fn add_to_user_dictionary(&mut self, word: String, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            let words = settings
+                .spellcheck
+                .get_or_insert_default()
+                .words
+                .get_or_insert_default();
+            if !words.contains(&word) {
+                words.push(word);
+            }
+        });
+    }
+}
+
+fn dictionary_directories(settings: &SpellcheckSettings) -> Vec<PathBuf> {
+    let mut directories = settings
+        .dictionary_paths
+        .iter()
+        .map(|path| PathBuf::from(shellexpand::tilde(path).as_ref()))
+        .collect::<Vec<_>>();
+    directories.push(paths::dictionaries_dir().clone());
+    directories.extend(SYSTEM_DICTIONARY_DIRECTORIES.iter().map(PathBuf::from));
+    if cfg!(target_os = "macos") {
+        directories.push(util::paths::home_dir().join("Library/Spelling"));
+    }
+    directories
+}
+
+/// Loads `<name>.aff` and `<name>.dic` from the first directory that contains both.
+async fn load_dictionary(
+    fs: &dyn Fs,
+    name: &str,
+    directories: &[PathBuf],
+    executor: &gpui::BackgroundExecutor,
+) -> Result<Dictionary> {
+    for directory in directories {
+        let aff_path = directory.join(format!("{name}.aff"));
+        let dic_path = directory.join(format!("{name}.dic"));
+        if !fs.is_file(&aff_path).await || !fs.is_file(&dic_path).await {
+            continue;
+        }
+
+        let aff = Dictionary::decode(&fs.load_bytes(&aff_path).await?);
+        let dic = Dictionary::decode(&fs.load_bytes(&dic_path).await?);
+        return executor
+            .spawn(async move { Dictionary::new(&aff, &dic) })
+            .await
+            .with_context(|| format!("parsing {aff_path:?}"));
+    }
+    anyhow::bail!("no {name}.aff and {name}.dic found in {directories:?}")
+}
+
+fn project_dictionary_path(buffer: &Buffer, cx: &App) -> Option<PathBuf> {
+    let file = project::File::from_dyn(buffer.file())?;
+    let root_dir = file.worktree.read(cx).root_dir()?;
+    Some(root_dir.join(paths::local_dictionary_file_relative_path().as_std_path()))
+}
+
+/// Returns the lowercased words of a word list, which has a word per line and `#` comments.
+fn parse_word_list(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_lowercase)
+}
+
+/// Returns the byte ranges of the words in the given range of the buffer's comments, strings and
+/// prose that are neither in the dictionary nor in `accepted_words`, which must be lowercase.
+pub fn misspelled_words(
+    snapshot: &BufferSnapshot,
+    range: Range<usize>,
+    dictionary: &Dictionary,
+    accepted_words: &HashSet<String>,
+) -> Vec<Range<usize>> {
+    let text = snapshot.text_for_range(range.clone()).collect::<String>();
+    words_to_check(&text)
+        .into_iter()
+        .filter(|word_range| {
+            let word = &text[word_range.clone()];
+            !accepted_words.contains(&word.to_lowercase())
+                && is_checked_at(snapshot, range.start + word_range.start)
+                && !dictionary.check(word)
+        })
+        .map(|word_range| range.start + word_range.start..range.start + word_range.end)
+        .collect()
+}
+
+/// Returns the ranges of the lines edited since the given version, merging adjacent ones.
+fn edited_lines(snapshot: &BufferSnapshot, since: &clock::Global) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for edit in snapshot.edits_since::<Point>(since) {
+        let start = snapshot.point_to_offset(Point::new(edit.new.start.row, 0));
+        let end_row = edit.new.end.row;
+        let end = snapshot.point_to_offset(Point::new(end_row, snapshot.line_len(end_row)));
+        match ranges.last_mut() {
+            Some(last) if start <= last.end + 1 => last.end = last.end.max(end),
+            _ => ranges.push(start..end),
+        }
+    }
+    ranges
+}
+
+fn is_checked_at(snapshot: &BufferSnapshot, offset: usize) -> bool {
+    let Some(scope) = snapshot.language_scope_at(offset) else {
+        return false;
+    };
+    match scope.override_name() {
+        Some(name) => CHECKED_SCOPES.contains(&name),
+        None => PROSE_LANGUAGES.contains(&scope.language_name().as_ref()),
+    }
+}
+
+/// Returns the ranges of the words in the text that look like natural language, skipping the
+/// ones that look like identifiers, numbers, paths, URLs or email addresses.
+fn words_to_check(text: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut chunk_start = None;
+    for (ix, ch) in text.char_indices().chain([(text.len(), ' ')]) {
+        if !ch.is_whitespace() {
+            chunk_start.get_or_insert(ix);
+            continue;
+        }
+        let Some(start) = chunk_start.take() else {
+            continue;
+        };
+        let chunk = &text[start..ix];
+        if chunk.contains("://") || chunk.contains(['@', '/', '\\', '`']) || has_inner_dot(chunk) {
+            continue;
+        }
+        words.extend(
+            word_ranges(chunk)
+                .into_iter()
+                .filter(|range| should_check_word(&chunk[range.clone()]))
+                .map(|range| start + range.start..start + range.end),
+        );
+    }
+    words
+}
+
+fn has_inner_dot(chunk: &str) -> bool {
+    let chars = chunk.chars().collect::<Vec<_>>();
+    chars.windows(3).any(|window| {
+        window[1] == '.' && window[0].is_alphanumeric() && window[2].is_alphanumeric()
+    })
+}
+
+/// Splits a chunk of non-whitespace text into runs of word characters, keeping apostrophes
+/// between letters (as in "don't").
+fn word_ranges(chunk: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut start = None;
+    let mut chars = chunk.char_indices().peekable();
+    while let Some((ix, ch)) = chars.next() {
+        let is_word_char = ch.is_alphanumeric()
+            || ch == '_'
+            || (start.is_some()
+                && matches!(ch, '\'' | '’')
+                && chars.peek().is_some_and(|(_, next)| next.is_alphabetic()));
+        if is_word_char {
+            start.get_or_insert(ix);
+        } else if let Some(start) = start.take() {
+            ranges.push(start..ix);
+        }
+    }
+    if let Some(start) = start {
+        ranges.push(start..chunk.len());
+    }
+    ranges
+}
+
+fn should_check_word(word: &str) -> bool {
+    if word.chars().count() < 2 || word.contains(|ch: char| ch.is_numeric() || ch == '_') {
+        return false;
+    }
+    // Acronyms such as "TODO" or "HTML".
+    if word.chars().all(|ch| !ch.is_lowercase()) {
+        return false;
+    }
+    // Identifiers such as "camelCase" or "HashMap".
+    let chars = word.chars().collect::<Vec<_>>();
+    !chars
+        .windows(2)
+        .any(|pair| pair[0].is_lowercase() && pair[1].is_uppercase())
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SpellcheckAction {
+    Replace { replacement: String },
+    AddToProjectDictionary { word: String },
+    AddToUserDictionary { word: String },
+}
+
+struct SpellcheckCodeActionProvider;
+
+impl CodeActionProvider for SpellcheckCodeActionProvider {
+    fn id(&self) -> Arc<str> {
+        DIAGNOSTIC_SOURCE.into()
+    }
+
+    fn code_actions(
+        &self,
+        buffer: &Entity<Buffer>,
+        range: Range<text::Anchor>,
+        _: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Vec<CodeAction>>> {
+        let Some(spellchecker) = Spellchecker::global(cx) else {
+            return Task::ready(Ok(Vec::new()));
+        };
+        let spellchecker = spellchecker.read(cx);
+        let server_id = spellchecker.server_id;
+        let Some(dictionary) = spellchecker.dictionary().cloned() else {
+            return Task::ready(Ok(Vec::new()));
+        };
+        let has_project_dictionary = project_dictionary_path(buffer.read(cx), cx).is_some();
+        let snapshot = buffer.read(cx).snapshot();
+        let misspellings = snapshot
+            .diagnostics_in_range::<_, usize>(range, false)
+            .filter(|entry| entry.diagnostic.source.as_deref() == Some(DIAGNOSTIC_SOURCE))
+            .map(|entry| entry.range)
+            .collect::<Vec<_>>();
+        if misspellings.is_empty() {
+            return Task::ready(Ok(Vec::new()));
+        }
+
+        cx.background_spawn(async move {
+            let mut actions = Vec::new();
+            for range in misspellings {
+                let word = snapshot.text_for_range(range.clone()).collect::<String>();
+                let range = snapshot.anchor_before(range.start)..snapshot.anchor_after(range.end);
+                for replacement in dictionary.suggest(&word, MAX_SUGGESTIONS) {
+                    actions.push(code_action(
+                        server_id,
+                        format!("Change to \"{replacement}\""),
+                        range.clone(),
+                        SpellcheckAction::Replace { replacement },
+                    ));
+                }
+                if has_project_dictionary {
+                    actions.push(code_action(
+                        server_id,
+                        format!("Add \"{word}\" to project dictionary"),
+                        range.clone(),
+                        SpellcheckAction::AddToProjectDictionary { word: word.clone() },
+                    ));
+                }
+                actions.push(code_action(
+                    server_id,
+                    format!("Add \"{word}\" to user dictionary"),
+                    range,
+                    SpellcheckAction::AddToUserDictionary { word },
+                ));
+            }
+            Ok(actions)
+        })
+    }
+
+    fn apply_code_action(
+        &self,
+        buffer_handle: Entity<Buffer>,
+        action: CodeAction,
+        _: ExcerptId,
+        push_to_history: bool,
+        _: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<ProjectTransaction>> {
+        let spellcheck_action = match &action.lsp_action {
+            LspAction::Action(lsp_action) => lsp_action
+                .data
+                .clone()
+                .and_then(|data| serde_json::from_value::<SpellcheckAction>(data).ok()),
+            _ => None,
+        };
+        let Some(spellcheck_action) = spellcheck_action else {
+            return Task::ready(Err(anyhow!("not a spellcheck code action")));
+        };
+        let Some(spellchecker) = Spellchecker::global(cx) else {
+            return Task::ready(Err(anyhow!("spellcheck isn't initialized")));
+        };
+
+        match spellcheck_action {
+            SpellcheckAction::Replace { replacement } => {
+                let transaction = buffer_handle.update(cx, |buffer, cx| {
+                    buffer.finalize_last_transaction();
+                    buffer.start_transaction();
+                    buffer.edit([(action.range, replacement)], None, cx);
+                    buffer.end_transaction(cx).and_then(|transaction_id| {
+                        if push_to_history {
+                            buffer.finalize_last_transaction();
+                            buffer.get_transaction(transaction_id).cloned()
+                        } else {
+                            buffer.forget_transaction(transaction_id)
+                        }
+                    })
+                });
+                let mut project_transaction = ProjectTransaction::default();
+                if let Some(transaction) = transaction {
+                    project_transaction.0.insert(buffer_handle, transaction);
+                }
+                Task::ready(Ok(project_transaction))
+            }
+            SpellcheckAction::AddToProjectDictionary { word } => {
+                let task = spellchecker.update(cx, |spellchecker, cx| {
+                    spellchecker.add_to_project_dictionary(&buffer_handle, word, cx)
+                });
+                cx.spawn(async move |_| {
+                    task.await?;
+                    Ok(ProjectTransaction::default())
+                })
+            }
+            SpellcheckAction::AddToUserDictionary { word } => {
+                spellchecker.update(cx, |spellchecker, cx| {
+                    spellchecker.add_to_user_dictionary(word, cx)
+                });
+                Task::ready(Ok(ProjectTransaction::default()))
+            }
+        }
+    }
+}
+
+fn code_action(
+    server_id: LanguageServerId,
+    title: String,
+    range: Range<text::Anchor>,
+    action: SpellcheckAction,
+) -> CodeAction {
+    CodeAction {
+        server_id,
+        range,
+        lsp_action: LspAction::Action(Box::new(lsp::CodeAction {
+            title,
+            kind: Some(lsp::CodeActionKind::QUICKFIX),
+            data: serde_json::to_value(action).ok(),
+            ..Default::default()
+        })),
+        resolved: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, UpdateGlobal};
+    use language::{Language, LanguageConfig};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::path;
+
+    const AFF: &str = "
+SET UTF-8
+TRY esianrtolcdugmphbyfvkwz
+
+SFX S Y 1
+SFX S   0     s          .
+";
+
+    const DIC: &str = "8
+a/S
+word/S
+comment/S
+string/S
+is/S
+here
+the
+misspelled
+";
+
+    fn rust_lang() -> Language {
+        Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                ..Default::default()
+            },
+            Some(tree_sitter_rust::LANGUAGE.into()),
+        )
+        .with_override_query(
+            r#"
+            [(line_comment) (block_comment)] @comment.inclusive
+            (string_literal) @string
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_words_to_check() {
+        let text = "Teh quick_brown fox's camelCase HTML v2 see https://zed.dev, \
+            user@example.com or foo.bar and `code` don't stop.";
+        let words = words_to_check(text)
+            .into_iter()
+            .map(|range| &text[range])
+            .collect::<Vec<_>>();
+        assert_eq!(words, ["Teh", "fox's", "see", "or", "and", "don't", "stop"]);
+    }
+
+    #[gpui::test]
+    async fn test_misspelled_words(cx: &mut TestAppContext) {
+        let dictionary = Dictionary::new(AFF, DIC).unwrap();
+        let text = "// A comment wiht a wurd\nfn speling() { let x = \"a strng\"; }\n";
+        let buffer = cx.new(|cx| Buffer::local(text, cx).with_language(Arc::new(rust_lang()), cx));
+        cx.executor().run_until_parked();
+
+        let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+        let misspelled = |accepted_words: &[&str]| {
+            let accepted_words = accepted_words.iter().map(|word| word.to_string()).collect();
+            misspelled_words(&snapshot, 0..snapshot.len(), &dictionary, &accepted_words)
+                .into_iter()
+                .map(|range| &text[range])
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(misspelled(&[]), ["wiht", "wurd", "strng"]);
+        assert_eq!(misspelled(&["wurd"]), ["wiht", "strng"]);
+
+        let text = "Here is teh word";
+        let buffer =
+            cx.new(|cx| Buffer::local(text, cx).with_language(language::PLAIN_TEXT.clone(), cx));
+        let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+        let misspelled = misspelled_words(
+            &snapshot,
+            0..snapshot.len(),
+            &dictionary,
+            &HashSet::default(),
+        )
+        .into_iter()
+        .map(|range| &text[range])
+        .collect::<Vec<_>>();
+        assert_eq!(misspelled, ["teh"]);
+    }
+
+    #[gpui::test]
+    async fn test_spellchecker_diagnostics(cx: &mut TestAppContext) {
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dictionaries"),
+            json!({ "test.aff": AFF, "test.dic": DIC }),
+        )
+        .await;
+        fs.insert_tree(
+            path!("/project"),
+            json!({ "notes.txt": "the wurd is here\na strng" }),
+        )
+        .await;
+        init_test(fs.clone(), cx);
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let server_id = project.read_with(cx, |project, _| {
+            project.languages().next_language_server_id()
+        });
+        cx.update(|cx| crate::init(server_id, cx));
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/project/notes.txt"), cx)
+            })
+            .await
+            .unwrap();
+        let lsp_store = project.read_with(cx, |project, _| project.lsp_store());
+        let spellchecker = cx.update(|cx| Spellchecker::global(cx).unwrap());
+        spellchecker.update(cx, |spellchecker, cx| {
+            spellchecker.register_buffer(&buffer, &lsp_store, cx)
+        });
+        cx.executor().advance_clock(CHECK_DEBOUNCE);
+        cx.executor().run_until_parked();
+
+        let diagnostics = |cx: &mut TestAppContext| {
+            buffer.read_with(cx, |buffer, _| {
+                buffer
+                    .buffer_diagnostics(Some(server_id))
+                    .into_iter()
+                    .map(|entry| entry.diagnostic.message.clone())
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(
+            diagnostics(cx),
+            ["Unknown word: \"wurd\"", "Unknown word: \"strng\""]
+        );
+
+        spellchecker
+            .update(cx, |spellchecker, cx| {
+                spellchecker.add_to_project_dictionary(&buffer, "wurd".to_string(), cx)
+            })
+            .await
+            .unwrap();
+        cx.executor().run_until_parked();
+        assert_eq!(
+            fs.load(Path::new(path!("/project/.zed/dictionary.txt")))
+                .await
+                .unwrap(),
+            "wurd\n"
+        );
+        assert_eq!(diagnostics(cx), ["Unknown word: \"strng\""]);
+
+        // Only the edited line is checked again, so a word accepted on another line since the
+        // last check is still reported.
+        fs.atomic_write(
+            PathBuf::from(path!("/project/.zed/dictionary.txt")),
+            "wurd\nstrng\n".to_string(),
+        )
+        .await
+        .unwrap();
+        buffer.update(cx, |buffer, cx| buffer.edit([(0..3, "teh")], None, cx));
+        cx.executor().advance_clock(CHECK_DEBOUNCE);
+        cx.executor().run_until_parked();
+        assert_eq!(
+            diagnostics(cx),
+            ["Unknown word: \"teh\"", "Unknown word: \"strng\""]
+        );
+
+        cx.update(|cx| {
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    settings.spellcheck.get_or_insert_default().enabled = Some(false);
+                });
+            });
+        });
+        cx.executor().run_until_parked();
+        assert_eq!(diagnostics(cx), Vec::<String>::new());
+    }
+
+    fn init_test(fs: Arc<FakeFs>, cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            language::init(cx);
+            Project::init_settings(cx);
+            <dyn Fs>::set_global(fs, cx);
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    let spellcheck = settings.spellcheck.get_or_insert_default();
+                    spellcheck.enabled = Some(true);
+                    spellcheck.dictionary = Some("test".to_string());
+                    spellcheck.dictionary_paths = Some(vec![path!("/dictionaries").to_string()]);
+                });
+            });
+        });
+    }
+}
@@ -0,0 +1,474 @@
+use anyhow::{Result, bail};
+use gpui::{Action, App, Context, Global, Window};
+use language::Point;
+use settings::Settings;
+use workspace::Workspace;
+
+use crate::{Vim, VimSettings, mapping::MapArgs, state::Mode};
+
+/// A mode that abbreviations can be defined in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum AbbreviationMode {
+    Insert,
+    Command,
+}
+
+impl AbbreviationMode {
+    fn abbreviation(self) -> char {
+        match self {
+            AbbreviationMode::Insert => 'i',
+            AbbreviationMode::Command => 'c',
+        }
+    }
+}
+
+const INSERT_COMMAND: &[AbbreviationMode] = &[AbbreviationMode::Insert, AbbreviationMode::Command];
+const INSERT: &[AbbreviationMode] = &[AbbreviationMode::Insert];
+const COMMAND: &[AbbreviationMode] = &[AbbreviationMode::Command];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum AbbreviateCommandKind {
+    Abbreviate,
+    Unabbreviate,
+    Clear,
+}
+
+/// The abbreviation commands, as the `(prefix, suffix)` they can be abbreviated to, what they do
+/// and the modes they apply to. Since abbreviations aren't remapped, the `noreabbrev` commands
+/// are the same as the `abbreviate` ones.
+pub(crate) const ABBREVIATE_COMMANDS: &[(
+    &str,
+    &str,
+    AbbreviateCommandKind,
+    &[AbbreviationMode],
+)] = &[
+    (
+        "ab",
+        "breviate",
+        AbbreviateCommandKind::Abbreviate,
+        INSERT_COMMAND,
+    ),
+    (
+        "norea",
+        "bbrev",
+        AbbreviateCommandKind::Abbreviate,
+        INSERT_COMMAND,
+    ),
+    ("ia", "bbrev", AbbreviateCommandKind::Abbreviate, INSERT),
+    ("inorea", "bbrev", AbbreviateCommandKind::Abbreviate, INSERT),
+    ("ca", "bbrev", AbbreviateCommandKind::Abbreviate, COMMAND),
+    (
+        "cnorea",
+        "bbrev",
+        AbbreviateCommandKind::Abbreviate,
+        COMMAND,
+    ),
+    (
+        "una",
+        "bbreviate",
+        AbbreviateCommandKind::Unabbreviate,
+        INSERT_COMMAND,
+    ),
+    ("iuna", "bbrev", AbbreviateCommandKind::Unabbreviate, INSERT),
+    (
+        "cuna",
+        "bbrev",
+        AbbreviateCommandKind::Unabbreviate,
+        COMMAND,
+    ),
+    ("abc", "lear", AbbreviateCommandKind::Clear, INSERT_COMMAND),
+    ("iabc", "lear", AbbreviateCommandKind::Clear, INSERT),
+    ("cabc", "lear", AbbreviateCommandKind::Clear, COMMAND),
+];
+
+/// Adds, removes or lists abbreviations.
+#[derive(Clone, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub(crate) struct AbbreviateCommand {
+    pub(crate) kind: AbbreviateCommandKind,
+    pub(crate) modes: &'static [AbbreviationMode],
+    pub(crate) args: String,
+}
+
+struct Abbreviation {
+    mode: AbbreviationMode,
+    lhs: String,
+    rhs: String,
+    /// Whether the abbreviation comes from the vimrc, rather than from a command.
+    sourced: bool,
+}
+
+#[derive(Default)]
+struct VimAbbreviations {
+    abbreviations: Vec<Abbreviation>,
+}
+
+impl Global for VimAbbreviations {}
+
+pub(crate) fn init(cx: &mut App) {
+    cx.set_global(VimAbbreviations::default());
+}
+
+impl AbbreviateCommand {
+    pub(crate) fn run(&self, cx: &mut Context<Workspace>) -> Result<()> {
+        if let Some(listing) = run_abbreviate_command(self.kind, self.modes, &self.args, false, cx)?
+        {
+            cx.emit(workspace::Event::OpenBundledFile {
+                text: listing.into(),
+                title: "Vim Abbreviations",
+                language: "Plain Text",
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Runs an abbreviation command with the given arguments, returning the listing of the
+/// abbreviations when it lists them.
+pub(crate) fn run_abbreviate_command(
+    kind: AbbreviateCommandKind,
+    modes: &[AbbreviationMode],
+    args: &str,
+    sourced: bool,
+    cx: &mut App,
+) -> Result<Option<String>> {
+    let args = MapArgs::parse(args)?;
+    match kind {
+        AbbreviateCommandKind::Abbreviate => {
+            let Some(lhs) = args.lhs else {
+                return list_abbreviations(modes, None, cx).map(Some);
+            };
+            let Some(rhs) = args.rhs else {
+                return list_abbreviations(modes, Some(lhs), cx).map(Some);
+            };
+            add_abbreviation(modes, lhs, rhs, args.unique, sourced, cx)?;
+        }
+        AbbreviateCommandKind::Unabbreviate => {
+            let Some(lhs) = args.lhs else {
+                bail!("argument required");
+            };
+            let abbreviations = &mut cx.global_mut::<VimAbbreviations>().abbreviations;
+            let count = abbreviations.len();
+            abbreviations.retain(|abbreviation| {
+                !modes.contains(&abbreviation.mode) || abbreviation.lhs != lhs
+            });
+            if abbreviations.len() == count {
+                bail!("no such abbreviation: {lhs}");
+            }
+        }
+        AbbreviateCommandKind::Clear => {
+            if let Some(lhs) = args.lhs {
+                bail!("trailing characters: {lhs}");
+            }
+            let abbreviations = &mut cx.global_mut::<VimAbbreviations>().abbreviations;
+            abbreviations.retain(|abbreviation| !modes.contains(&abbreviation.mode));
+        }
+    }
+    Ok(None)
+}
+
+/// Removes the abbreviations that came from the vimrc before it's sourced again.
+pub(crate) fn clear_sourced(cx: &mut App) {
+    let abbreviations = &mut cx.global_mut::<VimAbbreviations>().abbreviations;
+    abbreviations.retain(|abbreviation| !abbreviation.sourced);
+}
+
+fn add_abbreviation(
+    modes: &[AbbreviationMode],
+    lhs: &str,
+    rhs: &str,
+    unique: bool,
+    sourced: bool,
+    cx: &mut App,
+) -> Result<()> {
+    let abbreviations = &mut cx.global_mut::<VimAbbreviations>().abbreviations;
+    let exists = |abbreviation: &Abbreviation, mode: AbbreviationMode| {
+        abbreviation.mode == mode && abbreviation.lhs == lhs
+    };
+    if unique
+        && abbreviations
+            .iter()
+            .any(|abbreviation| modes.iter().any(|mode| exists(abbreviation, *mode)))
+    {
+        bail!("abbreviation already exists for {lhs}");
+    }
+    for &mode in modes {
+        abbreviations.retain(|abbreviation| !exists(abbreviation, mode));
+        abbreviations.push(Abbreviation {
+            mode,
+            lhs: lhs.to_string(),
+            rhs: rhs.to_string(),
+            sourced,
+        });
+    }
+    Ok(())
+}
+
+fn list_abbreviations(modes: &[AbbreviationMode], lhs: Option<&str>, cx: &App) -> Result<String> {
+    let listing = cx
+        .global::<VimAbbreviations>()
+        .abbreviations
+        .iter()
+        .filter(|abbreviation| {
+            modes.contains(&abbreviation.mode)
+                && abbreviation.lhs.starts_with(lhs.unwrap_or_default())
+        })
+        .map(|abbreviation| {
+            format!(
+                "{}  {:<15} {}\n",
+                abbreviation.mode.abbreviation(),
+                abbreviation.lhs,
+                abbreviation.rhs
+            )
+        })
+        .collect::<String>();
+    if listing.is_empty() {
+        bail!("no abbreviation found");
+    }
+    Ok(listing)
+}
+
+/// Returns whether `text` ends with the abbreviation `lhs`, and the character before it allows
+/// the abbreviation to be expanded. An abbreviation that starts with a keyword character can
+/// follow any non-keyword character, while the others have to follow whitespace. Both can start
+/// the text.
+fn ends_with_abbreviation(text: &str, lhs: &str, is_keyword: &impl Fn(char) -> bool) -> bool {
+    if lhs.is_empty() {
+        return false;
+    }
+    let Some(before) = text.strip_suffix(lhs) else {
+        return false;
+    };
+    match before.chars().next_back() {
+        None => true,
+        Some(char) if char.is_whitespace() => true,
+        Some(char) => !is_keyword(char) && lhs.chars().next().is_some_and(is_keyword),
+    }
+}
+
+/// Returns the insert mode abbreviation `text` ends with, as the length of its `lhs` and its
+/// `rhs`. The abbreviations created with commands take precedence over the ones for the language
+/// in the settings, which take precedence over the other ones in the settings.
+fn find_insert_abbreviation(
+    text: &str,
+    language: Option<&str>,
+    is_keyword: impl Fn(char) -> bool,
+    cx: &App,
+) -> Option<(usize, String)> {
+    let abbreviations = cx
+        .try_global::<VimAbbreviations>()
+        .into_iter()
+        .flat_map(|abbreviations| &abbreviations.abbreviations)
+        .filter(|abbreviation| abbreviation.mode == AbbreviationMode::Insert)
+        .map(|abbreviation| (&abbreviation.lhs, &abbreviation.rhs));
+    let settings = VimSettings::get_global(cx);
+    let language_abbreviations = language
+        .and_then(|language| settings.language_abbreviations.get(language))
+        .into_iter()
+        .flatten();
+    abbreviations
+        .chain(language_abbreviations)
+        .chain(&settings.abbreviations)
+        .find(|(lhs, _)| ends_with_abbreviation(text, lhs, &is_keyword))
+        .map(|(lhs, rhs)| (lhs.len(), rhs.clone()))
+}
+
+/// Expands the command line abbreviations in a command, as if it was typed: each abbreviation
+/// that's followed by a non-keyword character or ends the command is replaced.
+pub(crate) fn expand_command_line(command: &str, cx: &App) -> String {
+    let abbreviations = cx
+        .try_global::<VimAbbreviations>()
+        .into_iter()
+        .flat_map(|abbreviations| &abbreviations.abbreviations)
+        .filter(|abbreviation| abbreviation.mode == AbbreviationMode::Command)
+        .collect::<Vec<_>>();
+    if abbreviations.is_empty() {
+        return command.to_string();
+    }
+
+    let is_keyword = |char: char| char.is_alphanumeric() || char == '_';
+    let expand = |expanded: &mut String| {
+        if let Some(abbreviation) = abbreviations.iter().find(|abbreviation| {
+            ends_with_abbreviation(expanded.as_str(), &abbreviation.lhs, &is_keyword)
+        }) {
+            expanded.truncate(expanded.len() - abbreviation.lhs.len());
+            expanded.push_str(&abbreviation.rhs);
+        }
+    };
+    let mut expanded = String::new();
+    for char in command.chars() {
+        if !is_keyword(char) {
+            expand(&mut expanded);
+        }
+        expanded.push(char);
+    }
+    expand(&mut expanded);
+    expanded
+}
+
+impl Vim {
+    /// Expands the insert mode abbreviations that end before the cursors after `text` was typed,
+    /// when it's a non-keyword character. The expansion gets its own undo step, so that undoing it
+    /// restores the text as it was typed.
+    pub(crate) fn expand_abbreviation(
+        &mut self,
+        text: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.mode != Mode::Insert {
+            return;
+        }
+        let mut chars = text.chars();
+        let (Some(trigger), None) = (chars.next(), chars.next()) else {
+            return;
+        };
+
+        self.update_editor(cx, |vim, editor, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let mut edits = Vec::new();
+            for selection in editor.selections.all::<Point>(cx) {
+                if !selection.is_empty() {
+                    continue;
+                }
+                let head = selection.head();
+                let classifier = snapshot.char_classifier_at(head);
+                if classifier.is_word(trigger) {
+                    return;
+                }
+                let line = snapshot
+                    .text_for_range(Point::new(head.row, 0)..head)
+                    .collect::<String>();
+                let Some(before) = line.strip_suffix(trigger) else {
+                    continue;
+                };
+                let language = snapshot.language_at(head).map(|language| language.name());
+                let Some((len, rhs)) = find_insert_abbreviation(
+                    before,
+                    language.as_ref().map(|language| language.as_ref()),
+                    |char| classifier.is_word(char),
+                    cx,
+                ) else {
+                    continue;
+                };
+                let start = Point::new(head.row, (before.len() - len) as u32);
+                let end = Point::new(head.row, before.len() as u32);
+                edits.push((start..end, rhs));
+            }
+            if edits.is_empty() {
+                return;
+            }
+
+            // The text typed so far is grouped and finalized before expanding, so that the
+            // expansion isn't grouped with it when insert mode ends.
+            if let Some(transaction_id) = vim.current_tx.take() {
+                editor.group_until_transaction(transaction_id, cx);
+            }
+            editor.finalize_last_transaction(cx);
+            vim.current_tx = editor.transact(window, cx, |editor, _, cx| {
+                editor.edit(edits, cx);
+            });
+            vim.current_anchor = None;
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use gpui::TestAppContext;
+    use settings::SettingsStore;
+
+    use crate::{state::Mode, test::VimTestContext};
+
+    #[gpui::test]
+    async fn test_insert_abbreviation(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("ˇ", Mode::Normal);
+
+        cx.simulate_keystrokes(": i a b space t e h space t h e enter");
+        cx.simulate_keystrokes("i t e h space s t e h , t e h");
+        cx.assert_state("the steh,tehˇ", Mode::Insert);
+        cx.simulate_keystrokes(".");
+        cx.assert_state("the steh,the.ˇ", Mode::Insert);
+
+        cx.simulate_keystrokes("escape u");
+        cx.assert_state("the steh,tehˇ.", Mode::Normal);
+
+        cx.simulate_keystrokes(": i u n a b space t e h enter");
+        cx.simulate_keystrokes("A space t e h space");
+        cx.assert_state("the steh,teh. teh ˇ", Mode::Insert);
+    }
+
+    #[gpui::test]
+    async fn test_undo_abbreviation(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("ˇ", Mode::Normal);
+
+        cx.simulate_keystrokes(": a b space t e h space t h e enter");
+        cx.simulate_keystrokes("i t e h space c a t escape");
+        cx.assert_state("the caˇt", Mode::Normal);
+        cx.simulate_keystrokes("u");
+        cx.assert_state("teh ˇ", Mode::Normal);
+        cx.simulate_keystrokes("u");
+        cx.assert_state("ˇ", Mode::Normal);
+    }
+
+    #[gpui::test]
+    async fn test_command_line_abbreviation(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("ˇone two", Mode::Normal);
+
+        cx.simulate_keystrokes(": c a b b r e v space D space d e l e t e enter");
+        cx.simulate_keystrokes(": D enter");
+        cx.assert_state("ˇ", Mode::Normal);
+
+        // Command line abbreviations don't apply in insert mode.
+        cx.simulate_keystrokes("i D space");
+        cx.assert_state("D ˇ", Mode::Insert);
+    }
+
+    #[gpui::test]
+    async fn test_abbreviation_settings(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings(cx, |settings| {
+                let vim = settings.vim.get_or_insert_default();
+                vim.abbreviations = Some(
+                    [
+                        ("teh".to_string(), "the".to_string()),
+                        ("pritn".to_string(), "print".to_string()),
+                    ]
+                    .into_iter()
+                    .collect(),
+                );
+                vim.language_abbreviations = Some(
+                    [
+                        (
+                            "Rust".to_string(),
+                            [("teh".to_string(), "self".to_string())]
+                                .into_iter()
+                                .collect(),
+                        ),
+                        (
+                            "TypeScript".to_string(),
+                            [("fn".to_string(), "function".to_string())]
+                                .into_iter()
+                                .collect(),
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                );
+            });
+        });
+
+        // The test buffer is Rust, so only the abbreviations for Rust and the general ones apply.
+        cx.set_state("ˇ", Mode::Normal);
+        cx.simulate_keystrokes("i t e h space p r i t n space f n space");
+        cx.assert_state("self print fn ˇ", Mode::Insert);
+
+        cx.simulate_keystrokes("escape : i a b space t e h space t e a enter");
+        cx.simulate_keystrokes("A t e h space");
+        cx.assert_state("self print fn tea ˇ", Mode::Insert);
+    }
+}
@@ -10,7 +10,7 @@ use futures::AsyncWriteExt as _;
 use gpui::{Action, App, AppContext as _, Context, Global, Keystroke, Task, Window, actions};
 use itertools::Itertools;
 use language::Point;
-use multi_buffer::MultiBufferRow;
+use multi_buffer::{Anchor, MultiBufferRow, MultiBufferSnapshot};
 use project::ProjectPath;
 use regex::Regex;
 use schemars::JsonSchema;
@@ -29,12 +29,17 @@ use std::{
 use task::{HideStrategy, RevealStrategy, SpawnInTerminal, TaskId};
 use ui::ActiveTheme;
 use util::{ResultExt, rel_path::RelPath};
-use workspace::{Item, SaveIntent, notifications::NotifyResultExt};
+use workspace::{
+    Item, SaveIntent,
+    notifications::{NotifyResultExt, NotifyTaskExt},
+};
 use workspace::{SplitDirection, notifications::DetachAndPromptErr};
 use zed_actions::{OpenDocs, RevealTarget};
 
 use crate::{
     ToggleMarksView, ToggleRegistersView, Vim,
+    abbreviation::{self, AbbreviateCommand},
+    mapping::{self, MapCommand},
     motion::{EndOfDocument, Motion, MotionKind, StartOfDocument},
     normal::{
         JoinLines,
@@ -42,6 +47,7 @@ use crate::{
     },
     object::Object,
     state::{Mark, Mode},
+    vimrc::SourceCommand,
     visual::VisualDeleteLine,
 };
 
@@ -134,7 +140,7 @@ impl VimOption {
         .map(|(_, option)| option)
     }
 
-    fn from(option: &str) -> Option<Self> {
+    pub(crate) fn from(option: &str) -> Option<Self> {
         match option {
             "wrap" => Some(Self::Wrap(true)),
             "nowrap" => Some(Self::Wrap(false)),
@@ -170,6 +176,32 @@ impl VimOption {
             VimOption::IgnoreCase(false) => "noignorecase",
         }
     }
+
+    pub(crate) fn apply(&self, editor: &mut Editor, cx: &mut Context<Editor>) {
+        match self {
+            VimOption::Wrap(true) => {
+                editor.set_soft_wrap_mode(language::language_settings::SoftWrap::EditorWidth, cx);
+            }
+            VimOption::Wrap(false) => {
+                editor.set_soft_wrap_mode(language::language_settings::SoftWrap::None, cx);
+            }
+            VimOption::Number(enabled) => {
+                editor.set_show_line_numbers(*enabled, cx);
+            }
+            VimOption::RelativeNumber(enabled) => {
+                editor.set_relative_line_number(Some(*enabled), cx);
+            }
+            VimOption::IgnoreCase(enabled) => Self::set_ignore_case(*enabled, cx),
+        }
+    }
+
+    pub(crate) fn set_ignore_case(enabled: bool, cx: &mut App) {
+        let mut settings = EditorSettings::get_global(cx).clone();
+        settings.search.case_sensitive = !enabled;
+        SettingsStore::update(cx, |store, _| {
+            store.override_global(settings);
+        });
+    }
 }
 
 /// Sets vim options and configuration values.
@@ -256,28 +288,7 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     // Vim::action(editor, cx, |vim, action: &StartOfLine, window, cx| {
     Vim::action(editor, cx, |vim, action: &VimSet, _, cx| {
         for option in action.options.iter() {
-            vim.update_editor(cx, |_, editor, cx| match option {
-                VimOption::Wrap(true) => {
-                    editor
-                        .set_soft_wrap_mode(language::language_settings::SoftWrap::EditorWidth, cx);
-                }
-                VimOption::Wrap(false) => {
-                    editor.set_soft_wrap_mode(language::language_settings::SoftWrap::None, cx);
-                }
-                VimOption::Number(enabled) => {
-                    editor.set_show_line_numbers(*enabled, cx);
-                }
-                VimOption::RelativeNumber(enabled) => {
-                    editor.set_relative_line_number(Some(*enabled), cx);
-                }
-                VimOption::IgnoreCase(enabled) => {
-                    let mut settings = EditorSettings::get_global(cx).clone();
-                    settings.search.case_sensitive = !*enabled;
-                    SettingsStore::update(cx, |store, _| {
-                        store.override_global(settings);
-                    });
-                }
-            });
+            vim.update_editor(cx, |_, editor, cx| option.apply(editor, cx));
         }
     });
     Vim::action(editor, cx, |vim, _: &VisualCommand, window, cx| {
@@ -507,7 +518,7 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     });
 
     Vim::action(editor, cx, |vim, action: &VimNorm, window, cx| {
-        let keystrokes = action
+        let keystrokes: Vec<Keystroke> = action
             .command
             .chars()
             .map(|c| Keystroke::parse(&c.to_string()).unwrap())
@@ -515,52 +526,74 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
         vim.switch_mode(Mode::Normal, true, window, cx);
         let initial_selections =
             vim.update_editor(cx, |_, editor, _| editor.selections.disjoint_anchors_arc());
-        if let Some(range) = &action.range {
-            let result = vim.update_editor(cx, |vim, editor, cx| {
+        // The keystrokes run on each line of the range, or from each cursor without one, which is
+        // how they run on each of the lines matched by `:g`.
+        let result = vim.update_editor(cx, |vim, editor, cx| {
+            if let Some(range) = &action.range {
                 let range = range.buffer_range(vim, editor, window, cx)?;
-                editor.change_selections(
-                    SelectionEffects::no_scroll().nav_history(false),
-                    window,
-                    cx,
-                    |s| {
-                        s.select_ranges(
-                            (range.start.0..=range.end.0)
-                                .map(|line| Point::new(line, 0)..Point::new(line, 0)),
-                        );
-                    },
-                );
-                anyhow::Ok(())
-            });
-            if let Some(Err(err)) = result {
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                anyhow::Ok(
+                    (range.start.0..=range.end.0)
+                        .map(|row| snapshot.anchor_before(Point::new(row, 0)))
+                        .collect::<Vec<_>>(),
+                )
+            } else {
+                Ok(editor
+                    .selections
+                    .disjoint_anchors_arc()
+                    .iter()
+                    .map(|selection| selection.head())
+                    .collect())
+            }
+        });
+        let cursors = match result {
+            Some(Ok(cursors)) => cursors,
+            Some(Err(err)) => {
                 log::error!("Error selecting range: {}", err);
                 return;
             }
+            None => return,
         };
 
         let Some(workspace) = vim.workspace(window) else {
             return;
         };
-        let task = workspace.update(cx, |workspace, cx| {
-            workspace.send_keystrokes_impl(keystrokes, window, cx)
-        });
-        let had_range = action.range.is_some();
+        let grouped = cursors.len() > 1;
+        if grouped {
+            vim.update_editor(cx, |_, editor, cx| {
+                editor.start_transaction_at(Instant::now(), window, cx);
+            });
+        }
 
         cx.spawn_in(window, async move |vim, cx| {
-            task.await;
-            vim.update_in(cx, |vim, window, cx| {
-                vim.update_editor(cx, |_, editor, cx| {
-                    if had_range {
-                        editor.change_selections(SelectionEffects::default(), window, cx, |s| {
-                            s.select_anchor_ranges([s.newest_anchor().range()]);
-                        })
+            for cursor in cursors {
+                vim.update_in(cx, |vim, window, cx| {
+                    vim.update_editor(cx, |_, editor, cx| {
+                        editor.change_selections(
+                            SelectionEffects::no_scroll().nav_history(false),
+                            window,
+                            cx,
+                            |s| s.select_anchor_ranges([cursor..cursor]),
+                        );
+                    });
+                })?;
+                let task = workspace.update_in(cx, |workspace, window, cx| {
+                    workspace.send_keystrokes_impl(keystrokes.clone(), window, cx)
+                })?;
+                task.await;
+                vim.update_in(cx, |vim, window, cx| {
+                    if matches!(vim.mode, Mode::Insert | Mode::Replace) {
+                        vim.normal_before(&Default::default(), window, cx);
+                    } else {
+                        vim.switch_mode(Mode::Normal, true, window, cx);
                     }
-                });
-                if matches!(vim.mode, Mode::Insert | Mode::Replace) {
-                    vim.normal_before(&Default::default(), window, cx);
-                } else {
-                    vim.switch_mode(Mode::Normal, true, window, cx);
-                }
+                })?;
+            }
+            vim.update_in(cx, |vim, _, cx| {
                 vim.update_editor(cx, |_, editor, cx| {
+                    if grouped {
+                        editor.end_transaction_at(Instant::now(), cx);
+                    }
                     if let Some(first_sel) = initial_selections
                         && let Some(tx_id) = editor
                             .buffer()
@@ -574,9 +607,8 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
                     }
                 });
             })
-            .ok();
         })
-        .detach();
+        .detach_and_log_err(cx);
     });
 
     Vim::action(editor, cx, |vim, _: &CountCommand, window, cx| {
@@ -703,6 +735,22 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
 
     Vim::action(editor, cx, |vim, action: &ShellExec, window, cx| {
         action.run(vim, window, cx)
+    });
+
+    Vim::action(editor, cx, |vim, action: &MoveLines, window, cx| {
+        action.run(vim, window, cx)
+    });
+
+    Vim::action(editor, cx, |vim, action: &PutLines, window, cx| {
+        action.run(vim, window, cx)
+    });
+
+    Vim::action(editor, cx, |vim, action: &ReadFile, window, cx| {
+        action.run(vim, window, cx)
+    });
+
+    Vim::action(editor, cx, |vim, action: &ShiftLines, window, cx| {
+        action.run(vim, window, cx)
     })
 }
 
@@ -821,7 +869,6 @@ impl VimCommand {
         }
     }
 
-    // TODO: ranges with search queries
     fn parse_range(query: &str) -> (Option<CommandRange>, String) {
         let mut chars = query.chars().peekable();
 
@@ -907,6 +954,21 @@ impl VimCommand {
                     offset: Self::parse_offset(chars),
                 })
             }
+            '/' | '?' => {
+                // Only a closed pattern is an address, so that `:/foo` still searches for `foo`.
+                let mut lookahead = chars.clone();
+                let delimiter = lookahead.next()?;
+                let (pattern, closed) = parse_pattern(&mut lookahead, delimiter);
+                if !closed {
+                    return None;
+                }
+                *chars = lookahead;
+                Some(Position::Pattern {
+                    pattern,
+                    backwards: delimiter == '?',
+                    offset: Self::parse_offset(chars),
+                })
+            }
             _ => None,
         }
     }
@@ -938,10 +1000,26 @@ impl VimCommand {
 
 #[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq)]
 enum Position {
-    Line { row: u32, offset: i32 },
-    Mark { name: char, offset: i32 },
-    LastLine { offset: i32 },
-    CurrentLine { offset: i32 },
+    Line {
+        row: u32,
+        offset: i32,
+    },
+    Mark {
+        name: char,
+        offset: i32,
+    },
+    LastLine {
+        offset: i32,
+    },
+    CurrentLine {
+        offset: i32,
+    },
+    /// The next line matching a pattern, searching backwards for `?pattern?`.
+    Pattern {
+        pattern: String,
+        backwards: bool,
+        offset: i32,
+    },
 }
 
 impl Position {
@@ -952,22 +1030,54 @@ impl Position {
         window: &mut Window,
         cx: &mut App,
     ) -> Result<MultiBufferRow> {
+        let row = self.row(vim, editor, window, cx)?;
+        let max_row = editor.buffer().read(cx).snapshot(cx).max_row();
+        Ok(MultiBufferRow(row.max(0) as u32).min(max_row))
+    }
+
+    /// Returns the row that lines should go below when this is the destination of a command like
+    /// `:move`, or `None` when they should go above the first line, which is addressed as line 0.
+    fn destination(
+        &self,
+        vim: &Vim,
+        editor: &mut Editor,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Result<Option<MultiBufferRow>> {
+        let row = self.row(vim, editor, window, cx)?;
+        if row < 0 {
+            return Ok(None);
+        }
+        let max_row = editor.buffer().read(cx).snapshot(cx).max_row();
+        Ok(Some(MultiBufferRow(row as u32).min(max_row)))
+    }
+
+    /// Returns the zero-based row of the position, which is negative for line 0 and may be past the
+    /// end of the buffer.
+    fn row(
+        &self,
+        vim: &Vim,
+        editor: &mut Editor,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Result<i64> {
         let snapshot = editor.snapshot(window, cx);
-        let target = match self {
+        let (row, offset) = match self {
+            Position::Line { row: 0, offset } => (-1, offset),
             Position::Line { row, offset } => {
                 if let Some(anchor) = editor.active_excerpt(cx).and_then(|(_, buffer, _)| {
                     editor.buffer().read(cx).buffer_point_to_anchor(
                         &buffer,
-                        Point::new(row.saturating_sub(1), 0),
+                        Point::new(row - 1, 0),
                         cx,
                     )
                 }) {
-                    anchor
-                        .to_point(&snapshot.buffer_snapshot)
-                        .row
-                        .saturating_add_signed(*offset)
+                    (
+                        anchor.to_point(&snapshot.buffer_snapshot).row as i64,
+                        offset,
+                    )
                 } else {
-                    row.saturating_add_signed(offset.saturating_sub(1))
+                    (*row as i64 - 1, offset)
                 }
             }
             Position::Mark { name, offset } => {
@@ -979,25 +1089,61 @@ impl Position {
                 let Some(mark) = anchors.last() else {
                     anyhow::bail!("mark {name} contains empty anchors");
                 };
-                mark.to_point(&snapshot.buffer_snapshot)
-                    .row
-                    .saturating_add_signed(*offset)
+                (mark.to_point(&snapshot.buffer_snapshot).row as i64, offset)
+            }
+            Position::LastLine { offset } => (snapshot.buffer_snapshot.max_row().0 as i64, offset),
+            Position::CurrentLine { offset } => (
+                editor
+                    .selections
+                    .newest_anchor()
+                    .head()
+                    .to_point(&snapshot.buffer_snapshot)
+                    .row as i64,
+                offset,
+            ),
+            Position::Pattern {
+                pattern,
+                backwards,
+                offset,
+            } => {
+                if pattern.is_empty() {
+                    anyhow::bail!("no previous regular expression");
+                }
+                let regex = Regex::new(pattern)?;
+                let buffer = &snapshot.buffer_snapshot;
+                let current = editor
+                    .selections
+                    .newest_anchor()
+                    .head()
+                    .to_point(buffer)
+                    .row;
+                let row_count = buffer.max_row().0 + 1;
+                // The search starts on the line after the cursor, and wraps around the buffer.
+                let row = (1..=row_count)
+                    .map(|step| {
+                        if *backwards {
+                            (current + row_count - step) % row_count
+                        } else {
+                            (current + step) % row_count
+                        }
+                    })
+                    .find(|row| {
+                        let line = buffer
+                            .text_for_range(
+                                Point::new(*row, 0)
+                                    ..Point::new(*row, buffer.line_len(MultiBufferRow(*row))),
+                            )
+                            .collect::<String>();
+                        regex.is_match(&line)
+                    });
+                let Some(row) = row else {
+                    anyhow::bail!("pattern not found: {pattern}");
+                };
+                (row as i64, offset)
             }
-            Position::LastLine { offset } => snapshot
-                .buffer_snapshot
-                .max_row()
-                .0
-                .saturating_add_signed(*offset),
-            Position::CurrentLine { offset } => editor
-                .selections
-                .newest_anchor()
-                .head()
-                .to_point(&snapshot.buffer_snapshot)
-                .row
-                .saturating_add_signed(*offset),
         };
 
-        Ok(MultiBufferRow(target).min(snapshot.buffer_snapshot.max_row()))
+        Ok(row + *offset as i64)
     }
 }
 
@@ -1046,7 +1192,7 @@ impl CommandRange {
 }
 
 fn generate_commands(_: &App) -> Vec<VimCommand> {
-    vec![
+    let mut commands = vec![
         VimCommand::new(
             ("w", "rite"),
             workspace::Save {
@@ -1303,6 +1449,45 @@ fn generate_commands(_: &App) -> Vec<VimCommand> {
                 .boxed_clone(),
             )
         }),
+        VimCommand::new(
+            ("pu", "t"),
+            PutLines {
+                range: None,
+                register: None,
+                above: false,
+            },
+        )
+        .bang(PutLines {
+            range: None,
+            register: None,
+            above: true,
+        })
+        .args(|action, args| {
+            let mut action = action.as_any().downcast_ref::<PutLines>()?.clone();
+            let mut chars = args.chars();
+            action.register = Some(chars.next()?);
+            chars.next().is_none().then(|| action.boxed_clone())
+        })
+        .range(|action, range| {
+            let mut action = action.as_any().downcast_ref::<PutLines>()?.clone();
+            action.range = Some(range.clone());
+            Some(action.boxed_clone())
+        }),
+        VimCommand::new(("r", "ead"), ArgumentRequired)
+            .args(|_, filename| {
+                Some(
+                    ReadFile {
+                        range: None,
+                        filename,
+                    }
+                    .boxed_clone(),
+                )
+            })
+            .range(|action, range| {
+                let mut action = action.as_any().downcast_ref::<ReadFile>()?.clone();
+                action.range = Some(range.clone());
+                Some(action.boxed_clone())
+            }),
         VimCommand::new(("reg", "isters"), ToggleRegistersView).bang(ToggleRegistersView),
         VimCommand::new(("di", "splay"), ToggleRegistersView).bang(ToggleRegistersView),
         VimCommand::new(("marks", ""), ToggleMarksView).bang(ToggleMarksView),
@@ -1333,9 +1518,55 @@ fn generate_commands(_: &App) -> Vec<VimCommand> {
         VimCommand::new(("ex", ""), editor::actions::ReloadFile).bang(editor::actions::ReloadFile),
         VimCommand::new(("cpp", "link"), editor::actions::CopyPermalinkToLine).range(act_on_range),
         VimCommand::str(("opt", "ions"), "zed::OpenDefaultSettings"),
-        VimCommand::str(("map", ""), "vim::OpenDefaultKeymap"),
+        VimCommand::new(("so", "urce"), ArgumentRequired)
+            .args(|_, path| Some(SourceCommand { path }.boxed_clone())),
         VimCommand::new(("h", "elp"), OpenDocs),
-    ]
+    ];
+    commands.extend(
+        mapping::MAP_COMMANDS
+            .iter()
+            .map(|&(prefix, suffix, kind, modes)| {
+                let command = VimCommand::new(
+                    (prefix, suffix),
+                    MapCommand {
+                        kind,
+                        modes,
+                        args: String::new(),
+                    },
+                )
+                .args(|action, args| {
+                    let mut action = action.as_any().downcast_ref::<MapCommand>()?.clone();
+                    action.args = args;
+                    Some(action.boxed_clone())
+                });
+                match mapping::bang_modes(modes) {
+                    Some(modes) => command.bang(MapCommand {
+                        kind,
+                        modes,
+                        args: String::new(),
+                    }),
+                    None => command,
+                }
+            }),
+    );
+    commands.extend(abbreviation::ABBREVIATE_COMMANDS.iter().map(
+        |&(prefix, suffix, kind, modes)| {
+            VimCommand::new(
+                (prefix, suffix),
+                AbbreviateCommand {
+                    kind,
+                    modes,
+                    args: String::new(),
+                },
+            )
+            .args(|action, args| {
+                let mut action = action.as_any().downcast_ref::<AbbreviateCommand>()?.clone();
+                action.args = args;
+                Some(action.boxed_clone())
+            })
+        },
+    ));
+    commands
 }
 
 struct VimCommands(Vec<VimCommand>);
@@ -1389,6 +1620,8 @@ pub fn command_interceptor(mut input: &str, cx: &App) -> Vec<CommandInterceptRes
     while input.starts_with(':') {
         input = &input[1..];
     }
+    let input = abbreviation::expand_command_line(input, cx);
+    let input = input.as_str();
 
     let (range, query) = VimCommand::parse_range(input);
     let range_prefix = input[0..(input.len() - query.len())].to_string();
@@ -1459,6 +1692,10 @@ pub fn command_interceptor(mut input: &str, cx: &App) -> Vec<CommandInterceptRes
             end: Some(Position::LastLine { offset: 0 }),
         });
         OnMatchingLines::parse(query, invert, range, cx).map(|action| action.boxed_clone())
+    } else if query.starts_with('>') || query.starts_with('<') {
+        ShiftLines::parse(query, range.clone())
+    } else if let Some(action) = MoveLines::parse(query, range.clone()) {
+        Some(action)
     } else if query.contains('!') {
         ShellExec::parse(query, range.clone())
     } else {
@@ -1514,6 +1751,36 @@ fn generate_positions(string: &str, query: &str) -> Vec<usize> {
     positions
 }
 
+/// Reads a pattern up to its closing delimiter, returning it along with whether the delimiter was
+/// found. Escaped and unescaped parentheses are flipped, since only the escaped ones group in vim.
+fn parse_pattern(chars: &mut Peekable<Chars>, delimiter: char) -> (String, bool) {
+    let mut pattern = String::new();
+    let mut escaped = false;
+
+    for c in chars.by_ref() {
+        if escaped {
+            escaped = false;
+            // unescape escaped parens
+            if c != '(' && c != ')' && c != delimiter {
+                pattern.push('\\')
+            }
+            pattern.push(c)
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == delimiter {
+            return (pattern, true);
+        } else {
+            // escape unescaped parens
+            if c == '(' || c == ')' {
+                pattern.push('\\')
+            }
+            pattern.push(c)
+        }
+    }
+
+    (pattern, false)
+}
+
 /// Applies a command to all lines matching a pattern.
 #[derive(Debug, PartialEq, Clone, Action)]
 #[action(namespace = vim, no_json, no_register)]
@@ -1539,30 +1806,7 @@ impl OnMatchingLines {
             !c.is_alphanumeric() && *c != '"' && *c != '|' && *c != '\'' && *c != '!'
         })?;
 
-        let mut search = String::new();
-        let mut escaped = false;
-
-        for c in chars.by_ref() {
-            if escaped {
-                escaped = false;
-                // unescape escaped parens
-                if c != '(' && c != ')' && c != delimiter {
-                    search.push('\\')
-                }
-                search.push(c)
-            } else if c == '\\' {
-                escaped = true;
-            } else if c == delimiter {
-                break;
-            } else {
-                // escape unescaped parens
-                if c == '(' || c == ')' {
-                    search.push('\\')
-                }
-                search.push(c)
-            }
-        }
-
+        let (search, _) = parse_pattern(&mut chars, delimiter);
         let command: String = chars.collect();
 
         let action = WrappedAction(
@@ -1714,6 +1958,386 @@ impl OnMatchingLines {
     }
 }
 
+/// Moves the lines in a range below the line given by an address, or copies them with `copy`.
+#[derive(Clone, Debug, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub(crate) struct MoveLines {
+    range: Option<CommandRange>,
+    address: Position,
+    copy: bool,
+}
+
+impl MoveLines {
+    fn parse(query: &str, range: Option<CommandRange>) -> Option<Box<dyn Action>> {
+        let (rest, copy) = strip_command(query, ("m", "ove"))
+            .map(|rest| (rest, false))
+            .or_else(|| strip_command(query, ("co", "py")).map(|rest| (rest, true)))
+            .or_else(|| strip_command(query, ("t", "")).map(|rest| (rest, true)))?;
+        let mut chars = rest.trim().chars().peekable();
+        let address = VimCommand::parse_position(&mut chars)?;
+        if chars.next().is_some() {
+            return None;
+        }
+        Some(
+            MoveLines {
+                range,
+                address,
+                copy,
+            }
+            .boxed_clone(),
+        )
+    }
+
+    fn run(&self, vim: &mut Vim, window: &mut Window, cx: &mut Context<Vim>) {
+        vim.run_on_lines(
+            self.range.as_ref(),
+            window,
+            cx,
+            |vim, editor, range, window, cx| {
+                let rows = range.buffer_range(vim, editor, window, cx)?;
+                let below = self.address.destination(vim, editor, window, cx)?;
+                let column = editor.selections.newest::<Point>(cx).head().column;
+                let inserted = if self.copy {
+                    let snapshot = editor.buffer().read(cx).snapshot(cx);
+                    insert_lines(editor, below, &lines_text(&snapshot, &rows), cx)
+                } else {
+                    move_lines(editor, rows, below, cx)?
+                };
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                let row = inserted.end.to_point(&snapshot).row;
+                let point = snapshot.clip_point(Point::new(row, column), Bias::Left);
+                editor.change_selections(Default::default(), window, cx, |s| {
+                    s.select_ranges([point..point]);
+                });
+                Ok(())
+            },
+        );
+    }
+}
+
+/// Puts the text of a register on new lines below a line, or above it with `above`.
+#[derive(Clone, Debug, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub(crate) struct PutLines {
+    range: Option<CommandRange>,
+    register: Option<char>,
+    above: bool,
+}
+
+impl PutLines {
+    fn run(&self, vim: &mut Vim, window: &mut Window, cx: &mut Context<Vim>) {
+        vim.run_on_lines(
+            self.range.as_ref(),
+            window,
+            cx,
+            |vim, editor, range, window, cx| {
+                let register = Vim::update_globals(cx, |globals, cx| {
+                    globals.read_register(self.register, Some(editor), cx)
+                })
+                .filter(|register| !register.text.is_empty());
+                let Some(register) = register else {
+                    anyhow::bail!("nothing in register {}", self.register.unwrap_or('"'));
+                };
+                let text = register.text.strip_suffix('\n').unwrap_or(&register.text);
+                let below = put_destination(vim, editor, range, self.above, window, cx)?;
+                let inserted = insert_lines(editor, below, text, cx);
+                select_first_non_blank(editor, inserted.end, window, cx);
+                Ok(())
+            },
+        );
+    }
+}
+
+/// Reads a file into new lines below a line.
+#[derive(Clone, Debug, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub(crate) struct ReadFile {
+    range: Option<CommandRange>,
+    filename: String,
+}
+
+impl ReadFile {
+    fn run(&self, vim: &mut Vim, window: &mut Window, cx: &mut Context<Vim>) {
+        let Some(workspace) = vim.workspace(window) else {
+            return;
+        };
+        let project = workspace.read(cx).project().clone();
+        let buffer = project
+            .read(cx)
+            .find_project_path(&self.filename, cx)
+            .map(|path| project.update(cx, |project, cx| project.open_buffer(path, cx)));
+        let filename = self.filename.clone();
+        let range = self.range.clone();
+        cx.spawn_in(window, async move |vim, cx| {
+            let Some(buffer) = buffer else {
+                anyhow::bail!("can't open file {filename}");
+            };
+            let buffer = buffer.await?;
+            let text = buffer.read_with(cx, |buffer, _| buffer.text())?;
+            vim.update_in(cx, |vim, window, cx| {
+                let text = text.strip_suffix('\n').unwrap_or(&text);
+                vim.run_on_lines(
+                    range.as_ref(),
+                    window,
+                    cx,
+                    |vim, editor, range, window, cx| {
+                        let below = put_destination(vim, editor, range, false, window, cx)?;
+                        let inserted = insert_lines(editor, below, text, cx);
+                        select_first_non_blank(editor, inserted.start, window, cx);
+                        Ok(())
+                    },
+                );
+            })
+        })
+        .detach_and_notify_err(window, cx);
+    }
+}
+
+/// Shifts the lines in a range by a number of indents, to the left with `:<`.
+#[derive(Clone, Debug, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub(crate) struct ShiftLines {
+    range: Option<CommandRange>,
+    count: Option<u32>,
+    amount: usize,
+    left: bool,
+}
+
+impl ShiftLines {
+    fn parse(query: &str, range: Option<CommandRange>) -> Option<Box<dyn Action>> {
+        let direction = query.chars().next().filter(|c| *c == '>' || *c == '<')?;
+        let rest = query.trim_start_matches(direction);
+        let amount = query.len() - rest.len();
+        let rest = rest.trim();
+        let count = if rest.is_empty() {
+            None
+        } else {
+            Some(rest.parse::<u32>().ok().filter(|count| *count > 0)?)
+        };
+        Some(
+            ShiftLines {
+                range,
+                count,
+                amount,
+                left: direction == '<',
+            }
+            .boxed_clone(),
+        )
+    }
+
+    fn run(&self, vim: &mut Vim, window: &mut Window, cx: &mut Context<Vim>) {
+        vim.run_on_lines(
+            self.range.as_ref(),
+            window,
+            cx,
+            |vim, editor, range, window, cx| {
+                let mut rows = range.buffer_range(vim, editor, window, cx)?;
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                // A count is the number of lines to shift, from the last line of the range.
+                if let Some(count) = self.count {
+                    rows.start = rows.end;
+                    rows.end = MultiBufferRow(rows.end.0.saturating_add(count - 1))
+                        .min(snapshot.max_row());
+                }
+                editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                    s.select_ranges([Point::new(rows.start.0, 0)
+                        ..Point::new(rows.end.0, snapshot.line_len(rows.end))]);
+                });
+                for _ in 0..self.amount {
+                    if self.left {
+                        editor.outdent(&Default::default(), window, cx);
+                    } else {
+                        editor.indent(&Default::default(), window, cx);
+                    }
+                }
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                let end = snapshot.anchor_before(Point::new(rows.end.0, 0));
+                select_first_non_blank(editor, end, window, cx);
+                Ok(())
+            },
+        );
+    }
+}
+
+impl Vim {
+    /// Runs a command that acts on lines, once for its range or, without one, once for each
+    /// cursor with the cursor on its own. This is how the commands run by `:g` act on each of the
+    /// lines that matched, even when they move lines around.
+    fn run_on_lines(
+        &mut self,
+        range: Option<&CommandRange>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        mut f: impl FnMut(
+            &Vim,
+            &mut Editor,
+            &CommandRange,
+            &mut Window,
+            &mut Context<Editor>,
+        ) -> Result<()>,
+    ) {
+        self.switch_mode(Mode::Normal, false, window, cx);
+        let result = self.update_editor(cx, |vim, editor, cx| {
+            let current_line = CommandRange {
+                start: Position::CurrentLine { offset: 0 },
+                end: None,
+            };
+            let cursors = if range.is_some() {
+                vec![None]
+            } else {
+                editor
+                    .selections
+                    .disjoint_anchors_arc()
+                    .iter()
+                    .map(|selection| Some(selection.head()))
+                    .collect()
+            };
+            let mut result = Ok(());
+            editor.transact(window, cx, |editor, window, cx| {
+                for cursor in cursors {
+                    if let Some(cursor) = cursor {
+                        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                            s.select_anchor_ranges([cursor..cursor]);
+                        });
+                    }
+                    result = f(vim, editor, range.unwrap_or(&current_line), window, cx);
+                    if result.is_err() {
+                        break;
+                    }
+                }
+            });
+            result
+        });
+        if let Some(e @ Err(_)) = result {
+            let Some(workspace) = self.workspace(window) else {
+                return;
+            };
+            workspace.update(cx, |workspace, cx| {
+                e.notify_err(workspace, cx);
+            });
+        }
+    }
+}
+
+/// Strips a command that can be abbreviated down to its prefix from the start of a query, for the
+/// commands that can be followed by their arguments without a space.
+fn strip_command<'a>(query: &'a str, (prefix, suffix): (&str, &str)) -> Option<&'a str> {
+    let rest = query.strip_prefix(prefix)?;
+    let matched = rest
+        .chars()
+        .zip(suffix.chars())
+        .take_while(|(a, b)| a == b)
+        .count();
+    Some(&rest[matched..])
+}
+
+/// Returns the row that lines put at the head of a range should go below, or `None` when they
+/// should go above the first line.
+fn put_destination(
+    vim: &Vim,
+    editor: &mut Editor,
+    range: &CommandRange,
+    above: bool,
+    window: &mut Window,
+    cx: &mut App,
+) -> Result<Option<MultiBufferRow>> {
+    let below = range.head().destination(vim, editor, window, cx)?;
+    Ok(if above {
+        below.and_then(|row| row.0.checked_sub(1).map(MultiBufferRow))
+    } else {
+        below
+    })
+}
+
+/// Returns the text of the lines in a range, without a trailing newline.
+fn lines_text(snapshot: &MultiBufferSnapshot, rows: &Range<MultiBufferRow>) -> String {
+    snapshot
+        .text_for_range(
+            Point::new(rows.start.0, 0)..Point::new(rows.end.0, snapshot.line_len(rows.end)),
+        )
+        .collect()
+}
+
+/// Inserts text as new lines below a row, or above the first line when it's `None`, returning
+/// anchors at the start of the first inserted line and at the end of the last one.
+fn insert_lines(
+    editor: &mut Editor,
+    below: Option<MultiBufferRow>,
+    text: &str,
+    cx: &mut Context<Editor>,
+) -> Range<Anchor> {
+    let snapshot = editor.buffer().read(cx).snapshot(cx);
+    let (point, new_text, first_row) = match below {
+        Some(row) => (
+            Point::new(row.0, snapshot.line_len(row)),
+            format!("\n{text}"),
+            row.0 + 1,
+        ),
+        None => (Point::zero(), format!("{text}\n"), 0),
+    };
+    let last_row = first_row + text.matches('\n').count() as u32;
+    editor.edit([(point..point, new_text)], cx);
+    let snapshot = editor.buffer().read(cx).snapshot(cx);
+    snapshot.anchor_before(Point::new(first_row, 0))
+        ..snapshot.anchor_after(Point::new(
+            last_row,
+            snapshot.line_len(MultiBufferRow(last_row)),
+        ))
+}
+
+/// Moves lines below a row, or above the first line when it's `None`, returning anchors at the
+/// start and end of the lines in their new place.
+fn move_lines(
+    editor: &mut Editor,
+    rows: Range<MultiBufferRow>,
+    below: Option<MultiBufferRow>,
+    cx: &mut Context<Editor>,
+) -> Result<Range<Anchor>> {
+    let snapshot = editor.buffer().read(cx).snapshot(cx);
+    let start = Point::new(rows.start.0, 0);
+    let end = Point::new(rows.end.0, snapshot.line_len(rows.end));
+    match below {
+        Some(row) if rows.start <= row && row < rows.end => {
+            anyhow::bail!("cannot move a range of lines into itself")
+        }
+        // Moving lines right below the line above them, or below themselves, leaves them in place.
+        Some(row) if row == rows.end || row.0 + 1 == rows.start.0 => {
+            return Ok(snapshot.anchor_before(start)..snapshot.anchor_after(end));
+        }
+        None if rows.start.0 == 0 => {
+            return Ok(snapshot.anchor_before(start)..snapshot.anchor_after(end));
+        }
+        _ => {}
+    }
+
+    // Delete the lines along with the newline that separates them from the rest of the buffer.
+    let deletion = if rows.end < snapshot.max_row() {
+        start..Point::new(rows.end.0 + 1, 0)
+    } else {
+        let previous_row = MultiBufferRow(rows.start.0 - 1);
+        Point::new(previous_row.0, snapshot.line_len(previous_row))..end
+    };
+    let deletion = snapshot.anchor_after(deletion.start)..snapshot.anchor_before(deletion.end);
+    let inserted = insert_lines(editor, below, &lines_text(&snapshot, &rows), cx);
+    editor.edit([(deletion, "")], cx);
+    Ok(inserted)
+}
+
+/// Moves the cursor to the first non-blank character of a line.
+fn select_first_non_blank(
+    editor: &mut Editor,
+    line: Anchor,
+    window: &mut Window,
+    cx: &mut Context<Editor>,
+) {
+    let snapshot = editor.buffer().read(cx).snapshot(cx);
+    let row = line.to_point(&snapshot).row;
+    let point = Point::new(row, snapshot.indent_size_for_line(MultiBufferRow(row)).len);
+    editor.change_selections(Default::default(), window, cx, |s| {
+        s.select_ranges([point..point]);
+    });
+}
+
 /// Executes a shell command and returns the output.
 #[derive(Clone, Debug, PartialEq, Action)]
 #[action(namespace = vim, no_json, no_register)]
@@ -2061,7 +2685,7 @@ mod test {
     use std::path::Path;
 
     use crate::{
-        VimAddon,
+        Vim, VimAddon,
         state::Mode,
         test::{NeovimBackedTestContext, VimTestContext},
     };
@@ -2521,6 +3145,156 @@ mod test {
         // Once ctrl-v to input character literals is added there should be a test for redo
     }
 
+    #[gpui::test]
+    async fn test_command_move(cx: &mut TestAppContext) {
+        let mut cx = NeovimBackedTestContext::new(cx).await;
+
+        cx.set_shared_state("ˇ1\n2\n3\n4\n5").await;
+        cx.simulate_shared_keystrokes(": m $ enter").await;
+        cx.shared_state().await.assert_eq("2\n3\n4\n5\nˇ1");
+
+        cx.simulate_shared_keystrokes(": 1 , 2 m 3 enter").await;
+        cx.shared_state().await.assert_eq("4\n2\nˇ3\n5\n1");
+
+        cx.simulate_shared_keystrokes(": m 0 enter").await;
+        cx.shared_state().await.assert_eq("ˇ3\n4\n2\n5\n1");
+
+        cx.simulate_shared_keystrokes(": / 5 / m 0 enter").await;
+        cx.shared_state().await.assert_eq("ˇ5\n3\n4\n2\n1");
+
+        cx.simulate_shared_keystrokes("j j m a g g : ' a , ' a + 1 m $ enter")
+            .await;
+        cx.shared_state().await.assert_eq("5\n3\n1\n4\nˇ2");
+    }
+
+    #[gpui::test]
+    async fn test_command_copy(cx: &mut TestAppContext) {
+        let mut cx = NeovimBackedTestContext::new(cx).await;
+
+        cx.set_shared_state("ˇa\nb\nc").await;
+        cx.simulate_shared_keystrokes(": t . enter").await;
+        cx.shared_state().await.assert_eq("a\nˇa\nb\nc");
+
+        cx.simulate_shared_keystrokes(": 1 , 2 c o $ enter").await;
+        cx.shared_state().await.assert_eq("a\na\nb\nc\na\nˇa");
+
+        cx.simulate_shared_keystrokes(": ? b ? t 0 enter").await;
+        cx.shared_state().await.assert_eq("ˇb\na\na\nb\nc\na\na");
+    }
+
+    #[gpui::test]
+    async fn test_command_put(cx: &mut TestAppContext) {
+        let mut cx = NeovimBackedTestContext::new(cx).await;
+
+        cx.set_shared_state("ˇone\ntwo\nthree").await;
+        cx.simulate_shared_keystrokes("y y j : p u enter").await;
+        cx.shared_state().await.assert_eq("one\ntwo\nˇone\nthree");
+
+        cx.simulate_shared_keystrokes(": 0 p u enter").await;
+        cx.shared_state()
+            .await
+            .assert_eq("ˇone\none\ntwo\none\nthree");
+
+        cx.simulate_shared_keystrokes(": $ p u ! enter").await;
+        cx.shared_state()
+            .await
+            .assert_eq("one\none\ntwo\none\nˇone\nthree");
+
+        cx.set_shared_state("ˇone\ntwo\nthree").await;
+        cx.simulate_shared_keystrokes("\" b y y j y y : p u space b enter")
+            .await;
+        cx.shared_state().await.assert_eq("one\ntwo\nˇone\nthree");
+
+        cx.set_shared_state("ˇone two\nthree").await;
+        cx.simulate_shared_keystrokes("y e : p u enter").await;
+        cx.shared_state().await.assert_eq("one two\nˇone\nthree");
+    }
+
+    #[gpui::test]
+    async fn test_command_pattern_range(cx: &mut TestAppContext) {
+        let mut cx = NeovimBackedTestContext::new(cx).await;
+
+        cx.set_shared_state("ˇa\nb\nc\nd\ne\nf").await;
+        cx.simulate_shared_keystrokes(": / c / enter").await;
+        cx.shared_state().await.assert_eq("a\nb\nˇc\nd\ne\nf");
+
+        cx.simulate_shared_keystrokes(": ? a ? + 1 , / e / - 1 d enter")
+            .await;
+        cx.shared_state().await.assert_eq("a\nˇe\nf");
+
+        cx.simulate_shared_keystrokes(": / a / enter").await;
+        cx.shared_state().await.assert_eq("ˇa\ne\nf");
+    }
+
+    #[gpui::test]
+    async fn test_command_matching_lines_commands(cx: &mut TestAppContext) {
+        let mut cx = NeovimBackedTestContext::new(cx).await;
+
+        cx.set_shared_state("ˇa1\nb\na2\nb\na3").await;
+        cx.simulate_shared_keystrokes(": g / a / n o r m space A ! enter")
+            .await;
+        cx.shared_state().await.assert_eq("a1!\nb\na2!\nb\na3ˇ!");
+
+        cx.set_shared_state("ˇa1\nb\na2\nb\na3").await;
+        cx.simulate_shared_keystrokes(": g / b / m 0 enter").await;
+        cx.shared_state().await.assert_eq("ˇb\nb\na1\na2\na3");
+
+        cx.set_shared_state("ˇ1\n2\n3").await;
+        cx.simulate_shared_keystrokes(": g / ^ / m 0 enter").await;
+        cx.shared_state().await.assert_eq("ˇ3\n2\n1");
+    }
+
+    #[gpui::test]
+    async fn test_command_shift(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇa\nb\nc", Mode::Normal);
+        cx.simulate_keystrokes(": 1 , 2 > enter");
+        cx.assert_state("    a\n    ˇb\nc", Mode::Normal);
+
+        cx.simulate_keystrokes(": % < enter");
+        cx.assert_state("a\nb\nˇc", Mode::Normal);
+
+        cx.simulate_keystrokes("g g : > > space 2 enter");
+        cx.assert_state("        a\n        ˇb\nc", Mode::Normal);
+    }
+
+    #[gpui::test]
+    async fn test_command_history(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇa\nb", Mode::Normal);
+        cx.simulate_keystrokes(": > enter");
+        cx.assert_state("    ˇa\nb", Mode::Normal);
+        cx.update(|_, cx| assert_eq!(Vim::globals(cx).command_history, [">"]));
+
+        cx.simulate_keystrokes("j @ :");
+        cx.assert_state("    a\n    ˇb", Mode::Normal);
+        cx.simulate_keystrokes("k @ @");
+        cx.assert_state("        ˇa\n    b", Mode::Normal);
+        cx.simulate_keystrokes("\" : p");
+        cx.assert_state("        aˇ>\n    b", Mode::Normal);
+    }
+
+    #[gpui::test]
+    async fn test_command_read(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        let fs = cx.workspace(|workspace, _, cx| workspace.project().read(cx).fs().clone());
+        fs.as_fake()
+            .insert_file(path!("/root/dir/other.txt"), b"x\ny\n".to_vec())
+            .await;
+        cx.run_until_parked();
+
+        cx.set_state("ˇa\nb", Mode::Normal);
+        cx.simulate_keystrokes(": r space o t h e r . t x t enter");
+        cx.run_until_parked();
+        cx.assert_state("a\nˇx\ny\nb", Mode::Normal);
+
+        cx.simulate_keystrokes(": 0 r space o t h e r . t x t enter");
+        cx.run_until_parked();
+        cx.assert_state("ˇx\ny\na\nx\ny\nb", Mode::Normal);
+    }
+
     #[gpui::test]
     async fn test_command_tabnew(cx: &mut TestAppContext) {
         let mut cx = VimTestContext::new(cx, true).await;
@@ -1,7 +1,9 @@
 mod boundary;
+mod manipulate;
 mod object;
 mod paste;
 mod select;
+mod surround;
 
 use editor::display_map::DisplaySnapshot;
 use editor::{
@@ -13,12 +15,13 @@ use gpui::{Context, Window};
 use language::{CharClassifier, CharKind, Point};
 use search::{BufferSearchBar, SearchOptions};
 use settings::Settings;
+use std::ops::Range;
 use text::{Bias, SelectionGoal};
 use workspace::searchable;
 use workspace::searchable::FilteredSearchRange;
 
 use crate::motion;
-use crate::state::SearchState;
+use crate::state::{HelixRegexSelection, SearchState};
 use crate::{
     Vim,
     motion::{Motion, right},
@@ -51,6 +54,18 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     Vim::action(editor, cx, Vim::helix_goto_last_modification);
     Vim::action(editor, cx, Vim::helix_paste);
     Vim::action(editor, cx, Vim::helix_select_regex);
+    manipulate::register(editor, cx);
+}
+
+/// Returns where an offset ends up after a set of edits, which are sorted and don't overlap.
+/// An offset at an insertion moves past the inserted text.
+fn offset_after_edits(offset: usize, edits: &[(Range<usize>, String)]) -> usize {
+    let delta = edits
+        .iter()
+        .take_while(|(range, _)| range.end <= offset)
+        .map(|(range, text)| text.len() as isize - range.len() as isize)
+        .sum::<isize>();
+    offset.saturating_add_signed(delta)
 }
 
 impl Vim {
@@ -382,6 +397,17 @@ impl Vim {
         _: &HelixSelectRegex,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) {
+        self.helix_regex_prompt(HelixRegexSelection::Select, window, cx);
+    }
+
+    /// Opens the search bar to search for a regex within the selections, which changes the
+    /// selections when it's submitted.
+    fn helix_regex_prompt(
+        &mut self,
+        selection: HelixRegexSelection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) {
         Vim::take_forced_motion(cx);
         let Some(pane) = self.pane(window, cx) else {
@@ -427,7 +453,7 @@ impl Vim {
                         prior_selections,
                         prior_operator: self.operator_stack.last().cloned(),
                         prior_mode: self.mode,
-                        helix_select: true,
+                        helix_select: Some(selection),
                     }
                 });
             }
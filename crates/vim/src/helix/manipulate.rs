@@ -0,0 +1,350 @@
+use std::{mem, ops::Range};
+
+use editor::{Editor, ToOffset};
+use gpui::{Context, Window, actions};
+use language::Point;
+use search::BufferSearchBar;
+
+use crate::{Vim, helix::offset_after_edits, state::HelixRegexSelection};
+
+actions!(
+    vim,
+    [
+        /// Splits the selections on the matches of a given pattern.
+        HelixSplitSelection,
+        /// Keeps the selections that match a given pattern.
+        HelixKeepSelections,
+        /// Removes the selections that match a given pattern.
+        HelixRemoveSelections,
+        /// Aligns the selections in columns by inserting spaces before them.
+        HelixAlignSelections,
+        /// Makes the previous selection the primary one.
+        HelixRotateSelectionsBackward,
+        /// Makes the next selection the primary one.
+        HelixRotateSelectionsForward,
+    ]
+);
+
+pub(super) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
+    Vim::action(editor, cx, |vim, _: &HelixSplitSelection, window, cx| {
+        vim.helix_regex_prompt(HelixRegexSelection::Split, window, cx)
+    });
+    Vim::action(editor, cx, |vim, _: &HelixKeepSelections, window, cx| {
+        vim.helix_regex_prompt(HelixRegexSelection::Keep, window, cx)
+    });
+    Vim::action(editor, cx, |vim, _: &HelixRemoveSelections, window, cx| {
+        vim.helix_regex_prompt(HelixRegexSelection::Remove, window, cx)
+    });
+    Vim::action(editor, cx, Vim::helix_align_selections);
+    Vim::action(
+        editor,
+        cx,
+        |vim, _: &HelixRotateSelectionsBackward, window, cx| {
+            vim.helix_rotate_selections(false, window, cx)
+        },
+    );
+    Vim::action(
+        editor,
+        cx,
+        |vim, _: &HelixRotateSelectionsForward, window, cx| {
+            vim.helix_rotate_selections(true, window, cx)
+        },
+    );
+}
+
+impl Vim {
+    /// Changes the selections with the matches of the submitted regex prompt.
+    pub(crate) fn helix_regex_submit(
+        &mut self,
+        selection: HelixRegexSelection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(pane) = self.pane(window, cx) else {
+            return;
+        };
+        let has_match = pane.update(cx, |pane, cx| {
+            let Some(search_bar) = pane.toolbar().read(cx).item_of_type::<BufferSearchBar>() else {
+                return false;
+            };
+            search_bar.update(cx, |search_bar, cx| {
+                let has_match = search_bar.has_active_match();
+                search_bar.select_all_matches(&Default::default(), window, cx);
+                has_match
+            })
+        });
+        if !has_match {
+            return;
+        }
+
+        let prior_selections = mem::take(&mut self.search.prior_selections);
+        self.update_editor(cx, |_, editor, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let matches = editor.selections.all::<usize>(cx);
+            let prior_ranges = prior_selections
+                .iter()
+                .map(|range| {
+                    let start = range.start.to_offset(&snapshot);
+                    let end = range.end.to_offset(&snapshot);
+                    start.min(end)..start.max(end)
+                })
+                .collect::<Vec<_>>();
+            let matches_within = |range: &Range<usize>| {
+                matches
+                    .iter()
+                    .filter(|selection| {
+                        range.start <= selection.start && selection.end <= range.end
+                    })
+                    .map(|selection| selection.range())
+                    .collect::<Vec<_>>()
+            };
+
+            let ranges = match selection {
+                // The matches are already selected.
+                HelixRegexSelection::Select => return,
+                HelixRegexSelection::Split => prior_ranges
+                    .iter()
+                    .flat_map(|range| {
+                        let mut ranges = Vec::new();
+                        let mut start = range.start;
+                        for matched in matches_within(range) {
+                            if matched.start > start {
+                                ranges.push(start..matched.start);
+                            }
+                            start = start.max(matched.end);
+                        }
+                        if range.end > start {
+                            ranges.push(start..range.end);
+                        }
+                        ranges
+                    })
+                    .collect::<Vec<_>>(),
+                HelixRegexSelection::Keep => prior_ranges
+                    .iter()
+                    .filter(|range| !matches_within(range).is_empty())
+                    .cloned()
+                    .collect(),
+                HelixRegexSelection::Remove => prior_ranges
+                    .iter()
+                    .filter(|range| matches_within(range).is_empty())
+                    .cloned()
+                    .collect(),
+            };
+            // Like helix, the selections are left alone rather than removing all of them.
+            let ranges = if ranges.is_empty() {
+                prior_ranges
+            } else {
+                ranges
+            };
+            editor.change_selections(Default::default(), window, cx, |s| {
+                s.select_ranges(ranges);
+            });
+        });
+    }
+
+    fn helix_align_selections(
+        &mut self,
+        _: &HelixAlignSelections,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(cx, |_, editor, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let mut selections = editor.selections.all::<usize>(cx);
+
+            // The columns of the selections on each row, where the nth selection of every row is
+            // aligned with the others.
+            let mut rows: Vec<(u32, Vec<(usize, usize)>)> = Vec::new();
+            for selection in &selections {
+                let start = snapshot.offset_to_point(selection.start);
+                if snapshot.offset_to_point(selection.end).row != start.row {
+                    return;
+                }
+                let column = snapshot
+                    .text_for_range(Point::new(start.row, 0)..start)
+                    .flat_map(str::chars)
+                    .count();
+                match rows.last_mut() {
+                    Some((row, columns)) if *row == start.row => {
+                        columns.push((selection.start, column))
+                    }
+                    _ => rows.push((start.row, vec![(selection.start, column)])),
+                }
+            }
+
+            let mut padding = vec![0; rows.len()];
+            let mut edits = Vec::new();
+            let max_columns = rows.iter().map(|(_, columns)| columns.len()).max();
+            for index in 0..max_columns.unwrap_or(0) {
+                let Some(target) = rows
+                    .iter()
+                    .zip(&padding)
+                    .filter_map(|((_, columns), padding)| Some(columns.get(index)?.1 + padding))
+                    .max()
+                else {
+                    continue;
+                };
+                for ((_, columns), padding) in rows.iter().zip(&mut padding) {
+                    if let Some(&(offset, column)) = columns.get(index)
+                        && column + *padding < target
+                    {
+                        let spaces = target - column - *padding;
+                        edits.push((offset..offset, " ".repeat(spaces)));
+                        *padding += spaces;
+                    }
+                }
+            }
+            if edits.is_empty() {
+                return;
+            }
+            edits.sort_unstable_by_key(|(range, _)| range.start);
+
+            for selection in &mut selections {
+                selection.start = offset_after_edits(selection.start, &edits);
+                selection.end = offset_after_edits(selection.end, &edits);
+            }
+            editor.transact(window, cx, |editor, window, cx| {
+                editor.edit(edits, cx);
+                editor.change_selections(Default::default(), window, cx, |s| {
+                    s.select(selections);
+                });
+            });
+        });
+    }
+
+    fn helix_rotate_selections(
+        &mut self,
+        forward: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let count = Vim::take_count(cx).unwrap_or(1);
+        self.update_editor(cx, |_, editor, cx| {
+            let mut selections = editor.selections.all::<usize>(cx);
+            let newest_id = editor.selections.newest::<usize>(cx).id;
+            let Some(index) = selections
+                .iter()
+                .position(|selection| selection.id == newest_id)
+            else {
+                return;
+            };
+            let len = selections.len();
+            let index = if forward {
+                (index + count) % len
+            } else {
+                (index + len - count % len) % len
+            };
+            // The newest selection is the primary one.
+            editor.change_selections(Default::default(), window, cx, |s| {
+                selections[index].id = s.new_selection_id();
+                s.select(selections);
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use indoc::indoc;
+    use language::Point;
+
+    use crate::{state::Mode, test::VimTestContext};
+
+    #[gpui::test]
+    async fn test_helix_split_selection(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.enable_helix();
+
+        cx.set_state("«one, two, threeˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("shift-s ,");
+        cx.run_until_parked();
+        cx.simulate_keystrokes("enter");
+        cx.assert_state("«oneˇ»,« twoˇ»,« threeˇ»", Mode::HelixNormal);
+
+        cx.set_state("«one,ˇ» «two,ˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("shift-s o");
+        cx.run_until_parked();
+        cx.simulate_keystrokes("enter");
+        cx.assert_state("o«ne,ˇ» «twˇ»o«,ˇ»", Mode::HelixNormal);
+    }
+
+    #[gpui::test]
+    async fn test_helix_keep_and_remove_selections(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.enable_helix();
+
+        cx.set_state("«oneˇ» «twoˇ» «threeˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("shift-k t");
+        cx.run_until_parked();
+        cx.simulate_keystrokes("enter");
+        cx.assert_state("one «twoˇ» «threeˇ»", Mode::HelixNormal);
+
+        cx.set_state("«oneˇ» «twoˇ» «threeˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("alt-shift-k t");
+        cx.run_until_parked();
+        cx.simulate_keystrokes("enter");
+        cx.assert_state("«oneˇ» two three", Mode::HelixNormal);
+
+        // Removing every selection leaves them as they were.
+        cx.set_state("«oneˇ» «twoˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("alt-shift-k o");
+        cx.run_until_parked();
+        cx.simulate_keystrokes("enter");
+        cx.assert_state("«oneˇ» «twoˇ»", Mode::HelixNormal);
+    }
+
+    #[gpui::test]
+    async fn test_helix_align_selections(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.enable_helix();
+
+        cx.set_state(
+            indoc! {"
+            a ˇ= 1 ˇ// one
+            bbb ˇ= 22 ˇ// two
+            cc ˇ= 3"},
+            Mode::HelixNormal,
+        );
+        cx.simulate_keystrokes("&");
+        cx.assert_state(
+            indoc! {"
+            a   ˇ= 1  ˇ// one
+            bbb ˇ= 22 ˇ// two
+            cc  ˇ= 3"},
+            Mode::HelixNormal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_helix_rotate_selections(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.enable_helix();
+
+        cx.set_state("«oneˇ» «twoˇ» «threeˇ»", Mode::HelixNormal);
+        let primary = |cx: &mut VimTestContext| {
+            cx.update_editor(|editor, _, cx| editor.selections.newest::<Point>(cx).head())
+        };
+        assert_eq!(primary(&mut cx), Point::new(0, 13));
+        cx.simulate_keystrokes(")");
+        assert_eq!(primary(&mut cx), Point::new(0, 3));
+        cx.simulate_keystrokes(")");
+        assert_eq!(primary(&mut cx), Point::new(0, 7));
+        cx.simulate_keystrokes("( (");
+        assert_eq!(primary(&mut cx), Point::new(0, 13));
+        cx.assert_state("«oneˇ» «twoˇ» «threeˇ»", Mode::HelixNormal);
+    }
+
+    #[gpui::test]
+    async fn test_helix_expand_and_shrink_selection(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.enable_helix();
+
+        cx.set_state("fn a() { «fooˇ»(1, 2); }", Mode::HelixNormal);
+        cx.simulate_keystrokes("alt-o");
+        cx.run_until_parked();
+        cx.assert_state("fn a() { «foo(1, 2)ˇ»; }", Mode::HelixNormal);
+        cx.simulate_keystrokes("alt-i");
+        cx.run_until_parked();
+        cx.assert_state("fn a() { «fooˇ»(1, 2); }", Mode::HelixNormal);
+    }
+}
@@ -0,0 +1,276 @@
+use std::ops::Range;
+
+use editor::MultiBufferSnapshot;
+use gpui::{Context, Window};
+
+use crate::{Vim, helix::offset_after_edits};
+
+/// Returns the pair of characters that surround text for a character typed in match mode.
+/// Brackets can be typed either way round, and any other character surrounds on both sides.
+fn surround_pair(char: char) -> (char, char) {
+    match char {
+        '(' | ')' => ('(', ')'),
+        '[' | ']' => ('[', ']'),
+        '{' | '}' => ('{', '}'),
+        '<' | '>' => ('<', '>'),
+        char => (char, char),
+    }
+}
+
+/// Finds the offsets of the closest pair of characters surrounding a range.
+fn find_surround_pair(
+    snapshot: &MultiBufferSnapshot,
+    range: Range<usize>,
+    (open, close): (char, char),
+) -> Option<(usize, usize)> {
+    let open_offset = if snapshot.chars_at(range.start).next() == Some(open) && open != close {
+        range.start
+    } else {
+        let mut depth = 0;
+        let mut offset = range.start;
+        let mut found = None;
+        for char in snapshot.reversed_chars_at(range.start) {
+            offset -= char.len_utf8();
+            if char == open {
+                if depth == 0 {
+                    found = Some(offset);
+                    break;
+                }
+                depth -= 1;
+            } else if char == close {
+                depth += 1;
+            }
+        }
+        match found {
+            Some(offset) => offset,
+            // A pair of the same characters can start at the cursor.
+            None if open == close && snapshot.chars_at(range.start).next() == Some(open) => {
+                range.start
+            }
+            None => return None,
+        }
+    };
+
+    let mut depth = 0;
+    let mut offset = open_offset + open.len_utf8();
+    for char in snapshot.chars_at(offset) {
+        if char == close && depth == 0 && offset + char.len_utf8() >= range.end {
+            return Some((open_offset, offset));
+        } else if char == close && open != close {
+            depth -= 1;
+        } else if char == open && open != close {
+            depth += 1;
+        }
+        if depth < 0 {
+            return None;
+        }
+        offset += char.len_utf8();
+    }
+    None
+}
+
+impl Vim {
+    pub fn helix_surround_add(&mut self, char: char, window: &mut Window, cx: &mut Context<Self>) {
+        let (open, close) = surround_pair(char);
+        self.update_editor(cx, |_, editor, cx| {
+            editor.transact(window, cx, |editor, window, cx| {
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                let mut edits = Vec::new();
+                let mut ranges = Vec::new();
+                for selection in editor.selections.all::<usize>(cx) {
+                    let mut range = selection.range();
+                    if range.is_empty() {
+                        range.end = next_char_offset(&snapshot, range.start);
+                    }
+                    edits.push((range.start..range.start, open.to_string()));
+                    edits.push((range.end..range.end, close.to_string()));
+                    ranges.push(
+                        snapshot.anchor_before(range.start)..snapshot.anchor_after(range.end),
+                    );
+                }
+
+                editor.edit(edits, cx);
+                editor.change_selections(Default::default(), window, cx, |s| {
+                    s.select_anchor_ranges(ranges);
+                });
+            });
+        });
+    }
+
+    pub fn helix_surround_replace(
+        &mut self,
+        replaced_char: char,
+        char: char,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let (open, close) = surround_pair(char);
+        self.helix_edit_surround_pairs(
+            surround_pair(replaced_char),
+            open.to_string(),
+            close.to_string(),
+            window,
+            cx,
+        );
+    }
+
+    pub fn helix_surround_delete(
+        &mut self,
+        char: char,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.helix_edit_surround_pairs(
+            surround_pair(char),
+            String::new(),
+            String::new(),
+            window,
+            cx,
+        );
+    }
+
+    /// Replaces the pair surrounding each selection, doing nothing if a selection isn't
+    /// surrounded by the pair.
+    fn helix_edit_surround_pairs(
+        &mut self,
+        pair: (char, char),
+        open: String,
+        close: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(cx, |_, editor, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let mut selections = editor.selections.all::<usize>(cx);
+            let Some(mut pairs) = selections
+                .iter()
+                .map(|selection| {
+                    let mut range = selection.range();
+                    if range.is_empty() {
+                        // The cursor covers the character it's on.
+                        range.end = next_char_offset(&snapshot, range.start);
+                    }
+                    find_surround_pair(&snapshot, range, pair)
+                })
+                .collect::<Option<Vec<_>>>()
+            else {
+                return;
+            };
+            // Selections within the same pair only change it once.
+            pairs.sort_unstable();
+            pairs.dedup();
+
+            let mut edits = pairs
+                .into_iter()
+                .flat_map(|(open_offset, close_offset)| {
+                    [
+                        (
+                            open_offset..next_char_offset(&snapshot, open_offset),
+                            open.clone(),
+                        ),
+                        (
+                            close_offset..next_char_offset(&snapshot, close_offset),
+                            close.clone(),
+                        ),
+                    ]
+                })
+                .collect::<Vec<_>>();
+            edits.sort_unstable_by_key(|(range, _)| range.start);
+            edits.dedup_by_key(|(range, _)| range.start);
+
+            // The selections keep their place in the text, and a cursor on a replaced character
+            // stays on its replacement.
+            for selection in &mut selections {
+                selection.start = offset_after_edits(selection.start, &edits);
+                selection.end = offset_after_edits(selection.end, &edits);
+            }
+
+            editor.transact(window, cx, |editor, window, cx| {
+                editor.edit(edits, cx);
+                editor.change_selections(Default::default(), window, cx, |s| {
+                    s.select(selections);
+                });
+            });
+        });
+    }
+}
+
+fn next_char_offset(snapshot: &MultiBufferSnapshot, offset: usize) -> usize {
+    offset + snapshot.chars_at(offset).next().map_or(0, char::len_utf8)
+}
+
+#[cfg(test)]
+mod test {
+    use indoc::indoc;
+
+    use crate::{state::Mode, test::VimTestContext};
+
+    #[gpui::test]
+    async fn test_helix_surround_add(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.enable_helix();
+
+        cx.set_state("one «twoˇ» three", Mode::HelixNormal);
+        cx.simulate_keystrokes("m s (");
+        cx.assert_state("one «(two)ˇ» three", Mode::HelixNormal);
+
+        cx.set_state("one «twoˇ» «threeˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("m s ]");
+        cx.assert_state("one «[two]ˇ» «[three]ˇ»", Mode::HelixNormal);
+
+        cx.set_state("oˇne", Mode::HelixNormal);
+        cx.simulate_keystrokes("m s \"");
+        cx.assert_state("o«\"n\"ˇ»e", Mode::HelixNormal);
+    }
+
+    #[gpui::test]
+    async fn test_helix_surround_delete(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.enable_helix();
+
+        cx.set_state("fn(a, (bˇ), c)", Mode::HelixNormal);
+        cx.simulate_keystrokes("m d (");
+        cx.assert_state("fn(a, bˇ, c)", Mode::HelixNormal);
+        cx.simulate_keystrokes("m d )");
+        cx.assert_state("fna, bˇ, c", Mode::HelixNormal);
+
+        cx.set_state(
+            indoc! {"
+            [oˇne] two
+            [tˇwo] three"},
+            Mode::HelixNormal,
+        );
+        cx.simulate_keystrokes("m d [");
+        cx.assert_state(
+            indoc! {"
+            oˇne two
+            tˇwo three"},
+            Mode::HelixNormal,
+        );
+
+        // Nothing is deleted unless every selection is surrounded by the pair.
+        cx.set_state("(oˇne) tˇwo", Mode::HelixNormal);
+        cx.simulate_keystrokes("m d (");
+        cx.assert_state("(oˇne) tˇwo", Mode::HelixNormal);
+
+        cx.set_state("say \"«helloˇ»\"", Mode::HelixNormal);
+        cx.simulate_keystrokes("m d \"");
+        cx.assert_state("say «helloˇ»", Mode::HelixNormal);
+    }
+
+    #[gpui::test]
+    async fn test_helix_surround_replace(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.enable_helix();
+
+        cx.set_state("{ [oˇne] }", Mode::HelixNormal);
+        cx.simulate_keystrokes("m r [ (");
+        cx.assert_state("{ (oˇne) }", Mode::HelixNormal);
+        cx.simulate_keystrokes("m r { <");
+        cx.assert_state("< (oˇne) >", Mode::HelixNormal);
+
+        cx.set_state("'«twoˇ»'", Mode::HelixNormal);
+        cx.simulate_keystrokes("m r ' \"");
+        cx.assert_state("\"«twoˇ»\"", Mode::HelixNormal);
+    }
+}
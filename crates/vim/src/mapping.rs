@@ -0,0 +1,726 @@
+use std::{collections::VecDeque, mem};
+
+use anyhow::{Result, bail};
+use collections::HashSet;
+use editor::Editor;
+use gpui::{Action, App, AsyncApp, AsyncWindowContext, Context, Global, KeyBinding, Keystroke};
+use itertools::Itertools;
+use settings::KeybindSource;
+use util::post_inc;
+use workspace::Workspace;
+
+use crate::Vim;
+
+/// The leader used by `<Leader>` in mappings when `mapleader` isn't set.
+const DEFAULT_LEADER: &str = "\\";
+
+/// The most keys mappings can type before they're stopped, to avoid infinite recursion.
+const MAX_TYPED_KEYS: usize = 100;
+
+/// A mode that mappings can be defined in, as matched by the `vim_mode` of the key context.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MapMode {
+    Normal,
+    Visual,
+    OperatorPending,
+    Insert,
+}
+
+impl MapMode {
+    fn context(self) -> &'static str {
+        match self {
+            MapMode::Normal => "vim_mode == normal && !VimNoremap",
+            MapMode::Visual => "vim_mode == visual && !VimNoremap",
+            MapMode::OperatorPending => "vim_mode == operator && !VimNoremap",
+            MapMode::Insert => "vim_mode == insert && !VimNoremap",
+        }
+    }
+
+    fn abbreviation(self) -> char {
+        match self {
+            MapMode::Normal => 'n',
+            MapMode::Visual => 'v',
+            MapMode::OperatorPending => 'o',
+            MapMode::Insert => 'i',
+        }
+    }
+}
+
+const NORMAL_VISUAL_OPERATOR: &[MapMode] =
+    &[MapMode::Normal, MapMode::Visual, MapMode::OperatorPending];
+const NORMAL: &[MapMode] = &[MapMode::Normal];
+const VISUAL: &[MapMode] = &[MapMode::Visual];
+const OPERATOR_PENDING: &[MapMode] = &[MapMode::OperatorPending];
+const INSERT: &[MapMode] = &[MapMode::Insert];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MapCommandKind {
+    Map,
+    Noremap,
+    Unmap,
+    Clear,
+}
+
+/// The mapping commands, as the `(prefix, suffix)` they can be abbreviated to, what they do and the
+/// modes they apply to. With a bang, the commands that apply to normal, visual and
+/// operator-pending mode apply to insert mode instead.
+pub(crate) const MAP_COMMANDS: &[(&str, &str, MapCommandKind, &[MapMode])] = &[
+    ("map", "", MapCommandKind::Map, NORMAL_VISUAL_OPERATOR),
+    ("nm", "ap", MapCommandKind::Map, NORMAL),
+    ("vm", "ap", MapCommandKind::Map, VISUAL),
+    ("xm", "ap", MapCommandKind::Map, VISUAL),
+    ("om", "ap", MapCommandKind::Map, OPERATOR_PENDING),
+    ("im", "ap", MapCommandKind::Map, INSERT),
+    (
+        "no",
+        "remap",
+        MapCommandKind::Noremap,
+        NORMAL_VISUAL_OPERATOR,
+    ),
+    ("nn", "oremap", MapCommandKind::Noremap, NORMAL),
+    ("vn", "oremap", MapCommandKind::Noremap, VISUAL),
+    ("xn", "oremap", MapCommandKind::Noremap, VISUAL),
+    ("ono", "remap", MapCommandKind::Noremap, OPERATOR_PENDING),
+    ("ino", "remap", MapCommandKind::Noremap, INSERT),
+    ("unm", "ap", MapCommandKind::Unmap, NORMAL_VISUAL_OPERATOR),
+    ("nun", "map", MapCommandKind::Unmap, NORMAL),
+    ("vu", "nmap", MapCommandKind::Unmap, VISUAL),
+    ("xu", "nmap", MapCommandKind::Unmap, VISUAL),
+    ("ou", "nmap", MapCommandKind::Unmap, OPERATOR_PENDING),
+    ("iu", "nmap", MapCommandKind::Unmap, INSERT),
+    (
+        "mapc",
+        "lear",
+        MapCommandKind::Clear,
+        NORMAL_VISUAL_OPERATOR,
+    ),
+    ("nmapc", "lear", MapCommandKind::Clear, NORMAL),
+    ("vmapc", "lear", MapCommandKind::Clear, VISUAL),
+    ("xmapc", "lear", MapCommandKind::Clear, VISUAL),
+    ("omapc", "lear", MapCommandKind::Clear, OPERATOR_PENDING),
+    ("imapc", "lear", MapCommandKind::Clear, INSERT),
+];
+
+/// Returns the modes a mapping command applies to when it's given a bang.
+pub(crate) fn bang_modes(modes: &'static [MapMode]) -> Option<&'static [MapMode]> {
+    (modes == NORMAL_VISUAL_OPERATOR).then_some(INSERT)
+}
+
+/// Adds, removes or lists key mappings.
+#[derive(Clone, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub(crate) struct MapCommand {
+    pub(crate) kind: MapCommandKind,
+    pub(crate) modes: &'static [MapMode],
+    pub(crate) args: String,
+}
+
+/// Types the keys a mapping maps to.
+#[derive(Clone, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+struct MappedKeys {
+    mapping_id: usize,
+    keystrokes: Vec<Keystroke>,
+    noremap: bool,
+}
+
+struct Mapping {
+    /// Identifies the key binding of the mapping, so that it can be removed from the keymap.
+    id: usize,
+    mode: MapMode,
+    lhs: String,
+    keystrokes: Vec<Keystroke>,
+    rhs: String,
+    rhs_keystrokes: Vec<Keystroke>,
+    noremap: bool,
+    /// Whether the mapping comes from the vimrc, rather than from a command.
+    sourced: bool,
+}
+
+impl Mapping {
+    fn key_binding(&self) -> KeyBinding {
+        let keystrokes = self.keystrokes.iter().map(Keystroke::unparse).join(" ");
+        KeyBinding::new(
+            &keystrokes,
+            MappedKeys {
+                mapping_id: self.id,
+                keystrokes: self.rhs_keystrokes.clone(),
+                noremap: self.noremap,
+            },
+            Some(self.mode.context()),
+        )
+        .with_meta(KeybindSource::Vim.meta())
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct VimMappings {
+    mappings: Vec<Mapping>,
+    next_mapping_id: usize,
+    leader: Option<String>,
+    /// The keys that mappings are yet to type, and whether they come from a non-recursive mapping.
+    pending_keys: VecDeque<(Keystroke, bool)>,
+    /// Whether the pending keys are being typed.
+    typing: bool,
+    /// Whether the key being typed comes from a non-recursive mapping.
+    typing_noremap: bool,
+}
+
+impl Global for VimMappings {}
+
+impl VimMappings {
+    fn leader(&self) -> &str {
+        self.leader.as_deref().unwrap_or(DEFAULT_LEADER)
+    }
+}
+
+pub(crate) fn init(cx: &mut App) {
+    cx.set_global(VimMappings::default());
+}
+
+pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
+    Vim::action(editor, cx, |_, action: &MappedKeys, window, cx| {
+        let mappings = cx.global_mut::<VimMappings>();
+        // The keys are typed before the rest of the keys of the mapping that typed them, if any.
+        for keystroke in action.keystrokes.iter().rev() {
+            mappings
+                .pending_keys
+                .push_front((keystroke.clone(), action.noremap));
+        }
+        if mappings.typing || mappings.pending_keys.is_empty() {
+            return;
+        }
+        mappings.typing = true;
+        window
+            .spawn(cx, async move |cx| type_pending_keys(cx))
+            .detach();
+    });
+}
+
+fn type_pending_keys(cx: &mut AsyncWindowContext) {
+    let mut typed_keys = 0;
+    loop {
+        let typed = cx.update(|window, cx| {
+            let mappings = cx.global_mut::<VimMappings>();
+            let Some((keystroke, noremap)) = mappings.pending_keys.pop_front() else {
+                return false;
+            };
+            if mappings.typing_noremap != noremap {
+                mappings.typing_noremap = noremap;
+                // Mappings are disabled by the key context, which is only updated when the window
+                // is drawn, so it needs to be drawn before the key is dispatched.
+                window.refresh();
+                window.draw(cx).clear();
+            }
+            let focused = window.focused(cx);
+            window.dispatch_keystroke(keystroke, cx);
+            if window.focused(cx) != focused {
+                // Drawing schedules the focus change events, which need to happen before the
+                // next key is typed.
+                window.draw(cx).clear();
+            }
+            true
+        });
+        if !matches!(typed, Ok(true)) {
+            break;
+        }
+        typed_keys += 1;
+        if typed_keys == MAX_TYPED_KEYS {
+            log::error!("mappings typed over {MAX_TYPED_KEYS} keys");
+            break;
+        }
+    }
+
+    let cx: &mut AsyncApp = cx;
+    cx.update(|cx| {
+        let mappings = cx.global_mut::<VimMappings>();
+        mappings.pending_keys.clear();
+        mappings.typing = false;
+        if mem::take(&mut mappings.typing_noremap) {
+            cx.refresh_windows();
+        }
+    })
+    .ok();
+}
+
+/// Returns whether the keys of a non-recursive mapping are being typed, in which case mappings
+/// don't apply.
+pub(crate) fn is_typing_noremap(cx: &App) -> bool {
+    cx.try_global::<VimMappings>()
+        .is_some_and(|mappings| mappings.typing_noremap)
+}
+
+/// Adds the key bindings for the mappings created with `:map` and the other mapping commands to
+/// the keymap. This needs to be called again after the keymap is cleared.
+pub fn bind_mappings(cx: &mut App) {
+    let Some(mappings) = cx.try_global::<VimMappings>() else {
+        return;
+    };
+    let bindings = mappings
+        .mappings
+        .iter()
+        .map(Mapping::key_binding)
+        .collect::<Vec<_>>();
+    cx.bind_keys(bindings);
+}
+
+/// Removes the mappings the predicate returns true for along with their key bindings, returning
+/// whether any were removed.
+fn unmap(cx: &mut App, mut predicate: impl FnMut(&Mapping) -> bool) -> bool {
+    let mut removed_ids = HashSet::default();
+    cx.global_mut::<VimMappings>().mappings.retain(|mapping| {
+        let remove = predicate(mapping);
+        if remove {
+            removed_ids.insert(mapping.id);
+        }
+        !remove
+    });
+    if removed_ids.is_empty() {
+        return false;
+    }
+    cx.remove_key_bindings(|binding| {
+        binding
+            .action()
+            .as_any()
+            .downcast_ref::<MappedKeys>()
+            .is_some_and(|action| removed_ids.contains(&action.mapping_id))
+    });
+    true
+}
+
+impl MapCommand {
+    pub(crate) fn run(&self, cx: &mut Context<Workspace>) -> Result<()> {
+        if let Some(listing) = run_map_command(self.kind, self.modes, &self.args, false, cx)? {
+            cx.emit(workspace::Event::OpenBundledFile {
+                text: listing.into(),
+                title: "Vim Mappings",
+                language: "Plain Text",
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Runs a mapping command with the given arguments, returning the listing of the mappings when it
+/// lists them.
+pub(crate) fn run_map_command(
+    kind: MapCommandKind,
+    modes: &[MapMode],
+    args: &str,
+    sourced: bool,
+    cx: &mut App,
+) -> Result<Option<String>> {
+    let args = MapArgs::parse(args)?;
+    match kind {
+        MapCommandKind::Map | MapCommandKind::Noremap => {
+            let Some(lhs) = args.lhs else {
+                return list_mappings(modes, None, cx).map(Some);
+            };
+            let Some(rhs) = args.rhs else {
+                return list_mappings(modes, Some(lhs), cx).map(Some);
+            };
+            add_mapping(
+                modes,
+                lhs,
+                rhs,
+                kind == MapCommandKind::Noremap,
+                args.unique,
+                sourced,
+                cx,
+            )?;
+        }
+        MapCommandKind::Unmap => {
+            let Some(lhs) = args.lhs else {
+                bail!("argument required");
+            };
+            remove_mappings(modes, lhs, cx)?;
+        }
+        MapCommandKind::Clear => {
+            if let Some(lhs) = args.lhs {
+                bail!("trailing characters: {lhs}");
+            }
+            unmap(cx, |mapping| modes.contains(&mapping.mode));
+        }
+    }
+    Ok(None)
+}
+
+/// Sets the key `<Leader>` stands for in mappings that are created afterwards, written in key
+/// notation.
+pub(crate) fn set_leader(leader: Option<String>, cx: &mut App) {
+    cx.global_mut::<VimMappings>().leader = leader;
+}
+
+/// Removes the mappings that came from the vimrc, and the leader it set, before it's sourced
+/// again.
+pub(crate) fn clear_sourced(cx: &mut App) {
+    cx.global_mut::<VimMappings>().leader = None;
+    unmap(cx, |mapping| mapping.sourced);
+}
+
+/// The arguments of a mapping or abbreviation command.
+pub(crate) struct MapArgs<'a> {
+    pub(crate) lhs: Option<&'a str>,
+    pub(crate) rhs: Option<&'a str>,
+    pub(crate) unique: bool,
+}
+
+impl<'a> MapArgs<'a> {
+    pub(crate) fn parse(mut args: &'a str) -> Result<Self> {
+        let mut unique = false;
+        args = args.trim_start();
+        while let Some((option, rest)) =
+            args.strip_prefix('<').and_then(|rest| rest.split_once('>'))
+        {
+            match option.to_ascii_lowercase().as_str() {
+                "silent" | "nowait" | "special" => {}
+                "unique" => unique = true,
+                "buffer" | "expr" | "script" => bail!("<{option}> is not supported"),
+                _ => break,
+            }
+            args = rest.trim_start();
+        }
+
+        if args.is_empty() {
+            return Ok(Self {
+                lhs: None,
+                rhs: None,
+                unique,
+            });
+        }
+        let (lhs, rhs) = match args.split_once(char::is_whitespace) {
+            Some((lhs, rhs)) => (lhs, Some(rhs.trim_start()).filter(|rhs| !rhs.is_empty())),
+            None => (args, None),
+        };
+        Ok(Self {
+            lhs: Some(lhs),
+            rhs,
+            unique,
+        })
+    }
+}
+
+fn add_mapping(
+    modes: &[MapMode],
+    lhs: &str,
+    rhs: &str,
+    noremap: bool,
+    unique: bool,
+    sourced: bool,
+    cx: &mut App,
+) -> Result<()> {
+    let mappings = cx.global::<VimMappings>();
+    let keystrokes = parse_keys(lhs, mappings.leader())?;
+    if keystrokes.is_empty() {
+        bail!("invalid mapping: {lhs}");
+    }
+    let rhs_keystrokes = parse_keys(rhs, mappings.leader())?;
+
+    let exists =
+        |mapping: &Mapping, mode: MapMode| mapping.mode == mode && mapping.keystrokes == keystrokes;
+    if unique
+        && mappings
+            .mappings
+            .iter()
+            .any(|mapping| modes.iter().any(|mode| exists(mapping, *mode)))
+    {
+        bail!("mapping already exists for {lhs}");
+    }
+    unmap(cx, |mapping| {
+        modes.iter().any(|mode| exists(mapping, *mode))
+    });
+
+    let mappings = cx.global_mut::<VimMappings>();
+    let mut bindings = Vec::new();
+    for &mode in modes {
+        let mapping = Mapping {
+            id: post_inc(&mut mappings.next_mapping_id),
+            mode,
+            lhs: lhs.to_string(),
+            keystrokes: keystrokes.clone(),
+            rhs: rhs.to_string(),
+            rhs_keystrokes: rhs_keystrokes.clone(),
+            noremap,
+            sourced,
+        };
+        bindings.push(mapping.key_binding());
+        mappings.mappings.push(mapping);
+    }
+    cx.bind_keys(bindings);
+    Ok(())
+}
+
+fn remove_mappings(modes: &[MapMode], lhs: &str, cx: &mut App) -> Result<()> {
+    let keystrokes = parse_keys(lhs, cx.global::<VimMappings>().leader())?;
+    if !unmap(cx, |mapping| {
+        modes.contains(&mapping.mode) && mapping.keystrokes == keystrokes
+    }) {
+        bail!("no such mapping: {lhs}");
+    }
+    Ok(())
+}
+
+fn list_mappings(modes: &[MapMode], lhs: Option<&str>, cx: &App) -> Result<String> {
+    let mappings = cx.global::<VimMappings>();
+    let prefix = lhs
+        .map(|lhs| parse_keys(lhs, mappings.leader()))
+        .transpose()?
+        .unwrap_or_default();
+    let listing = mappings
+        .mappings
+        .iter()
+        .filter(|mapping| modes.contains(&mapping.mode) && mapping.keystrokes.starts_with(&prefix))
+        .map(|mapping| {
+            format!(
+                "{}  {:<15} {}{}\n",
+                mapping.mode.abbreviation(),
+                mapping.lhs,
+                if mapping.noremap { '*' } else { ' ' },
+                mapping.rhs
+            )
+        })
+        .collect::<String>();
+    if listing.is_empty() {
+        bail!("no mapping found");
+    }
+    Ok(listing)
+}
+
+/// Parses keys written in Vim's key notation, like `<C-w>j` or `<Leader>f`, into keystrokes.
+pub(crate) fn parse_keys(keys: &str, leader: &str) -> Result<Vec<Keystroke>> {
+    let mut keystrokes = Vec::new();
+    let mut rest = keys;
+    while let Some(char) = rest.chars().next() {
+        if char == '<'
+            && let Some(end) = rest.find('>')
+            && let Some(special) = parse_special_key(&rest[1..end], leader)?
+        {
+            keystrokes.extend(special);
+            rest = &rest[end + 1..];
+        } else {
+            keystrokes.push(parse_char(char)?);
+            rest = &rest[char.len_utf8()..];
+        }
+    }
+    Ok(keystrokes)
+}
+
+fn parse_char(key: char) -> Result<Keystroke> {
+    if key == ' ' {
+        return Ok(Keystroke::parse("space")?);
+    }
+    Ok(Keystroke::parse(&key.to_string())?)
+}
+
+/// Parses the name of a special key written between `<` and `>`, returning `None` for names that
+/// aren't special keys so that they're taken literally.
+fn parse_special_key(name: &str, leader: &str) -> Result<Option<Vec<Keystroke>>> {
+    match name.to_ascii_lowercase().as_str() {
+        "leader" => return parse_keys(leader, DEFAULT_LEADER).map(Some),
+        "nop" => return Ok(Some(Vec::new())),
+        _ => {}
+    }
+
+    let mut modifiers = String::new();
+    let mut key = name;
+    while let Some((modifier, rest)) = key.split_once('-')
+        && !rest.is_empty()
+    {
+        modifiers.push_str(match modifier.to_ascii_lowercase().as_str() {
+            "c" => "ctrl-",
+            "s" => "shift-",
+            "m" | "a" => "alt-",
+            "d" => "cmd-",
+            _ => return Ok(None),
+        });
+        key = rest;
+    }
+
+    let lowercase_key = key.to_ascii_lowercase();
+    let key = match lowercase_key.as_str() {
+        "cr" | "enter" | "return" => "enter",
+        "esc" => "escape",
+        "bs" => "backspace",
+        "del" => "delete",
+        "lt" => "<",
+        "bar" => "|",
+        "bslash" => "\\",
+        "tab" | "space" | "backspace" | "delete" | "up" | "down" | "left" | "right" | "home"
+        | "end" | "pageup" | "pagedown" | "insert" => lowercase_key.as_str(),
+        function_key
+            if function_key
+                .strip_prefix('f')
+                .and_then(|number| number.parse::<u8>().ok())
+                .is_some_and(|number| (1..=12).contains(&number)) =>
+        {
+            function_key
+        }
+        // Vim doesn't distinguish the case of letters typed with control.
+        _ if key.chars().count() == 1 && modifiers.contains("ctrl-") => lowercase_key.as_str(),
+        _ if key.chars().count() == 1 && !modifiers.is_empty() => key,
+        _ => return Ok(None),
+    };
+    Ok(Some(vec![Keystroke::parse(&format!("{modifiers}{key}"))?]))
+}
+
+#[cfg(test)]
+mod test {
+    use gpui::{Keystroke, TestAppContext};
+    use indoc::indoc;
+
+    use crate::{state::Mode, test::VimTestContext};
+
+    use super::{MappedKeys, parse_keys};
+
+    fn keystrokes(keys: &str) -> Vec<Keystroke> {
+        keys.split_whitespace()
+            .map(|key| Keystroke::parse(key).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_keys() {
+        assert_eq!(parse_keys("gj", "\\").unwrap(), keystrokes("g j"));
+        assert_eq!(parse_keys("G", "\\").unwrap(), keystrokes("shift-g"));
+        assert_eq!(parse_keys("<C-w>j", "\\").unwrap(), keystrokes("ctrl-w j"));
+        assert_eq!(
+            parse_keys("<c-S-Left><CR><Esc>", "\\").unwrap(),
+            keystrokes("ctrl-shift-left enter escape")
+        );
+        assert_eq!(
+            parse_keys(":w<Bar>q<lt>", "\\").unwrap(),
+            keystrokes(": w | q <")
+        );
+        assert_eq!(parse_keys("<Leader>f", "\\").unwrap(), keystrokes("\\ f"));
+        assert_eq!(
+            parse_keys("<leader>f", "<Space>").unwrap(),
+            keystrokes("space f")
+        );
+        assert_eq!(parse_keys("a b", "\\").unwrap(), keystrokes("a space b"));
+        assert_eq!(
+            parse_keys("<F5><M-x>", "\\").unwrap(),
+            keystrokes("f5 alt-x")
+        );
+        assert_eq!(parse_keys("<Nop>", "\\").unwrap(), keystrokes(""));
+        assert_eq!(parse_keys("<foo>", "\\").unwrap(), keystrokes("< f o o >"));
+    }
+
+    #[gpui::test]
+    async fn test_noremap(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state(
+            indoc! {"
+            one
+            ˇtwo
+            three"},
+            Mode::Normal,
+        );
+
+        cx.simulate_keystrokes(": n n o r e m a p space j space k enter");
+        cx.simulate_keystrokes(": n n o r e m a p space k space j enter");
+        cx.simulate_keystrokes("j");
+        cx.assert_state(
+            indoc! {"
+            ˇone
+            two
+            three"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes("k k");
+        cx.assert_state(
+            indoc! {"
+            one
+            two
+            ˇthree"},
+            Mode::Normal,
+        );
+
+        cx.simulate_keystrokes(": n u n m a p space k enter");
+        cx.simulate_keystrokes("k");
+        cx.assert_state(
+            indoc! {"
+            one
+            ˇtwo
+            three"},
+            Mode::Normal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_noremap_in_recursive_map(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state(
+            indoc! {"
+            one
+            two
+            ˇthree"},
+            Mode::Normal,
+        );
+
+        cx.simulate_keystrokes(": n n o r e m a p space j space k enter");
+        cx.simulate_keystrokes(": n n o r e m a p space k space j enter");
+        cx.simulate_keystrokes(": n m a p space Q space j k j j enter");
+        cx.simulate_keystrokes("shift-q");
+        cx.assert_state(
+            indoc! {"
+            ˇone
+            two
+            three"},
+            Mode::Normal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_remapping_replaces_key_binding(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("ˇone two three", Mode::Normal);
+
+        let mapping_bindings = |cx: &mut VimTestContext| {
+            cx.update(|_, cx| {
+                cx.key_bindings()
+                    .borrow()
+                    .bindings()
+                    .filter(|binding| binding.action().as_any().is::<MappedKeys>())
+                    .count()
+            })
+        };
+
+        cx.simulate_keystrokes(": n m a p space , space w enter");
+        cx.simulate_keystrokes(": n m a p space , space e enter");
+        assert_eq!(mapping_bindings(&mut cx), 1);
+        cx.simulate_keystrokes(",");
+        cx.assert_state("onˇe two three", Mode::Normal);
+
+        cx.simulate_keystrokes(": n u n m a p space , enter");
+        assert_eq!(mapping_bindings(&mut cx), 0);
+    }
+
+    #[gpui::test]
+    async fn test_recursive_map(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("ˇone two three", Mode::Normal);
+
+        cx.simulate_keystrokes(": n m a p space , space w w enter");
+        cx.simulate_keystrokes(": n m a p space < l e a d e r > x space , d w enter");
+        cx.simulate_keystrokes("\\ x");
+        cx.assert_state("one twoˇ ", Mode::Normal);
+
+        cx.simulate_keystrokes(": m a p c l e a r enter");
+        cx.simulate_keystrokes("0 ,");
+        cx.assert_state("ˇone two ", Mode::Normal);
+    }
+
+    #[gpui::test]
+    async fn test_insert_mode_map(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("ˇ", Mode::Normal);
+
+        cx.simulate_keystrokes(": i n o r e m a p space j k space < E s c > enter");
+        cx.simulate_keystrokes("i a j b j k");
+        cx.assert_state("ajˇb", Mode::Normal);
+
+        cx.simulate_keystrokes(": m a p ! space j k space x enter");
+        cx.simulate_keystrokes("a j k");
+        cx.assert_state("ajbxˇ", Mode::Insert);
+    }
+}
@@ -2,6 +2,7 @@ use std::{cell::RefCell, rc::Rc};
 
 use crate::{
     Vim,
+    command::command_interceptor,
     insert::NormalBefore,
     motion::Motion,
     normal::InsertBefore,
@@ -69,7 +70,8 @@ pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     Vim::action(editor, cx, |vim, _: &ToggleRecord, window, cx| {
         let globals = Vim::globals(cx);
         if let Some(char) = globals.recording_register.take() {
-            globals.last_recorded_register = Some(char)
+            globals.last_recorded_register = Some(char);
+            Vim::update_globals(cx, |globals, cx| globals.save_recording(char, cx));
         } else {
             vim.push_operator(Operator::RecordRegister, window, cx);
         }
@@ -192,6 +194,11 @@ impl Vim {
             };
             register = last;
         }
+        if register == ':' {
+            globals.last_replayed_register = Some(register);
+            self.replay_last_command(count, window, cx);
+            return;
+        }
         let Some(actions) = globals.recordings.get(&register) else {
             return;
         };
@@ -207,6 +214,19 @@ impl Vim {
         replayer.replay(repeated_actions, window, cx);
     }
 
+    /// Runs the last command from the command history again, like `@:`.
+    fn replay_last_command(&mut self, count: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(command) = Vim::globals(cx).command_history.last().cloned() else {
+            return;
+        };
+        let Some(result) = command_interceptor(&command, cx).into_iter().next() else {
+            return;
+        };
+        for _ in 0..count {
+            window.dispatch_action(result.action.boxed_clone(), cx);
+        }
+    }
+
     pub(crate) fn repeat(
         &mut self,
         from_insert_mode: bool,
@@ -368,7 +388,8 @@ mod test {
     use gpui::EntityInputHandler;
 
     use crate::{
-        state::Mode,
+        Vim,
+        state::{Mode, PersistedState, SerializedReplayableAction},
         test::{NeovimBackedTestContext, VimTestContext},
     };
 
@@ -793,4 +814,41 @@ mod test {
         cx.simulate_shared_keystrokes("@ b").await;
         cx.shared_state().await.assert_eq("aaaaaaabbbˇd");
     }
+
+    #[gpui::test]
+    async fn test_record_replay_restored(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇone two three four five", Mode::Normal);
+        cx.simulate_keystrokes("q a 2 d w i x escape w q");
+        cx.assert_state("xthree ˇfour five", Mode::Normal);
+
+        let recording = cx
+            .update(|_, cx| {
+                let actions = Vim::globals(cx).recordings.remove(&'a').unwrap();
+                actions
+                    .iter()
+                    .map(|action| action.to_serialized(cx))
+                    .collect::<Option<Vec<_>>>()
+            })
+            .expect("recording should be serializable");
+        assert!(recording.contains(&SerializedReplayableAction::Action {
+            name: "vim::Number".into(),
+            input: Some("2".into()),
+        }));
+
+        cx.update(|_, cx| {
+            Vim::update_globals(cx, |globals, cx| {
+                let state = PersistedState {
+                    recordings: vec![('a', recording)],
+                    ..Default::default()
+                };
+                globals.restore(state, cx)
+            })
+        });
+        cx.set_state("ˇone two three four five", Mode::Normal);
+        cx.simulate_keystrokes("@ a");
+        cx.run_until_parked();
+        cx.assert_state("xthree ˇfour five", Mode::Normal);
+    }
 }
@@ -13,7 +13,7 @@ use crate::{
     Vim,
     command::CommandRange,
     motion::Motion,
-    state::{Mode, SearchState},
+    state::{HistoryKind, Mode, SearchState},
 };
 
 /// Moves to the next search match.
@@ -161,6 +161,7 @@ impl Vim {
         let count = Vim::take_count(cx).unwrap_or(1);
         Vim::take_forced_motion(cx);
         let prior_selections = self.editor_selections(window, cx);
+        let search_history = Vim::globals(cx).search_history.clone();
         pane.update(cx, |pane, cx| {
             if let Some(search_bar) = pane.toolbar().read(cx).item_of_type::<BufferSearchBar>() {
                 search_bar.update(cx, |search_bar, cx| {
@@ -168,6 +169,7 @@ impl Vim {
                         return;
                     }
 
+                    search_bar.set_search_history(search_history);
                     search_bar.select_query(window, cx);
                     cx.focus_self(window);
 
@@ -195,7 +197,7 @@ impl Vim {
                         prior_selections,
                         prior_operator: self.operator_stack.last().cloned(),
                         prior_mode,
-                        helix_select: false,
+                        helix_select: None,
                     }
                 });
             }
@@ -213,18 +215,16 @@ impl Vim {
 
     pub fn search_submit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.store_visual_marks(window, cx);
+        if let Some(selection) = self.search.helix_select {
+            self.helix_regex_submit(selection, window, cx);
+            return;
+        }
         let Some(pane) = self.pane(window, cx) else {
             return;
         };
         let new_selections = self.editor_selections(window, cx);
         let result = pane.update(cx, |pane, cx| {
             let search_bar = pane.toolbar().read(cx).item_of_type::<BufferSearchBar>()?;
-            if self.search.helix_select {
-                search_bar.update(cx, |search_bar, cx| {
-                    search_bar.select_all_matches(&Default::default(), window, cx)
-                });
-                return None;
-            }
             search_bar.update(cx, |search_bar, cx| {
                 let mut count = self.search.count;
                 let direction = self.search.direction;
@@ -247,8 +247,11 @@ impl Vim {
                 let prior_mode = self.search.prior_mode;
                 let prior_operator = self.search.prior_operator.take();
 
-                let query = search_bar.query(cx).into();
-                Vim::globals(cx).registers.insert('/', query);
+                let query = search_bar.query(cx);
+                Vim::update_globals(cx, |globals, cx| {
+                    globals.registers.insert('/', query.clone().into());
+                    globals.add_to_history(HistoryKind::Search, query, cx);
+                });
                 Some((prior_selections, prior_mode, prior_operator))
             })
         });
@@ -649,6 +652,7 @@ mod test {
     use std::time::Duration;
 
     use crate::{
+        Vim,
         state::Mode,
         test::{NeovimBackedTestContext, VimTestContext},
     };
@@ -733,6 +737,43 @@ mod test {
         cx.assert_state("hi\nˇhigh\nhi\n", Mode::Normal);
     }
 
+    #[gpui::test]
+    async fn test_search_history(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇone two three two", Mode::Normal);
+        cx.simulate_keystrokes("/ t w o");
+        cx.run_until_parked();
+        cx.simulate_keystrokes("enter");
+        cx.assert_state("one ˇtwo three two", Mode::Normal);
+        cx.simulate_keystrokes("/ t h");
+        cx.run_until_parked();
+        cx.simulate_keystrokes("enter");
+        cx.assert_state("one two ˇthree two", Mode::Normal);
+        cx.update(|_, cx| assert_eq!(Vim::globals(cx).search_history, ["two", "th"]));
+
+        cx.simulate_keystrokes("/ up");
+        cx.run_until_parked();
+        cx.simulate_keystrokes("up");
+        cx.run_until_parked();
+        cx.simulate_keystrokes("enter");
+        cx.assert_state("one two three ˇtwo", Mode::Normal);
+        cx.update(|_, cx| assert_eq!(Vim::globals(cx).search_history, ["th", "two"]));
+        cx.update(|_, cx| {
+            assert_eq!(
+                Vim::globals(cx).registers.get(&'/').unwrap().text.as_ref(),
+                "two"
+            )
+        });
+
+        // Searches restored from another session can be navigated to as well.
+        cx.update(|_, cx| Vim::globals(cx).search_history = vec!["one".into()]);
+        cx.simulate_keystrokes("/ up");
+        cx.run_until_parked();
+        cx.simulate_keystrokes("enter");
+        cx.assert_state("ˇone two three two", Mode::Normal);
+    }
+
     #[gpui::test]
     async fn test_search(cx: &mut gpui::TestAppContext) {
         let mut cx = VimTestContext::new(cx, true).await;
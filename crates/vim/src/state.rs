@@ -26,6 +26,7 @@ use settings::{Settings, SettingsStore};
 use std::borrow::BorrowMut;
 use std::collections::HashSet;
 use std::path::Path;
+use std::rc::Rc;
 use std::{fmt::Display, ops::Range, sync::Arc};
 use text::{Bias, ToPoint};
 use theme::ThemeSettings;
@@ -144,6 +145,11 @@ pub enum Operator {
     HelixPrevious {
         around: bool,
     },
+    HelixSurroundAdd,
+    HelixSurroundReplace {
+        replaced_char: Option<char>,
+    },
+    HelixSurroundDelete,
 }
 
 #[derive(Default, Clone, Debug)]
@@ -233,6 +239,11 @@ pub struct VimGlobals {
     pub registers: HashMap<char, Register>,
     pub recordings: HashMap<char, Vec<ReplayableAction>>,
 
+    /// The queries searched for with `/` and `?`, from oldest to newest.
+    pub search_history: Vec<String>,
+    /// The commands run with `:`, from oldest to newest.
+    pub command_history: Vec<String>,
+
     pub focused_vim: Option<WeakEntity<Vim>>,
 
     pub marks: HashMap<EntityId, Entity<MarksState>>,
@@ -717,7 +728,14 @@ impl VimGlobals {
                 });
                 CommandPaletteInterceptor::update_global(cx, |interceptor, _| {
                     interceptor.set(Box::new(command_interceptor));
+                    interceptor.set_confirm_handler(Rc::new(|query, cx| {
+                        let command = query.trim_start_matches(':').trim().to_string();
+                        Vim::update_globals(cx, |globals, cx| {
+                            globals.add_to_history(HistoryKind::Command, command, cx)
+                        });
+                    }));
                 });
+                VimGlobals::load_persisted(cx);
                 for window in cx.windows() {
                     if let Some(workspace) = window.downcast::<Workspace>() {
                         workspace
@@ -766,6 +784,7 @@ impl VimGlobals {
         kind: MotionKind,
         cx: &mut Context<Editor>,
     ) {
+        let mut changed = vec!['"'];
         if let Some(register) = register {
             let lower = register.to_lowercase().next().unwrap_or(register);
             if lower != register {
@@ -775,9 +794,10 @@ impl VimGlobals {
                 current.clipboard_selections.take();
                 let yanked = current.clone();
                 self.registers.insert('"', yanked);
+                changed.push(lower);
             } else {
                 match lower {
-                    '_' | ':' | '.' | '%' | '#' | '=' | '/' => {}
+                    '_' | ':' | '.' | '%' | '#' | '=' | '/' => changed.clear(),
                     '+' => {
                         self.registers.insert('"', content.clone());
                         cx.write_to_clipboard(content.into());
@@ -792,10 +812,12 @@ impl VimGlobals {
                     '"' => {
                         self.registers.insert('"', content.clone());
                         self.registers.insert('0', content);
+                        changed.push('0');
                     }
                     _ => {
                         self.registers.insert('"', content.clone());
                         self.registers.insert(lower, content);
+                        changed.push(lower);
                     }
                 }
             }
@@ -814,14 +836,17 @@ impl VimGlobals {
             self.registers.insert('"', content.clone());
             if is_yank {
                 self.registers.insert('0', content);
+                changed.push('0');
             } else {
                 let contains_newline = content.text.contains('\n');
                 if !contains_newline {
                     self.registers.insert('-', content.clone());
+                    changed.push('-');
                 }
                 if kind.linewise() || contains_newline {
                     let mut content = content;
                     for i in '1'..='9' {
+                        changed.push(i);
                         if let Some(moved) = self.registers.insert(i, content) {
                             content = moved;
                         } else {
@@ -831,6 +856,111 @@ impl VimGlobals {
                 }
             }
         }
+        self.save_registers(changed, cx);
+    }
+
+    /// Persists the given registers, or removes them from the database if they're empty or too
+    /// long to be persisted.
+    fn save_registers(&self, names: Vec<char>, cx: &mut App) {
+        let registers: Vec<_> = names
+            .into_iter()
+            .filter(|name| is_persisted_register(*name))
+            .map(|name| {
+                let register = self
+                    .registers
+                    .get(&name)
+                    .filter(|register| register.text.len() <= MAX_PERSISTED_REGISTER_LEN)
+                    .cloned();
+                (name, register)
+            })
+            .collect();
+        if registers.is_empty() {
+            return;
+        }
+        let db = VimDb::global(cx);
+        cx.background_spawn(async move { db.set_registers(registers).await })
+            .detach_and_log_err(cx);
+    }
+
+    /// Persists the recording of the given register, or removes it from the database if it
+    /// can't be persisted.
+    pub(crate) fn save_recording(&self, name: char, cx: &mut App) {
+        let actions = self
+            .recordings
+            .get(&name)
+            .filter(|actions| actions.len() <= MAX_PERSISTED_RECORDING_LEN)
+            .and_then(|actions| {
+                actions
+                    .iter()
+                    .map(|action| action.to_serialized(cx))
+                    .collect::<Option<Vec<_>>>()
+            })
+            .and_then(|actions| serde_json::to_string(&actions).log_err());
+        if actions.is_none() {
+            log::info!("not persisting the recording of register {name}");
+        }
+        let db = VimDb::global(cx);
+        cx.background_spawn(async move { db.set_recording(name, actions).await })
+            .detach_and_log_err(cx);
+    }
+
+    pub(crate) fn add_to_history(&mut self, kind: HistoryKind, entry: String, cx: &mut App) {
+        if entry.is_empty() {
+            return;
+        }
+        let history = match kind {
+            HistoryKind::Search => &mut self.search_history,
+            HistoryKind::Command => &mut self.command_history,
+        };
+        push_history(history, entry.clone());
+        let db = VimDb::global(cx);
+        cx.background_spawn(async move { db.add_history_entry(kind, entry).await })
+            .detach_and_log_err(cx);
+    }
+
+    /// Loads the registers, recordings and histories persisted by previous sessions.
+    fn load_persisted(cx: &mut App) {
+        let db = VimDb::global(cx);
+        cx.spawn(async move |cx| {
+            let state = cx
+                .background_spawn(async move { db.get_persisted_state() })
+                .await?;
+            cx.update(|cx| Vim::update_globals(cx, |globals, cx| globals.restore(state, cx)))
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Restores persisted state, keeping anything that was set before it was loaded.
+    pub(crate) fn restore(&mut self, state: PersistedState, cx: &App) {
+        for (name, register) in state.registers {
+            self.registers.entry(name).or_insert(register);
+        }
+        for (name, actions) in state.recordings {
+            if self.recordings.contains_key(&name) {
+                continue;
+            }
+            if let Some(actions) = actions
+                .into_iter()
+                .map(|action| ReplayableAction::from_serialized(action, cx))
+                .collect::<Option<Vec<_>>>()
+            {
+                self.recordings.insert(name, actions);
+            }
+        }
+        for (history, persisted) in [
+            (&mut self.search_history, state.search_history),
+            (&mut self.command_history, state.command_history),
+        ] {
+            let current = std::mem::replace(history, persisted);
+            for entry in current {
+                push_history(history, entry);
+            }
+        }
+        if let Some(query) = self.search_history.last() {
+            self.registers
+                .entry('/')
+                .or_insert_with(|| query.clone().into());
+        }
     }
 
     pub(crate) fn read_register(
@@ -851,7 +981,8 @@ impl VimGlobals {
         };
         let lower = register.to_lowercase().next().unwrap_or(register);
         match lower {
-            '_' | ':' | '.' | '#' | '=' => None,
+            '_' | '.' | '#' | '=' => None,
+            ':' => self.command_history.last().cloned().map(Register::from),
             '+' => cx.read_from_clipboard().map(|item| item.into()),
             '*' => {
                 #[cfg(any(target_os = "linux", target_os = "freebsd"))]
@@ -966,6 +1097,61 @@ pub enum ReplayableAction {
     },
 }
 
+impl ReplayableAction {
+    /// Returns the form the action is persisted in, if it can be rebuilt later. Actions that
+    /// take input are stored with the input a binding in the keymap gives them.
+    pub(crate) fn to_serialized(&self, cx: &App) -> Option<SerializedReplayableAction> {
+        match self {
+            Self::Action(action) => {
+                let name = action.name();
+                let input = if cx
+                    .build_action(name, None)
+                    .is_ok_and(|built| built.partial_eq(&**action))
+                {
+                    None
+                } else {
+                    let keymap = cx.key_bindings();
+                    let keymap = keymap.borrow();
+                    let input = keymap
+                        .bindings_for_action(&**action)
+                        .find_map(|binding| binding.action_input())?;
+                    Some(input.to_string())
+                };
+                Some(SerializedReplayableAction::Action {
+                    name: name.to_string(),
+                    input,
+                })
+            }
+            Self::Insertion {
+                text,
+                utf16_range_to_replace,
+            } => Some(SerializedReplayableAction::Insertion {
+                text: text.to_string(),
+                utf16_range_to_replace: utf16_range_to_replace.clone(),
+            }),
+        }
+    }
+
+    pub(crate) fn from_serialized(action: SerializedReplayableAction, cx: &App) -> Option<Self> {
+        match action {
+            SerializedReplayableAction::Action { name, input } => {
+                let input = input
+                    .map(|input| serde_json::from_str(&input))
+                    .transpose()
+                    .log_err()?;
+                Some(Self::Action(cx.build_action(&name, input).log_err()?))
+            }
+            SerializedReplayableAction::Insertion {
+                text,
+                utf16_range_to_replace,
+            } => Some(Self::Insertion {
+                text: text.into(),
+                utf16_range_to_replace,
+            }),
+        }
+    }
+}
+
 impl Clone for ReplayableAction {
     fn clone(&self) -> Self {
         match self {
@@ -981,6 +1167,50 @@ impl Clone for ReplayableAction {
     }
 }
 
+/// A [`ReplayableAction`] as it's persisted in the database.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) enum SerializedReplayableAction {
+    Action {
+        name: String,
+        input: Option<String>,
+    },
+    Insertion {
+        text: String,
+        utf16_range_to_replace: Option<Range<isize>>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HistoryKind {
+    Search,
+    Command,
+}
+
+impl HistoryKind {
+    fn db_name(self) -> &'static str {
+        match self {
+            HistoryKind::Search => "search",
+            HistoryKind::Command => "command",
+        }
+    }
+}
+
+/// Adds an entry to the end of a history, removing any earlier copy of it and the oldest entries
+/// beyond the limit.
+fn push_history(history: &mut Vec<String>, entry: String) {
+    history.retain(|existing| *existing != entry);
+    history.push(entry);
+    if history.len() > MAX_HISTORY_LEN {
+        history.drain(..history.len() - MAX_HISTORY_LEN);
+    }
+}
+
+/// Whether a register is persisted between sessions. The clipboard registers are left to the
+/// system, and the read-only ones are derived from other state.
+fn is_persisted_register(name: char) -> bool {
+    name.is_ascii_lowercase() || name.is_ascii_digit() || name == '"' || name == '-'
+}
+
 #[derive(Clone, Default, Debug)]
 pub struct SearchState {
     pub direction: Direction,
@@ -989,7 +1219,20 @@ pub struct SearchState {
     pub prior_selections: Vec<Range<Anchor>>,
     pub prior_operator: Option<Operator>,
     pub prior_mode: Mode,
-    pub helix_select: bool,
+    pub helix_select: Option<HelixRegexSelection>,
+}
+
+/// What a helix regex prompt does with the matches within the selections.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HelixRegexSelection {
+    /// Selects the matches.
+    Select,
+    /// Splits the selections on the matches.
+    Split,
+    /// Keeps the selections containing a match.
+    Keep,
+    /// Removes the selections containing a match.
+    Remove,
 }
 
 impl Operator {
@@ -1034,6 +1277,9 @@ impl Operator {
             Operator::HelixMatch => "helix_m",
             Operator::HelixNext { .. } => "helix_next",
             Operator::HelixPrevious { .. } => "helix_previous",
+            Operator::HelixSurroundAdd => "helix_ms",
+            Operator::HelixSurroundReplace { .. } => "helix_mr",
+            Operator::HelixSurroundDelete => "helix_md",
         }
     }
 
@@ -1058,6 +1304,14 @@ impl Operator {
             Operator::HelixMatch => "m".to_string(),
             Operator::HelixNext { .. } => "]".to_string(),
             Operator::HelixPrevious { .. } => "[".to_string(),
+            Operator::HelixSurroundAdd => "ms".to_string(),
+            Operator::HelixSurroundReplace {
+                replaced_char: Some(replaced_char),
+            } => format!("mr{}", make_visible(&replaced_char.to_string())),
+            Operator::HelixSurroundReplace {
+                replaced_char: None,
+            } => "mr".to_string(),
+            Operator::HelixSurroundDelete => "md".to_string(),
             _ => self.id().to_string(),
         }
     }
@@ -1078,7 +1332,10 @@ impl Operator {
             | Operator::Digraph { .. }
             | Operator::Literal { .. }
             | Operator::ChangeSurrounds { target: Some(_) }
-            | Operator::DeleteSurrounds => true,
+            | Operator::DeleteSurrounds
+            | Operator::HelixSurroundAdd
+            | Operator::HelixSurroundReplace { .. }
+            | Operator::HelixSurroundDelete => true,
             Operator::Change
             | Operator::Delete
             | Operator::Yank
@@ -1141,7 +1398,10 @@ impl Operator {
             | Operator::Register
             | Operator::RecordRegister
             | Operator::ReplayRegister
-            | Operator::HelixMatch => false,
+            | Operator::HelixMatch
+            | Operator::HelixSurroundAdd
+            | Operator::HelixSurroundReplace { .. }
+            | Operator::HelixSurroundDelete => false,
         }
     }
 }
@@ -1687,8 +1947,13 @@ impl MarksView {
     }
 }
 
+#[derive(Clone)]
 pub struct VimDb(ThreadSafeConnection);
 
+/// Set as a global to persist registers, recordings and histories to a database other than
+/// [`DB`], so that tests don't see what other tests persisted.
+impl Global for VimDb {}
+
 impl Domain for VimDb {
     const NAME: &str = stringify!(VimDb);
 
@@ -1711,6 +1976,22 @@ impl Domain for VimDb {
             CREATE UNIQUE INDEX idx_vim_global_marks_paths
             ON vim_global_marks_paths(workspace_id, mark_name);
         ),
+        sql! (
+            CREATE TABLE vim_registers (
+                register_name TEXT PRIMARY KEY,
+                text TEXT NOT NULL,
+                clipboard_selections TEXT
+            );
+            CREATE TABLE vim_recordings (
+                register_name TEXT PRIMARY KEY,
+                actions TEXT NOT NULL
+            );
+            CREATE TABLE vim_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                kind TEXT NOT NULL,
+                entry TEXT NOT NULL
+            );
+        ),
     ];
 }
 
@@ -1722,7 +2003,39 @@ struct SerializedMark {
     points: Vec<Point>,
 }
 
+/// Registers with more text than this aren't persisted, like with neovim's default `shada`.
+const MAX_PERSISTED_REGISTER_LEN: usize = 10 * 1024;
+/// Recordings with more actions than this aren't persisted.
+const MAX_PERSISTED_RECORDING_LEN: usize = 1000;
+/// The number of entries kept in each of the search and command histories.
+pub(crate) const MAX_HISTORY_LEN: usize = 100;
+
+/// The registers, recordings and histories persisted for the user, which are shared by all
+/// workspaces.
+#[derive(Default)]
+pub(crate) struct PersistedState {
+    pub(crate) registers: Vec<(char, Register)>,
+    pub(crate) recordings: Vec<(char, Vec<SerializedReplayableAction>)>,
+    pub(crate) search_history: Vec<String>,
+    pub(crate) command_history: Vec<String>,
+}
+
 impl VimDb {
+    /// Returns the database that registers, recordings and histories are persisted to.
+    fn global(cx: &App) -> Self {
+        cx.try_global::<Self>()
+            .cloned()
+            .unwrap_or_else(|| DB.clone())
+    }
+
+    /// Opens an empty database that isn't shared with other tests.
+    #[cfg(test)]
+    pub(crate) async fn open_unique_test_db() -> Self {
+        static NEXT_ID: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        let id = NEXT_ID.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Self(db::open_test_db::<VimDb>(&format!("VimDb-{id}")).await)
+    }
+
     pub(crate) async fn set_marks(
         &self,
         workspace_id: WorkspaceId,
@@ -1829,4 +2142,198 @@ impl VimDb {
         })
         .await
     }
+
+    pub(crate) async fn set_registers(
+        &self,
+        registers: Vec<(char, Option<Register>)>,
+    ) -> Result<()> {
+        self.write(move |conn| {
+            let mut insert = conn.exec_bound(sql!(
+                INSERT OR REPLACE INTO vim_registers
+                    (register_name, text, clipboard_selections)
+                VALUES
+                    (?, ?, ?)
+            ))?;
+            let mut delete = conn.exec_bound(sql!(
+                DELETE FROM vim_registers WHERE register_name = ?
+            ))?;
+            for (name, register) in registers {
+                let name = name.to_string();
+                if let Some(register) = register {
+                    let clipboard_selections = register
+                        .clipboard_selections
+                        .map(|selections| serde_json::to_string(&selections))
+                        .transpose()?;
+                    insert((name, register.text.to_string(), clipboard_selections))?;
+                } else {
+                    delete(name)?;
+                }
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    pub(crate) async fn set_recording(&self, name: char, actions: Option<String>) -> Result<()> {
+        self.write(move |conn| {
+            if let Some(actions) = actions {
+                conn.exec_bound(sql!(
+                    INSERT OR REPLACE INTO vim_recordings
+                        (register_name, actions)
+                    VALUES
+                        (?, ?)
+                ))?((name.to_string(), actions))
+            } else {
+                conn.exec_bound(sql!(
+                    DELETE FROM vim_recordings WHERE register_name = ?
+                ))?(name.to_string())
+            }
+        })
+        .await
+    }
+
+    pub(crate) async fn add_history_entry(&self, kind: HistoryKind, entry: String) -> Result<()> {
+        let kind = kind.db_name();
+        self.write(move |conn| {
+            conn.exec_bound(sql!(
+                DELETE FROM vim_history WHERE kind = ? AND entry = ?
+            ))?((kind, entry.as_str()))?;
+            conn.exec_bound(sql!(
+                INSERT INTO vim_history (kind, entry) VALUES (?, ?)
+            ))?((kind, entry.as_str()))?;
+            conn.exec_bound(sql!(
+                DELETE FROM vim_history WHERE kind = ? AND id NOT IN (
+                    SELECT id FROM vim_history WHERE kind = ? ORDER BY id DESC LIMIT ?
+                )
+            ))?((kind, kind, MAX_HISTORY_LEN))
+        })
+        .await
+    }
+
+    fn get_history(&self, kind: HistoryKind) -> Result<Vec<String>> {
+        self.select_bound(sql!(
+            SELECT entry FROM vim_history
+                WHERE kind = ?
+                ORDER BY id
+        ))?(kind.db_name())
+    }
+
+    pub(crate) fn get_persisted_state(&self) -> Result<PersistedState> {
+        let registers: Vec<(String, String, Option<String>)> = self.select(sql!(
+            SELECT register_name, text, clipboard_selections FROM vim_registers
+        ))?()?;
+        let recordings: Vec<(String, String)> = self.select(sql!(
+            SELECT register_name, actions FROM vim_recordings
+        ))?()?;
+
+        Ok(PersistedState {
+            registers: registers
+                .into_iter()
+                .filter_map(|(name, text, clipboard_selections)| {
+                    let clipboard_selections = clipboard_selections
+                        .map(|selections| serde_json::from_str(&selections))
+                        .transpose()
+                        .log_err()?;
+                    let register = Register {
+                        text: text.into(),
+                        clipboard_selections,
+                    };
+                    Some((name.chars().next()?, register))
+                })
+                .collect(),
+            recordings: recordings
+                .into_iter()
+                .filter_map(|(name, actions)| {
+                    Some((
+                        name.chars().next()?,
+                        serde_json::from_str(&actions).log_err()?,
+                    ))
+                })
+                .collect(),
+            search_history: self.get_history(HistoryKind::Search)?,
+            command_history: self.get_history(HistoryKind::Command)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::VimTestContext;
+
+    #[gpui::test]
+    async fn test_persisted_state() {
+        let db = VimDb::open_test_db("test_persisted_state").await;
+
+        db.set_registers(vec![
+            ('a', Some(Register::from("one".to_string()))),
+            ('b', Some(Register::from("two".to_string()))),
+        ])
+        .await
+        .unwrap();
+        db.set_registers(vec![('b', None)]).await.unwrap();
+
+        let recording = vec![SerializedReplayableAction::Insertion {
+            text: "x".into(),
+            utf16_range_to_replace: None,
+        }];
+        db.set_recording('q', Some(serde_json::to_string(&recording).unwrap()))
+            .await
+            .unwrap();
+
+        for i in 0..=MAX_HISTORY_LEN {
+            db.add_history_entry(HistoryKind::Search, i.to_string())
+                .await
+                .unwrap();
+        }
+        db.add_history_entry(HistoryKind::Search, "1".into())
+            .await
+            .unwrap();
+        db.add_history_entry(HistoryKind::Command, "w".into())
+            .await
+            .unwrap();
+
+        let state = db.get_persisted_state().unwrap();
+        assert_eq!(state.registers.len(), 1);
+        assert_eq!(state.registers[0].0, 'a');
+        assert_eq!(state.registers[0].1.text.as_ref(), "one");
+        assert_eq!(state.recordings, [('q', recording)]);
+        assert_eq!(state.search_history.len(), MAX_HISTORY_LEN);
+        assert_eq!(state.search_history.first().unwrap(), "2");
+        assert_eq!(state.search_history.last().unwrap(), "1");
+        assert_eq!(state.command_history, ["w"]);
+    }
+
+    #[gpui::test]
+    async fn test_load_persisted(cx: &mut gpui::TestAppContext) {
+        VimTestContext::init(cx);
+        let db = cx.read(VimDb::global);
+        db.set_registers(vec![('a', Some(Register::from("one".to_string())))])
+            .await
+            .unwrap();
+        let recording = vec![SerializedReplayableAction::Insertion {
+            text: "x".into(),
+            utf16_range_to_replace: None,
+        }];
+        db.set_recording('q', Some(serde_json::to_string(&recording).unwrap()))
+            .await
+            .unwrap();
+        db.add_history_entry(HistoryKind::Search, "two".into())
+            .await
+            .unwrap();
+        db.add_history_entry(HistoryKind::Command, "w".into())
+            .await
+            .unwrap();
+
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.run_until_parked();
+        cx.read(|cx| {
+            let globals = cx.global::<VimGlobals>();
+            assert_eq!(globals.registers[&'a'].text.as_ref(), "one");
+            assert_eq!(globals.registers[&'/'].text.as_ref(), "two");
+            assert_eq!(globals.recordings[&'q'].len(), 1);
+            assert_eq!(globals.search_history, ["two"]);
+            assert_eq!(globals.command_history, ["w"]);
+        });
+    }
 }
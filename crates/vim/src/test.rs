@@ -7,13 +7,15 @@ use std::time::Duration;
 use collections::HashMap;
 use command_palette::CommandPalette;
 use editor::{
-    AnchorRangeExt, DisplayPoint, Editor, EditorMode, MultiBuffer, actions::DeleteLine,
-    code_context_menus::CodeContextMenu, display_map::DisplayRow,
-    test::editor_test_context::EditorTestContext,
+    AnchorRangeExt, DisplayPoint, Editor, EditorMode, MultiBuffer,
+    actions::DeleteLine,
+    code_context_menus::CodeContextMenu,
+    display_map::DisplayRow,
+    test::{editor_lsp_test_context::EditorLspTestContext, editor_test_context::EditorTestContext},
 };
 use futures::StreamExt;
 use gpui::{KeyBinding, Modifiers, MouseButton, TestAppContext, px};
-use language::Point;
+use language::{Point, language_settings::FoldingProvider};
 pub use neovim_backed_test_context::*;
 use settings::SettingsStore;
 use ui::Pixels;
@@ -691,6 +693,84 @@ async fn test_folds_panic(cx: &mut gpui::TestAppContext) {
         ˇ"});
 }
 
+#[perf]
+#[gpui::test]
+async fn test_lsp_folds(cx: &mut gpui::TestAppContext) {
+    VimTestContext::init(cx);
+    cx.update(|cx| {
+        SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings(cx, |settings| {
+                settings.project.all_languages.defaults.folding_provider =
+                    Some(FoldingProvider::Lsp);
+            });
+        });
+    });
+    let mut cx = VimTestContext::new_with_lsp(
+        EditorLspTestContext::new_rust(
+            lsp::ServerCapabilities {
+                folding_range_provider: Some(lsp::FoldingRangeProviderCapability::Simple(true)),
+                ..Default::default()
+            },
+            cx,
+        )
+        .await,
+        true,
+    );
+    let mut folding_range_requests = cx
+        .set_request_handler::<lsp::request::FoldingRangeRequest, _, _>(|_, _, _| async move {
+            Ok(Some(vec![lsp::FoldingRange {
+                start_line: 1,
+                end_line: 3,
+                ..Default::default()
+            }]))
+        });
+    cx.set_state(
+        indoc! {"
+            fn boop() {
+                // region
+                barp();
+                // endregion
+                ˇbazp();
+            }
+        "},
+        Mode::Normal,
+    );
+    cx.executor().advance_clock(Duration::from_millis(100));
+    folding_range_requests.next().await.unwrap();
+    cx.run_until_parked();
+
+    let unfolded = indoc! {"
+        fn boop() {
+            // region
+            barp();
+            // endregion
+            bazp();
+        }
+    "};
+    let folded = indoc! {"
+        fn boop() {
+            // region⋯
+            bazp();
+        }
+    "};
+    let display_text =
+        |cx: &mut VimTestContext| cx.update_editor(|editor, _, cx| editor.display_text(cx));
+
+    // Only the ranges from the language server can be folded, not the indented function body.
+    cx.simulate_keystrokes("z c");
+    assert_eq!(display_text(&mut cx), unfolded);
+
+    cx.simulate_keystrokes("k k z c");
+    assert_eq!(display_text(&mut cx), folded);
+    cx.simulate_keystrokes("z o");
+    assert_eq!(display_text(&mut cx), unfolded);
+
+    cx.simulate_keystrokes("z a");
+    assert_eq!(display_text(&mut cx), folded);
+    cx.simulate_keystrokes("z a");
+    assert_eq!(display_text(&mut cx), unfolded);
+}
+
 #[perf]
 #[gpui::test]
 async fn test_clear_counts(cx: &mut gpui::TestAppContext) {
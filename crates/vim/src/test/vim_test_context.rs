@@ -4,7 +4,10 @@ use editor::test::editor_lsp_test_context::EditorLspTestContext;
 use gpui::{Context, Entity, SemanticVersion, UpdateGlobal};
 use search::{BufferSearchBar, project_search::ProjectSearchBar};
 
-use crate::{state::Operator, *};
+use crate::{
+    state::{Operator, VimDb},
+    *,
+};
 
 pub struct VimTestContext {
     cx: EditorLspTestContext,
@@ -23,6 +26,7 @@ impl VimTestContext {
             command_palette::init(cx);
             project_panel::init(cx);
             git_ui::init(cx);
+            cx.set_global(db::smol::block_on(VimDb::open_unique_test_db()));
             crate::init(cx);
             search::init(cx);
             workspace::init_settings(cx);
@@ -3,12 +3,14 @@
 #[cfg(test)]
 mod test;
 
+mod abbreviation;
 mod change_list;
 mod command;
 mod digraph;
 mod helix;
 mod indent;
 mod insert;
+mod mapping;
 mod mode_indicator;
 mod motion;
 mod normal;
@@ -17,8 +19,10 @@ mod replace;
 mod rewrap;
 mod state;
 mod surrounds;
+mod vimrc;
 mod visual;
 
+use abbreviation::AbbreviateCommand;
 use collections::HashMap;
 use editor::{
     Anchor, Bias, Editor, EditorEvent, EditorSettings, HideMouseCursorOrigin, SelectionEffects,
@@ -33,6 +37,8 @@ use insert::{NormalBefore, TemporaryNormal};
 use language::{
     CharKind, CharScopeContext, CursorShape, Point, Selection, SelectionGoal, TransactionId,
 };
+use mapping::MapCommand;
+pub use mapping::bind_mappings;
 pub use mode_indicator::ModeIndicator;
 use motion::Motion;
 use normal::search::SearchSubmit;
@@ -49,7 +55,8 @@ use theme::ThemeSettings;
 use ui::{IntoElement, SharedString, px};
 use vim_mode_setting::HelixModeSetting;
 use vim_mode_setting::VimModeSetting;
-use workspace::{self, Pane, Workspace};
+use vimrc::SourceCommand;
+use workspace::{self, Pane, Workspace, notifications::NotifyResultExt};
 
 use crate::state::ReplayableAction;
 
@@ -248,6 +255,12 @@ actions!(
         ToggleProjectPanelFocus,
         /// Starts a match operation.
         PushHelixMatch,
+        /// Surrounds the selections with a pair of characters.
+        PushHelixSurroundAdd,
+        /// Replaces the pair of characters surrounding the selections.
+        PushHelixSurroundReplace,
+        /// Deletes the pair of characters surrounding the selections.
+        PushHelixSurroundDelete,
     ]
 );
 
@@ -265,10 +278,19 @@ pub fn init(cx: &mut App) {
     vim_mode_setting::init(cx);
     VimSettings::register(cx);
     VimGlobals::register(cx);
+    mapping::init(cx);
+    abbreviation::init(cx);
+    vimrc::init(cx);
 
     cx.observe_new(Vim::register).detach();
 
-    cx.observe_new(|workspace: &mut Workspace, _, _| {
+    cx.observe_new(|workspace: &mut Workspace, _, cx| {
+        vimrc::watch(workspace.app_state().fs.clone(), cx);
+        cx.observe_global::<SettingsStore>(|workspace, cx| {
+            vimrc::watch(workspace.app_state().fs.clone(), cx);
+        })
+        .detach();
+
         workspace.register_action(|workspace, _: &ToggleVimMode, _, cx| {
             let fs = workspace.app_state().fs.clone();
             let currently_enabled = Vim::enabled(cx);
@@ -332,6 +354,18 @@ pub fn init(cx: &mut App) {
             });
         });
 
+        workspace.register_action(|workspace, action: &MapCommand, _, cx| {
+            action.run(cx).notify_err(workspace, cx);
+        });
+
+        workspace.register_action(|workspace, action: &AbbreviateCommand, _, cx| {
+            action.run(cx).notify_err(workspace, cx);
+        });
+
+        workspace.register_action(|workspace, action: &SourceCommand, window, cx| {
+            action.run(workspace, window, cx);
+        });
+
         workspace.register_action(|workspace, _: &ResetPaneSizes, _, cx| {
             workspace.reset_pane_sizes(cx);
         });
@@ -570,6 +604,8 @@ impl Vim {
             vim.update(cx, |vim, _| {
                 vim.mode = Mode::Insert;
             });
+        } else {
+            vimrc::apply_options(editor, cx);
         }
 
         editor.register_addon(VimAddon {
@@ -840,6 +876,29 @@ impl Vim {
             Vim::action(editor, cx, |vim, _: &PushHelixMatch, window, cx| {
                 vim.push_operator(Operator::HelixMatch, window, cx)
             });
+            Vim::action(editor, cx, |vim, _: &PushHelixSurroundAdd, window, cx| {
+                vim.push_operator(Operator::HelixSurroundAdd, window, cx)
+            });
+            Vim::action(
+                editor,
+                cx,
+                |vim, _: &PushHelixSurroundReplace, window, cx| {
+                    vim.push_operator(
+                        Operator::HelixSurroundReplace {
+                            replaced_char: None,
+                        },
+                        window,
+                        cx,
+                    )
+                },
+            );
+            Vim::action(
+                editor,
+                cx,
+                |vim, _: &PushHelixSurroundDelete, window, cx| {
+                    vim.push_operator(Operator::HelixSurroundDelete, window, cx)
+                },
+            );
             Vim::action(editor, cx, |vim, action: &PushHelixNext, window, cx| {
                 vim.push_operator(
                     Operator::HelixNext {
@@ -864,6 +923,7 @@ impl Vim {
             helix::register(editor, cx);
             motion::register(editor, cx);
             command::register(editor, cx);
+            mapping::register(editor, cx);
             replace::register(editor, cx);
             indent::register(editor, cx);
             rewrap::register(editor, cx);
@@ -990,7 +1050,10 @@ impl Vim {
             EditorEvent::InputHandled {
                 text,
                 utf16_range_to_replace: range_to_replace,
-            } => Vim::globals(cx).observe_insertion(text, range_to_replace.clone()),
+            } => {
+                Vim::globals(cx).observe_insertion(text, range_to_replace.clone());
+                self.expand_abbreviation(text, window, cx);
+            }
             EditorEvent::TransactionBegun { transaction_id } => {
                 self.transaction_begun(*transaction_id, window, cx)
             }
@@ -1030,6 +1093,9 @@ impl Vim {
                 | Operator::ChangeSurrounds { .. }
                 | Operator::DeleteSurrounds
                 | Operator::Exchange
+                | Operator::HelixSurroundAdd
+                | Operator::HelixSurroundReplace { .. }
+                | Operator::HelixSurroundDelete
         ) {
             self.operator_stack.clear();
         };
@@ -1297,6 +1363,10 @@ impl Vim {
             }
         }
 
+        if mapping::is_typing_noremap(cx) {
+            context.add("VimNoremap");
+        }
+
         if mode == "normal"
             || mode == "visual"
             || mode == "operator"
@@ -1795,6 +1865,34 @@ impl Vim {
                 }
                 _ => self.clear_operator(window, cx),
             },
+            Some(Operator::HelixSurroundAdd) => {
+                if let Some(char) = text.chars().next() {
+                    self.helix_surround_add(char, window, cx);
+                }
+                self.clear_operator(window, cx);
+            }
+            Some(Operator::HelixSurroundReplace { replaced_char }) => {
+                if let Some(replaced_char) = replaced_char {
+                    if let Some(char) = text.chars().next() {
+                        self.helix_surround_replace(replaced_char, char, window, cx);
+                    }
+                    self.clear_operator(window, cx);
+                } else {
+                    let replaced_char = text.chars().next();
+                    self.pop_operator(window, cx);
+                    self.push_operator(
+                        Operator::HelixSurroundReplace { replaced_char },
+                        window,
+                        cx,
+                    );
+                }
+            }
+            Some(Operator::HelixSurroundDelete) => {
+                if let Some(char) = text.chars().next() {
+                    self.helix_surround_delete(char, window, cx);
+                }
+                self.clear_operator(window, cx);
+            }
             Some(Operator::Mark) => self.create_mark(text, window, cx),
             Some(Operator::RecordRegister) => {
                 self.record_register(text.chars().next().unwrap(), window, cx)
@@ -1868,6 +1966,9 @@ struct VimSettings {
     pub custom_digraphs: HashMap<String, Arc<str>>,
     pub highlight_on_yank_duration: u64,
     pub cursor_shape: CursorShapeSettings,
+    pub vimrc: Option<String>,
+    pub abbreviations: HashMap<String, String>,
+    pub language_abbreviations: HashMap<String, HashMap<String, String>>,
 }
 
 /// The settings for cursor shape.
@@ -1922,6 +2023,9 @@ impl Settings for VimSettings {
             custom_digraphs: vim.custom_digraphs.unwrap(),
             highlight_on_yank_duration: vim.highlight_on_yank_duration.unwrap(),
             cursor_shape: vim.cursor_shape.unwrap().into(),
+            vimrc: vim.vimrc,
+            abbreviations: vim.abbreviations.unwrap(),
+            language_abbreviations: vim.language_abbreviations.unwrap(),
         }
     }
 }
@@ -0,0 +1,356 @@
+use anyhow::{Result, anyhow, bail};
+use editor::Editor;
+use fs::Fs;
+use futures::StreamExt as _;
+use gpui::{Action, App, Context, Global, Task, Window};
+use settings::{Settings, watch_config_file};
+use std::{path::PathBuf, sync::Arc};
+use workspace::{
+    Workspace,
+    notifications::{NotifyResultExt, NotifyTaskExt},
+};
+
+use crate::{
+    VimSettings,
+    abbreviation::{self, ABBREVIATE_COMMANDS},
+    command::VimOption,
+    mapping::{self, MAP_COMMANDS},
+};
+
+/// Runs the commands of a vimrc-style file.
+#[derive(Clone, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub(crate) struct SourceCommand {
+    pub(crate) path: String,
+}
+
+/// The vimrc set by the `vimrc` setting, which is reloaded when it changes.
+#[derive(Default)]
+struct Vimrc {
+    path: Option<PathBuf>,
+    /// The options set by the vimrc, which are applied to each editor.
+    options: Vec<VimOption>,
+    _watch: Option<Task<()>>,
+}
+
+impl Global for Vimrc {}
+
+pub(crate) fn init(cx: &mut App) {
+    cx.set_global(Vimrc::default());
+}
+
+/// Starts watching the vimrc if the setting changed since it was loaded.
+pub(crate) fn watch(fs: Arc<dyn Fs>, cx: &mut App) {
+    let path = VimSettings::get_global(cx)
+        .vimrc
+        .as_deref()
+        .map(resolve_path);
+    if cx.global::<Vimrc>().path == path {
+        return;
+    }
+
+    let task = path.clone().map(|path| {
+        let mut contents = watch_config_file(cx.background_executor(), fs, path);
+        cx.spawn(async move |cx| {
+            while let Some(contents) = contents.next().await {
+                if cx.update(|cx| load(&contents, cx)).is_err() {
+                    break;
+                }
+            }
+        })
+    });
+    if task.is_none() {
+        load("", cx);
+    }
+    let vimrc = cx.global_mut::<Vimrc>();
+    vimrc.path = path;
+    vimrc._watch = task;
+}
+
+fn resolve_path(path: &str) -> PathBuf {
+    util::paths::home_dir().join(shellexpand::tilde(path).as_ref())
+}
+
+fn load(contents: &str, cx: &mut App) {
+    mapping::clear_sourced(cx);
+    abbreviation::clear_sourced(cx);
+    let (options, result) = source(contents, true, cx);
+    for option in &options {
+        // The options that aren't local to an editor only need to be applied once.
+        if let VimOption::IgnoreCase(enabled) = option {
+            VimOption::set_ignore_case(*enabled, cx);
+        }
+    }
+    cx.global_mut::<Vimrc>().options = options;
+    result
+        .map_err(|error| anyhow!("Invalid vimrc\n{error}"))
+        .notify_app_err(cx);
+}
+
+/// Applies the options set by the vimrc to an editor.
+pub(crate) fn apply_options(editor: &mut Editor, cx: &mut Context<Editor>) {
+    let Some(vimrc) = cx.try_global::<Vimrc>() else {
+        return;
+    };
+    for option in vimrc.options.clone() {
+        if !matches!(option, VimOption::IgnoreCase(_)) {
+            option.apply(editor, cx);
+        }
+    }
+}
+
+impl SourceCommand {
+    pub(crate) fn run(
+        &self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let fs = workspace.app_state().fs.clone();
+        let path = resolve_path(&self.path);
+        let editor = workspace.active_item_as::<Editor>(cx);
+        cx.spawn(async move |_, cx| {
+            let contents = fs.load(&path).await?;
+            cx.update(|cx| {
+                let (options, result) = source(&contents, false, cx);
+                if let Some(editor) = editor {
+                    editor.update(cx, |editor, cx| {
+                        for option in &options {
+                            option.apply(editor, cx);
+                        }
+                    });
+                }
+                result
+            })?
+        })
+        .detach_and_notify_err(window, cx);
+    }
+}
+
+/// Runs the lines of a vimrc-style file, returning the options it sets. Commands and options that
+/// aren't supported are skipped, and lines that fail don't prevent the others from running.
+fn source(contents: &str, from_vimrc: bool, cx: &mut App) -> (Vec<VimOption>, Result<()>) {
+    let mut options = Vec::new();
+    let mut errors = Vec::new();
+    for (row, line) in join_continuation_lines(contents) {
+        if let Err(error) = source_line(&line, from_vimrc, &mut options, cx) {
+            errors.push(format!("line {}: {error}", row + 1));
+        }
+    }
+    let result = if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!(errors.join("\n")))
+    };
+    (options, result)
+}
+
+/// Joins the lines starting with a backslash to the line before them, returning each line along
+/// with the row it starts on.
+fn join_continuation_lines(contents: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    for (row, line) in contents.lines().enumerate() {
+        if let Some(continuation) = line.trim_start().strip_prefix('\\')
+            && let Some((_, previous)) = lines.last_mut()
+        {
+            previous.push_str(continuation);
+        } else {
+            lines.push((row, line.to_string()));
+        }
+    }
+    lines
+}
+
+fn source_line(
+    line: &str,
+    from_vimrc: bool,
+    options: &mut Vec<VimOption>,
+    cx: &mut App,
+) -> Result<()> {
+    let line = line.trim_start().trim_start_matches(':').trim();
+    if line.is_empty() || line.starts_with('"') {
+        return Ok(());
+    }
+    let (command, args) = line
+        .split_once(char::is_whitespace)
+        .map(|(command, args)| (command, args.trim_start()))
+        .unwrap_or((line, ""));
+
+    match command {
+        "se" | "set" => {
+            for option in args.split_whitespace() {
+                if option.starts_with('"') {
+                    break;
+                }
+                match VimOption::from(option) {
+                    Some(option) => options.push(option),
+                    None => log::info!("skipping unsupported vim option: {option}"),
+                }
+            }
+            Ok(())
+        }
+        "let" => let_command(args, cx),
+        _ => {
+            let (name, bang) = match command.strip_suffix('!') {
+                Some(name) => (name, true),
+                None => (command, false),
+            };
+            let matches_command = |prefix: &str, suffix: &str| {
+                name.strip_prefix(prefix)
+                    .is_some_and(|rest| suffix.starts_with(rest))
+            };
+            if let Some(&(_, _, kind, modes)) = MAP_COMMANDS
+                .iter()
+                .find(|(prefix, suffix, _, _)| matches_command(prefix, suffix))
+            {
+                let modes = if bang {
+                    mapping::bang_modes(modes).ok_or_else(|| anyhow!("no ! allowed: {line}"))?
+                } else {
+                    modes
+                };
+                mapping::run_map_command(kind, modes, args, from_vimrc, cx)?;
+            } else if let Some(&(_, _, kind, modes)) = ABBREVIATE_COMMANDS
+                .iter()
+                .find(|(prefix, suffix, _, _)| matches_command(prefix, suffix))
+            {
+                if bang {
+                    bail!("no ! allowed: {line}");
+                }
+                abbreviation::run_abbreviate_command(kind, modes, args, from_vimrc, cx)?;
+            } else {
+                log::info!("skipping unsupported vim command: {line}");
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Runs `let`, which is only supported for `mapleader`.
+fn let_command(args: &str, cx: &mut App) -> Result<()> {
+    let Some((name, value)) = args.split_once('=') else {
+        bail!("invalid let: {args}");
+    };
+    match name.trim() {
+        "mapleader" | "g:mapleader" => {
+            let leader = parse_string(value.trim())?;
+            mapping::set_leader(Some(leader), cx);
+        }
+        name => log::info!("skipping unsupported vim variable: {name}"),
+    }
+    Ok(())
+}
+
+/// Parses a Vim string literal into keys written in key notation, so that the special keys of a
+/// double-quoted string like `"\<Space>"` can be used as the leader.
+fn parse_string(value: &str) -> Result<String> {
+    let mut chars = value.chars();
+    let mut string = String::new();
+    let rest = match chars.next() {
+        Some('\'') => loop {
+            match chars.next() {
+                Some('\'') if chars.as_str().starts_with('\'') => {
+                    chars.next();
+                    string.push('\'');
+                }
+                Some('\'') => break chars.as_str(),
+                Some('<') => string.push_str("<lt>"),
+                Some(char) => string.push(char),
+                None => bail!("missing quote: {value}"),
+            }
+        },
+        Some('"') => loop {
+            match chars.next() {
+                Some('\\') => match chars.next() {
+                    Some('<') => string.push('<'),
+                    Some('t') => string.push_str("<Tab>"),
+                    Some('e') => string.push_str("<Esc>"),
+                    Some('n' | 'r') => string.push_str("<CR>"),
+                    Some(char) => string.push(char),
+                    None => bail!("missing quote: {value}"),
+                },
+                Some('"') => break chars.as_str(),
+                Some('<') => string.push_str("<lt>"),
+                Some(char) => string.push(char),
+                None => bail!("missing quote: {value}"),
+            }
+        },
+        _ => bail!("invalid expression: {value}"),
+    };
+    let rest = rest.trim_start();
+    if !rest.is_empty() && !rest.starts_with('"') {
+        bail!("trailing characters: {rest}");
+    }
+    Ok(string)
+}
+
+#[cfg(test)]
+mod test {
+    use gpui::TestAppContext;
+    use indoc::indoc;
+    use settings::SettingsStore;
+    use util::path;
+
+    use crate::{state::Mode, test::VimTestContext};
+
+    use super::parse_string;
+
+    #[test]
+    fn test_parse_string() {
+        assert_eq!(parse_string("','").unwrap(), ",");
+        assert_eq!(parse_string("\" \"").unwrap(), " ");
+        assert_eq!(parse_string("\"\\<Space>\"").unwrap(), "<Space>");
+        assert_eq!(parse_string("\"\\\\\"  \" comment").unwrap(), "\\");
+        assert_eq!(parse_string("'it''s'").unwrap(), "it's");
+        assert_eq!(parse_string("'<'").unwrap(), "<lt>");
+        assert!(parse_string("\",").is_err());
+        assert!(parse_string("mapleader").is_err());
+    }
+
+    #[gpui::test]
+    async fn test_vimrc(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        let fs = cx.workspace(|workspace, _, cx| workspace.project().read(cx).fs().clone());
+        fs.as_fake()
+            .insert_file(
+                path!("/root/.vimrc"),
+                indoc! {r#"
+                    " Comments and unsupported lines are skipped.
+                    set nocompatible number
+                    syntax on
+                    let mapleader = "\<Space>"
+                    nnoremap <Leader>d
+                        \ dd
+                    inoremap jk <Esc>
+                "#}
+                .as_bytes()
+                .to_vec(),
+            )
+            .await;
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings(cx, |settings| {
+                settings.vim.get_or_insert_default().vimrc = Some(path!("/root/.vimrc").into());
+            });
+        });
+        cx.run_until_parked();
+
+        cx.set_state(
+            indoc! {"
+            ˇone
+            two"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes("space d");
+        cx.assert_state("ˇtwo", Mode::Normal);
+        cx.simulate_keystrokes("A ! j k");
+        cx.assert_state("twoˇ!", Mode::Normal);
+
+        fs.as_fake()
+            .insert_file(path!("/root/.vimrc"), b"nnoremap <Space>d x\n".to_vec())
+            .await;
+        cx.run_until_parked();
+        cx.simulate_keystrokes("0 space d");
+        cx.assert_state("ˇwo!", Mode::Normal);
+        cx.simulate_keystrokes("i j k");
+        cx.assert_state("jkˇwo!", Mode::Insert);
+    }
+}
@@ -16,7 +16,10 @@ use db::{
     sqlez_macros::sql,
 };
 use gpui::{Axis, Bounds, Task, WindowBounds, WindowId, point, size};
-use project::debugger::breakpoint_store::{BreakpointState, SourceBreakpoint};
+use project::{
+    bookmark_store::SerializedBookmark,
+    debugger::breakpoint_store::{BreakpointState, SourceBreakpoint},
+};
 
 use language::{LanguageName, Toolchain, ToolchainScope};
 use project::WorktreeId;
@@ -702,6 +705,17 @@ impl Domain for WorkspaceDb {
         sql!(
             DROP TABLE ssh_connections;
         ),
+        sql!(
+            CREATE TABLE bookmarks (
+                workspace_id INTEGER NOT NULL,
+                path TEXT NOT NULL,
+                row INTEGER NOT NULL,
+                label TEXT,
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            ) STRICT;
+        ),
     ];
 
     // Allow recovering from bad migration that was initially shipped to nightly
@@ -821,6 +835,7 @@ impl WorkspaceDb {
             docks,
             session_id: None,
             breakpoints: self.breakpoints(workspace_id),
+            bookmarks: self.bookmarks(workspace_id),
             window_id,
             user_toolchains: self.user_toolchains(workspace_id, remote_connection_id),
         })
@@ -872,6 +887,38 @@ impl WorkspaceDb {
         }
     }
 
+    fn bookmarks(&self, workspace_id: WorkspaceId) -> BTreeMap<Arc<Path>, Vec<SerializedBookmark>> {
+        let bookmarks: Result<Vec<(PathBuf, u32, Option<String>)>> = self
+            .select_bound(sql! {
+                SELECT path, row, label
+                FROM bookmarks
+                WHERE workspace_id = ?
+                ORDER BY path, row
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
+
+        match bookmarks {
+            Ok(bookmarks) => {
+                let mut map: BTreeMap<Arc<Path>, Vec<SerializedBookmark>> = Default::default();
+                for (path, row, label) in bookmarks {
+                    let path: Arc<Path> = path.into();
+                    map.entry(path.clone())
+                        .or_default()
+                        .push(SerializedBookmark {
+                            path,
+                            row,
+                            label: label.map(Arc::from),
+                        });
+                }
+                map
+            }
+            Err(msg) => {
+                log::error!("Bookmarks query failed with msg: {msg}");
+                Default::default()
+            }
+        }
+    }
+
     fn user_toolchains(
         &self,
         workspace_id: WorkspaceId,
@@ -998,6 +1045,27 @@ impl WorkspaceDb {
                     }
                 }
 
+                conn.exec_bound(
+                    sql!(
+                        DELETE FROM bookmarks WHERE workspace_id = ?1;
+                    )
+                )?(workspace.id).context("Clearing old bookmarks")?;
+
+                for (path, bookmarks) in workspace.bookmarks {
+                    for bookmark in bookmarks {
+                        conn.exec_bound(sql!(
+                            INSERT INTO bookmarks (workspace_id, path, row, label)
+                            VALUES (?1, ?2, ?3, ?4);
+                        ))?((
+                            workspace.id,
+                            path.as_ref(),
+                            bookmark.row,
+                            bookmark.label.as_deref(),
+                        ))
+                        .log_err();
+                    }
+                }
+
                 conn.exec_bound(
                     sql!(
                         DELETE FROM user_toolchains WHERE workspace_id = ?1;
@@ -1913,6 +1981,7 @@ mod tests {
             },
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2034,6 +2103,7 @@ mod tests {
             },
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2066,6 +2136,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: collections::BTreeMap::default(),
+            bookmarks: Default::default(),
             session_id: None,
             window_id: None,
             user_toolchains: Default::default(),
@@ -2082,6 +2153,53 @@ mod tests {
         assert!(empty_breakpoints.is_none());
     }
 
+    #[gpui::test]
+    async fn test_bookmarks() {
+        zlog::init_test();
+
+        let db = WorkspaceDb::open_test_db("test_bookmarks").await;
+        let id = db.next_id().await.unwrap();
+
+        let path: Arc<Path> = Arc::from(Path::new("/tmp/test_bookmarks.rs"));
+        let bookmarks = vec![
+            SerializedBookmark {
+                path: path.clone(),
+                row: 3,
+                label: None,
+            },
+            SerializedBookmark {
+                path: path.clone(),
+                row: 10,
+                label: Some("entry point".into()),
+            },
+        ];
+
+        let mut workspace = SerializedWorkspace {
+            id,
+            paths: PathList::new(&["/tmp"]),
+            location: SerializedWorkspaceLocation::Local,
+            center_group: Default::default(),
+            window_bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            centered_layout: false,
+            breakpoints: Default::default(),
+            bookmarks: collections::BTreeMap::from_iter([(path.clone(), bookmarks.clone())]),
+            session_id: None,
+            window_id: None,
+            user_toolchains: Default::default(),
+        };
+
+        db.save_workspace(workspace.clone()).await;
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(loaded.bookmarks.get(&path), Some(&bookmarks));
+
+        workspace.bookmarks = Default::default();
+        db.save_workspace(workspace).await;
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert!(loaded.bookmarks.is_empty());
+    }
+
     #[gpui::test]
     async fn test_next_id_stability() {
         zlog::init_test();
@@ -2164,6 +2282,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            bookmarks: Default::default(),
             session_id: None,
             window_id: None,
             user_toolchains: Default::default(),
@@ -2179,6 +2298,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            bookmarks: Default::default(),
             session_id: None,
             window_id: None,
             user_toolchains: Default::default(),
@@ -2283,6 +2403,7 @@ mod tests {
             center_group,
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            bookmarks: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -2317,6 +2438,7 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            bookmarks: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -2335,6 +2457,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            bookmarks: Default::default(),
             session_id: None,
             window_id: Some(2),
             user_toolchains: Default::default(),
@@ -2374,6 +2497,7 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            bookmarks: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -2415,6 +2539,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            bookmarks: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(10),
             user_toolchains: Default::default(),
@@ -2430,6 +2555,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            bookmarks: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(20),
             user_toolchains: Default::default(),
@@ -2445,6 +2571,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            bookmarks: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(30),
             user_toolchains: Default::default(),
@@ -2460,6 +2587,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            bookmarks: Default::default(),
             session_id: None,
             window_id: None,
             user_toolchains: Default::default(),
@@ -2486,6 +2614,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            bookmarks: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(50),
             user_toolchains: Default::default(),
@@ -2498,6 +2627,7 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            bookmarks: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -2552,6 +2682,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             breakpoints: Default::default(),
+            bookmarks: Default::default(),
             centered_layout: false,
             session_id: None,
             window_id: None,
@@ -2589,6 +2720,7 @@ mod tests {
             centered_layout: false,
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
+            bookmarks: Default::default(),
             window_id: Some(window_id),
             user_toolchains: Default::default(),
         })
@@ -2687,6 +2819,7 @@ mod tests {
             centered_layout: false,
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
+            bookmarks: Default::default(),
             window_id: Some(window_id),
             user_toolchains: Default::default(),
         })
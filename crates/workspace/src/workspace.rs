@@ -70,6 +70,7 @@ use postage::stream::Stream;
 use project::{
     DirectoryLister, Project, ProjectEntryId, ProjectPath, ResolvedPath, Worktree, WorktreeId,
     WorktreeSettings,
+    bookmark_store::BookmarkStoreEvent,
     debugger::{breakpoint_store::BreakpointStoreEvent, session::ThreadStatus},
     toolchain_store::ToolchainStoreEvent,
 };
@@ -1279,6 +1280,17 @@ impl Workspace {
             },
         )
         .detach();
+        cx.subscribe_in(
+            &project.read(cx).bookmark_store(),
+            window,
+            |workspace, _, event, window, cx| match event {
+                BookmarkStoreEvent::BookmarksUpdated(_)
+                | BookmarkStoreEvent::BookmarksCleared(_) => {
+                    workspace.serialize_workspace(window, cx);
+                }
+            },
+        )
+        .detach();
         if let Some(toolchain_store) = project.read(cx).toolchain_store() {
             cx.subscribe_in(
                 &toolchain_store,
@@ -5306,6 +5318,12 @@ impl Workspace {
                         .read(cx)
                         .all_source_breakpoints(cx)
                 });
+                let bookmarks = self.project.update(cx, |project, cx| {
+                    project
+                        .bookmark_store()
+                        .read(cx)
+                        .all_serialized_bookmarks(cx)
+                });
                 let user_toolchains = self
                     .project
                     .read(cx)
@@ -5327,6 +5345,7 @@ impl Workspace {
                     centered_layout: self.centered_layout,
                     session_id: self.session_id.clone(),
                     breakpoints,
+                    bookmarks,
                     window_id: Some(window.window_handle().window_id().as_u64()),
                     user_toolchains,
                 };
@@ -5514,6 +5533,15 @@ impl Workspace {
                 })?
                 .await;
 
+            project
+                .update(cx, |project, cx| {
+                    project.bookmark_store().update(cx, |bookmark_store, cx| {
+                        bookmark_store.with_serialized_bookmarks(serialized_workspace.bookmarks, cx)
+                    })
+                })?
+                .await
+                .log_err();
+
             // Clean up all the items that have _not_ been loaded. Our ItemIds aren't stable. That means
             // after loading the items, we might have different items and in order to avoid
             // the database filling up, we delete items that haven't been loaded now.
@@ -591,12 +591,16 @@ pub fn main() {
         ui_prompt::init(cx);
 
         go_to_line::init(cx);
+        bookmarks::init(cx);
+        spellcheck::init(app_state.languages.next_language_server_id(), cx);
+        local_history::init(cx);
         file_finder::init(cx);
         tab_switcher::init(cx);
         outline::init(cx);
         project_symbols::init(cx);
         project_panel::init(cx);
         outline_panel::init(cx);
+        hierarchy_panel::init(cx);
         tasks_ui::init(cx);
         snippets_ui::init(cx);
         channel::init(&app_state.client.clone(), app_state.user_store.clone(), cx);
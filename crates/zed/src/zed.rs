@@ -23,14 +23,15 @@ use editor::{Editor, MultiBuffer};
 use feature_flags::{FeatureFlagAppExt, PanicFeatureFlag};
 use futures::future::Either;
 use futures::{StreamExt, channel::mpsc, select_biased};
-use git_ui::git_panel::GitPanel;
 use git_ui::project_diff::ProjectDiffToolbar;
+use git_ui::{git_log_panel::GitLogPanel, git_panel::GitPanel};
 use gpui::{
     Action, App, AppContext as _, Context, DismissEvent, Element, Entity, Focusable, KeyBinding,
     ParentElement, PathPromptOptions, PromptLevel, ReadGlobal, SharedString, Styled, Task,
     TitlebarOptions, UpdateGlobal, Window, WindowKind, WindowOptions, actions, image_cache, point,
     px, retain_all,
 };
+use hierarchy_panel::HierarchyPanel;
 use image_viewer::ImageInfo;
 use language::Capability;
 use language_onboarding::BasedPyrightBanner;
@@ -566,8 +567,10 @@ fn initialize_panels(
     cx.spawn_in(window, async move |workspace_handle, cx| {
         let project_panel = ProjectPanel::load(workspace_handle.clone(), cx.clone());
         let outline_panel = OutlinePanel::load(workspace_handle.clone(), cx.clone());
+        let hierarchy_panel = HierarchyPanel::load(workspace_handle.clone(), cx.clone());
         let terminal_panel = TerminalPanel::load(workspace_handle.clone(), cx.clone());
         let git_panel = GitPanel::load(workspace_handle.clone(), cx.clone());
+        let git_log_panel = GitLogPanel::load(workspace_handle.clone(), cx.clone());
         let channels_panel =
             collab_ui::collab_panel::CollabPanel::load(workspace_handle.clone(), cx.clone());
         let notification_panel = collab_ui::notification_panel::NotificationPanel::load(
@@ -579,16 +582,20 @@ fn initialize_panels(
         let (
             project_panel,
             outline_panel,
+            hierarchy_panel,
             terminal_panel,
             git_panel,
+            git_log_panel,
             channels_panel,
             notification_panel,
             debug_panel,
         ) = futures::try_join!(
             project_panel,
             outline_panel,
+            hierarchy_panel,
             git_panel,
             terminal_panel,
+            git_log_panel,
             channels_panel,
             notification_panel,
             debug_panel,
@@ -597,8 +604,10 @@ fn initialize_panels(
         workspace_handle.update_in(cx, |workspace, window, cx| {
             workspace.add_panel(project_panel, window, cx);
             workspace.add_panel(outline_panel, window, cx);
+            workspace.add_panel(hierarchy_panel, window, cx);
             workspace.add_panel(terminal_panel, window, cx);
             workspace.add_panel(git_panel, window, cx);
+            workspace.add_panel(git_log_panel, window, cx);
             workspace.add_panel(channels_panel, window, cx);
             workspace.add_panel(notification_panel, window, cx);
             workspace.add_panel(debug_panel, window, cx);
@@ -1518,6 +1527,7 @@ fn reload_keymaps(cx: &mut App, mut user_key_bindings: Vec<KeyBinding>) {
         key_binding.set_meta(KeybindSource::User.meta());
     }
     cx.bind_keys(user_key_bindings);
+    vim::bind_mappings(cx);
 
     cx.set_menus(app_menus());
     // On Windows, this is set in the `update_jump_list` method of the `HistoryManager`.
@@ -4461,6 +4471,7 @@ mod tests {
                 "assistant",
                 "assistant2",
                 "auto_update",
+                "bookmarks",
                 "branches",
                 "buffer_search",
                 "channel_modal",
@@ -4481,15 +4492,18 @@ mod tests {
                 "feedback",
                 "file_finder",
                 "git",
+                "git_log_panel",
                 "git_onboarding",
                 "git_panel",
                 "go_to_line",
+                "hierarchy_panel",
                 "icon_theme_selector",
                 "journal",
                 "keymap_editor",
                 "keystroke_input",
                 "language_selector",
                 "line_ending",
+                "local_history",
                 "lsp_tool",
                 "markdown",
                 "menu",
@@ -4661,6 +4675,7 @@ mod tests {
             git_ui::init(cx);
             project_panel::init(cx);
             outline_panel::init(cx);
+            hierarchy_panel::init(cx);
             terminal_view::init(cx);
             copilot::copilot_chat::init(
                 app_state.fs.clone(),
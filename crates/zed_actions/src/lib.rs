@@ -182,7 +182,11 @@ pub mod git {
             #[action(deprecated_aliases = ["branches::OpenRecent"])]
             Branch,
             /// Opens the git stash selector.
-            ViewStash
+            ViewStash,
+            /// Opens the git tag selector.
+            ViewTags,
+            /// Opens the git worktree selector.
+            ViewWorktrees
         ]
     );
 }